@@ -17,7 +17,16 @@
 //! {conn_hash}:metadata:{schema}.{table}   -> TableMetadata
 //! {conn_hash}:fks:{schema}.{table}        -> [ForeignKeyInfo, ...]
 //! {conn_hash}:stats:{schema}.{table}.{col}-> ColumnStats
+//! {conn_hash}:indexes:{schema}.{table}    -> [IndexInfo, ...]
+//! {conn_hash}:rowcount:{schema}.{table}   -> RowCount
+//! {conn_hash}:samples:{schema}.{table}    -> SampleRows
 //! ```
+//!
+//! Build state lives in its own `build_state` table, keyed by model content
+//! hash and target, and is not cleared with the key-value cache.
+//! Accepted and rejected relationship suggestions live in the
+//! `inference_decisions` table, keyed by connection hash and column pair,
+//! for the same reason: they are user input rather than derived metadata.
 
 use std::path::PathBuf;
 
@@ -59,6 +68,37 @@ pub struct SavedCredential {
     pub last_used_at: i64,
 }
 
+/// Last successful build of a target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildState {
+    /// Content hash of the model the target was built from.
+    pub model_hash: String,
+    pub target: String,
+    /// Unix timestamp (seconds) when the build finished.
+    pub last_run_at: i64,
+    pub duration_ms: i64,
+    /// Rows affected, when the database reported it.
+    pub rows: Option<i64>,
+    /// Hash of the target's own definition (see `Model::definition_hash`).
+    pub definition_hash: String,
+}
+
+/// A user's decision on a suggested relationship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferenceDecision {
+    pub from_table: String,
+    pub from_column: String,
+    pub to_table: String,
+    pub to_column: String,
+    pub accepted: bool,
+    /// Rule that produced the suggestion.
+    pub rule: String,
+    /// Signal kinds that contributed positively to the suggestion.
+    pub signals: Vec<String>,
+    /// Unix timestamp (seconds) when the decision was made.
+    pub decided_at: i64,
+}
+
 /// SQLite-based metadata cache.
 pub struct MetadataCache {
     conn: Connection,
@@ -121,6 +161,29 @@ impl MetadataCache {
                 created_at INTEGER NOT NULL,
                 last_used_at INTEGER NOT NULL
             );
+
+            CREATE TABLE IF NOT EXISTS build_state (
+                model_hash TEXT NOT NULL,
+                target TEXT NOT NULL,
+                last_run_at INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                rows INTEGER,
+                definition_hash TEXT NOT NULL,
+                PRIMARY KEY (model_hash, target)
+            );
+
+            CREATE TABLE IF NOT EXISTS inference_decisions (
+                conn_hash TEXT NOT NULL,
+                from_table TEXT NOT NULL,
+                from_column TEXT NOT NULL,
+                to_table TEXT NOT NULL,
+                to_column TEXT NOT NULL,
+                accepted INTEGER NOT NULL,
+                rule TEXT NOT NULL,
+                signals TEXT NOT NULL,
+                decided_at INTEGER NOT NULL,
+                PRIMARY KEY (conn_hash, from_table, from_column, to_table, to_column)
+            );
             ",
         )?;
 
@@ -355,6 +418,161 @@ impl MetadataCache {
             .execute("DELETE FROM credentials WHERE id = ?", params![id])?;
         Ok(rows > 0)
     }
+
+    // ===== Build State Methods =====
+
+    /// Record a successful build, replacing any earlier state for the same
+    /// model hash and target.
+    pub fn record_build_state(&self, state: &BuildState) -> CacheResult<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO build_state (model_hash, target, last_run_at, duration_ms, rows, definition_hash)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                state.model_hash,
+                state.target,
+                state.last_run_at,
+                state.duration_ms,
+                state.rows,
+                state.definition_hash
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the build state of a target under a specific model hash.
+    pub fn get_build_state(
+        &self,
+        model_hash: &str,
+        target: &str,
+    ) -> CacheResult<Option<BuildState>> {
+        let state = self
+            .conn
+            .query_row(
+                "SELECT model_hash, target, last_run_at, duration_ms, rows, definition_hash
+                 FROM build_state WHERE model_hash = ? AND target = ?",
+                params![model_hash, target],
+                build_state_from_row,
+            )
+            .optional()?;
+        Ok(state)
+    }
+
+    /// Get the most recent build state of a target under any model hash.
+    ///
+    /// Editing one definition changes the model hash for every target, so
+    /// callers compare `definition_hash` to decide whether this still applies.
+    pub fn latest_build_state(&self, target: &str) -> CacheResult<Option<BuildState>> {
+        let state = self
+            .conn
+            .query_row(
+                "SELECT model_hash, target, last_run_at, duration_ms, rows, definition_hash
+                 FROM build_state WHERE target = ? ORDER BY last_run_at DESC LIMIT 1",
+                params![target],
+                build_state_from_row,
+            )
+            .optional()?;
+        Ok(state)
+    }
+
+    /// List build states recorded under a model hash.
+    pub fn build_states(&self, model_hash: &str) -> CacheResult<Vec<BuildState>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT model_hash, target, last_run_at, duration_ms, rows, definition_hash
+             FROM build_state WHERE model_hash = ? ORDER BY target",
+        )?;
+        let states = stmt
+            .query_map(params![model_hash], build_state_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(states)
+    }
+
+    /// Delete all recorded build state.
+    pub fn clear_build_state(&self) -> CacheResult<usize> {
+        let rows = self.conn.execute("DELETE FROM build_state", [])?;
+        Ok(rows)
+    }
+
+    // ===== Inference Decision Methods =====
+
+    /// Record a decision on a suggested relationship, replacing any earlier
+    /// decision on the same column pair for this connection.
+    pub fn record_inference_decision(
+        &self,
+        conn_hash: &str,
+        decision: &InferenceDecision,
+    ) -> CacheResult<()> {
+        let signals = serde_json::to_string(&decision.signals)?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO inference_decisions
+             (conn_hash, from_table, from_column, to_table, to_column, accepted, rule, signals, decided_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                conn_hash,
+                decision.from_table,
+                decision.from_column,
+                decision.to_table,
+                decision.to_column,
+                decision.accepted,
+                decision.rule,
+                signals,
+                decision.decided_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List the decisions recorded for a connection, oldest first.
+    pub fn inference_decisions(&self, conn_hash: &str) -> CacheResult<Vec<InferenceDecision>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT from_table, from_column, to_table, to_column, accepted, rule, signals, decided_at
+             FROM inference_decisions WHERE conn_hash = ?
+             ORDER BY decided_at, from_table, from_column",
+        )?;
+        let rows = stmt
+            .query_map(params![conn_hash], |row| {
+                Ok((
+                    InferenceDecision {
+                        from_table: row.get(0)?,
+                        from_column: row.get(1)?,
+                        to_table: row.get(2)?,
+                        to_column: row.get(3)?,
+                        accepted: row.get(4)?,
+                        rule: row.get(5)?,
+                        signals: Vec::new(),
+                        decided_at: row.get(7)?,
+                    },
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(mut decision, signals)| {
+                decision.signals = serde_json::from_str(&signals)?;
+                Ok(decision)
+            })
+            .collect()
+    }
+
+    /// Delete the decisions recorded for a connection.
+    pub fn clear_inference_decisions(&self, conn_hash: &str) -> CacheResult<usize> {
+        let rows = self.conn.execute(
+            "DELETE FROM inference_decisions WHERE conn_hash = ?",
+            params![conn_hash],
+        )?;
+        Ok(rows)
+    }
+}
+
+fn build_state_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<BuildState> {
+    Ok(BuildState {
+        model_hash: row.get(0)?,
+        target: row.get(1)?,
+        last_run_at: row.get(2)?,
+        duration_ms: row.get(3)?,
+        rows: row.get(4)?,
+        definition_hash: row.get(5)?,
+    })
 }
 
 /// Cache statistics.
@@ -406,6 +624,21 @@ impl CacheKey {
         format!("{}:stats:{}.{}.{}", conn_hash, schema, table, column)
     }
 
+    /// Key for table indexes.
+    pub fn indexes(conn_hash: &str, schema: &str, table: &str) -> String {
+        format!("{}:indexes:{}.{}", conn_hash, schema, table)
+    }
+
+    /// Key for a table's row count.
+    pub fn row_count(conn_hash: &str, schema: &str, table: &str) -> String {
+        format!("{}:rowcount:{}.{}", conn_hash, schema, table)
+    }
+
+    /// Key for rows sampled from a table.
+    pub fn sample_rows(conn_hash: &str, schema: &str, table: &str) -> String {
+        format!("{}:samples:{}.{}", conn_hash, schema, table)
+    }
+
     /// Key for database info.
     pub fn database_info(conn_hash: &str) -> String {
         format!("{}:dbinfo", conn_hash)
@@ -524,6 +757,15 @@ mod tests {
         let metadata = CacheKey::table_metadata(&conn, "main", "orders");
         assert!(metadata.contains(":metadata:main.orders"));
 
+        let indexes = CacheKey::indexes(&conn, "main", "orders");
+        assert!(indexes.contains(":indexes:main.orders"));
+
+        let row_count = CacheKey::row_count(&conn, "main", "orders");
+        assert!(row_count.contains(":rowcount:main.orders"));
+
+        let samples = CacheKey::sample_rows(&conn, "main", "orders");
+        assert!(samples.contains(":samples:main.orders"));
+
         let lineage = CacheKey::lineage("abc123hash");
         assert_eq!(lineage, "lineage:abc123hash");
     }
@@ -566,4 +808,85 @@ mod tests {
         assert!(deleted);
         assert!(cache.list_credentials().unwrap().is_empty());
     }
+
+    #[test]
+    fn test_build_state() {
+        let cache = MetadataCache::open_in_memory().unwrap();
+        let state = BuildState {
+            model_hash: "model1".into(),
+            target: "fact_orders".into(),
+            last_run_at: 1_000,
+            duration_ms: 250,
+            rows: Some(42),
+            definition_hash: "def1".into(),
+        };
+        cache.record_build_state(&state).unwrap();
+        cache
+            .record_build_state(&BuildState {
+                model_hash: "model2".into(),
+                last_run_at: 2_000,
+                rows: None,
+                ..state.clone()
+            })
+            .unwrap();
+
+        assert_eq!(
+            cache.get_build_state("model1", "fact_orders").unwrap(),
+            Some(state)
+        );
+        assert!(cache
+            .get_build_state("model1", "dim_customers")
+            .unwrap()
+            .is_none());
+
+        let latest = cache.latest_build_state("fact_orders").unwrap().unwrap();
+        assert_eq!(latest.model_hash, "model2");
+        assert_eq!(latest.rows, None);
+        assert_eq!(cache.build_states("model2").unwrap().len(), 1);
+
+        // Build state survives clearing the key-value cache
+        cache.clear_all().unwrap();
+        assert_eq!(cache.clear_build_state().unwrap(), 2);
+        assert!(cache.latest_build_state("fact_orders").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_inference_decisions() {
+        let cache = MetadataCache::open_in_memory().unwrap();
+        let decision = InferenceDecision {
+            from_table: "orders".into(),
+            from_column: "customer_id".into(),
+            to_table: "customers".into(),
+            to_column: "id".into(),
+            accepted: true,
+            rule: "suffix_id".into(),
+            signals: vec!["naming".into(), "type".into()],
+            decided_at: 1_000,
+        };
+        cache.record_inference_decision("conn1", &decision).unwrap();
+        // A later decision on the same pair replaces the earlier one
+        cache
+            .record_inference_decision(
+                "conn1",
+                &InferenceDecision {
+                    accepted: false,
+                    decided_at: 2_000,
+                    ..decision.clone()
+                },
+            )
+            .unwrap();
+        cache.record_inference_decision("conn2", &decision).unwrap();
+
+        let decisions = cache.inference_decisions("conn1").unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert!(!decisions[0].accepted);
+        assert_eq!(decisions[0].signals, vec!["naming", "type"]);
+
+        // Decisions survive clearing the key-value cache
+        cache.clear_all().unwrap();
+        cache.clear_connection("conn2").unwrap();
+        assert_eq!(cache.inference_decisions("conn2").unwrap(), vec![decision]);
+        assert_eq!(cache.clear_inference_decisions("conn1").unwrap(), 1);
+        assert!(cache.inference_decisions("conn1").unwrap().is_empty());
+    }
 }
@@ -56,6 +56,9 @@ pub use sql::ddl;
 pub use sql::dialect;
 pub use sql::dml;
 pub use sql::expr;
+pub use sql::format;
+pub use sql::params;
+pub use sql::parse;
 pub use sql::query;
 pub use sql::token;
 
@@ -64,7 +67,7 @@ pub mod web;
 
 /// Re-exports for convenient usage.
 pub mod prelude {
-    pub use crate::dialect::{Dialect, SqlDialect};
+    pub use crate::dialect::{Dialect, DialectTarget, SqlDialect, Version};
     pub use crate::expr::{
         // Constructors
         avg,
@@ -93,8 +96,8 @@ pub mod prelude {
         UnaryOperator,
     };
     pub use crate::query::{
-        Cte, Join, JoinType, LimitOffset, NullsOrder, OrderByExpr, Query, SelectExpr, SortDir,
-        TableRef,
+        Cte, Join, JoinType, LimitOffset, NullsOrder, OrderByExpr, Pivot, Query, SelectExpr,
+        SortDir, TableRef, Unpivot,
     };
     pub use crate::token::{Token, TokenStream};
 }
@@ -283,7 +283,7 @@ fn detect_string_kind_from_call(call: Node, source: &str) -> StringContext {
                     return match method_name.as_str() {
                         "from" => StringContext::TableReference,
                         "target" | "target_schema" => StringContext::TargetReference,
-                        "columns" | "primary_key" => StringContext::ColumnReference,
+                        "columns" | "primary_key" | "hierarchy" => StringContext::ColumnReference,
                         "source" => StringContext::EntityReference,
                         _ => StringContext::Other,
                     };
@@ -44,6 +44,23 @@ pub struct LocalEntity {
     pub columns: Vec<String>,
     /// The :from() table reference if present
     pub from_table: Option<String>,
+    /// Hierarchies declared with :hierarchy(name, {...})
+    pub hierarchies: Vec<LocalHierarchy>,
+}
+
+/// A dimension hierarchy extracted from the AST.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalHierarchy {
+    /// The hierarchy name
+    pub name: String,
+    /// Level columns, coarsest first
+    pub levels: Vec<String>,
+}
+
+impl std::fmt::Display for LocalHierarchy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.levels.join(" > "))
+    }
 }
 
 /// Extract all entities from source code.
@@ -94,8 +111,12 @@ fn try_extract_entity(call: tree_sitter::Node, source: &str) -> Option<LocalEnti
     // Get the range of the entire chain
     let range = node_to_range(call);
 
-    // Extract columns and from_table by walking the chain
-    let (columns, from_table) = extract_chain_info(call, source);
+    // Extract columns, from_table and hierarchies by walking the chain
+    let ChainInfo {
+        columns,
+        from_table,
+        hierarchies,
+    } = extract_chain_info(call, source);
 
     Some(LocalEntity {
         name,
@@ -103,6 +124,7 @@ fn try_extract_entity(call: tree_sitter::Node, source: &str) -> Option<LocalEnti
         range,
         columns,
         from_table,
+        hierarchies,
     })
 }
 
@@ -194,31 +216,51 @@ fn get_string_content(node: tree_sitter::Node, source: &str) -> String {
     text.trim_matches('"').trim_matches('\'').to_string()
 }
 
-/// Extract columns and from_table from the method chain.
-fn extract_chain_info(node: tree_sitter::Node, source: &str) -> (Vec<String>, Option<String>) {
-    let mut columns = Vec::new();
-    let mut from_table = None;
+/// Information collected from an entity's method chain.
+#[derive(Default)]
+struct ChainInfo {
+    columns: Vec<String>,
+    from_table: Option<String>,
+    hierarchies: Vec<LocalHierarchy>,
+}
 
-    // Walk the chain looking for :columns() and :from() calls
+/// Extract columns, from_table and hierarchies from the method chain.
+fn extract_chain_info(node: tree_sitter::Node, source: &str) -> ChainInfo {
+    let mut info = ChainInfo::default();
+
+    // Walk the chain looking for :columns(), :from() and :hierarchy() calls
     walk_chain(node, source, &mut |method_name, args_node| {
         match method_name {
             "from" => {
                 // Get the string argument
                 if let Some(table) = get_first_string_in_node(args_node, source) {
-                    from_table = Some(table);
+                    info.from_table = Some(table);
                 }
             }
             "columns" => {
                 // Get column names from the table constructor
                 if let Some(table_ctor) = find_table_constructor(args_node) {
-                    columns = extract_column_names(table_ctor, source);
+                    info.columns = extract_column_names(table_ctor, source);
+                }
+            }
+            "hierarchy" => {
+                if let (Some(name), Some(table_ctor)) = (
+                    get_first_string_in_node(args_node, source),
+                    find_table_constructor(args_node),
+                ) {
+                    info.hierarchies.push(LocalHierarchy {
+                        name,
+                        levels: extract_column_names(table_ctor, source),
+                    });
                 }
             }
             _ => {}
         }
     });
 
-    (columns, from_table)
+    // The chain is walked from the outermost call in
+    info.hierarchies.reverse();
+    info
 }
 
 /// Walk a method chain, calling the callback for each method.
@@ -304,6 +346,8 @@ fn find_table_constructor(node: tree_sitter::Node) -> Option<tree_sitter::Node>
 }
 
 /// Extract column names from a table constructor.
+///
+/// Handles both `{ name = type }` and `{ "name", ... }` lists.
 fn extract_column_names(table: tree_sitter::Node, source: &str) -> Vec<String> {
     let mut columns = Vec::new();
 
@@ -314,9 +358,12 @@ fn extract_column_names(table: tree_sitter::Node, source: &str) -> Vec<String> {
             if let Some(name_node) = child.child_by_field_name("name") {
                 columns.push(node_text(name_node, source));
             } else if let Some(first) = child.child(0) {
-                // For fields like `name = value`, first child is identifier
-                if first.kind() == "identifier" {
-                    columns.push(node_text(first, source));
+                // For fields like `name = value`, first child is identifier;
+                // positional fields hold the name as a string
+                match first.kind() {
+                    "identifier" => columns.push(node_text(first, source)),
+                    "string" => columns.push(get_string_content(first, source)),
+                    _ => {}
                 }
             }
         }
@@ -470,6 +517,39 @@ fact("sales"):source("orders"):measures({ count = count() })"#;
         assert_eq!(entities[0].from_table, Some("public.orders".to_string()));
     }
 
+    #[test]
+    fn test_extract_hierarchies() {
+        let source = r#"dimension("dim_dates")
+    :from("dates")
+    :columns({ "date_key", "year", "quarter", "month" })
+    :hierarchy("calendar", { "year", "quarter", "month" })
+    :hierarchy("fiscal", { "year" })"#;
+
+        let entities = extract_entities(source);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(
+            entities[0].columns,
+            vec!["date_key", "year", "quarter", "month"]
+        );
+        assert_eq!(
+            entities[0].hierarchies,
+            vec![
+                LocalHierarchy {
+                    name: "calendar".into(),
+                    levels: vec!["year".into(), "quarter".into(), "month".into()],
+                },
+                LocalHierarchy {
+                    name: "fiscal".into(),
+                    levels: vec!["year".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            entities[0].hierarchies[0].to_string(),
+            "calendar (year > quarter > month)"
+        );
+    }
+
     #[test]
     fn test_no_entities_in_empty_source() {
         let entities = extract_entities("");
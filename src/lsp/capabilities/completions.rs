@@ -221,6 +221,11 @@ fn complete_entity_references(project: &ProjectState) -> Vec<CompletionItem> {
         if !entity.columns.is_empty() {
             doc_parts.push(format!("Columns: {}", entity.columns.join(", ")));
         }
+        if !entity.hierarchies.is_empty() {
+            let hierarchies: Vec<String> =
+                entity.hierarchies.iter().map(|h| h.to_string()).collect();
+            doc_parts.push(format!("Hierarchies: {}", hierarchies.join(", ")));
+        }
 
         items.push(CompletionItem {
             label: name.clone(),
@@ -15,6 +15,7 @@ pub struct HoverInfo {
     pub name: String,
     pub from_table: Option<String>,
     pub columns: Vec<String>,
+    pub hierarchies: Vec<String>,
     pub description: Option<String>,
     pub defined_in: String,
 }
@@ -41,6 +42,7 @@ impl HoverInfo {
             name: entity.name.clone(),
             from_table: entity.from_table.clone(),
             columns: entity.columns.clone(),
+            hierarchies: entity.hierarchies.iter().map(|h| h.to_string()).collect(),
             description: None,
             defined_in,
         }
@@ -60,6 +62,10 @@ pub fn format_hover_markdown(info: &HoverInfo) -> String {
         lines.push(format!("**Columns:** {}", info.columns.join(", ")));
     }
 
+    if !info.hierarchies.is_empty() {
+        lines.push(format!("**Hierarchies:** {}", info.hierarchies.join(", ")));
+    }
+
     lines.push(format!("*Defined in {}*", info.defined_in));
 
     if let Some(ref desc) = info.description {
@@ -417,6 +423,7 @@ mod tests {
             name: "orders".to_string(),
             from_table: Some("raw.orders".to_string()),
             columns: vec!["id".to_string(), "amount".to_string()],
+            hierarchies: vec![],
             description: Some("Customer orders".to_string()),
             defined_in: "model.lua".to_string(),
         };
@@ -437,6 +444,7 @@ mod tests {
             name: "sales".to_string(),
             from_table: None,
             columns: vec![],
+            hierarchies: vec![],
             description: None,
             defined_in: "facts.lua".to_string(),
         };
@@ -450,7 +458,7 @@ mod tests {
 
     #[test]
     fn test_hover_info_from_entity() {
-        use crate::lsp::analysis::entities::{EntityKind, LocalEntity};
+        use crate::lsp::analysis::entities::{EntityKind, LocalEntity, LocalHierarchy};
         use tower_lsp::lsp_types::Range;
 
         let entity = LocalEntity {
@@ -459,6 +467,10 @@ mod tests {
             range: Range::default(),
             columns: vec!["id".to_string(), "name".to_string()],
             from_table: Some("raw.customers".to_string()),
+            hierarchies: vec![LocalHierarchy {
+                name: "geography".to_string(),
+                levels: vec!["country".to_string(), "city".to_string()],
+            }],
         };
 
         let uri = Url::parse("file:///project/dims.lua").unwrap();
@@ -468,5 +480,8 @@ mod tests {
         assert_eq!(info.name, "customers");
         assert_eq!(info.from_table, Some("raw.customers".to_string()));
         assert_eq!(info.defined_in, "dims.lua");
+        assert!(
+            format_hover_markdown(&info).contains("**Hierarchies:** geography (country > city)")
+        );
     }
 }
@@ -0,0 +1,840 @@
+//! Model bootstrapping from introspected metadata.
+//!
+//! Classifies tables as dimensions, facts, bridges or plain sources using
+//! naming conventions and relationship structure, then builds a typed
+//! [`Model`] with the generated fact and dimension definitions. Each
+//! generated definition carries the confidence of the classification that
+//! produced it.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mantis::metadata::{MetadataProviderExt, ModelBootstrapper};
+//! use mantis::model::emitter::EmitConfig;
+//!
+//! let tables = provider.get_tables("main").await?;
+//! let relationships = provider.infer_relationships(&tables, Default::default());
+//!
+//! let bootstrap = ModelBootstrapper::new().bootstrap(&tables, &relationships);
+//! std::fs::write("model.lua", bootstrap.to_lua(EmitConfig::default()))?;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::dimension::SCDType;
+use crate::model::emitter::{EmitConfig, LuaEmitter};
+use crate::model::types::{AggregationType, MaterializationStrategy};
+use crate::model::{
+    DimensionDefinition, FactDefinition, MeasureDefinition, Model, Relationship,
+    RelationshipSource as ModelRelationshipSource, SourceEntity,
+};
+use crate::semantic::inference::{InferredRelationship, RelationshipSource};
+
+use super::types::TableMetadata;
+
+/// Default schema for generated fact and dimension tables.
+const DEFAULT_TARGET_SCHEMA: &str = "analytics";
+
+/// Audit columns left out of generated dimensions.
+const SYSTEM_COLUMNS: &[&str] = &[
+    "created_at",
+    "updated_at",
+    "modified_at",
+    "deleted_at",
+    "created_by",
+    "updated_by",
+    "modified_by",
+    "deleted_by",
+    "row_version",
+    "etl_batch_id",
+    "dw_insert_date",
+    "dw_update_date",
+];
+
+/// Table name prefixes that carry a classification, with their confidence.
+const NAMING_PATTERNS: &[(&str, TableKind, f64)] = &[
+    ("dim_", TableKind::Dimension, 0.95),
+    ("dimension_", TableKind::Dimension, 0.95),
+    ("d_", TableKind::Dimension, 0.85),
+    ("fct_", TableKind::Fact, 0.95),
+    ("fact_", TableKind::Fact, 0.95),
+    ("f_", TableKind::Fact, 0.85),
+    ("bridge_", TableKind::Bridge, 0.90),
+    ("xref_", TableKind::Bridge, 0.90),
+    ("link_", TableKind::Bridge, 0.90),
+    ("stg_", TableKind::Source, 0.85),
+    ("staging_", TableKind::Source, 0.85),
+    ("raw_", TableKind::Source, 0.85),
+];
+
+/// What role a table plays in the generated model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableKind {
+    /// Plain source, no generated definition.
+    Source,
+    /// Descriptive table, generates a `DimensionDefinition`.
+    Dimension,
+    /// Transactional table with measures, generates a `FactDefinition`.
+    Fact,
+    /// Junction table for many-to-many relationships.
+    Bridge,
+}
+
+impl std::fmt::Display for TableKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Source => write!(f, "source"),
+            Self::Dimension => write!(f, "dimension"),
+            Self::Fact => write!(f, "fact"),
+            Self::Bridge => write!(f, "bridge"),
+        }
+    }
+}
+
+/// Classification of a single table.
+#[derive(Debug, Clone)]
+pub struct TableClassification {
+    /// Schema name.
+    pub schema: String,
+    /// Table name.
+    pub table: String,
+    /// Source entity name in the generated model.
+    pub entity: String,
+    /// Classified role.
+    pub kind: TableKind,
+    /// Confidence score (0.0 to 1.0).
+    pub confidence: f64,
+    /// Human-readable reasons for the classification.
+    pub reasons: Vec<String>,
+    /// Name of the generated fact or dimension, if any.
+    pub target: Option<String>,
+}
+
+/// Result of bootstrapping a model.
+#[derive(Debug, Clone)]
+pub struct Bootstrap {
+    /// The generated model.
+    pub model: Model,
+    /// Classification of every input table, in input order.
+    pub classifications: Vec<TableClassification>,
+}
+
+impl Bootstrap {
+    /// Classification of a table by name (case-insensitive).
+    pub fn classification(&self, table: &str) -> Option<&TableClassification> {
+        self.classifications
+            .iter()
+            .find(|c| c.table.eq_ignore_ascii_case(table))
+    }
+
+    /// Confidence of the classification that produced a fact or dimension.
+    pub fn confidence(&self, target: &str) -> Option<f64> {
+        self.classifications
+            .iter()
+            .find(|c| c.target.as_deref() == Some(target))
+            .map(|c| c.confidence)
+    }
+
+    /// Emit the generated model as Lua.
+    pub fn to_lua(&self, config: EmitConfig) -> String {
+        LuaEmitter::new(config).emit(&self.model)
+    }
+}
+
+/// Builds a starting `Model` from database metadata.
+#[derive(Debug, Clone)]
+pub struct ModelBootstrapper {
+    target_schema: String,
+    min_confidence: f64,
+}
+
+impl Default for ModelBootstrapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModelBootstrapper {
+    /// Create a bootstrapper targeting the `analytics` schema.
+    pub fn new() -> Self {
+        Self {
+            target_schema: DEFAULT_TARGET_SCHEMA.to_string(),
+            min_confidence: 0.5,
+        }
+    }
+
+    /// Schema that generated facts and dimensions materialize into.
+    pub fn with_target_schema(mut self, schema: impl Into<String>) -> Self {
+        self.target_schema = schema.into();
+        self
+    }
+
+    /// Minimum confidence for inferred relationships to be used.
+    pub fn with_min_confidence(mut self, confidence: f64) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+
+    /// Classify tables without building a model.
+    pub fn classify(
+        &self,
+        tables: &[TableMetadata],
+        relationships: &[InferredRelationship],
+    ) -> Vec<TableClassification> {
+        let relationships = self.usable_relationships(relationships);
+        let entities = entity_names(tables);
+
+        let mut outgoing: HashMap<String, usize> = HashMap::new();
+        let mut incoming: HashMap<String, usize> = HashMap::new();
+        for rel in &relationships {
+            *outgoing
+                .entry(table_key(&rel.from_schema, &rel.from_table))
+                .or_default() += 1;
+            *incoming
+                .entry(table_key(&rel.to_schema, &rel.to_table))
+                .or_default() += 1;
+        }
+
+        tables
+            .iter()
+            .map(|table| {
+                let key = table_key(&table.schema, &table.name);
+                let (kind, confidence, reasons) = classify_table(
+                    table,
+                    outgoing.get(&key).copied().unwrap_or(0),
+                    incoming.get(&key).copied().unwrap_or(0),
+                );
+                TableClassification {
+                    schema: table.schema.clone(),
+                    table: table.name.clone(),
+                    entity: entities[&key].clone(),
+                    kind,
+                    confidence,
+                    reasons,
+                    target: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Classify tables and generate a model with sources, relationships,
+    /// facts and dimensions.
+    pub fn bootstrap(
+        &self,
+        tables: &[TableMetadata],
+        relationships: &[InferredRelationship],
+    ) -> Bootstrap {
+        let mut classifications = self.classify(tables, relationships);
+        let relationships = self.usable_relationships(relationships);
+        let entities = entity_names(tables);
+
+        let mut model = Model::new();
+        for (table, classification) in tables.iter().zip(&classifications) {
+            let mut source = SourceEntity::from(table);
+            source.name = classification.entity.clone();
+            model.add_source(source);
+        }
+
+        for rel in &relationships {
+            let from = entities.get(&table_key(&rel.from_schema, &rel.from_table));
+            let to = entities.get(&table_key(&rel.to_schema, &rel.to_table));
+            if let (Some(from), Some(to)) = (from, to) {
+                model.add_relationship(to_model_relationship(rel, from, to));
+            }
+        }
+
+        let dimension_entities: HashSet<String> = classifications
+            .iter()
+            .filter(|c| c.kind == TableKind::Dimension)
+            .map(|c| c.entity.clone())
+            .collect();
+
+        let mut taken: HashSet<String> = model.sources.keys().cloned().collect();
+        let mut facts = Vec::new();
+        let mut dimensions = Vec::new();
+        for (table, classification) in tables.iter().zip(classifications.iter_mut()) {
+            let base = base_name(&table.name);
+            let name = match classification.kind {
+                TableKind::Dimension => {
+                    let name = unique_name("dim", base, &mut taken);
+                    dimensions.push(self.dimension(&name, &classification.entity, table));
+                    name
+                }
+                TableKind::Fact => {
+                    let name = unique_name("fact", base, &mut taken);
+                    let outgoing = model.relationships_from(&classification.entity);
+                    facts.push(self.fact(
+                        &name,
+                        &classification.entity,
+                        table,
+                        &outgoing,
+                        &dimension_entities,
+                    ));
+                    name
+                }
+                TableKind::Source | TableKind::Bridge => continue,
+            };
+            classification.target = Some(name);
+        }
+
+        for fact in facts {
+            model.add_fact(fact);
+        }
+        for dimension in dimensions {
+            model.add_dimension(dimension);
+        }
+
+        Bootstrap {
+            model,
+            classifications,
+        }
+    }
+
+    /// Relationships at or above the confidence threshold. Database
+    /// constraints and user-defined relationships are always kept.
+    fn usable_relationships<'a>(
+        &self,
+        relationships: &'a [InferredRelationship],
+    ) -> Vec<&'a InferredRelationship> {
+        relationships
+            .iter()
+            .filter(|rel| {
+                rel.source != RelationshipSource::Inferred || rel.confidence >= self.min_confidence
+            })
+            .collect()
+    }
+
+    fn dimension(&self, name: &str, entity: &str, table: &TableMetadata) -> DimensionDefinition {
+        let columns: Vec<&str> = table
+            .columns
+            .iter()
+            .filter(|c| !SYSTEM_COLUMNS.contains(&c.name.to_lowercase().as_str()))
+            .map(|c| c.name.as_str())
+            .collect();
+
+        let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        let scd_type = detect_scd_type(&names);
+
+        DimensionDefinition::new(name, name, entity)
+            .with_schema(&self.target_schema)
+            .with_columns(columns)
+            .with_primary_key(table.primary_key_columns())
+            .with_scd_type(scd_type)
+            .with_materialization(MaterializationStrategy::Table)
+    }
+
+    fn fact(
+        &self,
+        name: &str,
+        entity: &str,
+        table: &TableMetadata,
+        outgoing: &[&Relationship],
+        dimension_entities: &HashSet<String>,
+    ) -> FactDefinition {
+        let pk_columns = table.primary_key_columns();
+        let fk_columns: Vec<&str> = outgoing.iter().map(|r| r.from_column.as_str()).collect();
+
+        let mut fact = FactDefinition::new(name, name)
+            .with_schema(&self.target_schema)
+            .with_from(entity)
+            .with_materialization(MaterializationStrategy::Table);
+
+        // The primary key defines the grain; without one, the dimension
+        // keys do.
+        let grain = if pk_columns.is_empty() {
+            &fk_columns
+        } else {
+            &pk_columns
+        };
+        for column in grain {
+            fact = fact.with_grain(entity, *column);
+        }
+
+        let mut included = HashSet::new();
+        for rel in outgoing {
+            if dimension_entities.contains(&rel.to_entity)
+                && included.insert(rel.to_entity.as_str())
+            {
+                fact = fact.include_all(&rel.to_entity);
+            }
+        }
+
+        for column in &table.columns {
+            let is_key = fk_columns
+                .iter()
+                .chain(&pk_columns)
+                .any(|k| k.eq_ignore_ascii_case(&column.name));
+            if is_key || !is_numeric_type(&column.data_type) || is_date_type(&column.data_type) {
+                continue;
+            }
+            let lower = column.name.to_lowercase();
+            let aggregation = infer_aggregation(&lower);
+            fact = fact.with_measure(MeasureDefinition::new(
+                measure_name(&lower, &aggregation),
+                aggregation,
+                &column.name,
+            ));
+        }
+
+        fact
+    }
+}
+
+/// Classify a table by naming convention, falling back to its structure.
+fn classify_table(
+    table: &TableMetadata,
+    outgoing: usize,
+    incoming: usize,
+) -> (TableKind, f64, Vec<String>) {
+    let lower = table.name.to_lowercase();
+    if let Some((prefix, kind, confidence)) = NAMING_PATTERNS
+        .iter()
+        .find(|(prefix, _, _)| lower.starts_with(prefix) && lower.len() > prefix.len())
+    {
+        return (
+            *kind,
+            *confidence,
+            vec![format!("Name prefix '{}' indicates {} table", prefix, kind)],
+        );
+    }
+
+    let pk_columns = table.primary_key_columns();
+    let fk_columns: Vec<&str> = table
+        .foreign_keys
+        .iter()
+        .flat_map(|fk| fk.columns.iter().map(|s| s.as_str()))
+        .collect();
+    let numeric_columns = table
+        .columns
+        .iter()
+        .filter(|c| {
+            let is_key = fk_columns
+                .iter()
+                .chain(&pk_columns)
+                .any(|k| k.eq_ignore_ascii_case(&c.name));
+            !is_key && is_numeric_type(&c.data_type) && !is_date_type(&c.data_type)
+        })
+        .count();
+
+    if fk_columns.len() == 2 && table.columns.len() <= 4 {
+        let reason = format!(
+            "Has exactly 2 FK columns with {} total columns (bridge pattern)",
+            table.columns.len()
+        );
+        return (TableKind::Bridge, 0.80, vec![reason]);
+    }
+
+    if outgoing >= 2 && numeric_columns > 0 {
+        let mut reasons = vec![format!(
+            "Has {} FK relationships and {} numeric measure columns",
+            outgoing, numeric_columns
+        )];
+        let date_columns: Vec<&str> = table
+            .columns
+            .iter()
+            .filter(|c| is_date_type(&c.data_type))
+            .map(|c| c.name.as_str())
+            .collect();
+        if !date_columns.is_empty() {
+            reasons.push(format!("Has date columns: {}", date_columns.join(", ")));
+        }
+        return (TableKind::Fact, 0.75, reasons);
+    }
+
+    if incoming >= 1 && outgoing == 0 && numeric_columns == 0 {
+        let reason = format!(
+            "Referenced by {} tables with no outgoing FKs (dimension pattern)",
+            incoming
+        );
+        return (TableKind::Dimension, 0.70, vec![reason]);
+    }
+
+    if lower.ends_with('s') && !pk_columns.is_empty() && numeric_columns <= 1 {
+        let reason = "Plural name with primary key and mostly descriptive columns".to_string();
+        return (TableKind::Dimension, 0.60, vec![reason]);
+    }
+
+    let reason = "No clear pattern detected, defaulting to source".to_string();
+    (TableKind::Source, 0.5, vec![reason])
+}
+
+/// SCD2 when validity range columns (`valid_from`/`valid_to` or
+/// `effective_from`/`effective_to`) are present, SCD1 otherwise.
+pub fn detect_scd_type<S: AsRef<str>>(columns: &[S]) -> SCDType {
+    let find = |patterns: &[&str]| {
+        columns
+            .iter()
+            .map(AsRef::as_ref)
+            .find(|c| {
+                let lower = c.to_lowercase();
+                patterns.iter().any(|p| lower.contains(p))
+            })
+            .map(str::to_string)
+    };
+    match (
+        find(&["valid_from", "effective_from"]),
+        find(&["valid_to", "effective_to"]),
+    ) {
+        (Some(effective_from), Some(effective_to)) => SCDType::Type2 {
+            effective_from,
+            effective_to,
+            is_current: find(&["is_current"]),
+        },
+        _ => SCDType::Type1,
+    }
+}
+
+fn to_model_relationship(rel: &InferredRelationship, from: &str, to: &str) -> Relationship {
+    let mut relationship = match rel.source {
+        RelationshipSource::DatabaseConstraint => Relationship::from_foreign_key(
+            from,
+            to,
+            &rel.from_column,
+            &rel.to_column,
+            rel.cardinality,
+        ),
+        RelationshipSource::Inferred => Relationship::with_source(
+            from,
+            to,
+            &rel.from_column,
+            &rel.to_column,
+            rel.cardinality,
+            ModelRelationshipSource::inferred(&rel.rule, rel.confidence),
+        ),
+        RelationshipSource::UserDefined => {
+            Relationship::new(from, to, &rel.from_column, &rel.to_column, rel.cardinality)
+        }
+    };
+    relationship.additional_columns = rel.additional_columns.clone();
+    relationship
+}
+
+fn table_key(schema: &str, table: &str) -> String {
+    format!("{}.{}", schema, table).to_lowercase()
+}
+
+/// Source entity names keyed by `schema.table`. Tables that share a name across
+/// schemas get the schema as a prefix.
+fn entity_names(tables: &[TableMetadata]) -> HashMap<String, String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for table in tables {
+        *counts.entry(table.name.to_lowercase()).or_default() += 1;
+    }
+    tables
+        .iter()
+        .map(|table| {
+            let name = if counts[&table.name.to_lowercase()] > 1 && !table.schema.is_empty() {
+                format!("{}_{}", table.schema, table.name)
+            } else {
+                table.name.clone()
+            };
+            (table_key(&table.schema, &table.name), name)
+        })
+        .collect()
+}
+
+/// Table name without a classification prefix.
+fn base_name(table: &str) -> String {
+    let lower = table.to_lowercase();
+    NAMING_PATTERNS
+        .iter()
+        .find(|(prefix, _, _)| lower.starts_with(prefix) && lower.len() > prefix.len())
+        .map(|(prefix, _, _)| lower[prefix.len()..].to_string())
+        .unwrap_or(lower)
+}
+
+/// `{prefix}_{base}`, suffixed with a counter if the name is already taken.
+fn unique_name(prefix: &str, base: String, taken: &mut HashSet<String>) -> String {
+    let mut name = format!("{}_{}", prefix, base);
+    let mut n = 2;
+    while taken.contains(&name) {
+        name = format!("{}_{}_{}", prefix, base, n);
+        n += 1;
+    }
+    taken.insert(name.clone());
+    name
+}
+
+/// Check if a database type is numeric.
+fn is_numeric_type(db_type: &str) -> bool {
+    let lower = db_type.to_lowercase();
+    lower.contains("int")
+        || lower.contains("decimal")
+        || lower.contains("numeric")
+        || lower.contains("float")
+        || lower.contains("double")
+        || lower.contains("real")
+        || lower == "money"
+        || lower == "smallmoney"
+}
+
+/// Check if a database type is a date/time type.
+fn is_date_type(db_type: &str) -> bool {
+    let lower = db_type.to_lowercase();
+    lower.contains("date") || lower.contains("time")
+}
+
+/// Infer aggregation from a column name: rates and averages are averaged,
+/// everything else is summed.
+fn infer_aggregation(column: &str) -> AggregationType {
+    let averaged = ["_rate", "_pct", "_percent", "_avg", "_average"];
+    if averaged.iter().any(|suffix| column.ends_with(suffix)) {
+        AggregationType::Avg
+    } else {
+        AggregationType::Sum
+    }
+}
+
+/// Measure name derived from the column and its aggregation.
+fn measure_name(column: &str, aggregation: &AggregationType) -> String {
+    let clean = column
+        .trim_start_matches('_')
+        .trim_end_matches("_amount")
+        .trim_end_matches("_total")
+        .trim_end_matches("_qty")
+        .trim_end_matches("_sum");
+
+    match aggregation {
+        AggregationType::Sum
+            if ["revenue", "sales", "qty", "quantity"]
+                .iter()
+                .any(|w| column.contains(w)) =>
+        {
+            format!("total_{}", clean)
+        }
+        AggregationType::Sum => format!("{}_total", clean),
+        AggregationType::Avg => format!("avg_{}", clean),
+        AggregationType::Count | AggregationType::CountDistinct => format!("{}_count", clean),
+        AggregationType::Min => format!("min_{}", clean),
+        AggregationType::Max => format!("max_{}", clean),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{ColumnInfo, ForeignKeyInfo, PrimaryKeyInfo, TableType};
+    use crate::model::loader::load_model_from_str;
+    use crate::model::Cardinality;
+
+    fn column(name: &str, data_type: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            position: 0,
+            data_type: data_type.to_string(),
+            is_nullable: false,
+            max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            default_value: None,
+            is_identity: false,
+            is_computed: false,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<ColumnInfo>, pk: &[&str]) -> TableMetadata {
+        TableMetadata {
+            schema: "main".to_string(),
+            name: name.to_string(),
+            table_type: TableType::Table,
+            columns,
+            primary_key: Some(PrimaryKeyInfo {
+                name: format!("pk_{}", name),
+                columns: pk.iter().map(|c| c.to_string()).collect(),
+            }),
+            foreign_keys: vec![],
+            unique_constraints: vec![],
+        }
+    }
+
+    fn fk(from: &str, column: &str, to: &str, to_column: &str) -> InferredRelationship {
+        InferredRelationship {
+            from_schema: "main".to_string(),
+            from_table: from.to_string(),
+            from_column: column.to_string(),
+            to_schema: "main".to_string(),
+            to_table: to.to_string(),
+            to_column: to_column.to_string(),
+            additional_columns: Vec::new(),
+            confidence: 1.0,
+            rule: "fk_constraint".to_string(),
+            cardinality: Cardinality::ManyToOne,
+            signal_breakdown: None,
+            source: RelationshipSource::DatabaseConstraint,
+        }
+    }
+
+    fn sample() -> (Vec<TableMetadata>, Vec<InferredRelationship>) {
+        let customers = table(
+            "customers",
+            vec![
+                column("customer_id", "INTEGER"),
+                column("name", "VARCHAR"),
+                column("updated_at", "TIMESTAMP"),
+            ],
+            &["customer_id"],
+        );
+        let products = table(
+            "products",
+            vec![column("product_id", "INTEGER"), column("title", "VARCHAR")],
+            &["product_id"],
+        );
+        let mut orders = table(
+            "orders",
+            vec![
+                column("order_id", "INTEGER"),
+                column("customer_id", "INTEGER"),
+                column("product_id", "INTEGER"),
+                column("order_date", "DATE"),
+                column("sales_amount", "DECIMAL(18,2)"),
+                column("discount_rate", "DOUBLE"),
+            ],
+            &["order_id"],
+        );
+        orders.foreign_keys = vec![
+            ForeignKeyInfo {
+                name: "fk_customer".to_string(),
+                columns: vec!["customer_id".to_string()],
+                referenced_schema: "main".to_string(),
+                referenced_table: "customers".to_string(),
+                referenced_columns: vec!["customer_id".to_string()],
+                on_delete: None,
+                on_update: None,
+            },
+            ForeignKeyInfo {
+                name: "fk_product".to_string(),
+                columns: vec!["product_id".to_string()],
+                referenced_schema: "main".to_string(),
+                referenced_table: "products".to_string(),
+                referenced_columns: vec!["product_id".to_string()],
+                on_delete: None,
+                on_update: None,
+            },
+        ];
+        let relationships = vec![
+            fk("orders", "customer_id", "customers", "customer_id"),
+            fk("orders", "product_id", "products", "product_id"),
+        ];
+        (vec![customers, products, orders], relationships)
+    }
+
+    #[test]
+    fn test_classify_by_naming_and_structure() {
+        let (mut tables, relationships) = sample();
+        tables.push(table("stg_events", vec![column("id", "INTEGER")], &["id"]));
+
+        let classifications = ModelBootstrapper::new().classify(&tables, &relationships);
+        let kinds: Vec<(&str, TableKind)> = classifications
+            .iter()
+            .map(|c| (c.table.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("customers", TableKind::Dimension),
+                ("products", TableKind::Dimension),
+                ("orders", TableKind::Fact),
+                ("stg_events", TableKind::Source),
+            ]
+        );
+        assert_eq!(classifications[2].confidence, 0.75);
+        assert_eq!(classifications[3].confidence, 0.85);
+    }
+
+    #[test]
+    fn test_bootstrap_model() {
+        let (tables, relationships) = sample();
+        let bootstrap = ModelBootstrapper::new().bootstrap(&tables, &relationships);
+        let model = &bootstrap.model;
+
+        assert_eq!(model.sources.len(), 3);
+        assert_eq!(model.relationships.len(), 2);
+        assert!(model
+            .relationships
+            .iter()
+            .all(|r| r.source.is_foreign_key()));
+
+        let fact = model.get_fact("fact_orders").unwrap();
+        assert_eq!(fact.qualified_target_name(), "analytics.fact_orders");
+        assert_eq!(fact.from.as_deref(), Some("orders"));
+        assert_eq!(fact.grain.len(), 1);
+        assert_eq!(fact.grain[0].source_column, "order_id");
+        assert!(fact.includes.contains_key("customers"));
+        assert!(fact.includes.contains_key("products"));
+
+        let sales = &fact.measures["total_sales"];
+        assert_eq!(sales.aggregation, AggregationType::Sum);
+        assert_eq!(sales.source_column, "sales_amount");
+        let discount = &fact.measures["avg_discount_rate"];
+        assert_eq!(discount.aggregation, AggregationType::Avg);
+        assert_eq!(fact.measures.len(), 2);
+
+        let dim = model.get_dimension("dim_customers").unwrap();
+        assert_eq!(dim.source_entity, "customers");
+        assert_eq!(dim.primary_key, vec!["customer_id"]);
+        assert_eq!(dim.columns.len(), 2, "audit columns are excluded");
+
+        assert_eq!(bootstrap.confidence("fact_orders"), Some(0.75));
+        assert_eq!(bootstrap.confidence("dim_customers"), Some(0.70));
+        assert_eq!(
+            bootstrap
+                .classification("orders")
+                .unwrap()
+                .target
+                .as_deref(),
+            Some("fact_orders")
+        );
+        model.validate().unwrap();
+    }
+
+    #[test]
+    fn test_bootstrap_avoids_source_name_collisions() {
+        let tables = vec![table(
+            "dim_region",
+            vec![column("region_id", "INTEGER"), column("name", "VARCHAR")],
+            &["region_id"],
+        )];
+
+        let bootstrap = ModelBootstrapper::new().bootstrap(&tables, &[]);
+        assert!(bootstrap.model.get_source("dim_region").is_some());
+        let dim = bootstrap.model.get_dimension("dim_region_2").unwrap();
+        assert_eq!(dim.source_entity, "dim_region");
+        assert_eq!(bootstrap.confidence("dim_region_2"), Some(0.95));
+    }
+
+    #[test]
+    fn test_low_confidence_relationships_are_ignored() {
+        let (tables, mut relationships) = sample();
+        for rel in &mut relationships {
+            rel.source = RelationshipSource::Inferred;
+            rel.confidence = 0.3;
+        }
+
+        let bootstrap = ModelBootstrapper::new().bootstrap(&tables, &relationships);
+        assert!(bootstrap.model.relationships.is_empty());
+
+        let bootstrap = ModelBootstrapper::new()
+            .with_min_confidence(0.2)
+            .bootstrap(&tables, &relationships);
+        assert_eq!(bootstrap.model.relationships.len(), 2);
+        assert!(bootstrap.model.relationships[0].source.is_inferred());
+    }
+
+    #[test]
+    fn test_bootstrap_lua_round_trip() {
+        let (tables, relationships) = sample();
+        let bootstrap = ModelBootstrapper::new().bootstrap(&tables, &relationships);
+
+        let lua = bootstrap.to_lua(EmitConfig::minimal());
+        let loaded = load_model_from_str(&lua, "bootstrap.lua").unwrap();
+
+        let fact = loaded.get_fact("fact_orders").unwrap();
+        assert_eq!(fact.measures.len(), 2);
+        assert_eq!(fact.includes.len(), 2);
+        assert_eq!(fact.qualified_target_name(), "analytics.fact_orders");
+        let dim = loaded.get_dimension("dim_products").unwrap();
+        assert_eq!(dim.source_entity, "products");
+        assert_eq!(dim.primary_key, vec!["product_id"]);
+        assert_eq!(loaded.relationships.len(), 2);
+    }
+}
@@ -0,0 +1,901 @@
+//! Schema migration planning for materialized targets.
+//!
+//! Compares each materialized fact and dimension's expected columns
+//! ([`Model::target_columns`]) with the live table fetched through a
+//! [`MetadataProvider`], and plans the `ALTER TABLE` statements that bring the
+//! table up to date — or a rebuild when altering in place would lose data or
+//! the dialect cannot express the change.
+//!
+//! A rename looks exactly like a drop plus an add, so renames are only planned
+//! for pairs registered with [`MigrationPlanner::with_rename`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! let planner = MigrationPlanner::new(&model, Dialect::Postgres)
+//!     .with_rename("fact_orders", "amount", "total");
+//! let plan = planner.plan(&provider).await?;
+//!
+//! // Dry run
+//! println!("{}", plan.report());
+//!
+//! if !plan.is_destructive() {
+//!     for sql in plan.statements() {
+//!         // execute
+//!     }
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::provider::{MetadataProvider, MetadataResult};
+use super::types::TableMetadata;
+use crate::dialect::{Dialect, DialectTarget, SqlDialect};
+use crate::model::{DataType, MaterializationStrategy, Model, SCDType, TargetColumn};
+use crate::sql::ddl::{AlterTable, ColumnDef, DropTable};
+use crate::sql::expr::Expr;
+
+/// How much existing data a change can lose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Safety {
+    /// No data is lost
+    Safe,
+    /// Existing values may be truncated or fail to convert
+    Lossy,
+    /// Data is removed and cannot be rebuilt from the sources
+    Destructive,
+}
+
+impl fmt::Display for Safety {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Safety::Safe => write!(f, "safe"),
+            Safety::Lossy => write!(f, "lossy"),
+            Safety::Destructive => write!(f, "destructive"),
+        }
+    }
+}
+
+/// A difference between a target's expected and live columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnChange {
+    /// Column is in the model but not the table
+    Added {
+        column: String,
+        data_type: Option<DataType>,
+    },
+    /// Column is in the table but not the model
+    Dropped { column: String },
+    /// Column was renamed (from a [`MigrationPlanner::with_rename`] hint)
+    Renamed { from: String, to: String },
+    /// Column type differs between the table and the model
+    TypeChanged {
+        column: String,
+        from: DataType,
+        to: DataType,
+        /// Whether the live column accepts NULLs
+        nullable: bool,
+        /// Live default, as reported by the server
+        default: Option<String>,
+    },
+}
+
+impl ColumnChange {
+    /// Data loss risk of applying this change in place.
+    pub fn safety(&self) -> Safety {
+        match self {
+            ColumnChange::Added { .. } | ColumnChange::Renamed { .. } => Safety::Safe,
+            ColumnChange::Dropped { .. } => Safety::Destructive,
+            ColumnChange::TypeChanged { from, to, .. } => {
+                if is_widening(from, to) {
+                    Safety::Safe
+                } else {
+                    Safety::Lossy
+                }
+            }
+        }
+    }
+
+    fn describe(&self, dialect: DialectTarget) -> String {
+        match self {
+            ColumnChange::Added { column, data_type } => match data_type {
+                Some(dt) => format!("add column {} {}", column, dialect.emit_data_type(dt)),
+                None => format!("add column {} (unknown type)", column),
+            },
+            ColumnChange::Dropped { column } => format!("drop column {}", column),
+            ColumnChange::Renamed { from, to } => format!("rename column {} to {}", from, to),
+            ColumnChange::TypeChanged {
+                column, from, to, ..
+            } => format!(
+                "change column {} from {} to {}",
+                column,
+                dialect.emit_data_type(from),
+                dialect.emit_data_type(to)
+            ),
+        }
+    }
+}
+
+/// How a target table is brought up to date.
+#[derive(Debug, Clone)]
+pub enum MigrationAction {
+    /// The table does not exist yet; the next build creates it.
+    Create,
+    /// Apply these statements in order.
+    Alter(Vec<AlterTable>),
+    /// Drop the table so the next build recreates it.
+    Rebuild { reason: String },
+}
+
+/// Planned migration of one target table.
+#[derive(Debug, Clone)]
+pub struct TableMigration {
+    /// Fact or dimension name
+    pub target: String,
+    pub schema: String,
+    pub table: String,
+    /// Column differences, in the order they are applied
+    pub changes: Vec<ColumnChange>,
+    pub action: MigrationAction,
+    /// Whether the table holds history that a rebuild cannot restore
+    /// (incremental, snapshot and SCD Type 2/6 targets)
+    pub keeps_history: bool,
+}
+
+impl TableMigration {
+    /// Worst-case data loss of this migration.
+    ///
+    /// Rebuilding a table that keeps history is always destructive.
+    pub fn safety(&self) -> Safety {
+        let changes = self
+            .changes
+            .iter()
+            .map(ColumnChange::safety)
+            .max()
+            .unwrap_or(Safety::Safe);
+        match self.action {
+            MigrationAction::Rebuild { .. } if self.keeps_history => Safety::Destructive,
+            _ => changes,
+        }
+    }
+
+    /// SQL statements for this table.
+    ///
+    /// A rebuild drops the table; creating it is left to the next build.
+    pub fn statements(&self, dialect: impl Into<DialectTarget>) -> Vec<String> {
+        let dialect = dialect.into();
+        match &self.action {
+            MigrationAction::Create => Vec::new(),
+            MigrationAction::Alter(alters) => alters.iter().map(|a| a.to_sql(dialect)).collect(),
+            MigrationAction::Rebuild { .. } => vec![DropTable::new(&self.table)
+                .schema(&self.schema)
+                .if_exists()
+                .to_sql(dialect)],
+        }
+    }
+}
+
+/// Migrations for every out-of-date target of a model.
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    pub dialect: DialectTarget,
+    /// Tables that need a change, sorted by target name
+    pub tables: Vec<TableMigration>,
+}
+
+impl MigrationPlan {
+    /// Whether every target is up to date.
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Worst-case data loss across all tables.
+    pub fn safety(&self) -> Safety {
+        self.tables
+            .iter()
+            .map(TableMigration::safety)
+            .max()
+            .unwrap_or(Safety::Safe)
+    }
+
+    /// Whether applying the plan removes data.
+    pub fn is_destructive(&self) -> bool {
+        self.safety() == Safety::Destructive
+    }
+
+    /// Tables whose migration removes data.
+    pub fn destructive(&self) -> impl Iterator<Item = &TableMigration> {
+        self.tables
+            .iter()
+            .filter(|t| t.safety() == Safety::Destructive)
+    }
+
+    /// All SQL statements, in order.
+    pub fn statements(&self) -> Vec<String> {
+        self.tables
+            .iter()
+            .flat_map(|t| t.statements(self.dialect))
+            .collect()
+    }
+
+    /// Human-readable dry-run report of the plan.
+    pub fn report(&self) -> String {
+        if self.tables.is_empty() {
+            return "All targets are up to date.\n".to_string();
+        }
+
+        let mut out = String::new();
+        for table in &self.tables {
+            let action = match &table.action {
+                MigrationAction::Create => "create".to_string(),
+                MigrationAction::Alter(_) => "alter".to_string(),
+                MigrationAction::Rebuild { reason } => format!("rebuild ({})", reason),
+            };
+            out.push_str(&format!(
+                "{} ({}.{}): {} [{}]\n",
+                table.target,
+                table.schema,
+                table.table,
+                action,
+                table.safety()
+            ));
+            for change in &table.changes {
+                out.push_str(&format!(
+                    "  - {} [{}]\n",
+                    change.describe(self.dialect),
+                    change.safety()
+                ));
+            }
+            for sql in table.statements(self.dialect) {
+                out.push_str(&format!("    {}\n", sql));
+            }
+        }
+        out
+    }
+}
+
+/// Plans schema migrations for a model's materialized targets.
+pub struct MigrationPlanner<'a> {
+    model: &'a Model,
+    dialect: DialectTarget,
+    default_schema: String,
+    /// Target name → (from, to) column renames
+    renames: HashMap<String, Vec<(String, String)>>,
+}
+
+/// A materialized target resolved to its physical table.
+struct Target {
+    schema: String,
+    table: String,
+    keeps_history: bool,
+}
+
+impl<'a> MigrationPlanner<'a> {
+    /// Create a planner for `model` on `dialect`.
+    ///
+    /// Unqualified target tables are looked up in the dialect's default schema.
+    pub fn new(model: &'a Model, dialect: impl Into<DialectTarget>) -> Self {
+        let dialect = dialect.into();
+        Self {
+            model,
+            dialect,
+            default_schema: default_schema(dialect.dialect).to_string(),
+            renames: HashMap::new(),
+        }
+    }
+
+    /// Schema for target tables without one.
+    pub fn with_default_schema(mut self, schema: impl Into<String>) -> Self {
+        self.default_schema = schema.into();
+        self
+    }
+
+    /// Treat column `from` of `target` as renamed to `to`.
+    pub fn with_rename(
+        mut self,
+        target: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.renames
+            .entry(target.into())
+            .or_default()
+            .push((from.into(), to.into()));
+        self
+    }
+
+    /// Diff every materialized target against the live database.
+    pub async fn plan<P: MetadataProvider + ?Sized>(
+        &self,
+        provider: &P,
+    ) -> MetadataResult<MigrationPlan> {
+        let mut names: Vec<&str> = self
+            .model
+            .facts
+            .keys()
+            .chain(self.model.dimensions.keys())
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+
+        let mut existing: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut tables = Vec::new();
+        for name in names {
+            let Some(target) = self.resolve(name) else {
+                continue;
+            };
+            if !existing.contains_key(&target.schema) {
+                let listed = provider
+                    .list_tables(&target.schema)
+                    .await?
+                    .into_iter()
+                    .map(|t| t.name.to_lowercase())
+                    .collect();
+                existing.insert(target.schema.clone(), listed);
+            }
+            let live = if existing[&target.schema].contains(&target.table.to_lowercase()) {
+                Some(provider.get_table(&target.schema, &target.table).await?)
+            } else {
+                None
+            };
+            if let Some(migration) = self.plan_table(name, live.as_ref()) {
+                tables.push(migration);
+            }
+        }
+
+        Ok(MigrationPlan {
+            dialect: self.dialect,
+            tables,
+        })
+    }
+
+    /// Diff one target against its live table (`None` if it does not exist).
+    ///
+    /// Returns `None` when the table is up to date, or when `target` is not a
+    /// materialized table.
+    pub fn plan_table(&self, target: &str, live: Option<&TableMetadata>) -> Option<TableMigration> {
+        let resolved = self.resolve(target)?;
+        let expected = self.model.target_columns(target)?;
+
+        let mut migration = TableMigration {
+            target: target.to_string(),
+            schema: resolved.schema,
+            table: resolved.table,
+            changes: Vec::new(),
+            action: MigrationAction::Create,
+            keeps_history: resolved.keeps_history,
+        };
+        let Some(live) = live else {
+            return Some(migration);
+        };
+
+        migration.changes = self.diff(target, &expected, live);
+        if migration.changes.is_empty() {
+            return None;
+        }
+        migration.action = match self.rebuild_reason(&migration.changes) {
+            Some(reason) => MigrationAction::Rebuild { reason },
+            None => MigrationAction::Alter(
+                migration
+                    .changes
+                    .iter()
+                    .map(|change| self.alter(&migration.schema, &migration.table, change))
+                    .collect(),
+            ),
+        };
+        Some(migration)
+    }
+
+    fn resolve(&self, target: &str) -> Option<Target> {
+        let (materialized, strategy, qualified, scd_history) = match self.model.facts.get(target) {
+            Some(fact) => (
+                fact.materialized,
+                &fact.materialization,
+                fact.qualified_target_name(),
+                false,
+            ),
+            None => {
+                let dim = self.model.dimensions.get(target)?;
+                let scd_history =
+                    matches!(dim.scd_type, SCDType::Type2 { .. } | SCDType::Type6 { .. });
+                (
+                    dim.materialized,
+                    &dim.materialization,
+                    dim.qualified_target_name(),
+                    scd_history,
+                )
+            }
+        };
+        if !materialized || matches!(strategy, MaterializationStrategy::View) {
+            return None;
+        }
+
+        let keeps_history = scd_history
+            || matches!(
+                strategy,
+                MaterializationStrategy::Incremental { .. }
+                    | MaterializationStrategy::Snapshot { .. }
+            );
+        let (schema, table) = match qualified.rsplit_once('.') {
+            Some((schema, table)) => (schema.to_string(), table.to_string()),
+            None => (self.default_schema.clone(), qualified),
+        };
+        Some(Target {
+            schema,
+            table,
+            keeps_history,
+        })
+    }
+
+    /// Changes in apply order: renames, type changes, additions, then drops.
+    fn diff(
+        &self,
+        target: &str,
+        expected: &[TargetColumn],
+        live: &TableMetadata,
+    ) -> Vec<ColumnChange> {
+        let live_column = |name: &str| {
+            live.columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(name))
+        };
+        let expects = |name: &str| expected.iter().any(|c| c.name.eq_ignore_ascii_case(name));
+
+        let renames: Vec<(String, String)> = self
+            .renames
+            .get(target)
+            .into_iter()
+            .flatten()
+            .filter(|(from, to)| {
+                live_column(from).is_some() && live_column(to).is_none() && expects(to)
+            })
+            .cloned()
+            .collect();
+
+        let mut changes: Vec<ColumnChange> = renames
+            .iter()
+            .map(|(from, to)| ColumnChange::Renamed {
+                from: from.clone(),
+                to: to.clone(),
+            })
+            .collect();
+        let mut added = Vec::new();
+
+        for column in expected {
+            let renamed_from = renames
+                .iter()
+                .find(|(_, to)| to.eq_ignore_ascii_case(&column.name))
+                .map(|(from, _)| from.as_str());
+            let Some(actual) = live_column(renamed_from.unwrap_or(&column.name)) else {
+                added.push(ColumnChange::Added {
+                    column: column.name.clone(),
+                    data_type: column.data_type.clone(),
+                });
+                continue;
+            };
+            // Unknown types on either side are left alone
+            if let (Some(to), Some(from)) = (&column.data_type, actual.model_type()) {
+                if self.dialect.emit_data_type(&from) != self.dialect.emit_data_type(to) {
+                    changes.push(ColumnChange::TypeChanged {
+                        column: column.name.clone(),
+                        from,
+                        to: to.clone(),
+                        nullable: actual.is_nullable,
+                        default: actual.default_value.clone(),
+                    });
+                }
+            }
+        }
+        changes.extend(added);
+
+        for column in &live.columns {
+            let renamed = renames
+                .iter()
+                .any(|(from, _)| from.eq_ignore_ascii_case(&column.name));
+            if !renamed && !expects(&column.name) {
+                changes.push(ColumnChange::Dropped {
+                    column: column.name.clone(),
+                });
+            }
+        }
+        changes
+    }
+
+    /// Why the changes cannot be applied with `ALTER TABLE`, if they cannot.
+    fn rebuild_reason(&self, changes: &[ColumnChange]) -> Option<String> {
+        let dialect = self.dialect;
+        changes.iter().find_map(|change| match change {
+            ColumnChange::Added {
+                column,
+                data_type: None,
+            } => Some(format!("type of new column {} is unknown", column)),
+            ColumnChange::Dropped { .. }
+                if dialect == Dialect::Sqlite && !dialect.at_least(3, 35) =>
+            {
+                Some(format!("{} cannot drop columns", dialect))
+            }
+            ColumnChange::Renamed { .. }
+                if dialect == Dialect::Sqlite && !dialect.at_least(3, 25) =>
+            {
+                Some(format!(
+                    "{} cannot rename columns with ALTER TABLE",
+                    dialect
+                ))
+            }
+            ColumnChange::TypeChanged {
+                column, from, to, ..
+            } => {
+                if !is_widening(from, to) {
+                    Some(format!(
+                        "changing {} from {} to {} may lose data",
+                        column,
+                        dialect.emit_data_type(from),
+                        dialect.emit_data_type(to)
+                    ))
+                } else if !alters_type_in_place(dialect.dialect, from, to) {
+                    Some(format!(
+                        "{} cannot change the type of {} in place",
+                        dialect, column
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+
+    fn alter(&self, schema: &str, table: &str, change: &ColumnChange) -> AlterTable {
+        let alter = AlterTable::new(table).schema(schema);
+        match change {
+            ColumnChange::Added { column, data_type } => {
+                // Added columns stay nullable so existing rows remain valid
+                let data_type = data_type.clone().unwrap_or(DataType::String);
+                alter.add_column(ColumnDef::new(column, data_type))
+            }
+            ColumnChange::Dropped { column } => alter.drop_column(column),
+            ColumnChange::Renamed { from, to } => alter.rename_column(from, to),
+            ColumnChange::TypeChanged {
+                column,
+                to,
+                nullable,
+                default,
+                ..
+            } => {
+                // Restate nullability and default, which MySQL and T-SQL reset
+                let mut def = ColumnDef::new(column, to.clone());
+                if !nullable {
+                    def = def.not_null();
+                }
+                if let Some(default) = default {
+                    def = def.default(Expr::Raw(default.clone()));
+                }
+                alter.alter_column(def)
+            }
+        }
+    }
+}
+
+/// Whether every `from` value fits in `to` unchanged.
+fn is_widening(from: &DataType, to: &DataType) -> bool {
+    let int_width = |dt: &DataType| match dt {
+        DataType::Int8 => Some(1),
+        DataType::Int16 => Some(2),
+        DataType::Int32 => Some(4),
+        DataType::Int64 => Some(8),
+        _ => None,
+    };
+    match (from, to) {
+        (DataType::Float32, DataType::Float64) => true,
+        (DataType::Char(a) | DataType::Varchar(a), DataType::Varchar(b)) => b >= a,
+        (DataType::Char(_) | DataType::Varchar(_), DataType::String) => true,
+        (DataType::Decimal(p1, s1), DataType::Decimal(p2, s2)) => {
+            s2 >= s1 && i16::from(*p2) - i16::from(*s2) >= i16::from(*p1) - i16::from(*s1)
+        }
+        _ => matches!((int_width(from), int_width(to)), (Some(a), Some(b)) if b > a),
+    }
+}
+
+/// Whether `dialect` can widen `from` to `to` with `ALTER TABLE`.
+fn alters_type_in_place(dialect: Dialect, from: &DataType, to: &DataType) -> bool {
+    match dialect {
+        Dialect::Sqlite | Dialect::Databricks => false,
+        // Redshift only widens VARCHAR in place
+        Dialect::Redshift => matches!((from, to), (DataType::Varchar(_), DataType::Varchar(_))),
+        _ => true,
+    }
+}
+
+/// Schema that unqualified tables live in.
+fn default_schema(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::Postgres | Dialect::Redshift => "public",
+        Dialect::TSql => "dbo",
+        Dialect::Snowflake => "PUBLIC",
+        Dialect::DuckDb | Dialect::Sqlite => "main",
+        _ => "default",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::Version;
+    use crate::metadata::{ColumnInfo, TableType};
+    use crate::model::{FactDefinition, SourceEntity};
+
+    fn orders_model(strategy: MaterializationStrategy) -> Model {
+        let fact = FactDefinition::new("fact_orders", "analytics.fact_orders")
+            .with_grain("orders", "order_id")
+            .with_simple_column("note")
+            .with_sum("revenue", "total")
+            .with_materialization(strategy);
+
+        Model::new()
+            .with_source(
+                SourceEntity::new("orders", "raw.orders")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("note", DataType::Varchar(200))
+                    .with_required_column("total", DataType::Decimal(12, 2)),
+            )
+            .with_fact(fact)
+    }
+
+    fn column(name: &str, data_type: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            position: 0,
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            default_value: None,
+            is_identity: false,
+            is_computed: false,
+        }
+    }
+
+    fn table(columns: Vec<ColumnInfo>) -> TableMetadata {
+        TableMetadata {
+            schema: "analytics".to_string(),
+            name: "fact_orders".to_string(),
+            table_type: TableType::Table,
+            columns,
+            primary_key: None,
+            foreign_keys: vec![],
+            unique_constraints: vec![],
+        }
+    }
+
+    fn varchar(name: &str, length: i32) -> ColumnInfo {
+        ColumnInfo {
+            max_length: Some(length),
+            ..column(name, "varchar")
+        }
+    }
+
+    fn decimal(name: &str, precision: i32, scale: i32) -> ColumnInfo {
+        ColumnInfo {
+            numeric_precision: Some(precision),
+            numeric_scale: Some(scale),
+            ..column(name, "decimal")
+        }
+    }
+
+    #[test]
+    fn test_up_to_date_and_missing() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let live = table(vec![
+            column("order_id", "bigint"),
+            varchar("note", 200),
+            decimal("total", 12, 2),
+        ]);
+
+        assert!(planner.plan_table("fact_orders", Some(&live)).is_none());
+        let create = planner.plan_table("fact_orders", None).unwrap();
+        assert!(matches!(create.action, MigrationAction::Create));
+        assert!(create.statements(Dialect::Postgres).is_empty());
+        assert!(planner.plan_table("missing", None).is_none());
+
+        let view = orders_model(MaterializationStrategy::View);
+        let planner = MigrationPlanner::new(&view, Dialect::Postgres);
+        assert!(planner.plan_table("fact_orders", None).is_none());
+    }
+
+    #[test]
+    fn test_add_and_drop_columns() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let live = table(vec![
+            column("order_id", "bigint"),
+            decimal("total", 12, 2),
+            column("legacy_flag", "boolean"),
+        ]);
+
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert_eq!(
+            migration.changes,
+            vec![
+                ColumnChange::Added {
+                    column: "note".into(),
+                    data_type: Some(DataType::Varchar(200)),
+                },
+                ColumnChange::Dropped {
+                    column: "legacy_flag".into(),
+                },
+            ]
+        );
+        assert_eq!(migration.safety(), Safety::Destructive);
+        assert_eq!(
+            migration.statements(Dialect::Postgres),
+            vec![
+                "ALTER TABLE \"analytics\".\"fact_orders\" ADD COLUMN \"note\" VARCHAR(200)",
+                "ALTER TABLE \"analytics\".\"fact_orders\" DROP COLUMN \"legacy_flag\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_changes() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let widen = table(vec![
+            column("order_id", "integer"),
+            varchar("note", 100),
+            decimal("total", 12, 2),
+        ]);
+
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let migration = planner.plan_table("fact_orders", Some(&widen)).unwrap();
+        assert_eq!(migration.safety(), Safety::Safe);
+        assert_eq!(
+            migration.statements(Dialect::Postgres),
+            vec![
+                "ALTER TABLE \"analytics\".\"fact_orders\" ALTER COLUMN \"order_id\" TYPE BIGINT",
+                "ALTER TABLE \"analytics\".\"fact_orders\" ALTER COLUMN \"note\" TYPE VARCHAR(200)",
+            ]
+        );
+
+        // MySQL and T-SQL restate what their ALTER resets
+        let keyed = table(vec![
+            ColumnInfo {
+                is_nullable: false,
+                default_value: Some("0".into()),
+                ..column("order_id", "integer")
+            },
+            varchar("note", 200),
+            decimal("total", 12, 2),
+        ]);
+        let planner = MigrationPlanner::new(&model, Dialect::MySql);
+        let migration = planner.plan_table("fact_orders", Some(&keyed)).unwrap();
+        assert_eq!(
+            migration.statements(Dialect::MySql),
+            vec!["ALTER TABLE `analytics`.`fact_orders` MODIFY COLUMN `order_id` BIGINT NOT NULL DEFAULT 0"]
+        );
+        let planner = MigrationPlanner::new(&model, Dialect::TSql);
+        let migration = planner.plan_table("fact_orders", Some(&keyed)).unwrap();
+        assert_eq!(
+            migration.statements(Dialect::TSql),
+            vec!["ALTER TABLE [analytics].[fact_orders] ALTER COLUMN [order_id] BIGINT NOT NULL"]
+        );
+
+        // Redshift widens VARCHAR only
+        let planner = MigrationPlanner::new(&model, Dialect::Redshift);
+        let migration = planner.plan_table("fact_orders", Some(&widen)).unwrap();
+        assert!(matches!(migration.action, MigrationAction::Rebuild { .. }));
+
+        let narrow = table(vec![
+            column("order_id", "bigint"),
+            varchar("note", 200),
+            decimal("total", 18, 4),
+        ]);
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let migration = planner.plan_table("fact_orders", Some(&narrow)).unwrap();
+        assert_eq!(migration.safety(), Safety::Lossy);
+        assert_eq!(
+            migration.statements(Dialect::Postgres),
+            vec!["DROP TABLE IF EXISTS \"analytics\".\"fact_orders\""]
+        );
+    }
+
+    #[test]
+    fn test_rebuild_of_history_is_destructive() {
+        let model = orders_model(MaterializationStrategy::Incremental {
+            unique_key: vec!["order_id".into()],
+            incremental_key: "order_id".into(),
+            lookback: None,
+        });
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let live = table(vec![
+            column("order_id", "bigint"),
+            varchar("note", 200),
+            decimal("total", 18, 4),
+        ]);
+
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert!(migration.keeps_history);
+        assert_eq!(migration.safety(), Safety::Destructive);
+    }
+
+    #[test]
+    fn test_rename_hint() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let live = table(vec![
+            column("order_id", "bigint"),
+            varchar("comment", 200),
+            decimal("total", 12, 2),
+        ]);
+
+        let planner = MigrationPlanner::new(&model, Dialect::Postgres);
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert_eq!(migration.safety(), Safety::Destructive);
+
+        let planner = planner.with_rename("fact_orders", "comment", "note");
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert_eq!(
+            migration.changes,
+            vec![ColumnChange::Renamed {
+                from: "comment".into(),
+                to: "note".into(),
+            }]
+        );
+        assert_eq!(migration.safety(), Safety::Safe);
+        assert_eq!(
+            migration.statements(Dialect::Postgres),
+            vec!["ALTER TABLE \"analytics\".\"fact_orders\" RENAME COLUMN \"comment\" TO \"note\""]
+        );
+
+        let planner = MigrationPlanner::new(&model, Dialect::TSql).with_rename(
+            "fact_orders",
+            "comment",
+            "note",
+        );
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert_eq!(
+            migration.statements(Dialect::TSql),
+            vec!["EXEC sp_rename '[analytics].[fact_orders].[comment]', 'note', 'COLUMN'"]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_versions() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let live = table(vec![
+            column("order_id", "bigint"),
+            varchar("note", 200),
+            decimal("total", 12, 2),
+            column("legacy_flag", "boolean"),
+        ]);
+
+        let planner = MigrationPlanner::new(&model, Dialect::Sqlite.at(Version::new(3, 30)));
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert!(matches!(migration.action, MigrationAction::Rebuild { .. }));
+
+        let planner = MigrationPlanner::new(&model, Dialect::Sqlite);
+        let migration = planner.plan_table("fact_orders", Some(&live)).unwrap();
+        assert!(matches!(migration.action, MigrationAction::Alter(_)));
+    }
+
+    #[test]
+    fn test_report() {
+        let model = orders_model(MaterializationStrategy::Table);
+        let live = table(vec![column("order_id", "bigint"), decimal("total", 12, 2)]);
+        let plan = MigrationPlan {
+            dialect: Dialect::Postgres.into(),
+            tables: MigrationPlanner::new(&model, Dialect::Postgres)
+                .plan_table("fact_orders", Some(&live))
+                .into_iter()
+                .collect(),
+        };
+
+        assert_eq!(
+            plan.report(),
+            "fact_orders (analytics.fact_orders): alter [safe]\n\
+             \x20 - add column note VARCHAR(200) [safe]\n\
+             \x20   ALTER TABLE \"analytics\".\"fact_orders\" ADD COLUMN \"note\" VARCHAR(200)\n"
+        );
+        assert!(!plan.is_destructive());
+    }
+}
@@ -45,10 +45,22 @@
 //! let inferred = provider.infer_relationships(&[table], Default::default());
 //! ```
 
+mod bootstrap;
+mod migration;
 mod provider;
+mod quality;
 mod types;
 mod worker_provider;
 
+pub use bootstrap::{
+    detect_scd_type, Bootstrap, ModelBootstrapper, TableClassification, TableKind,
+};
+pub use migration::{
+    ColumnChange, MigrationAction, MigrationPlan, MigrationPlanner, Safety, TableMigration,
+};
 pub use provider::{MetadataProvider, MetadataProviderExt};
+pub use quality::{
+    DataTest, DataTestGenerator, DataTestKind, DataTestReport, DataTestResult, DataTestRunner,
+};
 pub use types::*;
 pub use worker_provider::WorkerMetadataProvider;
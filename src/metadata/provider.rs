@@ -7,6 +7,7 @@
 use async_trait::async_trait;
 
 use super::types::*;
+use crate::dialect::DialectTarget;
 use crate::semantic::inference::{
     InferenceConfig, InferenceEngine, InferredRelationship, TableInfo as InferenceTableInfo,
 };
@@ -84,13 +85,76 @@ pub trait MetadataProvider: Send + Sync {
         right_column: &str,
     ) -> MetadataResult<ValueOverlap>;
 
+    /// Get the indexes on a table.
+    ///
+    /// Default implementation reports none, so providers without index
+    /// introspection simply get no index signals.
+    async fn get_indexes(&self, _schema: &str, _table: &str) -> MetadataResult<Vec<IndexInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// Get the number of rows in a table, estimated unless `exact` is set.
+    ///
+    /// Returns `None` when the provider can't count rows (the default).
+    async fn get_row_count(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _exact: bool,
+    ) -> MetadataResult<Option<RowCount>> {
+        Ok(None)
+    }
+
+    /// Sample up to `limit` rows from a table (worker default: 10).
+    ///
+    /// Returns `None` when the provider can't sample rows (the default).
+    async fn sample_rows(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _limit: Option<i32>,
+    ) -> MetadataResult<Option<SampleRows>> {
+        Ok(None)
+    }
+
     /// Get database information.
     async fn get_database_info(&self) -> MetadataResult<DatabaseInfo>;
 
+    /// Get the dialect and server version to generate SQL for.
+    ///
+    /// Returns `None` when the database product has no supported dialect.
+    async fn get_dialect_target(&self) -> MetadataResult<Option<DialectTarget>> {
+        Ok(self.get_database_info().await?.dialect_target())
+    }
+
     // =========================================================================
     // Batch operations (default implementations using parallel fetches)
     // =========================================================================
 
+    /// Fetch the indexes, estimated row count and sampled rows of a table.
+    ///
+    /// Default implementation fetches all three in parallel.
+    async fn get_table_profile(
+        &self,
+        schema: &str,
+        table: &str,
+        sample_limit: Option<i32>,
+    ) -> MetadataResult<TableProfile> {
+        let (indexes, row_count, sample) = futures::try_join!(
+            self.get_indexes(schema, table),
+            self.get_row_count(schema, table, false),
+            self.sample_rows(schema, table, sample_limit),
+        )?;
+
+        Ok(TableProfile {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            indexes,
+            row_count,
+            sample,
+        })
+    }
+
     /// Batch fetch multiple tables.
     ///
     /// Default implementation fetches tables in parallel using `join_all`.
@@ -151,6 +215,20 @@ pub trait MetadataProvider: Send + Sync {
         &self,
         tables: &[TableMetadata],
         config: InferenceConfig,
+    ) -> Vec<InferredRelationship> {
+        self.infer_relationships_with_profiles(tables, &[], config)
+    }
+
+    /// Infer relationships, also scoring indexes, row counts and sampled
+    /// values from the given table profiles.
+    ///
+    /// This does NOT call the worker - fetch profiles first with
+    /// `get_table_profile`.
+    fn infer_relationships_with_profiles(
+        &self,
+        tables: &[TableMetadata],
+        profiles: &[TableProfile],
+        config: InferenceConfig,
     ) -> Vec<InferredRelationship> {
         let table_infos: Vec<InferenceTableInfo> = tables
             .iter()
@@ -165,6 +243,9 @@ pub trait MetadataProvider: Send + Sync {
         // Load database constraints for high-confidence detection
         engine.load_constraints(tables);
 
+        // Load indexes, row counts and samples for profile signals
+        engine.load_profiles(profiles);
+
         engine.infer_all_relationships(&table_infos)
     }
 
@@ -0,0 +1,876 @@
+//! Model-driven data quality tests.
+//!
+//! The model already states what the data should look like: source primary
+//! keys, required columns, accepted values, change-tracking timestamps,
+//! relationship cardinalities and dimension hierarchies. [`DataTestGenerator`] turns each of those
+//! claims into a query for the rows that break it, and [`DataTestRunner`]
+//! executes the queries through the worker's `query.execute` and collects a
+//! pass/fail report with sample failing rows.
+//!
+//! Tests read sources through their filter, so soft-deleted or test rows the
+//! model never sees do not fail a check. Primary keys enforced by the source's
+//! dedup configuration are not tested.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+//! let runner = DataTestRunner::new(client, "postgres", conn_str);
+//! let report = runner.run(&tests).await;
+//! println!("{}", report.summary());
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::dialect::{Dialect, DialectTarget};
+use crate::expr::{
+    col, count_distinct, count_star, func, lit_int, lit_str, max, raw_sql, star, table_col,
+    table_star, Expr, ExprExt,
+};
+use crate::model::{Cardinality, DimensionDefinition, Model, Relationship, SourceEntity};
+use crate::query::{Cte, Query, SelectExpr, TableRef};
+use crate::semantic::planner::emit::{convert_literal, convert_model_expr};
+use crate::worker::protocol::ExecuteQueryResponse;
+use crate::worker::WorkerClient;
+
+/// What a data test checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataTestKind {
+    /// Primary key values are unique
+    Unique,
+    /// A required column holds no NULLs
+    NotNull,
+    /// Every non-NULL foreign key has a matching row
+    Relationship,
+    /// The "one" side of a many-to-one relationship is unique
+    Cardinality,
+    /// A column holds only its accepted values
+    AcceptedValues,
+    /// The change-tracking timestamp is recent
+    Freshness,
+    /// Each hierarchy level value rolls up to a single parent value
+    Hierarchy,
+}
+
+impl std::fmt::Display for DataTestKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DataTestKind::Unique => "unique",
+            DataTestKind::NotNull => "not_null",
+            DataTestKind::Relationship => "relationship",
+            DataTestKind::Cardinality => "cardinality",
+            DataTestKind::AcceptedValues => "accepted_values",
+            DataTestKind::Freshness => "freshness",
+            DataTestKind::Hierarchy => "hierarchy",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A data test: a query for the rows that violate one model claim.
+#[derive(Debug, Clone)]
+pub struct DataTest {
+    /// Unique test name, e.g. `not_null_orders_customer_id`
+    pub name: String,
+    pub kind: DataTestKind,
+    /// Source entity the failing rows come from
+    pub entity: String,
+    /// Columns under test
+    pub columns: Vec<String>,
+    dialect: DialectTarget,
+    ctes: Vec<Cte>,
+    failing: Query,
+}
+
+impl DataTest {
+    /// Query returning a single `failures` count; zero means the test passes.
+    pub fn failures_sql(&self) -> String {
+        self.wrap(Query::new().select(vec![count_star().alias("failures")]))
+            .to_sql(self.dialect)
+    }
+
+    /// Query returning up to `limit` failing rows.
+    pub fn samples_sql(&self, limit: u64) -> String {
+        self.wrap(Query::new().select_star().limit(limit))
+            .to_sql(self.dialect)
+    }
+
+    fn wrap(&self, outer: Query) -> Query {
+        let outer = self
+            .ctes
+            .iter()
+            .fold(outer, |query, cte| query.with_cte(cte.clone()));
+        outer
+            .with_cte(Cte::new("failing", self.failing.clone()))
+            .from(TableRef::new("failing"))
+    }
+}
+
+/// Generates data tests from a model's sources and relationships.
+pub struct DataTestGenerator<'a> {
+    model: &'a Model,
+    dialect: DialectTarget,
+    max_age: Duration,
+}
+
+impl<'a> DataTestGenerator<'a> {
+    /// Create a generator for `model` on `dialect`.
+    pub fn new(model: &'a Model, dialect: impl Into<DialectTarget>) -> Self {
+        Self {
+            model,
+            dialect: dialect.into(),
+            max_age: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    /// Oldest acceptable change-tracking timestamp (default: 24 hours).
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// All tests, per source in name order, then per relationship, then per
+    /// dimension in name order.
+    pub fn generate(&self) -> Vec<DataTest> {
+        let mut sources: Vec<&SourceEntity> = self.model.sources.values().collect();
+        sources.sort_by_key(|s| s.name.as_str());
+        let mut dimensions: Vec<&DimensionDefinition> = self.model.dimensions.values().collect();
+        dimensions.sort_by_key(|d| d.name.as_str());
+
+        let mut tests = Vec::new();
+        for source in sources {
+            tests.extend(self.source_tests(source));
+        }
+        for relationship in &self.model.relationships {
+            tests.extend(self.relationship_tests(relationship));
+        }
+        for dimension in dimensions {
+            tests.extend(self.hierarchy_tests(dimension));
+        }
+        tests
+    }
+
+    /// Uniqueness, not-null, accepted-value and freshness tests for one source.
+    pub fn source_tests(&self, source: &SourceEntity) -> Vec<DataTest> {
+        let mut tests = Vec::new();
+        let name = source.name.as_str();
+
+        let deduped = source.dedup.as_ref().is_some_and(|d| {
+            source
+                .primary_key
+                .iter()
+                .all(|k| d.partition_by.contains(k))
+        });
+        if !source.primary_key.is_empty() && !deduped {
+            let keys: Vec<Expr> = source.primary_key.iter().map(|k| col(k)).collect();
+            let mut test = self.test(DataTestKind::Unique, name, source.primary_key.clone());
+            test.failing = duplicates(self.scan(&mut test, source, name), keys);
+            tests.push(test);
+        }
+
+        let mut columns: Vec<_> = source.columns.values().collect();
+        columns.sort_by_key(|c| c.name.as_str());
+
+        for column in columns.iter().filter(|c| !c.nullable) {
+            let mut test = self.test(DataTestKind::NotNull, name, vec![column.name.clone()]);
+            test.failing = Query::new()
+                .select(vec![star()])
+                .from(self.scan(&mut test, source, name))
+                .filter(col(&column.name).is_null());
+            tests.push(test);
+        }
+
+        for column in columns.iter().filter(|c| !c.accepted_values.is_empty()) {
+            let values = column.accepted_values.iter().map(convert_literal).collect();
+            let mut test = self.test(
+                DataTestKind::AcceptedValues,
+                name,
+                vec![column.name.clone()],
+            );
+            test.failing = Query::new()
+                .select(vec![star()])
+                .from(self.scan(&mut test, source, name))
+                .filter(
+                    col(&column.name)
+                        .is_not_null()
+                        .and(col(&column.name).not_in_list(values)),
+                );
+            tests.push(test);
+        }
+
+        if let Some(timestamp) = source.change_tracking.as_ref().and_then(timestamp_column) {
+            let mut test = self.test(DataTestKind::Freshness, name, vec![timestamp.to_string()]);
+            let latest = Query::new()
+                .select(vec![max(col(timestamp)).alias("latest")])
+                .from(self.scan(&mut test, source, name));
+            test.ctes.push(Cte::new("latest", latest));
+            test.failing = Query::new()
+                .select(vec![col("latest")])
+                .from(TableRef::new("latest"))
+                .filter(
+                    col("latest")
+                        .is_null()
+                        .or(col("latest").lt(self.oldest_allowed())),
+                );
+            tests.push(test);
+        }
+
+        tests
+    }
+
+    /// Referential integrity and cardinality tests for one relationship.
+    ///
+    /// Only relationships between source entities are tested; many-to-many
+    /// relationships have no side that must match, and band relationships
+    /// have no key to match on.
+    pub fn relationship_tests(&self, relationship: &Relationship) -> Vec<DataTest> {
+        if relationship.range.is_some() {
+            return Vec::new();
+        }
+
+        // Orient as child (many side) -> parent (one side), pairing every key column
+        let pairs = relationship.column_pairs();
+        let (child, parent, keys): (_, _, Vec<(&str, &str)>) = match relationship.cardinality {
+            Cardinality::ManyToOne | Cardinality::OneToOne => {
+                (&relationship.from_entity, &relationship.to_entity, pairs)
+            }
+            Cardinality::OneToMany => (
+                &relationship.to_entity,
+                &relationship.from_entity,
+                pairs.into_iter().map(|(from, to)| (to, from)).collect(),
+            ),
+            Cardinality::ManyToMany | Cardinality::Unknown => return Vec::new(),
+        };
+        let (Some(child_source), Some(parent_source)) = (
+            self.model.sources.get(child),
+            self.model.sources.get(parent),
+        ) else {
+            return Vec::new();
+        };
+        if keys.is_empty() {
+            return Vec::new();
+        }
+        let child_cols: Vec<String> = keys.iter().map(|(c, _)| c.to_string()).collect();
+        let parent_cols: Vec<String> = keys.iter().map(|(_, p)| p.to_string()).collect();
+        let all_not_null = |entity: Option<&str>, columns: &[String]| {
+            columns
+                .iter()
+                .map(|c| match entity {
+                    Some(entity) => table_col(entity, c).is_not_null(),
+                    None => col(c).is_not_null(),
+                })
+                .reduce(|a, b| a.and(b))
+                .expect("relationship has key columns")
+        };
+
+        let mut tests = Vec::new();
+
+        // A child key with a NULL column references nothing, as in a foreign key
+        let mut test = self.test(DataTestKind::Relationship, child, child_cols.clone());
+        test.name = format!("relationship_{}_{}_{}", child, child_cols.join("_"), parent);
+        let from = self.scan(&mut test, child_source, "child");
+        let parent_ref = self.scan(&mut test, parent_source, "parent");
+        let on = keys
+            .iter()
+            .map(|(c, p)| table_col("child", c).eq(table_col("parent", p)))
+            .reduce(|a, b| a.and(b))
+            .expect("relationship has key columns");
+        test.failing = Query::new()
+            .select(vec![table_star("child")])
+            .from(from)
+            .left_join(parent_ref, on)
+            .filter(
+                all_not_null(Some("child"), &child_cols)
+                    .and(table_col("parent", &parent_cols[0]).is_null()),
+            );
+        tests.push(test);
+
+        if relationship.cardinality != Cardinality::OneToOne {
+            let mut test = self.test(DataTestKind::Cardinality, parent, parent_cols.clone());
+            test.name = format!("cardinality_{}_{}_{}", child, parent, parent_cols.join("_"));
+            let from = self.scan(&mut test, parent_source, parent);
+            test.failing = duplicates(from, parent_cols.iter().map(|c| col(c)).collect())
+                .filter(all_not_null(None, &parent_cols));
+            tests.push(test);
+        }
+
+        tests
+    }
+
+    /// Functional dependency tests for a dimension's hierarchies.
+    ///
+    /// Every pair of adjacent levels gets a test for child values that roll
+    /// up to more than one parent, read from the dimension's source. Only
+    /// dimensions built from a source entity are tested.
+    pub fn hierarchy_tests(&self, dimension: &DimensionDefinition) -> Vec<DataTest> {
+        let Some(source) = self.model.sources.get(&dimension.source_entity) else {
+            return Vec::new();
+        };
+
+        let mut tests = Vec::new();
+        for hierarchy in &dimension.hierarchies {
+            for (child, parent) in hierarchy.dependencies() {
+                let (Some(child_col), Some(parent_col)) = (
+                    dimension.source_column(child),
+                    dimension.source_column(parent),
+                ) else {
+                    continue;
+                };
+
+                let mut test = self.test(
+                    DataTestKind::Hierarchy,
+                    &source.name,
+                    vec![child_col.to_string(), parent_col.to_string()],
+                );
+                test.name = format!("hierarchy_{}_{}_{}", dimension.name, child, parent);
+                let from = self.scan(&mut test, source, &source.name);
+                let parents = count_distinct(col(parent_col));
+                test.failing = Query::new()
+                    .select(vec![
+                        SelectExpr::new(col(child_col)),
+                        parents.clone().alias("parent_count"),
+                    ])
+                    .from(from)
+                    .group_by(vec![col(child_col)])
+                    .having(parents.gt(lit_int(1)));
+                tests.push(test);
+            }
+        }
+        tests
+    }
+
+    fn test(&self, kind: DataTestKind, entity: &str, columns: Vec<String>) -> DataTest {
+        let name = match columns.as_slice() {
+            [] => format!("{}_{}", kind, entity),
+            _ => format!("{}_{}_{}", kind, entity, columns.join("_")),
+        };
+        DataTest {
+            name,
+            kind,
+            entity: entity.to_string(),
+            columns,
+            dialect: self.dialect,
+            ctes: Vec::new(),
+            failing: Query::new(),
+        }
+    }
+
+    /// Reference to a source's rows as `alias`, through a filtering CTE if
+    /// the source has a filter.
+    fn scan(&self, test: &mut DataTest, source: &SourceEntity, alias: &str) -> TableRef {
+        let qualified = source.qualified_table_name();
+        let table = match qualified.rsplit_once('.') {
+            Some((schema, table)) => TableRef::new(table).with_schema(schema),
+            None => TableRef::new(&qualified),
+        };
+        let Some(filter) = &source.filter else {
+            return table.with_alias(alias);
+        };
+
+        let cte_name = format!("{}_rows", source.name);
+        if !test.ctes.iter().any(|c| c.name == cte_name) {
+            let rows = Query::new()
+                .select(vec![star()])
+                .from(table.with_alias(&source.name))
+                .filter(convert_model_expr(filter));
+            test.ctes.push(Cte::new(&cte_name, rows));
+        }
+        TableRef::new(&cte_name).with_alias(alias)
+    }
+
+    /// `now - max_age`, in the largest whole unit.
+    fn oldest_allowed(&self) -> Expr {
+        let secs = self.max_age.as_secs() as i64;
+        let (unit, amount) = if secs % 86_400 == 0 {
+            ("day", secs / 86_400)
+        } else if secs % 3_600 == 0 {
+            ("hour", secs / 3_600)
+        } else if secs % 60 == 0 {
+            ("minute", secs / 60)
+        } else {
+            ("second", secs)
+        };
+        let now = match self.dialect.dialect {
+            Dialect::ClickHouse => func("now", vec![]),
+            _ => raw_sql("CURRENT_TIMESTAMP"),
+        };
+        func("DATEADD", vec![lit_str(unit), lit_int(-amount), now])
+    }
+}
+
+/// Key values that occur more than once, with their row count.
+fn duplicates(from: TableRef, keys: Vec<Expr>) -> Query {
+    let mut select: Vec<SelectExpr> = keys.iter().cloned().map(SelectExpr::new).collect();
+    select.push(count_star().alias("row_count"));
+    Query::new()
+        .select(select)
+        .from(from)
+        .group_by(keys)
+        .having(count_star().gt(lit_int(1)))
+}
+
+fn timestamp_column(tracking: &crate::model::ChangeTracking) -> Option<&str> {
+    use crate::model::ChangeTracking;
+
+    match tracking {
+        ChangeTracking::AppendOnly { timestamp_column }
+        | ChangeTracking::CDC {
+            timestamp_column, ..
+        } => Some(timestamp_column),
+        ChangeTracking::FullSnapshot => None,
+    }
+}
+
+/// Outcome of one data test.
+#[derive(Debug, Clone)]
+pub struct DataTestResult {
+    pub name: String,
+    pub kind: DataTestKind,
+    pub entity: String,
+    /// Number of failing rows (or duplicated keys)
+    pub failures: u64,
+    /// Column names of the sample rows
+    pub sample_columns: Vec<String>,
+    /// Up to the runner's sample limit of failing rows
+    pub sample_rows: Vec<Vec<serde_json::Value>>,
+    /// Set when a query could not be run
+    pub error: Option<String>,
+}
+
+impl DataTestResult {
+    /// Build a result from the failure-count response.
+    pub fn from_count(test: &DataTest, response: &ExecuteQueryResponse) -> Self {
+        let count = response.rows.first().and_then(|row| row.first());
+        let failures = match count {
+            Some(serde_json::Value::Number(n)) => {
+                n.as_u64().or_else(|| n.as_f64().map(|f| f as u64))
+            }
+            Some(serde_json::Value::String(s)) => s.trim().parse().ok(),
+            _ => None,
+        };
+        let mut result = Self::new(test);
+        match failures {
+            Some(failures) => result.failures = failures,
+            None => result.error = Some("failure count query returned no count".to_string()),
+        }
+        result
+    }
+
+    fn new(test: &DataTest) -> Self {
+        Self {
+            name: test.name.clone(),
+            kind: test.kind,
+            entity: test.entity.clone(),
+            failures: 0,
+            sample_columns: Vec::new(),
+            sample_rows: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Whether the test ran and found no failing rows.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.failures == 0
+    }
+}
+
+/// Results of a data test run.
+#[derive(Debug, Clone, Default)]
+pub struct DataTestReport {
+    pub results: Vec<DataTestResult>,
+}
+
+impl DataTestReport {
+    /// Whether every test passed.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(DataTestResult::passed)
+    }
+
+    /// Tests that found failing rows or could not run.
+    pub fn failures(&self) -> impl Iterator<Item = &DataTestResult> {
+        self.results.iter().filter(|r| !r.passed())
+    }
+
+    /// Human-readable report with sample failing rows.
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        let (mut passed, mut failed, mut errored) = (0, 0, 0);
+        for result in &self.results {
+            if let Some(error) = &result.error {
+                errored += 1;
+                out.push_str(&format!("ERROR {}: {}\n", result.name, error));
+            } else if result.failures == 0 {
+                passed += 1;
+                out.push_str(&format!("PASS  {}\n", result.name));
+            } else {
+                failed += 1;
+                out.push_str(&format!(
+                    "FAIL  {} ({} failing)\n",
+                    result.name, result.failures
+                ));
+                if !result.sample_rows.is_empty() {
+                    out.push_str(&format!("      {}\n", result.sample_columns.join(", ")));
+                }
+                for row in &result.sample_rows {
+                    let values: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+                    out.push_str(&format!("      {}\n", values.join(", ")));
+                }
+            }
+        }
+        out.push_str(&format!(
+            "{} passed, {} failed, {} errored\n",
+            passed, failed, errored
+        ));
+        out
+    }
+}
+
+/// Runs data tests through the worker.
+pub struct DataTestRunner {
+    client: Arc<WorkerClient>,
+    driver: String,
+    connection_string: String,
+    sample_limit: u64,
+}
+
+impl DataTestRunner {
+    /// Create a runner for the given connection.
+    pub fn new(
+        client: Arc<WorkerClient>,
+        driver: impl Into<String>,
+        connection_string: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            driver: driver.into(),
+            connection_string: connection_string.into(),
+            sample_limit: 5,
+        }
+    }
+
+    /// Maximum failing rows fetched per failed test (default: 5).
+    pub fn with_sample_limit(mut self, limit: u64) -> Self {
+        self.sample_limit = limit;
+        self
+    }
+
+    /// Run every test. Query errors are recorded per test, not returned.
+    pub async fn run(&self, tests: &[DataTest]) -> DataTestReport {
+        let mut report = DataTestReport::default();
+        for test in tests {
+            report.results.push(self.run_test(test).await);
+        }
+        report
+    }
+
+    async fn run_test(&self, test: &DataTest) -> DataTestResult {
+        let count = match self.execute(&test.failures_sql()).await {
+            Ok(response) => response,
+            Err(error) => {
+                let mut result = DataTestResult::new(test);
+                result.error = Some(error.to_string());
+                return result;
+            }
+        };
+
+        let mut result = DataTestResult::from_count(test, &count);
+        if result.failures > 0 && self.sample_limit > 0 {
+            match self.execute(&test.samples_sql(self.sample_limit)).await {
+                Ok(samples) => {
+                    result.sample_columns = samples.columns.into_iter().map(|c| c.name).collect();
+                    result.sample_rows = samples.rows;
+                }
+                Err(error) => result.error = Some(error.to_string()),
+            }
+        }
+        result
+    }
+
+    async fn execute(&self, sql: &str) -> crate::worker::WorkerResult<ExecuteQueryResponse> {
+        self.client
+            .execute_query(&self.driver, &self.connection_string, sql)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::expr::{Expr as ModelExpr, Literal};
+    use crate::model::{ChangeTracking, DataType};
+    use crate::worker::protocol::QueryResultColumn;
+
+    fn model() -> Model {
+        Model::new()
+            .with_source(
+                SourceEntity::new("orders", "raw.orders")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_nullable_column("customer_id", DataType::Int64)
+                    .with_required_column("status", DataType::String)
+                    .with_required_column("created_at", DataType::Timestamp)
+                    .with_primary_key(vec!["order_id"])
+                    .with_accepted_values(
+                        "status",
+                        vec![
+                            Literal::String("open".into()),
+                            Literal::String("shipped".into()),
+                        ],
+                    )
+                    .with_change_tracking(ChangeTracking::AppendOnly {
+                        timestamp_column: "created_at".into(),
+                    }),
+            )
+            .with_source(
+                SourceEntity::new("customers", "raw.customers")
+                    .with_nullable_column("customer_id", DataType::Int64)
+                    .with_filter(
+                        ModelExpr::Column {
+                            entity: None,
+                            column: "deleted_at".into(),
+                        }
+                        .is_null(),
+                    ),
+            )
+            .with_relationship(Relationship::new(
+                "orders",
+                "customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+    }
+
+    fn find<'a>(tests: &'a [DataTest], name: &str) -> &'a DataTest {
+        tests
+            .iter()
+            .find(|t| t.name == name)
+            .unwrap_or_else(|| panic!("missing test {}", name))
+    }
+
+    #[test]
+    fn test_generate_names() {
+        let model = model();
+        let names: Vec<_> = DataTestGenerator::new(&model, Dialect::Postgres)
+            .generate()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "unique_orders_order_id",
+                "not_null_orders_created_at",
+                "not_null_orders_order_id",
+                "not_null_orders_status",
+                "accepted_values_orders_status",
+                "freshness_orders_created_at",
+                "relationship_orders_customer_id_customers",
+                "cardinality_orders_customers_customer_id",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failure_queries() {
+        let model = model();
+        let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+
+        assert_eq!(
+            find(&tests, "unique_orders_order_id").failures_sql(),
+            "WITH \"failing\" AS (\n\
+             SELECT\n  \"order_id\",\n  COUNT(*) AS \"row_count\"\n\
+             FROM \"raw\".\"orders\" AS \"orders\"\n\
+             GROUP BY \"order_id\"\n\
+             HAVING COUNT(*) > 1\n\
+             )\n\
+             SELECT\n  COUNT(*) AS \"failures\"\n\
+             FROM \"failing\""
+        );
+
+        let samples = find(&tests, "accepted_values_orders_status").samples_sql(5);
+        assert!(samples
+            .contains("WHERE \"status\" IS NOT NULL AND \"status\" NOT IN ('open', 'shipped')"));
+        assert!(samples.ends_with("FROM \"failing\"\nLIMIT 5"));
+    }
+
+    #[test]
+    fn test_relationship_reads_filtered_parent() {
+        let model = model();
+        let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+
+        let sql = find(&tests, "relationship_orders_customer_id_customers").failures_sql();
+        assert!(sql
+            .contains("FROM \"raw\".\"customers\" AS \"customers\"\nWHERE \"deleted_at\" IS NULL"));
+        assert!(sql.contains("FROM \"raw\".\"orders\" AS \"child\"\n"));
+        assert!(sql.contains(
+            "LEFT JOIN \"customers_rows\" AS \"parent\" ON \"child\".\"customer_id\" = \"parent\".\"customer_id\""
+        ));
+        assert!(sql.contains(
+            "WHERE \"child\".\"customer_id\" IS NOT NULL AND \"parent\".\"customer_id\" IS NULL"
+        ));
+
+        let sql = find(&tests, "cardinality_orders_customers_customer_id").failures_sql();
+        assert!(sql.contains(
+            "FROM \"customers_rows\" AS \"customers\"\nWHERE \"customer_id\" IS NOT NULL"
+        ));
+    }
+
+    #[test]
+    fn test_composite_relationship_matches_every_column() {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("order_lines", "raw.order_lines")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("line_no", DataType::Int32),
+            )
+            .with_source(
+                SourceEntity::new("shipments", "raw.shipments")
+                    .with_nullable_column("order_id", DataType::Int64)
+                    .with_nullable_column("line_no", DataType::Int32),
+            )
+            .with_relationship(Relationship::composite(
+                "order_lines",
+                "shipments",
+                vec![("order_id", "order_id"), ("line_no", "line_no")],
+                Cardinality::OneToMany,
+            ));
+        let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+
+        let test = find(
+            &tests,
+            "relationship_shipments_order_id_line_no_order_lines",
+        );
+        assert_eq!(test.columns, vec!["order_id", "line_no"]);
+        let sql = test.failures_sql();
+        assert!(
+            sql.contains(
+                "ON \"child\".\"order_id\" = \"parent\".\"order_id\" \
+             AND \"child\".\"line_no\" = \"parent\".\"line_no\""
+            ),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains(
+                "WHERE \"child\".\"order_id\" IS NOT NULL AND \"child\".\"line_no\" IS NOT NULL \
+             AND \"parent\".\"order_id\" IS NULL"
+            ),
+            "{}",
+            sql
+        );
+
+        let sql = find(&tests, "cardinality_shipments_order_lines_order_id_line_no").failures_sql();
+        assert!(
+            sql.contains("GROUP BY \"order_id\", \"line_no\""),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_freshness_per_dialect() {
+        let model = model();
+        let freshness = |dialect: Dialect| {
+            let tests = DataTestGenerator::new(&model, dialect)
+                .with_max_age(Duration::from_secs(6 * 3600))
+                .generate();
+            find(&tests, "freshness_orders_created_at").failures_sql()
+        };
+
+        assert!(freshness(Dialect::Postgres)
+            .contains("\"latest\" < (CURRENT_TIMESTAMP) + INTERVAL '-6 hour'"));
+        assert!(
+            freshness(Dialect::TSql).contains("[latest] < DATEADD(HOUR, -6, (CURRENT_TIMESTAMP))")
+        );
+    }
+
+    #[test]
+    fn test_dedup_skips_unique() {
+        let mut model = model();
+        model.sources.get_mut("orders").unwrap().dedup =
+            Some(crate::model::DedupConfig::new(vec!["order_id"], vec![]));
+
+        let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+        assert!(tests.iter().all(|t| t.kind != DataTestKind::Unique));
+    }
+
+    #[test]
+    fn test_hierarchy_functional_dependency() {
+        let model = model().with_dimension(
+            DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+                .with_column("country")
+                .with_column_as("region_name", "region")
+                .with_column("city")
+                .with_hierarchy("geography", vec!["country", "region", "city"]),
+        );
+        let tests = DataTestGenerator::new(&model, Dialect::Postgres).generate();
+
+        let names: Vec<_> = tests
+            .iter()
+            .filter(|t| t.kind == DataTestKind::Hierarchy)
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "hierarchy_dim_customers_city_region",
+                "hierarchy_dim_customers_region_country",
+            ]
+        );
+
+        let test = find(&tests, "hierarchy_dim_customers_city_region");
+        assert_eq!(test.entity, "customers");
+        assert_eq!(test.columns, vec!["city", "region_name"]);
+        let sql = test.failures_sql();
+        assert!(sql.contains("WHERE \"deleted_at\" IS NULL"));
+        assert!(sql.contains(
+            "SELECT\n  \"city\",\n  COUNT(DISTINCT \"region_name\") AS \"parent_count\"\n\
+             FROM \"customers_rows\" AS \"customers\"\n\
+             GROUP BY \"city\"\n\
+             HAVING COUNT(DISTINCT \"region_name\") > 1"
+        ));
+    }
+
+    #[test]
+    fn test_result_and_report() {
+        let model = model();
+        let tests = DataTestGenerator::new(&model, Dialect::DuckDb).generate();
+        let response = |rows: Vec<Vec<serde_json::Value>>| ExecuteQueryResponse {
+            columns: vec![QueryResultColumn {
+                name: "failures".into(),
+                data_type: "BIGINT".into(),
+            }],
+            row_count: rows.len() as i32,
+            rows,
+            rows_affected: None,
+        };
+
+        let pass = DataTestResult::from_count(&tests[0], &response(vec![vec![0.into()]]));
+        assert!(pass.passed());
+
+        let mut fail = DataTestResult::from_count(&tests[1], &response(vec![vec!["2".into()]]));
+        assert_eq!(fail.failures, 2);
+        fail.sample_columns = vec!["order_id".into(), "created_at".into()];
+        fail.sample_rows = vec![vec![1.into(), serde_json::Value::Null]];
+
+        let error = DataTestResult::from_count(&tests[2], &response(vec![]));
+        assert!(error.error.is_some());
+
+        let report = DataTestReport {
+            results: vec![pass, fail, error],
+        };
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 2);
+        assert_eq!(
+            report.summary(),
+            "PASS  unique_orders_order_id\n\
+             FAIL  not_null_orders_created_at (2 failing)\n\
+             \x20     order_id, created_at\n\
+             \x20     1, null\n\
+             ERROR not_null_orders_order_id: failure count query returned no count\n\
+             1 passed, 1 failed, 1 errored\n"
+        );
+    }
+}
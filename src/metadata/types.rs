@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::dialect::DialectTarget;
 use crate::model::{DataType, SourceColumn, SourceEntity};
 use crate::semantic::inference::{ColumnInfo as InferenceColumnInfo, TableInfo as InferenceTableInfo};
 use crate::worker::protocol;
@@ -93,6 +94,21 @@ pub struct ColumnInfo {
     pub is_computed: bool,
 }
 
+impl ColumnInfo {
+    /// Model type of the column, if the database type is recognized.
+    ///
+    /// String lengths come from `max_length`; `MAX` lengths map to `String`.
+    pub fn model_type(&self) -> Option<DataType> {
+        if self.data_type.to_lowercase().contains("char") {
+            return match self.max_length {
+                Some(len) if len < 0 => Some(DataType::String),
+                len => parse_db_type(&self.data_type, len.or(self.numeric_precision), None),
+            };
+        }
+        parse_db_type(&self.data_type, self.numeric_precision, self.numeric_scale)
+    }
+}
+
 impl From<protocol::ColumnInfo> for ColumnInfo {
     fn from(p: protocol::ColumnInfo) -> Self {
         Self {
@@ -298,6 +314,7 @@ impl From<&TableMetadata> for SourceEntity {
                         data_type,
                         nullable: c.is_nullable,
                         description: None,
+                        accepted_values: Vec::new(),
                     },
                 )
             })
@@ -342,7 +359,10 @@ fn parse_db_type(
         return Some(DataType::Decimal(p, s));
     }
 
-    if type_lower.contains("varchar") || type_lower.contains("nvarchar") {
+    if type_lower.contains("varchar")
+        || type_lower.contains("nvarchar")
+        || type_lower.contains("character varying")
+    {
         let len = precision.map(|p| p as u16).unwrap_or(255);
         return Some(DataType::Varchar(len));
     }
@@ -373,7 +393,11 @@ fn parse_db_type(
         // Date/Time
         "date" => Some(DataType::Date),
         "time" => Some(DataType::Time),
-        "timestamp" | "datetime" | "datetime2" | "smalldatetime" => Some(DataType::Timestamp),
+        "timestamp"
+        | "timestamp without time zone"
+        | "datetime"
+        | "datetime2"
+        | "smalldatetime" => Some(DataType::Timestamp),
         "timestamptz" | "timestamp with time zone" | "datetimeoffset" => Some(DataType::TimestampTz),
 
         // Binary
@@ -510,6 +534,113 @@ impl ValueOverlap {
     }
 }
 
+/// An index on a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexInfo {
+    /// Index name.
+    pub name: String,
+    /// Key columns, in index order.
+    pub columns: Vec<String>,
+    /// Whether the index enforces uniqueness.
+    pub is_unique: bool,
+    /// Whether this backs the primary key.
+    pub is_primary_key: bool,
+}
+
+impl From<protocol::IndexInfo> for IndexInfo {
+    fn from(p: protocol::IndexInfo) -> Self {
+        let mut key_columns: Vec<_> = p.columns.into_iter().filter(|c| !c.is_included).collect();
+        key_columns.sort_by_key(|c| c.position);
+
+        Self {
+            name: p.name,
+            columns: key_columns.into_iter().map(|c| c.name).collect(),
+            is_unique: p.is_unique,
+            is_primary_key: p.is_primary_key,
+        }
+    }
+}
+
+impl IndexInfo {
+    /// Check if a column is the leading key column, so lookups on it alone
+    /// can use the index.
+    pub fn leads_with(&self, column: &str) -> bool {
+        self.columns
+            .first()
+            .is_some_and(|c| c.eq_ignore_ascii_case(column))
+    }
+}
+
+/// Number of rows in a table.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RowCount {
+    /// Number of rows.
+    pub row_count: i64,
+    /// Whether this is an exact count or an estimate.
+    pub is_exact: bool,
+}
+
+impl From<protocol::RowCountResponse> for RowCount {
+    fn from(p: protocol::RowCountResponse) -> Self {
+        Self {
+            row_count: p.row_count,
+            is_exact: p.is_exact,
+        }
+    }
+}
+
+/// Rows sampled from a table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SampleRows {
+    /// Column names in order.
+    pub columns: Vec<String>,
+    /// Sampled data rows.
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl From<protocol::SampleRowsResponse> for SampleRows {
+    fn from(p: protocol::SampleRowsResponse) -> Self {
+        Self {
+            columns: p.columns,
+            rows: p.rows,
+        }
+    }
+}
+
+impl SampleRows {
+    /// Non-null sampled values of a column.
+    pub fn column_values(&self, column: &str) -> Vec<&serde_json::Value> {
+        let Some(index) = self
+            .columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(column))
+        else {
+            return Vec::new();
+        };
+
+        self.rows
+            .iter()
+            .filter_map(|row| row.get(index))
+            .filter(|v| !v.is_null())
+            .collect()
+    }
+}
+
+/// Indexes, row count and sampled rows of a table, used for inference.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableProfile {
+    /// Schema the table belongs to.
+    pub schema: String,
+    /// Table name.
+    pub table: String,
+    /// Indexes on the table.
+    pub indexes: Vec<IndexInfo>,
+    /// Row count, when fetched.
+    pub row_count: Option<RowCount>,
+    /// Sampled rows, when fetched.
+    pub sample: Option<SampleRows>,
+}
+
 /// Database information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseInfo {
@@ -525,6 +656,15 @@ pub struct DatabaseInfo {
     pub collation: Option<String>,
 }
 
+impl DatabaseInfo {
+    /// The dialect and server version to generate SQL for.
+    ///
+    /// Returns `None` for products without a supported dialect.
+    pub fn dialect_target(&self) -> Option<DialectTarget> {
+        DialectTarget::from_server(&self.product_name, &self.product_version)
+    }
+}
+
 impl From<protocol::DatabaseInfo> for DatabaseInfo {
     fn from(p: protocol::DatabaseInfo) -> Self {
         Self {
@@ -626,4 +766,29 @@ mod tests {
         assert!(metadata.is_unique_column("id"));
         assert!(!metadata.is_unique_column("customer_id"));
     }
+
+    #[test]
+    fn test_database_info_dialect_target() {
+        use crate::dialect::{Dialect, SqlDialect, Version};
+
+        let info = |name: &str, version: &str| DatabaseInfo {
+            product_name: name.into(),
+            product_version: version.into(),
+            database_name: "db".into(),
+            default_schema: None,
+            collation: None,
+        };
+
+        let pg = info("PostgreSQL", "14.9").dialect_target().unwrap();
+        assert_eq!(pg, Dialect::Postgres.at(Version::new(14, 9)));
+        assert!(!pg.supports_merge());
+
+        let mssql = info("Microsoft SQL Server", "16.0.1000.6")
+            .dialect_target()
+            .unwrap();
+        assert_eq!(mssql.version.map(|v| v.major), Some(16));
+        assert!(mssql.supports_named_windows());
+
+        assert!(info("Oracle", "19.0.0.0.0").dialect_target().is_none());
+    }
 }
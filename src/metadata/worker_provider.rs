@@ -214,6 +214,66 @@ impl MetadataProvider for WorkerMetadataProvider {
         Ok(response.into())
     }
 
+    async fn get_indexes(&self, schema: &str, table: &str) -> MetadataResult<Vec<IndexInfo>> {
+        let response: protocol::GetIndexesResponse = self
+            .client
+            .request(
+                methods::GET_INDEXES,
+                protocol::GetIndexesParams {
+                    connection: self.connection_params(),
+                    schema: schema.to_string(),
+                    table: table.to_string(),
+                },
+            )
+            .await?;
+
+        Ok(response.indexes.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_row_count(
+        &self,
+        schema: &str,
+        table: &str,
+        exact: bool,
+    ) -> MetadataResult<Option<RowCount>> {
+        let response: protocol::RowCountResponse = self
+            .client
+            .request(
+                methods::GET_ROW_COUNT,
+                protocol::GetRowCountParams {
+                    connection: self.connection_params(),
+                    schema: schema.to_string(),
+                    table: table.to_string(),
+                    exact: Some(exact),
+                },
+            )
+            .await?;
+
+        Ok(Some(response.into()))
+    }
+
+    async fn sample_rows(
+        &self,
+        schema: &str,
+        table: &str,
+        limit: Option<i32>,
+    ) -> MetadataResult<Option<SampleRows>> {
+        let response: protocol::SampleRowsResponse = self
+            .client
+            .request(
+                methods::SAMPLE_ROWS,
+                protocol::SampleRowsParams {
+                    connection: self.connection_params(),
+                    schema: schema.to_string(),
+                    table: table.to_string(),
+                    limit,
+                },
+            )
+            .await?;
+
+        Ok(Some(response.into()))
+    }
+
     async fn get_database_info(&self) -> MetadataResult<DatabaseInfo> {
         let response: protocol::GetDatabaseInfoResponse = self
             .client
@@ -0,0 +1,68 @@
+//! Bridge table definitions.
+//!
+//! A bridge resolves a many-to-many relationship (customer ↔ account,
+//! product ↔ category) through a source entity holding one row per pair.
+//! The bridge source relates many-to-one to both sides; queries that cross
+//! it are planned so additive measures are not double counted.
+//!
+//! # Example
+//!
+//! ```lua
+//! source "customer_accounts" { ... }
+//!
+//! link(customer_accounts.customer_id, customers.customer_id)
+//! link(customer_accounts.account_id, accounts.account_id)
+//!
+//! bridge "customer_accounts" {
+//!     weight = "allocation",  -- optional; weights per account sum to 1
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// A bridge entity resolving a many-to-many relationship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeDefinition {
+    /// The source entity holding the bridge rows.
+    pub entity: String,
+
+    /// Allocation weight column on the bridge.
+    ///
+    /// When set, additive measures crossing the bridge are multiplied by the
+    /// weight instead of being de-duplicated, so the weights for one key on
+    /// the measure side are expected to sum to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_column: Option<String>,
+
+    /// Human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl BridgeDefinition {
+    /// Declare `entity` as a bridge.
+    pub fn new(entity: impl Into<String>) -> Self {
+        Self {
+            entity: entity.into(),
+            weight_column: None,
+            description: None,
+        }
+    }
+
+    /// Allocate additive measures by this weight column.
+    pub fn with_weight(mut self, column: impl Into<String>) -> Self {
+        self.weight_column = Some(column.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Whether measures are allocated by weight rather than de-duplicated.
+    pub fn is_weighted(&self) -> bool {
+        self.weight_column.is_some()
+    }
+}
@@ -0,0 +1,939 @@
+//! Generated date dimensions.
+//!
+//! Every fact with a [`DateConfig`] joins its date roles to a date dimension.
+//! [`DateDimension`] builds that table for a date range instead of having it
+//! written by hand for each warehouse: one row per day, keyed by a
+//! `YYYYMMDD` integer, with the calendar parts named by the fact's
+//! [`GrainColumns`], ISO week and day of week, weekday/weekend flags, fiscal
+//! year/quarter/month and an optional holiday lookup.
+//!
+//! The day spine uses whatever the warehouse offers: `generate_series` on
+//! Postgres and DuckDB, `UNNEST(GENERATE_DATE_ARRAY(...))` on BigQuery,
+//! `sequence` on Databricks and Trino, `GENERATOR` on Snowflake, `numbers` on
+//! ClickHouse and `GENERATE_SERIES` on SQL Server 2022. Everywhere else the
+//! days are counted by cross joining a recursive CTE of the digits 0-9, one
+//! copy per decimal place, which stays within the recursion depth caps of
+//! older SQL Server and MySQL (100 and 1000 levels by default) for any range.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let config = model.get_fact("fact_orders").unwrap().date_config.as_ref().unwrap();
+//! let dates = DateDimension::from_config(config, "analytics.dim_date", "2020-01-01", "2030-12-31")
+//!     .unwrap()
+//!     .with_fiscal_year_start(7);
+//! let ddl = dates.create_table(Dialect::Snowflake)?.to_sql(Dialect::Snowflake);
+//! dates.register(&mut model);
+//! ```
+
+use thiserror::Error;
+
+use super::dimension_role::{DateConfig, GrainColumns};
+use super::source::{SourceColumn, SourceEntity};
+use super::target::TargetColumn;
+use super::types::{DataType, TableOptions};
+use super::Model;
+use crate::sql::ddl::{CreateTable, DdlError};
+use crate::sql::dialect::{Dialect, DialectTarget};
+use crate::sql::expr::{
+    cast, col, func, lit_bool, lit_int, lit_str, raw_sql, row_number, table_col, Expr, ExprExt,
+    WindowExt, WindowOrderBy,
+};
+use crate::sql::query::{Cte, Query, SelectExpr, TableRef};
+
+/// Column carrying each day through the spine and calendar CTEs.
+const DAY: &str = "date_day";
+
+/// Alias of the calendar CTE in the final SELECT.
+const CALENDAR: &str = "calendar";
+
+/// Alias of the joined holiday table.
+const HOLIDAY: &str = "holiday";
+
+/// Recursive CTE of the digits 0-9 counting days where no series function exists.
+const DIGITS: &str = "digits";
+
+/// Column of the digits CTE.
+const DIGIT: &str = "n";
+
+/// Errors from generating a date dimension.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DateDimensionError {
+    #[error("invalid date '{0}': expected YYYY-MM-DD")]
+    InvalidDate(String),
+
+    #[error("empty date range: {start} is after {end}")]
+    EmptyRange { start: String, end: String },
+
+    #[error("fiscal year cannot start in month {0}")]
+    InvalidFiscalMonth(u32),
+    #[error("invalid table options: {0}")]
+    TableOptions(#[from] DdlError),
+}
+
+/// A holiday calendar joined onto the generated days.
+///
+/// The table must hold at most one row per date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HolidayTable {
+    /// Physical table, optionally schema-qualified (e.g. "ref.holidays")
+    pub table: String,
+    /// Column holding the holiday's date
+    pub date_column: String,
+    /// Column holding the holiday's name, copied into `holiday_name`
+    pub name_column: Option<String>,
+}
+
+impl HolidayTable {
+    /// Look up holidays in `table` by its `date_column`.
+    pub fn new(table: impl Into<String>, date_column: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            date_column: date_column.into(),
+            name_column: None,
+        }
+    }
+
+    /// Copy the holiday's name from `column`.
+    pub fn with_name_column(mut self, column: impl Into<String>) -> Self {
+        self.name_column = Some(column.into());
+        self
+    }
+}
+
+/// A generated date dimension covering `start..=end`.
+#[derive(Debug, Clone)]
+pub struct DateDimension {
+    /// Entity name date roles refer to (e.g. "date")
+    pub name: String,
+    /// Physical target table (e.g. "analytics.dim_date")
+    pub table: String,
+    /// First day, `YYYY-MM-DD`
+    pub start: String,
+    /// Last day, `YYYY-MM-DD`
+    pub end: String,
+    /// `YYYYMMDD` integer key column
+    pub key_column: String,
+    /// Calendar date column
+    pub date_column: String,
+    /// Names of the year, quarter, month, ISO week and day-of-month columns
+    pub grain: GrainColumns,
+    /// Month (1-12) the fiscal year starts in; fiscal years are named for the
+    /// calendar year they end in
+    pub fiscal_year_start: u32,
+    /// Optional holiday lookup
+    pub holidays: Option<HolidayTable>,
+    /// Partitioning, clustering and storage options for the target table
+    pub table_options: TableOptions,
+}
+
+impl DateDimension {
+    /// Create a date dimension for `start..=end` (`YYYY-MM-DD`).
+    pub fn new(
+        name: impl Into<String>,
+        table: impl Into<String>,
+        start: impl Into<String>,
+        end: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            table: table.into(),
+            start: start.into(),
+            end: end.into(),
+            key_column: "date_id".into(),
+            date_column: "date".into(),
+            grain: default_grain(),
+            fiscal_year_start: 1,
+            holidays: None,
+            table_options: TableOptions::default(),
+        }
+    }
+
+    /// Create the dimension a fact's date roles point at.
+    ///
+    /// The name and key come from the primary role, the column names from the
+    /// config's grain columns. Returns `None` when the config has no roles.
+    pub fn from_config(
+        config: &DateConfig,
+        table: impl Into<String>,
+        start: impl Into<String>,
+        end: impl Into<String>,
+    ) -> Option<Self> {
+        let role = config.get_primary_role()?;
+        let mut dimension =
+            Self::new(&role.dimension, table, start, end).with_key_column(&role.pk_column);
+        if let Some(grain) = &config.grain_columns {
+            dimension = dimension.with_grain_columns(grain.clone());
+        }
+        Some(dimension)
+    }
+
+    /// Set the `YYYYMMDD` key column.
+    pub fn with_key_column(mut self, column: impl Into<String>) -> Self {
+        self.key_column = column.into();
+        self
+    }
+
+    /// Set the calendar date column.
+    pub fn with_date_column(mut self, column: impl Into<String>) -> Self {
+        self.date_column = column.into();
+        self
+    }
+
+    /// Name the calendar part columns. Parts left unset keep their default names.
+    pub fn with_grain_columns(mut self, grain: GrainColumns) -> Self {
+        let defaults = default_grain();
+        self.grain = GrainColumns {
+            year: grain.year,
+            quarter: grain.quarter.or(defaults.quarter),
+            month: grain.month.or(defaults.month),
+            week: grain.week.or(defaults.week),
+            day: grain.day.or(defaults.day),
+        };
+        self
+    }
+
+    /// Start the fiscal year in `month` (1-12).
+    pub fn with_fiscal_year_start(mut self, month: u32) -> Self {
+        self.fiscal_year_start = month;
+        self
+    }
+
+    /// Join a holiday calendar, adding `is_holiday` and `holiday_name`.
+    pub fn with_holidays(mut self, holidays: HolidayTable) -> Self {
+        self.holidays = Some(holidays);
+        self
+    }
+
+    /// Set partitioning, clustering and storage options for the target table.
+    pub fn with_table_options(mut self, options: TableOptions) -> Self {
+        self.table_options = options;
+        self
+    }
+
+    /// Columns of the generated table, in order.
+    pub fn columns(&self) -> Vec<TargetColumn> {
+        let column = |name: &str, data_type: DataType, description: &str| TargetColumn {
+            name: name.to_string(),
+            data_type: Some(data_type),
+            description: Some(description.to_string()),
+        };
+        let mut columns = vec![
+            column(&self.key_column, DataType::Int32, "Date key (YYYYMMDD)"),
+            column(&self.date_column, DataType::Date, "Calendar date"),
+            column(&self.grain.year, DataType::Int32, "Calendar year"),
+            column(
+                self.part(&self.grain.quarter),
+                DataType::Int32,
+                "Calendar quarter (1-4)",
+            ),
+            column(
+                self.part(&self.grain.month),
+                DataType::Int32,
+                "Calendar month (1-12)",
+            ),
+            column(
+                self.part(&self.grain.week),
+                DataType::Int32,
+                "ISO week (1-53)",
+            ),
+            column(self.part(&self.grain.day), DataType::Int32, "Day of month"),
+            column(
+                "day_of_week",
+                DataType::Int32,
+                "ISO day of week (1 = Monday)",
+            ),
+            column("is_weekday", DataType::Bool, "Monday to Friday"),
+            column("is_weekend", DataType::Bool, "Saturday or Sunday"),
+            column(
+                "fiscal_year",
+                DataType::Int32,
+                "Fiscal year, named for the year it ends in",
+            ),
+            column("fiscal_quarter", DataType::Int32, "Fiscal quarter (1-4)"),
+            column("fiscal_month", DataType::Int32, "Fiscal month (1-12)"),
+        ];
+        if let Some(holidays) = &self.holidays {
+            columns.push(column(
+                "is_holiday",
+                DataType::Bool,
+                "Listed in the holiday calendar",
+            ));
+            if holidays.name_column.is_some() {
+                columns.push(column("holiday_name", DataType::String, "Holiday name"));
+            }
+        }
+        columns
+    }
+
+    /// The SELECT producing one row per day.
+    pub fn query(&self, dialect: impl Into<DialectTarget>) -> Result<Query, DateDimensionError> {
+        let dialect = dialect.into();
+        let first = parse_date(&self.start)?;
+        let last = parse_date(&self.end)?;
+        if last < first {
+            return Err(DateDimensionError::EmptyRange {
+                start: self.start.clone(),
+                end: self.end.clone(),
+            });
+        }
+        if !(1..=12).contains(&self.fiscal_year_start) {
+            return Err(DateDimensionError::InvalidFiscalMonth(
+                self.fiscal_year_start,
+            ));
+        }
+        let days = day_number(last) - day_number(first) + 1;
+
+        let cal = |column: &str| table_col(CALENDAR, column);
+        let int = |expr: Expr| as_int(expr, dialect);
+        let key = int(cal("year")
+            .mul(lit_int(10000))
+            .add(cal("month").mul(lit_int(100)))
+            .add(cal("day")));
+
+        let mut select: Vec<SelectExpr> = vec![
+            key.alias(&self.key_column),
+            cal(DAY).alias(&self.date_column),
+            cal("year").alias(&self.grain.year),
+            cal("quarter").alias(self.part(&self.grain.quarter)),
+            cal("month").alias(self.part(&self.grain.month)),
+            cal("week").alias(self.part(&self.grain.week)),
+            cal("day").alias(self.part(&self.grain.day)),
+            cal("day_of_week").alias("day_of_week"),
+            flag(cal("day_of_week").lt(lit_int(6)), dialect).alias("is_weekday"),
+            flag(cal("day_of_week").gte(lit_int(6)), dialect).alias("is_weekend"),
+        ];
+        select.extend(self.fiscal_columns());
+
+        let mut query = Query::new();
+        for cte in self.spine(dialect, days) {
+            query = query.with_cte(cte);
+        }
+        query = query.with_cte(Cte::new(CALENDAR, calendar(dialect)));
+        if let Some(holidays) = &self.holidays {
+            let holiday_date = table_col(HOLIDAY, &holidays.date_column);
+            select.push(flag(holiday_date.clone().is_not_null(), dialect).alias("is_holiday"));
+            if let Some(name) = &holidays.name_column {
+                select.push(table_col(HOLIDAY, name).alias("holiday_name"));
+            }
+            let table = match holidays.table.rsplit_once('.') {
+                Some((schema, table)) => TableRef::new(table).with_schema(schema),
+                None => TableRef::new(&holidays.table),
+            };
+            query = query
+                .select(select)
+                .from(TableRef::new(CALENDAR))
+                .left_join(table.with_alias(HOLIDAY), holiday_date.eq(cal(DAY)));
+        } else {
+            query = query.select(select).from(TableRef::new(CALENDAR));
+        }
+        Ok(query)
+    }
+
+    /// `CREATE TABLE ... AS SELECT` for the dimension, with its table
+    /// options and column descriptions.
+    pub fn create_table(
+        &self,
+        dialect: impl Into<DialectTarget>,
+    ) -> Result<CreateTable, DateDimensionError> {
+        let dialect = dialect.into();
+        let query = self.query(dialect)?;
+        let table = match self.table.rsplit_once('.') {
+            Some((schema, table)) => CreateTable::new(table).schema(schema),
+            None => CreateTable::new(&self.table),
+        };
+        let comments = self
+            .columns()
+            .into_iter()
+            .filter_map(|c| Some((c.name, c.description?)));
+        let table = table
+            .as_select(query)
+            .options(self.table_options.clone())
+            .column_comments(comments);
+        table.validate(dialect)?;
+        Ok(table)
+    }
+
+    /// The entity date roles resolve to, reading the generated table.
+    pub fn entity(&self) -> SourceEntity {
+        let mut entity = SourceEntity::new(&self.name, &self.table)
+            .with_primary_key(vec![self.key_column.clone()]);
+        for column in self.columns() {
+            // Only days missing from the holiday calendar have no value
+            let nullable = column.name == "holiday_name";
+            let data_type = column.data_type.unwrap_or(DataType::String);
+            let mut source_column = SourceColumn::new(&column.name, data_type, nullable);
+            source_column.description = column.description;
+            entity.columns.insert(column.name, source_column);
+        }
+        entity
+    }
+
+    /// Register the dimension's entity in `model`, replacing any entity of
+    /// the same name.
+    pub fn register(&self, model: &mut Model) {
+        model.add_source(self.entity());
+    }
+
+    /// Name of an optional calendar part; always set by `with_grain_columns`.
+    fn part<'a>(&'a self, column: &'a Option<String>) -> &'a str {
+        column.as_deref().unwrap_or_default()
+    }
+
+    /// One `date_day` row per day from the first date, plus any CTE the
+    /// spine reads from.
+    fn spine(&self, dialect: DialectTarget, days: i64) -> Vec<Cte> {
+        let first = || cast(lit_str(&self.start), DataType::Date);
+        let last = || cast(lit_str(&self.end), DataType::Date);
+        let offset = |n: Expr| func("DATEADD", vec![lit_str("day"), n, first()]);
+        let from = |call: Expr| TableRef::function(call, "days");
+
+        let query = match dialect.dialect {
+            Dialect::Postgres => Query::new().select(vec![cast(
+                func(
+                    "GENERATE_SERIES",
+                    vec![first(), last(), raw_sql("INTERVAL '1 day'")],
+                ),
+                DataType::Date,
+            )
+            .alias(DAY)]),
+            Dialect::DuckDb => Query::new().select(vec![cast(
+                func(
+                    "UNNEST",
+                    vec![func(
+                        "GENERATE_SERIES",
+                        vec![first(), last(), raw_sql("INTERVAL '1 day'")],
+                    )],
+                ),
+                DataType::Date,
+            )
+            .alias(DAY)]),
+            Dialect::Databricks => Query::new().select(vec![func(
+                "EXPLODE",
+                vec![func("SEQUENCE", vec![first(), last()])],
+            )
+            .alias(DAY)]),
+            Dialect::BigQuery => Query::new().select(vec![col(DAY)]).from(TableRef::function(
+                func(
+                    "UNNEST",
+                    vec![func("GENERATE_DATE_ARRAY", vec![first(), last()])],
+                ),
+                DAY,
+            )),
+            Dialect::Trino => Query::new().select(vec![col(DAY)]).from(
+                from(func(
+                    "UNNEST",
+                    vec![func("SEQUENCE", vec![first(), last()])],
+                ))
+                .with_columns(vec![DAY]),
+            ),
+            Dialect::Snowflake => {
+                // SEQ4() may skip values; ROW_NUMBER() over it does not
+                let n = row_number()
+                    .over()
+                    .order_by(vec![WindowOrderBy::new(func("SEQ4", vec![]))])
+                    .build()
+                    .sub(lit_int(1));
+                Query::new()
+                    .select(vec![offset(n).alias(DAY)])
+                    .from(from(raw_sql(&format!(
+                        "TABLE(GENERATOR(ROWCOUNT => {}))",
+                        days
+                    ))))
+            }
+            Dialect::ClickHouse => Query::new()
+                .select(vec![offset(col("number")).alias(DAY)])
+                .from(from(raw_sql(&format!("numbers({})", days)))),
+            Dialect::TSql if dialect.at_least(16, 0) => Query::new()
+                .select(vec![offset(col("value")).alias(DAY)])
+                .from(from(func(
+                    "GENERATE_SERIES",
+                    vec![lit_int(0), lit_int(days - 1)],
+                ))),
+            _ => {
+                let anchor = Query::new().select(vec![lit_int(0)]);
+                let step = Query::new()
+                    .select(vec![col(DIGIT).add(lit_int(1))])
+                    .from(TableRef::new(DIGITS))
+                    .filter(col(DIGIT).lt(lit_int(9)));
+                let body = Query {
+                    set_op: Some(Box::new(anchor.union_all(step))),
+                    ..Default::default()
+                };
+                let digits = Cte::recursive(DIGITS, body).with_columns(vec![DIGIT]);
+
+                // One copy of the digits per decimal place of the last offset
+                let places = (days - 1).to_string().len();
+                let alias = |place: usize| format!("d{}", place);
+                let mut n = table_col(&alias(0), DIGIT);
+                let mut query = Query::new().from(TableRef::new(DIGITS).with_alias(&alias(0)));
+                let mut scale = 1;
+                for place in 1..places {
+                    scale *= 10;
+                    n = n.add(table_col(&alias(place), DIGIT).mul(lit_int(scale)));
+                    query = query.cross_join(TableRef::new(DIGITS).with_alias(&alias(place)));
+                }
+                let day = match dialect.dialect {
+                    Dialect::Sqlite => func("DATE", vec![offset(n.clone())]),
+                    _ => cast(offset(n.clone()), DataType::Date),
+                };
+                let query = query
+                    .select(vec![day.alias(DAY)])
+                    .filter(n.lt(lit_int(days)));
+                return vec![digits, Cte::new("spine", query)];
+            }
+        };
+        vec![Cte::new("spine", query)]
+    }
+
+    /// Fiscal year, quarter and month, shifted by the fiscal start month.
+    fn fiscal_columns(&self) -> Vec<SelectExpr> {
+        let cal = |column: &str| table_col(CALENDAR, column);
+        let start = i64::from(self.fiscal_year_start);
+        if start == 1 {
+            return vec![
+                cal("year").alias("fiscal_year"),
+                cal("quarter").alias("fiscal_quarter"),
+                cal("month").alias("fiscal_month"),
+            ];
+        }
+
+        let in_new_year = cal("month").gte(lit_int(start));
+        let fiscal_year = case(
+            vec![(in_new_year.clone(), cal("year").add(lit_int(1)))],
+            cal("year"),
+        );
+        let fiscal_month = case(
+            vec![(in_new_year, cal("month").sub(lit_int(start - 1)))],
+            cal("month").add(lit_int(13 - start)),
+        );
+        // Calendar months of fiscal quarters 1-3; the rest are quarter 4
+        let months = |quarter: i64| {
+            (0..3)
+                .map(|i| lit_int((start - 1 + quarter * 3 + i) % 12 + 1))
+                .collect::<Vec<_>>()
+        };
+        let fiscal_quarter = case(
+            (0..3)
+                .map(|q| (cal("month").in_list(months(q)), lit_int(q + 1)))
+                .collect(),
+            lit_int(4),
+        );
+        vec![
+            fiscal_year.alias("fiscal_year"),
+            fiscal_quarter.alias("fiscal_quarter"),
+            fiscal_month.alias("fiscal_month"),
+        ]
+    }
+}
+
+/// Default names of the calendar part columns.
+fn default_grain() -> GrainColumns {
+    GrainColumns::new("year")
+        .with_quarter("quarter")
+        .with_month("month")
+        .with_week("week")
+        .with_day("day")
+}
+
+/// Calendar parts of each spine day, from the canonical date functions.
+fn calendar(dialect: DialectTarget) -> Query {
+    let d = || col(DAY);
+    let part = |unit: &str| as_int(func("EXTRACT", vec![lit_str(unit), d()]), dialect);
+    // Weeks start on Monday, so days since the week's start give the ISO day
+    let day_of_week = as_int(
+        func(
+            "DATEDIFF",
+            vec![
+                lit_str("day"),
+                func("DATE_TRUNC", vec![lit_str("week"), d()]),
+                d(),
+            ],
+        )
+        .add(lit_int(1)),
+        dialect,
+    );
+    Query::new()
+        .select(vec![
+            d().into(),
+            part("year").alias("year"),
+            part("quarter").alias("quarter"),
+            part("month").alias("month"),
+            part("week").alias("week"),
+            part("day").alias("day"),
+            day_of_week.alias("day_of_week"),
+        ])
+        .from(TableRef::new("spine"))
+}
+
+/// Integer column type; MySQL's CAST has no INT target and SQLite's parts
+/// are already integers.
+fn as_int(expr: Expr, dialect: DialectTarget) -> Expr {
+    match dialect.dialect {
+        Dialect::MySql | Dialect::Sqlite => expr,
+        _ => cast(expr, DataType::Int32),
+    }
+}
+
+/// Boolean column from a condition; SQL Server stores it as BIT.
+fn flag(condition: Expr, dialect: DialectTarget) -> Expr {
+    let flag = case(vec![(condition, lit_bool(true))], lit_bool(false));
+    match dialect.dialect {
+        Dialect::TSql => cast(flag, DataType::Bool),
+        _ => flag,
+    }
+}
+
+fn case(when_clauses: Vec<(Expr, Expr)>, otherwise: Expr) -> Expr {
+    Expr::Case {
+        operand: None,
+        when_clauses,
+        else_clause: Some(Box::new(otherwise)),
+    }
+}
+
+/// Parse `YYYY-MM-DD` into (year, month, day).
+fn parse_date(s: &str) -> Result<(i64, u32, u32), DateDimensionError> {
+    let invalid = || DateDimensionError::InvalidDate(s.to_string());
+    let mut parts = s.split('-');
+    let (Some(y), Some(m), Some(d), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+        return Err(invalid());
+    }
+    let year: i64 = y.parse().map_err(|_| invalid())?;
+    let month: u32 = m.parse().map_err(|_| invalid())?;
+    let day: u32 = d.parse().map_err(|_| invalid())?;
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    if day == 0 || day > days_in_month {
+        return Err(invalid());
+    }
+    Ok((year, month, day))
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+fn day_number((year, month, day): (i64, u32, u32)) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DimensionRole;
+    use crate::sql::dialect::Version;
+    use crate::sql::test_utils::validate_sql;
+    use rusqlite::Connection;
+
+    fn year_2024() -> DateDimension {
+        DateDimension::new("date", "dim_date", "2024-01-01", "2024-12-31")
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let bad = |start: &str, end: &str, fiscal: u32| {
+            DateDimension::new("date", "dim_date", start, end)
+                .with_fiscal_year_start(fiscal)
+                .query(Dialect::Postgres)
+                .unwrap_err()
+        };
+
+        assert_eq!(
+            bad("2023-02-29", "2024-01-01", 1),
+            DateDimensionError::InvalidDate("2023-02-29".into())
+        );
+        assert_eq!(
+            bad("2024-1-01", "2024-01-01", 1),
+            DateDimensionError::InvalidDate("2024-1-01".into())
+        );
+        assert!(matches!(
+            bad("2024-02-01", "2024-01-31", 1),
+            DateDimensionError::EmptyRange { .. }
+        ));
+        assert_eq!(
+            bad("2024-01-01", "2024-12-31", 13),
+            DateDimensionError::InvalidFiscalMonth(13)
+        );
+    }
+
+    #[test]
+    fn test_day_number() {
+        assert_eq!(day_number((1970, 1, 1)), 0);
+        assert_eq!(day_number((2000, 3, 1)) - day_number((2000, 2, 28)), 2);
+        assert_eq!(day_number((2025, 1, 1)) - day_number((2024, 1, 1)), 366);
+        assert_eq!(day_number((1969, 12, 31)), -1);
+    }
+
+    #[test]
+    fn test_spine_per_dialect() {
+        let sql = |dialect: DialectTarget| year_2024().query(dialect).unwrap().to_sql(dialect);
+
+        assert!(sql(Dialect::Postgres.into())
+            .contains("GENERATE_SERIES(CAST('2024-01-01' AS DATE), CAST('2024-12-31' AS DATE), INTERVAL '1 day')"));
+        assert!(sql(Dialect::BigQuery.into()).contains(
+            "FROM UNNEST(GENERATE_DATE_ARRAY(CAST('2024-01-01' AS DATE), CAST('2024-12-31' AS DATE))) AS `date_day`"
+        ));
+        assert!(sql(Dialect::Trino.into()).contains("AS \"days\" (\"date_day\")"));
+        assert!(sql(Dialect::Databricks.into()).contains("EXPLODE(SEQUENCE("));
+        assert!(sql(Dialect::Snowflake.into()).contains("TABLE(GENERATOR(ROWCOUNT => 366))"));
+        assert!(sql(Dialect::ClickHouse.into()).contains("FROM numbers(366)"));
+        assert!(sql(Dialect::TSql.into()).contains("FROM GENERATE_SERIES(0, 365)"));
+
+        // Only the ten digits recurse, far below SQL Server's and MySQL's caps
+        let sql_2019 = sql(Dialect::TSql.at(Version::sql_server(2019)));
+        assert!(sql_2019.starts_with("WITH [digits] ([n]) AS ("));
+        assert!(sql_2019.contains("WHERE [n] < 9"));
+        assert!(sql_2019.contains("CROSS JOIN [digits] AS [d2]"));
+        assert!(!sql_2019.contains("[d3]"));
+        let mysql = sql(Dialect::MySql.into());
+        assert!(mysql.starts_with("WITH RECURSIVE `digits` (`n`) AS ("));
+        assert!(mysql.contains("`d0`.`n` + `d1`.`n` * 10 + `d2`.`n` * 100 < 366"));
+    }
+
+    #[test]
+    fn test_sqlite_decade_spine() {
+        let conn = Connection::open_in_memory().unwrap();
+        let sql = DateDimension::new("date", "dim_date", "2020-01-01", "2030-12-31")
+            .query(Dialect::Sqlite)
+            .unwrap()
+            .to_sql(Dialect::Sqlite);
+        let (count, first, last): (i64, String, String) = conn
+            .query_row(
+                &format!("SELECT COUNT(*), MIN(date), MAX(date) FROM ({})", sql),
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (count, first.as_str(), last.as_str()),
+            (4018, "2020-01-01", "2030-12-31")
+        );
+    }
+
+    #[test]
+    fn test_sql_parses_in_every_dialect() {
+        let dates = year_2024()
+            .with_fiscal_year_start(7)
+            .with_holidays(HolidayTable::new("ref.holidays", "day").with_name_column("name"));
+        for dialect in [
+            Dialect::Postgres,
+            Dialect::DuckDb,
+            Dialect::MySql,
+            Dialect::TSql,
+            Dialect::Redshift,
+            Dialect::Databricks,
+            Dialect::Sqlite,
+            Dialect::Trino,
+        ] {
+            let sql = dates.create_table(dialect).unwrap().to_sql(dialect);
+            validate_sql(&sql, dialect).unwrap_or_else(|e| panic!("{:?}: {}\n{}", dialect, e, sql));
+        }
+    }
+
+    #[test]
+    fn test_create_table_applies_options_and_comments() {
+        let dates =
+            year_2024().with_table_options(TableOptions::new().with_partition_by(vec!["date"]));
+
+        let table = dates.create_table(Dialect::BigQuery).unwrap();
+        let sql = table.to_sql(Dialect::BigQuery);
+        assert!(sql.contains("PARTITION BY `date`"), "{}", sql);
+        assert_eq!(
+            dates.create_table(Dialect::Postgres).unwrap_err(),
+            DateDimensionError::TableOptions(DdlError::UnsupportedOption {
+                option: "PARTITION BY",
+                dialect: Dialect::Postgres.into(),
+            })
+        );
+
+        let comments = year_2024()
+            .create_table(Dialect::Postgres)
+            .unwrap()
+            .comment_statements(Dialect::Postgres);
+        assert_eq!(comments.len(), year_2024().columns().len());
+        assert_eq!(
+            comments[0],
+            "COMMENT ON COLUMN \"dim_date\".\"date_id\" IS 'Date key (YYYYMMDD)'"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_dimension_executes() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE holidays (day TEXT, name TEXT);
+             INSERT INTO holidays VALUES ('2024-12-25', 'Christmas Day');",
+        )
+        .unwrap();
+        let dates = year_2024()
+            .with_fiscal_year_start(7)
+            .with_holidays(HolidayTable::new("holidays", "day").with_name_column("name"));
+        let ddl = dates
+            .create_table(Dialect::Sqlite)
+            .unwrap()
+            .to_sql(Dialect::Sqlite);
+        conn.execute_batch(&ddl).unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM dim_date", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 366);
+
+        let row = |key: i64| -> Vec<String> {
+            conn.query_row(
+                "SELECT date, year, quarter, month, day, day_of_week, is_weekend, \
+                 fiscal_year, fiscal_quarter, fiscal_month, is_holiday, holiday_name \
+                 FROM dim_date WHERE date_id = ?1",
+                [key],
+                |row| {
+                    (0..12)
+                        .map(|i| {
+                            let value: rusqlite::types::Value = row.get(i)?;
+                            Ok(match value {
+                                rusqlite::types::Value::Null => "NULL".to_string(),
+                                rusqlite::types::Value::Integer(n) => n.to_string(),
+                                rusqlite::types::Value::Text(s) => s,
+                                other => format!("{:?}", other),
+                            })
+                        })
+                        .collect()
+                },
+            )
+            .unwrap()
+        };
+
+        // A Wednesday in fiscal 2024's last quarter
+        assert_eq!(
+            row(20240515),
+            [
+                "2024-05-15",
+                "2024",
+                "2",
+                "5",
+                "15",
+                "3",
+                "0",
+                "2024",
+                "4",
+                "11",
+                "0",
+                "NULL"
+            ]
+        );
+        // The first day of fiscal 2025, a Monday
+        assert_eq!(
+            row(20240701),
+            [
+                "2024-07-01",
+                "2024",
+                "3",
+                "7",
+                "1",
+                "1",
+                "0",
+                "2025",
+                "1",
+                "1",
+                "0",
+                "NULL"
+            ]
+        );
+        assert_eq!(
+            row(20241225),
+            [
+                "2024-12-25",
+                "2024",
+                "4",
+                "12",
+                "25",
+                "3",
+                "0",
+                "2025",
+                "2",
+                "6",
+                "1",
+                "Christmas Day"
+            ]
+        );
+        assert_eq!(row(20241229)[6], "1");
+
+        // ISO week 1 of 2025 starts on Monday 2024-12-30
+        let week: i64 = conn
+            .query_row(
+                "SELECT week FROM dim_date WHERE date_id = 20241231",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(week, 1);
+    }
+
+    #[test]
+    fn test_from_config_registers_entity() {
+        let config = DateConfig::new()
+            .with_role(DimensionRole::new(
+                "order_date",
+                "order_date_id",
+                "calendar",
+                "calendar_key",
+            ))
+            .with_grain_columns(GrainColumns::new("cal_year").with_month("cal_month"));
+        assert!(
+            DateDimension::from_config(&DateConfig::new(), "t", "2024-01-01", "2024-01-31")
+                .is_none()
+        );
+
+        let dates = DateDimension::from_config(
+            &config,
+            "analytics.dim_calendar",
+            "2024-01-01",
+            "2024-12-31",
+        )
+        .unwrap();
+        let mut model = Model::new();
+        dates.register(&mut model);
+
+        let entity = model.get_source("calendar").unwrap();
+        assert_eq!(entity.table, "analytics.dim_calendar");
+        assert_eq!(entity.primary_key, vec!["calendar_key"]);
+        assert_eq!(
+            entity.get_column("calendar_key").unwrap().data_type,
+            DataType::Int32
+        );
+        assert_eq!(
+            entity.get_column("cal_year").unwrap().data_type,
+            DataType::Int32
+        );
+        assert!(entity.get_column("cal_month").is_some());
+        assert!(entity.get_column("quarter").is_some());
+        assert_eq!(entity.get_column("date").unwrap().data_type, DataType::Date);
+        assert_eq!(
+            entity.get_column("is_weekend").unwrap().data_type,
+            DataType::Bool
+        );
+        assert_eq!(entity.columns.len(), dates.columns().len());
+
+        let sql = dates
+            .create_table(Dialect::Postgres)
+            .unwrap()
+            .to_sql(Dialect::Postgres);
+        assert!(sql.starts_with("CREATE TABLE \"analytics\".\"dim_calendar\" AS WITH"));
+        assert!(sql.contains("AS \"calendar_key\""));
+        assert!(sql.contains("\"calendar\".\"month\" AS \"cal_month\""));
+    }
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::types::MaterializationStrategy;
+use super::types::{MaterializationStrategy, TableOptions};
 
 /// Default value for `materialized` field in serde deserialization.
 fn default_materialized() -> bool {
@@ -48,6 +48,22 @@ pub struct DimensionDefinition {
 
     /// How to materialize this dimension
     pub materialization: MaterializationStrategy,
+
+    /// Partitioning, clustering and distribution of the target table.
+    #[serde(default, skip_serializing_if = "TableOptions::is_empty")]
+    pub table_options: TableOptions,
+
+    /// Named drill paths through the dimension's columns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hierarchies: Vec<Hierarchy>,
+
+    /// Generated key replacing the natural primary key in fact joins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surrogate_key: Option<SurrogateKey>,
+
+    /// Row that facts with NULL or orphan keys point at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unknown_member: Option<UnknownMember>,
 }
 
 /// A column in a dimension table.
@@ -63,6 +79,126 @@ pub struct DimensionColumn {
     pub description: Option<String>,
 }
 
+/// A named drill path through a dimension, from the coarsest level down.
+///
+/// Each level is a dimension column that functionally determines the level
+/// above it: every city lies in one region, every region in one country.
+///
+/// # Example
+///
+/// ```ignore
+/// Hierarchy::new("geography", vec!["country", "region", "city"])
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hierarchy {
+    /// Hierarchy name (e.g., "geography")
+    pub name: String,
+
+    /// Level columns, coarsest first
+    pub levels: Vec<String>,
+}
+
+/// A generated key column for a dimension.
+///
+/// The natural key (the dimension's primary key) stays in the table; facts
+/// store the surrogate instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurrogateKey {
+    /// Surrogate key column (e.g., "customer_sk")
+    pub column: String,
+
+    /// How key values are generated
+    pub strategy: SurrogateKeyStrategy,
+}
+
+/// How surrogate key values are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SurrogateKeyStrategy {
+    /// Sequential integers, assigned in natural key order on each build
+    Identity,
+    /// Hash of the natural key, stable across rebuilds
+    Hash,
+}
+
+impl std::fmt::Display for SurrogateKeyStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SurrogateKeyStrategy::Identity => write!(f, "identity"),
+            SurrogateKeyStrategy::Hash => write!(f, "hash"),
+        }
+    }
+}
+
+impl std::str::FromStr for SurrogateKeyStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "identity" => Ok(SurrogateKeyStrategy::Identity),
+            "hash" => Ok(SurrogateKeyStrategy::Hash),
+            other => Err(format!(
+                "Unknown surrogate key strategy '{}': expected 'identity' or 'hash'",
+                other
+            )),
+        }
+    }
+}
+
+/// The "unknown" member of a dimension.
+///
+/// Fact builds point NULL and unmatched foreign keys at this row, so joins
+/// from facts to the dimension never lose rows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnknownMember {
+    /// Key of the member row (surrogate key, or natural key without one)
+    pub key: i64,
+
+    /// Value of the member's text columns
+    pub label: String,
+
+    /// Per-column values overriding the label, by target column name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<(String, String)>,
+}
+
+impl Default for UnknownMember {
+    fn default() -> Self {
+        Self {
+            key: -1,
+            label: "Unknown".into(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl UnknownMember {
+    /// Set the member's key.
+    pub fn with_key(mut self, key: i64) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Set the value of the member's text columns.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Give `column` its own value.
+    pub fn with_value(mut self, column: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.push((column.into(), value.into()));
+        self
+    }
+
+    /// The member's value for a text column.
+    pub fn value(&self, column: &str) -> &str {
+        self.values
+            .iter()
+            .find(|(c, _)| c == column)
+            .map_or(self.label.as_str(), |(_, v)| v.as_str())
+    }
+}
+
 /// Slowly Changing Dimension strategy.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[derive(Default)]
@@ -122,6 +258,10 @@ impl DimensionDefinition {
             primary_key: vec![],
             scd_type: SCDType::default(),
             materialization: MaterializationStrategy::default(),
+            table_options: TableOptions::default(),
+            hierarchies: vec![],
+            surrogate_key: None,
+            unknown_member: None,
         }
     }
 
@@ -193,6 +333,58 @@ impl DimensionDefinition {
         self
     }
 
+    /// Set the physical table options.
+    pub fn with_table_options(mut self, options: TableOptions) -> Self {
+        self.table_options = options;
+        self
+    }
+
+    /// Add a hierarchy with levels listed coarsest first.
+    pub fn with_hierarchy(mut self, name: impl Into<String>, levels: Vec<impl Into<String>>) -> Self {
+        self.hierarchies.push(Hierarchy::new(name, levels));
+        self
+    }
+
+    /// Generate a surrogate key in `column`.
+    pub fn with_surrogate_key(
+        mut self,
+        column: impl Into<String>,
+        strategy: SurrogateKeyStrategy,
+    ) -> Self {
+        self.surrogate_key = Some(SurrogateKey {
+            column: column.into(),
+            strategy,
+        });
+        self
+    }
+
+    /// Add an unknown member row.
+    pub fn with_unknown_member(mut self, member: UnknownMember) -> Self {
+        self.unknown_member = Some(member);
+        self
+    }
+
+    /// The column facts join on: the surrogate key, or the first natural key column.
+    pub fn join_key(&self) -> Option<&str> {
+        match &self.surrogate_key {
+            Some(key) => Some(key.column.as_str()),
+            None => self.primary_key.first().map(String::as_str),
+        }
+    }
+
+    /// Look up a hierarchy by name.
+    pub fn hierarchy(&self, name: &str) -> Option<&Hierarchy> {
+        self.hierarchies.iter().find(|h| h.name == name)
+    }
+
+    /// The source column behind a dimension column, if the dimension has it.
+    pub fn source_column(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|c| c.target_name() == column)
+            .map(|c| c.source_column.as_str())
+    }
+
     /// Get the fully qualified target table name.
     pub fn qualified_target_name(&self) -> String {
         match &self.target_schema {
@@ -202,6 +394,64 @@ impl DimensionDefinition {
     }
 }
 
+impl SCDType {
+    /// The `(effective_from, effective_to)` columns of a versioned dimension.
+    ///
+    /// Returns None for types that keep one row per key.
+    pub fn effective_range(&self) -> Option<(&str, &str)> {
+        match self {
+            SCDType::Type2 {
+                effective_from,
+                effective_to,
+                ..
+            }
+            | SCDType::Type6 {
+                effective_from,
+                effective_to,
+                ..
+            } => Some((effective_from, effective_to)),
+            SCDType::Type0 | SCDType::Type1 | SCDType::Type3 { .. } => None,
+        }
+    }
+}
+
+impl Hierarchy {
+    /// Create a hierarchy with levels listed coarsest first.
+    pub fn new(name: impl Into<String>, levels: Vec<impl Into<String>>) -> Self {
+        Self {
+            name: name.into(),
+            levels: levels.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Position of a level column, 0 being the coarsest.
+    pub fn level_index(&self, column: &str) -> Option<usize> {
+        self.levels.iter().position(|l| l == column)
+    }
+
+    /// The next coarser level above `column`.
+    pub fn parent(&self, column: &str) -> Option<&str> {
+        let index = self.level_index(column)?;
+        index.checked_sub(1).map(|i| self.levels[i].as_str())
+    }
+
+    /// The next finer level below `column`.
+    pub fn child(&self, column: &str) -> Option<&str> {
+        let index = self.level_index(column)?;
+        self.levels.get(index + 1).map(String::as_str)
+    }
+
+    /// `(child, parent)` level pairs, finest first.
+    ///
+    /// Each pair is a functional dependency the data must satisfy.
+    pub fn dependencies(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.levels
+            .windows(2)
+            .rev()
+            .map(|pair| (pair[1].as_str(), pair[0].as_str()))
+    }
+}
+
 impl DimensionColumn {
     /// Get the target column name (custom or derived from source).
     pub fn target_name(&self) -> &str {
@@ -238,6 +488,12 @@ mod tests {
                 is_current: Some("is_current".into()),
             });
 
+        assert_eq!(
+            dim.scd_type.effective_range(),
+            Some(("valid_from", "valid_to"))
+        );
+        assert_eq!(SCDType::Type1.effective_range(), None);
+
         match dim.scd_type {
             SCDType::Type2 { effective_from, effective_to, is_current } => {
                 assert_eq!(effective_from, "valid_from");
@@ -258,6 +514,29 @@ mod tests {
         assert_eq!(dim.columns[1].target_name(), "customer_name");
     }
 
+    #[test]
+    fn test_hierarchy_levels() {
+        let dim = DimensionDefinition::new("dim_stores", "dim_stores", "stores")
+            .with_column("country")
+            .with_column_as("region_name", "region")
+            .with_column("city")
+            .with_hierarchy("geography", vec!["country", "region", "city"]);
+
+        let geography = dim.hierarchy("geography").unwrap();
+        assert!(dim.hierarchy("calendar").is_none());
+        assert_eq!(geography.level_index("region"), Some(1));
+        assert_eq!(geography.parent("region"), Some("country"));
+        assert_eq!(geography.parent("country"), None);
+        assert_eq!(geography.child("region"), Some("city"));
+        assert_eq!(geography.child("city"), None);
+        assert_eq!(
+            geography.dependencies().collect::<Vec<_>>(),
+            vec![("city", "region"), ("region", "country")]
+        );
+        assert_eq!(dim.source_column("region"), Some("region_name"));
+        assert_eq!(dim.source_column("region_name"), None);
+    }
+
     #[test]
     fn test_scd_default() {
         assert_eq!(SCDType::default(), SCDType::Type1);
@@ -0,0 +1,612 @@
+//! Dimension and fact build SQL for surrogate keys and unknown members.
+//!
+//! [`DimensionBuild`] selects a dimension's columns from its source entity,
+//! adds the [`SurrogateKey`](super::SurrogateKey) when one is configured and
+//! appends the [`UnknownMember`] row. Fact builds look their dimension keys
+//! up through [`DimensionBuild::fact_join`] and [`DimensionBuild::fact_key`],
+//! which LEFT JOIN the dimension on its natural key and fall back to the
+//! unknown member's key, so NULL and orphan foreign keys keep their rows.
+//! The query planner relies on this to inner-join materialized facts to a
+//! dimension with an unknown member on its surrogate key; joins on the
+//! natural key keep the relationship's join type.
+//!
+//! Identity keys are `ROW_NUMBER()` over the natural key and are reassigned
+//! on every build, so facts must be rebuilt with their dimensions. Hash keys
+//! are the hex MD5 of the natural key columns joined by `|` and stay stable;
+//! SQLite has no MD5 function and only supports identity keys.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let build = DimensionBuild::from_model(&model, "dim_customers")?;
+//! let ddl = build.create_table(Dialect::Snowflake)?.to_sql(Dialect::Snowflake);
+//!
+//! let (table, on) = build.fact_join("orders", &["customer_id"], "dim_customers")?;
+//! let fact = Query::new()
+//!     .select(vec![build.fact_key("dim_customers")?.alias("customer_sk")])
+//!     .from(TableRef::new("orders"))
+//!     .left_join(table, on);
+//! ```
+
+use thiserror::Error;
+
+use super::dimension::{DimensionDefinition, SurrogateKeyStrategy, UnknownMember};
+use super::source::SourceEntity;
+use super::types::DataType;
+use super::Model;
+use crate::sql::ddl::{CreateTable, DdlError};
+use crate::sql::dialect::{Dialect, DialectTarget};
+use crate::sql::expr::{
+    cast, coalesce, func, lit_int, lit_null, lit_str, raw_sql, row_number, table_col, Expr,
+    ExprExt, WindowExt, WindowOrderBy,
+};
+use crate::sql::query::{Query, SelectExpr, TableRef};
+
+/// Alias of the source entity in the dimension query.
+const SOURCE: &str = "source";
+
+/// Errors from generating dimension build SQL.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DimensionBuildError {
+    #[error("unknown dimension '{0}'")]
+    UnknownDimension(String),
+
+    #[error("dimension '{dimension}' is built from '{source_entity}', which is not a source")]
+    UnknownSource {
+        dimension: String,
+        source_entity: String,
+    },
+
+    #[error("dimension '{dimension}' column '{column}' is not in its source")]
+    UnknownColumn { dimension: String, column: String },
+
+    #[error("dimension '{0}' needs a primary key to generate keys from")]
+    MissingNaturalKey(String),
+
+    #[error("fact key for dimension '{dimension}' needs {expected} column(s), got {actual}")]
+    KeyArity {
+        dimension: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("hash surrogate keys are not supported on {0}")]
+    UnsupportedHash(Dialect),
+
+    #[error(
+        "dimension '{dimension}' unknown member key cannot be stored in {column} ({data_type:?})"
+    )]
+    UnknownMemberKeyType {
+        dimension: String,
+        column: String,
+        data_type: DataType,
+    },
+
+    #[error("invalid table options: {0}")]
+    TableOptions(#[from] DdlError),
+}
+
+/// Build SQL for one dimension read from its source entity.
+#[derive(Debug, Clone, Copy)]
+pub struct DimensionBuild<'a> {
+    dimension: &'a DimensionDefinition,
+    source: &'a SourceEntity,
+}
+
+impl<'a> DimensionBuild<'a> {
+    /// Build `dimension` from `source`.
+    pub fn new(dimension: &'a DimensionDefinition, source: &'a SourceEntity) -> Self {
+        Self { dimension, source }
+    }
+
+    /// Build the dimension named `name` from its source entity in `model`.
+    pub fn from_model(model: &'a Model, name: &str) -> Result<Self, DimensionBuildError> {
+        let dimension = model
+            .dimensions
+            .get(name)
+            .ok_or_else(|| DimensionBuildError::UnknownDimension(name.to_string()))?;
+        let source = model.sources.get(&dimension.source_entity).ok_or_else(|| {
+            DimensionBuildError::UnknownSource {
+                dimension: dimension.name.clone(),
+                source_entity: dimension.source_entity.clone(),
+            }
+        })?;
+        Ok(Self::new(dimension, source))
+    }
+
+    /// The SELECT producing the dimension's rows, unknown member last.
+    pub fn query(&self, dialect: impl Into<DialectTarget>) -> Result<Query, DimensionBuildError> {
+        let dialect = dialect.into();
+        let mut select: Vec<SelectExpr> = Vec::new();
+        if let Some(key) = &self.dimension.surrogate_key {
+            select.push(
+                self.surrogate_key(key.strategy, dialect)?
+                    .alias(&key.column),
+            );
+        }
+        for column in &self.dimension.columns {
+            if self.source.get_column(&column.source_column).is_none() {
+                return Err(DimensionBuildError::UnknownColumn {
+                    dimension: self.dimension.name.clone(),
+                    column: column.source_column.clone(),
+                });
+            }
+            select.push(table_col(SOURCE, &column.source_column).alias(column.target_name()));
+        }
+
+        let rows = Query::new()
+            .select(select)
+            .from(physical_table(&self.source.table).with_alias(SOURCE));
+        Ok(match &self.dimension.unknown_member {
+            Some(member) => Query {
+                set_op: Some(Box::new(rows.union_all(self.unknown_member_row(member)?))),
+                ..Default::default()
+            },
+            None => rows,
+        })
+    }
+
+    /// `CREATE TABLE ... AS SELECT` for the dimension, with its table
+    /// options and column descriptions.
+    ///
+    /// Columns without a description take their source column's.
+    pub fn create_table(
+        &self,
+        dialect: impl Into<DialectTarget>,
+    ) -> Result<CreateTable, DimensionBuildError> {
+        let dialect = dialect.into();
+        let query = self.query(dialect)?;
+        let table = match &self.dimension.target_schema {
+            Some(schema) => CreateTable::new(&self.dimension.target_table).schema(schema),
+            None => match self.dimension.target_table.rsplit_once('.') {
+                Some((schema, table)) => CreateTable::new(table).schema(schema),
+                None => CreateTable::new(&self.dimension.target_table),
+            },
+        };
+        let comments = self.dimension.columns.iter().filter_map(|column| {
+            let description = column.description.clone().or_else(|| {
+                self.source
+                    .get_column(&column.source_column)
+                    .and_then(|c| c.description.clone())
+            })?;
+            Some((column.target_name().to_string(), description))
+        });
+        let table = table
+            .as_select(query)
+            .options(self.dimension.table_options.clone())
+            .column_comments(comments);
+        table.validate(dialect)?;
+        Ok(table)
+    }
+
+    /// The dimension table joined onto a fact build as `alias`, matching
+    /// `fact_columns` of `fact_alias` to the natural key in order.
+    ///
+    /// LEFT JOIN it so facts without a match keep their rows.
+    pub fn fact_join(
+        &self,
+        fact_alias: &str,
+        fact_columns: &[&str],
+        alias: &str,
+    ) -> Result<(TableRef, Expr), DimensionBuildError> {
+        let natural_key = self.natural_key()?;
+        if natural_key.len() != fact_columns.len() {
+            return Err(DimensionBuildError::KeyArity {
+                dimension: self.dimension.name.clone(),
+                expected: natural_key.len(),
+                actual: fact_columns.len(),
+            });
+        }
+        let on = fact_columns
+            .iter()
+            .zip(&natural_key)
+            .map(|(fact_column, key)| table_col(fact_alias, fact_column).eq(table_col(alias, key)))
+            .reduce(|a, b| a.and(b))
+            .expect("natural key is not empty");
+        let table = physical_table(&self.dimension.qualified_target_name()).with_alias(alias);
+        Ok((table, on))
+    }
+
+    /// The key a fact row stores for the dimension joined as `alias`: the
+    /// matched row's key, or the unknown member's when nothing matched.
+    pub fn fact_key(&self, alias: &str) -> Result<Expr, DimensionBuildError> {
+        let column = self
+            .dimension
+            .join_key()
+            .ok_or_else(|| DimensionBuildError::MissingNaturalKey(self.dimension.name.clone()))?;
+        let matched = table_col(alias, self.target_column(column));
+        Ok(match &self.dimension.unknown_member {
+            Some(member) => coalesce(vec![matched, self.member_key(member)?]),
+            None => matched,
+        })
+    }
+
+    /// Target names of the natural key columns.
+    fn natural_key(&self) -> Result<Vec<&str>, DimensionBuildError> {
+        if self.dimension.primary_key.is_empty() {
+            return Err(DimensionBuildError::MissingNaturalKey(
+                self.dimension.name.clone(),
+            ));
+        }
+        Ok(self
+            .dimension
+            .primary_key
+            .iter()
+            .map(|key| self.target_column(key))
+            .collect())
+    }
+
+    /// Target name of a dimension column given by source or target name.
+    fn target_column<'b>(&'b self, column: &'b str) -> &'b str {
+        self.dimension
+            .columns
+            .iter()
+            .find(|c| c.source_column == column)
+            .map_or(column, |c| c.target_name())
+    }
+
+    /// Source name of a natural key column.
+    fn source_column<'b>(&'b self, column: &'b str) -> &'b str {
+        self.dimension.source_column(column).unwrap_or(column)
+    }
+
+    /// Surrogate key of each source row.
+    fn surrogate_key(
+        &self,
+        strategy: SurrogateKeyStrategy,
+        dialect: DialectTarget,
+    ) -> Result<Expr, DimensionBuildError> {
+        let key: Vec<Expr> = self
+            .natural_key()?
+            .into_iter()
+            .map(|column| table_col(SOURCE, self.source_column(column)))
+            .collect();
+        match strategy {
+            SurrogateKeyStrategy::Identity => Ok(row_number()
+                .over()
+                .order_by(key.into_iter().map(WindowOrderBy::new).collect())
+                .build()),
+            SurrogateKeyStrategy::Hash => {
+                let text = key
+                    .into_iter()
+                    .map(|column| coalesce(vec![cast(column, DataType::String), lit_str("")]))
+                    .reduce(|a, b| a.concat(lit_str("|")).concat(b))
+                    .unwrap_or_else(|| lit_str(""));
+                md5_hex(text, dialect.dialect)
+            }
+        }
+    }
+
+    /// The unknown member's key, typed like the key column.
+    fn member_key(&self, member: &UnknownMember) -> Result<Expr, DimensionBuildError> {
+        match self.dimension.surrogate_key.as_ref().map(|k| k.strategy) {
+            Some(SurrogateKeyStrategy::Hash) => Ok(lit_str(&member.key.to_string())),
+            Some(SurrogateKeyStrategy::Identity) => Ok(lit_int(member.key)),
+            // Without a surrogate key the member takes the natural key
+            None => {
+                let column = self.dimension.join_key().ok_or_else(|| {
+                    DimensionBuildError::MissingNaturalKey(self.dimension.name.clone())
+                })?;
+                let source_column = self.source_column(column);
+                match self.source.get_column(source_column).map(|c| &c.data_type) {
+                    Some(DataType::String | DataType::Varchar(_) | DataType::Char(_)) => {
+                        Ok(lit_str(&member.key.to_string()))
+                    }
+                    Some(
+                        DataType::Int8
+                        | DataType::Int16
+                        | DataType::Int32
+                        | DataType::Int64
+                        | DataType::Float32
+                        | DataType::Float64
+                        | DataType::Decimal(..),
+                    )
+                    | None => Ok(lit_int(member.key)),
+                    Some(data_type) => Err(DimensionBuildError::UnknownMemberKeyType {
+                        dimension: self.dimension.name.clone(),
+                        column: self.target_column(column).to_string(),
+                        data_type: data_type.clone(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// The unknown member row: its key, the label in text columns and NULL
+    /// elsewhere.
+    fn unknown_member_row(&self, member: &UnknownMember) -> Result<Query, DimensionBuildError> {
+        let mut select: Vec<SelectExpr> = Vec::new();
+        if let Some(key) = &self.dimension.surrogate_key {
+            select.push(self.member_key(member)?.alias(&key.column));
+        }
+        // Without a surrogate key the member takes the natural key
+        let member_key_column = match &self.dimension.surrogate_key {
+            Some(_) => None,
+            None => self.dimension.join_key(),
+        };
+        for column in &self.dimension.columns {
+            let data_type = self
+                .source
+                .get_column(&column.source_column)
+                .map(|c| &c.data_type);
+            let value = if member_key_column == Some(column.source_column.as_str())
+                || member_key_column == Some(column.target_name())
+            {
+                self.member_key(member)?
+            } else if self
+                .dimension
+                .primary_key
+                .iter()
+                .any(|k| k == &column.source_column || k == column.target_name())
+            {
+                lit_null()
+            } else if matches!(
+                data_type,
+                Some(DataType::String | DataType::Varchar(_) | DataType::Char(_))
+            ) {
+                lit_str(member.value(column.target_name()))
+            } else {
+                lit_null()
+            };
+            select.push(value.alias(column.target_name()));
+        }
+        Ok(Query::new().select(select))
+    }
+}
+
+/// Hex MD5 of a text expression.
+fn md5_hex(text: Expr, dialect: Dialect) -> Result<Expr, DimensionBuildError> {
+    Ok(match dialect {
+        Dialect::BigQuery => func("TO_HEX", vec![func("MD5", vec![text])]),
+        Dialect::ClickHouse => func("lower", vec![func("hex", vec![func("MD5", vec![text])])]),
+        Dialect::Trino => func(
+            "lower",
+            vec![func(
+                "to_hex",
+                vec![func("md5", vec![func("to_utf8", vec![text])])],
+            )],
+        ),
+        Dialect::TSql => func(
+            "LOWER",
+            vec![func(
+                "CONVERT",
+                vec![
+                    raw_sql("VARCHAR(32)"),
+                    func("HASHBYTES", vec![lit_str("MD5"), text]),
+                    lit_int(2),
+                ],
+            )],
+        ),
+        Dialect::Sqlite => return Err(DimensionBuildError::UnsupportedHash(dialect)),
+        _ => func("MD5", vec![text]),
+    })
+}
+
+/// A possibly schema-qualified physical table.
+fn physical_table(table: &str) -> TableRef {
+    match table.rsplit_once('.') {
+        Some((schema, table)) => TableRef::new(table).with_schema(schema),
+        None => TableRef::new(table),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::TableOptions;
+    use crate::sql::test_utils::validate_sql;
+    use rusqlite::Connection;
+
+    fn customers() -> SourceEntity {
+        SourceEntity::new("customers", "raw.customers")
+            .with_required_column("customer_id", DataType::Int64)
+            .with_required_column("name", DataType::String)
+            .with_required_column("segment", DataType::String)
+            .with_required_column("credit_limit", DataType::Int64)
+            .with_primary_key(vec!["customer_id"])
+    }
+
+    fn dim_customers() -> DimensionDefinition {
+        DimensionDefinition::new("dim_customers", "analytics.dim_customers", "customers")
+            .with_columns(vec!["customer_id", "name", "credit_limit"])
+            .with_column_as("segment", "customer_segment")
+            .with_primary_key(vec!["customer_id"])
+            .with_unknown_member(UnknownMember::default().with_value("customer_segment", "n/a"))
+    }
+
+    #[test]
+    fn test_hash_key_parses_in_every_dialect() {
+        let source = customers();
+        let dim = dim_customers().with_surrogate_key("customer_sk", SurrogateKeyStrategy::Hash);
+        let build = DimensionBuild::new(&dim, &source);
+        for dialect in [
+            Dialect::Postgres,
+            Dialect::DuckDb,
+            Dialect::MySql,
+            Dialect::TSql,
+            Dialect::Redshift,
+            Dialect::Databricks,
+            Dialect::Trino,
+        ] {
+            let sql = build.create_table(dialect).unwrap().to_sql(dialect);
+            validate_sql(&sql, dialect).unwrap_or_else(|e| panic!("{:?}: {}\n{}", dialect, e, sql));
+            assert!(sql.contains("UNION ALL"), "{:?}: {}", dialect, sql);
+        }
+
+        let sql = build
+            .query(Dialect::Postgres)
+            .unwrap()
+            .to_sql(Dialect::Postgres);
+        assert!(
+            sql.contains("MD5(COALESCE(CAST(\"source\".\"customer_id\" AS TEXT), ''))"),
+            "{}",
+            sql
+        );
+        assert!(sql.contains("'-1' AS \"customer_sk\""), "{}", sql);
+        assert_eq!(
+            build.query(Dialect::Sqlite).unwrap_err(),
+            DimensionBuildError::UnsupportedHash(Dialect::Sqlite)
+        );
+    }
+
+    #[test]
+    fn test_create_table_applies_options_and_comments() {
+        let mut source = SourceEntity::new("customers", "raw.customers")
+            .with_required_column("customer_id", DataType::Int64)
+            .with_required_column("name", DataType::String)
+            .with_primary_key(vec!["customer_id"]);
+        source.columns.get_mut("name").unwrap().description = Some("Full name".into());
+        let mut dim =
+            DimensionDefinition::new("dim_customers", "analytics.dim_customers", "customers")
+                .with_columns(vec!["customer_id", "name"])
+                .with_primary_key(vec!["customer_id"])
+                .with_table_options(TableOptions::new().with_cluster_by(vec!["customer_id"]));
+        dim.columns[0].description = Some("Customer key".into());
+        let build = DimensionBuild::new(&dim, &source);
+
+        let table = build.create_table(Dialect::Snowflake).unwrap();
+        let sql = table.to_sql(Dialect::Snowflake);
+        assert!(sql.contains("CLUSTER BY (\"customer_id\")"), "{}", sql);
+        let comments = table.comment_statements(Dialect::Snowflake);
+        assert_eq!(comments.len(), 2, "{:?}", comments);
+        assert!(comments[0].contains("'Customer key'"), "{:?}", comments);
+        assert!(comments[1].contains("'Full name'"), "{:?}", comments);
+        for sql in &comments {
+            validate_sql(sql, Dialect::Snowflake).unwrap_or_else(|e| panic!("{}: {}", e, sql));
+        }
+
+        assert_eq!(
+            build.create_table(Dialect::Postgres).unwrap_err(),
+            DimensionBuildError::TableOptions(DdlError::UnsupportedOption {
+                option: "CLUSTER BY",
+                dialect: Dialect::Postgres.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sqlite_unknown_member_catches_unmatched_keys() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "ATTACH ':memory:' AS raw;
+             ATTACH ':memory:' AS analytics;
+             CREATE TABLE raw.customers (customer_id INTEGER, name TEXT, segment TEXT, credit_limit INTEGER);
+             INSERT INTO raw.customers VALUES (1, 'Ann', 'retail', 100), (2, 'Bob', 'b2b', 500);
+             CREATE TABLE orders (order_id INTEGER, customer_id INTEGER);
+             INSERT INTO orders VALUES (1, 1), (2, NULL), (3, 7);",
+        )
+        .unwrap();
+        let source = customers();
+        let dim = dim_customers();
+        let build = DimensionBuild::new(&dim, &source);
+        let ddl = build
+            .create_table(Dialect::Sqlite)
+            .unwrap()
+            .to_sql(Dialect::Sqlite);
+        conn.execute_batch(&ddl).unwrap();
+
+        let member: (i64, String, String, Option<i64>) = conn
+            .query_row(
+                "SELECT customer_id, name, customer_segment, credit_limit \
+                 FROM analytics.dim_customers WHERE customer_id = -1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(member, (-1, "Unknown".into(), "n/a".into(), None));
+
+        // Without a surrogate key, facts keep the natural key or the member's
+        let (table, on) = build.fact_join("orders", &["customer_id"], "dim").unwrap();
+        let fact = Query::new()
+            .select(vec![
+                table_col("orders", "order_id").into(),
+                build.fact_key("dim").unwrap().alias("customer_id"),
+            ])
+            .from(TableRef::new("orders"))
+            .left_join(table, on)
+            .to_sql(Dialect::Sqlite);
+        let mut stmt = conn.prepare(&fact).unwrap();
+        let keys: Vec<(i64, i64)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(keys, vec![(1, 1), (2, -1), (3, -1)]);
+    }
+
+    #[test]
+    fn test_unknown_member_natural_key_type() {
+        let products = SourceEntity::new("products", "raw.products")
+            .with_required_column("sku", DataType::Varchar(20))
+            .with_required_column("name", DataType::String)
+            .with_required_column("launched", DataType::Date);
+        let by_sku = DimensionDefinition::new("dim_products", "dim_products", "products")
+            .with_columns(vec!["sku", "name"])
+            .with_primary_key(vec!["sku"])
+            .with_unknown_member(UnknownMember::default());
+        let build = DimensionBuild::new(&by_sku, &products);
+
+        // Text keys get a text member key, so both UNION branches agree
+        let conn = Connection::open_in_memory().unwrap();
+        let sql = build
+            .query(Dialect::Sqlite)
+            .unwrap()
+            .to_sql(Dialect::Sqlite);
+        assert!(sql.contains("'-1' AS \"sku\""), "{}", sql);
+        validate_sql(&sql, Dialect::Postgres).unwrap();
+        conn.execute_batch(
+            "ATTACH ':memory:' AS raw;
+             CREATE TABLE raw.products (sku TEXT, name TEXT, launched TEXT);",
+        )
+        .unwrap();
+        conn.prepare(&sql).unwrap();
+        let fact = Query::new()
+            .select(vec![build.fact_key("dim").unwrap().alias("sku")])
+            .to_sql(Dialect::Postgres);
+        assert!(fact.contains("COALESCE(\"dim\".\"sku\", '-1')"), "{}", fact);
+
+        let by_date = by_sku
+            .clone()
+            .with_column("launched")
+            .with_primary_key(vec!["launched"]);
+        assert!(matches!(
+            DimensionBuild::new(&by_date, &products).query(Dialect::Postgres),
+            Err(DimensionBuildError::UnknownMemberKeyType { column, .. }) if column == "launched"
+        ));
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let source = customers();
+        let keyless = DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+            .with_columns(vec!["name"])
+            .with_surrogate_key("customer_sk", SurrogateKeyStrategy::Identity);
+        assert_eq!(
+            DimensionBuild::new(&keyless, &source)
+                .query(Dialect::Postgres)
+                .unwrap_err(),
+            DimensionBuildError::MissingNaturalKey("dim_customers".into())
+        );
+
+        let dim = dim_customers().with_column("email");
+        let build = DimensionBuild::new(&dim, &source);
+        assert!(matches!(
+            build.query(Dialect::Postgres),
+            Err(DimensionBuildError::UnknownColumn { column, .. }) if column == "email"
+        ));
+        assert!(matches!(
+            build.fact_join("orders", &["customer_id", "region"], "dim"),
+            Err(DimensionBuildError::KeyArity {
+                expected: 1,
+                actual: 2,
+                ..
+            })
+        ));
+
+        let model = Model::new().with_dimension(dim_customers());
+        assert!(matches!(
+            DimensionBuild::from_model(&model, "dim_customers"),
+            Err(DimensionBuildError::UnknownSource { .. })
+        ));
+    }
+}
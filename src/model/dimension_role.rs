@@ -46,6 +46,13 @@ pub struct DimensionRole {
 
     /// The PK column on the dimension (e.g., "date_id").
     pub pk_column: String,
+
+    /// The calendar date column on the dimension (e.g., "full_date").
+    ///
+    /// Needed to place a fact row in time when `pk_column` is a surrogate
+    /// key rather than the date itself, e.g. to pick SCD Type 2 versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_column: Option<String>,
 }
 
 impl DimensionRole {
@@ -61,8 +68,15 @@ impl DimensionRole {
             fk_column: fk_column.into(),
             dimension: dimension.into(),
             pk_column: pk_column.into(),
+            date_column: None,
         }
     }
+
+    /// Set the calendar date column on the dimension.
+    pub fn with_date_column(mut self, column: impl Into<String>) -> Self {
+        self.date_column = Some(column.into());
+        self
+    }
 }
 
 /// Date dimension configuration for a fact.
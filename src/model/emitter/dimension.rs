@@ -0,0 +1,204 @@
+//! DimensionDefinition → Lua emission.
+
+use super::fact::string_list;
+use super::format::{quote_identifier, quote_string, IndentWriter};
+use super::EmitConfig;
+use crate::model::dimension::{SCDType, UnknownMember};
+use crate::model::types::MaterializationStrategy;
+use crate::model::DimensionDefinition;
+
+/// Emit a DimensionDefinition to Lua using chained syntax.
+/// Example output:
+/// ```lua
+/// dimension("dim_customers")
+///     :target("analytics.dim_customers")
+///     :from("customers")
+///     :columns({ "customer_id", "name" })
+///     :primary_key({ "customer_id" })
+/// ```
+pub fn emit_dimension(w: &mut IndentWriter, dim: &DimensionDefinition, config: &EmitConfig) {
+    if config.include_comments {
+        w.write_comment(&format!(
+            "Dimension: {} (source: {})",
+            dim.name, dim.source_entity
+        ));
+    }
+
+    w.write_line(&format!("dimension({})", quote_string(&dim.name)));
+    w.indent();
+
+    w.write_line(&format!(
+        ":target({})",
+        quote_string(&dim.qualified_target_name())
+    ));
+    w.write_line(&format!(":from({})", quote_string(&dim.source_entity)));
+
+    if !dim.columns.is_empty() {
+        let columns: Vec<String> = dim
+            .columns
+            .iter()
+            .map(|c| c.source_column.clone())
+            .collect();
+        w.write_line(&format!(":columns({})", string_list(&columns)));
+    }
+
+    if !dim.primary_key.is_empty() {
+        w.write_line(&format!(":primary_key({})", string_list(&dim.primary_key)));
+    }
+
+    for hierarchy in &dim.hierarchies {
+        w.write_line(&format!(
+            ":hierarchy({}, {})",
+            quote_string(&hierarchy.name),
+            string_list(&hierarchy.levels)
+        ));
+    }
+
+    if let Some(key) = &dim.surrogate_key {
+        w.write_line(&format!(
+            ":surrogate_key({}, {})",
+            quote_string(&key.column),
+            quote_string(&key.strategy.to_string())
+        ));
+    }
+
+    if let Some(member) = &dim.unknown_member {
+        w.write_line(&format!(
+            ":unknown_member({})",
+            unknown_member_table(member)
+        ));
+    }
+
+    match &dim.scd_type {
+        SCDType::Type0 => w.write_line(":scd({ type = SCD0 })"),
+        SCDType::Type1 => {}
+        SCDType::Type2 {
+            effective_from,
+            effective_to,
+            is_current,
+        } => {
+            let mut line = format!(
+                ":scd({{ type = SCD2, effective_from = {}, effective_to = {}",
+                quote_string(effective_from),
+                quote_string(effective_to)
+            );
+            if let Some(is_current) = is_current {
+                line.push_str(&format!(", is_current = {}", quote_string(is_current)));
+            }
+            line.push_str(" })");
+            w.write_line(&line);
+        }
+        SCDType::Type3 { .. } | SCDType::Type6 { .. } => {
+            if config.include_comments {
+                w.write_comment("TODO: SCD type 3/6 settings are not emitted");
+            }
+        }
+    }
+
+    if dim.materialization == MaterializationStrategy::View {
+        w.write_line(":table_type(VIEW)");
+    }
+
+    if !dim.materialized {
+        w.write_line(":materialized(false)");
+    }
+
+    w.dedent();
+}
+
+/// The `:unknown_member()` argument, leaving out defaults.
+fn unknown_member_table(member: &UnknownMember) -> String {
+    let defaults = UnknownMember::default();
+    let mut fields = Vec::new();
+    if member.key != defaults.key {
+        fields.push(format!("key = {}", member.key));
+    }
+    if member.label != defaults.label {
+        fields.push(format!("label = {}", quote_string(&member.label)));
+    }
+    if !member.values.is_empty() {
+        let values: Vec<String> = member
+            .values
+            .iter()
+            .map(|(column, value)| {
+                format!("{} = {}", quote_identifier(column), quote_string(value))
+            })
+            .collect();
+        fields.push(format!("values = {{ {} }}", values.join(", ")));
+    }
+    if fields.is_empty() {
+        String::new()
+    } else {
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::emitter::format::Indent;
+
+    #[test]
+    fn test_emit_dimension_scd2() {
+        let dim = DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+            .with_schema("analytics")
+            .with_columns(vec!["customer_id", "name", "valid_from", "valid_to"])
+            .with_primary_key(vec!["customer_id"])
+            .with_scd_type(SCDType::Type2 {
+                effective_from: "valid_from".into(),
+                effective_to: "valid_to".into(),
+                is_current: None,
+            })
+            .with_materialization(MaterializationStrategy::Table);
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_dimension(&mut w, &dim, &EmitConfig::minimal());
+        let output = w.into_string();
+
+        assert!(output.starts_with("dimension(\"dim_customers\")\n"));
+        assert!(output.contains("\t:target(\"analytics.dim_customers\")\n"));
+        assert!(output.contains("\t:from(\"customers\")\n"));
+        assert!(output.contains(":primary_key({ \"customer_id\" })"));
+        assert!(output.contains(
+            ":scd({ type = SCD2, effective_from = \"valid_from\", effective_to = \"valid_to\" })"
+        ));
+        assert!(!output.contains(":table_type"));
+    }
+
+    #[test]
+    fn test_emit_dimension_hierarchy() {
+        let dim = DimensionDefinition::new("dim_dates", "dim_dates", "dates")
+            .with_columns(vec!["date_key", "year", "quarter", "month"])
+            .with_hierarchy("calendar", vec!["year", "quarter", "month"]);
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_dimension(&mut w, &dim, &EmitConfig::minimal());
+
+        assert!(w
+            .into_string()
+            .contains("\t:hierarchy(\"calendar\", { \"year\", \"quarter\", \"month\" })\n"));
+    }
+
+    #[test]
+    fn test_emit_dimension_surrogate_key_and_unknown_member() {
+        use crate::model::dimension::SurrogateKeyStrategy;
+
+        let dim = DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+            .with_columns(vec!["customer_id", "segment"])
+            .with_primary_key(vec!["customer_id"])
+            .with_surrogate_key("customer_sk", SurrogateKeyStrategy::Hash)
+            .with_unknown_member(UnknownMember::default().with_value("segment", "n/a"));
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_dimension(&mut w, &dim, &EmitConfig::minimal());
+        let output = w.into_string();
+
+        assert!(output.contains("\t:surrogate_key(\"customer_sk\", \"hash\")\n"));
+        assert!(output.contains("\t:unknown_member({ values = { segment = \"n/a\" } })\n"));
+        assert_eq!(
+            unknown_member_table(&UnknownMember::default().with_key(0).with_label("?")),
+            "{ key = 0, label = \"?\" }"
+        );
+        assert_eq!(unknown_member_table(&UnknownMember::default()), "");
+    }
+}
@@ -0,0 +1,166 @@
+//! FactDefinition → Lua emission.
+
+use super::format::{quote_string, IndentWriter};
+use super::EmitConfig;
+use crate::model::fact::ColumnSelection;
+use crate::model::types::{AggregationType, MaterializationStrategy};
+use crate::model::FactDefinition;
+
+/// Lua constructor for a measure, e.g. `sum("total")` or `count()`.
+fn measure_to_lua(aggregation: &AggregationType, column: &str) -> String {
+    let func = match aggregation {
+        AggregationType::Sum => "sum",
+        AggregationType::Count => "count",
+        AggregationType::CountDistinct => "count_distinct",
+        AggregationType::Avg => "avg",
+        AggregationType::Min => "min",
+        AggregationType::Max => "max",
+    };
+    if *aggregation == AggregationType::Count && column == "*" {
+        "count()".to_string()
+    } else {
+        format!("{}({})", func, quote_string(column))
+    }
+}
+
+/// Lua list of quoted strings, e.g. `{ "a", "b" }`.
+pub(super) fn string_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|s| quote_string(s)).collect();
+    format!("{{ {} }}", quoted.join(", "))
+}
+
+/// Emit a FactDefinition to Lua using chained syntax.
+/// Example output:
+/// ```lua
+/// fact("fact_orders")
+///     :target("analytics.fact_orders")
+///     :grain({ "orders.order_id" })
+///     :include("customers", ALL)
+///     :measure("revenue", sum("total"))
+/// ```
+pub fn emit_fact(w: &mut IndentWriter, fact: &FactDefinition, config: &EmitConfig) {
+    if config.include_comments {
+        w.write_comment(&format!("Fact: {}", fact.name));
+    }
+
+    w.write_line(&format!("fact({})", quote_string(&fact.name)));
+    w.indent();
+
+    w.write_line(&format!(
+        ":target({})",
+        quote_string(&fact.qualified_target_name())
+    ));
+
+    if let Some(from) = &fact.from {
+        w.write_line(&format!(":from({})", quote_string(from)));
+    }
+
+    if !fact.grain.is_empty() {
+        let grain: Vec<String> = fact
+            .grain
+            .iter()
+            .map(|g| format!("{}.{}", g.source_entity, g.source_column))
+            .collect();
+        w.write_line(&format!(":grain({})", string_list(&grain)));
+    }
+
+    // Sort for consistent output
+    let mut includes: Vec<_> = fact.includes.values().collect();
+    includes.sort_by(|a, b| a.entity.cmp(&b.entity));
+    for include in includes {
+        let selection = match &include.selection {
+            ColumnSelection::Columns(columns) => string_list(columns),
+            ColumnSelection::All => "ALL".to_string(),
+            ColumnSelection::Except(except) => {
+                if config.include_comments {
+                    w.write_comment(&format!("TODO: exclude {}", except.join(", ")));
+                }
+                "ALL".to_string()
+            }
+        };
+        w.write_line(&format!(
+            ":include({}, {})",
+            quote_string(&include.entity),
+            selection
+        ));
+    }
+
+    let mut measures: Vec<_> = fact.measures.values().collect();
+    measures.sort_by(|a, b| a.name.cmp(&b.name));
+    for measure in measures {
+        if measure.filter.is_some() && config.include_comments {
+            w.write_comment(&format!("TODO: filter for measure '{}'", measure.name));
+        }
+        w.write_line(&format!(
+            ":measure({}, {})",
+            quote_string(&measure.name),
+            measure_to_lua(&measure.aggregation, &measure.source_column)
+        ));
+    }
+
+    match &fact.materialization {
+        MaterializationStrategy::View => w.write_line(":table_type(VIEW)"),
+        MaterializationStrategy::Incremental {
+            unique_key,
+            incremental_key,
+            ..
+        } => {
+            let mut line = format!(":incremental({{ key = {}", quote_string(incremental_key));
+            if !unique_key.is_empty() {
+                line.push_str(&format!(", unique_key = {}", string_list(unique_key)));
+            }
+            line.push_str(" })");
+            w.write_line(&line);
+        }
+        MaterializationStrategy::Table | MaterializationStrategy::Snapshot { .. } => {}
+    }
+
+    if !fact.materialized {
+        w.write_line(":materialized(false)");
+    }
+
+    w.dedent();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::emitter::format::Indent;
+
+    #[test]
+    fn test_measure_to_lua() {
+        assert_eq!(
+            measure_to_lua(&AggregationType::Sum, "total"),
+            "sum(\"total\")"
+        );
+        assert_eq!(measure_to_lua(&AggregationType::Count, "*"), "count()");
+        assert_eq!(
+            measure_to_lua(&AggregationType::CountDistinct, "customer_id"),
+            "count_distinct(\"customer_id\")"
+        );
+    }
+
+    #[test]
+    fn test_emit_fact() {
+        let fact = FactDefinition::new("fact_orders", "analytics.fact_orders")
+            .with_grain("orders", "order_id")
+            .include("customers", vec!["name", "region"])
+            .with_sum("revenue", "total")
+            .with_count_star("order_count")
+            .with_materialization(MaterializationStrategy::Table);
+
+        let mut w = IndentWriter::new(Indent::Spaces(4));
+        emit_fact(&mut w, &fact, &EmitConfig::minimal());
+        let output = w.into_string();
+
+        assert_eq!(
+            output,
+            "fact(\"fact_orders\")\n\
+             \x20   :target(\"analytics.fact_orders\")\n\
+             \x20   :grain({ \"orders.order_id\" })\n\
+             \x20   :include(\"customers\", { \"name\", \"region\" })\n\
+             \x20   :measure(\"order_count\", count())\n\
+             \x20   :measure(\"revenue\", sum(\"total\"))\n"
+        );
+    }
+}
@@ -12,13 +12,15 @@
 //! let lua_code = emitter.emit(&model);
 //! ```
 
+mod dimension;
+mod fact;
 mod format;
 mod relationship;
 mod source;
 
 pub use format::{escape_lua_string, quote_identifier, quote_string, Indent, IndentWriter};
 
-use crate::model::{Model, Relationship, SourceEntity};
+use crate::model::{DimensionDefinition, FactDefinition, Model, Relationship, SourceEntity};
 use format::IndentWriter as Writer;
 use std::io;
 use std::path::Path;
@@ -143,6 +145,28 @@ impl LuaEmitter {
         w.into_string()
     }
 
+    /// Emit only facts to a Lua string.
+    #[must_use]
+    pub fn emit_facts(&self, facts: &[&FactDefinition]) -> String {
+        let mut w = Writer::new(self.config.indent.clone());
+        for f in facts {
+            fact::emit_fact(&mut w, f, &self.config);
+            w.blank_line();
+        }
+        w.into_string()
+    }
+
+    /// Emit only dimensions to a Lua string.
+    #[must_use]
+    pub fn emit_dimensions(&self, dimensions: &[&DimensionDefinition]) -> String {
+        let mut w = Writer::new(self.config.indent.clone());
+        for dim in dimensions {
+            dimension::emit_dimension(&mut w, dim, &self.config);
+            w.blank_line();
+        }
+        w.into_string()
+    }
+
     /// Emit the model to a file.
     pub fn emit_to_file(&self, model: &Model, path: &Path) -> io::Result<()> {
         let content = self.emit(model);
@@ -201,7 +225,7 @@ impl LuaEmitter {
     }
 
     fn emit_relationships_section(&self, w: &mut Writer, model: &Model) {
-        if model.relationships.is_empty() {
+        if model.relationships.is_empty() && model.bridges.is_empty() {
             return;
         }
 
@@ -210,6 +234,12 @@ impl LuaEmitter {
         for rel in &model.relationships {
             relationship::emit_relationship(w, rel, &self.config);
         }
+
+        let mut bridges: Vec<_> = model.bridges.values().collect();
+        bridges.sort_by(|a, b| a.entity.cmp(&b.entity));
+        for bridge in bridges {
+            relationship::emit_bridge(w, bridge, &self.config);
+        }
     }
 
     fn emit_facts_placeholder(&self, w: &mut Writer) {
@@ -224,10 +254,15 @@ impl LuaEmitter {
         }
     }
 
-    fn emit_facts_section(&self, w: &mut Writer, _model: &Model) {
+    fn emit_facts_section(&self, w: &mut Writer, model: &Model) {
         w.write_section_header("FACTS");
-        // TODO: Implement fact emission when FactDefinition is finalized
-        w.write_comment("Fact emission not yet implemented");
+
+        let mut facts: Vec<_> = model.facts.values().collect();
+        facts.sort_by(|a, b| a.name.cmp(&b.name));
+        for f in facts {
+            fact::emit_fact(w, f, &self.config);
+            w.blank_line();
+        }
     }
 
     fn emit_dimensions_placeholder(&self, w: &mut Writer) {
@@ -242,10 +277,15 @@ impl LuaEmitter {
         }
     }
 
-    fn emit_dimensions_section(&self, w: &mut Writer, _model: &Model) {
+    fn emit_dimensions_section(&self, w: &mut Writer, model: &Model) {
         w.write_section_header("DIMENSIONS");
-        // TODO: Implement dimension emission when DimensionDefinition is finalized
-        w.write_comment("Dimension emission not yet implemented");
+
+        let mut dimensions: Vec<_> = model.dimensions.values().collect();
+        dimensions.sort_by(|a, b| a.name.cmp(&b.name));
+        for dim in dimensions {
+            dimension::emit_dimension(w, dim, &self.config);
+            w.blank_line();
+        }
     }
 }
 
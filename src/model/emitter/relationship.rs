@@ -1,8 +1,8 @@
 //! Relationship → Lua emission.
 
-use super::format::IndentWriter;
+use super::format::{quote_string, IndentWriter};
 use super::EmitConfig;
-use crate::model::{Cardinality, Relationship, RelationshipSource};
+use crate::model::{BridgeDefinition, Cardinality, Relationship, RelationshipSource};
 
 /// Convert cardinality to Lua string representation.
 fn cardinality_to_lua(card: Cardinality) -> &'static str {
@@ -15,6 +15,31 @@ fn cardinality_to_lua(card: Cardinality) -> &'static str {
     }
 }
 
+/// Key reference for `link()`: `orders.id`, or `{ orders.a, orders.b }` for
+/// composite keys.
+fn key_refs(entity: &str, columns: &[&str]) -> String {
+    let refs: Vec<String> = columns
+        .iter()
+        .map(|c| format!("{}.{}", entity, c))
+        .collect();
+    match refs.as_slice() {
+        [single] => single.clone(),
+        _ => format!("{{ {} }}", refs.join(", ")),
+    }
+}
+
+/// Quoted key reference for `relationship {}` blocks.
+fn quoted_key_refs(entity: &str, columns: &[&str]) -> String {
+    let refs: Vec<String> = columns
+        .iter()
+        .map(|c| format!("\"{}.{}\"", entity, c))
+        .collect();
+    match refs.as_slice() {
+        [single] => single.clone(),
+        _ => format!("{{ {} }}", refs.join(", ")),
+    }
+}
+
 /// Emit a relationship to Lua.
 ///
 /// Uses short form (link) for simple many-to-one relationships without comments,
@@ -38,13 +63,29 @@ pub fn emit_relationship(w: &mut IndentWriter, rel: &Relationship, config: &Emit
         }
     }
 
+    if !rel.predicates.is_empty() && config.include_comments {
+        w.write_comment(&format!(
+            "TODO: {} join filter(s) for {} -> {}",
+            rel.predicates.len(),
+            rel.from_entity,
+            rel.to_entity
+        ));
+    }
+
     // Use short form for simple many-to-one relationships without comments
-    let use_short_form = rel.cardinality == Cardinality::ManyToOne && !config.include_comments;
+    let has_join_options = rel.optional || rel.range.is_some() || !rel.predicates.is_empty();
+    let use_short_form =
+        rel.cardinality == Cardinality::ManyToOne && !config.include_comments && !has_join_options;
+
+    let pairs = rel.column_pairs();
+    let from_columns: Vec<&str> = pairs.iter().map(|(from, _)| *from).collect();
+    let to_columns: Vec<&str> = pairs.iter().map(|(_, to)| *to).collect();
 
     if use_short_form {
         w.write_line(&format!(
-            "link({}.{}, {}.{})",
-            rel.from_entity, rel.from_column, rel.to_entity, rel.to_column
+            "link({}, {})",
+            key_refs(&rel.from_entity, &from_columns),
+            key_refs(&rel.to_entity, &to_columns)
         ));
         return;
     }
@@ -52,19 +93,70 @@ pub fn emit_relationship(w: &mut IndentWriter, rel: &Relationship, config: &Emit
     // Long form: relationship block
     w.write_line("relationship {");
     w.indent();
-    w.write_line(&format!(
-        "from = \"{}.{}\",",
-        rel.from_entity, rel.from_column
-    ));
-    w.write_line(&format!("to = \"{}.{}\",", rel.to_entity, rel.to_column));
+    if !pairs.is_empty() {
+        w.write_line(&format!(
+            "from = {},",
+            quoted_key_refs(&rel.from_entity, &from_columns)
+        ));
+        w.write_line(&format!(
+            "to = {},",
+            quoted_key_refs(&rel.to_entity, &to_columns)
+        ));
+    }
+    if let Some(range) = &rel.range {
+        let (value_entity, bounds_entity) = range.sides(&rel.from_entity, &rel.to_entity);
+        let exclusive = if range.exclusive_upper {
+            ", exclusive_upper = true"
+        } else {
+            ""
+        };
+        w.write_line(&format!(
+            "range = {{ value = \"{}.{}\", lower = \"{}.{}\", upper = \"{}.{}\"{} }},",
+            value_entity,
+            range.value_column,
+            bounds_entity,
+            range.lower_column,
+            bounds_entity,
+            range.upper_column,
+            exclusive
+        ));
+    }
     w.write_line(&format!(
         "cardinality = \"{}\",",
         cardinality_to_lua(rel.cardinality)
     ));
+    if rel.optional {
+        w.write_line("optional = true,");
+    }
     w.dedent();
     w.write_line("}");
 }
 
+/// Emit a bridge declaration.
+///
+/// Example output:
+/// ```lua
+/// bridge "customer_accounts" { weight = "allocation" }
+/// ```
+pub fn emit_bridge(w: &mut IndentWriter, bridge: &BridgeDefinition, config: &EmitConfig) {
+    let mut fields = Vec::new();
+    if let Some(weight) = &bridge.weight_column {
+        fields.push(format!("weight = {}", quote_string(weight)));
+    }
+    if config.include_comments {
+        if let Some(description) = &bridge.description {
+            fields.push(format!("description = {}", quote_string(description)));
+        }
+    }
+
+    let body = if fields.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", fields.join(", "))
+    };
+    w.write_line(&format!("bridge {} {}", quote_string(&bridge.entity), body));
+}
+
 /// Emit multiple relationships, optionally grouping by source type.
 #[allow(dead_code)]
 pub fn emit_relationships(
@@ -202,6 +294,74 @@ mod tests {
         assert!(output.contains("cardinality = \"one_to_many\""));
     }
 
+    #[test]
+    fn test_emit_relationship_join_options() {
+        let rel = Relationship::range(
+            "orders",
+            "tiers",
+            "amount",
+            "min_amount",
+            "max_amount",
+            Cardinality::ManyToOne,
+        )
+        .optional();
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_relationship(&mut w, &rel, &EmitConfig::minimal());
+        let output = w.into_string();
+
+        // Join options need the long form; there is no equality key to emit
+        assert!(output.contains("relationship {"));
+        assert!(!output.contains("from ="));
+        assert!(output.contains(
+            "range = { value = \"orders.amount\", lower = \"tiers.min_amount\", upper = \"tiers.max_amount\" },"
+        ));
+        assert!(output.contains("optional = true,"));
+    }
+
+    #[test]
+    fn test_emit_composite_relationship() {
+        let rel = Relationship::composite(
+            "shipments",
+            "order_lines",
+            vec![("order_id", "order_id"), ("line_no", "line_no")],
+            Cardinality::ManyToOne,
+        );
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_relationship(&mut w, &rel, &EmitConfig::minimal());
+        assert_eq!(
+            w.into_string().trim(),
+            "link({ shipments.order_id, shipments.line_no }, { order_lines.order_id, order_lines.line_no })"
+        );
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_relationship(&mut w, &rel, &EmitConfig::default());
+        let output = w.into_string();
+        assert!(output.contains("from = { \"shipments.order_id\", \"shipments.line_no\" },"));
+        assert!(output.contains("to = { \"order_lines.order_id\", \"order_lines.line_no\" },"));
+    }
+
+    #[test]
+    fn test_emit_bridge() {
+        let bridge = BridgeDefinition::new("customer_accounts").with_weight("allocation");
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_bridge(&mut w, &bridge, &EmitConfig::minimal());
+        assert_eq!(
+            w.into_string().trim(),
+            "bridge \"customer_accounts\" { weight = \"allocation\" }"
+        );
+
+        let mut w = IndentWriter::new(Indent::Tabs);
+        emit_bridge(
+            &mut w,
+            &BridgeDefinition::new("product_categories"),
+            &EmitConfig::minimal(),
+        );
+        assert_eq!(w.into_string().trim(), "bridge \"product_categories\" {}");
+    }
+
     #[test]
     fn test_emit_relationship_fk_source() {
         let rel = Relationship::from_foreign_key(
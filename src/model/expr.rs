@@ -256,6 +256,62 @@ impl Expr {
     pub fn is_not_null(self) -> Self {
         Self::unary(UnaryOp::IsNotNull, self)
     }
+
+    /// Qualify every unqualified column reference with `entity`.
+    pub fn qualify_columns(&mut self, entity: &str) {
+        self.visit_columns_mut(&mut |qualifier, _| {
+            if qualifier.is_none() {
+                *qualifier = Some(entity.to_string());
+            }
+        });
+    }
+
+    /// Call `f` with the entity qualifier and name of every column reference.
+    pub fn visit_columns_mut(&mut self, f: &mut impl FnMut(&mut Option<String>, &mut String)) {
+        match self {
+            Expr::Column { entity, column } => f(entity, column),
+            Expr::Literal(_) => {}
+            Expr::Function { args, .. } => {
+                args.iter_mut().for_each(|arg| arg.visit_columns_mut(f));
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                left.visit_columns_mut(f);
+                right.visit_columns_mut(f);
+            }
+            Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => expr.visit_columns_mut(f),
+            Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                if let Some(operand) = operand {
+                    operand.visit_columns_mut(f);
+                }
+                for clause in when_clauses {
+                    clause.condition.visit_columns_mut(f);
+                    clause.result.visit_columns_mut(f);
+                }
+                if let Some(else_clause) = else_clause {
+                    else_clause.visit_columns_mut(f);
+                }
+            }
+            Expr::Window {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                args.iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|o| &mut o.expr))
+                    .for_each(|e| e.visit_columns_mut(f));
+            }
+            Expr::FilteredAgg { agg, filter } => {
+                agg.visit_columns_mut(f);
+                filter.visit_columns_mut(f);
+            }
+        }
+    }
 }
 
 // =============================================================================
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 
 use super::dimension_role::DateConfig;
 use super::expr::{ColumnDef, Expr, OrderByExpr, WindowFrame, WindowFunc};
-use super::types::{AggregationType, DataType, MaterializationStrategy};
+use super::types::{AggregationType, DataType, MaterializationStrategy, TableOptions};
 
 /// Default value for `materialized` field in serde deserialization.
 fn default_materialized() -> bool {
@@ -95,6 +95,10 @@ pub struct FactDefinition {
     /// ```
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub date_config: Option<DateConfig>,
+
+    /// Partitioning, clustering and distribution of the target table.
+    #[serde(default, skip_serializing_if = "TableOptions::is_empty")]
+    pub table_options: TableOptions,
 }
 
 /// A column that defines the grain of the fact table.
@@ -202,6 +206,7 @@ impl FactDefinition {
             window_columns: vec![],
             materialization: MaterializationStrategy::default(),
             date_config: None,
+            table_options: TableOptions::default(),
         }
     }
 
@@ -399,6 +404,12 @@ impl FactDefinition {
         self
     }
 
+    /// Set the physical table options.
+    pub fn with_table_options(mut self, options: TableOptions) -> Self {
+        self.table_options = options;
+        self
+    }
+
     /// Add a column definition.
     pub fn with_column(mut self, column: ColumnDef) -> Self {
         self.columns.push(column);
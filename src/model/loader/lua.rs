@@ -30,10 +30,12 @@ use std::rc::Rc;
 use mlua::{Lua, Result as LuaResult, Table, Value};
 
 use super::{sql_expr, LoadError, LoadResult};
+use crate::dialect::Dialect;
 use crate::model::table::{FromClause, TableDefinition, TableTypeLabel, UnionType};
 use crate::model::{
     AggregationType,
     BinaryOp,
+    BridgeDefinition,
     Cardinality,
     ChangeTracking,
     ColumnDef,
@@ -46,6 +48,7 @@ use crate::model::{
     DimensionDefinition,
     DimensionInclude,
     DimensionRole,
+    Distribution,
     // New types for expression parsing
     Expr,
     FactDefinition,
@@ -61,6 +64,7 @@ use crate::model::{
     Model,
     NullsOrder,
     OrderByExpr,
+    PartitionGranularity,
     PivotColumns,
     PivotReport,
     PivotSort,
@@ -72,6 +76,7 @@ use crate::model::{
     QueryFilterValue,
     QueryOrderBy,
     QuerySelect,
+    RangeCondition,
     RefreshDelta,
     Relationship,
     // Report layer types
@@ -84,6 +89,8 @@ use crate::model::{
     SortDirection,
     SourceColumn,
     SourceEntity,
+    TableFormat,
+    TableOptions,
     TotalsConfig,
     UnaryOp,
     WindowColumnDef,
@@ -287,6 +294,19 @@ impl LuaLoader {
         })?;
         globals.set("relationship", relationship_fn)?;
 
+        // bridge "entity" { ... }
+        let state_clone = Rc::clone(&state);
+        let bridge_fn = lua.create_function(move |lua, name: String| {
+            let state = Rc::clone(&state_clone);
+            let inner = lua.create_function(move |_, table: Table| {
+                let bridge = parse_bridge(&name, &table)?;
+                state.borrow_mut().model.add_bridge(bridge);
+                Ok(())
+            })?;
+            Ok(inner)
+        })?;
+        globals.set("bridge", bridge_fn)?;
+
         // fact("name"):target("table"):grain({...}):measure(...) - chained syntax
         let fact_fn =
             lua.create_function(move |lua, name: String| Self::create_fact_builder(lua, name))?;
@@ -449,6 +469,29 @@ impl LuaLoader {
         })?;
         globals.set("relationship", relationship_fn)?;
 
+        // bridge "entity" { ... } - lenient mode
+        let state_clone = Rc::clone(&state);
+        let bridge_fn = lua.create_function(move |lua, name: String| {
+            let state = Rc::clone(&state_clone);
+            let inner = lua.create_function(move |_, table: Table| {
+                match parse_bridge(&name, &table) {
+                    Ok(bridge) => {
+                        state.borrow_mut().model.add_bridge(bridge);
+                    }
+                    Err(e) => {
+                        state.borrow_mut().parse_errors.push(ParseError {
+                            entity_type: "bridge".to_string(),
+                            entity_name: name.clone(),
+                            message: e.to_string(),
+                        });
+                    }
+                }
+                Ok(())
+            })?;
+            Ok(inner)
+        })?;
+        globals.set("bridge", bridge_fn)?;
+
         // fact("name"):target("table"):grain({...}) - lenient mode with chained syntax
         let fact_fn =
             lua.create_function(move |lua, name: String| Self::create_fact_builder(lua, name))?;
@@ -611,7 +654,29 @@ impl LuaLoader {
     }
 
     fn parse_table(name: &str, config: &Table, _lua: &Lua) -> LuaResult<TableDefinition> {
-        // Parse 'from' - can be string or array
+        // Parse optional raw SQL body - validated now so errors point at the table
+        let sql_body = config.get::<String>("sql").ok();
+        let sql_dialect = config.get::<String>("dialect").ok();
+        let dialect = match &sql_dialect {
+            Some(d) => Dialect::from_name(d).ok_or_else(|| {
+                mlua::Error::external(format!("table '{}' has unknown dialect '{}'", name, d))
+            })?,
+            None => Dialect::default(),
+        };
+        let sql_query = match &sql_body {
+            Some(body) => Some(crate::query::Query::parse(body, dialect).map_err(|e| {
+                mlua::Error::external(format!("SQL error in table '{}' sql: {}", name, e))
+            })?),
+            None => None,
+        };
+        let mut sql_sources: Vec<String> = Vec::new();
+        for source in sql_query.iter().flat_map(|q| q.source_tables()) {
+            if !sql_sources.contains(&source.table) {
+                sql_sources.push(source.table.clone());
+            }
+        }
+
+        // Parse 'from' - can be string or array, defaults to the SQL body's first table
         let from = if let Ok(single) = config.get::<String>("from") {
             FromClause::Single(single)
         } else if let Ok(table) = config.get::<Table>("from") {
@@ -626,6 +691,8 @@ impl LuaLoader {
                 )));
             }
             FromClause::Multiple(sources)
+        } else if let Some(first) = sql_query.as_ref().and_then(|q| q.source_tables().first().copied()) {
+            FromClause::Single(first.table.clone())
         } else {
             return Err(mlua::Error::external(format!(
                 "table '{}' requires 'from' field",
@@ -655,6 +722,9 @@ impl LuaLoader {
 
         let mut table = TableDefinition::new(name, "").with_table_type(table_type);
         table.from = from;
+        table.sql = sql_body;
+        table.sql_dialect = sql_dialect;
+        table.sql_sources = sql_sources;
         table.union_type = union_type;
 
         // Parse optional target_table
@@ -964,6 +1034,7 @@ impl LuaLoader {
                 window_columns: Vec::new(),
                 materialization: MaterializationStrategy::Table,
                 date_config: None,
+                table_options: TableOptions::default(),
             };
             state.borrow_mut().model.add_fact(fact);
 
@@ -1359,13 +1430,35 @@ impl LuaLoader {
         };
         builder.set("date_config", date_config_fn)?;
 
+        // :table_options() method - optional, sets partitioning/clustering/distribution
+        let table_options_fn = {
+            let name = name_clone.clone();
+            lua.create_function(move |lua, (builder, options): (Table, Table)| {
+                let state = lua
+                    .app_data_ref::<Rc<RefCell<LoaderState>>>()
+                    .ok_or_else(|| mlua::Error::external("LoaderState not found"))?
+                    .clone();
+
+                if let Some(fact) = state.borrow_mut().model.facts.get_mut(&name) {
+                    fact.table_options =
+                        parse_table_options(&options, &format!("fact '{}'", name))?;
+                }
+
+                Ok(builder)
+            })?
+        };
+        builder.set("table_options", table_options_fn)?;
+
         Ok(builder)
     }
 
     /// Create a dimension builder for chained syntax:
     /// dimension("name"):target("table"):from("entity"):columns({...}):primary_key("id")
     fn create_dimension_builder(lua: &Lua, name: String) -> LuaResult<Table> {
-        use crate::model::dimension::{DimensionColumn, DimensionDefinition, SCDType};
+        use crate::model::dimension::{
+            DimensionColumn, DimensionDefinition, Hierarchy, SCDType, SurrogateKey,
+            SurrogateKeyStrategy, UnknownMember,
+        };
         use crate::model::MaterializationStrategy;
 
         let builder = lua.create_table()?;
@@ -1388,6 +1481,10 @@ impl LuaLoader {
                 primary_key: Vec::new(),
                 scd_type: SCDType::Type1, // Default to Type1
                 materialization: MaterializationStrategy::Table,
+                table_options: TableOptions::default(),
+                hierarchies: Vec::new(),
+                surrogate_key: None,
+                unknown_member: None,
             };
             state.borrow_mut().model.add_dimension(dim);
 
@@ -1459,6 +1556,99 @@ impl LuaLoader {
         };
         builder.set("columns", columns_fn)?;
 
+        // :hierarchy() method - optional, declares a drill path, coarsest level first
+        let hierarchy_fn = {
+            let name = name_clone.clone();
+            lua.create_function(
+                move |lua, (builder, hierarchy, levels): (Table, String, Vec<String>)| {
+                    let state = lua
+                        .app_data_ref::<Rc<RefCell<LoaderState>>>()
+                        .ok_or_else(|| mlua::Error::external("LoaderState not found"))?
+                        .clone();
+
+                    if levels.is_empty() {
+                        return Err(mlua::Error::external(format!(
+                            "Hierarchy '{}' in dimension '{}' needs at least one level",
+                            hierarchy, name
+                        )));
+                    }
+                    if let Some(dim) = state.borrow_mut().model.dimensions.get_mut(&name) {
+                        if dim.hierarchy(&hierarchy).is_some() {
+                            return Err(mlua::Error::external(format!(
+                                "Hierarchy '{}' is defined twice in dimension '{}'",
+                                hierarchy, name
+                            )));
+                        }
+                        dim.hierarchies.push(Hierarchy::new(hierarchy, levels));
+                    }
+
+                    Ok(builder)
+                },
+            )?
+        };
+        builder.set("hierarchy", hierarchy_fn)?;
+
+        // :surrogate_key() method - optional, generates a key column ("identity" or "hash")
+        let surrogate_key_fn = {
+            let name = name_clone.clone();
+            lua.create_function(
+                move |lua, (builder, column, strategy): (Table, String, Option<String>)| {
+                    let state = lua
+                        .app_data_ref::<Rc<RefCell<LoaderState>>>()
+                        .ok_or_else(|| mlua::Error::external("LoaderState not found"))?
+                        .clone();
+
+                    let strategy: SurrogateKeyStrategy = strategy
+                        .as_deref()
+                        .unwrap_or("identity")
+                        .parse()
+                        .map_err(|e: String| {
+                            mlua::Error::external(format!("{} in dimension '{}'", e, name))
+                        })?;
+                    if let Some(dim) = state.borrow_mut().model.dimensions.get_mut(&name) {
+                        dim.surrogate_key = Some(SurrogateKey { column, strategy });
+                    }
+
+                    Ok(builder)
+                },
+            )?
+        };
+        builder.set("surrogate_key", surrogate_key_fn)?;
+
+        // :unknown_member() method - optional, adds the row unmatched fact keys point at
+        let unknown_member_fn = {
+            let name = name_clone.clone();
+            lua.create_function(move |lua, (builder, config): (Table, Option<Table>)| {
+                let state = lua
+                    .app_data_ref::<Rc<RefCell<LoaderState>>>()
+                    .ok_or_else(|| mlua::Error::external("LoaderState not found"))?
+                    .clone();
+
+                let mut member = UnknownMember::default();
+                if let Some(config) = config {
+                    if let Some(key) = config.get::<Option<i64>>("key")? {
+                        member.key = key;
+                    }
+                    if let Some(label) = config.get::<Option<String>>("label")? {
+                        member.label = label;
+                    }
+                    if let Some(values) = config.get::<Option<Table>>("values")? {
+                        for pair in values.pairs::<String, String>() {
+                            let (column, value) = pair?;
+                            member.values.push((column, value));
+                        }
+                        member.values.sort();
+                    }
+                }
+                if let Some(dim) = state.borrow_mut().model.dimensions.get_mut(&name) {
+                    dim.unknown_member = Some(member);
+                }
+
+                Ok(builder)
+            })?
+        };
+        builder.set("unknown_member", unknown_member_fn)?;
+
         // :primary_key() method - required, sets the primary key
         let primary_key_fn = {
             let name = name_clone.clone();
@@ -1588,6 +1778,25 @@ impl LuaLoader {
         };
         builder.set("table_type", table_type_fn)?;
 
+        // :table_options() method - optional, sets partitioning/clustering/distribution
+        let table_options_fn = {
+            let name = name_clone.clone();
+            lua.create_function(move |lua, (builder, options): (Table, Table)| {
+                let state = lua
+                    .app_data_ref::<Rc<RefCell<LoaderState>>>()
+                    .ok_or_else(|| mlua::Error::external("LoaderState not found"))?
+                    .clone();
+
+                if let Some(dim) = state.borrow_mut().model.dimensions.get_mut(&name) {
+                    dim.table_options =
+                        parse_table_options(&options, &format!("dimension '{}'", name))?;
+                }
+
+                Ok(builder)
+            })?
+        };
+        builder.set("table_options", table_options_fn)?;
+
         Ok(builder)
     }
 
@@ -1971,6 +2180,20 @@ fn parse_change_tracking_value(
     }
 }
 
+/// Accepted values are plain literals; strings are not column references here.
+fn parse_accepted_value(value: Value, context: &str) -> LuaResult<Literal> {
+    match value {
+        Value::String(s) => Ok(Literal::String(s.to_str()?.to_string())),
+        Value::Integer(n) => Ok(Literal::Int(n)),
+        Value::Number(n) => Ok(Literal::Float(n)),
+        Value::Boolean(b) => Ok(Literal::Bool(b)),
+        _ => Err(mlua::Error::external(format!(
+            "Invalid accepted value in {}. Expected string, number, or boolean.",
+            context
+        ))),
+    }
+}
+
 fn parse_source_column(name: &str, value: Value, context: &str) -> LuaResult<SourceColumn> {
     match value {
         Value::Table(table) => {
@@ -1980,12 +2203,20 @@ fn parse_source_column(name: &str, value: Value, context: &str) -> LuaResult<Sou
             })?;
             let nullable = get_optional::<bool>(&table, "nullable")?.unwrap_or(true);
             let description = get_optional::<String>(&table, "description")?;
+            let accepted_values = match get_optional::<Table>(&table, "accepted_values")? {
+                Some(values) => values
+                    .sequence_values::<Value>()
+                    .map(|v| parse_accepted_value(v?, context))
+                    .collect::<LuaResult<_>>()?,
+                None => Vec::new(),
+            };
 
             Ok(SourceColumn {
                 name: name.to_string(),
                 data_type,
                 nullable,
                 description,
+                accepted_values,
             })
         }
         Value::String(type_str) => {
@@ -1999,6 +2230,7 @@ fn parse_source_column(name: &str, value: Value, context: &str) -> LuaResult<Sou
                 data_type,
                 nullable: true,
                 description: None,
+                accepted_values: Vec::new(),
             })
         }
         _ => Err(mlua::Error::external(format!(
@@ -2009,13 +2241,56 @@ fn parse_source_column(name: &str, value: Value, context: &str) -> LuaResult<Sou
 }
 
 fn parse_relationship(table: &Table) -> LuaResult<Relationship> {
-    let from: String = get_required(table, "from", "relationship")?;
-    let to: String = get_required(table, "to", "relationship")?;
     let cardinality_str: String = get_required(table, "cardinality", "relationship")?;
 
-    // Parse "entity.column" format
-    let (from_entity, from_column) = parse_entity_column(&from, "relationship.from")?;
-    let (to_entity, to_column) = parse_entity_column(&to, "relationship.to")?;
+    // Band condition: { value = "orders.amount", lower = "tiers.min", upper = "tiers.max" }
+    let range = match get_optional::<Table>(table, "range")? {
+        Some(range) => Some(parse_range_condition(&range)?),
+        None => None,
+    };
+
+    // Parse "entity.column" or { "entity.a", "entity.b" } for composite keys.
+    // A band condition may stand in for the equality key.
+    let has_key = table.contains_key("from")? || table.contains_key("to")?;
+    let (from_entity, from_columns, to_entity, to_columns) = match &range {
+        Some((value_entity, bounds_entity, _)) if !has_key => (
+            value_entity.clone(),
+            Vec::new(),
+            bounds_entity.clone(),
+            Vec::new(),
+        ),
+        _ => {
+            let from: Value = get_required(table, "from", "relationship")?;
+            let to: Value = get_required(table, "to", "relationship")?;
+            let (from_entity, from_columns) = parse_key_columns(from, "relationship.from")?;
+            let (to_entity, to_columns) = parse_key_columns(to, "relationship.to")?;
+            (from_entity, from_columns, to_entity, to_columns)
+        }
+    };
+    let range = match range {
+        Some((value_entity, bounds_entity, condition)) => {
+            if value_entity == from_entity && bounds_entity == to_entity {
+                Some(condition)
+            } else if value_entity == to_entity && bounds_entity == from_entity {
+                Some(condition.reversed())
+            } else {
+                return Err(mlua::Error::external(format!(
+                    "Range in relationship {} -> {} compares '{}' with bounds on '{}'",
+                    from_entity, to_entity, value_entity, bounds_entity
+                )));
+            }
+        }
+        None => None,
+    };
+    if from_columns.len() != to_columns.len() {
+        return Err(mlua::Error::external(format!(
+            "Relationship {} -> {} has {} 'from' columns but {} 'to' columns",
+            from_entity,
+            to_entity,
+            from_columns.len(),
+            to_columns.len()
+        )));
+    }
 
     let cardinality = match cardinality_str.as_str() {
         "one_to_one" | "1:1" => Cardinality::OneToOne,
@@ -2033,14 +2308,76 @@ fn parse_relationship(table: &Table) -> LuaResult<Relationship> {
     // Parse optional role name (for role-playing dimensions)
     let role: Option<String> = get_optional(table, "role")?;
 
-    let mut rel = Relationship::new(from_entity, to_entity, from_column, to_column, cardinality);
+    let mut rel = if from_columns.is_empty() {
+        Relationship::new(from_entity, to_entity, "", "", cardinality)
+    } else {
+        let columns = from_columns.into_iter().zip(to_columns).collect();
+        Relationship::composite(from_entity, to_entity, columns, cardinality)
+    };
     if let Some(role_name) = role {
         rel = rel.with_role(role_name);
     }
+    if let Some(condition) = range {
+        rel = rel.with_range(condition);
+    }
+    if get_optional::<bool>(table, "optional")?.unwrap_or(false) {
+        rel = rel.optional();
+    }
+
+    // Extra join predicates: filter = "status = 'active'" or a list of them
+    let filters = match table.get::<Value>("filter")? {
+        Value::Nil => Vec::new(),
+        Value::String(s) => vec![s.to_str()?.to_string()],
+        Value::Table(t) => table_to_string_vec(&t)?,
+        _ => {
+            return Err(mlua::Error::external(
+                "Invalid filter in relationship. Expected a string or a list of strings",
+            ))
+        }
+    };
+    for filter_str in filters {
+        let predicate = sql_expr::parse_sql_expr(&filter_str).map_err(|e| {
+            mlua::Error::external(format!(
+                "Failed to parse filter '{}' in relationship {} -> {}: {}",
+                filter_str, rel.from_entity, rel.to_entity, e
+            ))
+        })?;
+        rel = rel.with_predicate(predicate);
+    }
 
     Ok(rel)
 }
 
+/// Parse a relationship band condition into `(value entity, bounds entity, condition)`.
+fn parse_range_condition(table: &Table) -> LuaResult<(String, String, RangeCondition)> {
+    let value: String = get_required(table, "value", "relationship.range")?;
+    let lower: String = get_required(table, "lower", "relationship.range")?;
+    let upper: String = get_required(table, "upper", "relationship.range")?;
+
+    let (value_entity, value_column) = parse_entity_column(&value, "relationship.range.value")?;
+    let (lower_entity, lower_column) = parse_entity_column(&lower, "relationship.range.lower")?;
+    let (upper_entity, upper_column) = parse_entity_column(&upper, "relationship.range.upper")?;
+    if lower_entity != upper_entity {
+        return Err(mlua::Error::external(format!(
+            "Range bounds in relationship must be on one entity, got '{}' and '{}'",
+            lower_entity, upper_entity
+        )));
+    }
+
+    let mut condition = RangeCondition::new(value_column, lower_column, upper_column);
+    if get_optional::<bool>(table, "exclusive_upper")?.unwrap_or(false) {
+        condition = condition.exclusive_upper();
+    }
+    Ok((value_entity, lower_entity, condition))
+}
+
+fn parse_bridge(name: &str, table: &Table) -> LuaResult<BridgeDefinition> {
+    let mut bridge = BridgeDefinition::new(name);
+    bridge.weight_column = get_optional(table, "weight")?;
+    bridge.description = get_optional(table, "description")?;
+    Ok(bridge)
+}
+
 #[allow(dead_code)]
 fn parse_fact(name: &str, table: &Table) -> LuaResult<FactDefinition> {
     // target_table is optional for non-materialized (virtual) facts
@@ -2124,6 +2461,11 @@ fn parse_fact(name: &str, table: &Table) -> LuaResult<FactDefinition> {
         )?);
     }
 
+    // Physical table options
+    if let Some(options_table) = get_optional::<Table>(table, "table_options")? {
+        fact.table_options = parse_table_options(&options_table, &format!("fact '{}'", name))?;
+    }
+
     Ok(fact)
 }
 
@@ -2181,6 +2523,7 @@ fn parse_measure(name: &str, table: &Table, context: &str) -> LuaResult<MeasureD
 ///     },
 ///     dimension = "date",                   -- target dimension
 ///     pk_column = "date_id",                -- PK column on dimension
+///     date_column = "full_date",            -- calendar date on dimension (optional)
 ///     primary_role = "order_date",          -- default for time intelligence
 ///     grain_columns = {
 ///         year = "year",
@@ -2200,16 +2543,17 @@ fn parse_date_config(table: &Table, context: &str) -> LuaResult<DateConfig> {
     let pk_column: String =
         get_optional(table, "pk_column")?.unwrap_or_else(|| "date_id".to_string());
 
+    // Parse calendar date column on dimension (when the PK is a surrogate key)
+    let date_column: Option<String> = get_optional(table, "date_column")?;
+
     // Parse roles (role_name -> fk_column mappings)
     if let Some(roles_table) = get_optional::<Table>(table, "roles")? {
         for pair in roles_table.pairs::<String, String>() {
             let (role_name, fk_column) = pair?;
-            config.add_role(DimensionRole::new(
-                role_name,
-                fk_column,
-                dimension.clone(),
-                pk_column.clone(),
-            ));
+            let mut role =
+                DimensionRole::new(role_name, fk_column, dimension.clone(), pk_column.clone());
+            role.date_column = date_column.clone();
+            config.add_role(role);
         }
     }
 
@@ -2243,6 +2587,71 @@ fn parse_date_config(table: &Table, context: &str) -> LuaResult<DateConfig> {
     Ok(config)
 }
 
+/// Parse physical table options.
+///
+/// Lua syntax:
+/// ```lua
+/// table_options = {
+///     partition_by = { "order_date" },      -- BigQuery, Databricks
+///     partition_granularity = "month",      -- BigQuery: hour, day, month, year
+///     cluster_by = { "customer_id" },       -- BigQuery, Snowflake, Databricks
+///     distribution = "even",                -- Redshift: auto, even, all
+///     dist_key = "customer_id",             -- Redshift DISTSTYLE KEY
+///     sort_key = { "order_date" },          -- Redshift
+///     format = "delta",                     -- Databricks: delta, parquet, iceberg
+///     columnstore = true,                   -- T-SQL clustered columnstore
+/// }
+/// ```
+fn parse_table_options(table: &Table, context: &str) -> LuaResult<TableOptions> {
+    let mut options = TableOptions::new();
+
+    if let Some(columns) = get_optional::<Table>(table, "partition_by")? {
+        options.partition_by = table_to_string_vec(&columns)?;
+    }
+    if let Some(granularity) = get_optional::<String>(table, "partition_granularity")? {
+        options.partition_granularity =
+            Some(PartitionGranularity::parse(&granularity).ok_or_else(|| {
+                mlua::Error::external(format!(
+                    "Invalid partition_granularity '{}' in {}. Expected: hour, day, month, year",
+                    granularity, context
+                ))
+            })?);
+    }
+    if let Some(columns) = get_optional::<Table>(table, "cluster_by")? {
+        options.cluster_by = table_to_string_vec(&columns)?;
+    }
+    if let Some(style) = get_optional::<String>(table, "distribution")? {
+        options.distribution = Some(match style.to_lowercase().as_str() {
+            "auto" => Distribution::Auto,
+            "even" => Distribution::Even,
+            "all" => Distribution::All,
+            _ => {
+                return Err(mlua::Error::external(format!(
+                    "Invalid distribution '{}' in {}. Expected: auto, even, all (or set dist_key)",
+                    style, context
+                )));
+            }
+        });
+    }
+    if let Some(column) = get_optional::<String>(table, "dist_key")? {
+        options.distribution = Some(Distribution::Key(column));
+    }
+    if let Some(columns) = get_optional::<Table>(table, "sort_key")? {
+        options.sort_key = table_to_string_vec(&columns)?;
+    }
+    if let Some(format) = get_optional::<String>(table, "format")? {
+        options.format = Some(TableFormat::parse(&format).ok_or_else(|| {
+            mlua::Error::external(format!(
+                "Invalid format '{}' in {}. Expected: delta, parquet, iceberg",
+                format, context
+            ))
+        })?);
+    }
+    options.columnstore = get_optional::<bool>(table, "columnstore")?.unwrap_or(false);
+
+    Ok(options)
+}
+
 #[allow(dead_code)]
 fn parse_dimension(name: &str, table: &Table) -> LuaResult<DimensionDefinition> {
     // target_table is optional for non-materialized (virtual) dimensions
@@ -2311,6 +2720,11 @@ fn parse_dimension(name: &str, table: &Table) -> LuaResult<DimensionDefinition>
             parse_materialization(&mat_str, table, &format!("dimension '{}'", name))?;
     }
 
+    // Physical table options
+    if let Some(options_table) = get_optional::<Table>(table, "table_options")? {
+        dim.table_options = parse_table_options(&options_table, &format!("dimension '{}'", name))?;
+    }
+
     Ok(dim)
 }
 
@@ -3144,6 +3558,46 @@ fn parse_entity_column(s: &str, context: &str) -> LuaResult<(String, String)> {
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
+/// Parse one side of a relationship key: `"entity.column"` or a list of
+/// `"entity.column"` references on the same entity (composite key).
+fn parse_key_columns(value: Value, context: &str) -> LuaResult<(String, Vec<String>)> {
+    let refs: Vec<String> = match value {
+        Value::String(s) => vec![s.to_str()?.to_string()],
+        Value::Table(t) => table_to_string_vec(&t)?,
+        _ => {
+            return Err(mlua::Error::external(format!(
+                "Invalid key in {}. Expected 'entity.column' or a list of them",
+                context
+            )))
+        }
+    };
+
+    let mut entity: Option<String> = None;
+    let mut columns = Vec::with_capacity(refs.len());
+    for r in &refs {
+        let (e, column) = parse_entity_column(r, context)?;
+        match &entity {
+            Some(existing) if *existing != e => {
+                return Err(mlua::Error::external(format!(
+                    "Composite key in {} mixes entities '{}' and '{}'",
+                    context, existing, e
+                )));
+            }
+            Some(_) => {}
+            None => entity = Some(e),
+        }
+        columns.push(column);
+    }
+
+    match entity {
+        Some(entity) => Ok((entity, columns)),
+        None => Err(mlua::Error::external(format!(
+            "Empty key in {}. Expected at least one 'entity.column'",
+            context
+        ))),
+    }
+}
+
 // =============================================================================
 // Expression Parsing
 // =============================================================================
@@ -4134,6 +4588,29 @@ mod tests {
         assert_eq!(orders.primary_key, vec!["order_id"]);
     }
 
+    #[test]
+    fn test_load_source_accepted_values() {
+        let lua = r#"
+            source("orders")
+                :from("raw.orders")
+                :columns({
+                    status = { type = string, accepted_values = { "open", "shipped" } },
+                    priority = { type = int32, accepted_values = { 1, 2, 3 } },
+                })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        let orders = &model.sources["orders"];
+        assert_eq!(
+            orders.columns["status"].accepted_values,
+            vec![
+                Literal::String("open".into()),
+                Literal::String("shipped".into())
+            ]
+        );
+        assert_eq!(orders.columns["priority"].accepted_values.len(), 3);
+    }
+
     #[test]
     fn test_load_relationship() {
         let lua = r#"
@@ -4196,6 +4673,143 @@ mod tests {
         assert_eq!(delivery_date_rel.role, Some("delivery_date".into()));
     }
 
+    #[test]
+    fn test_load_composite_relationship() {
+        let lua = r#"
+            source("order_lines"):from("raw.order_lines")
+            source("shipments"):from("raw.shipments")
+
+            link({ shipments.order_id, shipments.line_no }, { order_lines.order_id, order_lines.line_no })
+
+            relationship {
+                from = { "shipments.tenant_id", "shipments.order_id" },
+                to = "order_lines.order_id",
+                cardinality = "many_to_one",
+            }
+        "#;
+
+        let err = LuaLoader::load_from_str(lua, "test.lua").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("2 'from' columns but 1 'to' columns"));
+
+        let lua = r#"
+            source("order_lines"):from("raw.order_lines")
+            source("shipments"):from("raw.shipments")
+
+            link({ shipments.order_id, shipments.line_no }, { order_lines.order_id, order_lines.line_no })
+        "#;
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        let rel = &model.relationships[0];
+        assert_eq!(rel.from_entity, "shipments");
+        assert_eq!(rel.to_entity, "order_lines");
+        assert!(rel.is_composite());
+        assert_eq!(
+            rel.column_pairs(),
+            vec![("order_id", "order_id"), ("line_no", "line_no")]
+        );
+    }
+
+    #[test]
+    fn test_load_relationship_join_options() {
+        let lua = r#"
+            source("orders"):from("raw.orders")
+            source("customers"):from("raw.customers")
+            source("tiers"):from("raw.tiers")
+            source("rates"):from("raw.rates")
+
+            relationship {
+                from = orders.customer_id,
+                to = customers.customer_id,
+                cardinality = MANY_TO_ONE,
+                optional = true,
+                filter = "status = 'active'",
+            }
+
+            link_range(orders.amount, tiers.min_amount, tiers.max_amount)
+
+            relationship {
+                from = orders.currency,
+                to = rates.currency,
+                range = { value = orders.order_date, lower = rates.valid_from, upper = rates.valid_to, exclusive_upper = true },
+                cardinality = MANY_TO_ONE,
+            }
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        assert_eq!(model.relationships.len(), 3);
+
+        let customers = &model.relationships[0];
+        assert!(customers.optional);
+        assert_eq!(
+            customers.predicates,
+            vec![Expr::qualified_column("customers", "status").eq(Expr::string("active"))]
+        );
+
+        let tiers = &model.relationships[1];
+        assert_eq!(
+            (tiers.from_entity.as_str(), tiers.to_entity.as_str()),
+            ("orders", "tiers")
+        );
+        assert!(tiers.column_pairs().is_empty());
+        assert_eq!(
+            tiers.range,
+            Some(RangeCondition::new("amount", "min_amount", "max_amount"))
+        );
+
+        let rates = &model.relationships[2];
+        assert_eq!(rates.column_pairs(), vec![("currency", "currency")]);
+        assert_eq!(
+            rates.range,
+            Some(RangeCondition::new("order_date", "valid_from", "valid_to").exclusive_upper())
+        );
+
+        let lua = r#"
+            source("orders"):from("raw.orders")
+            source("tiers"):from("raw.tiers")
+
+            relationship {
+                range = { value = orders.amount, lower = tiers.min_amount, upper = orders.max_amount },
+                cardinality = MANY_TO_ONE,
+            }
+        "#;
+        let err = LuaLoader::load_from_str(lua, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("must be on one entity"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_bridge() {
+        let lua = r#"
+            source("customer_accounts")
+                :from("raw.customer_accounts")
+                :columns({
+                    customer_id = int64,
+                    account_id = int64,
+                    allocation = decimal(5,4),
+                })
+
+            bridge "customer_accounts" {
+                weight = "allocation",
+                description = "Joint account holders",
+            }
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        let bridge = model.get_bridge("customer_accounts").unwrap();
+        assert_eq!(bridge.weight_column.as_deref(), Some("allocation"));
+        assert_eq!(bridge.description.as_deref(), Some("Joint account holders"));
+
+        let lua = r#"
+            source("customer_accounts")
+                :from("raw.customer_accounts")
+                :columns({ customer_id = int64 })
+
+            bridge "customer_accounts" { weight = "share" }
+        "#;
+        let err = LuaLoader::load_from_str(lua, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("share"), "{}", err);
+    }
+
     #[test]
     fn test_link_without_role() {
         let lua = r#"
@@ -4228,6 +4842,7 @@ mod tests {
                 :date_config({
                     dimension = "date",
                     pk_column = "date_id",
+                    date_column = "full_date",
                     roles = {
                         order_date = "order_date_id",
                         ship_date = "ship_date_id",
@@ -4258,6 +4873,7 @@ mod tests {
         assert_eq!(order_date.fk_column, "order_date_id");
         assert_eq!(order_date.dimension, "date");
         assert_eq!(order_date.pk_column, "date_id");
+        assert_eq!(order_date.date_column.as_deref(), Some("full_date"));
 
         let ship_date = date_config
             .get_role("ship_date")
@@ -4282,6 +4898,61 @@ mod tests {
         assert_eq!(grain.day, Some("cal_day".into()));
     }
 
+    #[test]
+    fn test_table_options() {
+        let lua = r#"
+            source("orders"):from("raw.orders")
+            source("customers"):from("raw.customers")
+
+            fact("fact_orders")
+                :target("analytics.fact_orders")
+                :grain({ "orders.order_id" })
+                :table_options({
+                    partition_by = { "order_date" },
+                    partition_granularity = "month",
+                    cluster_by = { "customer_id" },
+                })
+
+            dimension("dim_customers")
+                :target("analytics.dim_customers")
+                :from("customers")
+                :columns({ "customer_id", "region" })
+                :table_options({ dist_key = "customer_id", sort_key = { "region" } })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+
+        let fact = &model.facts["fact_orders"];
+        assert_eq!(fact.table_options.partition_by, vec!["order_date"]);
+        assert_eq!(
+            fact.table_options.partition_granularity,
+            Some(PartitionGranularity::Month)
+        );
+        assert_eq!(fact.table_options.cluster_by, vec!["customer_id"]);
+
+        let dim = &model.dimensions["dim_customers"];
+        assert_eq!(
+            dim.table_options.distribution,
+            Some(Distribution::Key("customer_id".into()))
+        );
+        assert_eq!(dim.table_options.sort_key, vec!["region"]);
+    }
+
+    #[test]
+    fn test_table_options_invalid_format() {
+        let lua = r#"
+            source("orders"):from("raw.orders")
+
+            fact("fact_orders")
+                :target("analytics.fact_orders")
+                :grain({ "orders.order_id" })
+                :table_options({ format = "orc" })
+        "#;
+
+        let err = LuaLoader::load_from_str(lua, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Invalid format 'orc'"));
+    }
+
     #[test]
     fn test_load_fact() {
         let lua = r#"
@@ -4338,6 +5009,85 @@ mod tests {
         assert_eq!(dim.scd_type, SCDType::Type1);
     }
 
+    #[test]
+    fn test_load_dimension_hierarchies() {
+        let lua = r#"
+            source("stores"):from("raw.stores")
+
+            dimension("dim_stores")
+                :target("analytics.dim_stores")
+                :from("stores")
+                :columns({ "store_id", "country", "region", "city", "opened_on" })
+                :hierarchy("geography", { "country", "region", "city" })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        let dim = &model.dimensions["dim_stores"];
+        assert_eq!(dim.hierarchies.len(), 1);
+        assert_eq!(
+            dim.hierarchy("geography").unwrap().levels,
+            vec!["country", "region", "city"]
+        );
+
+        let duplicate = format!("{}\n:hierarchy(\"geography\", {{ \"country\" }})", lua);
+        let err = LuaLoader::load_from_str(&duplicate, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Hierarchy 'geography' is defined twice"));
+
+        let unknown = lua.replace("\"city\" })", "\"town\" })");
+        let err = LuaLoader::load_from_str(&unknown, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Unknown column 'dim_stores.town'"));
+    }
+
+    #[test]
+    fn test_load_dimension_surrogate_key_and_unknown_member() {
+        use crate::model::{SurrogateKey, SurrogateKeyStrategy};
+
+        let lua = r#"
+            source("customers"):from("raw.customers")
+
+            dimension("dim_customers")
+                :target("analytics.dim_customers")
+                :from("customers")
+                :columns({ "customer_id", "name", "segment" })
+                :primary_key({ "customer_id" })
+                :surrogate_key("customer_sk", "hash")
+                :unknown_member({ key = 0, values = { segment = "n/a" } })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua, "test.lua").unwrap();
+        let dim = &model.dimensions["dim_customers"];
+        assert_eq!(
+            dim.surrogate_key,
+            Some(SurrogateKey {
+                column: "customer_sk".into(),
+                strategy: SurrogateKeyStrategy::Hash,
+            })
+        );
+        let member = dim.unknown_member.as_ref().unwrap();
+        assert_eq!(member.key, 0);
+        assert_eq!(member.value("name"), "Unknown");
+        assert_eq!(member.value("segment"), "n/a");
+
+        let identity = lua.replace(", \"hash\")", ")");
+        let model = LuaLoader::load_from_str(&identity, "test.lua").unwrap();
+        assert_eq!(
+            model.dimensions["dim_customers"].surrogate_key.as_ref().unwrap().strategy,
+            SurrogateKeyStrategy::Identity
+        );
+
+        let bad = lua.replace("\"hash\"", "\"uuid\"");
+        let err = LuaLoader::load_from_str(&bad, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Unknown surrogate key strategy 'uuid'"));
+
+        let clash = lua.replace("\"customer_sk\"", "\"name\"");
+        let err = LuaLoader::load_from_str(&clash, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Duplicate column in dimension 'dim_customers' name: 'name'"));
+
+        let unknown = lua.replace("segment = ", "tier = ");
+        let err = LuaLoader::load_from_str(&unknown, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("Unknown column 'dim_customers.tier'"));
+    }
+
     #[test]
     fn test_validation_error() {
         let lua = r#"
@@ -5106,4 +5856,111 @@ LIMIT 50"#,
         assert!(table.filter.is_some());
         assert_eq!(table.columns.len(), 2);
     }
+
+    #[test]
+    fn test_table_with_sql_body() {
+        let lua_code = r#"
+            source("orders"):from("raw.orders")
+            source("customers"):from("raw.customers")
+
+            table("customer_orders", {
+                sql = [[
+                    SELECT o.order_id, c.name
+                    FROM orders o
+                    JOIN customers c ON o.customer_id = c.id
+                ]],
+            })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua_code, "test.lua").unwrap();
+        let table = model.tables.get("customer_orders").unwrap();
+
+        assert!(table.sql.is_some());
+        assert_eq!(table.from.primary(), "orders");
+        assert_eq!(table.sql_sources, vec!["orders", "customers"]);
+    }
+
+    #[test]
+    fn test_table_sql_body_dialect_and_dependencies() {
+        let lua_code = r#"
+            source("orders"):from("raw.orders")
+            source("customers"):from("raw.customers")
+
+            table("stg_customers", { from = "customers", columns = { "id", "name" } })
+
+            table("customer_orders", {
+                dialect = "tsql",
+                sql = [[
+                    SELECT TOP 10 [o].[order_id], [c].[name]
+                    FROM orders AS o
+                    JOIN stg_customers AS c ON [o].[customer_id] = [c].[id]
+                ]],
+            })
+        "#;
+
+        let model = LuaLoader::load_from_str(lua_code, "test.lua").unwrap();
+        let table = model.tables.get("customer_orders").unwrap();
+        assert_eq!(table.sql_dialect(), Dialect::TSql);
+        let body = table.sql.as_deref().unwrap();
+        assert!(crate::query::Query::parse(body, Dialect::DuckDb).is_err());
+
+        // The joined table is built first, though it isn't the body's first table
+        let graph = crate::semantic::ModelGraph::from_model(model).unwrap();
+        assert!(graph.depends_on("customer_orders", "stg_customers"));
+        let order = graph.topological_order().unwrap();
+        let position = |name: &str| order.iter().position(|t| t == name).unwrap();
+        assert!(position("stg_customers") < position("customer_orders"));
+
+        let unknown = r#"
+            source("orders"):from("raw.orders")
+            table("bad", { dialect = "oracle", sql = "SELECT * FROM orders" })
+        "#;
+        let err = LuaLoader::load_from_str(unknown, "test.lua").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown dialect 'oracle'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_table_with_invalid_sql_body() {
+        let lua_code = r#"
+            source("orders"):from("raw.orders")
+
+            table("bad", { sql = "SELECT * FROM (SELECT 1) t" })
+        "#;
+
+        let err = LuaLoader::load_from_str(lua_code, "test.lua").unwrap_err();
+        assert!(err.to_string().contains("table 'bad'"), "{}", err);
+    }
+
+    #[test]
+    fn test_prelude_dateadd_lowers_in_argument_order() {
+        use crate::dialect::Dialect;
+        use crate::semantic::planner::emit::convert_model_expr;
+
+        let lua = Lua::new();
+        lua.load(LuaLoader::PRELUDE).exec().unwrap();
+        let value: Value = lua
+            .load(r#"dateadd("day", 7, col("order_date"))"#)
+            .eval()
+            .unwrap();
+        let expr = convert_model_expr(&parse_expr(value, "test").unwrap());
+
+        let render = |dialect: Dialect| expr.to_tokens_for_dialect(dialect).serialize(dialect);
+        assert_eq!(render(Dialect::TSql), "DATEADD(DAY, 7, [order_date])");
+        assert_eq!(
+            render(Dialect::Postgres),
+            "\"order_date\" + INTERVAL '7 day'"
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let sql = format!(
+            "SELECT {} FROM (SELECT '2024-02-26' AS order_date)",
+            render(Dialect::Sqlite)
+        );
+        let due: String = conn.query_row(&sql, [], |row| row.get(0)).unwrap();
+        assert!(due.starts_with("2024-03-04"), "{}", due);
+    }
 }
@@ -432,6 +432,24 @@ function link_as(from_ref, to_ref, role_name, card)
     }
 end
 
+--- Define a band relationship: the value falls between the two bounds
+-- @param value_ref Value reference (entity.column)
+-- @param lower_ref Lower bound reference (entity.column, inclusive)
+-- @param upper_ref Upper bound reference (entity.column, inclusive)
+-- @param card Cardinality (optional, defaults to MANY_TO_ONE)
+--
+-- Example:
+--   link_range(orders.amount, tiers.min_amount, tiers.max_amount)
+--
+-- Use relationship { range = { ..., exclusive_upper = true } } for
+-- half-open bands, or to combine a band with an equality key.
+function link_range(value_ref, lower_ref, upper_ref, card)
+    relationship {
+        range = { value = value_ref, lower = lower_ref, upper = upper_ref },
+        cardinality = card or MANY_TO_ONE,
+    }
+end
+
 --- Define a many-to-one relationship
 function many_to_one(from_ref, to_ref)
     link(from_ref, to_ref, MANY_TO_ONE)
@@ -42,24 +42,35 @@
 //!     );
 //! ```
 
+pub mod bridge;
+pub mod date_dimension;
 pub mod dimension;
+pub mod dimension_build;
 pub mod dimension_role;
 pub mod emitter;
 pub mod expr;
 pub mod fact;
 pub mod loader;
+pub mod parent_child;
 pub mod pivot_report;
 pub mod query;
 pub mod report;
 pub mod source;
 pub mod table;
+pub mod target;
 pub mod types;
 
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-pub use dimension::{DimensionColumn, DimensionDefinition, SCDType};
+pub use bridge::BridgeDefinition;
+pub use date_dimension::{DateDimension, DateDimensionError, HolidayTable};
+pub use dimension::{
+    DimensionColumn, DimensionDefinition, Hierarchy, SCDType, SurrogateKey, SurrogateKeyStrategy,
+    UnknownMember,
+};
+pub use dimension_build::{DimensionBuild, DimensionBuildError};
 pub use dimension_role::{DateConfig, DimensionRole, GrainColumns, TimeGrain};
 pub use expr::{
     BinaryOp, ColumnDef, Expr, FrameBound, FrameKind, Func, IntervalUnit, Literal, NullsOrder,
@@ -69,6 +80,7 @@ pub use fact::{
     ColumnSelection, DimensionInclude, FactDefinition, GrainColumn, MeasureDefinition,
     WindowColumnDef,
 };
+pub use parent_child::{ParentChildDimension, ParentChildError};
 pub use pivot_report::{PivotColumns, PivotReport, PivotSort, PivotValue, SortDirection, TotalsConfig};
 pub use query::{
     DerivedExpression, DerivedOp, QueryDefinition, QueryFilter, QueryFilterOp, QueryFilterValue,
@@ -77,7 +89,11 @@ pub use query::{
 pub use report::{MeasureRef, RefreshDelta, Report, ReportDefaults, ReportMaterialization, ReportTableType};
 pub use source::{ChangeTracking, DedupConfig, DedupKeep, SourceColumn, SourceEntity};
 pub use table::{FromClause, JoinDef, JoinType, TableDefinition, TableTypeLabel, UnionType};
-pub use types::{AggregationType, DataType, MaterializationStrategy, TableType};
+pub use target::TargetColumn;
+pub use types::{
+    AggregationType, DataType, Distribution, MaterializationStrategy, PartitionGranularity,
+    TableFormat, TableOptions, TableType,
+};
 
 /// Cardinality of a relationship between entities.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -213,6 +229,12 @@ pub struct Relationship {
     pub from_column: String,
     /// Join column in target entity
     pub to_column: String,
+    /// Further `(from, to)` column pairs for composite keys.
+    ///
+    /// Empty for single-column relationships. Together with
+    /// `from_column`/`to_column` these make up the full join key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_columns: Vec<(String, String)>,
     /// Cardinality of the relationship
     pub cardinality: Cardinality,
     /// How this relationship was discovered/defined.
@@ -227,6 +249,24 @@ pub struct Relationship {
     /// Example: `link(orders.order_date_id, date.date_id):as("order_date")`
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub role: Option<String>,
+    /// Whether a `from` row may have no matching `to` row.
+    ///
+    /// Optional relationships are joined with LEFT JOIN when walked from
+    /// `from` to `to`; required ones (the default) with INNER JOIN.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Extra join predicates ANDed into the ON clause, e.g. `status = 'active'`.
+    ///
+    /// Column references are qualified with their entity; see
+    /// [`Relationship::with_predicate`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub predicates: Vec<expr::Expr>,
+    /// Band condition, e.g. `orders.amount BETWEEN tiers.min AND tiers.max`.
+    ///
+    /// May replace the column-equality key entirely, in which case
+    /// `from_column`/`to_column` are empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<RangeCondition>,
 }
 
 impl Relationship {
@@ -243,10 +283,38 @@ impl Relationship {
             to_entity: to_entity.into(),
             from_column: from_column.into(),
             to_column: to_column.into(),
+            additional_columns: Vec::new(),
             cardinality,
             source: RelationshipSource::Explicit,
             role: None,
+            optional: false,
+            predicates: Vec::new(),
+            range: None,
+        }
+    }
+
+    /// Create a relationship over a composite key (explicitly defined).
+    ///
+    /// `columns` holds the `(from, to)` column pairs in key order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is empty.
+    pub fn composite<F: Into<String>, T: Into<String>>(
+        from_entity: impl Into<String>,
+        to_entity: impl Into<String>,
+        columns: Vec<(F, T)>,
+        cardinality: Cardinality,
+    ) -> Self {
+        let mut pairs = columns.into_iter();
+        let (from_column, to_column) = pairs
+            .next()
+            .expect("composite relationship needs at least one column pair");
+        let mut rel = Self::new(from_entity, to_entity, from_column, to_column, cardinality);
+        for (from, to) in pairs {
+            rel = rel.with_key_column(from, to);
         }
+        rel
     }
 
     /// Create a relationship with a specific source.
@@ -263,12 +331,41 @@ impl Relationship {
             to_entity: to_entity.into(),
             from_column: from_column.into(),
             to_column: to_column.into(),
+            additional_columns: Vec::new(),
             cardinality,
             source,
             role: None,
+            optional: false,
+            predicates: Vec::new(),
+            range: None,
         }
     }
 
+    /// Create a relationship joined only by a band condition (explicitly defined).
+    ///
+    /// `value_column` on `from_entity` falls between `lower_column` and
+    /// `upper_column` on `to_entity`.
+    pub fn range(
+        from_entity: impl Into<String>,
+        to_entity: impl Into<String>,
+        value_column: impl Into<String>,
+        lower_column: impl Into<String>,
+        upper_column: impl Into<String>,
+        cardinality: Cardinality,
+    ) -> Self {
+        Self::new(from_entity, to_entity, "", "", cardinality).with_range(RangeCondition::new(
+            value_column,
+            lower_column,
+            upper_column,
+        ))
+    }
+
+    /// Add another column pair to the join key.
+    pub fn with_key_column(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.additional_columns.push((from.into(), to.into()));
+        self
+    }
+
     /// Set a role name for this relationship.
     ///
     /// Used for role-playing dimensions where the same dimension is
@@ -278,6 +375,28 @@ impl Relationship {
         self
     }
 
+    /// Mark the `to` side as optional (LEFT JOIN instead of INNER JOIN).
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Add a join predicate.
+    ///
+    /// Unqualified columns are taken to belong to `to_entity` and are
+    /// qualified here, so the predicate reads the same in either direction.
+    pub fn with_predicate(mut self, mut predicate: expr::Expr) -> Self {
+        predicate.qualify_columns(&self.to_entity);
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Add a band condition to the join.
+    pub fn with_range(mut self, range: RangeCondition) -> Self {
+        self.range = Some(range);
+        self
+    }
+
     /// Create a relationship from a foreign key constraint.
     pub fn from_foreign_key(
         from_entity: impl Into<String>,
@@ -323,12 +442,39 @@ impl Relationship {
             to_entity: self.from_entity.clone(),
             from_column: self.to_column.clone(),
             to_column: self.from_column.clone(),
+            additional_columns: self
+                .additional_columns
+                .iter()
+                .map(|(from, to)| (to.clone(), from.clone()))
+                .collect(),
             cardinality: self.cardinality.reverse(),
             source: self.source.clone(),
             role: self.role.clone(),
+            optional: false,
+            predicates: self.predicates.clone(),
+            range: self.range.as_ref().map(RangeCondition::reversed),
         }
     }
 
+    /// All `(from, to)` join column pairs, in key order.
+    ///
+    /// Empty for relationships joined only by a band condition.
+    pub fn column_pairs(&self) -> Vec<(&str, &str)> {
+        std::iter::once((self.from_column.as_str(), self.to_column.as_str()))
+            .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+            .chain(
+                self.additional_columns
+                    .iter()
+                    .map(|(from, to)| (from.as_str(), to.as_str())),
+            )
+            .collect()
+    }
+
+    /// Whether the join key spans more than one column.
+    pub fn is_composite(&self) -> bool {
+        !self.additional_columns.is_empty()
+    }
+
     /// Check if this relationship has a role assigned.
     pub fn has_role(&self) -> bool {
         self.role.is_some()
@@ -340,11 +486,73 @@ impl Relationship {
     }
 }
 
+/// A band join condition: a value on one side falls between a lower and an
+/// upper bound column on the other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeCondition {
+    /// Column holding the value being banded.
+    pub value_column: String,
+    /// Lower bound column (inclusive).
+    pub lower_column: String,
+    /// Upper bound column.
+    pub upper_column: String,
+    /// Whether the upper bound is exclusive (`value < upper`) rather than
+    /// inclusive (`BETWEEN`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive_upper: bool,
+    /// Whether the bounds are on the `from` side and the value on the `to`
+    /// side. False for relationships as declared; set when reversed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bounds_on_from: bool,
+}
+
+impl RangeCondition {
+    /// `value_column BETWEEN lower_column AND upper_column`, with the value on
+    /// the `from` side.
+    pub fn new(
+        value_column: impl Into<String>,
+        lower_column: impl Into<String>,
+        upper_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            value_column: value_column.into(),
+            lower_column: lower_column.into(),
+            upper_column: upper_column.into(),
+            exclusive_upper: false,
+            bounds_on_from: false,
+        }
+    }
+
+    /// Make the upper bound exclusive (`lower <= value < upper`).
+    pub fn exclusive_upper(mut self) -> Self {
+        self.exclusive_upper = true;
+        self
+    }
+
+    /// The same condition seen from the other side of the join.
+    pub fn reversed(&self) -> Self {
+        Self {
+            bounds_on_from: !self.bounds_on_from,
+            ..self.clone()
+        }
+    }
+
+    /// `(value entity, bounds entity)` for a join from `from` to `to`.
+    pub fn sides<'a>(&self, from: &'a str, to: &'a str) -> (&'a str, &'a str) {
+        if self.bounds_on_from {
+            (to, from)
+        } else {
+            (from, to)
+        }
+    }
+}
+
 /// The unified semantic model.
 ///
 /// Contains all definitions needed for both transform and query planning:
 /// - **Sources**: Raw data from upstream systems
 /// - **Relationships**: How entities connect (used for JOINs)
+/// - **Bridges**: Entities resolving many-to-many relationships
 /// - **Facts**: Denormalized fact tables to materialize
 /// - **Dimensions**: Conformed dimension tables to materialize
 /// - **Intermediates**: Ephemeral staging tables for transformations
@@ -358,6 +566,10 @@ pub struct Model {
     /// Relationships between entities
     pub relationships: Vec<Relationship>,
 
+    /// Bridge entities resolving many-to-many relationships, keyed by entity
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub bridges: HashMap<String, BridgeDefinition>,
+
     /// Fact definitions (targets for transform planner)
     pub facts: HashMap<String, FactDefinition>,
 
@@ -448,6 +660,17 @@ impl Model {
         self.tables.values().filter(|t| t.tags.contains(&tag.to_string())).collect()
     }
 
+    /// Declare a bridge entity.
+    pub fn with_bridge(mut self, bridge: BridgeDefinition) -> Self {
+        self.bridges.insert(bridge.entity.clone(), bridge);
+        self
+    }
+
+    /// Declare a bridge entity (mutable).
+    pub fn add_bridge(&mut self, bridge: BridgeDefinition) {
+        self.bridges.insert(bridge.entity.clone(), bridge);
+    }
+
     /// Add a report definition.
     pub fn with_report(mut self, report: Report) -> Self {
         self.reports.insert(report.name.clone(), report);
@@ -496,6 +719,56 @@ impl Model {
         self.dimensions.get(name)
     }
 
+    /// Hierarchies that can be drilled on `entity`, with their level columns
+    /// as `entity` names them.
+    ///
+    /// `entity` is either a dimension or the entity a dimension is built
+    /// from; in the latter case levels are mapped back to source columns and
+    /// hierarchies with an unmapped level are skipped. A dimension's own
+    /// hierarchies come first, then those of dimensions built from it, in
+    /// dimension name order.
+    pub fn hierarchies_on(&self, entity: &str) -> Vec<(&Hierarchy, Vec<String>)> {
+        let mut result = Vec::new();
+        if let Some(dim) = self.dimensions.get(entity) {
+            result.extend(dim.hierarchies.iter().map(|h| (h, h.levels.clone())));
+        }
+
+        let mut built_from: Vec<&DimensionDefinition> = self
+            .dimensions
+            .values()
+            .filter(|d| d.source_entity == entity && d.name != entity)
+            .collect();
+        built_from.sort_by_key(|d| d.name.as_str());
+        for dim in built_from {
+            for hierarchy in &dim.hierarchies {
+                let levels: Option<Vec<String>> = hierarchy
+                    .levels
+                    .iter()
+                    .map(|level| dim.source_column(level).map(str::to_string))
+                    .collect();
+                if let Some(levels) = levels {
+                    result.push((hierarchy, levels));
+                }
+            }
+        }
+        result
+    }
+
+    /// Level columns of the hierarchy named `hierarchy` on `entity`.
+    ///
+    /// See [`Model::hierarchies_on`].
+    pub fn hierarchy_levels(&self, entity: &str, hierarchy: &str) -> Option<Vec<String>> {
+        self.hierarchies_on(entity)
+            .into_iter()
+            .find(|(h, _)| h.name == hierarchy)
+            .map(|(_, levels)| levels)
+    }
+
+    /// Get the bridge declared for an entity.
+    pub fn get_bridge(&self, entity: &str) -> Option<&BridgeDefinition> {
+        self.bridges.get(entity)
+    }
+
     /// Get a report by name.
     pub fn get_report(&self, name: &str) -> Option<&Report> {
         self.reports.get(name)
@@ -646,6 +919,62 @@ impl Model {
                     context: format!("dimension '{}' source_entity", dim.name),
                 });
             }
+            for hierarchy in &dim.hierarchies {
+                let context = format!("dimension '{}' hierarchy '{}'", dim.name, hierarchy.name);
+                for (i, level) in hierarchy.levels.iter().enumerate() {
+                    if !dim.columns.iter().any(|c| c.target_name() == level) {
+                        return Err(ModelError::UnknownColumn {
+                            entity: dim.name.clone(),
+                            column: level.clone(),
+                            context,
+                        });
+                    }
+                    if hierarchy.levels[..i].contains(level) {
+                        return Err(ModelError::DuplicateName {
+                            name: level.clone(),
+                            kind: format!("level in {}", context),
+                        });
+                    }
+                }
+            }
+            if let Some(key) = &dim.surrogate_key {
+                if dim.columns.iter().any(|c| c.target_name() == key.column) {
+                    return Err(ModelError::DuplicateName {
+                        name: key.column.clone(),
+                        kind: format!("column in dimension '{}'", dim.name),
+                    });
+                }
+            }
+            if let Some(member) = &dim.unknown_member {
+                for (column, _) in &member.values {
+                    if !dim.columns.iter().any(|c| c.target_name() == column) {
+                        return Err(ModelError::UnknownColumn {
+                            entity: dim.name.clone(),
+                            column: column.clone(),
+                            context: format!("dimension '{}' unknown member", dim.name),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Check that bridges are sources and their weight columns exist
+        for bridge in self.bridges.values() {
+            let Some(source) = self.sources.get(&bridge.entity) else {
+                return Err(ModelError::UnknownEntity {
+                    name: bridge.entity.clone(),
+                    context: "bridge".into(),
+                });
+            };
+            if let Some(weight) = &bridge.weight_column {
+                if !source.columns.is_empty() && !source.columns.contains_key(weight) {
+                    return Err(ModelError::UnknownColumn {
+                        entity: bridge.entity.clone(),
+                        column: weight.clone(),
+                        context: format!("bridge '{}' weight", bridge.entity),
+                    });
+                }
+            }
         }
 
         // Check that table 'from' references exist
@@ -676,16 +1005,71 @@ impl Model {
     /// let cache_key = CacheKey::lineage(&hash);
     /// ```
     pub fn content_hash(&self) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+        hash_canonical(self)
+    }
+
+    /// Compute a hash of a single buildable definition (fact, dimension,
+    /// table, report or pivot report).
+    ///
+    /// Unlike [`Model::content_hash`], this only changes when the named
+    /// definition itself changes. Returns `None` for unknown names.
+    pub fn definition_hash(&self, name: &str) -> Option<String> {
+        if let Some(fact) = self.facts.get(name) {
+            Some(hash_canonical(fact))
+        } else if let Some(dim) = self.dimensions.get(name) {
+            Some(hash_canonical(dim))
+        } else if let Some(table) = self.tables.get(name) {
+            Some(hash_canonical(table))
+        } else if let Some(report) = self.reports.get(name) {
+            Some(hash_canonical(report))
+        } else {
+            self.pivot_reports.get(name).map(hash_canonical)
+        }
+    }
+}
 
-        // Serialize to JSON (canonical form)
-        let json = serde_json::to_string(self).unwrap_or_default();
+/// SHA-256 of the value's JSON with object keys sorted, so hashes are stable
+/// across loads (`HashMap` iteration order) and Rust releases.
+fn hash_canonical(value: &impl Serialize) -> String {
+    let value = serde_json::to_value(value).unwrap_or_default();
+    let mut json = String::new();
+    write_canonical_json(&value, &mut json);
+
+    let digest = ring::digest::digest(&ring::digest::SHA256, json.as_bytes());
+    digest
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
 
-        // Hash the JSON
-        let mut hasher = DefaultHasher::new();
-        json.hash(&mut hasher);
-        format!("{:016x}", hasher.finish())
+fn write_canonical_json(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
     }
 }
 
@@ -850,6 +1234,75 @@ mod tests {
         assert_eq!(Cardinality::OneToOne.reverse(), Cardinality::OneToOne);
     }
 
+    #[test]
+    fn test_relationship_join_options() {
+        let rel = Relationship::range("orders", "tiers", "amount", "min_amount", "max_amount", Cardinality::ManyToOne)
+            .optional()
+            .with_predicate(Expr::column("active").eq(Expr::bool(true)))
+            .with_predicate(Expr::qualified_column("orders", "status").ne(Expr::string("void")));
+
+        // Range-only relationships have no equality key
+        assert!(rel.column_pairs().is_empty());
+        // Unqualified predicate columns belong to the target
+        assert_eq!(
+            rel.predicates[0],
+            Expr::qualified_column("tiers", "active").eq(Expr::bool(true))
+        );
+        assert_eq!(
+            rel.predicates[1],
+            Expr::qualified_column("orders", "status").ne(Expr::string("void"))
+        );
+
+        let reversed = rel.reverse();
+        assert!(!reversed.optional);
+        assert_eq!(reversed.predicates, rel.predicates);
+        let range = reversed.range.as_ref().unwrap();
+        assert_eq!(range.sides("tiers", "orders"), ("orders", "tiers"));
+
+        let json = serde_json::to_string(&rel).unwrap();
+        let deserialized: Relationship = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.optional);
+        assert_eq!(deserialized.range, rel.range);
+        assert_eq!(deserialized.predicates, rel.predicates);
+    }
+
+    #[test]
+    fn test_hierarchies_on() {
+        let model = Model::new()
+            .with_source(SourceEntity::new("stores", "raw.stores"))
+            .with_dimension(
+                DimensionDefinition::new("dim_stores", "dim_stores", "stores")
+                    .with_column("country")
+                    .with_column_as("region_name", "region")
+                    .with_column("city")
+                    .with_column("store_id")
+                    .with_hierarchy("geography", vec!["country", "region", "city"])
+                    .with_hierarchy("store", vec!["store_id"]),
+            );
+
+        assert_eq!(
+            model.hierarchy_levels("dim_stores", "geography"),
+            Some(vec!["country".into(), "region".into(), "city".into()])
+        );
+        assert_eq!(
+            model.hierarchy_levels("stores", "geography"),
+            Some(vec!["country".into(), "region_name".into(), "city".into()])
+        );
+        assert_eq!(model.hierarchies_on("stores").len(), 2);
+        assert!(model.hierarchy_levels("stores", "calendar").is_none());
+        assert!(model.hierarchies_on("orders").is_empty());
+        assert!(model.validate().is_ok());
+
+        let mut invalid = model.clone();
+        invalid.dimensions.get_mut("dim_stores").unwrap().hierarchies[0]
+            .levels
+            .push("country".into());
+        assert!(matches!(
+            invalid.validate(),
+            Err(ModelError::DuplicateName { name, .. }) if name == "country"
+        ));
+    }
+
     #[test]
     fn test_model_serialization() {
         let model = sample_model();
@@ -859,4 +1312,32 @@ mod tests {
         assert_eq!(deserialized.sources.len(), model.sources.len());
         assert_eq!(deserialized.facts.len(), model.facts.len());
     }
+
+    #[test]
+    fn test_hashes_stable_across_loads() {
+        let lua = r#"
+            source("orders"):from("raw.orders")
+            source("customers"):from("raw.customers")
+            link(orders.customer_id, customers.customer_id)
+
+            fact("fact_orders")
+                :target("analytics.fact_orders")
+                :grain({ "orders.order_id" })
+                :measure("revenue", sum("total"))
+                :measure("tax", sum("tax"))
+                :measure("discount", sum("discount"))
+                :measure("shipping", sum("shipping"))
+                :measure("quantity", sum("quantity"))
+                :measure("orders", count("order_id"))
+        "#;
+        let load = || crate::model::loader::load_model_from_str(lua, "test.lua").unwrap();
+
+        let first = load();
+        let second = load();
+        assert_eq!(first.content_hash(), second.content_hash());
+        let hash = first.definition_hash("fact_orders").unwrap();
+        assert_eq!(second.definition_hash("fact_orders"), Some(hash.clone()));
+        assert_eq!(hash.len(), 64);
+        assert_eq!(first.definition_hash("missing"), None);
+    }
 }
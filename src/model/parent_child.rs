@@ -0,0 +1,497 @@
+//! Parent-child (recursive) dimensions.
+//!
+//! Org charts and account trees store each node with a reference to its
+//! parent (`id`/`parent_id`). [`ParentChildDimension`] flattens such a tree
+//! into a level table with a recursive CTE: one row per node with its depth,
+//! a path string from the root, and one `level_N` column per depth holding
+//! the node's ancestor at that depth. Nodes shallower than the deepest level
+//! repeat themselves in the levels below them, so grouping by any `level_N`
+//! rolls every measure up to its ancestor at that depth without NULL groups.
+//!
+//! Recursion stops at `max_depth`, which also guards against cycles in the
+//! data. Nodes below it are left out, so it must cover the deepest node.
+//! MySQL has no unbounded string cast, so paths there are cut at
+//! [`MYSQL_PATH_LENGTH`] characters.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let org = ParentChildDimension::new("org", "analytics.dim_org", "hr.employees", "employee_id", "manager_id")
+//!     .with_label_column("name")
+//!     .with_max_depth(6);
+//! let ddl = org.create_table(Dialect::Postgres)?.to_sql(Dialect::Postgres);
+//! org.register(&mut model);
+//! model.add_dimension(org.dimension("dim_org"));
+//! ```
+
+use thiserror::Error;
+
+use super::dimension::DimensionDefinition;
+use super::source::{SourceColumn, SourceEntity};
+use super::target::TargetColumn;
+use super::types::{DataType, TableOptions};
+use super::Model;
+use crate::sql::ddl::{CreateTable, DdlError};
+use crate::sql::dialect::{Dialect, DialectTarget};
+use crate::sql::expr::{cast, col, lit_int, lit_str, table_col, Expr, ExprExt};
+use crate::sql::query::{Cte, Query, SelectExpr, TableRef};
+
+/// Name of the recursive CTE walking the tree.
+const TREE: &str = "tree";
+
+/// Alias of the source table inside the CTE.
+const NODE: &str = "node";
+
+/// Longest path kept on MySQL.
+pub const MYSQL_PATH_LENGTH: u16 = 1000;
+
+/// Name of the hierarchy over the level columns.
+pub const LEVELS_HIERARCHY: &str = "levels";
+
+/// Errors from generating a parent-child dimension.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ParentChildError {
+    #[error("parent-child dimension '{0}' needs a max depth of at least 1")]
+    InvalidMaxDepth(String),
+    #[error("invalid table options: {0}")]
+    TableOptions(#[from] DdlError),
+}
+
+/// A tree stored as `id`/`parent_id` rows, flattened into a level table.
+#[derive(Debug, Clone)]
+pub struct ParentChildDimension {
+    /// Entity name queries refer to (e.g. "org")
+    pub name: String,
+    /// Physical target table (e.g. "analytics.dim_org")
+    pub table: String,
+    /// Physical table holding the tree, optionally schema-qualified
+    pub source_table: String,
+    /// Node key column, copied into the target
+    pub id_column: String,
+    /// Column referencing the parent's key; NULL on root nodes
+    pub parent_column: String,
+    /// Type of the key columns
+    pub key_type: DataType,
+    /// Column naming each node in the path; the key when unset
+    pub label_column: Option<String>,
+    /// Number of levels to flatten
+    pub max_depth: usize,
+    /// Separator between names in the path
+    pub path_separator: String,
+    /// Partitioning, clustering and storage options for the target table
+    pub table_options: TableOptions,
+}
+
+impl ParentChildDimension {
+    /// Flatten the tree in `source_table` linked by `parent_column` to `id_column`.
+    pub fn new(
+        name: impl Into<String>,
+        table: impl Into<String>,
+        source_table: impl Into<String>,
+        id_column: impl Into<String>,
+        parent_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            table: table.into(),
+            source_table: source_table.into(),
+            id_column: id_column.into(),
+            parent_column: parent_column.into(),
+            key_type: DataType::Int64,
+            label_column: None,
+            max_depth: 5,
+            path_separator: "/".into(),
+            table_options: TableOptions::default(),
+        }
+    }
+
+    /// Set the type of the key columns.
+    pub fn with_key_type(mut self, data_type: DataType) -> Self {
+        self.key_type = data_type;
+        self
+    }
+
+    /// Name nodes in the path by `column`, which is also copied into the target.
+    pub fn with_label_column(mut self, column: impl Into<String>) -> Self {
+        self.label_column = Some(column.into());
+        self
+    }
+
+    /// Flatten `depth` levels.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Separate names in the path with `separator`.
+    pub fn with_path_separator(mut self, separator: impl Into<String>) -> Self {
+        self.path_separator = separator.into();
+        self
+    }
+
+    /// Set partitioning, clustering and storage options for the target table.
+    pub fn with_table_options(mut self, options: TableOptions) -> Self {
+        self.table_options = options;
+        self
+    }
+
+    /// Name of the column holding each node's ancestor at `depth` (1 = root).
+    pub fn level_column(depth: usize) -> String {
+        format!("level_{}", depth)
+    }
+
+    /// Level columns, root first.
+    pub fn level_columns(&self) -> Vec<String> {
+        (1..=self.max_depth).map(Self::level_column).collect()
+    }
+
+    /// Columns of the generated table, in order.
+    pub fn columns(&self) -> Vec<TargetColumn> {
+        let column = |name: &str, data_type: DataType, description: &str| TargetColumn {
+            name: name.to_string(),
+            data_type: Some(data_type),
+            description: Some(description.to_string()),
+        };
+        let mut columns = vec![
+            column(&self.id_column, self.key_type.clone(), "Node key"),
+            column(
+                &self.parent_column,
+                self.key_type.clone(),
+                "Parent node key",
+            ),
+        ];
+        if let Some(label) = &self.label_column {
+            columns.push(column(label, DataType::String, "Node name"));
+        }
+        columns.push(column(
+            "depth",
+            DataType::Int32,
+            "Depth in the tree (1 = root)",
+        ));
+        columns.push(column(
+            "path",
+            DataType::String,
+            "Names from the root down to the node",
+        ));
+        for depth in 1..=self.max_depth {
+            columns.push(column(
+                &Self::level_column(depth),
+                self.key_type.clone(),
+                &format!(
+                    "Ancestor at depth {}, or the node itself below its own depth",
+                    depth
+                ),
+            ));
+        }
+        columns
+    }
+
+    /// The SELECT producing one row per node.
+    pub fn query(&self, dialect: impl Into<DialectTarget>) -> Result<Query, ParentChildError> {
+        let dialect = dialect.into();
+        if self.max_depth == 0 {
+            return Err(ParentChildError::InvalidMaxDepth(self.name.clone()));
+        }
+
+        let node = |column: &str| table_col(NODE, column);
+        let tree = |column: &str| table_col(TREE, column);
+        // Both halves of the union must agree on the path's type
+        let path_type = match dialect.dialect {
+            Dialect::MySql => DataType::Char(MYSQL_PATH_LENGTH),
+            _ => DataType::String,
+        };
+        let name = || match &self.label_column {
+            Some(label) => cast(node(label), path_type.clone()),
+            None => cast(node(&self.id_column), path_type.clone()),
+        };
+        let source = match self.source_table.rsplit_once('.') {
+            Some((schema, table)) => TableRef::new(table).with_schema(schema),
+            None => TableRef::new(&self.source_table),
+        }
+        .with_alias(NODE);
+
+        let mut anchor: Vec<SelectExpr> = self.node_columns();
+        anchor.push(lit_int(1).into());
+        anchor.push(name().into());
+        anchor.extend((1..=self.max_depth).map(|_| node(&self.id_column).into()));
+
+        let mut step: Vec<SelectExpr> = self.node_columns();
+        step.push(tree("depth").add(lit_int(1)).into());
+        step.push(
+            cast(
+                tree("path")
+                    .concat(lit_str(&self.path_separator))
+                    .concat(name()),
+                path_type.clone(),
+            )
+            .into(),
+        );
+        // The child fills every level below its parent's depth
+        step.extend((1..=self.max_depth).map(|depth| {
+            let level = Self::level_column(depth);
+            case(
+                tree("depth").lt(lit_int(depth as i64)),
+                node(&self.id_column),
+                tree(&level),
+            )
+            .into()
+        }));
+
+        let anchor = Query::new()
+            .select(anchor)
+            .from(source.clone())
+            .filter(node(&self.parent_column).is_null());
+        let step = Query::new()
+            .select(step)
+            .from(source)
+            .inner_join(
+                TableRef::new(TREE),
+                node(&self.parent_column).eq(tree(&self.id_column)),
+            )
+            .filter(tree("depth").lt(lit_int(self.max_depth as i64)));
+        let body = Query {
+            set_op: Some(Box::new(anchor.union_all(step))),
+            ..Default::default()
+        };
+
+        let names: Vec<String> = self.columns().into_iter().map(|c| c.name).collect();
+        let cte =
+            Cte::recursive(TREE, body).with_columns(names.iter().map(String::as_str).collect());
+        Ok(Query::new()
+            .with_cte(cte)
+            .select(
+                names
+                    .iter()
+                    .map(|n| col(n).into())
+                    .collect::<Vec<SelectExpr>>(),
+            )
+            .from(TableRef::new(TREE)))
+    }
+
+    /// `CREATE TABLE ... AS SELECT` for the dimension, with its table
+    /// options and column descriptions.
+    pub fn create_table(
+        &self,
+        dialect: impl Into<DialectTarget>,
+    ) -> Result<CreateTable, ParentChildError> {
+        let dialect = dialect.into();
+        let query = self.query(dialect)?;
+        let table = match self.table.rsplit_once('.') {
+            Some((schema, table)) => CreateTable::new(table).schema(schema),
+            None => CreateTable::new(&self.table),
+        };
+        let comments = self
+            .columns()
+            .into_iter()
+            .filter_map(|c| Some((c.name, c.description?)));
+        let table = table
+            .as_select(query)
+            .options(self.table_options.clone())
+            .column_comments(comments);
+        table.validate(dialect)?;
+        Ok(table)
+    }
+
+    /// The entity facts join to by node key, reading the generated table.
+    pub fn entity(&self) -> SourceEntity {
+        let mut entity = SourceEntity::new(&self.name, &self.table)
+            .with_primary_key(vec![self.id_column.clone()]);
+        for column in self.columns() {
+            // Roots have no parent
+            let nullable = column.name == self.parent_column;
+            let data_type = column.data_type.unwrap_or(DataType::String);
+            let mut source_column = SourceColumn::new(&column.name, data_type, nullable);
+            source_column.description = column.description;
+            entity.columns.insert(column.name, source_column);
+        }
+        entity
+    }
+
+    /// A dimension over the entity whose [`LEVELS_HIERARCHY`] hierarchy
+    /// drills through the level columns.
+    pub fn dimension(&self, name: impl Into<String>) -> DimensionDefinition {
+        let columns: Vec<String> = self.columns().into_iter().map(|c| c.name).collect();
+        DimensionDefinition::new(name, &self.table, &self.name)
+            .with_columns(columns.iter().map(String::as_str).collect())
+            .with_primary_key(vec![self.id_column.as_str()])
+            .with_hierarchy(LEVELS_HIERARCHY, self.level_columns())
+    }
+
+    /// Register the dimension's entity in `model`, replacing any entity of
+    /// the same name.
+    pub fn register(&self, model: &mut Model) {
+        model.add_source(self.entity());
+    }
+
+    /// Key, parent and label of the current node.
+    fn node_columns(&self) -> Vec<SelectExpr> {
+        let mut columns = vec![
+            table_col(NODE, &self.id_column).into(),
+            table_col(NODE, &self.parent_column).into(),
+        ];
+        if let Some(label) = &self.label_column {
+            columns.push(table_col(NODE, label).into());
+        }
+        columns
+    }
+}
+
+fn case(condition: Expr, then: Expr, otherwise: Expr) -> Expr {
+    Expr::Case {
+        operand: None,
+        when_clauses: vec![(condition, then)],
+        else_clause: Some(Box::new(otherwise)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::test_utils::validate_sql;
+    use rusqlite::Connection;
+
+    fn org() -> ParentChildDimension {
+        ParentChildDimension::new("org", "dim_org", "employees", "employee_id", "manager_id")
+            .with_label_column("name")
+            .with_max_depth(3)
+    }
+
+    #[test]
+    fn test_invalid_max_depth() {
+        assert_eq!(
+            org()
+                .with_max_depth(0)
+                .query(Dialect::Postgres)
+                .unwrap_err(),
+            ParentChildError::InvalidMaxDepth("org".into())
+        );
+    }
+
+    #[test]
+    fn test_recursive_keyword_per_dialect() {
+        let sql = |dialect: Dialect| org().query(dialect).unwrap().to_sql(dialect);
+
+        assert!(sql(Dialect::Postgres).starts_with("WITH RECURSIVE \"tree\" (\"employee_id\", \"manager_id\", \"name\", \"depth\", \"path\", \"level_1\", \"level_2\", \"level_3\") AS ("));
+        assert!(sql(Dialect::MySql).starts_with("WITH RECURSIVE `tree`"));
+        assert!(sql(Dialect::MySql).contains("CAST(`node`.`name` AS CHAR(1000))"));
+        assert!(sql(Dialect::TSql).starts_with("WITH [tree] ("));
+        assert!(sql(Dialect::TSql).contains("UNION ALL"));
+    }
+
+    #[test]
+    fn test_sql_parses_in_every_dialect() {
+        let org = org().with_path_separator(" > ");
+        for dialect in [
+            Dialect::Postgres,
+            Dialect::DuckDb,
+            Dialect::MySql,
+            Dialect::TSql,
+            Dialect::Redshift,
+            Dialect::Databricks,
+            Dialect::Sqlite,
+            Dialect::Trino,
+        ] {
+            let sql = org.create_table(dialect).unwrap().to_sql(dialect);
+            validate_sql(&sql, dialect).unwrap_or_else(|e| panic!("{:?}: {}\n{}", dialect, e, sql));
+        }
+    }
+
+    #[test]
+    fn test_create_table_applies_options_and_comments() {
+        let org = org().with_table_options(TableOptions::new().with_sort_key(vec!["employee_id"]));
+
+        let table = org.create_table(Dialect::Redshift).unwrap();
+        let sql = table.to_sql(Dialect::Redshift);
+        assert!(sql.contains("SORTKEY (\"employee_id\")"), "{}", sql);
+        let comments = table.comment_statements(Dialect::Redshift);
+        assert_eq!(comments.len(), org.columns().len());
+        assert_eq!(
+            comments[0],
+            "COMMENT ON COLUMN \"dim_org\".\"employee_id\" IS 'Node key'"
+        );
+        assert_eq!(
+            org.create_table(Dialect::Postgres).unwrap_err(),
+            ParentChildError::TableOptions(DdlError::UnsupportedOption {
+                option: "SORTKEY",
+                dialect: Dialect::Postgres.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sqlite_tree_flattens() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE employees (employee_id INTEGER, manager_id INTEGER, name TEXT);
+             INSERT INTO employees VALUES
+                 (1, NULL, 'Ada'), (2, 1, 'Bob'), (3, 1, 'Cy'), (4, 2, 'Dee'), (5, 4, 'Eve');",
+        )
+        .unwrap();
+        let ddl = org()
+            .create_table(Dialect::Sqlite)
+            .unwrap()
+            .to_sql(Dialect::Sqlite);
+        conn.execute_batch(&ddl).unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT employee_id, depth, path, level_1, level_2, level_3 \
+                 FROM dim_org ORDER BY employee_id",
+            )
+            .unwrap();
+        let rows: Vec<String> = stmt
+            .query_map([], |row| {
+                Ok(format!(
+                    "{}|{}|{}|{}|{}|{}",
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, i64>(5)?
+                ))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        // Eve sits below the third level and is left out
+        assert_eq!(
+            rows,
+            vec![
+                "1|1|Ada|1|1|1",
+                "2|2|Ada/Bob|1|2|2",
+                "3|2|Ada/Cy|1|3|3",
+                "4|3|Ada/Bob/Dee|1|2|4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_register_and_dimension() {
+        let org = org();
+        let mut model = Model::new();
+        org.register(&mut model);
+        model.add_dimension(org.dimension("dim_org"));
+
+        let entity = model.get_source("org").unwrap();
+        assert_eq!(entity.table, "dim_org");
+        assert_eq!(entity.primary_key, vec!["employee_id"]);
+        assert!(entity.get_column("manager_id").unwrap().nullable);
+        assert_eq!(
+            entity.get_column("level_2").unwrap().data_type,
+            DataType::Int64
+        );
+        assert_eq!(entity.columns.len(), org.columns().len());
+
+        assert_eq!(
+            model.hierarchy_levels("org", LEVELS_HIERARCHY),
+            Some(vec![
+                "level_1".to_string(),
+                "level_2".to_string(),
+                "level_3".to_string()
+            ])
+        );
+        model.validate().unwrap();
+    }
+}
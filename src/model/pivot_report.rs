@@ -166,7 +166,9 @@ pub struct PivotReport {
     /// Row dimensions (left side of pivot).
     ///
     /// These become the row headers. Multiple dimensions create
-    /// a hierarchy (e.g., region > segment).
+    /// a hierarchy (e.g., region > segment). A dimension hierarchy
+    /// reference (e.g., "stores.geography") expands to all of its levels,
+    /// and levels of one hierarchy are nested coarsest first.
     pub rows: Vec<String>,
 
     /// Column dimension (values become column headers).
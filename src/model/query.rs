@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use super::Model;
 use crate::semantic::error::SemanticError;
 use crate::semantic::planner::types::{
-    DerivedBinaryOp, DerivedExpr, DerivedField, FieldFilter, FieldRef, FilterOp, FilterValue,
+    AsOf, DerivedBinaryOp, DerivedExpr, DerivedField, FieldFilter, FieldRef, FilterOp, FilterValue,
     OrderField, SelectField, SemanticQuery, TimeFunction,
 };
 
@@ -231,6 +231,9 @@ impl QueryDefinition {
             derived,
             order_by,
             limit: self.limit,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: Vec::new(),
         }
     }
 
@@ -497,6 +500,9 @@ impl QueryDefinition {
             derived,
             order_by,
             limit: self.limit,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: Vec::new(),
         })
     }
 
@@ -43,7 +43,9 @@ pub enum ReportTableType {
 /// Refresh interval for materialized report tables.
 ///
 /// Specifies how often the report should be rebuilt.
-/// The CLI checks if `now - last_refresh > delta` and triggers a full rebuild.
+/// The last refresh is recorded in the metadata cache; once `now - last_refresh`
+/// reaches the delta, the report is due for a full rebuild (see
+/// `semantic::refresh`).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RefreshDelta {
     /// Number of seconds in the refresh interval.
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::expr::{Expr, OrderByExpr};
+use super::expr::{Expr, Literal, OrderByExpr};
 use super::types::DataType;
 
 /// A source entity representing raw data from an upstream system.
@@ -57,6 +57,10 @@ pub struct SourceColumn {
 
     /// Optional description for documentation
     pub description: Option<String>,
+
+    /// Values the column may hold; empty means unrestricted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accepted_values: Vec<Literal>,
 }
 
 /// How changes are tracked in the source for incremental processing.
@@ -185,6 +189,7 @@ impl SourceEntity {
                 data_type,
                 nullable,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         self
@@ -200,6 +205,14 @@ impl SourceEntity {
         self.with_column(name, data_type, true)
     }
 
+    /// Restrict an existing column to a fixed set of values.
+    pub fn with_accepted_values(mut self, column: &str, values: Vec<Literal>) -> Self {
+        if let Some(col) = self.columns.get_mut(column) {
+            col.accepted_values = values;
+        }
+        self
+    }
+
     /// Set the primary key.
     pub fn with_primary_key(mut self, columns: Vec<impl Into<String>>) -> Self {
         self.primary_key = columns.into_iter().map(Into::into).collect();
@@ -257,6 +270,7 @@ impl SourceColumn {
             data_type,
             nullable,
             description: None,
+            accepted_values: Vec::new(),
         }
     }
 
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::dialect::Dialect;
+
 use super::expr::{ColumnDef, Expr};
 use super::source::DedupConfig;
 use super::types::MaterializationStrategy;
@@ -32,6 +34,20 @@ pub struct TableDefinition {
     /// Source(s) - single entity or multiple for UNION
     pub from: FromClause,
 
+    /// Raw SQL body, used instead of the declarative fields below.
+    ///
+    /// Table names in the query refer to model entities.
+    #[serde(default)]
+    pub sql: Option<String>,
+
+    /// Dialect the SQL body is written in (e.g. "postgres"); DuckDB when unset.
+    #[serde(default)]
+    pub sql_dialect: Option<String>,
+
+    /// Entities the SQL body reads from, beyond `from`.
+    #[serde(default)]
+    pub sql_sources: Vec<String>,
+
     /// Union type when from has multiple sources
     #[serde(default)]
     pub union_type: UnionType,
@@ -162,6 +178,9 @@ impl TableDefinition {
             target_schema: None,
             materialized: true,
             from: FromClause::Single(from.into()),
+            sql: None,
+            sql_dialect: None,
+            sql_sources: vec![],
             union_type: UnionType::default(),
             joins: vec![],
             filter: None,
@@ -188,6 +207,20 @@ impl TableDefinition {
         self
     }
 
+    /// Set a raw SQL body.
+    pub fn with_sql(mut self, sql: impl Into<String>) -> Self {
+        self.sql = Some(sql.into());
+        self
+    }
+
+    /// Dialect to parse the SQL body with.
+    pub fn sql_dialect(&self) -> Dialect {
+        self.sql_dialect
+            .as_deref()
+            .and_then(Dialect::from_name)
+            .unwrap_or_default()
+    }
+
     /// Add a filter.
     pub fn with_filter(mut self, filter: Expr) -> Self {
         self.filter = Some(filter);
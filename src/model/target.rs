@@ -0,0 +1,391 @@
+//! Physical column layout of fact and dimension target tables.
+
+use super::dimension::SCDType;
+use super::expr::ColumnDef;
+use super::fact::ColumnSelection;
+use super::types::DataType;
+use super::Model;
+use crate::sql::ddl::{self, CreateTable, DdlResult};
+use crate::sql::dialect::DialectTarget;
+
+/// A column of a materialized fact or dimension table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetColumn {
+    /// Column name in the target table
+    pub name: String,
+
+    /// Declared or source type; `None` when it cannot be derived
+    pub data_type: Option<DataType>,
+
+    /// Source column or measure description
+    pub description: Option<String>,
+}
+
+impl TargetColumn {
+    fn new(name: impl Into<String>, data_type: Option<DataType>) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            description: None,
+        }
+    }
+}
+
+impl Model {
+    /// Columns of a fact or dimension target table, in table order.
+    ///
+    /// Facts hold their grain, pass-through and computed columns, included
+    /// dimension attributes as `prefix_attribute`, measure source columns and
+    /// window columns. Dimensions hold their columns plus the SCD bookkeeping
+    /// columns. Returns `None` for names that are neither.
+    pub fn target_columns(&self, target: &str) -> Option<Vec<TargetColumn>> {
+        let mut columns = TargetColumns::default();
+
+        if let Some(fact) = self.facts.get(target) {
+            let source_entity = fact
+                .from
+                .as_deref()
+                .or_else(|| fact.grain.first().map(|g| g.source_entity.as_str()))
+                .unwrap_or_default();
+
+            for grain in &fact.grain {
+                let name = grain.target_name.as_ref().unwrap_or(&grain.source_column);
+                columns.push(self.source_column(name, &grain.source_entity, &grain.source_column));
+            }
+
+            for column in &fact.columns {
+                match column {
+                    ColumnDef::Simple(name) => {
+                        columns.push(self.source_column(name, source_entity, name));
+                    }
+                    ColumnDef::Renamed { source, target } => {
+                        columns.push(self.source_column(target, source_entity, source));
+                    }
+                    ColumnDef::Computed {
+                        name, data_type, ..
+                    } => columns.push(TargetColumn::new(name, data_type.clone())),
+                }
+            }
+
+            let mut includes: Vec<_> = fact.includes.iter().collect();
+            includes.sort_by_key(|(alias, _)| alias.as_str());
+            for (alias, include) in includes {
+                let prefix = include.prefix.as_deref().unwrap_or(alias);
+                let available: Vec<String> = match self.dimensions.get(&include.entity) {
+                    Some(dim) => dim
+                        .columns
+                        .iter()
+                        .map(|c| c.source_column.clone())
+                        .collect(),
+                    None => {
+                        let mut names: Vec<String> = self
+                            .sources
+                            .get(&include.entity)
+                            .map(|s| s.columns.keys().cloned().collect())
+                            .unwrap_or_default();
+                        names.sort();
+                        names
+                    }
+                };
+                let attributes = match &include.selection {
+                    ColumnSelection::Columns(cols) => cols.clone(),
+                    ColumnSelection::All => available,
+                    ColumnSelection::Except(excluded) => available
+                        .into_iter()
+                        .filter(|c| !excluded.contains(c))
+                        .collect(),
+                };
+                let entity = self
+                    .dimensions
+                    .get(&include.entity)
+                    .map_or(include.entity.as_str(), |d| d.source_entity.as_str());
+                for attr in attributes {
+                    let name = format!("{}_{}", prefix, attr);
+                    columns.push(self.source_column(&name, entity, &attr));
+                }
+            }
+
+            // Materialized facts keep the measure inputs; measures aggregate at query time
+            let mut measures: Vec<_> = fact.measures.values().collect();
+            measures.sort_by_key(|m| m.name.as_str());
+            for measure in measures {
+                if measure.source_column == "*" {
+                    continue;
+                }
+                let mut column = self.source_column(
+                    &measure.source_column,
+                    source_entity,
+                    &measure.source_column,
+                );
+                if measure.description.is_some() {
+                    column.description = measure.description.clone();
+                }
+                columns.push(column);
+            }
+
+            for window in &fact.window_columns {
+                columns.push(TargetColumn::new(&window.name, window.data_type.clone()));
+            }
+        } else if let Some(dim) = self.dimensions.get(target) {
+            for col in &dim.columns {
+                let name = col.target_column.as_ref().unwrap_or(&col.source_column);
+                let mut column = self.source_column(name, &dim.source_entity, &col.source_column);
+                if col.description.is_some() {
+                    column.description = col.description.clone();
+                }
+                columns.push(column);
+            }
+
+            match &dim.scd_type {
+                SCDType::Type0 | SCDType::Type1 => {}
+                SCDType::Type2 {
+                    effective_from,
+                    effective_to,
+                    is_current,
+                } => {
+                    columns.push(TargetColumn::new(effective_from, Some(DataType::Timestamp)));
+                    columns.push(TargetColumn::new(effective_to, Some(DataType::Timestamp)));
+                    if let Some(is_current) = is_current {
+                        columns.push(TargetColumn::new(is_current, Some(DataType::Bool)));
+                    }
+                }
+                SCDType::Type3 { tracked_columns } => {
+                    for (source, previous) in tracked_columns {
+                        let mut column = self.source_column(previous, &dim.source_entity, source);
+                        column.description = None;
+                        columns.push(column);
+                    }
+                }
+                SCDType::Type6 {
+                    effective_from,
+                    effective_to,
+                    is_current,
+                    current_columns,
+                } => {
+                    columns.push(TargetColumn::new(effective_from, Some(DataType::Timestamp)));
+                    columns.push(TargetColumn::new(effective_to, Some(DataType::Timestamp)));
+                    columns.push(TargetColumn::new(is_current, Some(DataType::Bool)));
+                    for current in current_columns {
+                        let name = format!("current_{}", current);
+                        columns.push(self.source_column(&name, &dim.source_entity, current));
+                    }
+                }
+            }
+        } else {
+            return None;
+        }
+
+        Some(columns.0)
+    }
+
+    /// Column descriptions for a fact or dimension target table, as
+    /// `(target column, description)` pairs for DDL comments.
+    ///
+    /// Fact grain and pass-through columns take their source column's
+    /// description, measure source columns their measure's. Dimension columns
+    /// prefer their own description over the source column's.
+    pub fn column_descriptions(&self, target: &str) -> Vec<(String, String)> {
+        self.target_columns(target)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|c| Some((c.name, c.description?)))
+            .collect()
+    }
+
+    /// Empty `CREATE TABLE` for a fact or dimension target: its columns,
+    /// their descriptions as comments and its table options, checked against
+    /// `dialect`.
+    ///
+    /// Columns whose type cannot be derived are declared as strings. Returns
+    /// `None` for names that are neither.
+    pub fn target_table(
+        &self,
+        target: &str,
+        dialect: impl Into<DialectTarget>,
+    ) -> Option<DdlResult<CreateTable>> {
+        let (schema, name, options) = match (self.facts.get(target), self.dimensions.get(target)) {
+            (Some(fact), _) => (&fact.target_schema, &fact.target_table, &fact.table_options),
+            (None, Some(dim)) => (&dim.target_schema, &dim.target_table, &dim.table_options),
+            (None, None) => return None,
+        };
+        let mut table = match schema {
+            Some(schema) => CreateTable::new(name).schema(schema),
+            None => match name.rsplit_once('.') {
+                Some((schema, name)) => CreateTable::new(name).schema(schema),
+                None => CreateTable::new(name),
+            },
+        };
+        for column in self.target_columns(target)? {
+            let data_type = column.data_type.unwrap_or(DataType::String);
+            table = table.column(ddl::ColumnDef::new(column.name, data_type));
+        }
+        let table = table
+            .options(options.clone())
+            .column_comments(self.column_descriptions(target));
+        Some(table.validate(dialect).map(|()| table))
+    }
+
+    /// A target column copied from a source column, typed and described by it.
+    fn source_column(&self, name: &str, entity: &str, column: &str) -> TargetColumn {
+        match self.sources.get(entity).and_then(|s| s.columns.get(column)) {
+            Some(source) => TargetColumn {
+                name: name.to_string(),
+                data_type: Some(source.data_type.clone()),
+                description: source.description.clone(),
+            },
+            None => TargetColumn::new(name, None),
+        }
+    }
+}
+
+/// Column list that keeps the first definition of each name.
+#[derive(Default)]
+struct TargetColumns(Vec<TargetColumn>);
+
+impl TargetColumns {
+    fn push(&mut self, column: TargetColumn) {
+        if !self.0.iter().any(|c| c.name == column.name) {
+            self.0.push(column);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::TableOptions;
+    use crate::model::{DimensionDefinition, FactDefinition, SourceEntity};
+    use crate::sql::ddl::DdlError;
+    use crate::sql::dialect::Dialect;
+
+    fn model() -> Model {
+        let mut model = Model::new()
+            .with_source(
+                SourceEntity::new("orders", "raw.orders")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("total", DataType::Decimal(10, 2)),
+            )
+            .with_source(
+                SourceEntity::new("customers", "raw.customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("name", DataType::String)
+                    .with_required_column("region", DataType::String),
+            )
+            .with_fact(
+                FactDefinition::new("fact_orders", "analytics.fact_orders")
+                    .with_grain("orders", "order_id")
+                    .include("customers", vec!["name", "region"])
+                    .with_sum("revenue", "total"),
+            )
+            .with_dimension(
+                DimensionDefinition::new("dim_customers", "analytics.dim_customers", "customers")
+                    .with_columns(vec!["customer_id", "name", "region"]),
+            );
+
+        let orders = model.sources.get_mut("orders").unwrap();
+        orders.columns.get_mut("order_id").unwrap().description = Some("Order number".into());
+        let customers = model.sources.get_mut("customers").unwrap();
+        customers.columns.get_mut("name").unwrap().description = Some("Legal name".into());
+        customers.columns.get_mut("region").unwrap().description = Some("Sales region".into());
+        let fact = model.facts.get_mut("fact_orders").unwrap();
+        fact.measures.get_mut("revenue").unwrap().description = Some("Order revenue".into());
+        let dim = model.dimensions.get_mut("dim_customers").unwrap();
+        dim.columns[2].description = Some("Region at time of sale".into());
+        model
+    }
+
+    #[test]
+    fn test_fact_target_columns() {
+        let columns = model().target_columns("fact_orders").unwrap();
+        let layout: Vec<_> = columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.clone()))
+            .collect();
+
+        assert_eq!(
+            layout,
+            vec![
+                ("order_id", Some(DataType::Int64)),
+                ("customers_name", Some(DataType::String)),
+                ("customers_region", Some(DataType::String)),
+                ("total", Some(DataType::Decimal(10, 2))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dimension_target_columns_scd2() {
+        let mut model = model();
+        model.dimensions.get_mut("dim_customers").unwrap().scd_type = SCDType::Type2 {
+            effective_from: "valid_from".into(),
+            effective_to: "valid_to".into(),
+            is_current: None,
+        };
+
+        let names: Vec<_> = model
+            .target_columns("dim_customers")
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["customer_id", "name", "region", "valid_from", "valid_to"]
+        );
+        assert!(model.target_columns("missing").is_none());
+    }
+
+    #[test]
+    fn test_column_descriptions() {
+        let model = model();
+
+        assert_eq!(
+            model.column_descriptions("fact_orders"),
+            vec![
+                ("order_id".to_string(), "Order number".to_string()),
+                ("customers_name".to_string(), "Legal name".to_string()),
+                ("customers_region".to_string(), "Sales region".to_string()),
+                ("total".to_string(), "Order revenue".to_string()),
+            ]
+        );
+        assert_eq!(
+            model.column_descriptions("dim_customers"),
+            vec![
+                ("name".to_string(), "Legal name".to_string()),
+                ("region".to_string(), "Region at time of sale".to_string()),
+            ]
+        );
+        assert!(model.column_descriptions("missing").is_empty());
+    }
+
+    #[test]
+    fn test_target_table() {
+        let mut model = model();
+        model.facts.get_mut("fact_orders").unwrap().table_options =
+            TableOptions::new().with_cluster_by(vec!["customers_region"]);
+
+        let table = model
+            .target_table("fact_orders", Dialect::Snowflake)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            table.to_sql(Dialect::Snowflake),
+            "CREATE TABLE \"analytics\".\"fact_orders\" (\"order_id\" BIGINT COMMENT 'Order number', \
+             \"customers_name\" VARCHAR COMMENT 'Legal name', \
+             \"customers_region\" VARCHAR COMMENT 'Sales region', \
+             \"total\" NUMBER(10, 2) COMMENT 'Order revenue') CLUSTER BY (\"customers_region\")"
+        );
+        assert_eq!(
+            model
+                .target_table("fact_orders", Dialect::Postgres)
+                .unwrap()
+                .unwrap_err(),
+            DdlError::UnsupportedOption {
+                option: "CLUSTER BY",
+                dialect: Dialect::Postgres.into(),
+            }
+        );
+        assert!(model.target_table("missing", Dialect::Postgres).is_none());
+    }
+}
@@ -149,6 +149,171 @@ pub enum TableType {
     View,
 }
 
+/// Warehouse-specific physical layout for a materialized table.
+///
+/// Each option only applies to the dialects that have it; `CreateTable::validate`
+/// reports options the target cannot express.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableOptions {
+    /// Partition columns (BigQuery `PARTITION BY`, Databricks `PARTITIONED BY`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partition_by: Vec<String>,
+
+    /// Truncate the BigQuery partition column to this unit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_granularity: Option<PartitionGranularity>,
+
+    /// Clustering columns (BigQuery, Snowflake and Databricks `CLUSTER BY`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cluster_by: Vec<String>,
+
+    /// Row distribution across Redshift slices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<Distribution>,
+
+    /// Redshift `SORTKEY` columns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort_key: Vec<String>,
+
+    /// Databricks storage format (`USING ...`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<TableFormat>,
+
+    /// Store the table as a clustered columnstore index (T-SQL).
+    #[serde(default)]
+    pub columnstore: bool,
+}
+
+impl TableOptions {
+    /// Create empty table options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the partition columns.
+    pub fn with_partition_by(mut self, columns: Vec<impl Into<String>>) -> Self {
+        self.partition_by = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Truncate the partition column to a coarser unit.
+    pub fn with_partition_granularity(mut self, granularity: PartitionGranularity) -> Self {
+        self.partition_granularity = Some(granularity);
+        self
+    }
+
+    /// Set the clustering columns.
+    pub fn with_cluster_by(mut self, columns: Vec<impl Into<String>>) -> Self {
+        self.cluster_by = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the row distribution.
+    pub fn with_distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Set the sort key columns.
+    pub fn with_sort_key(mut self, columns: Vec<impl Into<String>>) -> Self {
+        self.sort_key = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the storage format.
+    pub fn with_format(mut self, format: TableFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Store as a clustered columnstore.
+    pub fn with_columnstore(mut self) -> Self {
+        self.columnstore = true;
+        self
+    }
+
+    /// Whether no option is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Unit a BigQuery partition column is truncated to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartitionGranularity {
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl PartitionGranularity {
+    /// Parse a granularity name (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "hour" => Some(PartitionGranularity::Hour),
+            "day" => Some(PartitionGranularity::Day),
+            "month" => Some(PartitionGranularity::Month),
+            "year" => Some(PartitionGranularity::Year),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PartitionGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartitionGranularity::Hour => write!(f, "HOUR"),
+            PartitionGranularity::Day => write!(f, "DAY"),
+            PartitionGranularity::Month => write!(f, "MONTH"),
+            PartitionGranularity::Year => write!(f, "YEAR"),
+        }
+    }
+}
+
+/// Redshift distribution style.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Distribution {
+    /// Let Redshift choose
+    Auto,
+    /// Round-robin across slices
+    Even,
+    /// Full copy on every node
+    All,
+    /// Co-locate rows by this column
+    Key(String),
+}
+
+/// Databricks table storage format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableFormat {
+    Delta,
+    Parquet,
+    Iceberg,
+}
+
+impl TableFormat {
+    /// Parse a format name (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "delta" => Some(TableFormat::Delta),
+            "parquet" => Some(TableFormat::Parquet),
+            "iceberg" => Some(TableFormat::Iceberg),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TableFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableFormat::Delta => write!(f, "DELTA"),
+            TableFormat::Parquet => write!(f, "PARQUET"),
+            TableFormat::Iceberg => write!(f, "ICEBERG"),
+        }
+    }
+}
+
 /// Aggregation types for measures.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AggregationType {
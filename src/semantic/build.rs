@@ -0,0 +1,516 @@
+//! Dependency-ordered build execution.
+//!
+//! A [`BuildPlan`] is the set of targets chosen by selectors (see
+//! [`ModelGraph::select`]) together with the statements that build each one.
+//! [`BuildRunner`] executes the plan in DAG order, running independent
+//! branches in parallel up to a concurrency limit. When a target fails,
+//! everything downstream of it is skipped rather than built on stale inputs.
+//!
+//! ```ignore
+//! let plan = graph
+//!     .build_plan(&["+fact_orders", "tag:mart"])?
+//!     .with_statements("dim_customers", vec![dim_customers_sql])
+//!     .with_statements("fact_orders", vec![fact_orders_sql]);
+//!
+//! let executor = WorkerExecutor::new(client, "postgres", conn_str);
+//! let report = BuildRunner::new(executor).with_concurrency(4).run(&plan).await;
+//! println!("{}", report.summary());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{FuturesUnordered, StreamExt};
+
+use crate::semantic::model_graph::ModelGraph;
+use crate::semantic::GraphResult;
+use crate::worker::{WorkerClient, WorkerResult};
+
+/// One target in a build plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildNode {
+    pub target: String,
+    /// Planned targets that must finish first, in build order
+    pub depends_on: Vec<String>,
+    /// Statements run in order to build the target
+    pub statements: Vec<String>,
+}
+
+/// Selected targets in build order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildPlan {
+    pub nodes: Vec<BuildNode>,
+}
+
+impl BuildPlan {
+    /// Set the statements that build `target`.
+    ///
+    /// Targets left without statements are reported as
+    /// [`BuildStatus::Empty`] rather than built.
+    pub fn with_statements(mut self, target: &str, statements: Vec<String>) -> Self {
+        if let Some(node) = self.nodes.iter_mut().find(|n| n.target == target) {
+            node.statements = statements;
+        }
+        self
+    }
+
+    /// Target names in build order.
+    pub fn targets(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(|n| n.target.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl ModelGraph {
+    /// Plan a build of the targets matched by `selectors`.
+    ///
+    /// Dependencies are limited to other planned targets; unselected upstream
+    /// targets are assumed to be built already.
+    pub fn build_plan(&self, selectors: &[&str]) -> GraphResult<BuildPlan> {
+        let selected = self.select(selectors)?;
+        let nodes = selected
+            .iter()
+            .map(|target| {
+                let depends_on = self
+                    .upstream_targets(target)?
+                    .into_iter()
+                    .filter(|dep| selected.contains(dep))
+                    .collect();
+                Ok(BuildNode {
+                    target: target.clone(),
+                    depends_on,
+                    statements: Vec::new(),
+                })
+            })
+            .collect::<GraphResult<_>>()?;
+        Ok(BuildPlan { nodes })
+    }
+}
+
+/// Runs build statements against a database.
+#[async_trait]
+pub trait BuildExecutor: Send + Sync {
+    /// Execute one statement, returning rows affected when the driver reports it.
+    async fn execute(&self, sql: &str) -> WorkerResult<Option<i64>>;
+}
+
+/// Executes build statements through the worker.
+pub struct WorkerExecutor {
+    client: Arc<WorkerClient>,
+    driver: String,
+    connection_string: String,
+}
+
+impl WorkerExecutor {
+    /// Create an executor for the given connection.
+    pub fn new(
+        client: Arc<WorkerClient>,
+        driver: impl Into<String>,
+        connection_string: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            driver: driver.into(),
+            connection_string: connection_string.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl BuildExecutor for WorkerExecutor {
+    async fn execute(&self, sql: &str) -> WorkerResult<Option<i64>> {
+        let response = self
+            .client
+            .execute_query(&self.driver, &self.connection_string, sql)
+            .await?;
+        Ok(response.rows_affected)
+    }
+}
+
+/// Outcome of building one target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildStatus {
+    Success,
+    Failed(String),
+    /// Nothing run because the plan has no statements for the target
+    Empty,
+    /// Not run because an upstream target failed
+    Skipped {
+        upstream: String,
+    },
+}
+
+/// Result of building one target.
+#[derive(Debug, Clone)]
+pub struct BuildResult {
+    pub target: String,
+    pub status: BuildStatus,
+    pub duration: Duration,
+    /// Rows affected by the last statement that reported a count
+    pub rows: Option<i64>,
+}
+
+impl BuildResult {
+    pub fn succeeded(&self) -> bool {
+        self.status == BuildStatus::Success
+    }
+}
+
+/// Results of a build, in the order targets finished.
+#[derive(Debug, Clone, Default)]
+pub struct BuildReport {
+    pub results: Vec<BuildResult>,
+    pub duration: Duration,
+}
+
+impl BuildReport {
+    /// Whether every target built; targets without statements did not.
+    pub fn succeeded(&self) -> bool {
+        self.results.iter().all(BuildResult::succeeded)
+    }
+
+    /// Result for a target.
+    pub fn result(&self, target: &str) -> Option<&BuildResult> {
+        self.results.iter().find(|r| r.target == target)
+    }
+
+    /// Targets that failed to build.
+    pub fn failures(&self) -> impl Iterator<Item = &BuildResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.status, BuildStatus::Failed(_)))
+    }
+
+    /// Human-readable per-target timings and row counts.
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        let (mut ok, mut failed, mut empty, mut skipped) = (0, 0, 0, 0);
+        for result in &self.results {
+            let elapsed = format!("{:.2}s", result.duration.as_secs_f64());
+            match &result.status {
+                BuildStatus::Success => {
+                    ok += 1;
+                    match result.rows {
+                        Some(rows) => out.push_str(&format!(
+                            "OK    {} ({}, {} rows)\n",
+                            result.target, elapsed, rows
+                        )),
+                        None => out.push_str(&format!("OK    {} ({})\n", result.target, elapsed)),
+                    }
+                }
+                BuildStatus::Failed(error) => {
+                    failed += 1;
+                    out.push_str(&format!(
+                        "FAIL  {} ({}): {}\n",
+                        result.target, elapsed, error
+                    ));
+                }
+                BuildStatus::Empty => {
+                    empty += 1;
+                    out.push_str(&format!("EMPTY {} (no statements)\n", result.target));
+                }
+                BuildStatus::Skipped { upstream } => {
+                    skipped += 1;
+                    out.push_str(&format!(
+                        "SKIP  {} (upstream {} failed)\n",
+                        result.target, upstream
+                    ));
+                }
+            }
+        }
+        out.push_str(&format!(
+            "{} built, {} failed, {} empty, {} skipped in {:.2}s\n",
+            ok,
+            failed,
+            empty,
+            skipped,
+            self.duration.as_secs_f64()
+        ));
+        out
+    }
+}
+
+/// Executes a [`BuildPlan`] in dependency order.
+pub struct BuildRunner<E> {
+    executor: E,
+    concurrency: usize,
+}
+
+impl<E: BuildExecutor> BuildRunner<E> {
+    pub fn new(executor: E) -> Self {
+        Self {
+            executor,
+            concurrency: 1,
+        }
+    }
+
+    pub fn executor(&self) -> &E {
+        &self.executor
+    }
+
+    /// Maximum targets built at once (default: 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Build every target in the plan. Failures are recorded per target.
+    pub async fn run(&self, plan: &BuildPlan) -> BuildReport {
+        let started = Instant::now();
+        let mut report = BuildReport::default();
+        let mut status: HashMap<&str, BuildStatus> = HashMap::new();
+        let mut pending: Vec<&BuildNode> = plan.nodes.iter().collect();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            // Start or skip every pending node whose dependencies have finished
+            let mut i = 0;
+            while i < pending.len() {
+                let node = pending[i];
+                match blocked_by(node, &status) {
+                    Readiness::Waiting => i += 1,
+                    Readiness::Skip(upstream) => {
+                        pending.remove(i);
+                        status.insert(&node.target, BuildStatus::Skipped { upstream });
+                        report.results.push(BuildResult {
+                            target: node.target.clone(),
+                            status: status[node.target.as_str()].clone(),
+                            duration: Duration::ZERO,
+                            rows: None,
+                        });
+                    }
+                    Readiness::Ready if running.len() < self.concurrency => {
+                        pending.remove(i);
+                        running.push(self.build(node));
+                    }
+                    Readiness::Ready => i += 1,
+                }
+            }
+
+            match running.next().await {
+                Some(result) => {
+                    let node = plan
+                        .nodes
+                        .iter()
+                        .find(|n| n.target == result.target)
+                        .expect("result for a planned target");
+                    status.insert(&node.target, result.status.clone());
+                    report.results.push(result);
+                }
+                None => break,
+            }
+        }
+
+        report.duration = started.elapsed();
+        report
+    }
+
+    async fn build(&self, node: &BuildNode) -> BuildResult {
+        let started = Instant::now();
+        let mut rows = None;
+        let mut status = if node.statements.is_empty() {
+            BuildStatus::Empty
+        } else {
+            BuildStatus::Success
+        };
+        for sql in &node.statements {
+            match self.executor.execute(sql).await {
+                Ok(affected) => rows = affected.or(rows),
+                Err(error) => {
+                    status = BuildStatus::Failed(error.to_string());
+                    break;
+                }
+            }
+        }
+        BuildResult {
+            target: node.target.clone(),
+            status,
+            duration: started.elapsed(),
+            rows,
+        }
+    }
+}
+
+enum Readiness {
+    Ready,
+    Waiting,
+    Skip(String),
+}
+
+/// Whether a node can start, given the status of finished targets.
+fn blocked_by(node: &BuildNode, status: &HashMap<&str, BuildStatus>) -> Readiness {
+    for dep in &node.depends_on {
+        match status.get(dep.as_str()) {
+            None => return Readiness::Waiting,
+            // An empty target leaves its table as it was
+            Some(BuildStatus::Success | BuildStatus::Empty) => {}
+            Some(BuildStatus::Failed(_)) => return Readiness::Skip(dep.clone()),
+            Some(BuildStatus::Skipped { upstream }) => return Readiness::Skip(upstream.clone()),
+        }
+    }
+    Readiness::Ready
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use crate::worker::WorkerError;
+
+    fn node(target: &str, depends_on: &[&str]) -> BuildNode {
+        BuildNode {
+            target: target.to_string(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            statements: vec![format!("build {}", target)],
+        }
+    }
+
+    /// Records executed statements; statements containing "bad" fail.
+    #[derive(Default)]
+    struct FakeExecutor {
+        executed: Mutex<Vec<String>>,
+        active: Mutex<(usize, usize)>,
+    }
+
+    #[async_trait]
+    impl BuildExecutor for FakeExecutor {
+        async fn execute(&self, sql: &str) -> WorkerResult<Option<i64>> {
+            {
+                let mut active = self.active.lock().unwrap();
+                active.0 += 1;
+                active.1 = active.1.max(active.0);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.active.lock().unwrap().0 -= 1;
+            self.executed.lock().unwrap().push(sql.to_string());
+            if sql.contains("bad") {
+                return Err(WorkerError::Remote {
+                    code: "QUERY_FAILED".into(),
+                    message: format!("{} failed", sql),
+                });
+            }
+            Ok(Some(sql.len() as i64))
+        }
+    }
+
+    fn position(executed: &[String], target: &str) -> usize {
+        let sql = format!("build {}", target);
+        executed.iter().position(|s| *s == sql).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_runs_in_dependency_order() {
+        let plan = BuildPlan {
+            nodes: vec![
+                node("dim_a", &[]),
+                node("dim_b", &[]),
+                node("fact", &["dim_a", "dim_b"]),
+                node("mart", &["dim_a", "dim_b", "fact"]),
+            ],
+        };
+        let runner = BuildRunner::new(FakeExecutor::default()).with_concurrency(4);
+        let report = runner.run(&plan).await;
+
+        assert!(report.succeeded());
+        assert_eq!(report.results.len(), 4);
+        let executed = runner.executor.executed.lock().unwrap().clone();
+        assert!(position(&executed, "dim_a") < position(&executed, "fact"));
+        assert!(position(&executed, "dim_b") < position(&executed, "fact"));
+        assert!(position(&executed, "fact") < position(&executed, "mart"));
+        // The two dimensions are independent and run together
+        assert_eq!(runner.executor.active.lock().unwrap().1, 2);
+        assert_eq!(report.result("fact").unwrap().rows, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let plan = BuildPlan {
+            nodes: vec![
+                node("a", &[]),
+                node("b", &[]),
+                node("c", &[]),
+                node("d", &[]),
+            ],
+        };
+        let runner = BuildRunner::new(FakeExecutor::default()).with_concurrency(2);
+        let report = runner.run(&plan).await;
+
+        assert!(report.succeeded());
+        assert_eq!(runner.executor.active.lock().unwrap().1, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failure_skips_downstream() {
+        let plan = BuildPlan {
+            nodes: vec![
+                node("dim_bad", &[]),
+                node("dim_ok", &[]),
+                node("fact", &["dim_bad", "dim_ok"]),
+                node("mart", &["dim_bad", "dim_ok", "fact"]),
+                node("other", &["dim_ok"]),
+            ],
+        };
+        let runner = BuildRunner::new(FakeExecutor::default()).with_concurrency(2);
+        let report = runner.run(&plan).await;
+
+        assert!(!report.succeeded());
+        assert!(matches!(
+            report.result("dim_bad").unwrap().status,
+            BuildStatus::Failed(_)
+        ));
+        for target in ["fact", "mart"] {
+            assert_eq!(
+                report.result(target).unwrap().status,
+                BuildStatus::Skipped {
+                    upstream: "dim_bad".into()
+                }
+            );
+        }
+        assert!(report.result("other").unwrap().succeeded());
+
+        let executed = runner.executor.executed.lock().unwrap().clone();
+        assert!(!executed.contains(&"build fact".to_string()));
+        assert!(!executed.contains(&"build mart".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_summary() {
+        let mut plan = BuildPlan {
+            nodes: vec![node("dim_bad", &[]), node("fact", &["dim_bad"])],
+        };
+        plan.nodes.push(BuildNode {
+            target: "empty".into(),
+            depends_on: Vec::new(),
+            statements: Vec::new(),
+        });
+        let report = BuildRunner::new(FakeExecutor::default()).run(&plan).await;
+        let summary = report.summary();
+
+        assert!(summary.contains("FAIL  dim_bad ("));
+        assert!(summary.contains("build dim_bad failed"));
+        assert!(summary.contains("SKIP  fact (upstream dim_bad failed)"));
+        assert!(summary.contains("EMPTY empty (no statements)"));
+        assert!(summary.contains("0 built, 1 failed, 1 empty, 1 skipped in "));
+    }
+
+    #[tokio::test]
+    async fn test_target_without_statements_is_empty() {
+        let mut plan = BuildPlan {
+            nodes: vec![node("dim", &[]), node("fact", &["dim"])],
+        };
+        plan = plan.with_statements("dim", Vec::new());
+        let report = BuildRunner::new(FakeExecutor::default()).run(&plan).await;
+
+        let dim = report.result("dim").unwrap();
+        assert_eq!(dim.status, BuildStatus::Empty);
+        assert!(!dim.succeeded());
+        assert!(!report.succeeded());
+        assert!(report.result("fact").unwrap().succeeded());
+    }
+}
@@ -19,11 +19,13 @@ use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use serde::{Deserialize, Serialize};
 
+use crate::dialect::Dialect;
 use crate::model::dimension::SCDType;
 use crate::model::expr::{Expr, OrderByExpr, WhenClause};
 use crate::model::fact::ColumnSelection;
 use crate::model::table::TableDefinition;
 use crate::model::{ColumnDef, Model};
+use crate::sql::expr::Expr as SqlExpr;
 
 // =============================================================================
 // Core Types
@@ -385,6 +387,42 @@ impl ColumnLineageGraph {
             let dim_entity = &include.entity;
             let prefix = include.prefix.as_deref().unwrap_or(alias);
 
+            // Join keys linking the fact's source to the included entity,
+            // one edge per key column so composite keys are fully tracked.
+            // Band bounds and join predicates count as join keys too.
+            let lookup_entity = model
+                .dimensions
+                .get(dim_entity)
+                .map(|dim| dim.source_entity.as_str())
+                .unwrap_or(dim_entity);
+            if let Some(rel) = model.relationships.iter().find(|r| {
+                (r.from_entity == source_entity && r.to_entity == lookup_entity)
+                    || (r.to_entity == source_entity && r.from_entity == lookup_entity)
+            }) {
+                let mut keys = Vec::new();
+                for (from_column, to_column) in rel.column_pairs() {
+                    keys.push(ColumnRef::new(&rel.from_entity, from_column));
+                    keys.push(ColumnRef::new(&rel.to_entity, to_column));
+                }
+                if let Some(range) = &rel.range {
+                    let (value_entity, bounds_entity) =
+                        range.sides(&rel.from_entity, &rel.to_entity);
+                    keys.push(ColumnRef::new(value_entity, &range.value_column));
+                    keys.push(ColumnRef::new(bounds_entity, &range.lower_column));
+                    keys.push(ColumnRef::new(bounds_entity, &range.upper_column));
+                }
+                for predicate in &rel.predicates {
+                    keys.extend(extract_column_refs(predicate, Some(&rel.to_entity)));
+                }
+                for key in keys {
+                    self.add_edge(
+                        key,
+                        ColumnRef::new(target_entity, format!("_join_{}", alias)),
+                        LineageEdge::join_key(),
+                    );
+                }
+            }
+
             // Get all available columns - prefer dimension columns, fallback to source
             let all_columns: Vec<String> = if let Some(dim) = model.dimensions.get(dim_entity) {
                 // Use the dimension's defined columns
@@ -576,6 +614,15 @@ impl ColumnLineageGraph {
     fn process_table(&mut self, table: &TableDefinition, _model: &Model) {
         let target_entity = &table.name;
 
+        // A raw SQL body replaces the declarative fields
+        if let Some(body) = &table.sql {
+            // Bodies are validated at load time; an unparseable one has no lineage
+            if let Ok(query) = crate::query::Query::parse(body, table.sql_dialect()) {
+                self.process_sql_query(target_entity, &query, &SqlScope::default());
+            }
+            return;
+        }
+
         // Get source entities from the from clause
         let source_entities = table.from.sources();
         let primary_source = table.from.primary();
@@ -691,6 +738,67 @@ impl ColumnLineageGraph {
         }
     }
 
+    /// Process a parsed SQL query whose output columns land in `target_entity`.
+    fn process_sql_query<'q>(
+        &mut self,
+        target_entity: &str,
+        query: &'q crate::query::Query,
+        outer: &SqlScope<'q>,
+    ) {
+        let scope = outer.enter(query);
+
+        if let Some(set_op) = &query.set_op {
+            self.process_sql_query(target_entity, &set_op.left, &scope);
+            self.process_sql_query(target_entity, &set_op.right, &scope);
+            return;
+        }
+
+        for item in &query.select {
+            let output = match (&item.alias, &item.expr) {
+                (Some(alias), _) => alias.clone(),
+                (None, SqlExpr::Column { column, .. }) => column.clone(),
+                _ => continue,
+            };
+            let edge = match &item.expr {
+                SqlExpr::Column { .. } => LineageEdge::passthrough(),
+                SqlExpr::Function { name, .. }
+                    if matches!(
+                        name.to_uppercase().as_str(),
+                        "SUM" | "COUNT" | "AVG" | "MIN" | "MAX"
+                    ) =>
+                {
+                    LineageEdge::aggregate()
+                }
+                expr => LineageEdge::transform(expr.to_tokens().serialize(Dialect::default())),
+            };
+            for dep in scope.column_refs(&item.expr) {
+                self.add_edge(dep, ColumnRef::new(target_entity, &output), edge.clone());
+            }
+        }
+
+        for join in &query.joins {
+            if let Some(on) = &join.on {
+                for dep in scope.column_refs(on) {
+                    self.add_edge(
+                        dep,
+                        ColumnRef::new(target_entity, format!("_join_{}", join.table.table)),
+                        LineageEdge::join_key(),
+                    );
+                }
+            }
+        }
+
+        if let Some(filter) = &query.where_clause {
+            for dep in scope.column_refs(filter) {
+                self.add_edge(
+                    dep,
+                    ColumnRef::new(target_entity, "_filter"),
+                    LineageEdge::filter(),
+                );
+            }
+        }
+    }
+
     /// Process a report definition to extract lineage.
     ///
     /// Reports aggregate measures from multiple facts into a single output.
@@ -1161,6 +1269,177 @@ fn extract_column_refs_inner(expr: &Expr, default_entity: Option<&str>, refs: &m
 // Tests
 // =============================================================================
 
+/// Name resolution for column references in a raw SQL table body.
+///
+/// Table aliases map to model entities; references to CTEs are traced
+/// through the CTE's SELECT list to the entities it reads.
+#[derive(Default, Clone)]
+struct SqlScope<'q> {
+    ctes: HashMap<&'q str, &'q crate::query::Query>,
+    tables: HashMap<&'q str, &'q crate::query::TableRef>,
+}
+
+impl<'q> SqlScope<'q> {
+    /// Scope for a query nested in this one.
+    fn enter(&self, query: &'q crate::query::Query) -> Self {
+        let mut scope = SqlScope {
+            ctes: self.ctes.clone(),
+            tables: HashMap::new(),
+        };
+        for cte in &query.with {
+            scope.ctes.insert(&cte.name, &cte.query);
+        }
+        for table in query.from.iter().chain(query.joins.iter().map(|j| &j.table)) {
+            scope
+                .tables
+                .insert(table.alias.as_deref().unwrap_or(&table.table), table);
+        }
+        scope
+    }
+
+    /// Source columns an expression depends on.
+    fn column_refs(&self, expr: &SqlExpr) -> Vec<ColumnRef> {
+        let mut refs = Vec::new();
+        self.collect_refs(expr, &mut refs, 0);
+        let mut seen = HashSet::new();
+        refs.retain(|r| seen.insert(r.clone()));
+        refs
+    }
+
+    fn collect_refs(&self, expr: &SqlExpr, refs: &mut Vec<ColumnRef>, depth: usize) {
+        // Guards against self-referencing (recursive) CTEs
+        if depth > 32 {
+            return;
+        }
+
+        match expr {
+            SqlExpr::Column { table, column } => {
+                let source = match table {
+                    Some(t) => self.tables.get(t.as_str()).copied(),
+                    // Unqualified columns are only resolvable with a single source
+                    None if self.tables.len() == 1 => self.tables.values().next().copied(),
+                    None => None,
+                };
+                let Some(source) = source else { return };
+
+                match self.ctes.get(source.table.as_str()) {
+                    Some(cte) if source.schema.is_none() => {
+                        self.collect_cte_refs(cte, column, refs, depth + 1)
+                    }
+                    _ => refs.push(ColumnRef::new(&source.table, column)),
+                }
+            }
+            SqlExpr::Literal(_) | SqlExpr::Star { .. } | SqlExpr::Raw(_) => {}
+            SqlExpr::BinaryOp { left, right, .. } => {
+                self.collect_refs(left, refs, depth);
+                self.collect_refs(right, refs, depth);
+            }
+            SqlExpr::UnaryOp { expr, .. }
+            | SqlExpr::Paren(expr)
+            | SqlExpr::Cast { expr, .. }
+            | SqlExpr::IsNull { expr, .. } => self.collect_refs(expr, refs, depth),
+            SqlExpr::Function { args, .. } => {
+                for arg in args {
+                    self.collect_refs(arg, refs, depth);
+                }
+            }
+            SqlExpr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                for e in operand.iter().chain(else_clause.iter()) {
+                    self.collect_refs(e, refs, depth);
+                }
+                for (when, then) in when_clauses {
+                    self.collect_refs(when, refs, depth);
+                    self.collect_refs(then, refs, depth);
+                }
+            }
+            SqlExpr::In { expr, values, .. } => {
+                self.collect_refs(expr, refs, depth);
+                for v in values {
+                    self.collect_refs(v, refs, depth);
+                }
+            }
+            SqlExpr::Between {
+                expr, low, high, ..
+            } => {
+                self.collect_refs(expr, refs, depth);
+                self.collect_refs(low, refs, depth);
+                self.collect_refs(high, refs, depth);
+            }
+            SqlExpr::LikeEscape { expr, pattern, .. } => {
+                self.collect_refs(expr, refs, depth);
+                self.collect_refs(pattern, refs, depth);
+            }
+            SqlExpr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                self.collect_refs(function, refs, depth);
+                for e in partition_by {
+                    self.collect_refs(e, refs, depth);
+                }
+                for o in order_by {
+                    self.collect_refs(&o.expr, refs, depth);
+                }
+            }
+            SqlExpr::InSubquery { expr, subquery, .. } => {
+                self.collect_refs(expr, refs, depth);
+                self.collect_subquery_refs(subquery, refs, depth);
+            }
+            SqlExpr::Subquery(subquery) | SqlExpr::Exists { subquery, .. } => {
+                self.collect_subquery_refs(subquery, refs, depth)
+            }
+        }
+    }
+
+    /// Trace a CTE output column back to the columns it is computed from.
+    fn collect_cte_refs(
+        &self,
+        cte: &'q crate::query::Query,
+        column: &str,
+        refs: &mut Vec<ColumnRef>,
+        depth: usize,
+    ) {
+        let branches: Vec<&crate::query::Query> = match &cte.set_op {
+            Some(set_op) => vec![&set_op.left, &set_op.right],
+            None => vec![cte],
+        };
+        for branch in branches {
+            let scope = self.enter(branch);
+            let item = branch.select.iter().find(|item| match (&item.alias, &item.expr) {
+                (Some(alias), _) => alias == column,
+                (None, SqlExpr::Column { column: c, .. }) => c == column,
+                _ => false,
+            });
+            if let Some(item) = item {
+                scope.collect_refs(&item.expr, refs, depth);
+            }
+        }
+    }
+
+    /// Columns a scalar subquery reads from, resolved in its own scope.
+    fn collect_subquery_refs(
+        &self,
+        subquery: &crate::query::Query,
+        refs: &mut Vec<ColumnRef>,
+        depth: usize,
+    ) {
+        // The subquery borrows shorter than 'q, so resolve with a fresh scope
+        let scope = SqlScope::default().enter(subquery);
+        for item in &subquery.select {
+            scope.collect_refs(&item.expr, refs, depth + 1);
+        }
+        if let Some(filter) = &subquery.where_clause {
+            scope.collect_refs(filter, refs, depth + 1);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1394,6 +1673,10 @@ mod tests {
                 is_current: Some("is_current".into()),
             },
             materialization: MaterializationStrategy::Table,
+            table_options: Default::default(),
+            hierarchies: Vec::new(),
+            surrogate_key: None,
+            unknown_member: None,
         };
 
         let mut model = Model::new();
@@ -1433,6 +1716,10 @@ mod tests {
                 tracked_columns: vec![("price".into(), "previous_price".into())],
             },
             materialization: MaterializationStrategy::Table,
+            table_options: Default::default(),
+            hierarchies: Vec::new(),
+            surrogate_key: None,
+            unknown_member: None,
         };
 
         let mut model = Model::new();
@@ -1866,6 +2153,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_include_composite_join_keys() {
+        use crate::model::{
+            fact::{DimensionInclude, FactDefinition, GrainColumn},
+            Cardinality, Relationship, SourceEntity,
+        };
+
+        let mut model = Model::new();
+        model.sources.insert(
+            "shipments".into(),
+            SourceEntity::new("shipments", "raw.shipments"),
+        );
+        model.sources.insert(
+            "order_lines".into(),
+            SourceEntity::new("order_lines", "raw.order_lines"),
+        );
+        model.relationships.push(Relationship::composite(
+            "shipments",
+            "order_lines",
+            vec![("order_id", "order_id"), ("line_no", "line_no")],
+            Cardinality::ManyToOne,
+        ));
+
+        let mut fact = FactDefinition::new("shipment_fact", "analytics.shipment_fact");
+        fact.from = Some("shipments".into());
+        fact.grain = vec![GrainColumn {
+            source_entity: "shipments".into(),
+            source_column: "shipment_id".into(),
+            target_name: None,
+        }];
+        fact.includes.insert(
+            "line".into(),
+            DimensionInclude {
+                entity: "order_lines".into(),
+                selection: ColumnSelection::Columns(vec!["sku".into()]),
+                prefix: None,
+            },
+        );
+        model.facts.insert("shipment_fact".into(), fact);
+
+        let graph = ColumnLineageGraph::from_model(&model);
+        let deps = graph.direct_dependencies(&ColumnRef::new("shipment_fact", "_join_line"));
+
+        for key in [
+            ColumnRef::new("shipments", "order_id"),
+            ColumnRef::new("shipments", "line_no"),
+            ColumnRef::new("order_lines", "order_id"),
+            ColumnRef::new("order_lines", "line_no"),
+        ] {
+            assert!(
+                deps.contains(&(key.clone(), LineageType::JoinKey)),
+                "missing join key {:?} in {:?}",
+                key,
+                deps
+            );
+        }
+    }
+
+    #[test]
+    fn test_include_range_join_keys() {
+        use crate::model::{
+            fact::{DimensionInclude, FactDefinition, GrainColumn},
+            Cardinality, Relationship, SourceEntity,
+        };
+
+        let mut model = Model::new();
+        model
+            .sources
+            .insert("orders".into(), SourceEntity::new("orders", "raw.orders"));
+        model
+            .sources
+            .insert("tiers".into(), SourceEntity::new("tiers", "raw.tiers"));
+        model.relationships.push(
+            Relationship::range(
+                "orders",
+                "tiers",
+                "amount",
+                "min_amount",
+                "max_amount",
+                Cardinality::ManyToOne,
+            )
+            .with_predicate(Expr::column("active").eq(Expr::bool(true))),
+        );
+
+        let mut fact = FactDefinition::new("order_fact", "analytics.order_fact");
+        fact.from = Some("orders".into());
+        fact.grain = vec![GrainColumn {
+            source_entity: "orders".into(),
+            source_column: "order_id".into(),
+            target_name: None,
+        }];
+        fact.includes.insert(
+            "tier".into(),
+            DimensionInclude {
+                entity: "tiers".into(),
+                selection: ColumnSelection::Columns(vec!["name".into()]),
+                prefix: None,
+            },
+        );
+        model.facts.insert("order_fact".into(), fact);
+
+        let graph = ColumnLineageGraph::from_model(&model);
+        let deps = graph.direct_dependencies(&ColumnRef::new("order_fact", "_join_tier"));
+
+        for key in [
+            ColumnRef::new("orders", "amount"),
+            ColumnRef::new("tiers", "min_amount"),
+            ColumnRef::new("tiers", "max_amount"),
+            ColumnRef::new("tiers", "active"),
+        ] {
+            assert!(
+                deps.contains(&(key.clone(), LineageType::JoinKey)),
+                "missing join key {:?} in {:?}",
+                key,
+                deps
+            );
+        }
+    }
+
     #[test]
     fn test_serialization_round_trip() {
         let mut graph = ColumnLineageGraph::new();
@@ -1913,4 +2319,31 @@ mod tests {
             serde_json::from_str(&json).expect("JSON deserialize should work");
         assert_eq!(from_json.edges.len(), 4);
     }
+
+    #[test]
+    fn test_table_sql_body_lineage() {
+        let mut model = Model::new();
+        model.add_table(
+            TableDefinition::new("customer_revenue", "orders").with_sql(
+                "WITH paid AS (SELECT customer_id, amount * fx_rate AS amount_usd FROM orders WHERE status = 'paid') \
+                 SELECT c.region, p.customer_id, SUM(p.amount_usd) AS revenue \
+                 FROM paid p JOIN customers c ON p.customer_id = c.id \
+                 GROUP BY c.region, p.customer_id",
+            ),
+        );
+
+        let graph = ColumnLineageGraph::from_model(&model);
+
+        let region = graph.direct_dependencies(&ColumnRef::new("customer_revenue", "region"));
+        assert_eq!(region, vec![(ColumnRef::new("customers", "region"), LineageType::Passthrough)]);
+
+        // CTE columns resolve through to the underlying entity
+        let revenue = graph.all_upstream(&ColumnRef::new("customer_revenue", "revenue"));
+        assert!(revenue.contains(&ColumnRef::new("orders", "amount")));
+        assert!(revenue.contains(&ColumnRef::new("orders", "fx_rate")));
+
+        let join = graph.direct_dependencies(&ColumnRef::new("customer_revenue", "_join_customers"));
+        assert!(join.iter().any(|(c, _)| c == &ColumnRef::new("orders", "customer_id")));
+        assert!(join.iter().any(|(c, _)| c == &ColumnRef::new("customers", "id")));
+    }
 }
@@ -124,6 +124,12 @@ pub enum SemanticError {
         available_roles: Vec<String>,
     },
 
+    /// Referenced a hierarchy that no dimension on the entity declares.
+    UnknownHierarchy {
+        entity: String,
+        hierarchy: String,
+    },
+
     /// Query planning error.
     ///
     /// Used for errors during query planning that don't fit other categories,
@@ -233,6 +239,9 @@ impl fmt::Display for SemanticError {
                     available_roles.first().map(|s| s.as_str()).unwrap_or("role")
                 )
             }
+            SemanticError::UnknownHierarchy { entity, hierarchy } => {
+                write!(f, "Unknown hierarchy '{}' on entity '{}'", hierarchy, entity)
+            }
             SemanticError::QueryPlanError(msg) => {
                 write!(f, "Query planning error: {}", msg)
             }
@@ -24,8 +24,9 @@
 //! ```
 
 use crate::cache::MetadataCache;
-use crate::dialect::Dialect;
+use crate::dialect::DialectTarget;
 use crate::model::Model;
+use crate::params::ParameterizedSql;
 use crate::query::Query;
 use crate::semantic::error::{SemanticError, SemanticResult};
 use crate::semantic::model_graph::ModelGraph;
@@ -160,6 +161,8 @@ impl QueryExecutor {
     ///     derived: vec![],
     ///     order_by: vec![],
     ///     limit: Some(10),
+    ///     top_n: None,
+    ///     as_of: AsOf::default(),
     /// };
     ///
     /// let result = executor.execute(&query)?;
@@ -184,17 +187,37 @@ impl QueryExecutor {
     /// let sql = executor.query_to_sql("monthly_revenue", Dialect::Snowflake)?;
     /// println!("{}", sql);
     /// ```
-    pub fn query_to_sql(&self, name: &str, dialect: Dialect) -> SemanticResult<String> {
+    pub fn query_to_sql(
+        &self,
+        name: &str,
+        dialect: impl Into<DialectTarget>,
+    ) -> SemanticResult<String> {
         let query = self.execute_named(name)?;
         Ok(query.to_sql(dialect))
     }
 
     /// Generate SQL for a semantic query in a specific dialect.
-    pub fn to_sql(&self, query: &SemanticQuery, dialect: Dialect) -> SemanticResult<String> {
+    pub fn to_sql(
+        &self,
+        query: &SemanticQuery,
+        dialect: impl Into<DialectTarget>,
+    ) -> SemanticResult<String> {
         let result = self.execute(query)?;
         Ok(result.to_sql(dialect))
     }
 
+    /// Generate SQL for a semantic query with literal values as bind parameters.
+    ///
+    /// Pass `params.args()` as `ExecuteQueryParams.args` when running the SQL.
+    pub fn to_sql_parameterized(
+        &self,
+        query: &SemanticQuery,
+        dialect: impl Into<DialectTarget>,
+    ) -> SemanticResult<ParameterizedSql> {
+        let result = self.execute(query)?;
+        Ok(result.to_sql_parameterized(dialect))
+    }
+
     /// List all query names defined in the model.
     ///
     /// # Example
@@ -242,6 +265,7 @@ impl QueryExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dialect::Dialect;
     use crate::model::{
         Cardinality, DataType, FactDefinition, QueryDefinition, QueryOrderBy, QuerySelect,
         Relationship, SourceEntity,
@@ -428,6 +452,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: Some(5),
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let result = executor.execute(&query).unwrap();
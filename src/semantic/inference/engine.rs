@@ -16,6 +16,9 @@ use super::{
     thresholds, Cardinality, InferredRelationship, RelationshipKey,
 };
 
+/// Rule name for composite keys matched by column names.
+const COMPOSITE_NAME_MATCH_RULE: &str = "composite_key_name_match";
+
 /// Metadata about a table, used for inference.
 #[derive(Debug, Clone)]
 pub struct TableInfo {
@@ -123,6 +126,12 @@ pub struct InferenceConfig {
     pub convention_scope: ConventionScope,
     /// Include signal breakdown in results (for explain mode).
     pub include_breakdown: bool,
+    /// Signal weights by source kind, overriding the preset's.
+    pub signal_weights: HashMap<String, f64>,
+    /// Confidence multipliers by rule name.
+    pub rule_weights: HashMap<String, f64>,
+    /// Relationships never to suggest, in either direction.
+    pub suppressed: HashSet<RelationshipKey>,
 }
 
 impl Default for InferenceConfig {
@@ -139,6 +148,9 @@ impl Default for InferenceConfig {
             excluded_keywords: Vec::new(),
             convention_scope: ConventionScope::default(),
             include_breakdown: false,
+            signal_weights: HashMap::new(),
+            rule_weights: HashMap::new(),
+            suppressed: HashSet::new(),
         }
     }
 }
@@ -213,6 +225,35 @@ impl InferenceConfig {
         self
     }
 
+    /// Builder: override the weight of a signal source kind.
+    pub fn with_signal_weight(mut self, kind: impl Into<String>, weight: f64) -> Self {
+        self.signal_weights
+            .insert(kind.into(), weight.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Builder: scale the confidence of relationships found by a rule.
+    pub fn with_rule_weight(mut self, rule: impl Into<String>, weight: f64) -> Self {
+        self.rule_weights.insert(rule.into(), weight.max(0.0));
+        self
+    }
+
+    /// Builder: never suggest a relationship, in either direction.
+    pub fn with_suppressed(mut self, key: RelationshipKey) -> Self {
+        self.suppressed.insert(key);
+        self
+    }
+
+    /// Whether a relationship is suppressed, in either direction.
+    pub fn is_suppressed(&self, key: &RelationshipKey) -> bool {
+        self.suppressed.contains(key) || self.suppressed.contains(&key.reversed())
+    }
+
+    /// Confidence multiplier for relationships found by a rule.
+    pub fn rule_weight(&self, rule: &str) -> f64 {
+        self.rule_weights.get(rule).copied().unwrap_or(1.0)
+    }
+
     /// Convert to pipeline config.
     fn to_pipeline_config(&self) -> PipelineConfig {
         PipelineConfig {
@@ -224,13 +265,17 @@ impl InferenceConfig {
         }
     }
 
-    /// Get signal weights for this preset.
-    fn signal_weights(&self) -> SignalWeights {
-        match self.weight_preset {
+    /// Get signal weights for this preset, with overrides applied.
+    pub(crate) fn weights(&self) -> SignalWeights {
+        let mut weights = match self.weight_preset {
             WeightPreset::Balanced => SignalWeights::new(),
             WeightPreset::HighPrecision => SignalWeights::high_precision(),
             WeightPreset::HighRecall => SignalWeights::high_recall(),
+        };
+        for (kind, weight) in &self.signal_weights {
+            weights.set(kind.clone(), *weight);
         }
+        weights
     }
 }
 
@@ -250,8 +295,11 @@ impl Default for InferenceEngine {
 impl InferenceEngine {
     /// Create an engine with custom configuration.
     pub fn with_config(config: InferenceConfig) -> Self {
-        let mut weights = config.signal_weights();
-        weights.min_confidence = config.min_confidence;
+        let mut weights = config.weights();
+        // Rule weights are applied after scoring, so let through candidates
+        // that the largest of them could still lift over the threshold
+        let max_rule_weight = config.rule_weights.values().copied().fold(1.0, f64::max);
+        weights.min_confidence = config.min_confidence / max_rule_weight;
 
         // Create pipeline with both config and weights
         let pipeline_config = config.to_pipeline_config();
@@ -276,6 +324,14 @@ impl InferenceEngine {
         self.pipeline.load_constraints(tables);
     }
 
+    /// Load table profiles for index, row count and sample signals.
+    ///
+    /// Call this with profiles fetched via `MetadataProvider::get_table_profile`.
+    /// Tables without a profile are scored as before.
+    pub fn load_profiles(&mut self, profiles: &[crate::metadata::TableProfile]) {
+        self.pipeline.load_profiles(profiles);
+    }
+
     /// Check if a relationship matches a database constraint.
     pub fn has_db_constraint(
         &self,
@@ -372,8 +428,13 @@ impl InferenceEngine {
         // Convert to InferredRelationship
         let mut relationships: Vec<InferredRelationship> = all_candidates
             .into_iter()
-            .filter(|sc| sc.score.confidence >= self.config.min_confidence)
             .map(|sc| self.scored_to_relationship(sc))
+            .filter(|r| r.confidence >= self.config.min_confidence)
+            .filter(|r| {
+                !self
+                    .config
+                    .is_suppressed(&RelationshipKey::from_relationship(r))
+            })
             .collect();
 
         // Sort by confidence descending
@@ -433,6 +494,7 @@ impl InferenceEngine {
                     to_schema: candidate.to_schema,
                     to_table: candidate.to_table,
                     to_column: candidate.to_column,
+                    additional_columns: Vec::new(),
                     confidence: candidate.base_score, // 0.98 from constraint
                     rule: candidate.matched_rule,
                     cardinality: Cardinality::ManyToOne, // FK usually many-to-one
@@ -444,6 +506,16 @@ impl InferenceEngine {
             }
         }
 
+        // Composite keys replace the single-column relationships they cover
+        let composites = self.infer_composite_relationships(tables);
+        all_relationships.retain(|r| !composites.iter().any(|c| c.covers(r)));
+        all_relationships.extend(composites);
+        all_relationships.retain(|r| {
+            !self
+                .config
+                .is_suppressed(&RelationshipKey::from_relationship(r))
+        });
+
         // Sort by confidence descending
         all_relationships.sort_by(|a, b| {
             b.confidence
@@ -468,6 +540,118 @@ impl InferenceEngine {
         all_relationships
     }
 
+    /// Infer relationships over multi-column keys.
+    ///
+    /// Composite FK constraints are taken as-is. Otherwise, a table carrying
+    /// every column of another table's multi-column primary key is linked to
+    /// it on all of those columns, provided there is evidence the columns
+    /// reference it: sampled key values found in the target or, without
+    /// samples, none of the columns being the table's own single-column key
+    /// (`products(id)` identifies products, it doesn't reference `id` elsewhere).
+    fn infer_composite_relationships(&self, tables: &[TableInfo]) -> Vec<InferredRelationship> {
+        let mut composites: Vec<InferredRelationship> = self
+            .pipeline
+            .get_composite_constraints()
+            .into_iter()
+            .map(|fk| {
+                let mut pairs = fk.columns.into_iter();
+                let (from_column, to_column) = pairs.next().unwrap_or_default();
+                InferredRelationship {
+                    from_schema: fk.from_schema,
+                    from_table: fk.from_table,
+                    from_column,
+                    to_schema: fk.to_schema,
+                    to_table: fk.to_table,
+                    to_column,
+                    additional_columns: pairs.collect(),
+                    confidence: thresholds::confidence::DB_CONSTRAINT,
+                    rule: format!("db_constraint:{}", fk.constraint_name),
+                    cardinality: Cardinality::ManyToOne,
+                    signal_breakdown: None,
+                    source: super::RelationshipSource::DatabaseConstraint,
+                }
+            })
+            .collect();
+
+        let name_match_confidence = (thresholds::confidence::COMPOSITE_NAME_MATCH
+            * self.config.rule_weight(COMPOSITE_NAME_MATCH_RULE))
+        .min(1.0);
+        if name_match_confidence < self.config.min_confidence {
+            return composites;
+        }
+
+        let lowercase_pk = |table: &TableInfo| -> HashSet<String> {
+            table.primary_key.iter().map(|c| c.to_lowercase()).collect()
+        };
+
+        for target in tables.iter().filter(|t| t.primary_key.len() > 1) {
+            let target_pk = lowercase_pk(target);
+
+            for table in tables {
+                // Skip the target itself and tables sharing its grain
+                if lowercase_pk(table) == target_pk {
+                    continue;
+                }
+
+                let pairs: Option<Vec<(String, String)>> = target
+                    .primary_key
+                    .iter()
+                    .map(|pk| {
+                        table
+                            .columns
+                            .iter()
+                            .find(|c| c.name.eq_ignore_ascii_case(pk))
+                            .map(|c| (c.name.clone(), pk.clone()))
+                    })
+                    .collect();
+                let Some(pairs) = pairs else {
+                    continue;
+                };
+
+                let references_target = match self.pipeline.composite_samples_overlap(
+                    &table.schema,
+                    &table.name,
+                    &target.schema,
+                    &target.name,
+                    &pairs,
+                ) {
+                    Some(overlap) => overlap,
+                    None => !is_own_key(table, &pairs),
+                };
+                if !references_target {
+                    continue;
+                }
+
+                let already_known = composites.iter().any(|c| {
+                    c.from_table.eq_ignore_ascii_case(&table.name)
+                        && c.to_table.eq_ignore_ascii_case(&target.name)
+                });
+                if already_known {
+                    continue;
+                }
+
+                let mut pairs = pairs.into_iter();
+                let (from_column, to_column) = pairs.next().unwrap_or_default();
+                composites.push(InferredRelationship {
+                    from_schema: table.schema.clone(),
+                    from_table: table.name.clone(),
+                    from_column,
+                    to_schema: target.schema.clone(),
+                    to_table: target.name.clone(),
+                    to_column,
+                    additional_columns: pairs.collect(),
+                    confidence: name_match_confidence,
+                    rule: COMPOSITE_NAME_MATCH_RULE.to_string(),
+                    cardinality: Cardinality::ManyToOne,
+                    signal_breakdown: None,
+                    source: super::RelationshipSource::Inferred,
+                });
+            }
+        }
+
+        composites
+    }
+
     /// Convert a scored candidate to an InferredRelationship.
     fn scored_to_relationship(&self, scored: ScoredCandidate) -> InferredRelationship {
         let candidate = scored.candidate;
@@ -484,11 +668,19 @@ impl InferenceEngine {
             None
         };
 
-        // Determine source based on whether we have a DB constraint signal
-        let source = if scored.signals.has_definitive_signal() {
-            super::RelationshipSource::DatabaseConstraint
+        // Determine source based on whether we have a DB constraint signal.
+        // Constraints are facts, so learned rule weights only apply to the rest.
+        let (source, confidence) = if scored.signals.has_definitive_signal() {
+            (
+                super::RelationshipSource::DatabaseConstraint,
+                scored.score.confidence,
+            )
         } else {
-            super::RelationshipSource::Inferred
+            let weight = self.config.rule_weight(&candidate.matched_rule);
+            (
+                super::RelationshipSource::Inferred,
+                (scored.score.confidence * weight).min(1.0),
+            )
         };
 
         InferredRelationship {
@@ -498,7 +690,8 @@ impl InferenceEngine {
             to_schema: candidate.to_schema,
             to_table: candidate.to_table,
             to_column: candidate.to_column,
-            confidence: scored.score.confidence,
+            additional_columns: Vec::new(),
+            confidence,
             rule: candidate.matched_rule,
             cardinality,
             signal_breakdown,
@@ -681,6 +874,14 @@ impl InferenceEngine {
     }
 }
 
+/// Whether any matched column is the table's own single-column primary key.
+fn is_own_key(table: &TableInfo, pairs: &[(String, String)]) -> bool {
+    match table.primary_key.as_slice() {
+        [own] => pairs.iter().any(|(from, _)| from.eq_ignore_ascii_case(own)),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -840,6 +1041,7 @@ mod tests {
             to_schema: "public".to_string(),
             to_table: "customers".to_string(),
             to_column: "id".to_string(),
+            additional_columns: Vec::new(),
             confidence: 0.70,
             rule: "suffix_id".to_string(),
             cardinality: Cardinality::Unknown,
@@ -879,6 +1081,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_profile_signals_in_breakdown() {
+        use crate::metadata::{IndexInfo, RowCount, SampleRows, TableProfile};
+
+        let profile = |table: &str, rows: i64, column: &str, values: [&str; 3]| TableProfile {
+            schema: "public".to_string(),
+            table: table.to_string(),
+            indexes: vec![IndexInfo {
+                name: format!("ix_{}", column),
+                columns: vec![column.to_string()],
+                is_unique: false,
+                is_primary_key: false,
+            }],
+            row_count: Some(RowCount {
+                row_count: rows,
+                is_exact: true,
+            }),
+            sample: Some(SampleRows {
+                columns: vec![column.to_string()],
+                rows: values.iter().map(|v| vec![serde_json::json!(v)]).collect(),
+            }),
+        };
+        let tables = make_test_tables();
+        let customer_confidence = |profiles: &[TableProfile]| {
+            let mut engine = InferenceEngine::with_config(
+                InferenceConfig::default()
+                    .with_breakdown(true)
+                    .with_min_confidence(0.0),
+            );
+            engine.prepare(&tables);
+            engine.load_profiles(profiles);
+            engine
+                .infer_relationships(&tables[0], &tables)
+                .into_iter()
+                .find(|r| r.from_column == "customer_id" && r.to_table == "customers")
+                .unwrap()
+        };
+
+        let baseline = customer_confidence(&[]);
+        let matching = customer_confidence(&[
+            profile("orders", 10_000, "customer_id", ["C-1", "C-2", "C-3"]),
+            profile("customers", 100, "id", ["C-1", "C-2", "C-4"]),
+        ]);
+        let sources: Vec<_> = matching
+            .signal_breakdown
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|b| b.source.as_str())
+            .collect();
+        assert!(sources.contains(&"index"));
+        assert!(sources.contains(&"row_ratio"));
+        assert!(sources.contains(&"sample"));
+        assert!(matching.confidence >= baseline.confidence);
+
+        let mismatched = customer_confidence(&[
+            profile("orders", 10_000, "customer_id", ["C-1", "C-2", "C-3"]),
+            profile("customers", 100, "id", ["Ann", "Bob", "Eve"]),
+        ]);
+        assert!(mismatched.confidence < baseline.confidence);
+    }
+
     #[test]
     fn test_negative_signals_filter() {
         let mut engine = InferenceEngine::default();
@@ -1266,6 +1530,206 @@ mod tests {
         );
     }
 
+    fn composite_key_tables() -> Vec<TableInfo> {
+        let column = |name: &str| ColumnInfo {
+            name: name.to_string(),
+            data_type: "integer".to_string(),
+            is_nullable: false,
+            is_unique: Some(false),
+        };
+
+        vec![
+            TableInfo {
+                schema: "public".to_string(),
+                name: "order_lines".to_string(),
+                columns: vec![column("order_id"), column("line_no"), column("quantity")],
+                primary_key: vec!["order_id".to_string(), "line_no".to_string()],
+            },
+            TableInfo {
+                schema: "public".to_string(),
+                name: "shipments".to_string(),
+                columns: vec![column("shipment_id"), column("order_id"), column("line_no")],
+                primary_key: vec!["shipment_id".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_composite_key_from_naming() {
+        let mut engine = InferenceEngine::default();
+        let tables = composite_key_tables();
+        engine.prepare(&tables);
+
+        let relationships = engine.infer_all_relationships(&tables);
+
+        let composite = relationships
+            .iter()
+            .find(|r| r.from_table == "shipments" && r.to_table == "order_lines")
+            .expect("Should link shipments to order_lines on the full key");
+        assert_eq!(
+            composite.column_pairs(),
+            vec![("order_id", "order_id"), ("line_no", "line_no")]
+        );
+        assert_eq!(composite.rule, "composite_key_name_match");
+        assert_eq!(
+            composite.source,
+            crate::semantic::inference::RelationshipSource::Inferred
+        );
+
+        // No partial-key relationship between the same tables survives
+        let between = relationships
+            .iter()
+            .filter(|r| {
+                (r.from_table == "shipments" && r.to_table == "order_lines")
+                    || (r.from_table == "order_lines" && r.to_table == "shipments")
+            })
+            .count();
+        assert_eq!(between, 1);
+    }
+
+    #[test]
+    fn test_composite_key_requires_evidence() {
+        use crate::metadata::{SampleRows, TableProfile};
+
+        let column = |name: &str| ColumnInfo {
+            name: name.to_string(),
+            data_type: "integer".to_string(),
+            is_nullable: false,
+            is_unique: Some(false),
+        };
+        let mut tables = composite_key_tables();
+        tables.push(TableInfo {
+            schema: "public".to_string(),
+            name: "customers".to_string(),
+            columns: vec![column("tenant_id"), column("id")],
+            primary_key: vec!["tenant_id".to_string(), "id".to_string()],
+        });
+        tables.push(TableInfo {
+            schema: "public".to_string(),
+            name: "products".to_string(),
+            columns: vec![column("id"), column("tenant_id")],
+            primary_key: vec!["id".to_string()],
+        });
+
+        let linked = |profiles: &[TableProfile], from: &str, to: &str| {
+            let mut engine = InferenceEngine::default();
+            engine.prepare(&tables);
+            engine.load_profiles(profiles);
+            engine
+                .infer_all_relationships(&tables)
+                .iter()
+                .any(|r| r.from_table == from && r.to_table == to && r.column_pairs().len() > 1)
+        };
+
+        // products.id is products' own key, not a reference to customers
+        assert!(!linked(&[], "products", "customers"));
+        assert!(linked(&[], "shipments", "order_lines"));
+
+        let sample = |table: &str, columns: [&str; 2], rows: [[i64; 2]; 2]| TableProfile {
+            schema: "public".to_string(),
+            table: table.to_string(),
+            indexes: vec![],
+            row_count: None,
+            sample: Some(SampleRows {
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                rows: rows
+                    .iter()
+                    .map(|row| row.iter().map(|v| serde_json::json!(v)).collect())
+                    .collect(),
+            }),
+        };
+
+        // Sampled values overrule the naming either way
+        let overlapping = [
+            sample("customers", ["tenant_id", "id"], [[1, 10], [1, 11]]),
+            sample("products", ["id", "tenant_id"], [[10, 1], [12, 2]]),
+        ];
+        assert!(linked(&overlapping, "products", "customers"));
+
+        let disjoint = [
+            sample("order_lines", ["order_id", "line_no"], [[1, 1], [1, 2]]),
+            sample("shipments", ["order_id", "line_no"], [[7, 1], [8, 1]]),
+        ];
+        assert!(!linked(&disjoint, "shipments", "order_lines"));
+    }
+
+    #[test]
+    fn test_composite_key_from_constraint() {
+        use crate::metadata::{
+            ColumnInfo as MetaColumnInfo, ForeignKeyInfo, PrimaryKeyInfo, TableMetadata, TableType,
+        };
+
+        let column = |name: &str, position: i32| MetaColumnInfo {
+            name: name.to_string(),
+            position,
+            data_type: "integer".to_string(),
+            is_nullable: false,
+            max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            default_value: None,
+            is_identity: false,
+            is_computed: false,
+        };
+
+        let table_metadata = vec![
+            TableMetadata {
+                schema: "public".to_string(),
+                name: "order_lines".to_string(),
+                table_type: TableType::Table,
+                columns: vec![column("order_id", 1), column("line_no", 2)],
+                primary_key: Some(PrimaryKeyInfo {
+                    name: "pk_order_lines".to_string(),
+                    columns: vec!["order_id".to_string(), "line_no".to_string()],
+                }),
+                foreign_keys: vec![],
+                unique_constraints: vec![],
+            },
+            TableMetadata {
+                schema: "public".to_string(),
+                name: "returns".to_string(),
+                table_type: TableType::Table,
+                columns: vec![column("return_id", 1), column("ord", 2), column("line", 3)],
+                primary_key: Some(PrimaryKeyInfo {
+                    name: "pk_returns".to_string(),
+                    columns: vec!["return_id".to_string()],
+                }),
+                foreign_keys: vec![ForeignKeyInfo {
+                    name: "fk_returns_line".to_string(),
+                    columns: vec!["ord".to_string(), "line".to_string()],
+                    referenced_schema: "public".to_string(),
+                    referenced_table: "order_lines".to_string(),
+                    referenced_columns: vec!["order_id".to_string(), "line_no".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                }],
+                unique_constraints: vec![],
+            },
+        ];
+        let tables: Vec<TableInfo> = table_metadata.iter().map(TableInfo::from).collect();
+
+        let mut engine = InferenceEngine::default();
+        engine.prepare(&tables);
+        engine.load_constraints(&table_metadata);
+
+        let relationships = engine.infer_all_relationships(&tables);
+
+        let between: Vec<_> = relationships
+            .iter()
+            .filter(|r| r.from_table == "returns" && r.to_table == "order_lines")
+            .collect();
+        assert_eq!(between.len(), 1, "{:?}", between);
+        assert_eq!(
+            between[0].column_pairs(),
+            vec![("ord", "order_id"), ("line", "line_no")]
+        );
+        assert_eq!(between[0].rule, "db_constraint:fk_returns_line");
+        assert_eq!(
+            between[0].source,
+            crate::semantic::inference::RelationshipSource::DatabaseConstraint
+        );
+    }
+
     #[test]
     fn test_update_with_statistics() {
         use crate::metadata::{ColumnStats, ValueOverlap};
@@ -1279,6 +1743,7 @@ mod tests {
             to_schema: "public".to_string(),
             to_table: "customers".to_string(),
             to_column: "id".to_string(),
+            additional_columns: Vec::new(),
             confidence: 0.65,
             rule: "suffix_id".to_string(),
             cardinality: Cardinality::Unknown,
@@ -1340,6 +1805,7 @@ mod tests {
             to_schema: "public".to_string(),
             to_table: "customers".to_string(),
             to_column: "id".to_string(),
+            additional_columns: Vec::new(),
             confidence: 0.65,
             rule: "suffix_id".to_string(),
             cardinality: Cardinality::Unknown,
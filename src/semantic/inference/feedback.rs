@@ -0,0 +1,348 @@
+//! Learning from accepted and rejected relationship suggestions.
+//!
+//! Decisions are persisted per connection in the [`MetadataCache`]. Loaded
+//! back as [`InferenceFeedback`], they suppress rejected pairs and recalibrate
+//! rule and signal weights, exported as an [`InferenceConfig`]:
+//!
+//! ```ignore
+//! let feedback = InferenceFeedback::load(&cache, &conn_hash)?;
+//! let engine = InferenceEngine::with_config(feedback.learned_config(InferenceConfig::default()));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cache::{CacheResult, InferenceDecision, MetadataCache};
+
+use super::{
+    signals::aggregator::SignalWeights, InferenceConfig, InferredRelationship, RelationshipKey,
+};
+
+/// Pseudo-decisions added on each side of an acceptance rate, so a rule or
+/// signal with little feedback stays close to neutral.
+const PRIOR_DECISIONS: f64 = 2.0;
+
+/// Lower bound on how far feedback can scale a weight.
+const MIN_MULTIPLIER: f64 = 0.5;
+
+/// Upper bound on how far feedback can scale a weight.
+const MAX_MULTIPLIER: f64 = 1.5;
+
+/// Rule name prefix of relationships backed by database constraints.
+const CONSTRAINT_RULE_PREFIX: &str = "db_constraint";
+
+/// Accumulated decisions on suggested relationships for one connection.
+#[derive(Debug, Clone, Default)]
+pub struct InferenceFeedback {
+    decisions: Vec<InferenceDecision>,
+}
+
+impl InferenceFeedback {
+    /// Create feedback from decisions, oldest first.
+    pub fn new(decisions: Vec<InferenceDecision>) -> Self {
+        Self { decisions }
+    }
+
+    /// Load the decisions recorded for a connection.
+    pub fn load(cache: &MetadataCache, conn_hash: &str) -> CacheResult<Self> {
+        Ok(Self::new(cache.inference_decisions(conn_hash)?))
+    }
+
+    /// Record a decision on a suggested relationship.
+    ///
+    /// Signals are taken from the relationship's breakdown, so suggestions
+    /// should come from an engine with `include_breakdown` enabled for them
+    /// to feed signal calibration.
+    pub fn record(
+        cache: &MetadataCache,
+        conn_hash: &str,
+        relationship: &InferredRelationship,
+        accepted: bool,
+    ) -> CacheResult<InferenceDecision> {
+        let decided_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let decision = Self::decision(relationship, accepted, decided_at);
+        cache.record_inference_decision(conn_hash, &decision)?;
+        Ok(decision)
+    }
+
+    /// Build the decision on a suggested relationship.
+    pub fn decision(
+        relationship: &InferredRelationship,
+        accepted: bool,
+        decided_at: i64,
+    ) -> InferenceDecision {
+        let mut signals: Vec<String> = Vec::new();
+        for entry in relationship.signal_breakdown.iter().flatten() {
+            if entry.contribution > 0.0 && !signals.contains(&entry.source) {
+                signals.push(entry.source.clone());
+            }
+        }
+
+        InferenceDecision {
+            from_table: relationship.from_table.clone(),
+            from_column: relationship.from_column.clone(),
+            to_table: relationship.to_table.clone(),
+            to_column: relationship.to_column.clone(),
+            accepted,
+            rule: relationship.rule.clone(),
+            signals,
+            decided_at,
+        }
+    }
+
+    /// The accumulated decisions, oldest first.
+    pub fn decisions(&self) -> &[InferenceDecision] {
+        &self.decisions
+    }
+
+    /// Pairs whose latest decision, in either direction, was a rejection.
+    pub fn rejected(&self) -> HashSet<RelationshipKey> {
+        let mut latest: HashMap<RelationshipKey, bool> = HashMap::new();
+        for decision in &self.decisions {
+            let key = decision_key(decision);
+            latest.remove(&key.reversed());
+            latest.insert(key, decision.accepted);
+        }
+
+        latest
+            .into_iter()
+            .filter(|(_, accepted)| !accepted)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Confidence multipliers by rule, from each rule's acceptance rate.
+    ///
+    /// A rule accepted as often as rejected keeps a weight of 1.0.
+    pub fn rule_weights(&self) -> HashMap<String, f64> {
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for decision in &self.decisions {
+            if decision.rule.starts_with(CONSTRAINT_RULE_PREFIX) {
+                continue;
+            }
+            let (accepted, total) = counts.entry(&decision.rule).or_default();
+            *accepted += usize::from(decision.accepted);
+            *total += 1;
+        }
+
+        counts
+            .into_iter()
+            .map(|(rule, (accepted, total))| {
+                let weight =
+                    (acceptance_rate(accepted, total) / 0.5).clamp(MIN_MULTIPLIER, MAX_MULTIPLIER);
+                (rule.to_string(), weight)
+            })
+            .collect()
+    }
+
+    /// Signal weights recalibrated from `base`.
+    ///
+    /// Each signal is scaled by how much more (or less) often suggestions
+    /// carrying it were accepted than suggestions overall, so a signal present
+    /// on every suggestion keeps its weight.
+    pub fn signal_weights(&self, base: &SignalWeights) -> HashMap<String, f64> {
+        let overall = acceptance_rate(
+            self.decisions.iter().filter(|d| d.accepted).count(),
+            self.decisions.len(),
+        );
+
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for decision in &self.decisions {
+            for signal in &decision.signals {
+                let (accepted, total) = counts.entry(signal).or_default();
+                *accepted += usize::from(decision.accepted);
+                *total += 1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .map(|(kind, (accepted, total))| {
+                let multiplier = (acceptance_rate(accepted, total) / overall)
+                    .clamp(MIN_MULTIPLIER, MAX_MULTIPLIER);
+                (
+                    kind.to_string(),
+                    (base.get(kind) * multiplier).clamp(0.0, 1.0),
+                )
+            })
+            .collect()
+    }
+
+    /// Export the learned configuration on top of `base`.
+    ///
+    /// Rejected pairs are suppressed and the rule and signal weights learned
+    /// from the decisions replace any set on `base`.
+    pub fn learned_config(&self, base: InferenceConfig) -> InferenceConfig {
+        let signal_weights = self.signal_weights(&base.weights());
+
+        let mut config = base;
+        config.signal_weights.extend(signal_weights);
+        config.rule_weights.extend(self.rule_weights());
+        config.suppressed.extend(self.rejected());
+        config
+    }
+}
+
+/// Acceptance rate, smoothed towards one half.
+fn acceptance_rate(accepted: usize, total: usize) -> f64 {
+    (accepted as f64 + PRIOR_DECISIONS) / (total as f64 + 2.0 * PRIOR_DECISIONS)
+}
+
+fn decision_key(decision: &InferenceDecision) -> RelationshipKey {
+    RelationshipKey::new(
+        &decision.from_table,
+        &decision.from_column,
+        &decision.to_table,
+        &decision.to_column,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::inference::{ColumnInfo, InferenceEngine, TableInfo};
+
+    fn column(name: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: "integer".to_string(),
+            is_nullable: false,
+            is_unique: None,
+        }
+    }
+
+    fn tables() -> Vec<TableInfo> {
+        let table = |name: &str, columns: &[&str]| TableInfo {
+            schema: "public".to_string(),
+            name: name.to_string(),
+            columns: columns.iter().map(|c| column(c)).collect(),
+            primary_key: vec!["id".to_string()],
+        };
+        vec![
+            table("orders", &["id", "customer_id", "product_id"]),
+            table("customers", &["id"]),
+            table("products", &["id"]),
+        ]
+    }
+
+    fn decision(
+        from: (&str, &str),
+        to: (&str, &str),
+        accepted: bool,
+        at: i64,
+    ) -> InferenceDecision {
+        InferenceDecision {
+            from_table: from.0.to_string(),
+            from_column: from.1.to_string(),
+            to_table: to.0.to_string(),
+            to_column: to.1.to_string(),
+            accepted,
+            rule: "suffix_id".to_string(),
+            signals: vec!["naming".to_string()],
+            decided_at: at,
+        }
+    }
+
+    #[test]
+    fn test_rejected_pairs_are_suppressed() {
+        let engine = InferenceEngine::default();
+        let relationships = engine.infer_all_relationships(&tables());
+        let product = relationships
+            .iter()
+            .find(|r| r.from_column == "product_id")
+            .expect("product_id relationship");
+
+        let cache = MetadataCache::open_in_memory().unwrap();
+        InferenceFeedback::record(&cache, "conn", product, false).unwrap();
+
+        let feedback = InferenceFeedback::load(&cache, "conn").unwrap();
+        let engine =
+            InferenceEngine::with_config(feedback.learned_config(InferenceConfig::default()));
+        let relationships = engine.infer_all_relationships(&tables());
+        assert!(relationships.iter().any(|r| r.from_column == "customer_id"));
+        assert!(!relationships.iter().any(|r| r.from_column == "product_id"));
+    }
+
+    #[test]
+    fn test_later_decision_overrides_reversed_rejection() {
+        let feedback = InferenceFeedback::new(vec![
+            decision(("orders", "customer_id"), ("customers", "id"), false, 1),
+            decision(("customers", "id"), ("orders", "customer_id"), true, 2),
+            decision(("orders", "product_id"), ("products", "id"), false, 3),
+        ]);
+
+        let rejected = feedback.rejected();
+        assert_eq!(rejected.len(), 1);
+        assert!(rejected.contains(&RelationshipKey::new(
+            "orders",
+            "product_id",
+            "products",
+            "id"
+        )));
+
+        let config = feedback.learned_config(InferenceConfig::default());
+        assert!(config.is_suppressed(&RelationshipKey::new(
+            "products",
+            "id",
+            "orders",
+            "product_id"
+        )));
+        assert!(!config.is_suppressed(&RelationshipKey::new(
+            "orders",
+            "customer_id",
+            "customers",
+            "id"
+        )));
+    }
+
+    #[test]
+    fn test_weights_recalibrate_from_decisions() {
+        let mut decisions: Vec<_> = (0..6)
+            .map(|i| InferenceDecision {
+                rule: "same_column_name".to_string(),
+                signals: vec!["type".to_string(), "convention".to_string()],
+                ..decision(("a", &format!("c{i}")), ("b", "id"), false, i)
+            })
+            .collect();
+        decisions.extend((0..6).map(|i| InferenceDecision {
+            signals: vec!["type".to_string(), "naming".to_string()],
+            ..decision(("c", &format!("c{i}")), ("d", "id"), true, 10 + i)
+        }));
+        let feedback = InferenceFeedback::new(decisions);
+
+        let rules = feedback.rule_weights();
+        assert!(rules["same_column_name"] < 1.0);
+        assert!(rules["suffix_id"] > 1.0);
+
+        let base = SignalWeights::new();
+        let signals = feedback.signal_weights(&base);
+        assert!(signals["convention"] < base.get("convention"));
+        assert!(signals["naming"] > base.get("naming"));
+        // Present on every suggestion, so it says nothing either way
+        assert!((signals["type"] - base.get("type")).abs() < 1e-9);
+
+        let config = feedback.learned_config(InferenceConfig::default());
+        assert_eq!(
+            config.rule_weight("same_column_name"),
+            rules["same_column_name"]
+        );
+        assert_eq!(config.rule_weight("unknown_rule"), 1.0);
+        assert_eq!(config.weights().get("naming"), signals["naming"]);
+    }
+
+    #[test]
+    fn test_rule_weight_scales_confidence() {
+        let baseline = InferenceEngine::default().infer_all_relationships(&tables());
+        let customer = baseline
+            .iter()
+            .find(|r| r.from_column == "customer_id")
+            .unwrap();
+
+        let config = InferenceConfig::default().with_rule_weight(customer.rule.clone(), 0.1);
+        let weighted = InferenceEngine::with_config(config).infer_all_relationships(&tables());
+        assert!(!weighted.iter().any(|r| r.from_column == "customer_id"));
+    }
+}
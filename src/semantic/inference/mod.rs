@@ -20,12 +20,14 @@
 //! ```
 
 mod engine;
+mod feedback;
 mod model_integration;
 mod rules;
 mod scoring;
 pub mod signals;
 
 pub use engine::{ColumnInfo, InferenceConfig, InferenceEngine, TableInfo, WeightPreset};
+pub use feedback::InferenceFeedback;
 pub use model_integration::{InferenceResult, ModelInferenceConfig};
 pub use rules::{default_rules, InferenceRule, RuleMatch};
 pub use scoring::{ConfidenceScore, ScoringFactors};
@@ -39,6 +41,8 @@ pub mod thresholds {
     pub mod confidence {
         /// Confidence for relationships backed by database FK constraints.
         pub const DB_CONSTRAINT: f64 = 0.98;
+        /// Confidence for composite keys matched by column names alone.
+        pub const COMPOSITE_NAME_MATCH: f64 = 0.80;
         /// Maximum confidence for inferred relationships (never 100% certain).
         pub const INFERENCE_CAP: f64 = 0.95;
         /// Minimum confidence for high-precision mode.
@@ -120,6 +124,8 @@ pub struct InferredRelationship {
     pub to_table: String,
     /// The target column name
     pub to_column: String,
+    /// Further `(from, to)` column pairs for composite keys
+    pub additional_columns: Vec<(String, String)>,
     /// Confidence score (0.0 to 1.0)
     pub confidence: f64,
     /// The rule that matched (primary signal source)
@@ -132,6 +138,37 @@ pub struct InferredRelationship {
     pub source: RelationshipSource,
 }
 
+impl InferredRelationship {
+    /// All `(from, to)` join column pairs, primary pair first.
+    pub fn column_pairs(&self) -> Vec<(&str, &str)> {
+        std::iter::once((self.from_column.as_str(), self.to_column.as_str()))
+            .chain(
+                self.additional_columns
+                    .iter()
+                    .map(|(from, to)| (from.as_str(), to.as_str())),
+            )
+            .collect()
+    }
+
+    /// Whether this composite relationship subsumes a single-column one
+    /// between the same tables, in either direction.
+    fn covers(&self, other: &InferredRelationship) -> bool {
+        let contains = |from: &str, to: &str| {
+            self.column_pairs()
+                .iter()
+                .any(|(f, t)| f.eq_ignore_ascii_case(from) && t.eq_ignore_ascii_case(to))
+        };
+        let same_tables = |from: &str, to: &str| {
+            self.from_table.eq_ignore_ascii_case(from) && self.to_table.eq_ignore_ascii_case(to)
+        };
+
+        (same_tables(&other.from_table, &other.to_table)
+            && contains(&other.from_column, &other.to_column))
+            || (same_tables(&other.to_table, &other.from_table)
+                && contains(&other.to_column, &other.from_column))
+    }
+}
+
 impl PartialEq for InferredRelationship {
     fn eq(&self, other: &Self) -> bool {
         self.from_schema == other.from_schema
@@ -140,6 +177,7 @@ impl PartialEq for InferredRelationship {
             && self.to_schema == other.to_schema
             && self.to_table == other.to_table
             && self.to_column == other.to_column
+            && self.additional_columns == other.additional_columns
             && (self.confidence - other.confidence).abs() < 0.001
             && self.rule == other.rule
             && self.cardinality == other.cardinality
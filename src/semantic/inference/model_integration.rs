@@ -107,13 +107,14 @@ impl Model {
 
             // Convert to model Relationship
             let cardinality: Cardinality = inf_rel.cardinality;
-            let relationship = Relationship::new(
+            let mut relationship = Relationship::new(
                 &inf_rel.from_table,
                 &inf_rel.to_table,
                 &inf_rel.from_column,
                 &inf_rel.to_column,
                 cardinality,
             );
+            relationship.additional_columns = inf_rel.additional_columns.clone();
 
             self.relationships.push(relationship);
             added_count += 1;
@@ -237,6 +238,7 @@ mod tests {
                 data_type: DataType::Int64,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         orders.columns.insert(
@@ -246,6 +248,7 @@ mod tests {
                 data_type: DataType::Int64,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         orders.columns.insert(
@@ -255,6 +258,7 @@ mod tests {
                 data_type: DataType::Int64,
                 nullable: true,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         orders.primary_key = vec!["id".to_string()];
@@ -269,6 +273,7 @@ mod tests {
                 data_type: DataType::Int64,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         customers.columns.insert(
@@ -278,6 +283,7 @@ mod tests {
                 data_type: DataType::String,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         customers.primary_key = vec!["id".to_string()];
@@ -292,6 +298,7 @@ mod tests {
                 data_type: DataType::Int64,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         products.columns.insert(
@@ -301,6 +308,7 @@ mod tests {
                 data_type: DataType::String,
                 nullable: false,
                 description: None,
+                accepted_values: Vec::new(),
             },
         );
         products.primary_key = vec!["id".to_string()];
@@ -13,7 +13,7 @@ use super::Signal;
 #[derive(Debug, Clone)]
 pub struct SignalWeights {
     /// Weight multipliers by signal source kind.
-    weights: HashMap<&'static str, f64>,
+    weights: HashMap<String, f64>,
     /// Default weight for unknown sources.
     default_weight: f64,
     /// Minimum confidence threshold (scores below this are rejected).
@@ -34,31 +34,40 @@ impl SignalWeights {
         let mut weights = HashMap::new();
 
         // Foreign key constraints are definitive
-        weights.insert("fk", 1.0);
+        weights.insert("fk".to_string(), 1.0);
 
         // Naming conventions are strong signals
-        weights.insert("naming", 0.85);
+        weights.insert("naming".to_string(), 0.85);
 
         // Type compatibility is important
-        weights.insert("type", 0.75);
+        weights.insert("type".to_string(), 0.75);
 
         // Statistics are valuable but noisy
-        weights.insert("stats", 0.65);
+        weights.insert("stats".to_string(), 0.65);
 
         // Unique constraints help validate PKs
-        weights.insert("unique", 0.70);
+        weights.insert("unique".to_string(), 0.70);
 
         // Value overlap is strong when available
-        weights.insert("overlap", 0.80);
+        weights.insert("overlap".to_string(), 0.80);
 
         // Negative signals have high weight (to filter false positives)
-        weights.insert("negative", 0.90);
+        weights.insert("negative".to_string(), 0.90);
 
         // Schema conventions are database-specific
-        weights.insert("convention", 0.60);
+        weights.insert("convention".to_string(), 0.60);
 
         // Learned patterns depend on quality of training data
-        weights.insert("learned", 0.55);
+        weights.insert("learned".to_string(), 0.55);
+
+        // Indexes are a hint, not a guarantee
+        weights.insert("index".to_string(), 0.60);
+
+        // Row counts only say which direction is plausible
+        weights.insert("row_ratio".to_string(), 0.50);
+
+        // Sampled value formats are strong when distinctive
+        weights.insert("sample".to_string(), 0.70);
 
         Self {
             weights,
@@ -74,8 +83,8 @@ impl SignalWeights {
     }
 
     /// Set the weight for a signal source kind.
-    pub fn set(&mut self, kind: &'static str, weight: f64) {
-        self.weights.insert(kind, weight.clamp(0.0, 1.0));
+    pub fn set(&mut self, kind: impl Into<String>, weight: f64) {
+        self.weights.insert(kind.into(), weight.clamp(0.0, 1.0));
     }
 
     /// Create weights optimized for high precision (fewer false positives).
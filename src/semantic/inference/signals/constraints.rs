@@ -31,6 +31,23 @@ pub struct StoredForeignKey {
     pub constraint_name: String,
 }
 
+/// Stored multi-column FK constraint from database.
+#[derive(Debug, Clone)]
+pub struct StoredCompositeKey {
+    /// Source schema.
+    pub from_schema: String,
+    /// Source table.
+    pub from_table: String,
+    /// Target schema.
+    pub to_schema: String,
+    /// Target table.
+    pub to_table: String,
+    /// `(from, to)` column pairs in key order.
+    pub columns: Vec<(String, String)>,
+    /// Constraint name.
+    pub constraint_name: String,
+}
+
 /// Collects and provides signals from database constraints.
 #[derive(Debug, Clone, Default)]
 pub struct ConstraintCollector {
@@ -42,6 +59,8 @@ pub struct ConstraintCollector {
     unique_columns: HashSet<ConstraintKey>,
     /// Known primary key columns: (schema, table, column).
     pk_columns: HashSet<ConstraintKey>,
+    /// Multi-column FK constraints, kept whole.
+    composite_keys: Vec<StoredCompositeKey>,
 }
 
 impl ConstraintCollector {
@@ -80,37 +99,7 @@ impl ConstraintCollector {
 
             // Load foreign keys
             for fk in &table.foreign_keys {
-                // Handle multi-column FKs by storing each column pair
-                for (i, from_col) in fk.columns.iter().enumerate() {
-                    if i < fk.referenced_columns.len() {
-                        let to_col = &fk.referenced_columns[i];
-
-                        let stored = StoredForeignKey {
-                            from_schema: schema.clone(),
-                            from_table: table_name.clone(),
-                            from_column: from_col.to_lowercase(),
-                            to_schema: fk.referenced_schema.to_lowercase(),
-                            to_table: fk.referenced_table.to_lowercase(),
-                            to_column: to_col.to_lowercase(),
-                            constraint_name: fk.name.clone(),
-                        };
-
-                        let key = (
-                            schema.clone(),
-                            table_name.clone(),
-                            from_col.to_lowercase(),
-                        );
-                        self.foreign_keys.insert(key, stored);
-
-                        // Also track target for reverse lookup
-                        let target_key = (
-                            fk.referenced_schema.to_lowercase(),
-                            fk.referenced_table.to_lowercase(),
-                            to_col.to_lowercase(),
-                        );
-                        self.fk_targets.insert(target_key);
-                    }
-                }
+                self.load_foreign_key(&table.schema, &table.name, fk);
             }
         }
     }
@@ -120,6 +109,24 @@ impl ConstraintCollector {
         let schema_lower = schema.to_lowercase();
         let table_lower = table.to_lowercase();
 
+        // Multi-column FKs are also kept whole so the full key can be joined on
+        if fk.columns.len() > 1 && fk.columns.len() == fk.referenced_columns.len() {
+            self.composite_keys.push(StoredCompositeKey {
+                from_schema: schema_lower.clone(),
+                from_table: table_lower.clone(),
+                to_schema: fk.referenced_schema.to_lowercase(),
+                to_table: fk.referenced_table.to_lowercase(),
+                columns: fk
+                    .columns
+                    .iter()
+                    .zip(&fk.referenced_columns)
+                    .map(|(from, to)| (from.to_lowercase(), to.to_lowercase()))
+                    .collect(),
+                constraint_name: fk.name.clone(),
+            });
+        }
+
+        // Handle multi-column FKs by storing each column pair
         for (i, from_col) in fk.columns.iter().enumerate() {
             if i < fk.referenced_columns.len() {
                 let to_col = &fk.referenced_columns[i];
@@ -240,6 +247,11 @@ impl ConstraintCollector {
         self.foreign_keys.values()
     }
 
+    /// Get all known multi-column foreign keys.
+    pub fn composite_foreign_keys(&self) -> impl Iterator<Item = &StoredCompositeKey> {
+        self.composite_keys.iter()
+    }
+
     /// Get count of loaded constraints.
     pub fn stats(&self) -> ConstraintStats {
         ConstraintStats {
@@ -255,6 +267,7 @@ impl ConstraintCollector {
         self.fk_targets.clear();
         self.unique_columns.clear();
         self.pk_columns.clear();
+        self.composite_keys.clear();
     }
 }
 
@@ -454,4 +467,41 @@ mod tests {
             .get_foreign_key("Public", "Orders", "Customer_Id")
             .is_some());
     }
+
+    #[test]
+    fn test_composite_foreign_key() {
+        let mut collector = ConstraintCollector::new();
+        collector.load_foreign_key(
+            "public",
+            "Shipments",
+            &ForeignKeyInfo {
+                name: "fk_shipments_line".to_string(),
+                columns: vec!["Order_Id".to_string(), "Line_No".to_string()],
+                referenced_schema: "public".to_string(),
+                referenced_table: "order_lines".to_string(),
+                referenced_columns: vec!["order_id".to_string(), "line_no".to_string()],
+                on_delete: None,
+                on_update: None,
+            },
+        );
+
+        let composites: Vec<_> = collector.composite_foreign_keys().collect();
+        assert_eq!(composites.len(), 1);
+        assert_eq!(composites[0].from_table, "shipments");
+        assert_eq!(
+            composites[0].columns,
+            vec![
+                ("order_id".to_string(), "order_id".to_string()),
+                ("line_no".to_string(), "line_no".to_string()),
+            ]
+        );
+
+        // Each column pair is still available individually
+        assert!(collector
+            .get_foreign_key("public", "shipments", "line_no")
+            .is_some());
+
+        collector.clear();
+        assert_eq!(collector.composite_foreign_keys().count(), 0);
+    }
 }
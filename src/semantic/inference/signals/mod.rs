@@ -14,6 +14,7 @@ pub mod inflection;
 pub mod naming;
 pub mod negative;
 pub mod pipeline;
+pub mod profile;
 pub mod statistics;
 pub mod types;
 
@@ -66,6 +67,15 @@ pub enum SignalSource {
         /// Source relationship that informed this.
         source: String,
     },
+
+    /// Source column leads an index.
+    IndexedColumn,
+
+    /// Row counts of the two tables, compared for many-to-one.
+    RowCountRatio,
+
+    /// Formats of sampled values, compared between the two columns.
+    SamplePattern,
 }
 
 impl SignalSource {
@@ -101,6 +111,9 @@ impl SignalSource {
             Self::NegativePattern { .. } => "negative",
             Self::SchemaConvention { .. } => "convention",
             Self::LearnedPattern { .. } => "learned",
+            Self::IndexedColumn => "index",
+            Self::RowCountRatio => "row_ratio",
+            Self::SamplePattern => "sample",
         }
     }
 }
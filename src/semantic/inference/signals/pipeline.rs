@@ -6,19 +6,21 @@
 //! - Type compatibility
 //! - Negative signals
 //! - Schema conventions
+//! - Table profiles (indexes, row counts, sampled values)
 //! - Statistics (async)
 
 use std::collections::{HashMap, HashSet};
 
-use crate::metadata::TableMetadata;
+use crate::metadata::{TableMetadata, TableProfile};
 use crate::model::DataType;
 
 use super::{
     aggregator::{SignalAggregator, SignalWeights},
-    constraints::ConstraintCollector,
+    constraints::{ConstraintCollector, StoredCompositeKey},
     conventions::{ConventionRegistry, ConventionScope, SchemaConventions},
     naming::NamingSignalCollector,
     negative::NegativeSignalDetector,
+    profile::ProfileCollector,
     types::TypeCompatibility,
     AggregatedScore, Signal, SignalCollection, SignalSource,
 };
@@ -184,6 +186,8 @@ pub struct SignalPipeline {
     convention_registry: ConventionRegistry,
     /// Database constraint collector.
     constraint_collector: ConstraintCollector,
+    /// Table profile collector.
+    profile_collector: ProfileCollector,
     /// Signal aggregator for scoring.
     aggregator: SignalAggregator,
     /// Pipeline configuration.
@@ -204,6 +208,7 @@ impl SignalPipeline {
             negative_detector: NegativeSignalDetector::new(),
             convention_registry: ConventionRegistry::new(ConventionScope::Schema),
             constraint_collector: ConstraintCollector::new(),
+            profile_collector: ProfileCollector::new(),
             aggregator: SignalAggregator::new(),
             config: PipelineConfig::default(),
         }
@@ -228,6 +233,7 @@ impl SignalPipeline {
             negative_detector,
             convention_registry: ConventionRegistry::new(config.convention_scope),
             constraint_collector: ConstraintCollector::new(),
+            profile_collector: ProfileCollector::new(),
             aggregator: SignalAggregator::new(),
             config,
         }
@@ -240,6 +246,7 @@ impl SignalPipeline {
             negative_detector: NegativeSignalDetector::new(),
             convention_registry: ConventionRegistry::new(ConventionScope::Schema),
             constraint_collector: ConstraintCollector::new(),
+            profile_collector: ProfileCollector::new(),
             aggregator: SignalAggregator::with_weights(weights),
             config: PipelineConfig::default(),
         }
@@ -264,6 +271,7 @@ impl SignalPipeline {
             negative_detector,
             convention_registry: ConventionRegistry::new(config.convention_scope),
             constraint_collector: ConstraintCollector::new(),
+            profile_collector: ProfileCollector::new(),
             aggregator: SignalAggregator::with_weights(weights),
             config,
         }
@@ -277,6 +285,13 @@ impl SignalPipeline {
         self.constraint_collector.load_from_metadata(tables);
     }
 
+    /// Load indexes, row counts and sampled values from table profiles.
+    ///
+    /// Tables without a profile simply get no profile signals.
+    pub fn load_profiles(&mut self, profiles: &[TableProfile]) {
+        self.profile_collector.load_profiles(profiles);
+    }
+
     /// Check if a candidate matches a known database constraint.
     pub fn has_db_constraint(&self, candidate: &RelationshipCandidate) -> bool {
         self.constraint_collector
@@ -316,6 +331,37 @@ impl SignalPipeline {
             .collect()
     }
 
+    /// Whether sampled values of a multi-column key appear in the referenced table.
+    ///
+    /// `None` when either table has no sampled values for the key.
+    pub fn composite_samples_overlap(
+        &self,
+        from_schema: &str,
+        from_table: &str,
+        to_schema: &str,
+        to_table: &str,
+        columns: &[(String, String)],
+    ) -> Option<bool> {
+        let from_columns: Vec<&str> = columns.iter().map(|(from, _)| from.as_str()).collect();
+        let to_columns: Vec<&str> = columns.iter().map(|(_, to)| to.as_str()).collect();
+        self.profile_collector.samples_overlap(
+            from_schema,
+            from_table,
+            &from_columns,
+            to_schema,
+            to_table,
+            &to_columns,
+        )
+    }
+
+    /// Get all multi-column FK constraints, kept whole.
+    pub fn get_composite_constraints(&self) -> Vec<StoredCompositeKey> {
+        self.constraint_collector
+            .composite_foreign_keys()
+            .cloned()
+            .collect()
+    }
+
     /// Analyze conventions from TableInfo structs.
     ///
     /// This should be called once before inference to detect naming patterns.
@@ -355,6 +401,7 @@ impl SignalPipeline {
     /// - Type compatibility
     /// - Negative patterns
     /// - Schema conventions
+    /// - Table profiles
     pub fn collect_sync_signals(&self, candidate: &RelationshipCandidate) -> SignalCollection {
         let mut signals = SignalCollection::new();
 
@@ -427,6 +474,16 @@ impl SignalPipeline {
             }
         }
 
+        // 6. Add index, row count and sample signals from table profiles
+        signals.extend(self.profile_collector.check_candidate(
+            &candidate.from_schema,
+            &candidate.from_table,
+            &candidate.from_column,
+            &candidate.to_schema,
+            &candidate.to_table,
+            &candidate.to_column,
+        ));
+
         signals
     }
 
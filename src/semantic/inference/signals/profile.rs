@@ -0,0 +1,459 @@
+//! Table profile signal generation.
+//!
+//! Provides signals from indexes, row counts and sampled values fetched
+//! from the database before inference:
+//! - FK candidates are usually indexed
+//! - The referencing table usually has at least as many rows as the referenced one
+//! - Sampled values of both columns usually share a format (e.g. `CUST-0042`)
+
+use std::collections::{HashMap, HashSet};
+
+use crate::metadata::{SampleRows, TableProfile};
+
+use super::{Signal, SignalSource};
+
+/// Key for looking up a column: (schema, table, column).
+type ColumnKey = (String, String, String);
+
+/// Key for looking up a table: (schema, table).
+type TableKey = (String, String);
+
+/// Minimum sampled values on each side before comparing their formats.
+const MIN_SAMPLE_VALUES: usize = 3;
+
+/// Share of source values whose format appears in the target to count as a match.
+const SAMPLE_MATCH_RATIO: f64 = 0.8;
+
+/// Share of source values at or below which the formats count as disjoint.
+const SAMPLE_MISMATCH_RATIO: f64 = 0.2;
+
+/// Row ratio (target over source) above which the direction looks reversed.
+const REVERSED_ROW_RATIO: f64 = 10.0;
+
+/// Shape of numeric values, which carry no format to compare.
+const NUMERIC_SHAPE: &str = "#";
+
+/// Collects and provides signals from table profiles.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileCollector {
+    /// Columns leading an index, with the index name.
+    indexed_columns: HashMap<ColumnKey, String>,
+    /// Row counts by table.
+    row_counts: HashMap<TableKey, i64>,
+    /// Shapes of sampled values by column.
+    sample_shapes: HashMap<ColumnKey, Vec<String>>,
+    /// Sampled rows by table.
+    samples: HashMap<TableKey, SampleRows>,
+}
+
+impl ProfileCollector {
+    /// Create an empty profile collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load indexes, row counts and sampled values from table profiles.
+    pub fn load_profiles(&mut self, profiles: &[TableProfile]) {
+        for profile in profiles {
+            let schema = profile.schema.to_lowercase();
+            let table = profile.table.to_lowercase();
+
+            for index in &profile.indexes {
+                if let Some(column) = index.columns.first() {
+                    self.indexed_columns
+                        .entry((schema.clone(), table.clone(), column.to_lowercase()))
+                        .or_insert_with(|| index.name.clone());
+                }
+            }
+
+            if let Some(row_count) = &profile.row_count {
+                self.row_counts
+                    .insert((schema.clone(), table.clone()), row_count.row_count);
+            }
+
+            if let Some(sample) = &profile.sample {
+                for column in &sample.columns {
+                    let shapes: Vec<String> = sample
+                        .column_values(column)
+                        .into_iter()
+                        .filter_map(value_shape)
+                        .collect();
+                    self.sample_shapes.insert(
+                        (schema.clone(), table.clone(), column.to_lowercase()),
+                        shapes,
+                    );
+                }
+                self.samples
+                    .insert((schema.clone(), table.clone()), sample.clone());
+            }
+        }
+    }
+
+    /// Collect profile signals for a candidate relationship.
+    pub fn check_candidate(
+        &self,
+        from_schema: &str,
+        from_table: &str,
+        from_column: &str,
+        to_schema: &str,
+        to_table: &str,
+        to_column: &str,
+    ) -> Vec<Signal> {
+        let from = column_key(from_schema, from_table, from_column);
+        let to = column_key(to_schema, to_table, to_column);
+
+        [
+            self.index_signal(&from),
+            self.row_count_signal(&from, &to),
+            self.sample_signal(&from, &to),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Whether sampled key tuples of one table appear in another.
+    ///
+    /// Returns `None` when either table has no sampled values for the
+    /// columns, so the caller can fall back to other evidence.
+    pub fn samples_overlap(
+        &self,
+        from_schema: &str,
+        from_table: &str,
+        from_columns: &[&str],
+        to_schema: &str,
+        to_table: &str,
+        to_columns: &[&str],
+    ) -> Option<bool> {
+        let from = self.sample_keys(from_schema, from_table, from_columns)?;
+        let to = self.sample_keys(to_schema, to_table, to_columns)?;
+        Some(from.iter().any(|key| to.contains(key)))
+    }
+
+    /// Sampled tuples of the given columns, skipping rows with a NULL in any of them.
+    fn sample_keys(
+        &self,
+        schema: &str,
+        table: &str,
+        columns: &[&str],
+    ) -> Option<HashSet<Vec<String>>> {
+        let sample = self
+            .samples
+            .get(&(schema.to_lowercase(), table.to_lowercase()))?;
+        let indexes: Option<Vec<usize>> = columns
+            .iter()
+            .map(|column| {
+                sample
+                    .columns
+                    .iter()
+                    .position(|c| c.eq_ignore_ascii_case(column))
+            })
+            .collect();
+        let indexes = indexes?;
+
+        let keys: HashSet<Vec<String>> = sample
+            .rows
+            .iter()
+            .filter_map(|row| {
+                indexes
+                    .iter()
+                    .map(|&i| row.get(i).filter(|v| !v.is_null()).map(|v| v.to_string()))
+                    .collect()
+            })
+            .collect();
+        (!keys.is_empty()).then_some(keys)
+    }
+
+    /// Positive signal when the source column leads an index.
+    fn index_signal(&self, from: &ColumnKey) -> Option<Signal> {
+        let index = self.indexed_columns.get(from)?;
+        Some(Signal::positive(
+            SignalSource::IndexedColumn,
+            0.85,
+            format!("'{}' is indexed by '{}'", from.2, index),
+        ))
+    }
+
+    /// Signal from the ratio of source to target row counts.
+    ///
+    /// Many-to-one relationships point from the larger table to the smaller
+    /// one, so a target with far more rows suggests the reverse direction.
+    fn row_count_signal(&self, from: &ColumnKey, to: &ColumnKey) -> Option<Signal> {
+        if from.0 == to.0 && from.1 == to.1 {
+            // Self-references have a ratio of one by definition
+            return None;
+        }
+
+        let from_rows = *self.row_counts.get(&(from.0.clone(), from.1.clone()))?;
+        let to_rows = *self.row_counts.get(&(to.0.clone(), to.1.clone()))?;
+        if from_rows <= 0 || to_rows <= 0 {
+            return None;
+        }
+
+        let ratio = from_rows as f64 / to_rows as f64;
+        if ratio >= 1.0 {
+            Some(Signal::positive(
+                SignalSource::RowCountRatio,
+                (0.8 + 0.075 * ratio.log10()).min(0.95),
+                format!(
+                    "'{}' has {} rows for {} in '{}', consistent with many-to-one",
+                    from.1, from_rows, to_rows, to.1
+                ),
+            ))
+        } else if 1.0 / ratio > REVERSED_ROW_RATIO {
+            Some(Signal::negative(
+                SignalSource::RowCountRatio,
+                0.3,
+                format!(
+                    "'{}' has {} rows but '{}' has {}, suggesting the reverse direction",
+                    from.1, from_rows, to.1, to_rows
+                ),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Signal from comparing the formats of sampled values.
+    fn sample_signal(&self, from: &ColumnKey, to: &ColumnKey) -> Option<Signal> {
+        let from_shapes = self.sample_shapes.get(from)?;
+        let to_shapes = self.sample_shapes.get(to)?;
+        if from_shapes.len() < MIN_SAMPLE_VALUES || to_shapes.len() < MIN_SAMPLE_VALUES {
+            return None;
+        }
+
+        // Numbers match numbers regardless of the relationship
+        let all_numeric = |shapes: &[String]| shapes.iter().all(|s| s == NUMERIC_SHAPE);
+        if all_numeric(from_shapes) && all_numeric(to_shapes) {
+            return None;
+        }
+
+        let target: HashSet<&str> = to_shapes.iter().map(String::as_str).collect();
+        let matching = from_shapes
+            .iter()
+            .filter(|s| target.contains(s.as_str()))
+            .count();
+        let ratio = matching as f64 / from_shapes.len() as f64;
+
+        if ratio >= SAMPLE_MATCH_RATIO {
+            Some(Signal::positive(
+                SignalSource::SamplePattern,
+                0.9,
+                format!(
+                    "Sampled values of '{}' match the format of '{}.{}' ('{}')",
+                    from.2, to.1, to.2, from_shapes[0]
+                ),
+            ))
+        } else if ratio <= SAMPLE_MISMATCH_RATIO {
+            Some(Signal::negative(
+                SignalSource::SamplePattern,
+                0.5,
+                format!(
+                    "Sampled values of '{}' ('{}') don't match the format of '{}.{}' ('{}')",
+                    from.2, from_shapes[0], to.1, to.2, to_shapes[0]
+                ),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn column_key(schema: &str, table: &str, column: &str) -> ColumnKey {
+    (
+        schema.to_lowercase(),
+        table.to_lowercase(),
+        column.to_lowercase(),
+    )
+}
+
+/// Format of a sampled value, used to compare columns.
+///
+/// Digits become `9`, letters `A`/`a` by case and other characters stay as
+/// they are, with repeats collapsed: `CUST-0042` and `CUST-7` both become
+/// `A-9`. Numbers are `#` and UUIDs `uuid`; other JSON values have no shape.
+pub fn value_shape(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Number(_) => Some(NUMERIC_SHAPE.to_string()),
+        serde_json::Value::String(s) if is_uuid(s) => Some("uuid".to_string()),
+        serde_json::Value::String(s) if s.parse::<f64>().is_ok() => Some(NUMERIC_SHAPE.to_string()),
+        serde_json::Value::String(s) => {
+            let mut shape = String::new();
+            for c in s.trim().chars() {
+                let class = if c.is_ascii_digit() {
+                    '9'
+                } else if c.is_uppercase() {
+                    'A'
+                } else if c.is_alphabetic() {
+                    'a'
+                } else {
+                    c
+                };
+                if !shape.ends_with(class) {
+                    shape.push(class);
+                }
+            }
+            Some(shape)
+        }
+        _ => None,
+    }
+}
+
+/// Check for the `8-4-4-4-12` hex digit layout of a UUID.
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{IndexInfo, RowCount, SampleRows};
+    use serde_json::json;
+
+    fn profile(
+        table: &str,
+        rows: i64,
+        column: &str,
+        values: Vec<serde_json::Value>,
+    ) -> TableProfile {
+        TableProfile {
+            schema: "public".to_string(),
+            table: table.to_string(),
+            indexes: vec![IndexInfo {
+                name: format!("ix_{}_{}", table, column),
+                columns: vec![column.to_string()],
+                is_unique: false,
+                is_primary_key: false,
+            }],
+            row_count: Some(RowCount {
+                row_count: rows,
+                is_exact: false,
+            }),
+            sample: Some(SampleRows {
+                columns: vec![column.to_string()],
+                rows: values.into_iter().map(|v| vec![v]).collect(),
+            }),
+        }
+    }
+
+    fn signal(signals: &[Signal], source: SignalSource) -> Option<&Signal> {
+        signals.iter().find(|s| s.source == source)
+    }
+
+    #[test]
+    fn test_value_shape() {
+        assert_eq!(value_shape(&json!("CUST-0042")).unwrap(), "A-9");
+        assert_eq!(value_shape(&json!("CUST-7")).unwrap(), "A-9");
+        assert_eq!(value_shape(&json!("Jane Doe")).unwrap(), "Aa Aa");
+        assert_eq!(value_shape(&json!(42)).unwrap(), "#");
+        assert_eq!(value_shape(&json!("42")).unwrap(), "#");
+        assert_eq!(
+            value_shape(&json!("550e8400-e29b-41d4-a716-446655440000")).unwrap(),
+            "uuid"
+        );
+        assert!(value_shape(&json!(true)).is_none());
+    }
+
+    #[test]
+    fn test_profile_signals_for_matching_columns() {
+        let mut collector = ProfileCollector::new();
+        collector.load_profiles(&[
+            profile(
+                "orders",
+                10_000,
+                "customer_code",
+                vec![json!("CUST-001"), json!("CUST-002"), json!("CUST-001")],
+            ),
+            profile(
+                "customers",
+                100,
+                "code",
+                vec![json!("CUST-001"), json!("CUST-002"), json!("CUST-003")],
+            ),
+        ]);
+
+        let signals = collector.check_candidate(
+            "public",
+            "orders",
+            "customer_code",
+            "public",
+            "customers",
+            "code",
+        );
+        assert!(signal(&signals, SignalSource::IndexedColumn).is_some());
+        let ratio = signal(&signals, SignalSource::RowCountRatio).unwrap();
+        assert!(ratio.is_positive());
+        assert!((ratio.score - 0.95).abs() < 1e-9);
+        assert!(signal(&signals, SignalSource::SamplePattern)
+            .unwrap()
+            .is_positive());
+    }
+
+    #[test]
+    fn test_profile_signals_for_mismatched_columns() {
+        let mut collector = ProfileCollector::new();
+        collector.load_profiles(&[
+            profile(
+                "customers",
+                100,
+                "order_ref",
+                vec![json!("Jane"), json!("John"), json!("Ann")],
+            ),
+            profile(
+                "orders",
+                10_000,
+                "ref",
+                vec![json!("ORD-1"), json!("ORD-2"), json!("ORD-3")],
+            ),
+        ]);
+
+        let signals = collector.check_candidate(
+            "PUBLIC",
+            "Customers",
+            "Order_Ref",
+            "public",
+            "orders",
+            "ref",
+        );
+        assert!(signal(&signals, SignalSource::RowCountRatio)
+            .unwrap()
+            .is_negative());
+        assert!(signal(&signals, SignalSource::SamplePattern)
+            .unwrap()
+            .is_negative());
+
+        // Nothing is known about unprofiled tables
+        assert!(collector
+            .check_candidate("public", "payments", "order_ref", "public", "orders", "ref")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_numeric_samples_are_not_compared() {
+        let mut collector = ProfileCollector::new();
+        collector.load_profiles(&[
+            profile(
+                "orders",
+                100,
+                "customer_id",
+                vec![json!(1), json!(2), json!(3)],
+            ),
+            profile("customers", 100, "id", vec![json!(1), json!(2), json!(3)]),
+        ]);
+
+        let signals = collector.check_candidate(
+            "public",
+            "orders",
+            "customer_id",
+            "public",
+            "customers",
+            "id",
+        );
+        assert!(signal(&signals, SignalSource::SamplePattern).is_none());
+    }
+}
@@ -16,12 +16,14 @@
 //! - Easier testing (test each phase independently)
 //! - Future extensions (HAVING, window functions, subqueries)
 
+pub mod build;
 pub mod column_lineage;
 pub mod error;
 pub mod executor;
 pub mod inference;
 pub mod model_graph;
 pub mod planner;
+pub mod refresh;
 pub mod semantic_model;
 
 // Re-export Cardinality from model (canonical source)
@@ -44,14 +46,23 @@ pub use column_lineage::{
 // Re-export model graph types (primary graph implementation)
 pub use model_graph::{
     EdgeData, EntityInfo, EntityNode, EntityType, JoinEdge, JoinPath, ModelGraph,
-    ModelResolvedField,
+    ModelResolvedField, Selector, SelectorMethod,
 };
 
+// Re-export build execution types
+pub use build::{
+    BuildExecutor, BuildNode, BuildPlan, BuildReport, BuildResult, BuildRunner, BuildStatus,
+    WorkerExecutor,
+};
+
+// Re-export refresh scheduling types
+pub use refresh::{RefreshScheduler, RefreshState, Staleness};
+
 // Re-export planner types
 pub use planner::{
     // Query types
-    DerivedBinaryOp, DerivedExpr, DerivedField, FieldFilter, FieldRef, FilterOp, FilterValue,
-    OrderField, SelectField, SemanticQuery, TimeFunction,
+    AsOf, DerivedBinaryOp, DerivedExpr, DerivedField, Drill, DrillDirection, FieldFilter, FieldRef,
+    FilterOp, FilterValue, OrderField, SelectField, SemanticQuery, TimeFunction,
     // Planner
     PlanPhases, QueryPlanner,
     // Phase types (for advanced usage)
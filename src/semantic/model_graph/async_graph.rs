@@ -31,7 +31,8 @@ use tokio::sync::RwLock;
 
 use crate::cache::{CacheKey, CacheResult, MetadataCache};
 use crate::metadata::{
-    ForeignKeyInfo, MetadataProvider, SchemaInfo, TableInfo, TableMetadata,
+    ForeignKeyInfo, IndexInfo, MetadataProvider, RowCount, SampleRows, SchemaInfo, TableInfo,
+    TableMetadata, TableProfile,
 };
 use crate::model::{Cardinality, Relationship, SourceEntity};
 use crate::semantic::inference::{InferenceConfig, InferenceEngine, TableInfo as InferenceTableInfo};
@@ -341,6 +342,16 @@ impl<P: MetadataProvider> AsyncModelGraph<P> {
             primary_key: source.primary_key.clone(),
         };
 
+        // Profiles of tables fetched earlier, for index/row count/sample signals
+        let profiles: Vec<TableProfile> = graph
+            .model()
+            .sources
+            .values()
+            .filter_map(|s| {
+                self.get_cached_table_profile(&s.schema.clone().unwrap_or_default(), &s.name)
+            })
+            .collect();
+
         // Collect all tables for inference context
         let all_tables: Vec<InferenceTableInfo> = graph
             .model()
@@ -366,7 +377,8 @@ impl<P: MetadataProvider> AsyncModelGraph<P> {
         drop(graph); // Release read lock
 
         // Run inference
-        let engine = InferenceEngine::with_config(self.inference_config.clone());
+        let mut engine = InferenceEngine::with_config(self.inference_config.clone());
+        engine.load_profiles(&profiles);
         let inferred = engine.infer_relationships(&table_info, &all_tables);
 
         let mut added = 0;
@@ -374,7 +386,7 @@ impl<P: MetadataProvider> AsyncModelGraph<P> {
         for inf in inferred {
             // Only add high-confidence relationships
             if inf.confidence >= self.inference_config.min_confidence {
-                let rel = Relationship::inferred(
+                let mut rel = Relationship::inferred(
                     entity_name,
                     &inf.to_table,
                     &inf.from_column,
@@ -383,6 +395,7 @@ impl<P: MetadataProvider> AsyncModelGraph<P> {
                     &inf.rule,
                     inf.confidence,
                 );
+                rel.additional_columns = inf.additional_columns.clone();
 
                 let mut graph = self.inner.write().await;
                 if graph.add_relationship(rel).unwrap_or(false) {
@@ -622,6 +635,124 @@ impl<P: MetadataProvider> AsyncModelGraph<P> {
 
         Ok(StatisticsSignals::from_stats(&from_stats, &to_stats, &overlap))
     }
+
+    // =========================================================================
+    // Profile Operations
+    // =========================================================================
+
+    /// Get or fetch the indexes on a table.
+    ///
+    /// Checks cache first, then fetches from provider if not found.
+    pub async fn get_or_fetch_indexes(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> AsyncGraphResult<Vec<IndexInfo>> {
+        let key = CacheKey::indexes(&self.conn_hash, schema, table);
+        if let Some(indexes) = self.cache.get(&key).ok().flatten() {
+            return Ok(indexes);
+        }
+
+        let indexes = self.provider.get_indexes(schema, table).await?;
+        let _ = self.cache.set(&key, &indexes);
+        Ok(indexes)
+    }
+
+    /// Get or fetch the estimated row count of a table.
+    ///
+    /// Checks cache first, then fetches from provider if not found.
+    /// Returns `None` when the provider can't count rows.
+    pub async fn get_or_fetch_row_count(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> AsyncGraphResult<Option<RowCount>> {
+        let key = CacheKey::row_count(&self.conn_hash, schema, table);
+        if let Some(row_count) = self.cache.get(&key).ok().flatten() {
+            return Ok(Some(row_count));
+        }
+
+        let row_count = self.provider.get_row_count(schema, table, false).await?;
+        if let Some(row_count) = &row_count {
+            let _ = self.cache.set(&key, row_count);
+        }
+        Ok(row_count)
+    }
+
+    /// Get or fetch rows sampled from a table.
+    ///
+    /// Checks cache first, then fetches from provider if not found.
+    /// Returns `None` when the provider can't sample rows.
+    pub async fn get_or_fetch_sample_rows(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> AsyncGraphResult<Option<SampleRows>> {
+        let key = CacheKey::sample_rows(&self.conn_hash, schema, table);
+        if let Some(sample) = self.cache.get(&key).ok().flatten() {
+            return Ok(Some(sample));
+        }
+
+        let sample = self.provider.sample_rows(schema, table, None).await?;
+        if let Some(sample) = &sample {
+            let _ = self.cache.set(&key, sample);
+        }
+        Ok(sample)
+    }
+
+    /// Get or fetch the indexes, row count and sampled rows of a table.
+    ///
+    /// Once fetched, the profile feeds relationship inference for the table.
+    pub async fn get_or_fetch_table_profile(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> AsyncGraphResult<TableProfile> {
+        let (indexes, row_count, sample) = tokio::try_join!(
+            self.get_or_fetch_indexes(schema, table),
+            self.get_or_fetch_row_count(schema, table),
+            self.get_or_fetch_sample_rows(schema, table)
+        )?;
+
+        Ok(TableProfile {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            indexes,
+            row_count,
+            sample,
+        })
+    }
+
+    /// Get whatever parts of a table's profile are cached, without fetching.
+    fn get_cached_table_profile(&self, schema: &str, table: &str) -> Option<TableProfile> {
+        let indexes: Option<Vec<IndexInfo>> = self
+            .cache
+            .get(&CacheKey::indexes(&self.conn_hash, schema, table))
+            .ok()
+            .flatten();
+        let row_count: Option<RowCount> = self
+            .cache
+            .get(&CacheKey::row_count(&self.conn_hash, schema, table))
+            .ok()
+            .flatten();
+        let sample: Option<SampleRows> = self
+            .cache
+            .get(&CacheKey::sample_rows(&self.conn_hash, schema, table))
+            .ok()
+            .flatten();
+
+        if indexes.is_none() && row_count.is_none() && sample.is_none() {
+            return None;
+        }
+
+        Some(TableProfile {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            indexes: indexes.unwrap_or_default(),
+            row_count,
+            sample,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -827,4 +958,27 @@ mod tests {
         assert!(async_graph.has_entity("orders").await);
         assert!(async_graph.has_entity("customers").await);
     }
+
+    #[tokio::test]
+    async fn test_table_profile_without_provider_support() {
+        let graph = ModelGraph::from_model(Model::new()).unwrap();
+        let cache = MetadataCache::open_in_memory().unwrap();
+
+        let async_graph = AsyncModelGraph::with_cache(
+            graph,
+            Arc::new(MockProvider::new()),
+            cache,
+            "test_conn".to_string(),
+            "main".to_string(),
+        );
+
+        // The mock relies on the default profile methods
+        let profile = async_graph
+            .get_or_fetch_table_profile("main", "orders")
+            .await
+            .unwrap();
+        assert!(profile.indexes.is_empty());
+        assert!(profile.row_count.is_none());
+        assert!(profile.sample.is_none());
+    }
 }
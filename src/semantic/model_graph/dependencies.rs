@@ -109,10 +109,10 @@ impl ModelGraph {
         Ok(affected)
     }
 
-    /// Build a dependency DAG for targets (facts and dimensions).
+    /// Build a dependency DAG for targets (facts, dimensions and tables).
     ///
     /// Returns a separate graph where:
-    /// - Nodes are target names (facts and dimensions)
+    /// - Nodes are target names (facts, dimensions and tables)
     /// - Edges represent "A depends on B" (A -> B means build B before A)
     ///
     /// This is used for determining build order.
@@ -129,23 +129,21 @@ impl ModelGraph {
             let idx = dag.add_node(name.clone());
             indices.insert(name.clone(), idx);
         }
+        for name in self.model.tables.keys() {
+            let idx = dag.add_node(name.clone());
+            indices.insert(name.clone(), idx);
+        }
 
-        // Add edges for fact dependencies
-        for (fact_name, fact) in &self.model.facts {
-            let fact_idx = indices[fact_name];
-
-            // Facts depend on dimensions/facts they include
-            for include in fact.includes.values() {
-                // Check if the included entity is a dimension
-                if let Some(&dim_idx) = indices.get(&include.entity) {
-                    dag.add_edge(fact_idx, dim_idx, ());
+        // Add an edge for every upstream entity that is itself a target
+        for name in indices.keys() {
+            let idx = indices[name];
+            for dep in self.target_inputs(name) {
+                // A dimension may share its name with the source it reads
+                if dep == name.as_str() {
+                    continue;
                 }
-            }
-
-            // Facts depend on facts/dimensions used in grain
-            for grain in &fact.grain {
-                if let Some(&dep_idx) = indices.get(&grain.source_entity) {
-                    dag.add_edge(fact_idx, dep_idx, ());
+                if let Some(&dep_idx) = indices.get(dep) {
+                    dag.add_edge(idx, dep_idx, ());
                 }
             }
         }
@@ -153,6 +151,47 @@ impl ModelGraph {
         dag
     }
 
+    /// Entities a target reads from, whether sources or other targets.
+    ///
+    /// - Facts: grain entities, included entities and `from`
+    /// - Dimensions: the source entity
+    /// - Tables: `from` entities, joined entities and tables read by a SQL body
+    pub(super) fn target_inputs(&self, target: &str) -> Vec<&str> {
+        let mut inputs = Vec::new();
+        if let Some(fact) = self.model.facts.get(target) {
+            inputs.extend(fact.grain.iter().map(|g| g.source_entity.as_str()));
+            inputs.extend(fact.includes.values().map(|i| i.entity.as_str()));
+            inputs.extend(fact.from.as_deref());
+        }
+        if let Some(dim) = self.model.dimensions.get(target) {
+            inputs.push(dim.source_entity.as_str());
+        }
+        if let Some(table) = self.model.tables.get(target) {
+            inputs.extend(table.from.sources());
+            inputs.extend(table.joins.iter().map(|j| j.entity.as_str()));
+            inputs.extend(table.sql_sources.iter().map(String::as_str));
+        }
+        inputs
+    }
+
+    /// Targets and sources a target reads from directly.
+    pub fn target_dependencies(&self, target: &str) -> GraphResult<Vec<&str>> {
+        if !self.is_build_target(target) {
+            return Err(SemanticError::UnknownEntity(target.into()));
+        }
+        let mut deps = self.target_inputs(target);
+        deps.sort_unstable();
+        deps.dedup();
+        Ok(deps)
+    }
+
+    /// Whether `name` is built by the transform layer (fact, dimension or table).
+    pub fn is_build_target(&self, name: &str) -> bool {
+        self.model.facts.contains_key(name)
+            || self.model.dimensions.contains_key(name)
+            || self.model.tables.contains_key(name)
+    }
+
     /// Get targets in topological order (dependencies first).
     ///
     /// Returns targets ordered so that dependencies come before dependents.
@@ -6,6 +6,7 @@
 //! The module is organized into submodules:
 //! - `path`: Path finding between entities (for JOIN generation)
 //! - `dependencies`: Dependency analysis (for build ordering)
+//! - `selection`: dbt-style selectors for choosing build targets
 //! - `resolution`: Entity and field resolution
 //! - `validation`: Model validation helpers
 //! - `async_graph`: Async wrapper for metadata introspection
@@ -14,8 +15,11 @@ pub mod async_graph;
 mod dependencies;
 mod path;
 mod resolution;
+mod selection;
 mod validation;
 
+pub use selection::{Selector, SelectorMethod};
+
 #[cfg(test)]
 mod tests;
 
@@ -23,7 +27,7 @@ use std::collections::HashMap;
 
 use petgraph::graph::{DiGraph, NodeIndex};
 
-use crate::model::{Cardinality, Model};
+use crate::model::{Cardinality, Expr, Model, RangeCondition};
 
 // Re-export error types from the unified error module
 pub use super::error::{SemanticError, SemanticResult};
@@ -117,8 +121,67 @@ pub struct EdgeData {
     pub from_column: String,
     /// Column in the target entity
     pub to_column: String,
+    /// Further `(from, to)` column pairs for composite keys
+    pub additional_columns: Vec<(String, String)>,
     /// Cardinality of the relationship
     pub cardinality: Cardinality,
+    /// Whether the target side may be missing (LEFT JOIN)
+    pub optional: bool,
+    /// Extra join predicates, columns qualified by entity
+    pub predicates: Vec<Expr>,
+    /// Band condition, oriented for this direction
+    pub range: Option<RangeCondition>,
+}
+
+impl EdgeData {
+    /// Edge following a relationship in its declared direction.
+    pub fn from_relationship(rel: &crate::model::Relationship) -> Self {
+        Self {
+            from_column: rel.from_column.clone(),
+            to_column: rel.to_column.clone(),
+            additional_columns: rel.additional_columns.clone(),
+            cardinality: rel.cardinality,
+            optional: rel.optional,
+            predicates: rel.predicates.clone(),
+            range: rel.range.clone(),
+        }
+    }
+
+    /// Edge joining on the given `(from, to)` column pairs.
+    ///
+    /// An empty list yields an edge with empty join columns.
+    pub fn from_pairs(pairs: Vec<(String, String)>, cardinality: Cardinality) -> Self {
+        let mut pairs = pairs.into_iter();
+        let (from_column, to_column) = pairs.next().unwrap_or_default();
+        Self {
+            from_column,
+            to_column,
+            additional_columns: pairs.collect(),
+            cardinality,
+            optional: false,
+            predicates: Vec::new(),
+            range: None,
+        }
+    }
+
+    /// The same edge walked in the opposite direction.
+    ///
+    /// Optionality only applies in the declared direction.
+    pub fn reversed(&self) -> Self {
+        Self {
+            from_column: self.to_column.clone(),
+            to_column: self.from_column.clone(),
+            additional_columns: self
+                .additional_columns
+                .iter()
+                .map(|(from, to)| (to.clone(), from.clone()))
+                .collect(),
+            cardinality: self.cardinality.reverse(),
+            optional: false,
+            predicates: self.predicates.clone(),
+            range: self.range.as_ref().map(RangeCondition::reversed),
+        }
+    }
 }
 
 /// An edge in a join path.
@@ -128,13 +191,53 @@ pub struct JoinEdge {
     pub to_entity: String,
     pub from_column: String,
     pub to_column: String,
+    /// Further `(from, to)` column pairs for composite keys
+    pub additional_columns: Vec<(String, String)>,
     pub cardinality: Cardinality,
+    /// Whether `to_entity` may be missing (LEFT JOIN)
+    pub optional: bool,
+    /// Extra join predicates, columns qualified by entity
+    pub predicates: Vec<Expr>,
+    /// Band condition, oriented for this direction
+    pub range: Option<RangeCondition>,
 }
 
 impl JoinEdge {
+    /// Edge between two entities over `data`.
+    pub fn new(from_entity: impl Into<String>, to_entity: impl Into<String>, data: &EdgeData) -> Self {
+        Self {
+            from_entity: from_entity.into(),
+            to_entity: to_entity.into(),
+            from_column: data.from_column.clone(),
+            to_column: data.to_column.clone(),
+            additional_columns: data.additional_columns.clone(),
+            cardinality: data.cardinality,
+            optional: data.optional,
+            predicates: data.predicates.clone(),
+            range: data.range.clone(),
+        }
+    }
+
+    /// All `(from, to)` join column pairs, primary pair first.
+    ///
+    /// Empty for edges joined only by a band condition.
+    pub fn column_pairs(&self) -> Vec<(&str, &str)> {
+        std::iter::once((self.from_column.as_str(), self.to_column.as_str()))
+            .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+            .chain(
+                self.additional_columns
+                    .iter()
+                    .map(|(from, to)| (from.as_str(), to.as_str())),
+            )
+            .collect()
+    }
+
     /// Does this edge cause row multiplication (fan-out)?
+    ///
+    /// Walking a band condition from the bounds side always fans out: one
+    /// band covers many values, whatever the declared cardinality.
     pub fn causes_fanout(&self) -> bool {
-        self.cardinality.causes_fanout()
+        self.cardinality.causes_fanout() || self.range.as_ref().is_some_and(|r| r.bounds_on_from)
     }
 }
 
@@ -364,26 +467,23 @@ impl ModelGraph {
             })?;
 
             // Forward edge
-            entity_graph.add_edge(
-                *from_idx,
-                *to_idx,
-                EdgeData {
-                    from_column: rel.from_column.clone(),
-                    to_column: rel.to_column.clone(),
-                    cardinality: rel.cardinality,
-                },
-            );
+            let mut forward = EdgeData::from_relationship(rel);
+            // A materialized fact storing a dimension's surrogate key looked
+            // it up with `DimensionBuild::fact_key`, which points unmatched
+            // keys at the unknown member, so an inner join keeps every row
+            let maps_unknown_member = model.dimensions.get(&rel.to_entity).is_some_and(|d| {
+                d.unknown_member.is_some()
+                    && d.surrogate_key.as_ref().is_some_and(|k| k.column == rel.to_column)
+            });
+            let materialized_fact = model.facts.get(&rel.from_entity).is_some_and(|f| f.materialized);
+            if maps_unknown_member && materialized_fact {
+                forward.optional = false;
+            }
+            let reverse = forward.reversed();
+            entity_graph.add_edge(*from_idx, *to_idx, forward);
 
             // Reverse edge (with reversed cardinality)
-            entity_graph.add_edge(
-                *to_idx,
-                *from_idx,
-                EdgeData {
-                    from_column: rel.to_column.clone(),
-                    to_column: rel.from_column.clone(),
-                    cardinality: rel.cardinality.reverse(),
-                },
-            );
+            entity_graph.add_edge(*to_idx, *from_idx, reverse);
         }
 
         // Add implicit edges from facts to their related entities.
@@ -431,22 +531,20 @@ impl ModelGraph {
                     entity_graph.add_edge(
                         *fact_idx,
                         *grain_idx,
-                        EdgeData {
-                            from_column: grain_col.clone(),
-                            to_column: grain_col.clone(),
-                            cardinality: Cardinality::OneToOne,
-                        },
+                        EdgeData::from_pairs(
+                            vec![(grain_col.clone(), grain_col.clone())],
+                            Cardinality::OneToOne,
+                        ),
                     );
 
                     // Reverse edge: grain source -> fact
                     entity_graph.add_edge(
                         *grain_idx,
                         *fact_idx,
-                        EdgeData {
-                            from_column: grain_col.clone(),
-                            to_column: grain_col,
-                            cardinality: Cardinality::OneToOne,
-                        },
+                        EdgeData::from_pairs(
+                            vec![(grain_col.clone(), grain_col)],
+                            Cardinality::OneToOne,
+                        ),
                     );
                 }
             }
@@ -465,27 +563,27 @@ impl ModelGraph {
 
                     // Find the relationship to this included entity
                     // First, check relationships from grain sources
-                    let mut join_columns = (String::new(), String::new());
+                    let mut join_key: Vec<(String, String)> = Vec::new();
                     'outer: for grain_entity in &grain_entities {
                         // Check if there's a relationship from grain to included entity
                         if let Some(rel) = model.relationships.iter().find(|r| {
                             r.from_entity == *grain_entity && &r.to_entity == include_entity
                         }) {
-                            join_columns = (rel.from_column.clone(), rel.to_column.clone());
+                            join_key = oriented_pairs(rel, false);
                             break 'outer;
                         }
                         // Also check reverse direction
                         if let Some(rel) = model.relationships.iter().find(|r| {
                             r.to_entity == *grain_entity && &r.from_entity == include_entity
                         }) {
-                            join_columns = (rel.to_column.clone(), rel.from_column.clone());
+                            join_key = oriented_pairs(rel, true);
                             break 'outer;
                         }
                     }
 
                     // If no direct relationship from grain, check relationships from other included entities
                     // This handles multi-hop relationships like: order_items -> orders -> dates
-                    if join_columns.0.is_empty() {
+                    if join_key.is_empty() {
                         'outer2: for other_include in &include_entities {
                             if other_include == include_entity {
                                 continue;
@@ -494,40 +592,26 @@ impl ModelGraph {
                             if let Some(rel) = model.relationships.iter().find(|r| {
                                 &r.from_entity == other_include && &r.to_entity == include_entity
                             }) {
-                                join_columns = (rel.from_column.clone(), rel.to_column.clone());
+                                join_key = oriented_pairs(rel, false);
                                 break 'outer2;
                             }
                             // Also check reverse direction
                             if let Some(rel) = model.relationships.iter().find(|r| {
                                 &r.to_entity == other_include && &r.from_entity == include_entity
                             }) {
-                                join_columns = (rel.to_column.clone(), rel.from_column.clone());
+                                join_key = oriented_pairs(rel, true);
                                 break 'outer2;
                             }
                         }
                     }
 
                     // Forward edge: fact -> included entity (many-to-one)
-                    entity_graph.add_edge(
-                        *fact_idx,
-                        *include_idx,
-                        EdgeData {
-                            from_column: join_columns.0.clone(),
-                            to_column: join_columns.1.clone(),
-                            cardinality: Cardinality::ManyToOne,
-                        },
-                    );
+                    let forward = EdgeData::from_pairs(join_key, Cardinality::ManyToOne);
+                    let reverse = forward.reversed();
+                    entity_graph.add_edge(*fact_idx, *include_idx, forward);
 
                     // Reverse edge: included entity -> fact (one-to-many)
-                    entity_graph.add_edge(
-                        *include_idx,
-                        *fact_idx,
-                        EdgeData {
-                            from_column: join_columns.1,
-                            to_column: join_columns.0,
-                            cardinality: Cardinality::OneToMany,
-                        },
-                    );
+                    entity_graph.add_edge(*include_idx, *fact_idx, reverse);
                 }
             }
 
@@ -543,43 +627,29 @@ impl ModelGraph {
                         }
 
                         // Find join columns from grain to the dimension's source entity
-                        let mut join_columns = (String::new(), String::new());
+                        let mut join_key: Vec<(String, String)> = Vec::new();
                         'dim_outer: for grain_entity in &grain_entities {
                             if let Some(rel) = model.relationships.iter().find(|r| {
                                 r.from_entity == *grain_entity && r.to_entity == dim.source_entity
                             }) {
-                                join_columns = (rel.from_column.clone(), rel.to_column.clone());
+                                join_key = oriented_pairs(rel, false);
                                 break 'dim_outer;
                             }
                             if let Some(rel) = model.relationships.iter().find(|r| {
                                 r.to_entity == *grain_entity && r.from_entity == dim.source_entity
                             }) {
-                                join_columns = (rel.to_column.clone(), rel.from_column.clone());
+                                join_key = oriented_pairs(rel, true);
                                 break 'dim_outer;
                             }
                         }
 
                         // Forward edge: fact -> dimension (many-to-one)
-                        entity_graph.add_edge(
-                            *fact_idx,
-                            *dim_idx,
-                            EdgeData {
-                                from_column: join_columns.0.clone(),
-                                to_column: join_columns.1.clone(),
-                                cardinality: Cardinality::ManyToOne,
-                            },
-                        );
+                        let forward = EdgeData::from_pairs(join_key, Cardinality::ManyToOne);
+                        let reverse = forward.reversed();
+                        entity_graph.add_edge(*fact_idx, *dim_idx, forward);
 
                         // Reverse edge: dimension -> fact (one-to-many)
-                        entity_graph.add_edge(
-                            *dim_idx,
-                            *fact_idx,
-                            EdgeData {
-                                from_column: join_columns.1,
-                                to_column: join_columns.0,
-                                cardinality: Cardinality::OneToMany,
-                            },
-                        );
+                        entity_graph.add_edge(*dim_idx, *fact_idx, reverse);
                     }
                 }
             }
@@ -757,8 +827,7 @@ impl ModelGraph {
         let exists = self.model.relationships.iter().any(|r| {
             r.from_entity == relationship.from_entity
                 && r.to_entity == relationship.to_entity
-                && r.from_column == relationship.from_column
-                && r.to_column == relationship.to_column
+                && r.column_pairs() == relationship.column_pairs()
         });
 
         if exists {
@@ -766,26 +835,12 @@ impl ModelGraph {
         }
 
         // Add forward edge
-        self.entity_graph.add_edge(
-            *from_idx,
-            *to_idx,
-            EdgeData {
-                from_column: relationship.from_column.clone(),
-                to_column: relationship.to_column.clone(),
-                cardinality: relationship.cardinality,
-            },
-        );
+        let forward = EdgeData::from_relationship(&relationship);
+        let reverse = forward.reversed();
+        self.entity_graph.add_edge(*from_idx, *to_idx, forward);
 
         // Add reverse edge
-        self.entity_graph.add_edge(
-            *to_idx,
-            *from_idx,
-            EdgeData {
-                from_column: relationship.to_column.clone(),
-                to_column: relationship.from_column.clone(),
-                cardinality: relationship.cardinality.reverse(),
-            },
-        );
+        self.entity_graph.add_edge(*to_idx, *from_idx, reverse);
 
         // Add to model
         self.model.relationships.push(relationship);
@@ -809,3 +864,17 @@ impl ModelGraph {
         &mut self.model
     }
 }
+
+/// Key column pairs of a relationship, optionally walked from its `to` side.
+fn oriented_pairs(rel: &crate::model::Relationship, reversed: bool) -> Vec<(String, String)> {
+    rel.column_pairs()
+        .into_iter()
+        .map(|(from, to)| {
+            if reversed {
+                (to.to_string(), from.to_string())
+            } else {
+                (from.to_string(), to.to_string())
+            }
+        })
+        .collect()
+}
@@ -106,13 +106,7 @@ impl ModelGraph {
             let from_node = &self.entity_graph[info.parent];
             let to_node = &self.entity_graph[current];
 
-            edges.push(JoinEdge {
-                from_entity: from_node.name.clone(),
-                to_entity: to_node.name.clone(),
-                from_column: edge_data.from_column.clone(),
-                to_column: edge_data.to_column.clone(),
-                cardinality: edge_data.cardinality,
-            });
+            edges.push(JoinEdge::new(&from_node.name, &to_node.name, edge_data));
 
             current = info.parent;
         }
@@ -203,13 +197,7 @@ impl ModelGraph {
                 let from_name = &self.entity_graph[current].name;
                 let to_name = &self.entity_graph[neighbor].name;
 
-                let join_edge = JoinEdge {
-                    from_entity: from_name.clone(),
-                    to_entity: to_name.clone(),
-                    from_column: edge_data.from_column.clone(),
-                    to_column: edge_data.to_column.clone(),
-                    cardinality: edge_data.cardinality,
-                };
+                let join_edge = JoinEdge::new(from_name, to_name, edge_data);
 
                 let mut new_path = path.clone();
                 new_path.push(join_edge);
@@ -45,6 +45,11 @@ impl ModelGraph {
         self.model.dimensions.get(name)
     }
 
+    /// Get the bridge declared on an entity, if it is one.
+    pub fn get_bridge(&self, entity: &str) -> Option<&crate::model::BridgeDefinition> {
+        self.model.bridges.get(entity)
+    }
+
     /// Get entity information for query planning.
     ///
     /// Returns the physical table name, schema, and entity type for any entity
@@ -0,0 +1,180 @@
+//! dbt-style target selection for builds.
+//!
+//! A selector names the targets to build:
+//!
+//! - `fact_orders` - just that target
+//! - `+fact_orders` - the target and everything upstream of it
+//! - `dim_customers+` - the target and everything downstream of it
+//! - `+fact_orders+` - both directions
+//! - `tag:mart` - tables carrying the tag (graph operators apply as above)
+//! - `orders+` - targets reading the `orders` source, directly or not
+//!
+//! Selected targets always come back in build order.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::Direction;
+
+use super::{GraphResult, ModelGraph, SemanticError};
+
+/// What a selector matches before graph operators are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorMethod {
+    /// A target or source by name
+    Name(String),
+    /// Tables tagged with this tag
+    Tag(String),
+}
+
+/// A parsed build selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub method: SelectorMethod,
+    /// Leading `+`: include upstream targets
+    pub parents: bool,
+    /// Trailing `+`: include downstream targets
+    pub children: bool,
+}
+
+impl Selector {
+    /// Parse a selector such as `+fact_orders`, `dim_customers+` or `tag:mart`.
+    pub fn parse(s: &str) -> GraphResult<Self> {
+        let invalid = || SemanticError::InvalidReference(format!("invalid selector '{}'", s));
+        let s = s.trim();
+        let (parents, rest) = match s.strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (children, body) = match rest.strip_suffix('+') {
+            Some(body) => (true, body),
+            None => (false, rest),
+        };
+        let method = match body.split_once(':') {
+            Some(("tag", tag)) if !tag.is_empty() => SelectorMethod::Tag(tag.to_string()),
+            Some(_) => return Err(invalid()),
+            None if !body.is_empty() && !body.contains('+') => {
+                SelectorMethod::Name(body.to_string())
+            }
+            None => return Err(invalid()),
+        };
+        Ok(Self {
+            method,
+            parents,
+            children,
+        })
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SemanticError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl ModelGraph {
+    /// Resolve selectors to the targets they name, in build order.
+    ///
+    /// The result is the union of all selectors. No selectors selects every
+    /// target.
+    pub fn select(&self, selectors: &[&str]) -> GraphResult<Vec<String>> {
+        let order = self.topological_order()?;
+        if selectors.is_empty() {
+            return Ok(order);
+        }
+
+        let dag = self.build_dependency_dag();
+        let mut selected: HashSet<NodeIndex> = HashSet::new();
+        for selector in selectors {
+            let selector = Selector::parse(selector)?;
+            let roots = self.selector_roots(&dag, &selector)?;
+            for &root in &roots {
+                // Edges point from a target to what it depends on
+                if selector.parents {
+                    selected.extend(reachable(&dag, root, Direction::Outgoing));
+                }
+                if selector.children {
+                    selected.extend(reachable(&dag, root, Direction::Incoming));
+                }
+            }
+            selected.extend(roots);
+        }
+
+        let names: HashSet<&str> = selected.iter().map(|&idx| dag[idx].as_str()).collect();
+        Ok(order
+            .into_iter()
+            .filter(|name| names.contains(name.as_str()))
+            .collect())
+    }
+
+    /// Targets `target` depends on, directly or transitively, in build order.
+    pub fn upstream_targets(&self, target: &str) -> GraphResult<Vec<String>> {
+        let dag = self.build_dependency_dag();
+        let start = dag
+            .node_indices()
+            .find(|&idx| dag[idx] == target)
+            .ok_or_else(|| SemanticError::UnknownEntity(target.into()))?;
+        let upstream: HashSet<&str> = reachable(&dag, start, Direction::Outgoing)
+            .into_iter()
+            .map(|idx| dag[idx].as_str())
+            .collect();
+        Ok(self
+            .topological_order()?
+            .into_iter()
+            .filter(|name| upstream.contains(name.as_str()))
+            .collect())
+    }
+
+    /// Targets a selector matches before its graph operators.
+    fn selector_roots(
+        &self,
+        dag: &DiGraph<String, ()>,
+        selector: &Selector,
+    ) -> GraphResult<Vec<NodeIndex>> {
+        let node = |name: &str| dag.node_indices().find(|&idx| dag[idx] == name);
+        match &selector.method {
+            SelectorMethod::Tag(tag) => Ok(self
+                .model
+                .tables_with_tag(tag)
+                .iter()
+                .filter_map(|table| node(&table.name))
+                .collect()),
+            SelectorMethod::Name(name) => {
+                if let Some(idx) = node(name) {
+                    return Ok(vec![idx]);
+                }
+                if !self.model.sources.contains_key(name) {
+                    return Err(SemanticError::UnknownEntity(name.clone()));
+                }
+                // Sources are not built; they only lead to what reads them
+                if !selector.children {
+                    return Err(SemanticError::InvalidReference(format!(
+                        "'{}' is a source; select '{}+' to build what reads it",
+                        name, name
+                    )));
+                }
+                Ok(dag
+                    .node_indices()
+                    .filter(|&idx| self.target_inputs(&dag[idx]).contains(&name.as_str()))
+                    .collect())
+            }
+        }
+    }
+}
+
+/// Nodes reachable from `start` in `direction`, excluding `start`.
+fn reachable(dag: &DiGraph<String, ()>, start: NodeIndex, direction: Direction) -> Vec<NodeIndex> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(idx) = stack.pop() {
+        for next in dag.neighbors_directed(idx, direction) {
+            if next != start && seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    seen.into_iter().collect()
+}
@@ -1,7 +1,9 @@
 //! Tests for ModelGraph.
 
 use super::*;
-use crate::model::{DataType, DimensionDefinition, FactDefinition, Relationship, SourceEntity};
+use crate::model::{
+    DataType, DimensionDefinition, FactDefinition, Relationship, SourceEntity, TableDefinition,
+};
 
 fn sample_model() -> Model {
     Model::new()
@@ -134,7 +136,11 @@ fn test_join_path_fanout() {
             to_entity: "customers".into(),
             from_column: "customer_id".into(),
             to_column: "customer_id".into(),
+            additional_columns: vec![],
             cardinality: Cardinality::ManyToOne,
+            optional: false,
+            predicates: vec![],
+            range: None,
         }],
     };
 
@@ -147,7 +153,11 @@ fn test_join_path_fanout() {
             to_entity: "orders".into(),
             from_column: "customer_id".into(),
             to_column: "customer_id".into(),
+            additional_columns: vec![],
             cardinality: Cardinality::OneToMany,
+            optional: false,
+            predicates: vec![],
+            range: None,
         }],
     };
 
@@ -164,14 +174,22 @@ fn test_join_path_entities() {
                 to_entity: "customers".into(),
                 from_column: "customer_id".into(),
                 to_column: "customer_id".into(),
+                additional_columns: vec![],
                 cardinality: Cardinality::ManyToOne,
+                optional: false,
+                predicates: vec![],
+                range: None,
             },
             JoinEdge {
                 from_entity: "customers".into(),
                 to_entity: "regions".into(),
                 from_column: "region_id".into(),
                 to_column: "region_id".into(),
+                additional_columns: vec![],
                 cardinality: Cardinality::ManyToOne,
+                optional: false,
+                predicates: vec![],
+                range: None,
             },
         ],
     };
@@ -1087,3 +1105,100 @@ fn test_get_entity_info_with_role_alias() {
     assert_eq!(info.physical_table, "date_dim");
     assert_eq!(info.physical_schema, Some("raw".to_string()));
 }
+
+// =============================================================================
+// Selection Tests
+// =============================================================================
+
+fn model_with_build_chain() -> Model {
+    Model::new()
+        .with_source(
+            SourceEntity::new("raw_customers", "raw.customers")
+                .with_required_column("id", DataType::Int64),
+        )
+        .with_source(
+            SourceEntity::new("raw_orders", "raw.orders")
+                .with_required_column("id", DataType::Int64),
+        )
+        .with_dimension(
+            DimensionDefinition::new("dim_customers", "analytics.dim_customers", "raw_customers")
+                .with_columns(vec!["id"]),
+        )
+        .with_fact(
+            FactDefinition::new("fact_orders", "analytics.fact_orders")
+                .with_grain("raw_orders", "id")
+                .include("dim_customers", vec!["id"]),
+        )
+        .with_table(TableDefinition::new("mart_revenue", "fact_orders").with_tag("mart"))
+        .with_table(TableDefinition::new("stg_orders", "raw_orders"))
+}
+
+#[test]
+fn test_selector_parse() {
+    let selector = Selector::parse("+fact_orders").unwrap();
+    assert_eq!(selector.method, SelectorMethod::Name("fact_orders".into()));
+    assert!(selector.parents && !selector.children);
+
+    let selector: Selector = "tag:mart+".parse().unwrap();
+    assert_eq!(selector.method, SelectorMethod::Tag("mart".into()));
+    assert!(!selector.parents && selector.children);
+
+    for invalid in ["", "+", "tag:", "path:models", "a++"] {
+        assert!(
+            Selector::parse(invalid).is_err(),
+            "{invalid:?} should not parse"
+        );
+    }
+}
+
+#[test]
+fn test_select_graph_operators() {
+    let graph = ModelGraph::from_model(model_with_build_chain()).unwrap();
+
+    assert_eq!(graph.select(&["fact_orders"]).unwrap(), vec!["fact_orders"]);
+    assert_eq!(
+        graph.select(&["+fact_orders"]).unwrap(),
+        vec!["dim_customers", "fact_orders"]
+    );
+    assert_eq!(
+        graph.select(&["dim_customers+"]).unwrap(),
+        vec!["dim_customers", "fact_orders", "mart_revenue"]
+    );
+    assert_eq!(graph.select(&[]).unwrap().len(), 4);
+}
+
+#[test]
+fn test_select_tags_and_sources() {
+    let graph = ModelGraph::from_model(model_with_build_chain()).unwrap();
+
+    assert_eq!(graph.select(&["tag:mart"]).unwrap(), vec!["mart_revenue"]);
+    assert_eq!(
+        graph.select(&["+tag:mart"]).unwrap(),
+        vec!["dim_customers", "fact_orders", "mart_revenue"]
+    );
+
+    let selected = graph.select(&["raw_orders+"]).unwrap();
+    assert_eq!(selected.len(), 3);
+    assert!(!selected.contains(&"dim_customers".to_string()));
+
+    assert!(matches!(
+        graph.select(&["raw_orders"]),
+        Err(SemanticError::InvalidReference(_))
+    ));
+    assert!(matches!(
+        graph.select(&["missing+"]),
+        Err(SemanticError::UnknownEntity(_))
+    ));
+}
+
+#[test]
+fn test_build_plan_dependencies() {
+    let graph = ModelGraph::from_model(model_with_build_chain()).unwrap();
+    let plan = graph.build_plan(&["fact_orders+"]).unwrap();
+
+    let targets: Vec<&str> = plan.targets().collect();
+    assert_eq!(targets, vec!["fact_orders", "mart_revenue"]);
+    // dim_customers was not selected, so it is not waited on
+    assert!(plan.nodes[0].depends_on.is_empty());
+    assert_eq!(plan.nodes[1].depends_on, vec!["fact_orders"]);
+}
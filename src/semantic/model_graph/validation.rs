@@ -28,23 +28,26 @@ impl ModelGraph {
 
         // Validate that relationship columns exist
         for rel in &self.model.relationships {
-            // Check from_column exists
-            if let Some(source) = self.model.sources.get(&rel.from_entity) {
-                if !source.columns.contains_key(&rel.from_column) {
-                    return Err(SemanticError::InvalidModel(format!(
-                        "Relationship column '{}' not found in entity '{}'",
-                        rel.from_column, rel.from_entity
-                    )));
-                }
+            let mut columns: Vec<(&str, &str)> = Vec::new();
+            for (from_column, to_column) in rel.column_pairs() {
+                columns.push((&rel.from_entity, from_column));
+                columns.push((&rel.to_entity, to_column));
+            }
+            if let Some(range) = &rel.range {
+                let (value_entity, bounds_entity) = range.sides(&rel.from_entity, &rel.to_entity);
+                columns.push((value_entity, &range.value_column));
+                columns.push((bounds_entity, &range.lower_column));
+                columns.push((bounds_entity, &range.upper_column));
             }
 
-            // Check to_column exists
-            if let Some(source) = self.model.sources.get(&rel.to_entity) {
-                if !source.columns.contains_key(&rel.to_column) {
-                    return Err(SemanticError::InvalidModel(format!(
-                        "Relationship column '{}' not found in entity '{}'",
-                        rel.to_column, rel.to_entity
-                    )));
+            for (entity, column) in columns {
+                if let Some(source) = self.model.sources.get(entity) {
+                    if !source.columns.contains_key(column) {
+                        return Err(SemanticError::InvalidModel(format!(
+                            "Relationship column '{}' not found in entity '{}'",
+                            column, entity
+                        )));
+                    }
                 }
             }
         }
@@ -2,21 +2,51 @@
 //!
 //! This phase converts a logical plan into a SQL Query object.
 //! It handles the translation from logical operations to physical SQL constructs.
+//!
+//! # Bridges
+//!
+//! When the join tree crosses a bridge, measures are protected from the
+//! bridge's fan-out in one of two ways. Weighted allocation multiplies
+//! additive measures by the bridge weight:
+//!
+//! ```sql
+//! SELECT customers.name, SUM(balances.amount * customer_accounts.allocation)
+//! FROM ... JOIN customer_accounts ... JOIN customers ...
+//! ```
+//!
+//! Distinct-key pre-aggregation first collects the distinct groups reached
+//! by each root row, then joins the root back so each row counts once per group:
+//!
+//! ```sql
+//! WITH bridged AS (
+//!     SELECT DISTINCT customers.name AS customers_name, balances.account_id AS _key_account_id
+//!     FROM ... JOIN customer_accounts ... JOIN customers ... WHERE ...
+//! )
+//! SELECT bridged.customers_name AS name, SUM(balances.amount)
+//! FROM bridged INNER JOIN balances ON balances.account_id = bridged._key_account_id
+//! GROUP BY bridged.customers_name
+//! ```
 
-use crate::expr::{avg, col, count_distinct, count_star, func, max, min, sum, table_col, Expr, ExprExt};
-use crate::model::AggregationType;
-use crate::query::{OrderByExpr, Query, SelectExpr, TableRef};
+use crate::expr::{
+    avg, col, count_distinct, count_star, func, lit_int, max, min, sum, table_col, Expr, ExprExt,
+};
+use crate::model::{AggregationType, RangeCondition};
+use crate::query::{Cte, OrderByExpr, Query, SelectExpr, TableRef};
 use crate::semantic::error::PlanResult;
 
 use super::emit_time::TimeEmitter;
 use super::logical::{LogicalJoinType, LogicalPlan};
 use super::prune::PrunedColumns;
 use super::resolved::{
-    ResolvedColumn, ResolvedDerivedExpr, ResolvedEntity, ResolvedFilter, ResolvedMeasure,
-    ResolvedOrder, ResolvedOrderExpr, ResolvedSelect,
+    AsOfPoint, BridgeStrategy, ResolvedAsOf, ResolvedBridge, ResolvedColumn, ResolvedDerivedExpr,
+    ResolvedEntity, ResolvedFilter, ResolvedMeasure, ResolvedOrder, ResolvedOrderExpr,
+    ResolvedSelect,
 };
 use super::types::{DerivedBinaryOp, FilterOp, FilterValue};
 
+/// Name of the CTE holding the distinct groups reached through a bridge.
+const BRIDGED_CTE: &str = "bridged";
+
 /// Emitter - handles Phase 4 of query planning.
 ///
 /// Optionally uses pruned column information to optimize generated SQL.
@@ -27,6 +57,8 @@ pub struct Emitter {
     /// Currently used for debugging/inspection; future: optimize SELECT.
     #[allow(dead_code)]
     pruned_columns: Option<PrunedColumns>,
+    /// Bridge crossed by the join tree, if any.
+    bridge: Option<ResolvedBridge>,
 }
 
 impl Emitter {
@@ -34,6 +66,7 @@ impl Emitter {
         Self {
             default_schema: "dbo".to_string(),
             pruned_columns: None,
+            bridge: None,
         }
     }
 
@@ -53,39 +86,26 @@ impl Emitter {
         self
     }
 
+    /// Apply the strategy of a bridge crossed by the join tree.
+    pub fn with_bridge(mut self, bridge: ResolvedBridge) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
     /// Emit a SQL Query from a logical plan.
     pub fn emit(&self, plan: &LogicalPlan) -> PlanResult<Query> {
         // We need to traverse the plan and collect information
         let mut ctx = EmitContext::new();
         self.collect_plan_info(plan, &mut ctx);
 
-        // Build the query
-        let mut query = Query::new();
-
-        // FROM clause
-        if let Some(from) = &ctx.from {
-            query = query.from(self.emit_table_ref(from));
-        }
-
-        // JOINs
-        for join in &ctx.joins {
-            let table_ref = self.emit_table_ref(&join.entity);
-            let on_expr = table_col(&join.left_entity, &join.left_column)
-                .eq(table_col(&join.right_entity, &join.right_column));
-
-            query = match join.join_type {
-                LogicalJoinType::Inner => query.inner_join(table_ref, on_expr),
-                LogicalJoinType::Left => query.left_join(table_ref, on_expr),
-                LogicalJoinType::Right => query.right_join(table_ref, on_expr),
-                LogicalJoinType::Full => query.full_join(table_ref, on_expr),
-            };
-        }
-
-        // WHERE clause
-        for filter in &ctx.filters {
-            let expr = self.emit_filter(filter);
-            query = query.filter(expr);
-        }
+        // FROM, JOINs and WHERE
+        let mut query = match &self.bridge {
+            Some(ResolvedBridge {
+                strategy: BridgeStrategy::DistinctKey { keys },
+                ..
+            }) => self.emit_bridged_source(&mut ctx, keys),
+            _ => self.emit_source(&ctx),
+        };
 
         // SELECT clause (includes group by columns)
         let mut select_exprs = Vec::new();
@@ -130,6 +150,162 @@ impl Emitter {
         Ok(query)
     }
 
+    /// Emit the FROM, JOIN and WHERE clauses.
+    fn emit_source(&self, ctx: &EmitContext) -> Query {
+        let mut query = Query::new();
+
+        // FROM clause
+        if let Some(from) = &ctx.from {
+            query = query.from(self.emit_table_ref(from));
+        }
+
+        // JOINs
+        for join in &ctx.joins {
+            let table_ref = self.emit_table_ref(&join.entity);
+            let key_columns = std::iter::once((&join.left_column, &join.right_column))
+                .filter(|(left, right)| !left.is_empty() && !right.is_empty())
+                .chain(
+                    join.additional_columns
+                        .iter()
+                        .map(|(left, right)| (left, right)),
+                );
+            let mut conditions: Vec<Expr> = key_columns
+                .map(|(left_column, right_column)| {
+                    table_col(&join.left_entity, left_column)
+                        .eq(table_col(&join.right_entity, right_column))
+                })
+                .collect();
+            if let Some(range) = &join.range {
+                conditions.push(emit_range(range, &join.left_entity, &join.right_entity));
+            }
+            conditions.extend(join.predicates.iter().map(convert_model_expr));
+            if let Some(as_of) = &join.as_of {
+                conditions.push(self.emit_as_of(as_of));
+            }
+            let on_expr = conditions
+                .into_iter()
+                .reduce(|a, b| a.and(b))
+                .unwrap_or_else(|| lit_int(1).eq(lit_int(1)));
+
+            // A surrogate date key finds its date through the date dimension,
+            // joined under its own alias ahead of the versioned dimension
+            if let Some(as_of) = &join.as_of {
+                if let AsOfPoint::Lookup { dimension, key_column, key, .. } = &as_of.point {
+                    let alias = as_of_alias(as_of);
+                    let lookup = self.emit_table_ref(dimension).with_alias(&alias);
+                    query = query.left_join(lookup, table_col(&alias, key_column).eq(self.emit_column(key)));
+                }
+            }
+
+            query = match join.join_type {
+                LogicalJoinType::Inner => query.inner_join(table_ref, on_expr),
+                LogicalJoinType::Left => query.left_join(table_ref, on_expr),
+                LogicalJoinType::Right => query.right_join(table_ref, on_expr),
+                LogicalJoinType::Full => query.full_join(table_ref, on_expr),
+            };
+        }
+
+        // WHERE clause
+        for filter in &ctx.filters {
+            let expr = self.emit_filter(filter);
+            query = query.filter(expr);
+        }
+
+        query
+    }
+
+    /// Emit the source of a query crossing a bridge by distinct keys.
+    ///
+    /// The joins and filters move into a `SELECT DISTINCT` CTE over the
+    /// non-root columns and the root key; the root is then joined back on
+    /// its key. Non-root columns in `ctx` are rewritten to read from the CTE.
+    fn emit_bridged_source(&self, ctx: &mut EmitContext, keys: &[String]) -> Query {
+        let Some(root) = ctx.from.clone() else {
+            return self.emit_source(ctx);
+        };
+
+        let mut columns: Vec<ResolvedColumn> = Vec::new();
+        {
+            let order_columns = ctx.order_by.iter().filter_map(|o| match &o.expr {
+                ResolvedOrderExpr::Column(column) => Some(column),
+                ResolvedOrderExpr::Measure(_) => None,
+            });
+            let projected_columns = ctx.projections.iter().filter_map(|p| match p {
+                ResolvedSelect::Column { column, .. } => Some(column),
+                _ => None,
+            });
+            for column in ctx
+                .group_by
+                .iter()
+                .chain(projected_columns)
+                .chain(order_columns)
+            {
+                let seen = columns.iter().any(|c| {
+                    c.entity_alias == column.entity_alias && c.physical_name == column.physical_name
+                });
+                if column.entity_alias != root.name && !seen {
+                    columns.push(column.clone());
+                }
+            }
+        }
+
+        let mut select_exprs: Vec<SelectExpr> = columns
+            .iter()
+            .map(|c| SelectExpr::new(self.emit_column(c)).with_alias(&bridged_alias(c)))
+            .collect();
+        for key in keys {
+            select_exprs.push(
+                SelectExpr::new(table_col(&root.name, key)).with_alias(&format!("_key_{}", key)),
+            );
+        }
+        let bridged = self.emit_source(ctx).select(select_exprs).distinct();
+
+        let on_expr = keys
+            .iter()
+            .map(|key| {
+                table_col(&root.name, key).eq(table_col(BRIDGED_CTE, &format!("_key_{}", key)))
+            })
+            .reduce(|a, b| a.and(b))
+            .expect("distinct-key bridge strategy has at least one key");
+
+        // Everything outside the root now reads from the CTE
+        let remap = |column: &mut ResolvedColumn| {
+            if column.entity_alias != root.name {
+                column.physical_name = bridged_alias(column);
+                column.entity_alias = BRIDGED_CTE.to_string();
+            }
+        };
+        ctx.group_by.iter_mut().for_each(remap);
+        for projection in &mut ctx.projections {
+            if let ResolvedSelect::Column { column, .. } = projection {
+                remap(column);
+            }
+        }
+        for order in &mut ctx.order_by {
+            if let ResolvedOrderExpr::Column(column) = &mut order.expr {
+                remap(column);
+            }
+        }
+        ctx.filters.clear();
+
+        Query::new()
+            .with_cte(Cte::new(BRIDGED_CTE, bridged))
+            .from(TableRef::new(BRIDGED_CTE))
+            .inner_join(self.emit_table_ref(&root), on_expr)
+    }
+
+    /// The bridge weight column, when measures are allocated by weight.
+    fn allocation_weight(&self) -> Option<Expr> {
+        match &self.bridge {
+            Some(ResolvedBridge {
+                entity,
+                strategy: BridgeStrategy::WeightedAllocation { weight_column },
+                ..
+            }) => Some(table_col(entity, weight_column)),
+            _ => None,
+        }
+    }
+
     /// Collect information from the logical plan tree.
     #[allow(clippy::only_used_in_recursion)]
     fn collect_plan_info(&self, plan: &LogicalPlan, ctx: &mut EmitContext) {
@@ -152,6 +328,10 @@ impl Emitter {
                         left_column: join.on.left_column.clone(),
                         right_entity: join.on.right_entity.clone(),
                         right_column: join.on.right_column.clone(),
+                        additional_columns: join.on.additional_columns.clone(),
+                        as_of: join.on.as_of.clone(),
+                        range: join.on.range.clone(),
+                        predicates: join.on.predicates.clone(),
                         join_type: join.join_type,
                     });
                 }
@@ -215,6 +395,18 @@ impl Emitter {
             .with_alias(&entity.name)
     }
 
+    /// Emit the version predicate of a join to an SCD Type 2 dimension.
+    fn emit_as_of(&self, as_of: &ResolvedAsOf) -> Expr {
+        let point = match &as_of.point {
+            AsOfPoint::Current => None,
+            AsOfPoint::Column(column) => Some(self.emit_column(column)),
+            AsOfPoint::Date(date) => Some(crate::expr::lit_str(date)),
+            // Joined in emit_source
+            AsOfPoint::Lookup { date_column, .. } => Some(table_col(&as_of_alias(as_of), date_column)),
+        };
+        emit_version_predicate(as_of, point)
+    }
+
     /// Emit a column expression.
     fn emit_column(&self, column: &ResolvedColumn) -> Expr {
         table_col(&column.entity_alias, &column.physical_name)
@@ -285,12 +477,28 @@ impl Emitter {
             match source_col {
                 Some(col) => col,
                 None => {
-                    // COUNT(*) - return early with count_star()
-                    return count_star();
+                    // COUNT(*) - return early with count_star(), or the total weight
+                    return match self.allocation_weight() {
+                        Some(weight) => sum(weight),
+                        None => count_star(),
+                    };
                 }
             }
         };
 
+        // Allocate additive measures across a weighted bridge
+        if let Some(weight) = self.allocation_weight() {
+            match measure.aggregation {
+                AggregationType::Sum => return sum(aggregation_expr.mul(weight)),
+                AggregationType::Count if has_query_filter || has_def_filter => {
+                    return sum(aggregation_expr.mul(weight));
+                }
+                AggregationType::Count => return weighted_count(aggregation_expr, weight),
+                AggregationType::Avg => return weighted_avg(aggregation_expr, weight),
+                _ => {}
+            }
+        }
+
         // Apply aggregation function
         match measure.aggregation {
             AggregationType::Sum => sum(aggregation_expr),
@@ -409,6 +617,16 @@ impl Emitter {
     fn emit_aggregate(&self, column: &ResolvedColumn, aggregation: &str) -> Expr {
         let source_col = table_col(&column.entity_alias, &column.physical_name);
 
+        // Allocate additive aggregates across a weighted bridge
+        if let Some(weight) = self.allocation_weight() {
+            match aggregation.to_uppercase().as_str() {
+                "SUM" => return sum(source_col.mul(weight)),
+                "COUNT" => return weighted_count(source_col, weight),
+                "AVG" => return weighted_avg(source_col, weight),
+                _ => {}
+            }
+        }
+
         match aggregation.to_uppercase().as_str() {
             "SUM" => sum(source_col),
             "COUNT" => func("COUNT", vec![source_col]),
@@ -515,9 +733,74 @@ struct JoinInfo {
     left_column: String,
     right_entity: String,
     right_column: String,
+    additional_columns: Vec<(String, String)>,
+    as_of: Option<Box<ResolvedAsOf>>,
+    range: Option<Box<RangeCondition>>,
+    predicates: Vec<crate::model::Expr>,
     join_type: LogicalJoinType,
 }
 
+/// A band condition between `from` and `to` as a SQL predicate.
+pub(crate) fn emit_range(range: &RangeCondition, from: &str, to: &str) -> Expr {
+    let (value_entity, bounds_entity) = range.sides(from, to);
+    let value = table_col(value_entity, &range.value_column);
+    let lower = table_col(bounds_entity, &range.lower_column);
+    let upper = table_col(bounds_entity, &range.upper_column);
+    if range.exclusive_upper {
+        value.clone().gte(lower).and(value.lt(upper))
+    } else {
+        value.between(lower, upper)
+    }
+}
+
+/// Version predicate of a join to an SCD Type 2 dimension at `point`, or of
+/// its current version when `point` is None.
+///
+/// A version is in effect from `effective_from` (inclusive) until
+/// `effective_to` (exclusive); the current version has no `effective_to`.
+pub(crate) fn emit_version_predicate(as_of: &ResolvedAsOf, point: Option<Expr>) -> Expr {
+    let effective_from = table_col(&as_of.dimension, &as_of.effective_from);
+    let effective_to = table_col(&as_of.dimension, &as_of.effective_to);
+
+    let Some(point) = point else {
+        return effective_to.is_null();
+    };
+
+    let open_ended = point
+        .clone()
+        .lt(effective_to.clone())
+        .or(effective_to.is_null());
+    point
+        .gte(effective_from)
+        .and(Expr::Paren(Box::new(open_ended)))
+}
+
+/// Alias of the date dimension an SCD Type 2 join looks its date up in.
+fn as_of_alias(as_of: &ResolvedAsOf) -> String {
+    format!("{}_as_of", as_of.dimension)
+}
+
+/// Alias of a non-root column in the bridged CTE.
+fn bridged_alias(column: &ResolvedColumn) -> String {
+    format!("{}_{}", column.entity_alias, column.physical_name)
+}
+
+/// `SUM(CASE WHEN expr IS NOT NULL THEN weight END)` - a weighted `COUNT(expr)`.
+fn weighted_count(expr: Expr, weight: Expr) -> Expr {
+    sum(Expr::Case {
+        operand: None,
+        when_clauses: vec![(expr.is_not_null(), weight)],
+        else_clause: None,
+    })
+}
+
+/// `SUM(expr * weight) / NULLIF(<weighted count>, 0)` - a weighted `AVG(expr)`.
+fn weighted_avg(expr: Expr, weight: Expr) -> Expr {
+    let total = sum(expr.clone().mul(weight.clone()));
+    let count = weighted_count(expr, weight);
+    total.div(func("NULLIF", vec![count, crate::expr::lit_int(0)]))
+}
+
 // =============================================================================
 // Expression Conversion
 // =============================================================================
@@ -526,8 +809,7 @@ struct JoinInfo {
 ///
 /// This converts the model's dialect-agnostic expression AST to the SQL
 /// emitter's expression AST.
-#[allow(dead_code)]
-fn convert_model_expr(expr: &crate::model::expr::Expr) -> Expr {
+pub(crate) fn convert_model_expr(expr: &crate::model::expr::Expr) -> Expr {
     convert_model_expr_with_context(expr, None)
 }
 
@@ -593,9 +875,8 @@ fn convert_model_expr_with_context(expr: &crate::model::expr::Expr, default_enti
             }
         }
         ModelExpr::Function { func: fn_type, args } => {
-            let func_name = convert_func(fn_type);
             let arg_exprs: Vec<Expr> = args.iter().map(|e| convert_model_expr_with_context(e, default_entity)).collect();
-            func(&func_name, arg_exprs)
+            convert_function(fn_type, arg_exprs)
         }
         ModelExpr::Cast { expr, target_type } => {
             let inner = convert_model_expr_with_context(expr, default_entity);
@@ -611,7 +892,7 @@ fn convert_model_expr_with_context(expr: &crate::model::expr::Expr, default_enti
     }
 }
 
-fn convert_literal(lit: &crate::model::expr::Literal) -> Expr {
+pub(crate) fn convert_literal(lit: &crate::model::expr::Literal) -> Expr {
     use crate::model::expr::Literal as ModelLit;
 
     match lit {
@@ -659,6 +940,49 @@ fn convert_binary_op(op: &crate::model::expr::BinaryOp) -> crate::sql::expr::Bin
 }
 
 
+/// Lower a model function call to SQL.
+///
+/// Date functions are lowered to the canonical call shapes in
+/// [`crate::sql::dialect::functions`], which rewrite them per dialect at render time.
+fn convert_function(fn_type: &crate::model::expr::Func, mut args: Vec<Expr>) -> Expr {
+    use crate::model::expr::Func;
+    use crate::sql::expr::Literal as SqlLit;
+
+    let part = match fn_type {
+        Func::Year => Some("year"),
+        Func::Quarter => Some("quarter"),
+        Func::Month => Some("month"),
+        Func::Day => Some("day"),
+        Func::Hour => Some("hour"),
+        Func::Minute => Some("minute"),
+        Func::Second => Some("second"),
+        _ => None,
+    };
+    if let (Some(unit), 1) = (part, args.len()) {
+        args.insert(0, crate::expr::lit_str(unit));
+        return func("EXTRACT", args);
+    }
+
+    match (fn_type, args.len()) {
+        // Model order matches the canonical (unit, amount, date); subtraction negates the amount
+        (Func::DateSub, 3) => {
+            args[1] = match std::mem::replace(&mut args[1], Expr::Literal(SqlLit::Null)) {
+                Expr::Literal(SqlLit::Int(n)) => crate::expr::lit_int(-n),
+                amount => Expr::UnaryOp {
+                    op: crate::sql::expr::UnaryOperator::Minus,
+                    expr: Box::new(Expr::Paren(Box::new(amount))),
+                },
+            };
+            func("DATEADD", args)
+        }
+        // strftime patterns are recognised by their % codes
+        (Func::ToChar, 2) if matches!(&args[1], Expr::Literal(SqlLit::String(fmt)) if fmt.contains('%')) => {
+            func("STRFTIME", args)
+        }
+        _ => func(&convert_func(fn_type), args),
+    }
+}
+
 fn convert_func(func: &crate::model::expr::Func) -> String {
     use crate::model::expr::Func;
 
@@ -28,18 +28,30 @@
 //! ```
 
 use crate::expr::{
-    coalesce, count_distinct, count_star, func, lit_int, max, min, sum, table_col, Expr, ExprExt,
+    coalesce, count_distinct, count_star, func, lit_int, lit_str, max, min, sum, table_col, Expr,
+    ExprExt,
 };
 use crate::model::AggregationType;
 use crate::query::{Cte, OrderByExpr, Query, SelectExpr, TableRef};
 
+use super::emit::{convert_model_expr, emit_range, emit_version_predicate};
 use super::resolved::{
-    FactAggregate, MultiFactQuery, ResolvedMeasure, ResolvedOrderExpr, SharedDimension,
+    AsOfPoint, FactAggregate, FactJoinKey, MultiFactQuery, ResolvedEntity, ResolvedMeasure,
+    ResolvedOrderExpr, SharedDimension,
 };
 #[cfg(test)]
 use super::resolved::ResolvedColumn;
 use super::types::{FilterOp, FilterValue};
 
+/// Table reference for an entity, aliased by its logical name.
+fn entity_table_ref(entity: &ResolvedEntity) -> TableRef {
+    if let Some(ref schema) = entity.physical_schema {
+        TableRef::new(&format!("{}.{}", schema, entity.physical_table)).with_alias(&entity.name)
+    } else {
+        TableRef::new(&entity.physical_table).with_alias(&entity.name)
+    }
+}
+
 /// Emitter for multi-fact queries.
 pub struct MultiFactEmitter<'a> {
     query: &'a MultiFactQuery,
@@ -104,14 +116,7 @@ impl<'a> MultiFactEmitter<'a> {
 
     /// Generate a CTE for a single fact.
     fn emit_fact_cte(&self, fact_agg: &FactAggregate) -> Cte {
-        let table_ref = if let Some(ref schema) = fact_agg.fact.physical_schema {
-            TableRef::new(&format!("{}.{}", schema, fact_agg.fact.physical_table))
-                .with_alias(&fact_agg.fact.name)
-        } else {
-            TableRef::new(&fact_agg.fact.physical_table).with_alias(&fact_agg.fact.name)
-        };
-
-        let mut subquery = Query::new().from(table_ref);
+        let mut subquery = Query::new().from(entity_table_ref(&fact_agg.fact));
 
         // Collect SELECT and GROUP BY expressions
         let mut select_exprs: Vec<SelectExpr> = Vec::new();
@@ -119,9 +124,32 @@ impl<'a> MultiFactEmitter<'a> {
 
         // Add join key columns to SELECT and GROUP BY
         for key in &fact_agg.join_keys {
-            if !key.fact_column.is_empty() {
-                let col_expr = table_col(&fact_agg.fact.name, &key.fact_column);
-                select_exprs.push(SelectExpr::new(col_expr.clone()).with_alias(&key.fact_column));
+            let dimension = self
+                .query
+                .shared_dimensions
+                .iter()
+                .find(|sd| sd.dimension.name == key.dimension);
+            if let (true, Some(dim)) = (key.joins_in_cte(), dimension) {
+                let mut on = self.emit_key_condition(key, &fact_agg.fact.name);
+                if let Some(version) = self.emit_version_condition(&key.dimension) {
+                    on = on.and(version);
+                }
+                let dim_ref = entity_table_ref(&dim.dimension);
+                subquery = if key.optional {
+                    subquery.left_join(dim_ref, on)
+                } else {
+                    subquery.inner_join(dim_ref, on)
+                };
+            }
+
+            let fact_columns = key.column_pairs();
+            for (cte_column, dim_column) in key.key_columns() {
+                let col_expr = if fact_columns.iter().any(|(fact, _)| *fact == cte_column) {
+                    table_col(&fact_agg.fact.name, &cte_column)
+                } else {
+                    table_col(&key.dimension, &dim_column)
+                };
+                select_exprs.push(SelectExpr::new(col_expr.clone()).with_alias(&cte_column));
                 group_exprs.push(col_expr);
             }
         }
@@ -170,16 +198,18 @@ impl<'a> MultiFactEmitter<'a> {
             );
         }
 
-        // JOIN dimension tables
+        // JOIN dimension tables; LEFT JOIN when any fact may lack the dimension
         for dim in &self.query.shared_dimensions {
-            let join_condition = self.emit_dimension_join_condition(dim, &cte_aliases);
-            let dim_ref = if let Some(ref schema) = dim.dimension.physical_schema {
-                TableRef::new(&format!("{}.{}", schema, dim.dimension.physical_table))
-                    .with_alias(&dim.dimension.name)
+            let mut join_condition = self.emit_dimension_join_condition(dim, &cte_aliases);
+            if let Some(version) = self.emit_version_condition(&dim.dimension.name) {
+                join_condition = join_condition.and(version);
+            }
+            let dim_ref = entity_table_ref(&dim.dimension);
+            query = if dim.paths.iter().any(|(_, key)| key.optional) {
+                query.left_join(dim_ref, join_condition)
             } else {
-                TableRef::new(&dim.dimension.physical_table).with_alias(&dim.dimension.name)
+                query.inner_join(dim_ref, join_condition)
             };
-            query = query.inner_join(dim_ref, join_condition);
         }
 
         // Build SELECT expressions
@@ -214,11 +244,17 @@ impl<'a> MultiFactEmitter<'a> {
             let left_key = self.find_cte_join_key(dim, left_cte);
             let right_key = self.find_cte_join_key(dim, right_cte);
 
+            // Match the CTE columns carrying the same dimension column
             if let (Some(lk), Some(rk)) = (left_key, right_key) {
-                if !lk.fact_column.is_empty() && !rk.fact_column.is_empty() {
-                    let left_col = table_col(left_cte, &lk.fact_column);
-                    let right_col = table_col(right_cte, &rk.fact_column);
-                    conditions.push(left_col.eq(right_col));
+                let right_columns = rk.key_columns();
+                for (left_column, dim_column) in lk.key_columns() {
+                    if let Some((right_column, _)) =
+                        right_columns.iter().find(|(_, d)| *d == dim_column)
+                    {
+                        let left_col = table_col(left_cte, &left_column);
+                        let right_col = table_col(right_cte, right_column);
+                        conditions.push(left_col.eq(right_col));
+                    }
                 }
             }
         }
@@ -235,7 +271,7 @@ impl<'a> MultiFactEmitter<'a> {
         &self,
         dim: &'b SharedDimension,
         cte_alias: &str,
-    ) -> Option<&'b super::resolved::FactJoinKey> {
+    ) -> Option<&'b FactJoinKey> {
         dim.paths.iter().find_map(|(fact_name, key)| {
             self.query
                 .fact_aggregates
@@ -251,28 +287,75 @@ impl<'a> MultiFactEmitter<'a> {
         dim: &SharedDimension,
         cte_aliases: &[&str],
     ) -> Expr {
-        // Build COALESCE of all CTE join keys
-        let coalesce_args: Vec<Expr> = cte_aliases
+        // Dimension columns matched by any fact's key, in first-seen order
+        let mut dim_columns: Vec<String> = Vec::new();
+        for (_, key) in &dim.paths {
+            for (_, dim_column) in key.key_columns() {
+                if !dim_columns.contains(&dim_column) {
+                    dim_columns.push(dim_column);
+                }
+            }
+        }
+
+        // For each, COALESCE of the CTE columns carrying it
+        let conditions: Vec<Expr> = dim_columns
             .iter()
-            .filter_map(|cte_alias| {
-                self.find_cte_join_key(dim, cte_alias)
-                    .filter(|key| !key.fact_column.is_empty())
-                    .map(|key| table_col(cte_alias, &key.fact_column))
+            .filter_map(|dim_column| {
+                let coalesce_args: Vec<Expr> = cte_aliases
+                    .iter()
+                    .filter_map(|cte_alias| {
+                        let key = self.find_cte_join_key(dim, cte_alias)?;
+                        key.key_columns()
+                            .into_iter()
+                            .find(|(_, d)| d == dim_column)
+                            .map(|(cte_column, _)| table_col(cte_alias, &cte_column))
+                    })
+                    .collect();
+                let coalesce_expr = match coalesce_args.len() {
+                    0 => return None,
+                    1 => coalesce_args.into_iter().next().unwrap(),
+                    _ => coalesce(coalesce_args),
+                };
+                Some(coalesce_expr.eq(table_col(&dim.dimension.name, dim_column)))
             })
             .collect();
 
-        if coalesce_args.is_empty() || dim.paths.is_empty() {
-            return lit_int(1).eq(lit_int(1)); // True condition
+        conditions
+            .into_iter()
+            .reduce(|a, b| a.and(b))
+            .unwrap_or_else(|| lit_int(1).eq(lit_int(1))) // True condition
+    }
+
+    /// Full join condition between a fact and a dimension, for joins made
+    /// inside the fact CTE.
+    fn emit_key_condition(&self, key: &FactJoinKey, fact_alias: &str) -> Expr {
+        let mut conditions: Vec<Expr> = key
+            .column_pairs()
+            .into_iter()
+            .map(|(fact_column, dim_column)| {
+                table_col(fact_alias, &fact_column).eq(table_col(&key.dimension, &dim_column))
+            })
+            .collect();
+        if let Some(range) = &key.range {
+            conditions.push(emit_range(range, fact_alias, &key.dimension));
         }
+        conditions.extend(key.predicates.iter().map(convert_model_expr));
 
-        let dim_column = &dim.paths[0].1.dimension_column;
-        let coalesce_expr = if coalesce_args.len() == 1 {
-            coalesce_args.into_iter().next().unwrap()
-        } else {
-            coalesce(coalesce_args)
-        };
+        conditions
+            .into_iter()
+            .reduce(|a, b| a.and(b))
+            .unwrap_or_else(|| lit_int(1).eq(lit_int(1)))
+    }
 
-        coalesce_expr.eq(table_col(&dim.dimension.name, dim_column))
+    /// Version predicate of an SCD Type 2 dimension, if it keeps history.
+    fn emit_version_condition(&self, dimension: &str) -> Option<Expr> {
+        let as_of = self.query.as_of.iter().find(|a| a.dimension == dimension)?;
+        let point = match &as_of.point {
+            AsOfPoint::Date(date) => Some(lit_str(date)),
+            // Fact dates are rejected when resolving multi-fact queries
+            _ => None,
+        };
+        Some(emit_version_predicate(as_of, point))
     }
 
     /// Generate aggregate expression for a measure.
@@ -361,11 +444,7 @@ mod tests {
                         materialized: true,
                     },
                     cte_alias: "orders_agg".into(),
-                    join_keys: vec![FactJoinKey {
-                        fact_column: "date_id".into(),
-                        dimension: "date".into(),
-                        dimension_column: "date_id".into(),
-                    }],
+                    join_keys: vec![FactJoinKey::new("date_id", "date", "date_id")],
                     measures: vec![ResolvedMeasure {
                         entity_alias: "orders".into(),
                         name: "revenue".into(),
@@ -384,11 +463,7 @@ mod tests {
                         materialized: true,
                     },
                     cte_alias: "returns_agg".into(),
-                    join_keys: vec![FactJoinKey {
-                        fact_column: "date_id".into(),
-                        dimension: "date".into(),
-                        dimension_column: "date_id".into(),
-                    }],
+                    join_keys: vec![FactJoinKey::new("date_id", "date", "date_id")],
                     measures: vec![ResolvedMeasure {
                         entity_alias: "returns".into(),
                         name: "return_amount".into(),
@@ -415,25 +490,18 @@ mod tests {
                 paths: vec![
                     (
                         "orders".into(),
-                        FactJoinKey {
-                            fact_column: "date_id".into(),
-                            dimension: "date".into(),
-                            dimension_column: "date_id".into(),
-                        },
+                        FactJoinKey::new("date_id", "date", "date_id"),
                     ),
                     (
                         "returns".into(),
-                        FactJoinKey {
-                            fact_column: "date_id".into(),
-                            dimension: "date".into(),
-                            dimension_column: "date_id".into(),
-                        },
+                        FactJoinKey::new("date_id", "date", "date_id"),
                     ),
                 ],
             }],
             global_filters: vec![],
             order_by: vec![],
             limit: Some(100),
+            as_of: vec![],
         }
     }
 
@@ -0,0 +1,397 @@
+//! Top-N per group emitter.
+//!
+//! Wraps an aggregated query so only the best `n` rows of each partition
+//! survive, optionally re-aggregating the remainder into an "Other" row.
+//!
+//! # Patterns
+//!
+//! With `QUALIFY` (DuckDB, Snowflake, BigQuery, Databricks):
+//!
+//! ```sql
+//! SELECT region, product, SUM(amount) AS revenue
+//! FROM ...
+//! GROUP BY region, product
+//! QUALIFY ROW_NUMBER() OVER (PARTITION BY region ORDER BY SUM(amount) DESC) <= 10
+//! ```
+//!
+//! Without `QUALIFY`, the ranking is computed in a CTE and filtered outside:
+//!
+//! ```sql
+//! WITH top_n_ranked AS (
+//!     SELECT region, product, SUM(amount) AS revenue,
+//!            ROW_NUMBER() OVER (PARTITION BY region ORDER BY SUM(amount) DESC) AS top_n_rank
+//!     FROM ... GROUP BY region, product
+//! )
+//! SELECT region, product, revenue FROM top_n_ranked WHERE top_n_rank <= 10
+//! ```
+//!
+//! With a remainder bucket, the outer query re-aggregates instead:
+//!
+//! ```sql
+//! SELECT region,
+//!        CASE WHEN top_n_rank <= 10 THEN product ELSE 'Other' END AS product,
+//!        SUM(revenue) AS revenue
+//! FROM top_n_ranked
+//! GROUP BY region, CASE WHEN top_n_rank <= 10 THEN product ELSE 'Other' END
+//! ```
+//!
+//! Derived fields are recomputed over the bucket from the re-aggregated
+//! measures, so `revenue / orders` becomes `SUM(revenue) / SUM(orders)`.
+
+use crate::dialect::{DialectTarget, SqlDialect};
+use crate::expr::{col, lit_int, lit_str, max, min, row_number, sum, Expr, ExprExt, WindowExt, WindowOrderBy};
+use crate::model::AggregationType;
+use crate::query::{Cte, OrderByExpr, Query, SelectExpr, TableRef};
+use crate::semantic::error::{PlanResult, SemanticError};
+
+use super::resolved::ResolvedTopN;
+
+/// Name of the CTE holding the ranked rows.
+const RANKED_CTE: &str = "top_n_ranked";
+
+/// Name of the ROW_NUMBER() column in the ranked CTE.
+const RANK_COLUMN: &str = "top_n_rank";
+
+/// Emitter for top-N per group.
+///
+/// Works on the output of either the single-fact or multi-fact emitter,
+/// referencing columns by their output aliases.
+pub struct TopNEmitter<'a> {
+    top_n: &'a ResolvedTopN,
+    use_qualify: bool,
+}
+
+impl<'a> TopNEmitter<'a> {
+    /// Create an emitter that uses the portable CTE pattern.
+    pub fn new(top_n: &'a ResolvedTopN) -> Self {
+        Self {
+            top_n,
+            use_qualify: false,
+        }
+    }
+
+    /// Target a dialect, using `QUALIFY` when it is supported.
+    pub fn with_dialect(mut self, dialect: impl Into<DialectTarget>) -> Self {
+        self.use_qualify = dialect.into().supports_qualify();
+        self
+    }
+
+    /// Apply the top-N to an aggregated query.
+    pub fn emit(&self, base: Query) -> PlanResult<Query> {
+        let rank = self.rank_expr(&base)?;
+
+        if self.use_qualify && self.top_n.other_label.is_none() {
+            return Ok(base.qualify(rank.lte(lit_int(self.top_n.n as i64))));
+        }
+
+        let mut inner = base;
+        let ctes = std::mem::take(&mut inner.with);
+        let order_by = std::mem::take(&mut inner.order_by);
+        let limit_offset = inner.limit_offset.take();
+
+        let outputs: Vec<String> = inner
+            .select
+            .iter()
+            .filter_map(|s| s.alias.clone())
+            .collect();
+        let outer_order: Vec<OrderByExpr> = order_by
+            .into_iter()
+            .map(|o| OrderByExpr {
+                expr: output_ref(&inner.select, &o.expr),
+                ..o
+            })
+            .collect();
+
+        inner
+            .select
+            .push(SelectExpr::new(rank).with_alias(RANK_COLUMN));
+
+        let mut outer = match &self.top_n.other_label {
+            Some(label) => self.emit_bucketed(&inner.select, &outputs, label)?,
+            None => Query::new()
+                .select(outputs.iter().map(|a| col(a)).collect::<Vec<_>>())
+                .filter(self.in_top_n()),
+        };
+        outer = outer.from(TableRef::new(RANKED_CTE));
+
+        for cte in ctes {
+            outer = outer.with_cte(cte);
+        }
+        outer = outer.with_cte(Cte::new(RANKED_CTE, inner));
+
+        if !outer_order.is_empty() {
+            outer = outer.order_by(outer_order);
+        }
+        outer.limit_offset = limit_offset;
+
+        Ok(outer)
+    }
+
+    /// Build the re-aggregating outer query for the remainder bucket.
+    fn emit_bucketed(
+        &self,
+        select: &[SelectExpr],
+        outputs: &[String],
+        label: &str,
+    ) -> PlanResult<Query> {
+        let mut select_exprs = Vec::new();
+        let mut group_exprs = Vec::new();
+
+        for alias in outputs {
+            if self.top_n.partition_by.contains(alias) {
+                select_exprs.push(SelectExpr::new(col(alias)).with_alias(alias));
+                group_exprs.push(col(alias));
+            } else if self.top_n.bucketed.contains(alias) {
+                let bucket = Expr::Case {
+                    operand: None,
+                    when_clauses: vec![(self.in_top_n(), col(alias))],
+                    else_clause: Some(Box::new(lit_str(label))),
+                };
+                select_exprs.push(SelectExpr::new(bucket.clone()).with_alias(alias));
+                group_exprs.push(bucket);
+            } else if let Some((_, rollup)) = self.top_n.measures.iter().find(|(m, _)| m == alias) {
+                select_exprs.push(SelectExpr::new(reaggregate(alias, *rollup)).with_alias(alias));
+            } else if self.top_n.derived.contains(alias) {
+                let expr = self.reaggregate_derived(select, alias)?;
+                select_exprs.push(SelectExpr::new(expr).with_alias(alias));
+            }
+        }
+
+        let query = Query::new().select(select_exprs);
+        if group_exprs.is_empty() {
+            Ok(query)
+        } else {
+            Ok(query.group_by(group_exprs))
+        }
+    }
+
+    /// Recompute a derived field over the remainder bucket by replacing each
+    /// measure it combines with that measure's re-aggregated column.
+    ///
+    /// Fails when the field reads anything else from the base query, such as
+    /// a measure that is not selected or a window over the ranked rows.
+    fn reaggregate_derived(&self, select: &[SelectExpr], alias: &str) -> PlanResult<Expr> {
+        let measures: Vec<(&Expr, Expr)> = self
+            .top_n
+            .measures
+            .iter()
+            .filter_map(|(measure, rollup)| {
+                let expr = select
+                    .iter()
+                    .find(|s| s.alias.as_deref() == Some(measure))?;
+                Some((&expr.expr, reaggregate(measure, *rollup)))
+            })
+            .collect();
+
+        let mut unresolved = false;
+        let expr = select_expr_in(select, alias)?.rewrite(&mut |e| {
+            if let Some((_, rolled_up)) = measures.iter().find(|(m, _)| *m == e) {
+                return Some(rolled_up.clone());
+            }
+            // Only arithmetic may remain around the measures
+            if !matches!(
+                e,
+                Expr::Literal(_) | Expr::BinaryOp { .. } | Expr::UnaryOp { .. } | Expr::Paren(_)
+            ) {
+                unresolved = true;
+            }
+            None
+        });
+        if unresolved {
+            return Err(SemanticError::QueryPlanError(format!(
+                "Cannot bucket '{}' into a top-N remainder: it must combine selected, \
+                 additive measures only",
+                alias
+            )));
+        }
+        Ok(expr)
+    }
+
+    /// `ROW_NUMBER() OVER (PARTITION BY ... ORDER BY measure)` over the base query.
+    fn rank_expr(&self, base: &Query) -> PlanResult<Expr> {
+        let partition: Vec<Expr> = self
+            .top_n
+            .partition_by
+            .iter()
+            .map(|alias| select_expr(base, alias).cloned())
+            .collect::<PlanResult<_>>()?;
+
+        let measure = select_expr(base, &self.top_n.rank_by)?.clone();
+        let order = if self.top_n.descending {
+            WindowOrderBy::desc(measure)
+        } else {
+            WindowOrderBy::asc(measure)
+        };
+
+        Ok(row_number()
+            .over()
+            .partition_by(partition)
+            .order_by(vec![order])
+            .build())
+    }
+
+    /// `top_n_rank <= n`
+    fn in_top_n(&self) -> Expr {
+        col(RANK_COLUMN).lte(lit_int(self.top_n.n as i64))
+    }
+}
+
+/// Re-aggregate a measure's output column over the remainder bucket.
+fn reaggregate(alias: &str, rollup: AggregationType) -> Expr {
+    match rollup {
+        AggregationType::Min => min(col(alias)),
+        AggregationType::Max => max(col(alias)),
+        _ => sum(col(alias)),
+    }
+}
+
+/// Find the expression behind an output alias in a query's SELECT list.
+fn select_expr<'q>(query: &'q Query, alias: &str) -> PlanResult<&'q Expr> {
+    select_expr_in(&query.select, alias)
+}
+
+/// Find the expression behind an output alias in a SELECT list.
+fn select_expr_in<'q>(select: &'q [SelectExpr], alias: &str) -> PlanResult<&'q Expr> {
+    select
+        .iter()
+        .find(|s| s.alias.as_deref() == Some(alias))
+        .map(|s| &s.expr)
+        .ok_or_else(|| {
+            SemanticError::QueryPlanError(format!(
+                "Top-N column '{}' is not in the query output",
+                alias
+            ))
+        })
+}
+
+/// Rewrite an ORDER BY expression of the base query as a reference to its output alias.
+fn output_ref(select: &[SelectExpr], expr: &Expr) -> Expr {
+    if let Some(alias) = select
+        .iter()
+        .find(|s| &s.expr == expr)
+        .and_then(|s| s.alias.as_deref())
+    {
+        return col(alias);
+    }
+
+    match expr {
+        Expr::Column { column, .. } if select.iter().any(|s| s.alias.as_deref() == Some(column)) => {
+            col(column)
+        }
+        _ => expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::Dialect;
+    use crate::expr::{count_star, table_col};
+
+    fn base_query() -> Query {
+        Query::new()
+            .select(vec![
+                SelectExpr::new(table_col("customers", "region")).with_alias("region"),
+                SelectExpr::new(table_col("products", "name")).with_alias("product"),
+                SelectExpr::new(sum(table_col("orders", "amount"))).with_alias("revenue"),
+            ])
+            .from(TableRef::new("fact_orders").with_alias("orders"))
+            .group_by(vec![
+                table_col("customers", "region"),
+                table_col("products", "name"),
+            ])
+            .order_by(vec![OrderByExpr::desc(sum(table_col("orders", "amount")))])
+            .limit(100)
+    }
+
+    fn top_n(other: Option<&str>) -> ResolvedTopN {
+        ResolvedTopN {
+            n: 10,
+            partition_by: vec!["region".into()],
+            rank_by: "revenue".into(),
+            descending: true,
+            other_label: other.map(String::from),
+            bucketed: vec!["product".into()],
+            measures: vec![("revenue".into(), AggregationType::Sum)],
+            derived: vec![],
+        }
+    }
+
+    #[test]
+    fn test_top_n_uses_qualify() {
+        let top_n = top_n(None);
+        let query = TopNEmitter::new(&top_n)
+            .with_dialect(Dialect::DuckDb)
+            .emit(base_query())
+            .unwrap();
+        let sql = query.to_sql(Dialect::DuckDb);
+
+        assert!(sql.contains("QUALIFY ROW_NUMBER() OVER (PARTITION BY"), "SQL: {}", sql);
+        assert!(sql.contains("<= 10"), "SQL: {}", sql);
+        assert!(!sql.contains(RANKED_CTE), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_top_n_subquery_without_qualify() {
+        let top_n = top_n(None);
+        let query = TopNEmitter::new(&top_n)
+            .with_dialect(Dialect::Postgres)
+            .emit(base_query())
+            .unwrap();
+        let sql = query.to_sql(Dialect::Postgres);
+
+        assert!(!sql.contains("QUALIFY"), "SQL: {}", sql);
+        assert!(sql.contains("WITH \"top_n_ranked\" AS"), "SQL: {}", sql);
+        assert!(sql.contains("WHERE \"top_n_rank\" <= 10"), "SQL: {}", sql);
+        // ORDER BY and LIMIT move to the outer query and reference aliases
+        assert!(sql.contains("ORDER BY \"revenue\" DESC"), "SQL: {}", sql);
+        assert!(sql.ends_with("LIMIT 100"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_top_n_other_bucket_reaggregates() {
+        let top_n = top_n(Some("Other"));
+        let query = TopNEmitter::new(&top_n)
+            .with_dialect(Dialect::DuckDb)
+            .emit(base_query())
+            .unwrap();
+        let sql = query.to_sql(Dialect::DuckDb);
+
+        assert!(!sql.contains("QUALIFY"), "SQL: {}", sql);
+        assert!(
+            sql.contains("CASE WHEN \"top_n_rank\" <= 10 THEN \"product\" ELSE 'Other' END AS \"product\""),
+            "SQL: {}",
+            sql
+        );
+        assert!(sql.contains("SUM(\"revenue\") AS \"revenue\""), "SQL: {}", sql);
+        assert!(sql.contains("GROUP BY \"region\", CASE WHEN"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_top_n_other_bucket_rejects_unselected_measure_in_derived() {
+        let mut top_n = top_n(Some("Other"));
+        top_n.derived = vec!["aov".into()];
+        let base = base_query().select(vec![
+            SelectExpr::new(table_col("customers", "region")).with_alias("region"),
+            SelectExpr::new(table_col("products", "name")).with_alias("product"),
+            SelectExpr::new(sum(table_col("orders", "amount"))).with_alias("revenue"),
+            SelectExpr::new(sum(table_col("orders", "amount")).div(count_star())).with_alias("aov"),
+        ]);
+
+        // COUNT(*) is not an output, so the bucket cannot recompute it
+        let result = TopNEmitter::new(&top_n).emit(base);
+        assert!(
+            matches!(result, Err(SemanticError::QueryPlanError(ref msg)) if msg.contains("'aov'")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_top_n_unknown_alias_errors() {
+        let mut top_n = top_n(None);
+        top_n.rank_by = "missing".into();
+        let result = TopNEmitter::new(&top_n).emit(base_query());
+        assert!(result.is_err());
+    }
+}
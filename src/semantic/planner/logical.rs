@@ -4,11 +4,13 @@
 //! The logical plan represents operations without committing to
 //! physical execution details.
 
+use crate::model::{Expr, RangeCondition};
 use crate::semantic::error::PlanResult;
 use crate::semantic::model_graph::{JoinEdge, ModelGraph};
 
 use super::resolved::{
-    ResolvedColumn, ResolvedEntity, ResolvedFilter, ResolvedMeasure, ResolvedOrder, ResolvedSelect,
+    ResolvedAsOf, ResolvedColumn, ResolvedEntity, ResolvedFilter, ResolvedMeasure, ResolvedOrder,
+    ResolvedSelect,
 };
 use super::validate::ValidatedQuery;
 
@@ -67,6 +69,30 @@ pub struct JoinCondition {
     pub left_column: String,
     pub right_entity: String,
     pub right_column: String,
+    /// Further `(left, right)` column pairs for composite keys.
+    pub additional_columns: Vec<(String, String)>,
+    /// Version predicate when the right side is an SCD Type 2 dimension.
+    pub as_of: Option<Box<ResolvedAsOf>>,
+    /// Band condition, oriented from left to right.
+    pub range: Option<Box<RangeCondition>>,
+    /// Extra predicates from the relationship, columns qualified by entity.
+    pub predicates: Vec<Expr>,
+}
+
+impl JoinCondition {
+    /// All `(left, right)` column pairs, primary pair first.
+    ///
+    /// Empty for joins on a band condition only.
+    pub fn column_pairs(&self) -> Vec<(&str, &str)> {
+        std::iter::once((self.left_column.as_str(), self.right_column.as_str()))
+            .filter(|(left, right)| !left.is_empty() && !right.is_empty())
+            .chain(
+                self.additional_columns
+                    .iter()
+                    .map(|(left, right)| (left.as_str(), right.as_str())),
+            )
+            .collect()
+    }
 }
 
 /// Logical join types.
@@ -293,11 +319,14 @@ impl<'a> LogicalPlanner<'a> {
 
             // Find the relationship between grain entity and included dimension
             if let Some((rel, reversed)) = graph.find_relationship_either_direction(&grain_entity, &include.entity) {
-                let (left_col, right_col) = if reversed {
-                    (rel.to_column.clone(), rel.from_column.clone())
-                } else {
-                    (rel.from_column.clone(), rel.to_column.clone())
-                };
+                let mut pairs = rel.column_pairs().into_iter().map(|(from, to)| {
+                    if reversed {
+                        (to.to_string(), from.to_string())
+                    } else {
+                        (from.to_string(), to.to_string())
+                    }
+                });
+                let (left_col, right_col) = pairs.next().unwrap_or_default();
 
                 let right = LogicalPlan::Scan(ScanNode {
                     entity: dim_resolved,
@@ -311,8 +340,34 @@ impl<'a> LogicalPlanner<'a> {
                         left_column: left_col,
                         right_entity: dim_alias.clone(),
                         right_column: right_col,
+                        additional_columns: pairs.collect(),
+                        as_of: None,
+                        range: rel.range.as_ref().map(|range| {
+                            Box::new(if reversed {
+                                range.reversed()
+                            } else {
+                                range.clone()
+                            })
+                        }),
+                        predicates: rel
+                            .predicates
+                            .iter()
+                            .map(|predicate| {
+                                let mut predicate = predicate.clone();
+                                predicate.visit_columns_mut(&mut |entity, _| {
+                                    if entity.as_deref() == Some(include.entity.as_str()) {
+                                        *entity = Some(dim_alias.clone());
+                                    }
+                                });
+                                predicate
+                            })
+                            .collect(),
+                    },
+                    join_type: if rel.optional && !reversed {
+                        LogicalJoinType::Left
+                    } else {
+                        LogicalJoinType::Inner
                     },
-                    join_type: LogicalJoinType::Inner,
                 });
             }
             // If no direct relationship, skip the join (validated should catch this)
@@ -370,8 +425,21 @@ impl<'a> LogicalPlanner<'a> {
                     left_column: edge.from_column.clone(),
                     right_entity: edge.to_entity.clone(),
                     right_column: edge.to_column.clone(),
+                    additional_columns: edge.additional_columns.clone(),
+                    as_of: validated
+                        .query
+                        .as_of
+                        .iter()
+                        .find(|a| a.dimension == edge.to_entity)
+                        .map(|a| Box::new(a.clone())),
+                    range: edge.range.clone().map(Box::new),
+                    predicates: edge.predicates.clone(),
+                },
+                join_type: if edge.optional {
+                    LogicalJoinType::Left
+                } else {
+                    LogicalJoinType::Inner
                 },
-                join_type: LogicalJoinType::Inner,
             });
         }
 
@@ -16,7 +16,7 @@
 //!        │
 //!        ▼
 //! ┌─────────────────────┐
-//! │  Phase 2: VALIDATE  │  Check join safety (no fan-out)
+//! │  Phase 2: VALIDATE  │  Check join safety (no fan-out, bridges)
 //! │  (validate.rs)      │  Validate GROUP BY completeness
 //! └─────────────────────┘
 //!        │
@@ -45,6 +45,7 @@
 pub mod emit;
 pub mod emit_multi;
 pub mod emit_time;
+pub mod emit_top_n;
 pub mod logical;
 pub mod prune;
 pub mod report;
@@ -57,19 +58,22 @@ pub mod validate;
 pub use emit::Emitter;
 pub use emit_multi::MultiFactEmitter;
 pub use emit_time::TimeEmitter;
+pub use emit_top_n::TopNEmitter;
 pub use prune::{ColumnPruner, PrunedColumns};
 pub use logical::{LogicalPlan, LogicalPlanner};
 pub use resolve::Resolver;
 pub use resolved::{
-    FactAggregate, FactJoinKey, MultiFactQuery, ResolvedColumn, ResolvedMeasure,
-    ResolvedQuery, ResolvedQueryPlan, ResolvedSelect, SharedDimension,
+    BridgeStrategy, FactAggregate, FactJoinKey, MultiFactQuery, ResolvedBridge, ResolvedColumn,
+    ResolvedMeasure,
+    ResolvedQuery, ResolvedQueryPlan, ResolvedSelect, ResolvedTopN, SharedDimension,
 };
 pub use types::{
-    DerivedBinaryOp, DerivedExpr, DerivedField, FieldFilter, FieldRef, FilterOp, FilterValue,
-    OrderField, SelectField, SemanticQuery, TimeFunction,
+    AsOf, DerivedBinaryOp, DerivedExpr, DerivedField, Drill, DrillDirection, FieldFilter, FieldRef,
+    FilterOp, FilterValue, OrderField, SelectField, SemanticQuery, TimeFunction, TopN,
 };
 pub use validate::{ValidatedQuery, Validator};
 
+use crate::dialect::DialectTarget;
 use crate::query::Query;
 use crate::semantic::column_lineage::ColumnLineageGraph;
 use crate::semantic::error::{PlanResult, SemanticError};
@@ -83,10 +87,14 @@ use crate::semantic::model_graph::ModelGraph;
 /// Optionally uses column lineage for:
 /// - Cycle detection (validates no circular column dependencies)
 /// - Column pruning (determines minimal columns needed)
+///
+/// Optionally targets a dialect, so dialect-specific constructs
+/// (e.g. `QUALIFY` for top-N) can be used instead of portable fallbacks.
 pub struct QueryPlanner<'a> {
     graph: &'a ModelGraph,
     lineage: Option<&'a ColumnLineageGraph>,
     default_schema: String,
+    dialect: Option<DialectTarget>,
 }
 
 impl<'a> QueryPlanner<'a> {
@@ -95,6 +103,7 @@ impl<'a> QueryPlanner<'a> {
             graph,
             lineage: None,
             default_schema: "dbo".to_string(),
+            dialect: None,
         }
     }
 
@@ -110,6 +119,15 @@ impl<'a> QueryPlanner<'a> {
         self
     }
 
+    /// Target a specific dialect, optionally pinned to a server version.
+    ///
+    /// Without a dialect, the planner only emits constructs that every
+    /// dialect supports.
+    pub fn with_dialect(mut self, dialect: impl Into<DialectTarget>) -> Self {
+        self.dialect = Some(dialect.into());
+        self
+    }
+
     /// Plan a semantic query into a SQL query.
     ///
     /// This is the main entry point that runs all four phases.
@@ -125,6 +143,7 @@ impl<'a> QueryPlanner<'a> {
 
         // Phase 1: Resolve - detect single vs multi-fact
         let resolver = Resolver::new(self.graph);
+        let query = &resolver.apply_drills(query)?;
 
         // Check if this is a multi-fact query
         if resolver.is_multi_fact(query)? {
@@ -150,10 +169,16 @@ impl<'a> QueryPlanner<'a> {
 
         // Phase 4: Emit
         let mut emitter = Emitter::new().with_default_schema(&self.default_schema);
+        if let Some(bridge) = &validated.join_tree.bridge {
+            emitter = emitter.with_bridge(bridge.clone());
+        }
         if let Some(pruned) = pruned_columns {
             emitter = emitter.with_pruned_columns(pruned);
         }
-        emitter.emit(&logical_plan)
+        let sql_query = emitter.emit(&logical_plan)?;
+
+        // Phase 5: Top-N (ranks the aggregated result)
+        self.apply_top_n(query, &resolver, &single_fact_outputs(&validated), sql_query)
     }
 
     /// Plan a multi-fact query using the symmetric aggregate pattern.
@@ -163,7 +188,32 @@ impl<'a> QueryPlanner<'a> {
 
         // Use the multi-fact emitter
         let emitter = MultiFactEmitter::new(&multi_fact);
-        Ok(emitter.emit())
+        let sql_query = emitter.emit();
+
+        self.apply_top_n(query, resolver, &multi_fact_outputs(&multi_fact), sql_query)
+    }
+
+    /// Wrap an emitted query with the top-N clause, if the query has one.
+    ///
+    /// `outputs` describes the SELECT list of `sql_query` so top-N fields
+    /// can be mapped to output aliases.
+    fn apply_top_n(
+        &self,
+        query: &SemanticQuery,
+        resolver: &Resolver,
+        outputs: &[ResolvedSelect],
+        sql_query: Query,
+    ) -> PlanResult<Query> {
+        let Some(top_n) = &query.top_n else {
+            return Ok(sql_query);
+        };
+
+        let resolved = resolver.resolve_top_n(top_n, outputs)?;
+        let mut emitter = TopNEmitter::new(&resolved);
+        if let Some(dialect) = self.dialect {
+            emitter = emitter.with_dialect(dialect);
+        }
+        emitter.emit(sql_query)
     }
 
     /// Plan with access to intermediate representations.
@@ -177,6 +227,7 @@ impl<'a> QueryPlanner<'a> {
 
         // Phase 1: Resolve
         let resolver = Resolver::new(self.graph);
+        let query = &resolver.apply_drills(query)?;
         let resolved = resolver.resolve(query)?;
 
         // Phase 2: Validate
@@ -195,10 +246,15 @@ impl<'a> QueryPlanner<'a> {
 
         // Phase 4: Emit
         let mut emitter = Emitter::new().with_default_schema(&self.default_schema);
+        if let Some(bridge) = &validated.join_tree.bridge {
+            emitter = emitter.with_bridge(bridge.clone());
+        }
         if let Some(ref pruned) = pruned_columns {
             emitter = emitter.with_pruned_columns(pruned.clone());
         }
         let sql_query = emitter.emit(&logical_plan)?;
+        let sql_query =
+            self.apply_top_n(query, &resolver, &single_fact_outputs(&validated), sql_query)?;
 
         Ok(PlanPhases {
             validated,
@@ -209,6 +265,39 @@ impl<'a> QueryPlanner<'a> {
     }
 }
 
+/// The SELECT list emitted for a single-fact query: GROUP BY columns first,
+/// then the explicit select items.
+fn single_fact_outputs(validated: &ValidatedQuery) -> Vec<ResolvedSelect> {
+    validated
+        .query
+        .group_by
+        .iter()
+        .map(|column| ResolvedSelect::Column {
+            column: column.clone(),
+            alias: None,
+        })
+        .chain(validated.query.select.iter().cloned())
+        .collect()
+}
+
+/// The SELECT list emitted for a multi-fact query: shared dimension columns,
+/// then each fact's measures.
+fn multi_fact_outputs(multi_fact: &MultiFactQuery) -> Vec<ResolvedSelect> {
+    let columns = multi_fact.shared_dimensions.iter().flat_map(|dim| {
+        dim.columns.iter().map(|column| ResolvedSelect::Column {
+            column: column.clone(),
+            alias: None,
+        })
+    });
+    let measures = multi_fact.fact_aggregates.iter().flat_map(|fa| {
+        fa.measures.iter().map(|measure| ResolvedSelect::Measure {
+            measure: measure.clone(),
+            alias: None,
+        })
+    });
+    columns.chain(measures).collect()
+}
+
 /// Result of planning with all intermediate representations.
 #[derive(Debug)]
 pub struct PlanPhases {
@@ -225,12 +314,27 @@ pub struct PlanPhases {
     pub pruned_columns: Option<PrunedColumns>,
 }
 
+impl PlanPhases {
+    /// Explain the planning decisions that change the shape of the SQL.
+    ///
+    /// Currently reports the strategy used to cross a bridge, if any.
+    pub fn explain(&self) -> Vec<String> {
+        self.validated
+            .join_tree
+            .bridge
+            .iter()
+            .map(|bridge| bridge.to_string())
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dialect::Dialect;
     use crate::model::{
-        Cardinality, DataType, FactDefinition, Model, Relationship, SourceEntity,
+        BridgeDefinition, Cardinality, DataType, DateConfig, DimensionDefinition, DimensionRole,
+        Expr, FactDefinition, Model, Relationship, SCDType, SourceEntity,
     };
 
     fn sample_graph() -> ModelGraph {
@@ -291,6 +395,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let query = planner.plan(&sq).unwrap();
@@ -323,6 +430,9 @@ mod tests {
             derived: vec![],
             order_by: vec![OrderField::desc("orders_fact", "revenue")],
             limit: Some(10),
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let query = planner.plan(&sq).unwrap();
@@ -337,6 +447,117 @@ mod tests {
         assert!(sql.contains("LIMIT 10"));
     }
 
+    #[test]
+    fn test_plan_with_composite_join() {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("order_lines", "dbo.order_lines")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("line_no", DataType::Int32)
+                    .with_required_column("sku", DataType::String)
+                    .with_primary_key(vec!["order_id", "line_no"]),
+            )
+            .with_relationship(Relationship::composite(
+                "shipments_fact",
+                "order_lines",
+                vec![("order_id", "order_id"), ("line_no", "line_no")],
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("shipments_fact", "dbo.shipments_fact")
+                    .with_grain("order_lines", "order_id")
+                    .with_count("shipment_count", "*"),
+            );
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph);
+
+        let sq = SemanticQuery {
+            from: Some("shipments_fact".into()),
+            filters: vec![],
+            group_by: vec![FieldRef::new("order_lines", "sku")],
+            select: vec![SelectField::new("shipments_fact", "shipment_count")],
+            derived: vec![],
+            order_by: vec![],
+            limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
+        };
+
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::DuckDb);
+
+        println!("Generated SQL:\n{}", sql);
+
+        assert!(
+            sql.contains(r#""shipments_fact"."order_id" = "order_lines"."order_id""#),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains(r#"AND "shipments_fact"."line_no" = "order_lines"."line_no""#),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_multi_fact_composite_join() {
+        let composite = |fact: &str| {
+            Relationship::composite(
+                fact,
+                "order_lines",
+                vec![("order_id", "order_id"), ("line_no", "line_no")],
+                Cardinality::ManyToOne,
+            )
+        };
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("order_lines", "order_lines")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("line_no", DataType::Int32)
+                    .with_required_column("sku", DataType::String)
+                    .with_primary_key(vec!["order_id", "line_no"]),
+            )
+            .with_relationship(composite("shipments_fact"))
+            .with_relationship(composite("returns_fact"))
+            .with_fact(
+                FactDefinition::new("shipments_fact", "shipments_fact")
+                    .with_grain("order_lines", "order_id")
+                    .with_sum("shipped", "qty"),
+            )
+            .with_fact(
+                FactDefinition::new("returns_fact", "returns_fact")
+                    .with_grain("order_lines", "order_id")
+                    .with_sum("returned", "qty"),
+            );
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph);
+
+        // Order 1 has two lines with different SKUs
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE order_lines (order_id INTEGER, line_no INTEGER, sku TEXT);
+             CREATE TABLE shipments_fact (order_id INTEGER, line_no INTEGER, qty INTEGER);
+             CREATE TABLE returns_fact (order_id INTEGER, line_no INTEGER, qty INTEGER);
+             INSERT INTO order_lines VALUES (1, 1, 'apple'), (1, 2, 'pear');
+             INSERT INTO shipments_fact VALUES (1, 1, 10), (1, 2, 20);
+             INSERT INTO returns_fact VALUES (1, 2, 3);",
+        )
+        .unwrap();
+
+        let query = SemanticQuery {
+            group_by: vec![FieldRef::new("order_lines", "sku")],
+            select: vec![
+                SelectField::new("shipments_fact", "shipped"),
+                SelectField::new("returns_fact", "returned"),
+            ],
+            ..Default::default()
+        };
+        let mut rows = run_sqlite(&conn, &planner, &query);
+        rows.sort();
+        assert_eq!(rows, vec!["apple|0|10", "pear|3|20"]);
+    }
+
     #[test]
     fn test_plan_phases() {
         let graph = sample_graph();
@@ -351,6 +572,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let phases = planner.plan_phases(&sq).unwrap();
@@ -381,6 +605,9 @@ mod tests {
             derived: vec![],
             order_by: vec![OrderField::desc("orders_fact", "revenue")],
             limit: Some(10),
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let query = planner.plan(&sq).unwrap();
@@ -433,6 +660,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         // Should succeed (no cycles)
@@ -475,6 +705,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         // Should fail due to cycle
@@ -519,6 +752,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let phases = planner.plan_phases(&sq).unwrap();
@@ -584,6 +820,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let query = planner.plan(&sq).unwrap();
@@ -636,6 +875,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let query = planner.plan(&sq).unwrap();
@@ -650,4 +892,1150 @@ mod tests {
             sql
         );
     }
+
+    fn top_n_query(top_n: TopN) -> SemanticQuery {
+        SemanticQuery {
+            from: Some("orders_fact".into()),
+            filters: vec![],
+            group_by: vec![
+                FieldRef::new("customers", "region"),
+                FieldRef::new("customers", "customer_name"),
+            ],
+            select: vec![SelectField::new("orders_fact", "revenue")],
+            derived: vec![],
+            order_by: vec![OrderField::asc("customers", "region")],
+            limit: None,
+            top_n: Some(top_n),
+            as_of: AsOf::default(),
+            drill: vec![],
+        }
+    }
+
+    #[test]
+    fn test_top_n_per_group_qualify() {
+        let graph = sample_graph();
+        let planner = QueryPlanner::new(&graph).with_dialect(Dialect::DuckDb);
+
+        let sq = top_n_query(
+            TopN::new(3, FieldRef::new("orders_fact", "revenue"))
+                .partition_by(vec![FieldRef::new("customers", "region")]),
+        );
+
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::DuckDb);
+        println!("Top-N SQL:\n{}", sql);
+
+        assert!(sql.contains("QUALIFY ROW_NUMBER() OVER (PARTITION BY \"customers\".\"region\""));
+        assert!(sql.contains("ORDER BY SUM(\"orders_fact\".\"amount\") DESC) <= 3"));
+    }
+
+    #[test]
+    fn test_top_n_per_group_without_qualify() {
+        let graph = sample_graph();
+        let planner = QueryPlanner::new(&graph).with_dialect(Dialect::TSql);
+
+        let sq = top_n_query(
+            TopN::new(3, FieldRef::new("orders_fact", "revenue"))
+                .partition_by(vec![FieldRef::new("customers", "region")]),
+        );
+
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::TSql);
+        println!("Top-N SQL:\n{}", sql);
+
+        assert!(!sql.contains("QUALIFY"));
+        assert!(sql.contains("ROW_NUMBER() OVER"));
+        assert!(sql.contains("WHERE [top_n_rank] <= 3"));
+        assert!(sql.contains("ORDER BY [region] ASC"));
+    }
+
+    #[test]
+    fn test_top_n_with_other_bucket() {
+        let graph = sample_graph();
+        let planner = QueryPlanner::new(&graph).with_dialect(Dialect::Postgres);
+
+        let sq = top_n_query(
+            TopN::new(5, FieldRef::new("orders_fact", "revenue"))
+                .partition_by(vec![FieldRef::new("customers", "region")])
+                .with_other("Other"),
+        );
+
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::Postgres);
+        println!("Top-N SQL:\n{}", sql);
+
+        assert!(sql.contains(
+            "CASE WHEN \"top_n_rank\" <= 5 THEN \"customer_name\" ELSE 'Other' END AS \"customer_name\""
+        ));
+        assert!(sql.contains("SUM(\"revenue\") AS \"revenue\""));
+        assert!(sql.contains("GROUP BY \"region\", CASE WHEN"));
+    }
+
+    #[test]
+    fn test_top_n_rejects_unselected_measure() {
+        let graph = sample_graph();
+        let planner = QueryPlanner::new(&graph);
+
+        let sq = top_n_query(TopN::new(3, FieldRef::new("orders_fact", "order_count")));
+
+        assert!(planner.plan(&sq).is_err());
+    }
+
+    #[test]
+    fn test_top_n_multi_fact() {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("orders", "dbo.orders")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("amount", DataType::Decimal(10, 2))
+                    .with_primary_key(vec!["order_id"]),
+            )
+            .with_source(
+                SourceEntity::new("returns", "dbo.returns")
+                    .with_required_column("return_id", DataType::Int64)
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("refund", DataType::Decimal(10, 2))
+                    .with_primary_key(vec!["return_id"]),
+            )
+            .with_source(
+                SourceEntity::new("customers", "dbo.customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("region", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_relationship(Relationship::new(
+                "orders_fact",
+                "customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_relationship(Relationship::new(
+                "returns_fact",
+                "customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("orders_fact", "dbo.orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount"),
+            )
+            .with_fact(
+                FactDefinition::new("returns_fact", "dbo.returns_fact")
+                    .with_grain("returns", "return_id")
+                    .with_sum("refunds", "refund"),
+            );
+
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph).with_dialect(Dialect::Postgres);
+
+        let sq = SemanticQuery {
+            from: None,
+            filters: vec![],
+            group_by: vec![FieldRef::new("customers", "region")],
+            select: vec![
+                SelectField::new("orders_fact", "revenue"),
+                SelectField::new("returns_fact", "refunds"),
+            ],
+            derived: vec![],
+            order_by: vec![],
+            limit: None,
+            top_n: Some(TopN::new(2, FieldRef::new("orders_fact", "revenue")).with_other("Other")),
+            as_of: AsOf::default(),
+            drill: vec![],
+        };
+
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::Postgres);
+        println!("Multi-fact top-N SQL:\n{}", sql);
+
+        // Fact CTEs are hoisted ahead of the ranked CTE
+        assert!(sql.starts_with("WITH \"orders_fact_agg\" AS"));
+        assert!(sql.contains("\"top_n_ranked\" AS"));
+        assert!(sql.contains("ORDER BY COALESCE(\"orders_fact_agg\".\"revenue\", 0) DESC"));
+        assert!(sql.contains("SUM(\"refunds\") AS \"refunds\""));
+    }
+
+    fn aov() -> DerivedField {
+        DerivedField::new(
+            "aov",
+            DerivedExpr::BinaryOp {
+                left: Box::new(DerivedExpr::MeasureRef("revenue".into())),
+                op: DerivedBinaryOp::Div,
+                right: Box::new(DerivedExpr::MeasureRef("order_count".into())),
+            },
+        )
+    }
+
+    #[test]
+    fn test_top_n_other_bucket_recomputes_derived() {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("orders", "orders")
+                    .with_required_column("order_id", DataType::Int64)
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("amount", DataType::Float64)
+                    .with_primary_key(vec!["order_id"]),
+            )
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("customer_name", DataType::String)
+                    .with_required_column("region", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_relationship(Relationship::new(
+                "orders_fact",
+                "customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("orders_fact", "orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount")
+                    .with_count("order_count", "*"),
+            );
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE customers (customer_id INTEGER, customer_name TEXT, region TEXT);
+             CREATE TABLE orders_fact (order_id INTEGER, customer_id INTEGER, amount REAL);
+             INSERT INTO customers VALUES (1, 'alice', 'east'), (2, 'bob', 'east'), (3, 'carol', 'east');
+             INSERT INTO orders_fact VALUES
+                 (1, 1, 60.0), (2, 1, 40.0), (3, 2, 30.0), (4, 3, 10.0), (5, 3, 20.0);",
+        )
+        .unwrap();
+
+        let mut query = top_n_query(
+            TopN::new(1, FieldRef::new("orders_fact", "revenue"))
+                .partition_by(vec![FieldRef::new("customers", "region")])
+                .with_other("Other"),
+        );
+        query.select.push(SelectField::new("orders_fact", "order_count"));
+        query.derived = vec![aov()];
+        query.order_by = vec![OrderField::desc("orders_fact", "revenue")];
+
+        // The remainder's average is 60 / 3 orders, not the sum of 30 and 15
+        assert_eq!(
+            run_sqlite(&conn, &planner, &query),
+            vec!["east|alice|100|2|50", "east|Other|60|3|20"]
+        );
+    }
+
+    #[test]
+    fn test_multi_fact_rejects_derived_fields() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph);
+
+        let query = SemanticQuery {
+            group_by: vec![FieldRef::new("dim_customers", "segment")],
+            select: vec![
+                SelectField::new("orders_fact", "revenue"),
+                SelectField::new("returns_fact", "refunds"),
+            ],
+            derived: vec![aov()],
+            as_of: AsOf::Current,
+            ..Default::default()
+        };
+        assert!(matches!(
+            planner.plan(&query),
+            Err(SemanticError::QueryPlanError(msg)) if msg.contains("Derived field 'aov'")
+        ));
+    }
+
+    /// Orders joined to a customer dimension that keeps SCD Type 2 history.
+    ///
+    /// Order dates join the date dimension on the date itself, ship and
+    /// return dates on its surrogate key.
+    fn scd2_graph() -> ModelGraph {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("segment", DataType::String)
+                    .with_required_column("valid_from", DataType::Date)
+                    .with_required_column("valid_to", DataType::Date),
+            )
+            .with_source(
+                SourceEntity::new("dates", "dates")
+                    .with_required_column("date_key", DataType::Int64)
+                    .with_required_column("full_date", DataType::Date)
+                    .with_primary_key(vec!["date_key"]),
+            )
+            .with_dimension(
+                DimensionDefinition::new("dim_date", "dim_date", "dates")
+                    .with_columns(vec!["date_key", "full_date"])
+                    .with_primary_key(vec!["date_key"]),
+            )
+            .with_dimension(
+                DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+                    .with_columns(vec!["customer_id", "segment", "valid_from", "valid_to"])
+                    .with_primary_key(vec!["customer_id", "valid_from"])
+                    .with_scd_type(SCDType::Type2 {
+                        effective_from: "valid_from".into(),
+                        effective_to: "valid_to".into(),
+                        is_current: None,
+                    }),
+            )
+            .with_relationship(Relationship::new(
+                "orders_fact",
+                "dim_customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("orders_fact", "orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount")
+                    .with_date_config(
+                        DateConfig::new()
+                            .with_role(DimensionRole::new("order_date", "order_date", "dim_date", "full_date"))
+                            .with_role(
+                                DimensionRole::new("ship_date", "ship_date_key", "dim_date", "date_key")
+                                    .with_date_column("full_date"),
+                            )
+                            .with_role(DimensionRole::new("return_date", "return_date_key", "dim_date", "date_key")),
+                    ),
+            )
+            .with_relationship(Relationship::new(
+                "returns_fact",
+                "dim_customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("returns_fact", "returns_fact")
+                    .with_grain("returns", "return_id")
+                    .with_sum("refunds", "refund"),
+            );
+
+        ModelGraph::from_model(model).unwrap()
+    }
+
+    fn revenue_by_segment(as_of: AsOf) -> SemanticQuery {
+        SemanticQuery {
+            from: Some("orders_fact".into()),
+            group_by: vec![FieldRef::new("dim_customers", "segment")],
+            select: vec![SelectField::new("orders_fact", "revenue")],
+            order_by: vec![OrderField::asc("dim_customers", "segment")],
+            as_of,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scd2_join_as_of_fact_date() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sql = planner
+            .plan(&revenue_by_segment(AsOf::default()))
+            .unwrap()
+            .to_sql(Dialect::DuckDb);
+        println!("SCD2 SQL:\n{}", sql);
+        assert!(sql.contains(
+            "ON \"orders_fact\".\"customer_id\" = \"dim_customers\".\"customer_id\" \
+             AND \"orders_fact\".\"order_date\" >= \"dim_customers\".\"valid_from\" \
+             AND (\"orders_fact\".\"order_date\" < \"dim_customers\".\"valid_to\" \
+             OR \"dim_customers\".\"valid_to\" IS NULL)"
+        ));
+
+        // Customer 1 moved from 'smb' to 'enterprise' on 2024-03-01
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE orders_fact (order_id INTEGER, customer_id INTEGER, order_date TEXT, ship_date_key INTEGER, amount INTEGER);
+             CREATE TABLE dim_customers (customer_id INTEGER, segment TEXT, valid_from TEXT, valid_to TEXT);
+             CREATE TABLE dim_date (date_key INTEGER, full_date TEXT);
+             INSERT INTO orders_fact VALUES (1, 1, '2024-01-15', 20240305, 10), (2, 1, '2024-04-01', 20240402, 20);
+             INSERT INTO dim_customers VALUES (1, 'smb', '2023-01-01', '2024-03-01'), (1, 'enterprise', '2024-03-01', NULL);
+             INSERT INTO dim_date VALUES (20240115, '2024-01-15'), (20240305, '2024-03-05'), (20240401, '2024-04-01'), (20240402, '2024-04-02');",
+        )
+        .unwrap();
+        let run = |as_of: AsOf| -> Vec<(String, i64)> {
+            let sql = planner.plan(&revenue_by_segment(as_of)).unwrap().to_sql(Dialect::Sqlite);
+            let mut stmt = conn.prepare(&sql).unwrap();
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+            rows.collect::<Result<Vec<_>, _>>().unwrap()
+        };
+
+        // Each order matches exactly one version, so revenue is not multiplied
+        assert_eq!(run(AsOf::default()), vec![("enterprise".into(), 20), ("smb".into(), 10)]);
+        assert_eq!(
+            run(AsOf::FactDate { role: Some("ship_date".into()) }),
+            vec![("enterprise".into(), 30)]
+        );
+        assert_eq!(run(AsOf::Current), vec![("enterprise".into(), 30)]);
+        assert_eq!(run(AsOf::Date("2024-02-01".into())), vec![("smb".into(), 30)]);
+    }
+
+    #[test]
+    fn test_scd2_join_looks_up_surrogate_date_key() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+        let query = revenue_by_segment(AsOf::FactDate { role: Some("ship_date".into()) });
+
+        let sql = planner.plan(&query).unwrap().to_sql(Dialect::Postgres);
+        assert!(sql.contains(
+            "LEFT JOIN \"main\".\"dim_date\" AS \"dim_customers_as_of\" \
+             ON \"dim_customers_as_of\".\"date_key\" = \"orders_fact\".\"ship_date_key\""
+        ), "{}", sql);
+        assert!(sql.contains("AND \"dim_customers_as_of\".\"full_date\" >= \"dim_customers\".\"valid_from\""), "{}", sql);
+
+        // No correlated subquery in the join condition, which these warehouses reject
+        for dialect in [Dialect::Snowflake, Dialect::BigQuery, Dialect::Databricks, Dialect::ClickHouse] {
+            let sql = planner.plan(&query).unwrap().to_sql(dialect);
+            assert!(!sql.contains("(SELECT"), "{:?}: {}", dialect, sql);
+            crate::sql::test_utils::validate_sql(&sql, dialect)
+                .unwrap_or_else(|e| panic!("{:?}: {}\n{}", dialect, e, sql));
+        }
+    }
+
+    #[test]
+    fn test_scd2_join_current_and_fixed_date() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph);
+
+        let sql = planner.plan(&revenue_by_segment(AsOf::Current)).unwrap().to_sql(Dialect::Postgres);
+        assert!(sql.contains("AND \"dim_customers\".\"valid_to\" IS NULL"));
+        assert!(!sql.contains("order_date"));
+
+        let sql = planner
+            .plan(&revenue_by_segment(AsOf::Date("2024-06-30".into())))
+            .unwrap()
+            .to_sql(Dialect::Postgres);
+        assert!(sql.contains(
+            "AND '2024-06-30' >= \"dim_customers\".\"valid_from\" \
+             AND ('2024-06-30' < \"dim_customers\".\"valid_to\" OR \"dim_customers\".\"valid_to\" IS NULL)"
+        ));
+    }
+
+    #[test]
+    fn test_scd2_join_requires_date_role() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph);
+
+        let result = planner.plan(&revenue_by_segment(AsOf::FactDate {
+            role: Some("delivery_date".into()),
+        }));
+        assert!(matches!(
+            result,
+            Err(SemanticError::QueryPlanError(msg)) if msg.contains("date role 'delivery_date'")
+        ));
+
+        // A surrogate date key is not a date, and the role doesn't say where the date is
+        let result = planner.plan(&revenue_by_segment(AsOf::FactDate {
+            role: Some("return_date".into()),
+        }));
+        assert!(matches!(
+            result,
+            Err(SemanticError::QueryPlanError(msg)) if msg.contains("joins 'dim_date' on 'date_key'")
+        ));
+    }
+
+    #[test]
+    fn test_scd2_join_across_facts() {
+        let graph = scd2_graph();
+        let planner = QueryPlanner::new(&graph);
+        let query = |as_of: AsOf| SemanticQuery {
+            group_by: vec![FieldRef::new("dim_customers", "segment")],
+            select: vec![
+                SelectField::new("orders_fact", "revenue"),
+                SelectField::new("returns_fact", "refunds"),
+            ],
+            as_of,
+            ..Default::default()
+        };
+
+        // Fact dates are aggregated away before the dimension is joined
+        let result = planner.plan(&query(AsOf::default()));
+        assert!(matches!(
+            result,
+            Err(SemanticError::QueryPlanError(msg)) if msg.contains("several facts")
+        ));
+
+        let sql = planner.plan(&query(AsOf::Current)).unwrap().to_sql(Dialect::Postgres);
+        assert!(sql.contains("AND \"dim_customers\".\"valid_to\" IS NULL"), "{}", sql);
+
+        let sql = planner
+            .plan(&query(AsOf::Date("2024-06-30".into())))
+            .unwrap()
+            .to_sql(Dialect::Postgres);
+        assert!(sql.contains("AND '2024-06-30' >= \"dim_customers\".\"valid_from\""), "{}", sql);
+    }
+
+    /// Accounts shared by customers through the `customer_accounts` bridge.
+    fn bridge_graph(bridge: Option<BridgeDefinition>) -> ModelGraph {
+        let mut model = Model::new()
+            .with_source(
+                SourceEntity::new("accounts", "accounts")
+                    .with_required_column("account_id", DataType::Int64)
+                    .with_required_column("balance", DataType::Int64)
+                    .with_primary_key(vec!["account_id"]),
+            )
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("name", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_source(
+                SourceEntity::new("customer_accounts", "customer_accounts")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("account_id", DataType::Int64)
+                    .with_required_column("allocation", DataType::Float64),
+            )
+            .with_relationship(Relationship::new(
+                "customer_accounts",
+                "customers",
+                "customer_id",
+                "customer_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_relationship(Relationship::new(
+                "customer_accounts",
+                "accounts",
+                "account_id",
+                "account_id",
+                Cardinality::ManyToOne,
+            ));
+        if let Some(bridge) = bridge {
+            model = model.with_bridge(bridge);
+        }
+
+        ModelGraph::from_model(model).unwrap()
+    }
+
+    /// Account 1 (100) is shared half-and-half by A and B; account 2 (50) belongs to A.
+    fn bridge_connection() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE accounts (account_id INTEGER, balance INTEGER);
+             CREATE TABLE customers (customer_id INTEGER, name TEXT);
+             CREATE TABLE customer_accounts (customer_id INTEGER, account_id INTEGER, allocation REAL);
+             INSERT INTO accounts VALUES (1, 100), (2, 50);
+             INSERT INTO customers VALUES (1, 'A'), (2, 'B');
+             INSERT INTO customer_accounts VALUES (1, 1, 0.5), (2, 1, 0.5), (1, 2, 1.0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    /// Total balance, optionally per customer name, restricted to customers with a name.
+    fn bridge_query(by_customer: bool) -> SemanticQuery {
+        SemanticQuery {
+            from: Some("accounts".into()),
+            filters: vec![FieldFilter {
+                field: FieldRef::new("customers", "name"),
+                op: FilterOp::IsNotNull,
+                value: FilterValue::Null,
+            }],
+            group_by: if by_customer {
+                vec![FieldRef::new("customers", "name")]
+            } else {
+                vec![]
+            },
+            select: vec![SelectField::aggregate("accounts", "balance", "SUM").with_alias("total")],
+            derived: vec![],
+            order_by: if by_customer {
+                vec![OrderField::asc("customers", "name")]
+            } else {
+                vec![]
+            },
+            limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
+        }
+    }
+
+    fn run_bridge_query(planner: &QueryPlanner, sq: &SemanticQuery) -> Vec<String> {
+        run_sqlite(&bridge_connection(), planner, sq)
+    }
+
+    /// Run a planned query on SQLite, rendering each row as `a|b|...`.
+    fn run_sqlite(conn: &rusqlite::Connection, planner: &QueryPlanner, sq: &SemanticQuery) -> Vec<String> {
+        let sql = planner.plan(sq).unwrap().to_sql(Dialect::Sqlite);
+        println!("SQL:\n{}", sql);
+
+        let mut stmt = conn.prepare(&sql).unwrap();
+        let width = stmt.column_count();
+        let rows = stmt
+            .query_map([], |row| {
+                let values = (0..width)
+                    .map(|i| match row.get_ref(i)? {
+                        rusqlite::types::ValueRef::Text(t) => Ok(String::from_utf8_lossy(t).into_owned()),
+                        rusqlite::types::ValueRef::Null => Ok("NULL".to_string()),
+                        _ => row.get::<_, f64>(i).map(|n| n.to_string()),
+                    })
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(values.join("|"))
+            })
+            .unwrap();
+        rows.collect::<Result<Vec<_>, _>>().unwrap()
+    }
+
+    #[test]
+    fn test_fanout_without_bridge_is_rejected() {
+        let graph = bridge_graph(None);
+        let planner = QueryPlanner::new(&graph);
+
+        let result = planner.plan(&bridge_query(true));
+        assert!(matches!(result, Err(SemanticError::UnsafeJoinPath { .. })));
+    }
+
+    #[test]
+    fn test_bridge_distinct_key_does_not_double_count() {
+        let graph = bridge_graph(Some(BridgeDefinition::new("customer_accounts")));
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sql = planner.plan(&bridge_query(true)).unwrap().to_sql(Dialect::DuckDb);
+        assert!(sql.starts_with("WITH \"bridged\" AS (\nSELECT DISTINCT"));
+        assert!(sql.contains("\"accounts\".\"account_id\" AS \"_key_account_id\""));
+
+        // Each customer sees every account they share once
+        assert_eq!(run_bridge_query(&planner, &bridge_query(true)), vec!["A|150", "B|100"]);
+        // The total counts account 1 once, not once per customer
+        assert_eq!(run_bridge_query(&planner, &bridge_query(false)), vec!["150"]);
+    }
+
+    #[test]
+    fn test_bridge_weighted_allocation_does_not_double_count() {
+        let graph = bridge_graph(Some(
+            BridgeDefinition::new("customer_accounts").with_weight("allocation"),
+        ));
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sql = planner.plan(&bridge_query(true)).unwrap().to_sql(Dialect::DuckDb);
+        assert!(sql.contains(
+            "SUM(\"accounts\".\"balance\" * \"customer_accounts\".\"allocation\") AS \"total\""
+        ));
+
+        // Account 1 is split between A and B, so the groups add up to the total
+        assert_eq!(run_bridge_query(&planner, &bridge_query(true)), vec!["A|100", "B|50"]);
+        assert_eq!(run_bridge_query(&planner, &bridge_query(false)), vec!["150"]);
+    }
+
+    #[test]
+    fn test_bridge_rejects_measures_beyond_bridge() {
+        let graph = bridge_graph(Some(BridgeDefinition::new("customer_accounts")));
+        let planner = QueryPlanner::new(&graph);
+
+        let mut sq = bridge_query(true);
+        sq.select = vec![SelectField::aggregate("customers", "customer_id", "COUNT")];
+
+        let result = planner.plan(&sq);
+        assert!(matches!(result, Err(SemanticError::UnsafeJoinPath { .. })));
+    }
+
+    #[test]
+    fn test_plan_phases_explains_bridge_strategy() {
+        let graph = bridge_graph(Some(BridgeDefinition::new("customer_accounts")));
+        let phases = QueryPlanner::new(&graph).plan_phases(&bridge_query(true)).unwrap();
+        assert_eq!(
+            phases.explain(),
+            vec![
+                "Bridge 'customer_accounts' (accounts -> customers): distinct-key pre-aggregation \
+                 on (account_id); each row is counted once per group"
+            ]
+        );
+
+        let graph = bridge_graph(Some(
+            BridgeDefinition::new("customer_accounts").with_weight("allocation"),
+        ));
+        let phases = QueryPlanner::new(&graph).plan_phases(&bridge_query(true)).unwrap();
+        assert_eq!(
+            phases.explain(),
+            vec![
+                "Bridge 'customer_accounts' (accounts -> customers): weighted allocation \
+                 of additive measures by 'customer_accounts.allocation'"
+            ]
+        );
+
+        let phases = QueryPlanner::new(&sample_graph())
+            .plan_phases(&top_n_query(TopN::new(3, FieldRef::new("orders_fact", "revenue"))))
+            .unwrap();
+        assert!(phases.explain().is_empty());
+    }
+
+    /// Orders with an optional, active-only customer and a band into amount
+    /// tiers; returns banded into the same tiers.
+    fn join_options_graph(tier_cardinality: Cardinality) -> ModelGraph {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("name", DataType::String)
+                    .with_required_column("status", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_source(
+                SourceEntity::new("tiers", "tiers")
+                    .with_required_column("tier", DataType::String)
+                    .with_required_column("min_amount", DataType::Int64)
+                    .with_required_column("max_amount", DataType::Int64),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "orders_fact",
+                    "customers",
+                    "customer_id",
+                    "customer_id",
+                    Cardinality::ManyToOne,
+                )
+                .optional()
+                .with_predicate(Expr::column("status").eq(Expr::string("active"))),
+            )
+            .with_relationship(Relationship::range(
+                "orders_fact",
+                "tiers",
+                "amount",
+                "min_amount",
+                "max_amount",
+                tier_cardinality,
+            ))
+            .with_relationship(Relationship::range(
+                "returns_fact",
+                "tiers",
+                "refund",
+                "min_amount",
+                "max_amount",
+                tier_cardinality,
+            ))
+            .with_fact(
+                FactDefinition::new("orders_fact", "orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount"),
+            )
+            .with_fact(
+                FactDefinition::new("returns_fact", "returns_fact")
+                    .with_grain("returns", "return_id")
+                    .with_sum("refunds", "refund"),
+            );
+
+        ModelGraph::from_model(model).unwrap()
+    }
+
+    /// Order 2 belongs to a closed customer, order 3 has none and is above
+    /// every tier.
+    fn join_options_connection() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE orders_fact (order_id INTEGER, customer_id INTEGER, amount INTEGER);
+             CREATE TABLE returns_fact (return_id INTEGER, refund INTEGER);
+             CREATE TABLE customers (customer_id INTEGER, name TEXT, status TEXT);
+             CREATE TABLE tiers (tier TEXT, min_amount INTEGER, max_amount INTEGER);
+             INSERT INTO orders_fact VALUES (1, 1, 50), (2, 2, 150), (3, NULL, 5000), (4, 3, 20);
+             INSERT INTO returns_fact VALUES (1, 30), (2, 200);
+             INSERT INTO customers VALUES (1, 'Ann', 'active'), (2, 'Bob', 'closed'), (3, 'Cy', 'active');
+             INSERT INTO tiers VALUES ('small', 0, 99), ('medium', 100, 999);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn revenue_by(entity: &str, column: &str) -> SemanticQuery {
+        SemanticQuery {
+            from: Some("orders_fact".into()),
+            group_by: vec![FieldRef::new(entity, column)],
+            select: vec![SelectField::new("orders_fact", "revenue")],
+            order_by: vec![OrderField::asc(entity, column)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_optional_relationship_left_joins_with_predicate() {
+        let graph = join_options_graph(Cardinality::ManyToOne);
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sql = planner
+            .plan(&revenue_by("customers", "name"))
+            .unwrap()
+            .to_sql(Dialect::DuckDb);
+        assert!(sql.contains(
+            "LEFT JOIN \"main\".\"customers\" AS \"customers\" \
+             ON \"orders_fact\".\"customer_id\" = \"customers\".\"customer_id\" \
+             AND \"customers\".\"status\" = 'active'"
+        ), "{}", sql);
+
+        // Orders without an active customer are kept, unattributed
+        assert_eq!(
+            run_sqlite(&join_options_connection(), &planner, &revenue_by("customers", "name")),
+            vec!["NULL|5150", "Ann|50", "Cy|20"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_member_keeps_inner_join_lossless() {
+        use crate::model::{DimensionBuild, SurrogateKeyStrategy, UnknownMember};
+        use crate::sql::expr::{table_col, ExprExt};
+        use crate::sql::query::{Query, TableRef};
+
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("name", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_dimension(
+                DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+                    .with_columns(vec!["customer_id", "name"])
+                    .with_primary_key(vec!["customer_id"])
+                    .with_surrogate_key("customer_sk", SurrogateKeyStrategy::Identity)
+                    .with_unknown_member(UnknownMember::default()),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "orders_fact",
+                    "dim_customers",
+                    "customer_sk",
+                    "customer_sk",
+                    Cardinality::ManyToOne,
+                )
+                .optional(),
+            )
+            .with_fact(
+                FactDefinition::new("orders_fact", "orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount"),
+            );
+
+        // Order 2 has no customer and order 3 an unknown one
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE customers (customer_id INTEGER, name TEXT);
+             CREATE TABLE orders (order_id INTEGER, customer_id INTEGER, amount INTEGER);
+             INSERT INTO customers VALUES (1, 'Ann'), (2, 'Bob');
+             INSERT INTO orders VALUES (1, 1, 50), (2, NULL, 30), (3, 9, 20), (4, 2, 5);",
+        )
+        .unwrap();
+        let build = DimensionBuild::from_model(&model, "dim_customers").unwrap();
+        conn.execute_batch(&build.create_table(Dialect::Sqlite).unwrap().to_sql(Dialect::Sqlite))
+            .unwrap();
+        let (table, on) = build.fact_join("orders", &["customer_id"], "dim").unwrap();
+        let fact = Query::new()
+            .select(vec![
+                table_col("orders", "order_id").into(),
+                build.fact_key("dim").unwrap().alias("customer_sk"),
+                table_col("orders", "amount").into(),
+            ])
+            .from(TableRef::new("orders"))
+            .left_join(table, on);
+        conn.execute_batch(&format!(
+            "CREATE TABLE orders_fact AS {}",
+            fact.to_sql(Dialect::Sqlite)
+        ))
+        .unwrap();
+
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+        let query = revenue_by("dim_customers", "name");
+        let sql = planner.plan(&query).unwrap().to_sql(Dialect::DuckDb);
+        assert!(sql.contains("INNER JOIN \"main\".\"dim_customers\""), "{}", sql);
+
+        assert_eq!(
+            run_sqlite(&conn, &planner, &query),
+            vec!["Ann|50", "Bob|5", "Unknown|50"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_member_keeps_optional_natural_key_join() {
+        use crate::model::UnknownMember;
+
+        // Facts built without the unknown member lookup still hold NULL and orphan keys
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("customers", "customers")
+                    .with_required_column("customer_id", DataType::Int64)
+                    .with_required_column("name", DataType::String)
+                    .with_primary_key(vec!["customer_id"]),
+            )
+            .with_dimension(
+                DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+                    .with_columns(vec!["customer_id", "name"])
+                    .with_primary_key(vec!["customer_id"])
+                    .with_unknown_member(UnknownMember::default()),
+            )
+            .with_relationship(
+                Relationship::new(
+                    "orders_fact",
+                    "dim_customers",
+                    "customer_id",
+                    "customer_id",
+                    Cardinality::ManyToOne,
+                )
+                .optional(),
+            )
+            .with_fact(
+                FactDefinition::new("orders_fact", "orders_fact")
+                    .with_grain("orders", "order_id")
+                    .with_sum("revenue", "amount"),
+            );
+
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph);
+        let sql = planner
+            .plan(&revenue_by("dim_customers", "name"))
+            .unwrap()
+            .to_sql(Dialect::DuckDb);
+        assert!(sql.contains("LEFT JOIN \"dbo\".\"dim_customers\""), "{}", sql);
+    }
+
+    #[test]
+    fn test_range_relationship_joins_on_band() {
+        let graph = join_options_graph(Cardinality::ManyToOne);
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sql = planner.plan(&revenue_by("tiers", "tier")).unwrap().to_sql(Dialect::DuckDb);
+        assert!(sql.contains(
+            "INNER JOIN \"main\".\"tiers\" AS \"tiers\" \
+             ON \"orders_fact\".\"amount\" BETWEEN \"tiers\".\"min_amount\" AND \"tiers\".\"max_amount\""
+        ), "{}", sql);
+
+        // Each order lands in one tier; the order above every tier drops out
+        assert_eq!(
+            run_sqlite(&join_options_connection(), &planner, &revenue_by("tiers", "tier")),
+            vec!["medium|150", "small|70"]
+        );
+    }
+
+    #[test]
+    fn test_range_relationship_fanout_is_rejected() {
+        // Overlapping bands may match several tiers per order
+        let graph = join_options_graph(Cardinality::ManyToMany);
+        let result = QueryPlanner::new(&graph).plan(&revenue_by("tiers", "tier"));
+        assert!(matches!(
+            result,
+            Err(SemanticError::UnsafeJoinPath { message, .. }) if message.contains("may overlap")
+        ));
+
+        // Walking from the band side multiplies the banded rows
+        let graph = join_options_graph(Cardinality::ManyToOne);
+        let sq = SemanticQuery {
+            from: Some("tiers".into()),
+            group_by: vec![FieldRef::new("tiers", "tier")],
+            select: vec![SelectField::new("orders_fact", "revenue")],
+            ..Default::default()
+        };
+        let result = QueryPlanner::new(&graph).plan(&sq);
+        assert!(matches!(
+            result,
+            Err(SemanticError::UnsafeJoinPath { message, .. }) if message.contains("Each band in 'tiers'")
+        ));
+    }
+
+    #[test]
+    fn test_multi_fact_range_relationship() {
+        let graph = join_options_graph(Cardinality::ManyToOne);
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let sq = SemanticQuery {
+            group_by: vec![FieldRef::new("tiers", "tier")],
+            select: vec![
+                SelectField::new("orders_fact", "revenue"),
+                SelectField::new("returns_fact", "refunds"),
+            ],
+            ..Default::default()
+        };
+
+        // Each fact is banded before aggregation and matched on the band bounds
+        let sql = planner.plan(&sq).unwrap().to_sql(Dialect::DuckDb);
+        assert!(sql.contains("\"tiers\".\"min_amount\" AS \"tiers_min_amount\""), "{}", sql);
+        assert!(sql.contains(
+            "\"orders_fact_agg\".\"tiers_min_amount\" = \"returns_fact_agg\".\"tiers_min_amount\""
+        ));
+
+        let mut rows = run_sqlite(&join_options_connection(), &planner, &sq);
+        rows.sort();
+        assert_eq!(rows, vec!["medium|150|200", "small|70|30"]);
+    }
+
+    fn hierarchy_graph() -> ModelGraph {
+        let model = Model::new()
+            .with_source(
+                SourceEntity::new("stores", "stores")
+                    .with_required_column("store_id", DataType::Int64)
+                    .with_required_column("country", DataType::String)
+                    .with_required_column("region", DataType::String)
+                    .with_required_column("city", DataType::String)
+                    .with_primary_key(vec!["store_id"]),
+            )
+            .with_relationship(Relationship::new(
+                "sales_fact",
+                "stores",
+                "store_id",
+                "store_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_dimension(
+                DimensionDefinition::new("dim_stores", "dim_stores", "stores")
+                    .with_columns(vec!["store_id", "country", "region", "city"])
+                    .with_hierarchy("geography", vec!["country", "region", "city"]),
+            )
+            .with_fact(
+                FactDefinition::new("sales_fact", "sales_fact")
+                    .with_grain("sales", "sale_id")
+                    .with_sum("revenue", "amount"),
+            );
+
+        ModelGraph::from_model(model).unwrap()
+    }
+
+    fn hierarchy_connection() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sales_fact (sale_id INTEGER, store_id INTEGER, amount INTEGER);
+             CREATE TABLE stores (store_id INTEGER, country TEXT, region TEXT, city TEXT);
+             INSERT INTO sales_fact VALUES (1, 1, 10), (2, 2, 20), (3, 3, 40), (4, 1, 5);
+             INSERT INTO stores VALUES
+                 (1, 'FR', 'North', 'Lille'), (2, 'FR', 'South', 'Nice'), (3, 'US', 'West', 'LA');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn drilled(group_by: &[&str], drill: Vec<Drill>) -> SemanticQuery {
+        SemanticQuery {
+            from: Some("sales_fact".into()),
+            group_by: group_by.iter().map(|c| FieldRef::new("stores", c)).collect(),
+            select: vec![SelectField::new("sales_fact", "revenue")],
+            order_by: group_by.iter().map(|c| OrderField::asc("stores", c)).collect(),
+            drill,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_drill_down_adds_next_level() {
+        let graph = hierarchy_graph();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+        let conn = hierarchy_connection();
+
+        let top = drilled(&[], vec![Drill::down("stores", "geography")]);
+        assert_eq!(run_sqlite(&conn, &planner, &top), vec!["FR|35", "US|40"]);
+
+        let regions = drilled(&["country"], vec![Drill::down("stores", "geography")]);
+        assert_eq!(
+            run_sqlite(&conn, &planner, &regions),
+            vec!["FR|North|15", "FR|South|20", "US|West|40"]
+        );
+
+        // Drills apply in order, and work on the dimension's own name too
+        let resolver = Resolver::new(&graph);
+        let twice = drilled(&[], vec![Drill::down("stores", "geography"); 2]);
+        let fields: Vec<_> = resolver.apply_drills(&twice).unwrap().group_by;
+        assert_eq!(
+            fields.iter().map(|f| f.field.as_str()).collect::<Vec<_>>(),
+            vec!["country", "region"]
+        );
+        let on_dimension = SemanticQuery {
+            drill: vec![Drill::down("dim_stores", "geography")],
+            ..Default::default()
+        };
+        assert_eq!(resolver.apply_drills(&on_dimension).unwrap().group_by[0].field, "country");
+    }
+
+    #[test]
+    fn test_drill_up_removes_finest_level() {
+        let graph = hierarchy_graph();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let mut sq = drilled(&["country", "region", "city"], vec![Drill::up("stores", "geography")]);
+        sq.select.insert(0, SelectField::new("stores", "city"));
+        let drilled_up = Resolver::new(&graph).apply_drills(&sq).unwrap();
+        assert_eq!(drilled_up.group_by.len(), 2);
+        assert_eq!(drilled_up.select.len(), 1);
+        assert_eq!(drilled_up.order_by.len(), 2);
+        assert!(drilled_up.drill.is_empty());
+
+        assert_eq!(
+            run_sqlite(&hierarchy_connection(), &planner, &sq),
+            vec!["FR|North|15", "FR|South|20", "US|West|40"]
+        );
+    }
+
+    #[test]
+    fn test_drill_past_hierarchy_ends_is_rejected() {
+        let graph = hierarchy_graph();
+        let planner = QueryPlanner::new(&graph);
+
+        let unknown = drilled(&[], vec![Drill::down("stores", "calendar")]);
+        assert!(matches!(
+            planner.plan(&unknown),
+            Err(SemanticError::UnknownHierarchy { hierarchy, .. }) if hierarchy == "calendar"
+        ));
+
+        let leaf = drilled(&["city"], vec![Drill::down("stores", "geography")]);
+        let err = planner.plan(&leaf).unwrap_err();
+        assert!(err.to_string().contains("'city' is its finest level"), "{}", err);
+
+        let top = drilled(&[], vec![Drill::up("stores", "geography")]);
+        let err = planner.plan(&top).unwrap_err();
+        assert!(err.to_string().contains("no level of it is grouped"), "{}", err);
+    }
+
+    #[test]
+    fn test_parent_child_rolls_up_to_ancestors() {
+        use crate::model::parent_child::LEVELS_HIERARCHY;
+        use crate::model::ParentChildDimension;
+
+        let org = ParentChildDimension::new("org", "dim_org", "employees", "employee_id", "manager_id")
+            .with_max_depth(3);
+        let mut model = Model::new()
+            .with_relationship(Relationship::new(
+                "sales_fact",
+                "org",
+                "employee_id",
+                "employee_id",
+                Cardinality::ManyToOne,
+            ))
+            .with_fact(
+                FactDefinition::new("sales_fact", "sales_fact")
+                    .with_grain("sales", "sale_id")
+                    .with_sum("revenue", "amount"),
+            );
+        org.register(&mut model);
+        model.add_dimension(org.dimension("dim_org"));
+        let graph = ModelGraph::from_model(model).unwrap();
+        let planner = QueryPlanner::new(&graph).with_default_schema("main");
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE employees (employee_id INTEGER, manager_id INTEGER);
+             INSERT INTO employees VALUES (1, NULL), (2, 1), (3, 1), (4, 2), (5, NULL);
+             CREATE TABLE sales_fact (sale_id INTEGER, employee_id INTEGER, amount INTEGER);
+             INSERT INTO sales_fact VALUES (1, 1, 7), (2, 2, 10), (3, 3, 20), (4, 4, 5), (5, 5, 1);",
+        )
+        .unwrap();
+        conn.execute_batch(&org.create_table(Dialect::Sqlite).unwrap().to_sql(Dialect::Sqlite))
+            .unwrap();
+
+        let by_level = |level: &str| SemanticQuery {
+            from: Some("sales_fact".into()),
+            group_by: vec![FieldRef::new("org", level)],
+            select: vec![SelectField::new("sales_fact", "revenue")],
+            order_by: vec![OrderField::asc("org", level)],
+            ..Default::default()
+        };
+        assert_eq!(run_sqlite(&conn, &planner, &by_level("level_1")), vec!["1|42", "5|1"]);
+        // Ancestors above level 2 keep their own sales under themselves
+        assert_eq!(
+            run_sqlite(&conn, &planner, &by_level("level_2")),
+            vec!["1|7", "2|15", "3|20", "5|1"]
+        );
+
+        let mut drilled = by_level("level_1");
+        drilled.drill = vec![Drill::down("org", LEVELS_HIERARCHY)];
+        assert_eq!(
+            run_sqlite(&conn, &planner, &drilled),
+            vec!["1|1|7", "1|2|15", "1|3|20", "5|5|1"]
+        );
+    }
 }
@@ -7,16 +7,17 @@
 //! # How it works
 //!
 //! 1. Collect all columns referenced in the query (SELECT, WHERE, GROUP BY, ORDER BY)
-//! 2. Add join key columns (needed for JOINs even if not in SELECT)
+//! 2. Add join key and SCD version columns (needed for JOINs even if not in SELECT)
 //! 3. Expand computed columns to their source dependencies using lineage graph
 //! 4. Return the minimal set of source columns needed
 
 use std::collections::HashSet;
 
-use crate::semantic::column_lineage::{ColumnLineageGraph, ColumnRef};
+use crate::semantic::column_lineage::{extract_column_refs, ColumnLineageGraph, ColumnRef};
 
 use super::resolved::{
-    ResolvedColumn, ResolvedJoinTree, ResolvedOrderExpr, ResolvedQuery, ResolvedSelect,
+    AsOfPoint, BridgeStrategy, ResolvedColumn, ResolvedJoinTree, ResolvedOrderExpr, ResolvedQuery,
+    ResolvedSelect,
 };
 use super::validate::ValidatedQuery;
 
@@ -52,6 +53,7 @@ impl<'a> ColumnPruner<'a> {
 
         // 5. Columns from JOIN keys
         self.collect_join_columns(&query.join_tree, &mut target_columns);
+        self.collect_as_of_columns(&query.query, &mut target_columns);
 
         // 6. Expand to source columns using lineage
         self.expand_to_source_columns(target_columns)
@@ -121,10 +123,49 @@ impl<'a> ColumnPruner<'a> {
     /// Collect columns used as join keys.
     fn collect_join_columns(&self, join_tree: &ResolvedJoinTree, columns: &mut HashSet<ColumnRef>) {
         for edge in &join_tree.edges {
-            // From side join key
-            columns.insert(ColumnRef::new(&edge.from_entity, &edge.from_column));
-            // To side join key
-            columns.insert(ColumnRef::new(&edge.to_entity, &edge.to_column));
+            for (from_column, to_column) in edge.column_pairs() {
+                // From side join key
+                columns.insert(ColumnRef::new(&edge.from_entity, from_column));
+                // To side join key
+                columns.insert(ColumnRef::new(&edge.to_entity, to_column));
+            }
+            if let Some(range) = &edge.range {
+                let (value_entity, bounds_entity) = range.sides(&edge.from_entity, &edge.to_entity);
+                columns.insert(ColumnRef::new(value_entity, &range.value_column));
+                columns.insert(ColumnRef::new(bounds_entity, &range.lower_column));
+                columns.insert(ColumnRef::new(bounds_entity, &range.upper_column));
+            }
+            for predicate in &edge.predicates {
+                columns.extend(extract_column_refs(predicate, None));
+            }
+        }
+
+        // Bridge strategies read the weight or the root key
+        if let Some(bridge) = &join_tree.bridge {
+            match &bridge.strategy {
+                BridgeStrategy::WeightedAllocation { weight_column } => {
+                    columns.insert(ColumnRef::new(&bridge.entity, weight_column));
+                }
+                BridgeStrategy::DistinctKey { keys } => {
+                    for key in keys {
+                        columns.insert(ColumnRef::new(&join_tree.root, key));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collect columns used to pick SCD Type 2 versions.
+    fn collect_as_of_columns(&self, query: &ResolvedQuery, columns: &mut HashSet<ColumnRef>) {
+        for as_of in &query.as_of {
+            columns.insert(ColumnRef::new(&as_of.dimension, &as_of.effective_from));
+            columns.insert(ColumnRef::new(&as_of.dimension, &as_of.effective_to));
+            match &as_of.point {
+                AsOfPoint::Column(column) | AsOfPoint::Lookup { key: column, .. } => {
+                    columns.insert(self.resolved_to_ref(column));
+                }
+                AsOfPoint::Date(_) | AsOfPoint::Current => {}
+            }
         }
     }
 
@@ -272,6 +313,7 @@ mod tests {
                 }],
                 order_by: vec![],
                 limit: None,
+                as_of: vec![],
             },
             join_tree: ResolvedJoinTree::empty("orders_fact"),
             entity_info: HashMap::new(),
@@ -349,7 +391,11 @@ mod tests {
             to_entity: "customers".into(),
             from_column: "customer_id".into(),
             to_column: "customer_id".into(),
+            additional_columns: vec![],
             cardinality: Cardinality::ManyToOne,
+            optional: false,
+            predicates: vec![],
+            range: None,
         }];
 
         let required = pruner.required_columns(&query);
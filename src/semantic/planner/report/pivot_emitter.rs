@@ -2,10 +2,10 @@
 //!
 //! Generates dialect-specific SQL for pivot operations.
 
-use crate::dialect::Dialect;
+use crate::dialect::{Dialect, DialectTarget};
 use crate::semantic::error::{PlanError, PlanResult};
 
-use super::pivot_planner::{PivotColumnValues, PivotPlan};
+use super::pivot_planner::{PivotColumnValues, PivotDimension, PivotPlan};
 
 /// Emitter for Pivot SQL generation.
 ///
@@ -37,8 +37,8 @@ impl PivotEmitter {
     ///
     /// Returns raw SQL string because pivot syntax varies significantly
     /// between dialects and doesn't fit well into the Query builder.
-    pub fn emit(&self, plan: &PivotPlan, dialect: Dialect) -> PlanResult<String> {
-        match dialect {
+    pub fn emit(&self, plan: &PivotPlan, dialect: impl Into<DialectTarget>) -> PlanResult<String> {
+        match dialect.into().dialect {
             Dialect::DuckDb => self.emit_duckdb(plan),
             Dialect::Postgres => self.emit_postgres(plan),
             Dialect::TSql => self.emit_tsql(plan),
@@ -47,6 +47,9 @@ impl PivotEmitter {
             Dialect::Databricks => self.emit_duckdb(plan), // Databricks has native PIVOT similar to DuckDB
             Dialect::BigQuery => self.emit_postgres(plan), // BigQuery uses conditional aggregation
             Dialect::Redshift => self.emit_postgres(plan), // Redshift uses conditional aggregation
+            Dialect::Sqlite => self.emit_postgres(plan), // SQLite uses conditional aggregation
+            Dialect::ClickHouse => self.emit_postgres(plan), // ClickHouse uses conditional aggregation
+            Dialect::Trino => self.emit_postgres(plan), // Trino uses conditional aggregation
         }
     }
 
@@ -111,6 +114,12 @@ impl PivotEmitter {
             sql.push_str(&group_cols.join(", "));
         }
 
+        // ORDER BY nested hierarchy levels
+        let order = order_terms(plan, |d| format!("{}.{}", d.entity, d.column), None);
+        if !order.is_empty() {
+            sql.push_str(&format!("\nORDER BY {}", order.join(", ")));
+        }
+
         Ok(sql)
     }
 
@@ -184,12 +193,16 @@ impl PivotEmitter {
         }
 
         // ORDER BY
-        if let Some(sort) = &plan.sort {
-            sql.push_str(&format!(
-                "\nORDER BY Total_{} {}",
+        let sort = plan.sort.as_ref().map(|sort| {
+            format!(
+                "Total_{} {}",
                 sort.by_measure,
                 if sort.descending { "DESC" } else { "ASC" }
-            ));
+            )
+        });
+        let order = order_terms(plan, |d| format!("{}.{}", d.entity, d.column), sort);
+        if !order.is_empty() {
+            sql.push_str(&format!("\nORDER BY {}", order.join(", ")));
         }
 
         Ok(sql)
@@ -264,14 +277,46 @@ impl PivotEmitter {
         }
 
         // ORDER BY
-        if let Some(sort) = &plan.sort {
-            sql.push_str(&format!(
-                "\nORDER BY {} {}",
+        let sort = plan.sort.as_ref().map(|sort| {
+            format!(
+                "{} {}",
                 sort.by_measure,
                 if sort.descending { "DESC" } else { "ASC" }
-            ));
+            )
+        });
+        let order = order_terms(plan, |d| d.column.clone(), sort);
+        if !order.is_empty() {
+            sql.push_str(&format!("\nORDER BY {}", order.join(", ")));
         }
 
         Ok(sql)
     }
 }
+
+/// ORDER BY terms for a pivot.
+///
+/// Rows are ordered down to the last one a finer hierarchy level nests
+/// under, so children and subtotals stay with their parent; `sort` then
+/// orders siblings. Without a sort, nested rows are ordered by every row
+/// dimension.
+fn order_terms(
+    plan: &PivotPlan,
+    name: impl Fn(&PivotDimension) -> String,
+    sort: Option<String>,
+) -> Vec<String> {
+    let rows = &plan.row_dimensions;
+    let last_parent = plan
+        .parent_rows()
+        .filter_map(|parent| rows.iter().position(|row| std::ptr::eq(row, parent)))
+        .max();
+
+    let Some(last_parent) = last_parent else {
+        return sort.into_iter().collect();
+    };
+    let mut terms: Vec<String> = rows[..=last_parent].iter().map(&name).collect();
+    match sort {
+        Some(sort) => terms.push(sort),
+        None => terms.extend(rows[last_parent + 1..].iter().map(&name)),
+    }
+    terms
+}
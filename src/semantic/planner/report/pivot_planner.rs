@@ -51,6 +51,24 @@ pub struct PivotDimension {
     pub column: String,
     /// Physical table (for joins).
     pub physical_table: Option<String>,
+    /// Hierarchy the column is a level of, if any.
+    pub hierarchy: Option<String>,
+}
+
+impl PivotPlan {
+    /// Row dimensions that a finer level of the same hierarchy nests under.
+    ///
+    /// Sorting on these first keeps each parent's children, and the parent's
+    /// subtotal, together.
+    pub fn parent_rows(&self) -> impl Iterator<Item = &PivotDimension> {
+        self.row_dimensions.iter().enumerate().filter_map(|(i, dim)| {
+            let hierarchy = dim.hierarchy.as_ref()?;
+            self.row_dimensions[i + 1..]
+                .iter()
+                .any(|later| later.entity == dim.entity && later.hierarchy.as_ref() == Some(hierarchy))
+                .then_some(dim)
+        })
+    }
 }
 
 /// Pivot column values.
@@ -108,12 +126,12 @@ impl<'a> PivotPlanner<'a> {
             PlanError::UnknownEntity(source_fact.clone())
         })?;
 
-        // Parse row dimensions
-        let row_dimensions: Vec<PivotDimension> = pivot
-            .rows
-            .iter()
-            .map(|r| self.parse_dimension(r))
-            .collect::<Result<_, _>>()?;
+        // Parse row dimensions, expanding hierarchies into their levels
+        let mut rows = Vec::new();
+        for row in &pivot.rows {
+            rows.extend(self.parse_row(row)?);
+        }
+        let row_dimensions = nest_hierarchy_levels(rows, self.model);
 
         // Parse column dimension
         let (column_dimension, column_values) = self.parse_column_dimension(&pivot.columns)?;
@@ -169,13 +187,42 @@ impl<'a> PivotPlanner<'a> {
             ))
         })?;
 
+        let hierarchy = self
+            .model
+            .hierarchies_on(entity)
+            .into_iter()
+            .find(|(_, levels)| levels.iter().any(|l| l == column))
+            .map(|(h, _)| h.name.clone());
+
         Ok(PivotDimension {
             entity: entity.to_string(),
             column: column.to_string(),
             physical_table: self.get_physical_table(entity),
+            hierarchy,
         })
     }
 
+    /// Parse a row reference: `entity.column`, or `entity.hierarchy` for all
+    /// of a hierarchy's levels.
+    fn parse_row(&self, row_ref: &str) -> PlanResult<Vec<PivotDimension>> {
+        let levels = row_ref
+            .split_once('.')
+            .and_then(|(entity, name)| Some((entity, name, self.model.hierarchy_levels(entity, name)?)));
+
+        match levels {
+            Some((entity, name, levels)) => Ok(levels
+                .into_iter()
+                .map(|column| PivotDimension {
+                    entity: entity.to_string(),
+                    column,
+                    physical_table: self.get_physical_table(entity),
+                    hierarchy: Some(name.to_string()),
+                })
+                .collect()),
+            None => Ok(vec![self.parse_dimension(row_ref)?]),
+        }
+    }
+
     fn parse_column_dimension(
         &self,
         columns: &PivotColumns,
@@ -236,3 +283,40 @@ impl<'a> PivotPlanner<'a> {
         None
     }
 }
+
+/// Reorder rows so the levels of each hierarchy sit together, coarsest
+/// first, at the position of the first of them. Other rows keep their order
+/// and duplicate levels are dropped.
+fn nest_hierarchy_levels(rows: Vec<PivotDimension>, model: &Model) -> Vec<PivotDimension> {
+    let level_index = |dim: &PivotDimension| {
+        let hierarchy = dim.hierarchy.as_deref()?;
+        let levels = model.hierarchy_levels(&dim.entity, hierarchy)?;
+        levels.iter().position(|l| *l == dim.column)
+    };
+    let same_hierarchy = |a: &PivotDimension, b: &PivotDimension| {
+        a.hierarchy.is_some() && a.entity == b.entity && a.hierarchy == b.hierarchy
+    };
+
+    let mut nested: Vec<PivotDimension> = Vec::with_capacity(rows.len());
+    for dim in &rows {
+        if nested.iter().any(|n| n.entity == dim.entity && n.column == dim.column) {
+            continue;
+        }
+        if dim.hierarchy.is_none() {
+            nested.push(dim.clone());
+            continue;
+        }
+        if nested.iter().any(|n| same_hierarchy(n, dim)) {
+            continue;
+        }
+        let mut levels: Vec<&PivotDimension> =
+            rows.iter().filter(|r| same_hierarchy(r, dim)).collect();
+        levels.sort_by_key(|r| level_index(r));
+        for level in levels {
+            if !nested.iter().any(|n| n.entity == level.entity && n.column == level.column) {
+                nested.push(level.clone());
+            }
+        }
+    }
+    nested
+}
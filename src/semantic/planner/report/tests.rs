@@ -5,7 +5,8 @@ use std::collections::HashMap;
 use crate::dialect::Dialect;
 use crate::expr::Expr;
 use crate::model::{
-    Cardinality, DataType, FactDefinition, Model, PivotReport, Relationship, Report, SourceEntity,
+    Cardinality, DataType, DimensionDefinition, FactDefinition, Model, PivotReport, PivotSort,
+    Relationship, Report, SourceEntity,
 };
 use crate::semantic::error::PlanError;
 use crate::semantic::model_graph::ModelGraph;
@@ -480,6 +481,84 @@ fn test_pivot_emit_duckdb() {
     assert!(sql.contains("USING"));
 }
 
+fn hierarchy_pivot_model() -> Model {
+    sample_pivot_model().with_dimension(
+        DimensionDefinition::new("dim_customers", "dim_customers", "customers")
+            .with_columns(vec!["customer_id", "segment", "country", "region", "city"])
+            .with_hierarchy("geography", vec!["country", "region", "city"]),
+    )
+}
+
+#[test]
+fn test_pivot_rows_follow_hierarchy() {
+    let model = hierarchy_pivot_model();
+    let graph = ModelGraph::from_model(model.clone()).unwrap();
+    let planner = PivotPlanner::new(&model, &graph);
+    let rows = |pivot: PivotReport| -> Vec<(String, Option<String>)> {
+        let pivot = pivot
+            .with_columns("time.quarter")
+            .with_value("revenue", "orders_fact", "revenue");
+        planner
+            .plan(&pivot)
+            .unwrap()
+            .row_dimensions
+            .into_iter()
+            .map(|d| (d.column, d.hierarchy))
+            .collect()
+    };
+    let geo = |column: &str| (column.to_string(), Some("geography".to_string()));
+
+    // A hierarchy row expands to all of its levels
+    assert_eq!(
+        rows(PivotReport::new("p").with_row("customers.segment").with_row("customers.geography")),
+        vec![("segment".into(), None), geo("country"), geo("region"), geo("city")]
+    );
+
+    // Levels listed out of order nest coarsest first
+    assert_eq!(
+        rows(
+            PivotReport::new("p")
+                .with_row("customers.city")
+                .with_row("customers.segment")
+                .with_row("customers.country")
+        ),
+        vec![geo("country"), geo("city"), ("segment".into(), None)]
+    );
+}
+
+#[test]
+fn test_pivot_orders_rows_by_hierarchy() {
+    let model = hierarchy_pivot_model();
+    let graph = ModelGraph::from_model(model.clone()).unwrap();
+    let planner = PivotPlanner::new(&model, &graph);
+
+    let pivot = PivotReport::new("revenue_by_geography")
+        .with_row("customers.segment")
+        .with_row("customers.geography")
+        .with_columns_explicit("time.quarter", vec!["Q1".into(), "Q2".into()])
+        .with_value("revenue", "orders_fact", "revenue");
+    let plan = planner.plan(&pivot).unwrap();
+    let parents: Vec<_> = plan.parent_rows().map(|d| d.column.as_str()).collect();
+    assert_eq!(parents, vec!["country", "region"]);
+
+    let emitter = PivotEmitter::new();
+    let sql = emitter.emit(&plan, Dialect::DuckDb).unwrap();
+    assert!(sql.ends_with(
+        "ORDER BY customers.segment, customers.country, customers.region, customers.city"
+    ));
+
+    // Siblings are sorted within their parent
+    let sorted = planner
+        .plan(&pivot.with_sort(PivotSort::desc("revenue")))
+        .unwrap();
+    let sql = emitter.emit(&sorted, Dialect::Postgres).unwrap();
+    assert!(sql.ends_with(
+        "ORDER BY customers.segment, customers.country, customers.region, Total_revenue DESC"
+    ));
+    let sql = emitter.emit(&sorted, Dialect::TSql).unwrap();
+    assert!(sql.ends_with("ORDER BY segment, country, region, revenue DESC"));
+}
+
 #[test]
 fn test_pivot_emit_postgres_requires_explicit_columns() {
     let emitter = PivotEmitter::new();
@@ -490,11 +569,13 @@ fn test_pivot_emit_postgres_requires_explicit_columns() {
             entity: "customers".to_string(),
             column: "region".to_string(),
             physical_table: None,
+            hierarchy: None,
         }],
         column_dimension: PivotDimension {
             entity: "time".to_string(),
             column: "quarter".to_string(),
             physical_table: None,
+            hierarchy: None,
         },
         column_values: PivotColumnValues::Dynamic, // Dynamic won't work for Postgres
         value_measures: vec![PivotMeasure {
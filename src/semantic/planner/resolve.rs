@@ -13,11 +13,13 @@ use crate::semantic::error::{PlanError, PlanResult, SemanticError};
 use crate::semantic::model_graph::{EntityType, ModelGraph, ModelResolvedField};
 
 use super::resolved::{
-    FactAggregate, FactJoinKey, MultiFactQuery, ResolvedColumn, ResolvedDerivedExpr,
-    ResolvedEntity, ResolvedFilter, ResolvedMeasure, ResolvedOrder, ResolvedOrderExpr,
-    ResolvedQuery, ResolvedQueryPlan, ResolvedSelect, SharedDimension,
+    AsOfPoint, FactAggregate, FactJoinKey, MultiFactQuery, ResolvedAsOf, ResolvedColumn,
+    ResolvedDerivedExpr, ResolvedEntity, ResolvedFilter, ResolvedMeasure, ResolvedOrder,
+    ResolvedOrderExpr, ResolvedQuery, ResolvedQueryPlan, ResolvedSelect, ResolvedTopN,
+    SharedDimension,
 };
-use super::types::{FieldRef, SemanticQuery};
+use super::types::{AsOf, DrillDirection, FieldRef, SemanticQuery, TopN};
+use crate::model::{AggregationType, DataType};
 
 /// Resolver - handles Phase 1 of query planning.
 pub struct Resolver<'a> {
@@ -74,6 +76,9 @@ impl<'a> Resolver<'a> {
         // Resolve order by
         let order_by = self.resolve_order_by(&query.order_by)?;
 
+        // Pick a version of every SCD Type 2 dimension that gets joined
+        let as_of = self.resolve_as_of(&query.as_of, &from_entity, &referenced_entities)?;
+
         Ok(ResolvedQuery {
             from,
             referenced_entities,
@@ -82,9 +87,193 @@ impl<'a> Resolver<'a> {
             select,
             order_by,
             limit: query.limit,
+            as_of,
         })
     }
 
+    /// Rewrite a query's GROUP BY by applying its drills in order.
+    ///
+    /// Drilling down adds the level below the finest grouped level of the
+    /// hierarchy, or its top level if none is grouped. Drilling up removes
+    /// the finest grouped level, along with any plain select or order by
+    /// entries on it.
+    pub fn apply_drills(&self, query: &SemanticQuery) -> PlanResult<SemanticQuery> {
+        let mut query = query.clone();
+        for drill in std::mem::take(&mut query.drill) {
+            let levels = self
+                .graph
+                .model()
+                .hierarchy_levels(&drill.entity, &drill.hierarchy)
+                .ok_or_else(|| SemanticError::UnknownHierarchy {
+                    entity: drill.entity.clone(),
+                    hierarchy: drill.hierarchy.clone(),
+                })?;
+            let is_level = |field: &FieldRef, level: &str| {
+                field.entity == drill.entity && field.field == level
+            };
+            let finest = levels
+                .iter()
+                .rposition(|level| query.group_by.iter().any(|f| is_level(f, level)));
+
+            match (drill.direction, finest) {
+                (DrillDirection::Down, None) => {
+                    query.group_by.push(FieldRef::new(&drill.entity, &levels[0]));
+                }
+                (DrillDirection::Down, Some(i)) => {
+                    let child = levels.get(i + 1).ok_or_else(|| {
+                        SemanticError::QueryPlanError(format!(
+                            "Cannot drill down '{}' on '{}': '{}' is its finest level",
+                            drill.hierarchy, drill.entity, levels[i]
+                        ))
+                    })?;
+                    let at = query
+                        .group_by
+                        .iter()
+                        .rposition(|f| is_level(f, &levels[i]))
+                        .map_or(query.group_by.len(), |p| p + 1);
+                    query.group_by.insert(at, FieldRef::new(&drill.entity, child));
+                }
+                (DrillDirection::Up, None) => {
+                    return Err(SemanticError::QueryPlanError(format!(
+                        "Cannot drill up '{}' on '{}': no level of it is grouped",
+                        drill.hierarchy, drill.entity
+                    )));
+                }
+                (DrillDirection::Up, Some(i)) => {
+                    let level = &levels[i];
+                    query.group_by.retain(|f| !is_level(f, level));
+                    query
+                        .select
+                        .retain(|s| s.is_aggregate() || !is_level(&s.field, level));
+                    query.order_by.retain(|o| !is_level(&o.field, level));
+                }
+            }
+        }
+        Ok(query)
+    }
+
+    /// Resolve version predicates for the SCD Type 2 dimensions a query joins.
+    ///
+    /// Covers dimensions joined along the way to a referenced entity, not
+    /// just the referenced ones. A dimension used as the anchor itself is not
+    /// joined and gets no predicate.
+    fn resolve_as_of(
+        &self,
+        mode: &AsOf,
+        from_entity: &str,
+        referenced_entities: &HashSet<String>,
+    ) -> PlanResult<Vec<ResolvedAsOf>> {
+        let model = self.graph.model();
+        if model
+            .dimensions
+            .values()
+            .all(|d| d.scd_type.effective_range().is_none())
+        {
+            return Ok(vec![]);
+        }
+
+        // Entities on the join tree; path errors are reported by validation
+        let mut joined: HashSet<String> = referenced_entities.clone();
+        let targets: Vec<&str> = referenced_entities
+            .iter()
+            .filter(|e| *e != from_entity)
+            .map(|s| s.as_str())
+            .collect();
+        if let Ok(tree) = self.graph.find_join_tree(from_entity, &targets) {
+            joined.extend(tree.edges.into_iter().map(|e| e.to_entity));
+        }
+        joined.remove(from_entity);
+
+        let mut versioned: Vec<(&str, &str, &str)> = joined
+            .iter()
+            .filter_map(|name| {
+                let dimension = self.graph.get_dimension(name)?;
+                let (from, to) = dimension.scd_type.effective_range()?;
+                Some((name.as_str(), from, to))
+            })
+            .collect();
+        versioned.sort();
+
+        let mut resolved = Vec::new();
+        for (dimension, effective_from, effective_to) in versioned {
+            let point = match mode {
+                AsOf::Current => AsOfPoint::Current,
+                AsOf::Date(date) => AsOfPoint::Date(date.clone()),
+                AsOf::FactDate { role } => {
+                    self.resolve_date_role(from_entity, role.as_deref(), dimension)?
+                }
+            };
+            resolved.push(ResolvedAsOf {
+                dimension: dimension.to_string(),
+                effective_from: effective_from.to_string(),
+                effective_to: effective_to.to_string(),
+                point,
+            });
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolve the fact date used to pick a version of `dimension`.
+    ///
+    /// A role joining its date dimension on the date itself uses the fact's
+    /// key column. A role joining on a surrogate key looks the date up in the
+    /// dimension's declared date column instead.
+    fn resolve_date_role(
+        &self,
+        from_entity: &str,
+        role: Option<&str>,
+        dimension: &str,
+    ) -> PlanResult<AsOfPoint> {
+        let date_config = self
+            .graph
+            .get_fact(from_entity)
+            .and_then(|fact| fact.date_config.as_ref());
+
+        let found = match (date_config, role) {
+            (Some(config), Some(name)) => config.get_role(name),
+            (Some(config), None) => config.get_primary_role(),
+            (None, _) => None,
+        };
+
+        let Some(role) = found else {
+            return Err(PlanError::QueryPlanError(format!(
+                "Dimension '{}' keeps SCD Type 2 history, but '{}' has no {} to pick a version by. \
+                 Query the current version or the version as of a fixed date instead.",
+                dimension,
+                from_entity,
+                role.map_or_else(|| "date role".to_string(), |r| format!("date role '{}'", r)),
+            )));
+        };
+
+        let key = ResolvedColumn {
+            entity_alias: from_entity.to_string(),
+            logical_name: role.fk_column.clone(),
+            physical_name: role.fk_column.clone(),
+        };
+
+        if let Some(date_column) = &role.date_column {
+            return Ok(AsOfPoint::Lookup {
+                dimension: self.resolve_entity(&role.dimension)?,
+                key_column: role.pk_column.clone(),
+                date_column: date_column.clone(),
+                key,
+            });
+        }
+
+        match self.graph.get_column_type(&role.dimension, &role.pk_column) {
+            Ok(DataType::Date | DataType::Timestamp | DataType::TimestampTz) => {
+                Ok(AsOfPoint::Column(key))
+            }
+            _ => Err(PlanError::QueryPlanError(format!(
+                "Date role '{}' of '{}' joins '{}' on '{}', which is not known to be a date, \
+                 so it cannot pick a version of '{}'. Declare the calendar date column of '{}' \
+                 on the role.",
+                role.name, from_entity, role.dimension, role.pk_column, dimension, role.dimension,
+            ))),
+        }
+    }
+
     /// Detect anchor facts from measures in the query.
     ///
     /// Returns the set of fact entities that contain the measures referenced
@@ -152,6 +341,15 @@ impl<'a> Resolver<'a> {
         query: &SemanticQuery,
         anchors: &[String],
     ) -> PlanResult<MultiFactQuery> {
+        // Each fact aggregates in its own CTE; nothing combines their measures
+        if let Some(derived) = query.derived.first() {
+            return Err(PlanError::QueryPlanError(format!(
+                "Derived field '{}' combines measures, which a query across several facts \
+                 does not support. Query each fact separately instead.",
+                derived.alias
+            )));
+        }
+
         // 1. Collect dimensions from query (non-measures in select + group_by)
         let dimension_refs = self.collect_dimension_refs(query)?;
 
@@ -167,15 +365,63 @@ impl<'a> Resolver<'a> {
         // 5. Resolve order by
         let order_by = self.resolve_order_by(&query.order_by)?;
 
+        // 6. Pick a version of every shared SCD Type 2 dimension
+        let as_of = self.resolve_multi_fact_as_of(&query.as_of, &shared_dimensions)?;
+
         Ok(MultiFactQuery {
             fact_aggregates,
             shared_dimensions,
             global_filters,
             order_by,
             limit: query.limit,
+            as_of,
         })
     }
 
+    /// Resolve version predicates for the shared SCD Type 2 dimensions of a
+    /// multi-fact query.
+    ///
+    /// Shared dimensions are joined to the facts' aggregates, where fact
+    /// dates are gone, so only the current version or the version as of a
+    /// fixed date can be picked.
+    fn resolve_multi_fact_as_of(
+        &self,
+        mode: &AsOf,
+        shared_dimensions: &[SharedDimension],
+    ) -> PlanResult<Vec<ResolvedAsOf>> {
+        let mut resolved = Vec::new();
+        for shared in shared_dimensions {
+            let dimension = &shared.dimension.name;
+            let Some((effective_from, effective_to)) = self
+                .graph
+                .get_dimension(dimension)
+                .and_then(|d| d.scd_type.effective_range())
+            else {
+                continue;
+            };
+            let point = match mode {
+                AsOf::Current => AsOfPoint::Current,
+                AsOf::Date(date) => AsOfPoint::Date(date.clone()),
+                AsOf::FactDate { .. } => {
+                    return Err(PlanError::QueryPlanError(format!(
+                        "Dimension '{}' keeps SCD Type 2 history, but a query across several \
+                         facts joins it to their aggregates, where there is no fact date to \
+                         pick a version by. Query the current version or the version as of a \
+                         fixed date instead.",
+                        dimension
+                    )));
+                }
+            };
+            resolved.push(ResolvedAsOf {
+                dimension: dimension.clone(),
+                effective_from: effective_from.to_string(),
+                effective_to: effective_to.to_string(),
+                point,
+            });
+        }
+        Ok(resolved)
+    }
+
     /// Collect dimension references from query (non-measures).
     fn collect_dimension_refs(&self, query: &SemanticQuery) -> PlanResult<Vec<FieldRef>> {
         let mut dims = Vec::new();
@@ -235,22 +481,21 @@ impl<'a> Resolver<'a> {
                     paths.push((
                         anchor.clone(),
                         FactJoinKey {
-                            fact_column: last_edge.from_column.clone(),
-                            dimension: dim_entity.clone(),
-                            dimension_column: last_edge.to_column.clone(),
+                            additional_columns: last_edge.additional_columns.clone(),
+                            range: last_edge.range.clone(),
+                            predicates: last_edge.predicates.clone(),
+                            optional: last_edge.optional,
+                            ..FactJoinKey::new(
+                                &last_edge.from_column,
+                                &dim_entity,
+                                &last_edge.to_column,
+                            )
                         },
                     ));
                 } else if anchor == &dim_entity {
                     // Direct - dimension is the anchor itself
                     // This is unusual but possible
-                    paths.push((
-                        anchor.clone(),
-                        FactJoinKey {
-                            fact_column: String::new(),
-                            dimension: dim_entity.clone(),
-                            dimension_column: String::new(),
-                        },
-                    ));
+                    paths.push((anchor.clone(), FactJoinKey::new("", &dim_entity, "")));
                 } else {
                     // No path found
                     return Err(SemanticError::DimensionNotShared {
@@ -581,6 +826,130 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Resolve a top-N clause against the output columns of the aggregated query.
+    ///
+    /// `outputs` is the final SELECT list (dimensions and measures) of the
+    /// plan the top-N wraps. Partition fields must be output dimensions and
+    /// the ranking field must be an output measure. When a remainder bucket
+    /// is requested, every output measure must be re-aggregatable; derived
+    /// fields are recomputed from the measures when the bucket is emitted.
+    pub fn resolve_top_n(
+        &self,
+        top_n: &TopN,
+        outputs: &[ResolvedSelect],
+    ) -> PlanResult<ResolvedTopN> {
+        if top_n.n == 0 {
+            return Err(PlanError::InvalidReference(
+                "Top-N requires n greater than zero".into(),
+            ));
+        }
+
+        let mut partition_by = Vec::with_capacity(top_n.partition_by.len());
+        for field in &top_n.partition_by {
+            let column = self.resolve_column(field)?;
+            let output = outputs
+                .iter()
+                .find(|o| match o {
+                    ResolvedSelect::Column { column: c, .. } => {
+                        c.entity_alias == column.entity_alias
+                            && c.physical_name == column.physical_name
+                    }
+                    _ => false,
+                })
+                .ok_or_else(|| {
+                    PlanError::InvalidReference(format!(
+                        "Top-N partition field {}.{} must be grouped by or selected",
+                        field.entity, field.field
+                    ))
+                })?;
+            partition_by.push(output.output_alias().to_string());
+        }
+
+        let rank_output = match self.resolve_field(&top_n.rank_by)? {
+            ResolvedFieldKind::Measure(measure) => outputs.iter().find(|o| match o {
+                ResolvedSelect::Measure { measure: m, .. } => {
+                    m.entity_alias == measure.entity_alias && m.name == measure.name
+                }
+                _ => false,
+            }),
+            ResolvedFieldKind::Column(column) => outputs.iter().find(|o| match o {
+                ResolvedSelect::Aggregate { column: c, .. } => {
+                    c.entity_alias == column.entity_alias
+                        && c.physical_name == column.physical_name
+                }
+                _ => false,
+            }),
+        };
+        let rank_by = rank_output
+            .ok_or_else(|| {
+                PlanError::InvalidReference(format!(
+                    "Top-N ranking field {}.{} must be a selected measure",
+                    top_n.rank_by.entity, top_n.rank_by.field
+                ))
+            })?
+            .output_alias()
+            .to_string();
+
+        let mut bucketed = Vec::new();
+        let mut measures = Vec::new();
+        let mut derived = Vec::new();
+        for output in outputs {
+            let alias = output.output_alias().to_string();
+            let aggregation = match output {
+                ResolvedSelect::Column { .. } => {
+                    if !partition_by.contains(&alias) && !bucketed.contains(&alias) {
+                        bucketed.push(alias);
+                    }
+                    continue;
+                }
+                ResolvedSelect::Measure { measure, .. } => Some(measure.aggregation),
+                ResolvedSelect::Aggregate { aggregation, .. } => {
+                    match aggregation.to_uppercase().as_str() {
+                        "SUM" => Some(AggregationType::Sum),
+                        "COUNT" => Some(AggregationType::Count),
+                        "MIN" => Some(AggregationType::Min),
+                        "MAX" => Some(AggregationType::Max),
+                        _ => None,
+                    }
+                }
+                ResolvedSelect::Derived { .. } => {
+                    derived.push(alias);
+                    continue;
+                }
+            };
+
+            // Counts add up across rows; averages and distinct counts don't.
+            let rollup = match aggregation {
+                Some(AggregationType::Sum | AggregationType::Count) => Some(AggregationType::Sum),
+                Some(AggregationType::Min) => Some(AggregationType::Min),
+                Some(AggregationType::Max) => Some(AggregationType::Max),
+                _ => None,
+            };
+
+            match rollup {
+                Some(rollup) => measures.push((alias, rollup)),
+                None if top_n.other_label.is_some() => {
+                    return Err(PlanError::InvalidReference(format!(
+                        "Cannot bucket '{}' into a top-N remainder: it is not additive",
+                        alias
+                    )));
+                }
+                None => {}
+            }
+        }
+
+        Ok(ResolvedTopN {
+            n: top_n.n,
+            partition_by,
+            rank_by,
+            descending: top_n.descending,
+            other_label: top_n.other_label.clone(),
+            bucketed,
+            measures,
+            derived,
+        })
+    }
+
     /// Resolve ORDER BY expressions.
     fn resolve_order_by(&self, fields: &[super::types::OrderField]) -> PlanResult<Vec<ResolvedOrder>> {
         let mut resolved = Vec::with_capacity(fields.len());
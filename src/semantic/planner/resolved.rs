@@ -5,7 +5,7 @@
 
 use std::collections::HashSet;
 
-use crate::model::AggregationType;
+use crate::model::{AggregationType, Expr, RangeCondition};
 use crate::semantic::model_graph::JoinEdge;
 
 /// A fully resolved query - all fields validated and mapped to physical names.
@@ -31,6 +31,9 @@ pub struct ResolvedQuery {
 
     /// Limit clause.
     pub limit: Option<u64>,
+
+    /// Version predicates for joins to SCD Type 2 dimensions.
+    pub as_of: Vec<ResolvedAsOf>,
 }
 
 /// A resolved entity reference.
@@ -220,6 +223,38 @@ pub enum ResolvedOrderExpr {
     Measure(ResolvedMeasure),
 }
 
+/// A resolved top-N clause, expressed against output column aliases.
+///
+/// Top-N is applied to the aggregated result, so every reference here is
+/// an alias from the final SELECT list rather than a physical column.
+#[derive(Debug, Clone)]
+pub struct ResolvedTopN {
+    /// Number of rows to keep per partition.
+    pub n: u64,
+
+    /// Output aliases of the partition dimensions.
+    pub partition_by: Vec<String>,
+
+    /// Output alias of the ranking measure.
+    pub rank_by: String,
+
+    /// Rank highest values first?
+    pub descending: bool,
+
+    /// Label for the remainder bucket, if any.
+    pub other_label: Option<String>,
+
+    /// Output aliases of the dimensions replaced by the label in the remainder bucket.
+    pub bucketed: Vec<String>,
+
+    /// Output aliases of measures with the aggregation used to re-aggregate them.
+    pub measures: Vec<(String, AggregationType)>,
+
+    /// Output aliases of derived fields, recomputed in the remainder bucket
+    /// from the re-aggregated measures they combine.
+    pub derived: Vec<String>,
+}
+
 /// The join tree computed during resolution.
 #[derive(Debug, Clone)]
 pub struct ResolvedJoinTree {
@@ -229,8 +264,11 @@ pub struct ResolvedJoinTree {
     /// The edges in the join tree.
     pub edges: Vec<JoinEdge>,
 
-    /// Is the join tree safe (no fan-out)?
+    /// Is the join tree safe (no fan-out outside a bridge crossing)?
     pub is_safe: bool,
+
+    /// The bridge crossed by the join tree, if any.
+    pub bridge: Option<ResolvedBridge>,
 }
 
 impl ResolvedJoinTree {
@@ -239,6 +277,109 @@ impl ResolvedJoinTree {
             root: root.into(),
             edges: vec![],
             is_safe: true,
+            bridge: None,
+        }
+    }
+}
+
+/// Version predicate for the join to an SCD Type 2 dimension.
+///
+/// ANDed into the join's ON clause so each key matches one version.
+#[derive(Debug, Clone)]
+pub struct ResolvedAsOf {
+    /// The dimension (also its SQL alias).
+    pub dimension: String,
+
+    /// Column holding the first date a version is in effect.
+    pub effective_from: String,
+
+    /// Column holding the date a version stops being in effect (NULL = current).
+    pub effective_to: String,
+
+    /// The point in time the version must be in effect at.
+    pub point: AsOfPoint,
+}
+
+/// The point in time an SCD Type 2 version is selected at.
+#[derive(Debug, Clone)]
+pub enum AsOfPoint {
+    /// A date column on the fact (a date role keyed by the date itself).
+    Column(ResolvedColumn),
+
+    /// A date looked up in the date dimension by the fact's surrogate key
+    /// (a date role keyed by a surrogate key).
+    Lookup {
+        /// The date dimension.
+        dimension: ResolvedEntity,
+        /// Key column on the date dimension.
+        key_column: String,
+        /// Calendar date column on the date dimension.
+        date_column: String,
+        /// Key column on the fact.
+        key: ResolvedColumn,
+    },
+
+    /// A fixed date literal.
+    Date(String),
+
+    /// The current version.
+    Current,
+}
+
+/// A bridge entity crossed by the join tree.
+///
+/// Joining into a bridge fans out rows of the entity on the near side; the
+/// strategy decides how measures avoid counting those rows more than once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedBridge {
+    /// The bridge entity (also its SQL alias).
+    pub entity: String,
+
+    /// Entity joined into the bridge (the side the measures come from).
+    pub from_entity: String,
+
+    /// Entity reached through the bridge.
+    pub to_entity: String,
+
+    /// How measures are kept from double counting.
+    pub strategy: BridgeStrategy,
+}
+
+/// How a query crossing a bridge keeps measures from double counting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BridgeStrategy {
+    /// Collect the distinct (grouping values, root key) pairs reachable
+    /// through the bridge, then aggregate each root row once per group.
+    DistinctKey {
+        /// Key columns of the root entity.
+        keys: Vec<String>,
+    },
+
+    /// Multiply additive measures by the bridge's allocation weight.
+    WeightedAllocation {
+        /// Weight column on the bridge.
+        weight_column: String,
+    },
+}
+
+impl std::fmt::Display for ResolvedBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bridge '{}' ({} -> {}): ",
+            self.entity, self.from_entity, self.to_entity
+        )?;
+        match &self.strategy {
+            BridgeStrategy::DistinctKey { keys } => write!(
+                f,
+                "distinct-key pre-aggregation on ({}); each row is counted once per group",
+                keys.join(", ")
+            ),
+            BridgeStrategy::WeightedAllocation { weight_column } => write!(
+                f,
+                "weighted allocation of additive measures by '{}.{}'",
+                self.entity, weight_column
+            ),
         }
     }
 }
@@ -288,6 +429,9 @@ pub struct MultiFactQuery {
 
     /// Limit clause.
     pub limit: Option<u64>,
+
+    /// Version predicates for the shared SCD Type 2 dimensions.
+    pub as_of: Vec<ResolvedAsOf>,
 }
 
 /// Aggregates from a single fact - becomes one CTE.
@@ -320,7 +464,70 @@ pub struct FactJoinKey {
     pub dimension: String,
 
     /// PK column on the dimension (physical name).
+    ///
+    /// Empty, like `fact_column`, when the key is a band condition only.
     pub dimension_column: String,
+
+    /// Further `(fact, dimension)` column pairs for composite keys.
+    pub additional_columns: Vec<(String, String)>,
+
+    /// Band condition from the fact to the dimension.
+    pub range: Option<RangeCondition>,
+
+    /// Extra join predicates from the relationship.
+    pub predicates: Vec<Expr>,
+
+    /// Whether fact rows may have no matching dimension row.
+    pub optional: bool,
+}
+
+impl FactJoinKey {
+    /// Key on a fact column matching a dimension column.
+    pub fn new(
+        fact_column: impl Into<String>,
+        dimension: impl Into<String>,
+        dimension_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            fact_column: fact_column.into(),
+            dimension: dimension.into(),
+            dimension_column: dimension_column.into(),
+            additional_columns: Vec::new(),
+            range: None,
+            predicates: Vec::new(),
+            optional: false,
+        }
+    }
+
+    /// Whether the dimension is joined inside the fact's aggregate.
+    ///
+    /// Band conditions and predicates cannot be checked once the fact is
+    /// grouped, so the fact CTE joins the dimension itself and groups by the
+    /// band bounds instead of the fact column.
+    pub fn joins_in_cte(&self) -> bool {
+        self.range.is_some() || !self.predicates.is_empty()
+    }
+
+    /// All `(fact column, dimension column)` pairs, primary pair first.
+    ///
+    /// Empty for keys joined only by a band condition.
+    pub fn column_pairs(&self) -> Vec<(String, String)> {
+        std::iter::once((self.fact_column.clone(), self.dimension_column.clone()))
+            .filter(|(fact, _)| !fact.is_empty())
+            .chain(self.additional_columns.iter().cloned())
+            .collect()
+    }
+
+    /// `(CTE column, dimension column)` pairs carried out of the fact CTE.
+    pub fn key_columns(&self) -> Vec<(String, String)> {
+        let mut columns = self.column_pairs();
+        if let Some(range) = &self.range {
+            for bound in [&range.lower_column, &range.upper_column] {
+                columns.push((format!("{}_{}", self.dimension, bound), bound.clone()));
+            }
+        }
+        columns
+    }
 }
 
 /// A shared dimension - reachable from all anchor facts.
@@ -19,6 +19,16 @@ pub struct SemanticQuery {
     pub derived: Vec<DerivedField>,
     pub order_by: Vec<OrderField>,
     pub limit: Option<u64>,
+    /// Top-N per group, optionally bucketing the remainder as "Other".
+    ///
+    /// Applied after aggregation; `order_by` and `limit` apply to the
+    /// ranked result.
+    pub top_n: Option<TopN>,
+    /// Which version of SCD Type 2 dimensions to join.
+    pub as_of: AsOf,
+    /// Moves along dimension hierarchies, applied to `group_by` in order
+    /// before planning.
+    pub drill: Vec<Drill>,
 }
 
 /// A reference to a field: entity.field
@@ -133,6 +143,150 @@ impl SelectField {
     }
 }
 
+/// One step up or down a dimension hierarchy.
+///
+/// Drilling down groups by the level below the finest level of the
+/// hierarchy already grouped on (or the top level if none is). Drilling up
+/// drops the finest grouped level.
+///
+/// # Example
+///
+/// ```ignore
+/// // Revenue by country -> revenue by country and region
+/// query.group_by = vec![FieldRef::new("stores", "country")];
+/// query.drill = vec![Drill::down("stores", "geography")];
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drill {
+    /// The dimension, or the entity it is built from, the levels are read on
+    pub entity: String,
+    /// Hierarchy name on the dimension
+    pub hierarchy: String,
+    pub direction: DrillDirection,
+}
+
+impl Drill {
+    pub fn up(entity: &str, hierarchy: &str) -> Self {
+        Self {
+            entity: entity.into(),
+            hierarchy: hierarchy.into(),
+            direction: DrillDirection::Up,
+        }
+    }
+
+    pub fn down(entity: &str, hierarchy: &str) -> Self {
+        Self {
+            entity: entity.into(),
+            hierarchy: hierarchy.into(),
+            direction: DrillDirection::Down,
+        }
+    }
+}
+
+/// Direction of a [`Drill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrillDirection {
+    /// Toward coarser levels
+    Up,
+    /// Toward finer levels
+    Down,
+}
+
+/// Which version of an SCD Type 2 dimension a query joins to.
+///
+/// Type 2 dimensions keep one row per version of each business key, so
+/// joining on the key alone multiplies fact rows across versions. Every
+/// mode restricts the join to a single version per key.
+///
+/// # Example
+///
+/// ```ignore
+/// // Customer attributes as they were when each order shipped
+/// AsOf::FactDate { role: Some("ship_date".into()) }
+///
+/// // Customer attributes as they were at year end
+/// AsOf::Date("2024-12-31".into())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsOf {
+    /// The version in effect at the fact's date.
+    ///
+    /// Uses the named date role of the anchor fact's `DateConfig`, or its
+    /// primary role when `role` is None:
+    /// `fact_date >= effective_from AND (fact_date < effective_to OR effective_to IS NULL)`
+    FactDate { role: Option<String> },
+
+    /// The current version (`effective_to IS NULL`).
+    Current,
+
+    /// The version in effect on a fixed date (e.g. `"2024-06-30"`).
+    Date(String),
+}
+
+impl Default for AsOf {
+    fn default() -> Self {
+        AsOf::FactDate { role: None }
+    }
+}
+
+/// Top-N per group - keep the best `n` rows within each partition.
+///
+/// Ranking uses `ROW_NUMBER()` over the aggregated result, partitioned by
+/// `partition_by` and ordered by the `rank_by` measure. When `other_label`
+/// is set, rows outside the top N are re-aggregated into a single row per
+/// partition whose remaining dimensions are replaced by the label.
+///
+/// # Example
+///
+/// ```ignore
+/// // Top 10 products by revenue per region, everything else as "Other"
+/// TopN::new(10, FieldRef::new("orders_fact", "revenue"))
+///     .partition_by(vec![FieldRef::new("customers", "region")])
+///     .with_other("Other")
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopN {
+    /// Number of rows to keep per partition.
+    pub n: u64,
+    /// Dimensions that define the groups. Empty means a single global group.
+    pub partition_by: Vec<FieldRef>,
+    /// The measure used to rank rows within each group.
+    pub rank_by: FieldRef,
+    /// Rank highest values first (default: true).
+    pub descending: bool,
+    /// Label for the remainder bucket. If None, remaining rows are dropped.
+    pub other_label: Option<String>,
+}
+
+impl TopN {
+    pub fn new(n: u64, rank_by: FieldRef) -> Self {
+        Self {
+            n,
+            partition_by: vec![],
+            rank_by,
+            descending: true,
+            other_label: None,
+        }
+    }
+
+    pub fn partition_by(mut self, fields: Vec<FieldRef>) -> Self {
+        self.partition_by = fields;
+        self
+    }
+
+    /// Rank lowest values first (bottom-N).
+    pub fn ascending(mut self) -> Self {
+        self.descending = false;
+        self
+    }
+
+    /// Bucket rows outside the top N into a single row with this label.
+    pub fn with_other(mut self, label: &str) -> Self {
+        self.other_label = Some(label.into());
+        self
+    }
+}
+
 /// A derived field - a calculation from other measures.
 ///
 /// These are computed after aggregation and reference
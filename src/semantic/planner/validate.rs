@@ -1,7 +1,7 @@
 //! Phase 2: Validation
 //!
 //! This phase validates the resolved query for semantic correctness:
-//! - Checks join path safety (no fan-out)
+//! - Checks join path safety (no fan-out outside a declared bridge)
 //! - Validates GROUP BY completeness
 //! - Ensures measures are only used in appropriate contexts
 
@@ -9,10 +9,11 @@ use std::collections::{HashMap, HashSet};
 
 use crate::model::DataType;
 use crate::semantic::error::{PlanError, PlanResult, TypeMismatchDetails};
-use crate::semantic::model_graph::ModelGraph;
+use crate::semantic::model_graph::{JoinEdge, ModelGraph};
 
 use super::resolved::{
-    ResolvedColumn, ResolvedEntity, ResolvedJoinTree, ResolvedQuery, ResolvedSelect,
+    BridgeStrategy, ResolvedBridge, ResolvedColumn, ResolvedEntity, ResolvedJoinTree,
+    ResolvedOrderExpr, ResolvedQuery, ResolvedSelect,
 };
 
 /// Check if two data types are compatible for joining.
@@ -99,6 +100,7 @@ impl<'a> Validator<'a> {
 
         // Check join safety (cardinality)
         self.validate_join_safety(&join_tree)?;
+        self.validate_bridge_measures(&query, &join_tree)?;
 
         // Check join type compatibility
         self.validate_join_types(&join_tree)?;
@@ -160,15 +162,107 @@ impl<'a> Validator<'a> {
         }
 
         let join_path = self.graph.find_join_tree(root, &other_entities)?;
-        let is_safe = join_path.is_safe();
+        let bridge = self.resolve_bridge(root, &join_path.edges)?;
+
+        // Fan-out into a declared bridge is handled by the bridge strategy
+        let is_safe = join_path
+            .edges
+            .iter()
+            .filter(|e| e.causes_fanout())
+            .all(|e| bridge.as_ref().is_some_and(|b| is_bridge_entry(b, e)));
 
         Ok(ResolvedJoinTree {
             root: root.clone(),
             edges: join_path.edges,
             is_safe,
+            bridge,
         })
     }
 
+    /// Find the bridge crossed by the join tree and pick its strategy.
+    ///
+    /// A bridge is crossed when a fan-out edge leads into a declared bridge
+    /// entity. Only one bridge per query is supported.
+    fn resolve_bridge(&self, root: &str, edges: &[JoinEdge]) -> PlanResult<Option<ResolvedBridge>> {
+        let mut entries = edges
+            .iter()
+            .filter(|e| e.causes_fanout() && self.graph.get_bridge(&e.to_entity).is_some());
+
+        let Some(entry) = entries.next() else {
+            return Ok(None);
+        };
+        if let Some(second) = entries.next() {
+            return Err(PlanError::UnsafeJoinPath {
+                from: second.from_entity.clone(),
+                to: second.to_entity.clone(),
+                message: format!(
+                    "Query crosses both bridge '{}' and bridge '{}'. \
+                     Only one bridge can be crossed per query.",
+                    entry.to_entity, second.to_entity
+                ),
+            });
+        }
+
+        let definition = self.graph.get_bridge(&entry.to_entity).unwrap();
+        let to_entity = edges
+            .iter()
+            .find(|e| e.from_entity == entry.to_entity)
+            .map_or_else(|| entry.to_entity.clone(), |e| e.to_entity.clone());
+
+        let strategy = match &definition.weight_column {
+            Some(weight_column) => BridgeStrategy::WeightedAllocation {
+                weight_column: weight_column.clone(),
+            },
+            None => {
+                let keys = self.root_keys(root);
+                if keys.is_empty() {
+                    return Err(PlanError::UnsafeJoinPath {
+                        from: entry.from_entity.clone(),
+                        to: entry.to_entity.clone(),
+                        message: format!(
+                            "Crossing bridge '{}' needs the key of '{}' to avoid double counting. \
+                             Declare a primary key on '{}' or a weight on the bridge.",
+                            entry.to_entity, root, root
+                        ),
+                    });
+                }
+                BridgeStrategy::DistinctKey { keys }
+            }
+        };
+
+        Ok(Some(ResolvedBridge {
+            entity: entry.to_entity.clone(),
+            from_entity: entry.from_entity.clone(),
+            to_entity,
+            strategy,
+        }))
+    }
+
+    /// Columns identifying one row of the root entity.
+    ///
+    /// Facts are keyed by their grain; sources and dimensions by their
+    /// primary key.
+    fn root_keys(&self, root: &str) -> Vec<String> {
+        if let Some(fact) = self.graph.get_fact(root) {
+            return fact
+                .grain
+                .iter()
+                .map(|g| {
+                    g.target_name
+                        .clone()
+                        .unwrap_or_else(|| g.source_column.clone())
+                })
+                .collect();
+        }
+        if let Some(source) = self.graph.get_source(root) {
+            return source.primary_key.clone();
+        }
+        if let Some(dimension) = self.graph.get_dimension(root) {
+            return dimension.primary_key.clone();
+        }
+        Vec::new()
+    }
+
     /// Validate that the join tree doesn't cause fan-out.
     fn validate_join_safety(&self, join_tree: &ResolvedJoinTree) -> PlanResult<()> {
         if join_tree.is_safe {
@@ -176,14 +270,80 @@ impl<'a> Validator<'a> {
         }
 
         // Find the first unsafe edge for a helpful error message
-        if let Some(edge) = join_tree.edges.iter().find(|e| e.cardinality.causes_fanout()) {
+        let unsafe_edge = join_tree.edges.iter().find(|e| {
+            e.causes_fanout()
+                && !join_tree
+                    .bridge
+                    .as_ref()
+                    .is_some_and(|b| is_bridge_entry(b, e))
+        });
+        if let Some(edge) = unsafe_edge {
+            let message = match &edge.range {
+                Some(range) if range.bounds_on_from => format!(
+                    "Each band in '{}' covers many rows of '{}', so joining {} -> {} \
+                     causes row multiplication. Start from '{}' instead.",
+                    edge.from_entity,
+                    edge.to_entity,
+                    edge.from_entity,
+                    edge.to_entity,
+                    edge.to_entity
+                ),
+                Some(_) => format!(
+                    "Bands in '{}' may overlap, so joining {} -> {} can match several bands \
+                     per row. Declare the relationship many_to_one if the bands don't overlap.",
+                    edge.to_entity, edge.from_entity, edge.to_entity
+                ),
+                None => format!(
+                    "Joining {} -> {} is 1:N which causes row multiplication. \
+                     Start from '{}' instead.",
+                    edge.from_entity, edge.to_entity, edge.to_entity
+                ),
+            };
             return Err(PlanError::UnsafeJoinPath {
                 from: edge.from_entity.clone(),
                 to: edge.to_entity.clone(),
+                message,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validate that measures crossing a bridge come from the root entity.
+    ///
+    /// Bridge strategies de-duplicate or allocate rows of the root; measures
+    /// from any other entity would still be multiplied by the bridge.
+    fn validate_bridge_measures(
+        &self,
+        query: &ResolvedQuery,
+        join_tree: &ResolvedJoinTree,
+    ) -> PlanResult<()> {
+        let Some(bridge) = &join_tree.bridge else {
+            return Ok(());
+        };
+
+        let mut entities = Vec::new();
+        for select in &query.select {
+            match select {
+                ResolvedSelect::Measure { measure, .. } => entities.push(&measure.entity_alias),
+                ResolvedSelect::Aggregate { column, .. } => entities.push(&column.entity_alias),
+                ResolvedSelect::Column { .. } | ResolvedSelect::Derived { .. } => {}
+            }
+        }
+        for order in &query.order_by {
+            if let ResolvedOrderExpr::Measure(measure) = &order.expr {
+                entities.push(&measure.entity_alias);
+            }
+        }
+
+        if let Some(entity) = entities.into_iter().find(|e| **e != join_tree.root) {
+            return Err(PlanError::UnsafeJoinPath {
+                from: bridge.from_entity.clone(),
+                to: bridge.entity.clone(),
                 message: format!(
-                    "Joining {} -> {} is 1:N which causes row multiplication. \
-                     Start from '{}' instead.",
-                    edge.from_entity, edge.to_entity, edge.to_entity
+                    "Measures from '{}' would be multiplied by bridge '{}'. \
+                     Only measures from '{}' can be aggregated across the bridge.",
+                    entity, bridge.entity, join_tree.root
                 ),
             });
         }
@@ -196,28 +356,51 @@ impl<'a> Validator<'a> {
     /// This catches type mismatches before query execution, providing
     /// better error messages than database errors.
     fn validate_join_types(&self, join_tree: &ResolvedJoinTree) -> PlanResult<()> {
-        for edge in &join_tree.edges {
-            // Try to get column types - skip validation if types not available
-            // (e.g., for fact entities that don't have explicit column definitions)
-            let from_type = match self.graph.get_column_type(&edge.from_entity, &edge.from_column) {
-                Ok(t) => t,
-                Err(_) => continue, // Skip if column not found (e.g., fact entity)
-            };
+        // An (entity, column) side of a join comparison
+        type Side<'e> = (&'e str, &'e str);
 
-            let to_type = match self.graph.get_column_type(&edge.to_entity, &edge.to_column) {
-                Ok(t) => t,
-                Err(_) => continue, // Skip if column not found
-            };
+        for edge in &join_tree.edges {
+            // Column pairs compared by the join condition
+            let mut compared: Vec<(Side, Side)> = edge
+                .column_pairs()
+                .into_iter()
+                .map(|(from_column, to_column)| {
+                    (
+                        (edge.from_entity.as_str(), from_column),
+                        (edge.to_entity.as_str(), to_column),
+                    )
+                })
+                .collect();
+            if let Some(range) = &edge.range {
+                let (value_entity, bounds_entity) = range.sides(&edge.from_entity, &edge.to_entity);
+                for bound in [&range.lower_column, &range.upper_column] {
+                    compared.push(((value_entity, &range.value_column), (bounds_entity, bound)));
+                }
+            }
 
-            if !types_compatible(&from_type, &to_type) {
-                return Err(PlanError::TypeMismatch(Box::new(TypeMismatchDetails {
-                    left_entity: edge.from_entity.clone(),
-                    left_column: edge.from_column.clone(),
-                    left_type: format!("{:?}", from_type),
-                    right_entity: edge.to_entity.clone(),
-                    right_column: edge.to_column.clone(),
-                    right_type: format!("{:?}", to_type),
-                })));
+            for ((left_entity, left_column), (right_entity, right_column)) in compared {
+                // Try to get column types - skip validation if types not available
+                // (e.g., for fact entities that don't have explicit column definitions)
+                let left_type = match self.graph.get_column_type(left_entity, left_column) {
+                    Ok(t) => t,
+                    Err(_) => continue, // Skip if column not found (e.g., fact entity)
+                };
+
+                let right_type = match self.graph.get_column_type(right_entity, right_column) {
+                    Ok(t) => t,
+                    Err(_) => continue, // Skip if column not found
+                };
+
+                if !types_compatible(&left_type, &right_type) {
+                    return Err(PlanError::TypeMismatch(Box::new(TypeMismatchDetails {
+                        left_entity: left_entity.to_string(),
+                        left_column: left_column.to_string(),
+                        left_type: format!("{:?}", left_type),
+                        right_entity: right_entity.to_string(),
+                        right_column: right_column.to_string(),
+                        right_type: format!("{:?}", right_type),
+                    })));
+                }
             }
         }
 
@@ -254,6 +437,11 @@ impl<'a> Validator<'a> {
     }
 }
 
+/// Is `edge` the fan-out edge entering `bridge`?
+fn is_bridge_entry(bridge: &ResolvedBridge, edge: &JoinEdge) -> bool {
+    edge.from_entity == bridge.from_entity && edge.to_entity == bridge.entity
+}
+
 /// Check if a column is in the GROUP BY set.
 fn is_column_in_group(grouped: &HashSet<(&str, &str)>, column: &ResolvedColumn) -> bool {
     grouped.contains(&(column.entity_alias.as_str(), column.physical_name.as_str()))
@@ -265,7 +453,7 @@ mod tests {
     use crate::model::{Cardinality, DataType, FactDefinition, Model, Relationship, SourceEntity};
     use crate::semantic::model_graph::ModelGraph;
     use crate::semantic::planner::resolve::Resolver;
-    use crate::semantic::planner::types::{SelectField, SemanticQuery};
+    use crate::semantic::planner::types::{AsOf, SelectField, SemanticQuery};
 
     // =========================================================================
     // types_compatible tests
@@ -385,6 +573,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let resolved = resolver.resolve(&sq).expect("Resolve should succeed");
@@ -428,6 +619,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let resolved = resolver.resolve(&sq).expect("Resolve should succeed");
@@ -487,6 +681,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let resolved = resolver.resolve(&sq).expect("Resolve failed");
@@ -509,6 +706,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         let resolved = resolver.resolve(&sq).unwrap();
@@ -0,0 +1,508 @@
+//! Build state tracking and scheduled refresh of materialized reports.
+//!
+//! Successful builds are recorded in the [`MetadataCache`] (see
+//! [`BuildState`]). A target is stale when it has never been built, when its
+//! definition changed since the last build, or when it is a report table
+//! whose `refresh_delta` has elapsed.
+//!
+//! [`RefreshScheduler`] is the long-running mode: it wakes when the next
+//! report table falls due and rebuilds everything that is stale.
+//!
+//! ```ignore
+//! let scheduler = RefreshScheduler::new(model, cache, executor)
+//!     .with_statements("rpt_daily_sales", vec![rebuild_sql]);
+//!
+//! scheduler
+//!     .run(tokio::signal::ctrl_c().map(|_| ()), |report| {
+//!         println!("{}", report.summary())
+//!     })
+//!     .await?;
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cache::{BuildState, CacheResult, MetadataCache};
+use crate::model::{Model, RefreshDelta, ReportMaterialization, ReportTableType};
+use crate::semantic::build::{BuildExecutor, BuildNode, BuildPlan, BuildReport, BuildRunner};
+
+/// Why a target does or does not need rebuilding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Staleness {
+    /// Built from the current definition and not yet due
+    Fresh,
+    /// No build has been recorded
+    NeverBuilt,
+    /// The definition changed since the last build
+    DefinitionChanged,
+    /// The refresh delta elapsed; `overdue` is the time past the deadline
+    RefreshDue { overdue: Duration },
+}
+
+impl Staleness {
+    pub fn is_stale(&self) -> bool {
+        *self != Staleness::Fresh
+    }
+}
+
+impl std::fmt::Display for Staleness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Staleness::Fresh => write!(f, "fresh"),
+            Staleness::NeverBuilt => write!(f, "never built"),
+            Staleness::DefinitionChanged => write!(f, "definition changed"),
+            Staleness::RefreshDue { overdue } => {
+                write!(f, "refresh due ({}s overdue)", overdue.as_secs())
+            }
+        }
+    }
+}
+
+/// Build state of a model, read from and written to the cache.
+pub struct RefreshState<'a> {
+    model: &'a Model,
+    cache: &'a MetadataCache,
+    model_hash: String,
+}
+
+impl<'a> RefreshState<'a> {
+    pub fn new(model: &'a Model, cache: &'a MetadataCache) -> Self {
+        Self {
+            model,
+            cache,
+            model_hash: model.content_hash(),
+        }
+    }
+
+    /// Every target with build state: facts, dimensions, tables and
+    /// materialized reports, sorted by name.
+    pub fn targets(&self) -> Vec<&'a str> {
+        let model = self.model;
+        let mut targets: Vec<&str> = model
+            .facts
+            .keys()
+            .chain(model.dimensions.keys())
+            .chain(model.tables.keys())
+            .map(String::as_str)
+            .collect();
+        targets.extend(
+            model
+                .reports
+                .values()
+                .filter(|r| is_materialized(r.materialization.as_ref()))
+                .map(|r| r.name.as_str()),
+        );
+        targets.extend(
+            model
+                .pivot_reports
+                .values()
+                .filter(|r| is_materialized(r.materialization.as_ref()))
+                .map(|r| r.name.as_str()),
+        );
+        targets.sort_unstable();
+        targets
+    }
+
+    /// Refresh interval of a report materialized as a table.
+    pub fn refresh_delta(&self, target: &str) -> Option<&'a RefreshDelta> {
+        let materialization = match self.model.reports.get(target) {
+            Some(report) => report.materialization.as_ref(),
+            None => self
+                .model
+                .pivot_reports
+                .get(target)?
+                .materialization
+                .as_ref(),
+        }?;
+        match materialization.table_type {
+            ReportTableType::Table if materialization.materialized => {
+                materialization.refresh_delta.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// The most recent build of a target that applies to this model.
+    ///
+    /// Falls back to builds under earlier model hashes, since editing one
+    /// target changes the model hash for all of them.
+    pub fn last_build(&self, target: &str) -> CacheResult<Option<BuildState>> {
+        match self.cache.get_build_state(&self.model_hash, target)? {
+            Some(state) => Ok(Some(state)),
+            None => self.cache.latest_build_state(target),
+        }
+    }
+
+    /// Whether a target needs rebuilding at `now` (Unix seconds).
+    ///
+    /// Unknown targets are reported as never built.
+    pub fn staleness(&self, target: &str, now: i64) -> CacheResult<Staleness> {
+        let Some(last) = self.last_build(target)? else {
+            return Ok(Staleness::NeverBuilt);
+        };
+        if self.model.definition_hash(target).as_deref() != Some(last.definition_hash.as_str()) {
+            return Ok(Staleness::DefinitionChanged);
+        }
+        if let Some(delta) = self.refresh_delta(target) {
+            let due_at = last.last_run_at + delta.seconds as i64;
+            if now >= due_at {
+                return Ok(Staleness::RefreshDue {
+                    overdue: Duration::from_secs((now - due_at) as u64),
+                });
+            }
+        }
+        Ok(Staleness::Fresh)
+    }
+
+    /// Targets that need rebuilding at `now`, sorted by name.
+    pub fn stale_targets(&self, now: i64) -> CacheResult<Vec<(&'a str, Staleness)>> {
+        let mut stale = Vec::new();
+        for target in self.targets() {
+            let staleness = self.staleness(target, now)?;
+            if staleness.is_stale() {
+                stale.push((target, staleness));
+            }
+        }
+        Ok(stale)
+    }
+
+    /// When the next report table falls due (Unix seconds).
+    ///
+    /// Report tables that are already due or never built return `now`.
+    pub fn next_refresh(&self, now: i64) -> CacheResult<Option<i64>> {
+        let mut next: Option<i64> = None;
+        for target in self.targets() {
+            let Some(delta) = self.refresh_delta(target) else {
+                continue;
+            };
+            let due_at = match self.last_build(target)? {
+                Some(last) => (last.last_run_at + delta.seconds as i64).max(now),
+                None => now,
+            };
+            next = Some(next.map_or(due_at, |n| n.min(due_at)));
+        }
+        Ok(next)
+    }
+
+    /// Record every successful target in a build report started at `started`
+    /// (Unix seconds) as run when its build finished.
+    ///
+    /// Finish times are `started` plus each target's duration, so targets
+    /// queued behind the concurrency limit are recorded as finishing early
+    /// and fall due slightly sooner, never later.
+    pub fn record(&self, report: &BuildReport, started: i64) -> CacheResult<()> {
+        for result in report.results.iter().filter(|r| r.succeeded()) {
+            let Some(definition_hash) = self.model.definition_hash(&result.target) else {
+                continue;
+            };
+            self.cache.record_build_state(&BuildState {
+                model_hash: self.model_hash.clone(),
+                target: result.target.clone(),
+                last_run_at: started + result.duration.as_secs() as i64,
+                duration_ms: result.duration.as_millis() as i64,
+                rows: result.rows,
+                definition_hash,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn is_materialized(materialization: Option<&ReportMaterialization>) -> bool {
+    materialization.is_some_and(|m| m.materialized)
+}
+
+/// Current time in Unix seconds.
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Rebuilds materialized report tables as their refresh deltas elapse.
+///
+/// Only reports given statements with [`RefreshScheduler::with_statements`]
+/// are scheduled; other report tables are left to the caller.
+pub struct RefreshScheduler<E> {
+    model: Model,
+    cache: MetadataCache,
+    runner: BuildRunner<E>,
+    statements: HashMap<String, Vec<String>>,
+    poll_interval: Duration,
+}
+
+impl<E: BuildExecutor> RefreshScheduler<E> {
+    pub fn new(model: Model, cache: MetadataCache, executor: E) -> Self {
+        Self {
+            model,
+            cache,
+            runner: BuildRunner::new(executor),
+            statements: HashMap::new(),
+            poll_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Set the statements that rebuild a report table.
+    pub fn with_statements(mut self, report: impl Into<String>, statements: Vec<String>) -> Self {
+        self.statements.insert(report.into(), statements);
+        self
+    }
+
+    /// Longest time to sleep between checks (default: 60 seconds).
+    ///
+    /// Bounds how long a definition change waits to be picked up.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Maximum reports rebuilt at once (default: 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.runner = self.runner.with_concurrency(concurrency);
+        self
+    }
+
+    fn state(&self) -> RefreshState<'_> {
+        RefreshState::new(&self.model, &self.cache)
+    }
+
+    /// Scheduled report tables that are stale at `now`, by name.
+    pub fn due(&self, now: i64) -> CacheResult<Vec<String>> {
+        let state = self.state();
+        let mut due = Vec::new();
+        for (target, _) in state.stale_targets(now)? {
+            if state.refresh_delta(target).is_some() && self.statements.contains_key(target) {
+                due.push(target.to_string());
+            }
+        }
+        Ok(due)
+    }
+
+    /// Rebuild every due report table, starting at `now`, and record the
+    /// successes.
+    pub async fn tick(&self, now: i64) -> CacheResult<BuildReport> {
+        let nodes = self
+            .due(now)?
+            .into_iter()
+            .map(|target| BuildNode {
+                statements: self.statements[&target].clone(),
+                target,
+                depends_on: Vec::new(),
+            })
+            .collect();
+        let report = self.runner.run(&BuildPlan { nodes }).await;
+        self.state().record(&report, now)?;
+        Ok(report)
+    }
+
+    /// Refresh report tables until `shutdown` completes.
+    ///
+    /// `on_refresh` receives the report of every tick that rebuilt something.
+    pub async fn run(
+        &self,
+        shutdown: impl Future<Output = ()>,
+        mut on_refresh: impl FnMut(&BuildReport),
+    ) -> CacheResult<()> {
+        tokio::pin!(shutdown);
+        loop {
+            let now = unix_now();
+            let report = self.tick(now).await?;
+            if !report.results.is_empty() {
+                on_refresh(&report);
+            }
+
+            let now = unix_now();
+            let wait = match self.state().next_refresh(now)? {
+                // Due reports that could not be built are retried on the next poll
+                Some(next) if next > now => {
+                    Duration::from_secs((next - now) as u64).min(self.poll_interval)
+                }
+                _ => self.poll_interval,
+            };
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{DataType, FactDefinition, Report, SourceEntity};
+    use crate::semantic::build::BuildResult;
+    use crate::worker::WorkerResult;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    fn model(delta_hours: u64) -> Model {
+        Model::new()
+            .with_source(
+                SourceEntity::new("orders", "raw.orders")
+                    .with_required_column("order_id", DataType::Int64),
+            )
+            .with_fact(FactDefinition::new("fact_orders", "analytics.fact_orders"))
+            .with_report(
+                Report::new("rpt_sales")
+                    .with_measure("fact_orders", "revenue")
+                    .with_materialization(ReportMaterialization::table(
+                        "analytics.rpt_sales",
+                        RefreshDelta::from_hours(delta_hours),
+                    )),
+            )
+            .with_report(
+                Report::new("rpt_live")
+                    .with_materialization(ReportMaterialization::view("analytics.rpt_live")),
+            )
+            .with_report(Report::new("rpt_adhoc"))
+    }
+
+    fn built(targets: &[&str]) -> BuildReport {
+        BuildReport {
+            results: targets
+                .iter()
+                .map(|t| BuildResult {
+                    target: t.to_string(),
+                    status: crate::semantic::build::BuildStatus::Success,
+                    duration: Duration::from_millis(1500),
+                    rows: Some(10),
+                })
+                .collect(),
+            duration: Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn test_targets_and_refresh_delta() {
+        let model = model(4);
+        let cache = MetadataCache::open_in_memory().unwrap();
+        let state = RefreshState::new(&model, &cache);
+
+        assert_eq!(
+            state.targets(),
+            vec!["fact_orders", "rpt_live", "rpt_sales"]
+        );
+        assert_eq!(state.refresh_delta("rpt_sales").unwrap().seconds, 4 * 3600);
+        assert!(state.refresh_delta("rpt_live").is_none());
+        assert!(state.refresh_delta("fact_orders").is_none());
+    }
+
+    #[test]
+    fn test_staleness() {
+        let model = model(4);
+        let cache = MetadataCache::open_in_memory().unwrap();
+        let state = RefreshState::new(&model, &cache);
+        assert_eq!(state.stale_targets(0).unwrap().len(), 3);
+
+        state
+            .record(&built(&["fact_orders", "rpt_live", "rpt_sales"]), 1_000)
+            .unwrap();
+        let recorded = cache
+            .get_build_state(&model.content_hash(), "rpt_sales")
+            .unwrap()
+            .unwrap();
+        assert_eq!(recorded.duration_ms, 1500);
+        // Recorded when the build finished
+        assert_eq!(recorded.last_run_at, 1_001);
+        assert!(state.stale_targets(1_001).unwrap().is_empty());
+
+        // Report tables fall due; other targets stay fresh
+        let stale = state.stale_targets(1_001 + 4 * 3600 + 5).unwrap();
+        assert_eq!(
+            stale,
+            vec![(
+                "rpt_sales",
+                Staleness::RefreshDue {
+                    overdue: Duration::from_secs(5)
+                }
+            )]
+        );
+        assert_eq!(state.next_refresh(1_001).unwrap(), Some(1_001 + 4 * 3600));
+    }
+
+    #[test]
+    fn test_definition_change() {
+        let cache = MetadataCache::open_in_memory().unwrap();
+        let original = model(4);
+        RefreshState::new(&original, &cache)
+            .record(&built(&["fact_orders", "rpt_sales"]), 1_000)
+            .unwrap();
+
+        // Changing the report changes the model hash, but only the report is stale
+        let changed = model(8);
+        let state = RefreshState::new(&changed, &cache);
+        assert_eq!(
+            state.staleness("rpt_sales", 1_000).unwrap(),
+            Staleness::DefinitionChanged
+        );
+        assert_eq!(
+            state.staleness("fact_orders", 1_000).unwrap(),
+            Staleness::Fresh
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        executed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl BuildExecutor for RecordingExecutor {
+        async fn execute(&self, sql: &str) -> WorkerResult<Option<i64>> {
+            self.executed.lock().unwrap().push(sql.to_string());
+            Ok(Some(3))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_tick() {
+        let scheduler = RefreshScheduler::new(
+            model(4),
+            MetadataCache::open_in_memory().unwrap(),
+            RecordingExecutor::default(),
+        )
+        .with_statements("rpt_sales", vec!["rebuild rpt_sales".into()]);
+
+        let now = unix_now();
+        assert_eq!(scheduler.due(now).unwrap(), vec!["rpt_sales"]);
+        let report = scheduler.tick(now).await.unwrap();
+        assert!(report.succeeded());
+        assert_eq!(report.result("rpt_sales").unwrap().rows, Some(3));
+
+        // Nothing is due again until the delta elapses
+        assert!(scheduler.due(now).unwrap().is_empty());
+        assert!(scheduler.tick(now).await.unwrap().results.is_empty());
+        assert_eq!(
+            scheduler.due(now + 4 * 3600 + 1).unwrap(),
+            vec!["rpt_sales"]
+        );
+        assert_eq!(scheduler.runner_executed(), vec!["rebuild rpt_sales"]);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_run_until_shutdown() {
+        let scheduler = RefreshScheduler::new(
+            model(4),
+            MetadataCache::open_in_memory().unwrap(),
+            RecordingExecutor::default(),
+        )
+        .with_statements("rpt_sales", vec!["rebuild rpt_sales".into()])
+        .with_poll_interval(Duration::from_millis(10));
+
+        let mut refreshed = 0;
+        let shutdown = tokio::time::sleep(Duration::from_millis(50));
+        scheduler.run(shutdown, |_| refreshed += 1).await.unwrap();
+
+        assert_eq!(refreshed, 1);
+        assert_eq!(scheduler.runner_executed().len(), 1);
+    }
+
+    impl RefreshScheduler<RecordingExecutor> {
+        fn runner_executed(&self) -> Vec<String> {
+            self.runner.executor().executed.lock().unwrap().clone()
+        }
+    }
+}
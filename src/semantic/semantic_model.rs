@@ -179,7 +179,7 @@ mod tests {
     use super::*;
 
     use crate::model::{Cardinality, DataType, FactDefinition, Relationship, SourceEntity};
-    use crate::semantic::planner::{SelectField, SemanticQuery};
+    use crate::semantic::planner::{AsOf, SelectField, SemanticQuery};
 
     fn sample_model() -> Model {
         Model::new()
@@ -275,6 +275,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         // planner() includes lineage
@@ -298,6 +301,9 @@ mod tests {
             derived: vec![],
             order_by: vec![],
             limit: None,
+            top_n: None,
+            as_of: AsOf::default(),
+            drill: vec![],
         };
 
         // planner_fast() skips lineage
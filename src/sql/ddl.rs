@@ -17,13 +17,42 @@
 //! println!("{}", table.to_sql(Dialect::Postgres));
 //! ```
 
-use super::dialect::{Dialect, SqlDialect};
+use thiserror::Error;
+
+use super::dialect::{helpers, Dialect, DialectTarget, SqlDialect};
 use super::expr::Expr;
+use super::params::ParameterizedSql;
 use super::query::Query;
 use super::token::{Token, TokenStream};
 
 // Re-export DataType from model::types for convenience
 pub use crate::model::types::DataType;
+pub use crate::model::types::{Distribution, PartitionGranularity, TableFormat, TableOptions};
+
+/// Errors from checking DDL against a target dialect.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DdlError {
+    #[error("{option} is not supported by {dialect}")]
+    UnsupportedOption {
+        option: &'static str,
+        dialect: DialectTarget,
+    },
+
+    #[error("{option} references unknown column '{column}'")]
+    UnknownColumn {
+        option: &'static str,
+        column: String,
+    },
+
+    #[error("invalid {option}: {message}")]
+    InvalidOption {
+        option: &'static str,
+        message: String,
+    },
+}
+
+/// Result type for DDL validation.
+pub type DdlResult<T> = Result<T, DdlError>;
 
 /// DDL statement types.
 #[derive(Debug, Clone)]
@@ -40,12 +69,14 @@ pub enum DdlStatement {
 
 impl DdlStatement {
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         match self {
             DdlStatement::CreateTable(ct) => ct.to_tokens(dialect),
             DdlStatement::AlterTable(at) => at.to_tokens(dialect),
@@ -59,11 +90,81 @@ impl DdlStatement {
     }
 }
 
+/// Conversions shared by every DDL statement.
+pub trait Ddl {
+    /// Convert to SQL for the given dialect.
+    fn ddl_sql(&self, dialect: DialectTarget) -> String;
+
+    /// Convert to SQL for [`ParameterizedSql`] callers.
+    ///
+    /// Servers reject bind parameters in DDL, so literals stay inline.
+    fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        ParameterizedSql::unbound(self.ddl_sql(dialect.into()))
+    }
+}
+
+impl Ddl for DdlStatement {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for CreateTable {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for AlterTable {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for DropTable {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for CreateIndex {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for DropIndex {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for Truncate {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for CreateView {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
+impl Ddl for DropView {
+    fn ddl_sql(&self, dialect: DialectTarget) -> String {
+        self.to_sql(dialect)
+    }
+}
+
 // ============================================================================
 // CREATE TABLE
 // ============================================================================
 
 /// CREATE TABLE statement.
+///
+/// Warehouse layout comes from [`TableOptions`]. Options the dialect cannot
+/// express are skipped; [`CreateTable::validate`] reports them instead.
 #[derive(Debug, Clone)]
 #[must_use = "DDL statements have no effect until converted to SQL with to_sql()"]
 pub struct CreateTable {
@@ -73,6 +174,11 @@ pub struct CreateTable {
     pub columns: Vec<ColumnDef>,
     pub constraints: Vec<TableConstraint>,
     pub as_query: Option<Box<Query>>,
+    pub options: TableOptions,
+    pub comment: Option<String>,
+    /// Comments on columns of CREATE TABLE AS SELECT, whose columns come from
+    /// the query rather than column definitions.
+    pub query_column_comments: Vec<(String, String)>,
 }
 
 impl CreateTable {
@@ -85,6 +191,9 @@ impl CreateTable {
             columns: Vec::new(),
             constraints: Vec::new(),
             as_query: None,
+            options: TableOptions::default(),
+            comment: None,
+            query_column_comments: Vec::new(),
         }
     }
 
@@ -124,13 +233,191 @@ impl CreateTable {
         self
     }
 
+    /// Set partitioning, clustering, distribution and storage options.
+    pub fn options(mut self, options: TableOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the table comment.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Comment the named columns.
+    ///
+    /// Names without a column definition are kept for CREATE TABLE AS SELECT,
+    /// and ignored otherwise. Pairs with
+    /// [`Model::column_descriptions`](crate::model::Model::column_descriptions).
+    pub fn column_comments(mut self, comments: impl IntoIterator<Item = (String, String)>) -> Self {
+        for (name, comment) in comments {
+            match self.columns.iter_mut().find(|c| c.name == name) {
+                Some(col) => col.comment = Some(comment),
+                None => self.query_column_comments.push((name, comment)),
+            }
+        }
+        self
+    }
+
+    /// Check the table options against a dialect.
+    ///
+    /// Reports options the dialect lacks, option columns missing from the
+    /// column list, and combinations the warehouse rejects.
+    pub fn validate(&self, dialect: impl Into<DialectTarget>) -> DdlResult<()> {
+        let dialect = dialect.into();
+        let options = &self.options;
+        let unsupported = |option| DdlError::UnsupportedOption { option, dialect };
+        let invalid = |option, message: &str| DdlError::InvalidOption {
+            option,
+            message: message.into(),
+        };
+
+        if !options.partition_by.is_empty()
+            && !matches!(dialect.dialect, Dialect::BigQuery | Dialect::Databricks)
+        {
+            return Err(unsupported("PARTITION BY"));
+        }
+        if options.partition_granularity.is_some() && dialect != Dialect::BigQuery {
+            return Err(unsupported("partition granularity"));
+        }
+        if !options.cluster_by.is_empty()
+            && !matches!(
+                dialect.dialect,
+                Dialect::BigQuery | Dialect::Snowflake | Dialect::Databricks
+            )
+        {
+            return Err(unsupported("CLUSTER BY"));
+        }
+        if options.distribution.is_some() && dialect != Dialect::Redshift {
+            return Err(unsupported("DISTSTYLE"));
+        }
+        if !options.sort_key.is_empty() && dialect != Dialect::Redshift {
+            return Err(unsupported("SORTKEY"));
+        }
+        if options.format.is_some() && dialect != Dialect::Databricks {
+            return Err(unsupported("USING"));
+        }
+        if options.columnstore && dialect != Dialect::TSql {
+            return Err(unsupported("clustered columnstore"));
+        }
+
+        // CREATE TABLE AS SELECT takes its columns from the query
+        if self.as_query.is_none() {
+            let dist_key = match &options.distribution {
+                Some(Distribution::Key(column)) => Some(column),
+                _ => None,
+            };
+            let referenced = options
+                .partition_by
+                .iter()
+                .map(|c| ("PARTITION BY", c))
+                .chain(options.cluster_by.iter().map(|c| ("CLUSTER BY", c)))
+                .chain(options.sort_key.iter().map(|c| ("SORTKEY", c)))
+                .chain(dist_key.map(|c| ("DISTKEY", c)));
+            for (option, column) in referenced {
+                if !self.columns.iter().any(|c| &c.name == column) {
+                    return Err(DdlError::UnknownColumn {
+                        option,
+                        column: column.clone(),
+                    });
+                }
+            }
+        }
+
+        match dialect.dialect {
+            Dialect::BigQuery => {
+                if options.partition_by.len() > 1 {
+                    return Err(invalid("PARTITION BY", "BigQuery partitions by one column"));
+                }
+                if options.cluster_by.len() > 4 {
+                    return Err(invalid(
+                        "CLUSTER BY",
+                        "BigQuery clusters by at most four columns",
+                    ));
+                }
+                match (options.partition_granularity, self.partition_type()) {
+                    (Some(_), _) if options.partition_by.is_empty() => {
+                        return Err(invalid("partition granularity", "no PARTITION BY column"));
+                    }
+                    (Some(PartitionGranularity::Hour), Some(DataType::Date)) => {
+                        return Err(invalid(
+                            "partition granularity",
+                            "DATE columns have no HOUR",
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            Dialect::Databricks
+                if !options.partition_by.is_empty() && !options.cluster_by.is_empty() =>
+            {
+                return Err(invalid(
+                    "CLUSTER BY",
+                    "liquid clustering cannot be combined with PARTITIONED BY",
+                ));
+            }
+            Dialect::TSql if options.columnstore => {
+                if self.as_query.is_some() {
+                    return Err(invalid("clustered columnstore", "needs a column list"));
+                }
+                if !dialect.at_least(12, 0) {
+                    return Err(invalid(
+                        "clustered columnstore",
+                        "inline indexes need SQL Server 2014 or later",
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
+    /// Statements that set the table and column comments, to run after
+    /// [`CreateTable::to_sql`].
+    ///
+    /// PostgreSQL, DuckDB and Redshift use `COMMENT ON` and T-SQL uses
+    /// `sp_addextendedproperty`; other dialects comment inline, so this is
+    /// empty. Inline comments need column definitions, so CREATE TABLE AS
+    /// SELECT only gets its column comments from these statements, which
+    /// Snowflake also emits as `COMMENT ON COLUMN`.
+    pub fn comment_statements(&self, dialect: impl Into<DialectTarget>) -> Vec<String> {
+        let dialect = dialect.into();
+        let inline = dialect == Dialect::Snowflake;
+        let query_columns = self
+            .query_column_comments
+            .iter()
+            .filter(|_| self.as_query.is_some())
+            .map(|(name, comment)| (name.as_str(), comment.as_str()));
+        let column_comments: Vec<(&str, &str)> = self
+            .columns
+            .iter()
+            .filter(|_| !inline)
+            .filter_map(|c| Some((c.name.as_str(), c.comment.as_deref()?)))
+            .chain(query_columns)
+            .collect();
+        comment_statements(
+            dialect,
+            CommentTarget {
+                object: "TABLE",
+                schema: self.schema.as_deref(),
+                name: &self.name,
+            },
+            self.comment.as_deref(),
+            &column_comments,
+        )
+    }
+
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // CREATE TABLE
@@ -147,23 +434,24 @@ impl CreateTable {
         }
 
         // Table name
-        ts.space();
-        if let Some(ref schema) = self.schema {
-            ts.push(Token::QualifiedIdent {
-                schema: Some(schema.clone()),
-                name: self.name.clone(),
-            });
-        } else {
-            ts.push(Token::Ident(self.name.clone()));
-        }
+        ts.space()
+            .push(object_name(self.schema.as_deref(), &self.name));
 
         // AS SELECT or column definitions
         if let Some(ref query) = self.as_query {
+            self.emit_options(&mut ts, dialect);
             ts.space()
                 .push(Token::As)
                 .space()
                 .append(&query.to_tokens_for_dialect(dialect));
         } else {
+            // A clustered columnstore replaces the clustered primary key index
+            let columnstore = self.options.columnstore && dialect == Dialect::TSql;
+
+            // ClickHouse keys live in the engine's ORDER BY and cannot be nullable
+            let clickhouse = dialect == Dialect::ClickHouse;
+            let primary_key = self.primary_key_columns();
+
             // Column definitions and constraints
             ts.space().lparen();
 
@@ -173,22 +461,192 @@ impl CreateTable {
                     ts.comma().space();
                 }
                 first = false;
-                ts.append(&col.to_tokens(dialect));
+                if clickhouse && primary_key.contains(&col.name) {
+                    let col = col.clone().not_null();
+                    ts.append(&col.column_tokens(dialect, columnstore));
+                } else {
+                    ts.append(&col.column_tokens(dialect, columnstore));
+                }
             }
 
-            for constraint in &self.constraints {
+            for constraint in self.constraints.iter().filter(|_| !clickhouse) {
                 if !first {
                     ts.comma().space();
                 }
                 first = false;
-                ts.append(&constraint.to_tokens(dialect));
+                ts.append(&constraint.constraint_tokens(dialect, columnstore));
+            }
+
+            if columnstore {
+                if !first {
+                    ts.comma().space();
+                }
+                ts.push(Token::Index)
+                    .space()
+                    .push(Token::Ident(format!("cci_{}", self.name)))
+                    .space()
+                    .push(Token::Raw("CLUSTERED COLUMNSTORE".into()));
             }
 
             ts.rparen();
+            self.emit_options(&mut ts, dialect);
         }
 
         ts
     }
+
+    /// Primary key columns, declared inline or as a table constraint.
+    fn primary_key_columns(&self) -> Vec<String> {
+        let inline = self
+            .columns
+            .iter()
+            .filter(|c| {
+                c.constraints
+                    .iter()
+                    .any(|k| matches!(k, ColumnConstraint::PrimaryKey))
+            })
+            .map(|c| c.name.clone());
+        let table = self.constraints.iter().flat_map(|c| match c {
+            TableConstraint::PrimaryKey { columns, .. } => columns.clone(),
+            _ => Vec::new(),
+        });
+        inline.chain(table).collect()
+    }
+
+    /// Declared type of the partition column.
+    fn partition_type(&self) -> Option<&DataType> {
+        let name = self.options.partition_by.first()?;
+        self.columns
+            .iter()
+            .find(|c| &c.name == name)
+            .map(|c| &c.data_type)
+    }
+
+    /// Emit the table options and inline table comment that follow the column
+    /// list, or precede `AS` in CREATE TABLE AS SELECT.
+    fn emit_options(&self, ts: &mut TokenStream, dialect: DialectTarget) {
+        let options = &self.options;
+        match dialect.dialect {
+            Dialect::BigQuery => {
+                if let Some(column) = options.partition_by.first() {
+                    ts.space().push(Token::PartitionBy).space();
+                    self.emit_bigquery_partition(ts, column);
+                }
+                if !options.cluster_by.is_empty() {
+                    ts.space().push(Token::Raw("CLUSTER BY".into())).space();
+                    for (i, column) in options.cluster_by.iter().enumerate() {
+                        if i > 0 {
+                            ts.comma().space();
+                        }
+                        ts.push(Token::Ident(column.clone()));
+                    }
+                }
+                if let Some(ref comment) = self.comment {
+                    ts.space();
+                    emit_description_option(ts, comment);
+                }
+            }
+            Dialect::Snowflake => {
+                if !options.cluster_by.is_empty() {
+                    ts.space().push(Token::Raw("CLUSTER BY".into())).space();
+                    emit_column_list(ts, &options.cluster_by);
+                }
+                emit_comment_clause(ts, self.comment.as_deref(), true);
+            }
+            Dialect::Databricks => {
+                if let Some(format) = options.format {
+                    ts.space()
+                        .push(Token::Using)
+                        .space()
+                        .push(Token::Raw(format.to_string()));
+                }
+                if !options.partition_by.is_empty() {
+                    ts.space().push(Token::Raw("PARTITIONED BY".into())).space();
+                    emit_column_list(ts, &options.partition_by);
+                }
+                if !options.cluster_by.is_empty() {
+                    ts.space().push(Token::Raw("CLUSTER BY".into())).space();
+                    emit_column_list(ts, &options.cluster_by);
+                }
+                emit_comment_clause(ts, self.comment.as_deref(), false);
+            }
+            Dialect::Redshift => {
+                match options.distribution {
+                    Some(Distribution::Key(ref column)) => {
+                        ts.space()
+                            .push(Token::Raw("DISTSTYLE KEY DISTKEY".into()))
+                            .space()
+                            .lparen()
+                            .push(Token::Ident(column.clone()))
+                            .rparen();
+                    }
+                    Some(ref style) => {
+                        let style = match style {
+                            Distribution::Auto => "AUTO",
+                            Distribution::Even => "EVEN",
+                            _ => "ALL",
+                        };
+                        ts.space().push(Token::Raw(format!("DISTSTYLE {}", style)));
+                    }
+                    None => {}
+                }
+                if !options.sort_key.is_empty() {
+                    ts.space().push(Token::Raw("SORTKEY".into())).space();
+                    emit_column_list(ts, &options.sort_key);
+                }
+            }
+            Dialect::MySql => emit_comment_clause(ts, self.comment.as_deref(), true),
+            Dialect::ClickHouse => {
+                // MergeTree sorts by the primary key; keyless tables use an empty sort key
+                ts.space()
+                    .push(Token::Raw("ENGINE = MergeTree ORDER BY".into()))
+                    .space();
+                let primary_key = self.primary_key_columns();
+                if primary_key.is_empty() {
+                    ts.push(Token::Raw("tuple()".into()));
+                } else {
+                    emit_column_list(ts, &primary_key);
+                }
+                emit_comment_clause(ts, self.comment.as_deref(), false);
+            }
+            Dialect::Trino => emit_comment_clause(ts, self.comment.as_deref(), false),
+            _ => {}
+        }
+    }
+
+    /// BigQuery partitions by a DATE column or a truncated timestamp.
+    fn emit_bigquery_partition(&self, ts: &mut TokenStream, column: &str) {
+        let is_timestamp = matches!(
+            self.partition_type(),
+            Some(DataType::Timestamp | DataType::TimestampTz)
+        );
+        let column = Token::Ident(column.to_string());
+        match (self.options.partition_granularity, is_timestamp) {
+            (None | Some(PartitionGranularity::Day), false) => {
+                ts.push(column);
+            }
+            (None, true) => {
+                ts.push(Token::Raw("DATE".into()))
+                    .lparen()
+                    .push(column)
+                    .rparen();
+            }
+            (Some(granularity), _) => {
+                let function = if is_timestamp {
+                    "TIMESTAMP_TRUNC"
+                } else {
+                    "DATE_TRUNC"
+                };
+                ts.push(Token::Raw(function.into()))
+                    .lparen()
+                    .push(column)
+                    .comma()
+                    .space()
+                    .push(Token::Raw(granularity.to_string()))
+                    .rparen();
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -203,6 +661,7 @@ pub struct ColumnDef {
     pub nullable: Option<bool>,
     pub default: Option<Expr>,
     pub constraints: Vec<ColumnConstraint>,
+    pub comment: Option<String>,
 }
 
 impl ColumnDef {
@@ -214,6 +673,7 @@ impl ColumnDef {
             nullable: None,
             default: None,
             constraints: Vec::new(),
+            comment: None,
         }
     }
 
@@ -278,27 +738,45 @@ impl ColumnDef {
         self
     }
 
+    /// Set the column comment.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        self.column_tokens(dialect.into(), false)
+    }
+
+    /// Column tokens, with a NONCLUSTERED primary key next to a T-SQL columnstore.
+    fn column_tokens(&self, dialect: DialectTarget, nonclustered_key: bool) -> TokenStream {
         let mut ts = TokenStream::new();
 
         // Column name
         ts.push(Token::Ident(self.name.clone()));
 
-        // Data type
-        ts.space()
-            .push(Token::Raw(dialect.emit_data_type(&self.data_type)));
+        // Data type; ClickHouse spells nullability in the type and has no column constraints
+        let clickhouse = dialect == Dialect::ClickHouse;
+        let data_type = if clickhouse && self.nullable != Some(false) {
+            helpers::emit_nullable_type_clickhouse(&self.data_type)
+        } else {
+            dialect.emit_data_type(&self.data_type)
+        };
+        ts.space().push(Token::Raw(data_type));
 
         // Identity/auto-increment (needs to come before NOT NULL for some dialects)
         for constraint in &self.constraints {
             if let ColumnConstraint::Identity { start, increment } = constraint {
-                ts.space()
-                    .append(&dialect.emit_identity(*start, *increment));
+                let identity = dialect.emit_identity(*start, *increment);
+                if !identity.tokens().is_empty() {
+                    ts.space().append(&identity);
+                }
             }
         }
 
         // NULL/NOT NULL
-        if let Some(nullable) = self.nullable {
+        if let Some(nullable) = self.nullable.filter(|_| !clickhouse) {
             if nullable {
                 ts.space().push(Token::Null);
             } else {
@@ -315,10 +793,13 @@ impl ColumnDef {
         }
 
         // Other constraints
-        for constraint in &self.constraints {
+        for constraint in self.constraints.iter().filter(|_| !clickhouse) {
             match constraint {
                 ColumnConstraint::PrimaryKey => {
                     ts.space().push(Token::Primary).space().push(Token::Key);
+                    if nonclustered_key {
+                        ts.space().push(Token::Raw("NONCLUSTERED".into()));
+                    }
                 }
                 ColumnConstraint::Unique => {
                     ts.space().push(Token::Unique);
@@ -346,6 +827,22 @@ impl ColumnDef {
             }
         }
 
+        // COMMENT (dialects with inline column comments)
+        if let Some(ref comment) = self.comment {
+            match dialect.dialect {
+                Dialect::BigQuery => {
+                    ts.space();
+                    emit_description_option(&mut ts, comment);
+                }
+                Dialect::MySql
+                | Dialect::Snowflake
+                | Dialect::Databricks
+                | Dialect::ClickHouse
+                | Dialect::Trino => emit_comment_clause(&mut ts, Some(comment), false),
+                _ => {}
+            }
+        }
+
         ts
     }
 }
@@ -439,7 +936,12 @@ impl TableConstraint {
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        self.constraint_tokens(dialect.into(), false)
+    }
+
+    /// Constraint tokens, with a NONCLUSTERED primary key next to a T-SQL columnstore.
+    fn constraint_tokens(&self, dialect: DialectTarget, nonclustered_key: bool) -> TokenStream {
         let mut ts = TokenStream::new();
 
         match self {
@@ -451,6 +953,9 @@ impl TableConstraint {
                         .space();
                 }
                 ts.push(Token::Primary).space().push(Token::Key).space();
+                if nonclustered_key {
+                    ts.push(Token::Raw("NONCLUSTERED".into())).space();
+                }
                 emit_column_list(&mut ts, columns);
             }
             TableConstraint::Unique { name, columns } => {
@@ -534,7 +1039,7 @@ pub enum ReferentialAction {
 
 impl ReferentialAction {
     /// Convert to token stream.
-    pub fn to_tokens(&self, _dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, _dialect: impl Into<DialectTarget>) -> TokenStream {
         let mut ts = TokenStream::new();
         match self {
             ReferentialAction::NoAction => ts.push(Token::NoAction),
@@ -611,13 +1116,108 @@ impl AlterTable {
         self
     }
 
+    /// Change a column's data type, leaving it nullable and without a default.
+    pub fn alter_column_type(self, name: impl Into<String>, data_type: DataType) -> Self {
+        self.alter_column(ColumnDef::new(name, data_type))
+    }
+
+    /// Change a column's data type, restating the rest of its definition.
+    ///
+    /// MySQL replaces the whole definition (nullability, default) and T-SQL
+    /// resets nullability, so `column` is the column as it should end up.
+    /// SQLite cannot change types; rebuild the table instead.
+    pub fn alter_column(mut self, column: ColumnDef) -> Self {
+        self.actions.push(AlterAction::AlterColumnType(column));
+        self
+    }
+
+    /// Check that the dialect can apply every action.
+    pub fn validate(&self, dialect: impl Into<DialectTarget>) -> DdlResult<()> {
+        let dialect = dialect.into();
+        let alters_type = self
+            .actions
+            .iter()
+            .any(|a| matches!(a, AlterAction::AlterColumnType(_)));
+        if alters_type && dialect == Dialect::Sqlite {
+            return Err(DdlError::UnsupportedOption {
+                option: "ALTER COLUMN TYPE",
+                dialect,
+            });
+        }
+        Ok(())
+    }
+
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    ///
+    /// Actions the dialect cannot express are skipped; [`AlterTable::validate`]
+    /// reports them instead.
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    ///
+    /// T-SQL renames columns with `sp_rename`, emitted as separate statements
+    /// after the ALTER TABLE (if any actions remain for it).
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
+        let (renames, actions): (Vec<&AlterAction>, Vec<&AlterAction>) =
+            self.actions.iter().partition(|a| {
+                dialect == Dialect::TSql && matches!(a, AlterAction::RenameColumn { .. })
+            });
+        let actions: Vec<&AlterAction> = actions
+            .into_iter()
+            .filter(|a| {
+                !(dialect == Dialect::Sqlite && matches!(a, AlterAction::AlterColumnType(_)))
+            })
+            .collect();
+
+        let mut statements = Vec::new();
+        if !actions.is_empty() {
+            statements.push(self.alter_tokens(&actions, dialect));
+        }
+        for rename in renames {
+            if let AlterAction::RenameColumn { from, to } = rename {
+                statements.push(self.sp_rename_tokens(from, to, dialect));
+            }
+        }
+
+        let mut ts = TokenStream::new();
+        for (i, statement) in statements.iter().enumerate() {
+            if i > 0 {
+                ts.push(Token::Raw(";".into())).push(Token::Newline);
+            }
+            ts.append(statement);
+        }
+        ts
+    }
+
+    /// `EXEC sp_rename 'schema.table.from', 'to', 'COLUMN'`.
+    fn sp_rename_tokens(&self, from: &str, to: &str, dialect: DialectTarget) -> TokenStream {
+        let object = self
+            .schema
+            .iter()
+            .chain([&self.name])
+            .map(|part| dialect.quote_identifier(part))
+            .chain([dialect.quote_identifier(from)])
+            .collect::<Vec<_>>()
+            .join(".");
+        let mut ts = TokenStream::new();
+        ts.push(Token::Raw("EXEC sp_rename".into()))
+            .space()
+            .push(Token::LitString(object))
+            .comma()
+            .space()
+            .push(Token::LitString(to.into()))
+            .comma()
+            .space()
+            .push(Token::LitString("COLUMN".into()));
+        ts
+    }
+
+    /// `ALTER TABLE name action, ...`.
+    fn alter_tokens(&self, actions: &[&AlterAction], dialect: DialectTarget) -> TokenStream {
         let mut ts = TokenStream::new();
 
         ts.push(Token::Alter).space().push(Token::Table).space();
@@ -633,7 +1233,7 @@ impl AlterTable {
 
         // Actions
         let mut first = true;
-        for action in &self.actions {
+        for action in actions {
             if !first {
                 ts.comma();
             }
@@ -653,11 +1253,15 @@ pub enum AlterAction {
     AddConstraint(TableConstraint),
     DropConstraint { name: String },
     RenameColumn { from: String, to: String },
+    /// Change a column's type; the rest of the definition is restated where
+    /// the dialect requires it.
+    AlterColumnType(ColumnDef),
 }
 
 impl AlterAction {
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         match self {
@@ -699,6 +1303,54 @@ impl AlterAction {
                     .space()
                     .push(Token::Ident(to.clone()));
             }
+            AlterAction::AlterColumnType(column) => {
+                let name = Token::Ident(column.name.clone());
+                let data_type = Token::Raw(dialect.emit_data_type(&column.data_type));
+                match dialect.dialect {
+                    // MODIFY COLUMN replaces the whole column definition
+                    Dialect::MySql | Dialect::ClickHouse => {
+                        ts.push(Token::Raw("MODIFY COLUMN".into()))
+                            .space()
+                            .append(&column.to_tokens(dialect));
+                    }
+                    // ALTER COLUMN makes the column nullable unless told otherwise
+                    Dialect::TSql => {
+                        ts.push(Token::Raw("ALTER COLUMN".into()))
+                            .space()
+                            .push(name)
+                            .space()
+                            .push(data_type)
+                            .space();
+                        if column.nullable == Some(false) {
+                            ts.push(Token::Not).space();
+                        }
+                        ts.push(Token::Null);
+                    }
+                    Dialect::Postgres
+                    | Dialect::DuckDb
+                    | Dialect::Redshift
+                    | Dialect::Databricks => {
+                        ts.push(Token::Raw("ALTER COLUMN".into()))
+                            .space()
+                            .push(name)
+                            .space()
+                            .push(Token::Raw("TYPE".into()))
+                            .space()
+                            .push(data_type);
+                    }
+                    Dialect::Snowflake | Dialect::BigQuery | Dialect::Trino => {
+                        ts.push(Token::Raw("ALTER COLUMN".into()))
+                            .space()
+                            .push(name)
+                            .space()
+                            .push(Token::Raw("SET DATA TYPE".into()))
+                            .space()
+                            .push(data_type);
+                    }
+                    // No in-place type changes; AlterTable skips and reports these
+                    Dialect::Sqlite => {}
+                }
+            }
         }
 
         ts
@@ -749,12 +1401,14 @@ impl DropTable {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         ts.push(Token::Drop).space().push(Token::Table);
@@ -857,12 +1511,14 @@ impl CreateIndex {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         ts.push(Token::Create);
@@ -1009,12 +1665,14 @@ impl DropIndex {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         ts.push(Token::Drop).space().push(Token::Index);
@@ -1063,6 +1721,7 @@ impl DropIndex {
 ///
 /// // PostgreSQL/DuckDB: TRUNCATE TABLE "etl"."staging_orders" CASCADE
 /// // T-SQL/MySQL: TRUNCATE TABLE [etl].[staging_orders] (no CASCADE)
+/// // SQLite: DELETE FROM "etl"."staging_orders" (no TRUNCATE)
 /// ```
 #[derive(Debug, Clone)]
 #[must_use = "DDL statements have no effect until converted to SQL with to_sql()"]
@@ -1098,15 +1757,21 @@ impl Truncate {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
-        ts.push(Token::Truncate).space().push(Token::Table).space();
+        if dialect.supports_truncate() {
+            ts.push(Token::Truncate).space().push(Token::Table).space();
+        } else {
+            ts.push(Token::Delete).space().push(Token::From).space();
+        }
 
         if let Some(ref schema) = self.schema {
             ts.push(Token::QualifiedIdent {
@@ -1154,6 +1819,7 @@ pub struct CreateView {
     pub name: String,
     pub columns: Vec<String>,
     pub as_query: Box<Query>,
+    pub comment: Option<String>,
 }
 
 impl CreateView {
@@ -1167,6 +1833,7 @@ impl CreateView {
             name: name.into(),
             columns: Vec::new(),
             as_query: Box::new(query),
+            comment: None,
         }
     }
 
@@ -1206,13 +1873,21 @@ impl CreateView {
         self
     }
 
+    /// Set the view comment.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // CREATE
@@ -1258,6 +1933,21 @@ impl CreateView {
             emit_column_list(&mut ts, &self.columns);
         }
 
+        // Inline comment
+        match dialect.dialect {
+            Dialect::BigQuery => {
+                if let Some(ref comment) = self.comment {
+                    ts.space();
+                    emit_description_option(&mut ts, comment);
+                }
+            }
+            Dialect::Snowflake => emit_comment_clause(&mut ts, self.comment.as_deref(), true),
+            Dialect::Databricks | Dialect::Trino => {
+                emit_comment_clause(&mut ts, self.comment.as_deref(), false)
+            }
+            _ => {}
+        }
+
         // AS query
         ts.space()
             .push(Token::As)
@@ -1266,6 +1956,28 @@ impl CreateView {
 
         ts
     }
+
+    /// Statements that set the view comment, to run after [`CreateView::to_sql`].
+    ///
+    /// Empty where the comment is part of the CREATE statement.
+    pub fn comment_statements(&self, dialect: impl Into<DialectTarget>) -> Vec<String> {
+        let dialect = dialect.into();
+        let object = if self.materialized && dialect.supports_materialized_view() {
+            "MATERIALIZED VIEW"
+        } else {
+            "VIEW"
+        };
+        comment_statements(
+            dialect,
+            CommentTarget {
+                object,
+                schema: self.schema.as_deref(),
+                name: &self.name,
+            },
+            self.comment.as_deref(),
+            &[],
+        )
+    }
 }
 
 // ============================================================================
@@ -1330,12 +2042,14 @@ impl DropView {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         ts.push(Token::Drop);
@@ -1376,6 +2090,142 @@ impl DropView {
 // Helpers
 // ============================================================================
 
+/// Table or view name, schema-qualified when a schema is set.
+fn object_name(schema: Option<&str>, name: &str) -> Token {
+    match schema {
+        Some(schema) => Token::QualifiedIdent {
+            schema: Some(schema.to_string()),
+            name: name.to_string(),
+        },
+        None => Token::Ident(name.to_string()),
+    }
+}
+
+/// Inline `COMMENT 'text'`, or `COMMENT = 'text'` for MySQL and Snowflake tables.
+fn emit_comment_clause(ts: &mut TokenStream, comment: Option<&str>, equals: bool) {
+    let Some(comment) = comment else {
+        return;
+    };
+    ts.space().push(Token::Raw("COMMENT".into())).space();
+    if equals {
+        ts.push(Token::Eq).space();
+    }
+    ts.push(Token::LitString(comment.to_string()));
+}
+
+/// BigQuery `OPTIONS(description = 'text')`.
+fn emit_description_option(ts: &mut TokenStream, comment: &str) {
+    ts.push(Token::Raw("OPTIONS".into()))
+        .lparen()
+        .push(Token::Raw("description".into()))
+        .space()
+        .push(Token::Eq)
+        .space()
+        .push(Token::LitString(comment.to_string()))
+        .rparen();
+}
+
+/// The object a comment statement describes.
+struct CommentTarget<'a> {
+    /// `TABLE`, `VIEW` or `MATERIALIZED VIEW`
+    object: &'static str,
+    schema: Option<&'a str>,
+    name: &'a str,
+}
+
+/// Follow-up statements for dialects without inline comments: `COMMENT ON`
+/// for PostgreSQL, DuckDB and Redshift, `sp_addextendedproperty` for T-SQL.
+fn comment_statements(
+    dialect: DialectTarget,
+    target: CommentTarget<'_>,
+    comment: Option<&str>,
+    columns: &[(&str, &str)],
+) -> Vec<String> {
+    let mut statements = Vec::new();
+    match dialect.dialect {
+        Dialect::Postgres | Dialect::DuckDb | Dialect::Redshift => {
+            let name = object_name(target.schema, target.name);
+            let described = comment
+                .map(|c| (target.object, None, c))
+                .into_iter()
+                .chain(columns.iter().map(|(col, c)| ("COLUMN", Some(*col), *c)));
+            for (object, column, comment) in described {
+                let mut ts = TokenStream::new();
+                ts.push(Token::Raw("COMMENT".into()))
+                    .space()
+                    .push(Token::On)
+                    .space()
+                    .push(Token::Raw(object.into()))
+                    .space()
+                    .push(name.clone());
+                if let Some(column) = column {
+                    ts.push(Token::Dot).push(Token::Ident(column.to_string()));
+                }
+                ts.space()
+                    .push(Token::Raw("IS".into()))
+                    .space()
+                    .push(Token::LitString(comment.to_string()));
+                statements.push(ts.serialize(dialect));
+            }
+        }
+        // The table comment is inline; only CREATE TABLE AS SELECT columns
+        // get here
+        Dialect::Snowflake => {
+            let name = object_name(target.schema, target.name);
+            for (column, comment) in columns {
+                let mut ts = TokenStream::new();
+                ts.push(Token::Raw("COMMENT ON COLUMN".into()))
+                    .space()
+                    .push(name.clone())
+                    .push(Token::Dot)
+                    .push(Token::Ident(column.to_string()))
+                    .space()
+                    .push(Token::Raw("IS".into()))
+                    .space()
+                    .push(Token::LitString(comment.to_string()));
+                statements.push(ts.serialize(dialect));
+            }
+        }
+        Dialect::TSql => {
+            let schema = target.schema.unwrap_or("dbo");
+            let described = comment
+                .map(|c| (None, c))
+                .into_iter()
+                .chain(columns.iter().map(|(col, c)| (Some(*col), *c)));
+            for (column, comment) in described {
+                let mut args = vec![
+                    ("name", "MS_Description"),
+                    ("value", comment),
+                    ("level0type", "SCHEMA"),
+                    ("level0name", schema),
+                    ("level1type", target.object),
+                    ("level1name", target.name),
+                ];
+                if let Some(column) = column {
+                    args.push(("level2type", "COLUMN"));
+                    args.push(("level2name", column));
+                }
+                let mut ts = TokenStream::new();
+                ts.push(Token::Raw("EXEC sp_addextendedproperty".into()));
+                for (i, (param, value)) in args.into_iter().enumerate() {
+                    if i > 0 {
+                        ts.comma();
+                    }
+                    ts.space()
+                        .push(Token::Raw(format!("@{}", param)))
+                        .space()
+                        .push(Token::Eq)
+                        .space()
+                        .push(Token::LitString(value.to_string()));
+                }
+                statements.push(ts.serialize(dialect));
+            }
+        }
+        _ => {}
+    }
+    statements
+}
+
 fn emit_column_list(ts: &mut TokenStream, columns: &[String]) {
     ts.lparen();
     let mut first = true;
@@ -1396,7 +2246,9 @@ fn emit_column_list(ts: &mut TokenStream, columns: &[String]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sql::dialect::{Dialect, Version};
     use crate::sql::expr::{col, lit_int, ExprExt};
+    use crate::sql::query::TableRef;
 
     #[test]
     fn test_create_table_basic() {
@@ -1418,6 +2270,17 @@ mod tests {
         assert!(sql.contains("PRIMARY KEY"));
     }
 
+    #[test]
+    fn test_create_table_parameterized_is_unbound() {
+        let table = CreateTable::new("users")
+            .column(ColumnDef::new("active", DataType::Int32).default(lit_int(1)));
+
+        let sql = table.to_sql_parameterized(Dialect::Postgres);
+        assert_eq!(sql.sql, table.to_sql(Dialect::Postgres));
+        assert!(sql.sql.contains("DEFAULT 1"));
+        assert!(sql.params.is_empty());
+    }
+
     #[test]
     fn test_create_table_if_not_exists() {
         let table = CreateTable::new("users")
@@ -1480,6 +2343,67 @@ mod tests {
         assert!(sql.contains("DROP COLUMN"));
     }
 
+    #[test]
+    fn test_alter_table_alter_column_type() {
+        let alter = AlterTable::new("users").alter_column_type("age", DataType::Int64);
+
+        assert_eq!(
+            alter.to_sql(Dialect::Postgres),
+            "ALTER TABLE \"users\" ALTER COLUMN \"age\" TYPE BIGINT"
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::MySql),
+            "ALTER TABLE `users` MODIFY COLUMN `age` BIGINT"
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::Snowflake),
+            "ALTER TABLE \"users\" ALTER COLUMN \"age\" SET DATA TYPE BIGINT"
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::TSql),
+            "ALTER TABLE [users] ALTER COLUMN [age] BIGINT NULL"
+        );
+        assert!(alter.validate(Dialect::Sqlite).is_err());
+        assert!(!alter.to_sql(Dialect::Sqlite).contains("DATA TYPE"));
+    }
+
+    #[test]
+    fn test_alter_column_restates_definition() {
+        let alter = AlterTable::new("users").alter_column(
+            ColumnDef::new("age", DataType::Int64)
+                .not_null()
+                .default(lit_int(0)),
+        );
+
+        assert_eq!(
+            alter.to_sql(Dialect::MySql),
+            "ALTER TABLE `users` MODIFY COLUMN `age` BIGINT NOT NULL DEFAULT 0"
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::TSql),
+            "ALTER TABLE [users] ALTER COLUMN [age] BIGINT NOT NULL"
+        );
+        assert_eq!(
+            alter.to_sql(Dialect::Postgres),
+            "ALTER TABLE \"users\" ALTER COLUMN \"age\" TYPE BIGINT"
+        );
+    }
+
+    #[test]
+    fn test_rename_column_tsql() {
+        let alter = AlterTable::new("users")
+            .schema("dbo")
+            .rename_column("fullname", "full_name");
+
+        assert_eq!(
+            alter.to_sql(Dialect::TSql),
+            "EXEC sp_rename '[dbo].[users].[fullname]', 'full_name', 'COLUMN'"
+        );
+        assert!(alter
+            .to_sql(Dialect::Postgres)
+            .contains("RENAME COLUMN \"fullname\" TO \"full_name\""));
+    }
+
     #[test]
     fn test_create_index() {
         let index = CreateIndex::new("idx_users_email", "users")
@@ -1576,6 +2500,150 @@ mod tests {
         assert!(sql.contains("AUTO_INCREMENT"));
     }
 
+    #[test]
+    fn test_create_table_validate_options() {
+        let table = CreateTable::new("orders")
+            .column(ColumnDef::new("order_date", DataType::Date))
+            .column(ColumnDef::new("customer_id", DataType::Int64));
+        let clustered = table
+            .clone()
+            .options(TableOptions::new().with_cluster_by(vec!["customer_id"]));
+
+        assert!(clustered.validate(Dialect::Snowflake).is_ok());
+        assert_eq!(
+            clustered.validate(Dialect::Postgres),
+            Err(DdlError::UnsupportedOption {
+                option: "CLUSTER BY",
+                dialect: Dialect::Postgres.into(),
+            })
+        );
+
+        let unknown = table
+            .clone()
+            .options(TableOptions::new().with_sort_key(vec!["shipped_at"]));
+        assert_eq!(
+            unknown.validate(Dialect::Redshift),
+            Err(DdlError::UnknownColumn {
+                option: "SORTKEY",
+                column: "shipped_at".into(),
+            })
+        );
+
+        let two_partitions = table
+            .clone()
+            .options(TableOptions::new().with_partition_by(vec!["order_date", "customer_id"]));
+        assert!(two_partitions.validate(Dialect::BigQuery).is_err());
+        assert!(two_partitions.validate(Dialect::Databricks).is_ok());
+
+        let hourly = table.clone().options(
+            TableOptions::new()
+                .with_partition_by(vec!["order_date"])
+                .with_partition_granularity(PartitionGranularity::Hour),
+        );
+        assert!(hourly.validate(Dialect::BigQuery).is_err());
+
+        let liquid = table.clone().options(
+            TableOptions::new()
+                .with_partition_by(vec!["order_date"])
+                .with_cluster_by(vec!["customer_id"]),
+        );
+        assert!(liquid.validate(Dialect::Databricks).is_err());
+
+        let columnstore = table.options(TableOptions::new().with_columnstore());
+        assert!(columnstore.validate(Dialect::TSql).is_ok());
+        assert!(columnstore
+            .validate(Dialect::TSql.at(Version::sql_server(2012)))
+            .is_err());
+    }
+
+    #[test]
+    fn test_create_table_skips_unsupported_options() {
+        let table = CreateTable::new("orders")
+            .column(ColumnDef::new("customer_id", DataType::Int64))
+            .options(
+                TableOptions::new()
+                    .with_cluster_by(vec!["customer_id"])
+                    .with_distribution(Distribution::Even),
+            );
+
+        assert_eq!(
+            table.to_sql(Dialect::Postgres),
+            "CREATE TABLE \"orders\" (\"customer_id\" BIGINT)"
+        );
+    }
+
+    #[test]
+    fn test_column_comments() {
+        let table = CreateTable::new("orders")
+            .column(ColumnDef::new("order_id", DataType::Int64))
+            .column(ColumnDef::new("total", DataType::Decimal(10, 2)))
+            .column_comments([
+                ("total".to_string(), "Order total".to_string()),
+                ("missing".to_string(), "Ignored".to_string()),
+            ]);
+
+        assert_eq!(table.columns[0].comment, None);
+        assert_eq!(table.columns[1].comment.as_deref(), Some("Order total"));
+        assert_eq!(table.comment_statements(Dialect::Postgres).len(), 1);
+
+        // CREATE TABLE AS SELECT comments the query's columns afterwards
+        let ctas = CreateTable::new("orders")
+            .as_select(
+                Query::new()
+                    .select(vec![col("total")])
+                    .from(TableRef::new("raw")),
+            )
+            .column_comments([("total".to_string(), "Order total".to_string())]);
+        assert_eq!(
+            ctas.comment_statements(Dialect::Postgres),
+            vec!["COMMENT ON COLUMN \"orders\".\"total\" IS 'Order total'"]
+        );
+        assert_eq!(
+            ctas.comment_statements(Dialect::Snowflake),
+            vec!["COMMENT ON COLUMN \"orders\".\"total\" IS 'Order total'"]
+        );
+        assert!(table.comment_statements(Dialect::Snowflake).is_empty());
+    }
+
+    #[test]
+    fn test_view_comment_statements() {
+        let query = Query::new()
+            .select(vec![col("order_id")])
+            .from(crate::sql::query::TableRef::new("orders"));
+        let view = CreateView::new("recent_orders", query)
+            .materialized()
+            .comment("Orders in the last week");
+
+        let sql = view.to_sql(Dialect::Postgres);
+        assert!(!sql.contains("COMMENT"), "{}", sql);
+        assert_eq!(
+            view.comment_statements(Dialect::Postgres),
+            vec!["COMMENT ON MATERIALIZED VIEW \"recent_orders\" IS 'Orders in the last week'"]
+        );
+        assert!(view.comment_statements(Dialect::Snowflake).is_empty());
+    }
+
+    #[test]
+    fn test_create_table_clickhouse_engine() {
+        let table = CreateTable::new("orders")
+            .column(ColumnDef::new("order_id", DataType::Int64))
+            .column(ColumnDef::new("region", DataType::Varchar(20)).not_null())
+            .column(ColumnDef::new("note", DataType::String))
+            .constraint(TableConstraint::primary_key(["order_id"]));
+        assert_eq!(
+            table.to_sql(Dialect::ClickHouse),
+            "CREATE TABLE `orders` (`order_id` Int64, `region` String, `note` Nullable(String)) \
+             ENGINE = MergeTree ORDER BY (`order_id`)"
+        );
+
+        let keyless = CreateTable::new("events")
+            .column(ColumnDef::new("kind", DataType::Varchar(20)).unique());
+        assert_eq!(
+            keyless.to_sql(Dialect::ClickHouse),
+            "CREATE TABLE `events` (`kind` Nullable(String)) ENGINE = MergeTree ORDER BY tuple()"
+        );
+    }
+
     // ========================================================================
     // Snapshot tests with roundtrip validation
     // ========================================================================
@@ -1587,6 +2655,109 @@ mod tests {
         use crate::sql::test_utils::validate_sql;
         use insta::assert_snapshot;
 
+        // --------------------------------------------------------------------
+        // Table options and comments
+        // --------------------------------------------------------------------
+
+        /// The CREATE statement followed by its comment statements.
+        fn with_comments(table: &CreateTable, dialect: Dialect) -> String {
+            std::iter::once(table.to_sql(dialect))
+                .chain(table.comment_statements(dialect))
+                .collect::<Vec<_>>()
+                .join(";\n")
+        }
+
+        fn orders_table() -> CreateTable {
+            CreateTable::new("orders")
+                .column(ColumnDef::new("order_id", DataType::Int64).primary_key())
+                .column(ColumnDef::new("ordered_at", DataType::Timestamp))
+                .column(ColumnDef::new("customer_id", DataType::Int64))
+                .column(ColumnDef::new("total", DataType::Decimal(10, 2)).comment("Order total"))
+                .comment("One row per order")
+        }
+
+        #[test]
+        fn create_table_partitioned_bigquery() {
+            let sql = orders_table()
+                .options(
+                    TableOptions::new()
+                        .with_partition_by(vec!["ordered_at"])
+                        .with_partition_granularity(PartitionGranularity::Month)
+                        .with_cluster_by(vec!["customer_id"]),
+                )
+                .to_sql(Dialect::BigQuery);
+            assert_snapshot!(sql);
+        }
+
+        #[test]
+        fn create_table_clustered_snowflake() {
+            let sql = orders_table()
+                .options(TableOptions::new().with_cluster_by(vec!["customer_id", "ordered_at"]))
+                .to_sql(Dialect::Snowflake);
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Snowflake).unwrap();
+        }
+
+        #[test]
+        fn create_table_distributed_redshift() {
+            let table = orders_table().options(
+                TableOptions::new()
+                    .with_distribution(Distribution::Key("customer_id".into()))
+                    .with_sort_key(vec!["ordered_at"]),
+            );
+            let sql = with_comments(&table, Dialect::Redshift);
+            assert_snapshot!(sql);
+        }
+
+        #[test]
+        fn create_table_delta_databricks() {
+            let sql = orders_table()
+                .options(
+                    TableOptions::new()
+                        .with_format(TableFormat::Delta)
+                        .with_partition_by(vec!["customer_id"]),
+                )
+                .to_sql(Dialect::Databricks);
+            assert_snapshot!(sql);
+        }
+
+        #[test]
+        fn create_table_columnstore_tsql() {
+            let table = orders_table().options(TableOptions::new().with_columnstore());
+            let sql = with_comments(&table, Dialect::TSql);
+            assert_snapshot!(sql);
+        }
+
+        #[test]
+        fn create_table_comments_postgres() {
+            let table = orders_table().schema("sales");
+            assert!(!table.to_sql(Dialect::Postgres).contains("COMMENT"));
+            assert_eq!(table.comment_statements(Dialect::Postgres).len(), 2);
+            let sql = with_comments(&table, Dialect::Postgres);
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Postgres).unwrap();
+        }
+
+        #[test]
+        fn create_table_comments_mysql() {
+            let table = orders_table();
+            assert!(table.comment_statements(Dialect::MySql).is_empty());
+            let sql = table.to_sql(Dialect::MySql);
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::MySql).unwrap();
+        }
+
+        #[test]
+        fn create_view_comment_snowflake() {
+            let query = Query::new()
+                .select(vec![col("order_id")])
+                .from(TableRef::new("orders"));
+            let sql = CreateView::new("recent_orders", query)
+                .comment("Orders in the last week")
+                .to_sql(Dialect::Snowflake);
+            assert_snapshot!(sql);
+        }
+
         // --------------------------------------------------------------------
         // Truncate tests
         // --------------------------------------------------------------------
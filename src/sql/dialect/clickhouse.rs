@@ -0,0 +1,83 @@
+//! ClickHouse SQL dialect.
+//!
+//! ClickHouse differences from ANSI:
+//! - Backtick identifier quoting (`` `name` ``)
+//! - Backslash escapes in string literals
+//! - Native types with their own names (Int64, Float64, String, DateTime64)
+//! - LIMIT ... OFFSET ... for pagination
+//! - No auto-increment columns
+//! - No LATERAL joins, MERGE or RETURNING
+//! - DISTINCT ON and named windows supported
+//! - No native PIVOT (use conditional aggregation)
+
+use super::helpers;
+use super::SqlDialect;
+use crate::sql::token::TokenStream;
+
+/// ClickHouse SQL dialect.
+#[derive(Debug, Clone, Copy)]
+pub struct ClickHouse;
+
+impl SqlDialect for ClickHouse {
+    fn name(&self) -> &'static str {
+        "clickhouse"
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        helpers::quote_backtick(ident)
+    }
+
+    fn quote_string(&self, s: &str) -> String {
+        helpers::quote_string_backslash(s)
+    }
+
+    fn format_bool(&self, b: bool) -> &'static str {
+        helpers::format_bool_literal(b)
+    }
+
+    // Uses default emit_limit_offset (LIMIT ... OFFSET ...)
+
+    fn supports_lateral(&self) -> bool {
+        false
+    }
+
+    fn format_date_literal(&self, date: &str) -> String {
+        format!("toDate('{}')", date)
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn supports_distinct_on(&self) -> bool {
+        true
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        true
+    }
+
+    fn remap_function(&self, name: &str) -> Option<&'static str> {
+        helpers::remap_function_clickhouse(name)
+    }
+
+    fn emit_data_type(&self, dt: &crate::model::types::DataType) -> String {
+        helpers::emit_data_type_clickhouse(dt)
+    }
+
+    fn emit_identity(&self, start: i64, increment: i64) -> TokenStream {
+        helpers::emit_identity_none(start, increment)
+    }
+
+    fn supports_drop_cascade(&self) -> bool {
+        false
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        false
+    }
+
+    fn supports_include_columns(&self) -> bool {
+        false
+    }
+}
@@ -9,6 +9,7 @@
 
 use super::helpers;
 use super::SqlDialect;
+use crate::sql::params::Param;
 
 /// DuckDB SQL dialect.
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +28,14 @@ impl SqlDialect for DuckDb {
         helpers::format_bool_literal(b)
     }
 
+    fn placeholder(&self, index: usize, param: &Param) -> String {
+        helpers::placeholder_dollar(index, param, "DOUBLE")
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
     // Uses default emit_limit_offset (LIMIT ... OFFSET ...)
 
     fn supports_native_pivot(&self) -> bool {
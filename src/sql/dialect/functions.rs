@@ -0,0 +1,1553 @@
+//! Argument-aware translation of date/time and string function calls.
+//!
+//! [`SqlDialect::remap_function`] only renames a call. That is not enough for
+//! functions whose argument order, unit syntax or format-string language differs
+//! between databases, so the calls below are recognised by name and shape when an
+//! [`Expr::Function`] is rendered, and rewritten as a whole for the target dialect:
+//!
+//! | Canonical call | Meaning |
+//! |----------------|---------|
+//! | `DATE_TRUNC('unit', d)` | Start of the `unit` containing `d` |
+//! | `DATEADD('unit', n, d)` | `d` shifted by `n` units |
+//! | `DATEDIFF('unit', start, end)` | `unit` boundaries crossed from `start` to `end` (whole units on MySQL/Databricks) |
+//! | `EXTRACT('unit', d)` | The `unit` part of `d` as a number (ISO 8601 week for `week`) |
+//! | `LAST_DAY(d)` | Last day of the month containing `d` |
+//! | `STRFTIME(d, 'fmt')` | `d` formatted with a strftime pattern |
+//! | `TO_CHAR(d, 'fmt')` | `d` formatted with a PostgreSQL `TO_CHAR` pattern |
+//! | `LEFT(s, n)` / `RIGHT(s, n)` | The first / last `n` characters of `s` |
+//!
+//! The unit may be a string literal, or a bare identifier spelling the full unit
+//! name (`day`, not `d`, which is more likely a column). Calls that don't fit a
+//! shape (unknown unit, non-literal format, a format field the target can't
+//! express) fall back to plain renaming. Weeks start on Monday. Other string
+//! functions differ only in name and are handled by renaming alone.
+
+use super::{Dialect, DialectTarget, SqlDialect};
+use crate::model::types::DataType;
+use crate::sql::expr::{Expr, Literal};
+use crate::sql::token::{Token, TokenStream};
+
+// =============================================================================
+// Units
+// =============================================================================
+
+/// A calendar unit for date truncation, arithmetic and extraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateUnit {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateUnit {
+    /// Parse a unit name, accepting plurals and the T-SQL abbreviations.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "year" | "years" | "yy" | "yyyy" => Some(DateUnit::Year),
+            "quarter" | "quarters" | "qq" | "q" => Some(DateUnit::Quarter),
+            "month" | "months" | "mm" | "m" => Some(DateUnit::Month),
+            "week" | "weeks" | "wk" | "ww" => Some(DateUnit::Week),
+            "day" | "days" | "dd" | "d" => Some(DateUnit::Day),
+            "hour" | "hours" | "hh" => Some(DateUnit::Hour),
+            "minute" | "minutes" | "mi" | "n" => Some(DateUnit::Minute),
+            "second" | "seconds" | "ss" | "s" => Some(DateUnit::Second),
+            _ => None,
+        }
+    }
+
+    /// The unit as a bare keyword (`MONTH`).
+    pub fn keyword(self) -> &'static str {
+        match self {
+            DateUnit::Year => "YEAR",
+            DateUnit::Quarter => "QUARTER",
+            DateUnit::Month => "MONTH",
+            DateUnit::Week => "WEEK",
+            DateUnit::Day => "DAY",
+            DateUnit::Hour => "HOUR",
+            DateUnit::Minute => "MINUTE",
+            DateUnit::Second => "SECOND",
+        }
+    }
+
+    /// The unit as a lower-case name (`month`), for dialects that take it as a string.
+    pub fn name(self) -> &'static str {
+        match self {
+            DateUnit::Year => "year",
+            DateUnit::Quarter => "quarter",
+            DateUnit::Month => "month",
+            DateUnit::Week => "week",
+            DateUnit::Day => "day",
+            DateUnit::Hour => "hour",
+            DateUnit::Minute => "minute",
+            DateUnit::Second => "second",
+        }
+    }
+
+    fn is_time(self) -> bool {
+        matches!(self, DateUnit::Hour | DateUnit::Minute | DateUnit::Second)
+    }
+
+    /// Length in seconds of a time unit.
+    fn seconds(self) -> i64 {
+        match self {
+            DateUnit::Hour => 3600,
+            DateUnit::Minute => 60,
+            _ => 1,
+        }
+    }
+}
+
+// =============================================================================
+// Format Strings
+// =============================================================================
+
+/// A date format-string language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatStyle {
+    /// C `strftime` (`%Y-%m-%d %H:%M`): DuckDB, BigQuery
+    Strftime,
+    /// The part of strftime SQLite understands: no names, no 12-hour clock
+    Sqlite,
+    /// PostgreSQL `TO_CHAR` (`YYYY-MM-DD HH24:MI`): PostgreSQL, Redshift
+    ToChar,
+    /// Snowflake `TO_CHAR` (`YYYY-MM-DD`, `MMMM`, `DY`, `FF6`)
+    Snowflake,
+    /// .NET custom format (`yyyy-MM-dd HH:mm`): T-SQL `FORMAT`
+    DotNet,
+    /// MySQL `DATE_FORMAT` (`%Y-%m-%d %H:%i`): MySQL, Trino, ClickHouse
+    MySql,
+    /// Java `DateTimeFormatter` (`yyyy-MM-dd HH:mm`): Databricks
+    Java,
+}
+
+impl FormatStyle {
+    /// The format language of `dialect`'s date formatting function.
+    pub fn for_dialect(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::DuckDb | Dialect::BigQuery => FormatStyle::Strftime,
+            Dialect::Sqlite => FormatStyle::Sqlite,
+            Dialect::Postgres | Dialect::Redshift => FormatStyle::ToChar,
+            Dialect::Snowflake => FormatStyle::Snowflake,
+            Dialect::TSql => FormatStyle::DotNet,
+            Dialect::MySql | Dialect::ClickHouse | Dialect::Trino => FormatStyle::MySql,
+            Dialect::Databricks => FormatStyle::Java,
+        }
+    }
+
+    fn is_percent(self) -> bool {
+        matches!(
+            self,
+            FormatStyle::Strftime | FormatStyle::Sqlite | FormatStyle::MySql
+        )
+    }
+
+    /// The token for `field`, or `None` if the language can't express it.
+    fn token(self, field: Field) -> Option<&'static str> {
+        use Field::*;
+        let token = match self {
+            FormatStyle::Strftime => match field {
+                Year => "%Y",
+                Year2 => "%y",
+                Month => "%m",
+                MonthName => "%B",
+                MonthAbbr => "%b",
+                Day => "%d",
+                DayOfYear => "%j",
+                Weekday => "%A",
+                WeekdayAbbr => "%a",
+                Hour24 => "%H",
+                Hour12 => "%I",
+                Minute => "%M",
+                Second => "%S",
+                Micros => "%f",
+                Meridiem => "%p",
+            },
+            FormatStyle::Sqlite => match field {
+                Year => "%Y",
+                Month => "%m",
+                Day => "%d",
+                DayOfYear => "%j",
+                Hour24 => "%H",
+                Minute => "%M",
+                Second => "%S",
+                _ => return None,
+            },
+            FormatStyle::MySql => match field {
+                Year => "%Y",
+                Year2 => "%y",
+                Month => "%m",
+                MonthName => "%M",
+                MonthAbbr => "%b",
+                Day => "%d",
+                DayOfYear => "%j",
+                Weekday => "%W",
+                WeekdayAbbr => "%a",
+                Hour24 => "%H",
+                Hour12 => "%I",
+                Minute => "%i",
+                Second => "%S",
+                Micros => "%f",
+                Meridiem => "%p",
+            },
+            FormatStyle::ToChar => match field {
+                Year => "YYYY",
+                Year2 => "YY",
+                Month => "MM",
+                MonthName => "FMMonth",
+                MonthAbbr => "Mon",
+                Day => "DD",
+                DayOfYear => "DDD",
+                Weekday => "FMDay",
+                WeekdayAbbr => "Dy",
+                Hour24 => "HH24",
+                Hour12 => "HH12",
+                Minute => "MI",
+                Second => "SS",
+                Micros => "US",
+                Meridiem => "AM",
+            },
+            FormatStyle::Snowflake => match field {
+                Year => "YYYY",
+                Year2 => "YY",
+                Month => "MM",
+                MonthName => "MMMM",
+                MonthAbbr => "MON",
+                Day => "DD",
+                WeekdayAbbr => "DY",
+                Hour24 => "HH24",
+                Hour12 => "HH12",
+                Minute => "MI",
+                Second => "SS",
+                Micros => "FF6",
+                Meridiem => "AM",
+                DayOfYear | Weekday => return None,
+            },
+            FormatStyle::DotNet => match field {
+                Year => "yyyy",
+                Year2 => "yy",
+                Month => "MM",
+                MonthName => "MMMM",
+                MonthAbbr => "MMM",
+                Day => "dd",
+                Weekday => "dddd",
+                WeekdayAbbr => "ddd",
+                Hour24 => "HH",
+                Hour12 => "hh",
+                Minute => "mm",
+                Second => "ss",
+                Micros => "ffffff",
+                Meridiem => "tt",
+                DayOfYear => return None,
+            },
+            FormatStyle::Java => match field {
+                Year => "yyyy",
+                Year2 => "yy",
+                Month => "MM",
+                MonthName => "MMMM",
+                MonthAbbr => "MMM",
+                Day => "dd",
+                DayOfYear => "DDD",
+                Weekday => "EEEE",
+                WeekdayAbbr => "EEE",
+                Hour24 => "HH",
+                Hour12 => "hh",
+                Minute => "mm",
+                Second => "ss",
+                Micros => "SSSSSS",
+                Meridiem => "a",
+            },
+        };
+        Some(token)
+    }
+}
+
+/// A date/time component in a format string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Year,
+    Year2,
+    Month,
+    MonthName,
+    MonthAbbr,
+    Day,
+    DayOfYear,
+    Weekday,
+    WeekdayAbbr,
+    Hour24,
+    Hour12,
+    Minute,
+    Second,
+    Micros,
+    Meridiem,
+}
+
+const ALL_FIELDS: [Field; 15] = [
+    Field::Year,
+    Field::Year2,
+    Field::Month,
+    Field::MonthName,
+    Field::MonthAbbr,
+    Field::Day,
+    Field::DayOfYear,
+    Field::Weekday,
+    Field::WeekdayAbbr,
+    Field::Hour24,
+    Field::Hour12,
+    Field::Minute,
+    Field::Second,
+    Field::Micros,
+    Field::Meridiem,
+];
+
+/// TO_CHAR patterns, longest first. Matching is case-insensitive.
+const TO_CHAR_PATTERNS: [(&str, Field); 19] = [
+    ("MONTH", Field::MonthName),
+    ("MMMM", Field::MonthName),
+    ("YYYY", Field::Year),
+    ("HH24", Field::Hour24),
+    ("HH12", Field::Hour12),
+    ("FF6", Field::Micros),
+    ("DDD", Field::DayOfYear),
+    ("DAY", Field::Weekday),
+    ("MON", Field::MonthAbbr),
+    ("YY", Field::Year2),
+    ("MM", Field::Month),
+    ("DD", Field::Day),
+    ("DY", Field::WeekdayAbbr),
+    ("HH", Field::Hour12),
+    ("MI", Field::Minute),
+    ("SS", Field::Second),
+    ("US", Field::Micros),
+    ("AM", Field::Meridiem),
+    ("PM", Field::Meridiem),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+fn push_literal(parts: &mut Vec<Part>, text: &str) {
+    if let Some(Part::Literal(last)) = parts.last_mut() {
+        last.push_str(text);
+    } else {
+        parts.push(Part::Literal(text.to_string()));
+    }
+}
+
+/// Translate a date format string from one format language to another.
+///
+/// Returns `None` if the source has a token this module doesn't know, or uses
+/// a field the target language can't express (e.g. month names on SQLite).
+pub fn translate_format(fmt: &str, from: FormatStyle, to: FormatStyle) -> Option<String> {
+    if from == to {
+        return Some(fmt.to_string());
+    }
+    render_format(&parse_format(fmt, from)?, to)
+}
+
+fn parse_format(fmt: &str, style: FormatStyle) -> Option<Vec<Part>> {
+    let chars: Vec<char> = fmt.chars().collect();
+    let mut parts = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if style.is_percent() {
+            if c != '%' {
+                push_literal(&mut parts, &c.to_string());
+                i += 1;
+                continue;
+            }
+            let code = *chars.get(i + 1)?;
+            i += 2;
+            if code == '%' {
+                push_literal(&mut parts, "%");
+                continue;
+            }
+            let field = match (style, code) {
+                (FormatStyle::MySql, 'h') => Field::Hour12,
+                (FormatStyle::MySql, 's') => Field::Second,
+                _ => {
+                    let token = format!("%{}", code);
+                    *ALL_FIELDS
+                        .iter()
+                        .find(|f| style.token(**f) == Some(token.as_str()))?
+                }
+            };
+            parts.push(Part::Field(field));
+            continue;
+        }
+
+        match style {
+            FormatStyle::ToChar | FormatStyle::Snowflake => {
+                if c == '"' {
+                    let end = chars[i + 1..].iter().position(|&c| c == '"')? + i + 1;
+                    let text: String = chars[i + 1..end].iter().collect();
+                    push_literal(&mut parts, &text);
+                    i = end + 1;
+                    continue;
+                }
+                let rest: String = chars[i..].iter().collect::<String>().to_uppercase();
+                // FM only suppresses padding
+                if rest.starts_with("FM") {
+                    i += 2;
+                    continue;
+                }
+                match TO_CHAR_PATTERNS.iter().find(|(p, _)| rest.starts_with(p)) {
+                    Some((pattern, field)) => {
+                        parts.push(Part::Field(*field));
+                        i += pattern.len();
+                    }
+                    None => {
+                        push_literal(&mut parts, &c.to_string());
+                        i += 1;
+                    }
+                }
+            }
+            FormatStyle::DotNet | FormatStyle::Java => {
+                if c == '\'' || (c == '"' && style == FormatStyle::DotNet) {
+                    // Java writes a literal quote as '', inside or outside quoted text
+                    if style == FormatStyle::Java && chars.get(i + 1) == Some(&c) {
+                        push_literal(&mut parts, "'");
+                        i += 2;
+                        continue;
+                    }
+                    let mut text = String::new();
+                    i += 1;
+                    loop {
+                        let q = *chars.get(i)?;
+                        i += 1;
+                        if q != c {
+                            text.push(q);
+                        } else if style == FormatStyle::Java && chars.get(i) == Some(&c) {
+                            text.push(c);
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    push_literal(&mut parts, &text);
+                    continue;
+                }
+                if c == '\\' && style == FormatStyle::DotNet {
+                    push_literal(&mut parts, &chars.get(i + 1)?.to_string());
+                    i += 2;
+                    continue;
+                }
+                let run = chars[i..].iter().take_while(|&&r| r == c).count();
+                match letter_run_field(style, c, run) {
+                    Some(field) => parts.push(Part::Field(field)),
+                    None => push_literal(&mut parts, &chars[i..i + run].iter().collect::<String>()),
+                }
+                i += run;
+            }
+            _ => unreachable!("percent styles handled above"),
+        }
+    }
+
+    Some(parts)
+}
+
+/// Field for a run of `run` repeated `letter`s in a .NET or Java pattern.
+fn letter_run_field(style: FormatStyle, letter: char, run: usize) -> Option<Field> {
+    let field = match (letter, run) {
+        ('y', 2) => Field::Year2,
+        ('y', _) => Field::Year,
+        ('M', 1 | 2) => Field::Month,
+        ('M', 3) => Field::MonthAbbr,
+        ('M', _) => Field::MonthName,
+        ('d', 1 | 2) => Field::Day,
+        ('H', _) => Field::Hour24,
+        ('h', _) => Field::Hour12,
+        ('m', _) => Field::Minute,
+        ('s', _) => Field::Second,
+        _ => match style {
+            FormatStyle::DotNet => match (letter, run) {
+                ('d', 3) => Field::WeekdayAbbr,
+                ('d', _) => Field::Weekday,
+                ('f', _) => Field::Micros,
+                ('t', _) => Field::Meridiem,
+                _ => return None,
+            },
+            _ => match (letter, run) {
+                ('D', _) => Field::DayOfYear,
+                ('E', 1..=3) => Field::WeekdayAbbr,
+                ('E', _) => Field::Weekday,
+                ('S', _) => Field::Micros,
+                ('a', _) => Field::Meridiem,
+                _ => return None,
+            },
+        },
+    };
+    Some(field)
+}
+
+fn render_format(parts: &[Part], style: FormatStyle) -> Option<String> {
+    let mut out = String::new();
+    for part in parts {
+        match part {
+            Part::Field(field) => out.push_str(style.token(*field)?),
+            Part::Literal(text) => out.push_str(&quote_format_literal(text, style)),
+        }
+    }
+    Some(out)
+}
+
+/// Escape literal text so the target language copies it through unchanged.
+fn quote_format_literal(text: &str, style: FormatStyle) -> String {
+    match style {
+        FormatStyle::Strftime | FormatStyle::Sqlite | FormatStyle::MySql => text.replace('%', "%%"),
+        FormatStyle::ToChar | FormatStyle::Snowflake => {
+            if text.chars().any(|c| c.is_ascii_alphabetic()) {
+                format!("\"{}\"", text)
+            } else {
+                text.to_string()
+            }
+        }
+        FormatStyle::DotNet => text
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphabetic() || matches!(c, '%' | '\\' | '"' | '\'') {
+                    format!("\\{}", c)
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect(),
+        FormatStyle::Java => {
+            if text.chars().any(|c| c.is_ascii_alphabetic() || c == '\'') {
+                format!("'{}'", text.replace('\'', "''"))
+            } else {
+                text.to_string()
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Call Translation
+// =============================================================================
+
+/// Translate a canonical function call for `dialect`.
+///
+/// Returns `None` when the call isn't one of the shapes described in the module
+/// docs; the caller then renders it as an ordinary (renamed) function call.
+pub(crate) fn translate(dialect: DialectTarget, name: &str, args: &[Expr]) -> Option<TokenStream> {
+    let arg = |e: &Expr| operand(e, dialect);
+    match (name.to_uppercase().as_str(), args) {
+        ("DATE_TRUNC", [unit, d]) => Some(date_trunc(dialect, unit_arg(unit)?, &arg(d))),
+        ("DATEADD", [unit, n, d]) => Some(date_add(dialect, unit_arg(unit)?, n, &arg(d))),
+        ("DATEDIFF", [unit, start, end]) => {
+            Some(date_diff(dialect, unit_arg(unit)?, &arg(start), &arg(end)))
+        }
+        ("EXTRACT", [unit, d]) => Some(extract(dialect, unit_arg(unit)?, &arg(d))),
+        ("LAST_DAY", [d]) => Some(last_day(dialect, &arg(d))),
+        ("STRFTIME", [d, Expr::Literal(Literal::String(fmt))]) => {
+            format_date(dialect, FormatStyle::Strftime, arg(d), fmt)
+        }
+        ("TO_CHAR", [d, Expr::Literal(Literal::String(fmt))]) => {
+            format_date(dialect, FormatStyle::ToChar, arg(d), fmt)
+        }
+        ("LEFT", [s, n]) if dialect.dialect == Dialect::Sqlite => {
+            Some(call("SUBSTR", vec![arg(s), int(1), arg(n)]))
+        }
+        ("RIGHT", [s, n]) if dialect.dialect == Dialect::Sqlite => {
+            Some(sqlite_right(&arg(s), &arg(n)))
+        }
+        _ => None,
+    }
+}
+
+/// A unit from a string literal, or from a bare identifier spelling a full unit name.
+fn unit_arg(expr: &Expr) -> Option<DateUnit> {
+    match expr {
+        Expr::Literal(Literal::String(s)) => DateUnit::parse(s),
+        Expr::Column {
+            table: None,
+            column,
+        } => DateUnit::parse(column).filter(|unit| column.eq_ignore_ascii_case(unit.name())),
+        _ => None,
+    }
+}
+
+/// Render an argument, parenthesized when it could bind looser than an operator.
+fn operand(expr: &Expr, dialect: DialectTarget) -> TokenStream {
+    let ts = expr.to_tokens_for_dialect(dialect);
+    match expr {
+        Expr::BinaryOp { .. } | Expr::UnaryOp { .. } | Expr::Raw(_) => paren(ts),
+        _ => ts,
+    }
+}
+
+fn date_trunc(dialect: DialectTarget, unit: DateUnit, d: &TokenStream) -> TokenStream {
+    let u = unit.keyword();
+    match dialect.dialect {
+        // SQL Server 2022 has DATETRUNC; ISO_WEEK starts on Monday regardless of DATEFIRST
+        Dialect::TSql if dialect.known_at_least(16, 0) => {
+            let u = if unit == DateUnit::Week {
+                "ISO_WEEK"
+            } else {
+                u
+            };
+            call("DATETRUNC", vec![raw(u), d.clone()])
+        }
+        Dialect::TSql => match unit {
+            // DATEDIFF(week, ...) counts Sunday boundaries; day 0 (1900-01-01) is a Monday
+            DateUnit::Week => call(
+                "DATEADD",
+                vec![
+                    raw("WEEK"),
+                    binary(
+                        call("DATEDIFF", vec![raw("DAY"), int(0), d.clone()]),
+                        Token::Div,
+                        int(7),
+                    ),
+                    int(0),
+                ],
+            ),
+            // Seconds since 1900 overflow DATEDIFF's INT, so anchor on the day instead
+            DateUnit::Second => {
+                let day = cast(
+                    cast(d.clone(), DataType::Date, dialect),
+                    DataType::Timestamp,
+                    dialect,
+                );
+                call(
+                    "DATEADD",
+                    vec![
+                        raw(u),
+                        call("DATEDIFF", vec![raw(u), day.clone(), d.clone()]),
+                        day,
+                    ],
+                )
+            }
+            _ => call(
+                "DATEADD",
+                vec![
+                    raw(u),
+                    call("DATEDIFF", vec![raw(u), int(0), d.clone()]),
+                    int(0),
+                ],
+            ),
+        },
+        Dialect::MySql => {
+            let formatted = |fmt: &str, dt: DataType| {
+                cast(
+                    call("DATE_FORMAT", vec![d.clone(), string(fmt)]),
+                    dt,
+                    dialect,
+                )
+            };
+            match unit {
+                DateUnit::Year => formatted("%Y-01-01", DataType::Date),
+                DateUnit::Quarter => binary(
+                    call("MAKEDATE", vec![call("YEAR", vec![d.clone()]), int(1)]),
+                    Token::Plus,
+                    interval(
+                        paren(binary(
+                            call("QUARTER", vec![d.clone()]),
+                            Token::Minus,
+                            int(1),
+                        )),
+                        "QUARTER",
+                    ),
+                ),
+                DateUnit::Month => formatted("%Y-%m-01", DataType::Date),
+                DateUnit::Week => call(
+                    "DATE_SUB",
+                    vec![
+                        call("DATE", vec![d.clone()]),
+                        interval(call("WEEKDAY", vec![d.clone()]), "DAY"),
+                    ],
+                ),
+                DateUnit::Day => call("DATE", vec![d.clone()]),
+                DateUnit::Hour => formatted("%Y-%m-%d %H:00:00", DataType::Timestamp),
+                DateUnit::Minute => formatted("%Y-%m-%d %H:%i:00", DataType::Timestamp),
+                DateUnit::Second => formatted("%Y-%m-%d %H:%i:%S", DataType::Timestamp),
+            }
+        }
+        Dialect::Sqlite => {
+            let date = |modifiers: Vec<TokenStream>| {
+                let mut args = vec![d.clone()];
+                args.extend(modifiers);
+                call("DATE", args)
+            };
+            let strftime = |fmt: &str| call("STRFTIME", vec![string(fmt), d.clone()]);
+            match unit {
+                DateUnit::Year => date(vec![string("start of year")]),
+                DateUnit::Quarter => {
+                    // Step back (month - 1) % 3 months from the start of the month
+                    let months_back = paren(binary(
+                        paren(binary(sqlite_part("%m", d), Token::Minus, int(1))),
+                        Token::Mod,
+                        int(3),
+                    ));
+                    date(vec![
+                        string("start of month"),
+                        binary(
+                            binary(string("-"), Token::Concat, months_back),
+                            Token::Concat,
+                            string(" months"),
+                        ),
+                    ])
+                }
+                DateUnit::Month => date(vec![string("start of month")]),
+                // 'weekday 1' moves forward to the next Monday, so start six days back
+                DateUnit::Week => date(vec![string("-6 days"), string("weekday 1")]),
+                DateUnit::Day => date(vec![]),
+                DateUnit::Hour => strftime("%Y-%m-%d %H:00:00"),
+                DateUnit::Minute => strftime("%Y-%m-%d %H:%M:00"),
+                DateUnit::Second => strftime("%Y-%m-%d %H:%M:%S"),
+            }
+        }
+        Dialect::BigQuery => {
+            let (name, u) = match unit {
+                DateUnit::Week => ("DATE_TRUNC", "ISOWEEK"),
+                u if u.is_time() => ("TIMESTAMP_TRUNC", u.keyword()),
+                u => ("DATE_TRUNC", u.keyword()),
+            };
+            call(name, vec![d.clone(), raw(u)])
+        }
+        _ => call("DATE_TRUNC", vec![string(unit.name()), d.clone()]),
+    }
+}
+
+fn date_add(dialect: DialectTarget, unit: DateUnit, n: &Expr, d: &TokenStream) -> TokenStream {
+    let u = unit.keyword();
+    let amount = operand(n, dialect);
+    match dialect.dialect {
+        Dialect::DuckDb | Dialect::Postgres => {
+            // Neither accepts a quarter interval
+            let (multiplier, step) = match unit {
+                DateUnit::Quarter => (3, "month"),
+                u => (1, u.name()),
+            };
+            let shift = match n {
+                Expr::Literal(Literal::Int(k)) => {
+                    raw_interval(&format!("{} {}", k * multiplier, step))
+                }
+                _ => binary(
+                    amount,
+                    Token::Mul,
+                    raw_interval(&format!("{} {}", multiplier, step)),
+                ),
+            };
+            binary(d.clone(), Token::Plus, shift)
+        }
+        Dialect::TSql | Dialect::Snowflake | Dialect::Redshift => {
+            call("DATEADD", vec![raw(u), amount, d.clone()])
+        }
+        Dialect::Databricks => call("TIMESTAMPADD", vec![raw(u), amount, d.clone()]),
+        Dialect::MySql => call("DATE_ADD", vec![d.clone(), interval(amount, u)]),
+        Dialect::BigQuery => {
+            let name = if unit.is_time() {
+                "TIMESTAMP_ADD"
+            } else {
+                "DATE_ADD"
+            };
+            call(name, vec![d.clone(), interval(amount, u)])
+        }
+        Dialect::ClickHouse => call("DATE_ADD", vec![raw(u), amount, d.clone()]),
+        Dialect::Trino => call("DATE_ADD", vec![string(unit.name()), amount, d.clone()]),
+        Dialect::Sqlite => {
+            let (multiplier, step) = match unit {
+                DateUnit::Quarter => (3, "months"),
+                DateUnit::Week => (7, "days"),
+                DateUnit::Year => (1, "years"),
+                DateUnit::Month => (1, "months"),
+                DateUnit::Day => (1, "days"),
+                DateUnit::Hour => (1, "hours"),
+                DateUnit::Minute => (1, "minutes"),
+                DateUnit::Second => (1, "seconds"),
+            };
+            let modifier = match n {
+                Expr::Literal(Literal::Int(k)) => string(&format!("{:+} {}", k * multiplier, step)),
+                _ => {
+                    let count = if multiplier == 1 {
+                        amount
+                    } else {
+                        paren(binary(amount, Token::Mul, int(multiplier)))
+                    };
+                    binary(count, Token::Concat, string(&format!(" {}", step)))
+                }
+            };
+            call("DATETIME", vec![d.clone(), modifier])
+        }
+    }
+}
+
+fn date_diff(
+    dialect: DialectTarget,
+    unit: DateUnit,
+    start: &TokenStream,
+    end: &TokenStream,
+) -> TokenStream {
+    let u = unit.keyword();
+    match dialect.dialect {
+        Dialect::DuckDb | Dialect::Trino | Dialect::ClickHouse => call(
+            "DATE_DIFF",
+            vec![string(unit.name()), start.clone(), end.clone()],
+        ),
+        Dialect::TSql | Dialect::Snowflake | Dialect::Redshift => {
+            call("DATEDIFF", vec![raw(u), start.clone(), end.clone()])
+        }
+        Dialect::Databricks => call("TIMESTAMPDIFF", vec![raw(u), start.clone(), end.clone()]),
+        Dialect::MySql => match unit {
+            DateUnit::Day => call("DATEDIFF", vec![end.clone(), start.clone()]),
+            _ => call("TIMESTAMPDIFF", vec![raw(u), start.clone(), end.clone()]),
+        },
+        Dialect::BigQuery => {
+            let (name, u) = match unit {
+                DateUnit::Week => ("DATE_DIFF", "ISOWEEK"),
+                u if u.is_time() => ("TIMESTAMP_DIFF", u.keyword()),
+                u => ("DATE_DIFF", u.keyword()),
+            };
+            call(name, vec![end.clone(), start.clone(), raw(u)])
+        }
+        Dialect::Postgres | Dialect::Sqlite => {
+            let diff = match unit {
+                DateUnit::Year | DateUnit::Quarter | DateUnit::Month => {
+                    calendar_diff(dialect, unit, start, end)
+                }
+                DateUnit::Day | DateUnit::Week => {
+                    let days = |d: &TokenStream| match dialect.dialect {
+                        Dialect::Postgres => cast(d.clone(), DataType::Date, dialect),
+                        _ => call("JULIANDAY", vec![call("DATE", vec![d.clone()])]),
+                    };
+                    if unit == DateUnit::Day {
+                        binary(days(end), Token::Minus, days(start))
+                    } else {
+                        let monday =
+                            |d: &TokenStream| days(&date_trunc(dialect, DateUnit::Week, d));
+                        binary(
+                            paren(binary(monday(end), Token::Minus, monday(start))),
+                            Token::Div,
+                            int(7),
+                        )
+                    }
+                }
+                _ => {
+                    let epoch = |d: &TokenStream| {
+                        let truncated = date_trunc(dialect, unit, d);
+                        match dialect.dialect {
+                            Dialect::Postgres => extract_from("EPOCH", &truncated),
+                            _ => call("STRFTIME", vec![string("%s"), truncated]),
+                        }
+                    };
+                    binary(
+                        paren(binary(epoch(end), Token::Minus, epoch(start))),
+                        Token::Div,
+                        int(unit.seconds()),
+                    )
+                }
+            };
+            cast(diff, DataType::Int64, dialect)
+        }
+    }
+}
+
+/// Year, quarter or month boundaries crossed, from the extracted calendar parts.
+fn calendar_diff(
+    dialect: DialectTarget,
+    unit: DateUnit,
+    start: &TokenStream,
+    end: &TokenStream,
+) -> TokenStream {
+    let part = |u: DateUnit, d: &TokenStream| extract(dialect, u, d);
+    let years = binary(
+        part(DateUnit::Year, end),
+        Token::Minus,
+        part(DateUnit::Year, start),
+    );
+    let (per_year, sub_unit) = match unit {
+        DateUnit::Quarter => (4, DateUnit::Quarter),
+        DateUnit::Month => (12, DateUnit::Month),
+        _ => return years,
+    };
+    binary(
+        binary(
+            binary(paren(years), Token::Mul, int(per_year)),
+            Token::Plus,
+            part(sub_unit, end),
+        ),
+        Token::Minus,
+        part(sub_unit, start),
+    )
+}
+
+fn extract(dialect: DialectTarget, unit: DateUnit, d: &TokenStream) -> TokenStream {
+    match dialect.dialect {
+        Dialect::TSql => {
+            let u = match unit {
+                DateUnit::Week => "ISO_WEEK",
+                u => u.keyword(),
+            };
+            call("DATEPART", vec![raw(u), d.clone()])
+        }
+        Dialect::Sqlite => match unit {
+            DateUnit::Quarter => paren(binary(
+                paren(binary(sqlite_part("%m", d), Token::Plus, int(2))),
+                Token::Div,
+                int(3),
+            )),
+            DateUnit::Year => sqlite_part("%Y", d),
+            DateUnit::Month => sqlite_part("%m", d),
+            DateUnit::Week => sqlite_iso_week(d),
+            DateUnit::Day => sqlite_part("%d", d),
+            DateUnit::Hour => sqlite_part("%H", d),
+            DateUnit::Minute => sqlite_part("%M", d),
+            DateUnit::Second => sqlite_part("%S", d),
+        },
+        // Case-insensitive aliases of toYear(), toISOWeek() and friends
+        Dialect::ClickHouse => match unit {
+            DateUnit::Week => call("WEEK", vec![d.clone(), int(3)]),
+            u => call(u.keyword(), vec![d.clone()]),
+        },
+        // Mode 3: weeks start on Monday and week 1 holds the year's first Thursday
+        Dialect::MySql if unit == DateUnit::Week => call("WEEK", vec![d.clone(), int(3)]),
+        Dialect::BigQuery if unit == DateUnit::Week => extract_from("ISOWEEK", d),
+        Dialect::Snowflake if unit == DateUnit::Week => extract_from("WEEKISO", d),
+        _ => extract_from(unit.keyword(), d),
+    }
+}
+
+fn last_day(dialect: DialectTarget, d: &TokenStream) -> TokenStream {
+    match dialect.dialect {
+        Dialect::Postgres => cast(
+            binary(
+                binary(
+                    call("DATE_TRUNC", vec![string("month"), d.clone()]),
+                    Token::Plus,
+                    raw_interval("1 month"),
+                ),
+                Token::Minus,
+                raw_interval("1 day"),
+            ),
+            DataType::Date,
+            dialect,
+        ),
+        Dialect::TSql => call("EOMONTH", vec![d.clone()]),
+        Dialect::Sqlite => call(
+            "DATE",
+            vec![
+                d.clone(),
+                string("start of month"),
+                string("+1 month"),
+                string("-1 day"),
+            ],
+        ),
+        Dialect::Trino => call("LAST_DAY_OF_MONTH", vec![d.clone()]),
+        _ => call("LAST_DAY", vec![d.clone()]),
+    }
+}
+
+fn format_date(
+    dialect: DialectTarget,
+    from: FormatStyle,
+    d: TokenStream,
+    fmt: &str,
+) -> Option<TokenStream> {
+    let to = FormatStyle::for_dialect(dialect.dialect);
+    let fmt = string(&translate_format(fmt, from, to)?);
+    Some(match dialect.dialect {
+        Dialect::DuckDb => call("STRFTIME", vec![d, fmt]),
+        Dialect::Sqlite => call("STRFTIME", vec![fmt, d]),
+        Dialect::Postgres | Dialect::Redshift | Dialect::Snowflake => call("TO_CHAR", vec![d, fmt]),
+        Dialect::TSql => call("FORMAT", vec![d, fmt]),
+        Dialect::BigQuery => call("FORMAT_TIMESTAMP", vec![fmt, d]),
+        Dialect::MySql | Dialect::ClickHouse | Dialect::Trino | Dialect::Databricks => {
+            call("DATE_FORMAT", vec![d, fmt])
+        }
+    })
+}
+
+// =============================================================================
+// Token Builders
+// =============================================================================
+//
+// Generated calls are emitted as tokens rather than `Expr`s so they are never
+// translated a second time.
+
+fn call(name: &str, args: Vec<TokenStream>) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::FunctionName(name.into())).lparen();
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            ts.comma().space();
+        }
+        ts.append(arg);
+    }
+    ts.rparen();
+    ts
+}
+
+fn raw(sql: &str) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::Raw(sql.into()));
+    ts
+}
+
+// Units, formats and offsets are part of the call, never bind parameters
+fn string(s: &str) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::Inline(Box::new(Token::LitString(s.into()))));
+    ts
+}
+
+fn int(n: i64) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::Inline(Box::new(Token::LitInt(n))));
+    ts
+}
+
+fn binary(left: TokenStream, op: Token, right: TokenStream) -> TokenStream {
+    let mut ts = left;
+    ts.space().push(op).space().append(&right);
+    ts
+}
+
+fn paren(inner: TokenStream) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.lparen().append(&inner).rparen();
+    ts
+}
+
+fn cast(inner: TokenStream, data_type: DataType, dialect: DialectTarget) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::FunctionName("CAST".into()))
+        .lparen()
+        .append(&inner)
+        .space()
+        .push(Token::As)
+        .space()
+        .push(Token::Raw(dialect.emit_data_type(&data_type)))
+        .rparen();
+    ts
+}
+
+/// `INTERVAL <amount> <UNIT>`
+fn interval(amount: TokenStream, unit: &str) -> TokenStream {
+    let mut ts = raw("INTERVAL");
+    ts.space()
+        .append(&amount)
+        .space()
+        .push(Token::Raw(unit.into()));
+    ts
+}
+
+/// `INTERVAL '<quantity unit>'`
+fn raw_interval(quantity: &str) -> TokenStream {
+    let mut ts = raw("INTERVAL");
+    ts.space()
+        .push(Token::Inline(Box::new(Token::LitString(quantity.into()))));
+    ts
+}
+
+/// `EXTRACT(<FIELD> FROM d)`
+fn extract_from(field: &str, d: &TokenStream) -> TokenStream {
+    let mut ts = TokenStream::new();
+    ts.push(Token::FunctionName("EXTRACT".into()))
+        .lparen()
+        .push(Token::Raw(field.into()))
+        .space()
+        .push(Token::From)
+        .space()
+        .append(d)
+        .rparen();
+    ts
+}
+
+/// `CAST(STRFTIME('<code>', d) AS INTEGER)`
+fn sqlite_part(code: &str, d: &TokenStream) -> TokenStream {
+    cast(
+        call("STRFTIME", vec![string(code), d.clone()]),
+        DataType::Int64,
+        Dialect::Sqlite.into(),
+    )
+}
+
+/// `SUBSTR(s, MAX(LENGTH(s) - n + 1, 1))`
+///
+/// A start below one would count from the end of `s`, so it is clamped to the
+/// first character when `n` exceeds the length.
+fn sqlite_right(s: &TokenStream, n: &TokenStream) -> TokenStream {
+    let start = binary(
+        binary(call("LENGTH", vec![s.clone()]), Token::Minus, n.clone()),
+        Token::Plus,
+        int(1),
+    );
+    call("SUBSTR", vec![s.clone(), call("MAX", vec![start, int(1)])])
+}
+
+/// ISO 8601 week number: the day of year of the week's Thursday, in sevens.
+///
+/// `STRFTIME('%V')` only arrived in SQLite 3.46, and `%W` counts from the
+/// year's first Monday instead.
+fn sqlite_iso_week(d: &TokenStream) -> TokenStream {
+    let thursday = call(
+        "DATE",
+        vec![d.clone(), string("-3 days"), string("weekday 4")],
+    );
+    paren(binary(
+        binary(
+            paren(binary(sqlite_part("%j", &thursday), Token::Minus, int(1))),
+            Token::Div,
+            int(7),
+        ),
+        Token::Plus,
+        int(1),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::dialect::Version;
+    use crate::sql::expr::{col, func, lit_int, lit_str, ExprExt};
+    use crate::sql::query::{Query, TableRef};
+    use crate::sql::test_utils::validate_sql;
+    use rusqlite::Connection;
+
+    const DIALECTS: [Dialect; 11] = [
+        Dialect::DuckDb,
+        Dialect::Postgres,
+        Dialect::TSql,
+        Dialect::MySql,
+        Dialect::Snowflake,
+        Dialect::BigQuery,
+        Dialect::Redshift,
+        Dialect::Databricks,
+        Dialect::Sqlite,
+        Dialect::ClickHouse,
+        Dialect::Trino,
+    ];
+
+    const UNITS: [DateUnit; 8] = [
+        DateUnit::Year,
+        DateUnit::Quarter,
+        DateUnit::Month,
+        DateUnit::Week,
+        DateUnit::Day,
+        DateUnit::Hour,
+        DateUnit::Minute,
+        DateUnit::Second,
+    ];
+
+    fn render(expr: &Expr, dialect: Dialect) -> String {
+        expr.to_tokens_for_dialect(dialect).serialize(dialect)
+    }
+
+    fn select(expr: Expr, dialect: Dialect) -> String {
+        Query::new()
+            .select(vec![expr])
+            .from(TableRef::new("t"))
+            .to_sql(dialect)
+    }
+
+    #[test]
+    fn test_translate_format() {
+        let fmt = "%Y-%m-%d %H:%M:%S";
+        let cases = [
+            (FormatStyle::ToChar, "YYYY-MM-DD HH24:MI:SS"),
+            (FormatStyle::Snowflake, "YYYY-MM-DD HH24:MI:SS"),
+            (FormatStyle::DotNet, "yyyy-MM-dd HH:mm:ss"),
+            (FormatStyle::MySql, "%Y-%m-%d %H:%i:%S"),
+            (FormatStyle::Java, "yyyy-MM-dd HH:mm:ss"),
+            (FormatStyle::Sqlite, "%Y-%m-%d %H:%M:%S"),
+        ];
+        for (style, expected) in cases {
+            assert_eq!(
+                translate_format(fmt, FormatStyle::Strftime, style).as_deref(),
+                Some(expected),
+                "{:?}",
+                style
+            );
+            // And back again
+            assert_eq!(
+                translate_format(expected, style, FormatStyle::Strftime).as_deref(),
+                Some(fmt),
+                "{:?}",
+                style
+            );
+        }
+
+        assert_eq!(
+            translate_format("FMMonth DD, YYYY", FormatStyle::ToChar, FormatStyle::MySql)
+                .as_deref(),
+            Some("%M %d, %Y")
+        );
+        assert_eq!(
+            translate_format("dddd hh:mm tt", FormatStyle::DotNet, FormatStyle::ToChar).as_deref(),
+            Some("FMDay HH12:MI AM")
+        );
+        assert_eq!(
+            translate_format("%h:%i %p", FormatStyle::MySql, FormatStyle::Java).as_deref(),
+            Some("hh:mm a")
+        );
+    }
+
+    #[test]
+    fn test_format_literals_are_escaped() {
+        let fmt = "Week of %Y-%m-%d (100%%)";
+        assert_eq!(
+            translate_format(fmt, FormatStyle::Strftime, FormatStyle::ToChar).as_deref(),
+            Some("\"Week of \"YYYY-MM-DD (100%)")
+        );
+        assert_eq!(
+            translate_format(fmt, FormatStyle::Strftime, FormatStyle::DotNet).as_deref(),
+            Some("\\W\\e\\e\\k \\o\\f yyyy-MM-dd (100\\%)")
+        );
+        assert_eq!(
+            translate_format(fmt, FormatStyle::Strftime, FormatStyle::Java).as_deref(),
+            Some("'Week of 'yyyy-MM-dd (100%)")
+        );
+        assert_eq!(
+            translate_format("YYYY\"T\"HH24", FormatStyle::ToChar, FormatStyle::Strftime)
+                .as_deref(),
+            Some("%YT%H")
+        );
+        assert_eq!(
+            translate_format(
+                "yyyy'T'HH 'o''clock'''",
+                FormatStyle::Java,
+                FormatStyle::Strftime
+            )
+            .as_deref(),
+            Some("%YT%H o'clock'")
+        );
+    }
+
+    #[test]
+    fn test_untranslatable_formats() {
+        // SQLite has no month names
+        assert_eq!(
+            translate_format("%B %Y", FormatStyle::Strftime, FormatStyle::Sqlite),
+            None
+        );
+        // Unknown strftime code
+        assert_eq!(
+            translate_format("%Q", FormatStyle::Strftime, FormatStyle::ToChar),
+            None
+        );
+
+        // ... which leaves the call to plain renaming
+        let expr = func("STRFTIME", vec![col("d"), lit_str("%B %Y")]);
+        assert_eq!(render(&expr, Dialect::Sqlite), "STRFTIME(\"d\", '%B %Y')");
+    }
+
+    #[test]
+    fn test_format_call_shapes() {
+        let expr = func("STRFTIME", vec![col("d"), lit_str("%Y-%m")]);
+        let cases = [
+            (Dialect::DuckDb, "STRFTIME(\"d\", '%Y-%m')"),
+            (Dialect::Postgres, "TO_CHAR(\"d\", 'YYYY-MM')"),
+            (Dialect::TSql, "FORMAT([d], 'yyyy-MM')"),
+            (Dialect::MySql, "DATE_FORMAT(`d`, '%Y-%m')"),
+            (Dialect::Snowflake, "TO_CHAR(\"d\", 'YYYY-MM')"),
+            (Dialect::BigQuery, "FORMAT_TIMESTAMP('%Y-%m', `d`)"),
+            (Dialect::Databricks, "DATE_FORMAT(`d`, 'yyyy-MM')"),
+            (Dialect::Sqlite, "STRFTIME('%Y-%m', \"d\")"),
+            (Dialect::Trino, "DATE_FORMAT(\"d\", '%Y-%m')"),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&expr, dialect), expected, "{:?}", dialect);
+        }
+
+        let to_char = func("TO_CHAR", vec![col("d"), lit_str("YYYY-MM-DD HH24:MI")]);
+        assert_eq!(
+            render(&to_char, Dialect::DuckDb),
+            "STRFTIME(\"d\", '%Y-%m-%d %H:%M')"
+        );
+        assert_eq!(
+            render(&to_char, Dialect::TSql),
+            "FORMAT([d], 'yyyy-MM-dd HH:mm')"
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_shapes() {
+        let expr = func("DATE_TRUNC", vec![lit_str("Month"), col("d")]);
+        let cases = [
+            (Dialect::DuckDb, "DATE_TRUNC('month', \"d\")"),
+            (Dialect::Postgres, "DATE_TRUNC('month', \"d\")"),
+            (Dialect::TSql, "DATEADD(MONTH, DATEDIFF(MONTH, 0, [d]), 0)"),
+            (Dialect::MySql, "CAST(DATE_FORMAT(`d`, '%Y-%m-01') AS DATE)"),
+            (Dialect::BigQuery, "DATE_TRUNC(`d`, MONTH)"),
+            (Dialect::Sqlite, "DATE(\"d\", 'start of month')"),
+            (Dialect::Trino, "DATE_TRUNC('month', \"d\")"),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&expr, dialect), expected, "{:?}", dialect);
+        }
+
+        // Bare identifiers work as units too
+        let expr = func("DATE_TRUNC", vec![col("week"), col("d")]);
+        assert_eq!(render(&expr, Dialect::BigQuery), "DATE_TRUNC(`d`, ISOWEEK)");
+    }
+
+    #[test]
+    fn test_date_trunc_sql_server_2022() {
+        let mssql_2022 = Dialect::TSql.at(Version::sql_server(2022));
+        let month = func("DATE_TRUNC", vec![lit_str("month"), col("d")]);
+        let week = func("DATE_TRUNC", vec![lit_str("week"), col("d")]);
+        let trunc = |expr: &Expr| expr.to_tokens_for_dialect(mssql_2022).serialize(mssql_2022);
+
+        assert_eq!(trunc(&month), "DATETRUNC(MONTH, [d])");
+        assert_eq!(trunc(&week), "DATETRUNC(ISO_WEEK, [d])");
+        // 2019 keeps the DATEADD/DATEDIFF form
+        let mssql_2019 = Dialect::TSql.at(Version::sql_server(2019));
+        assert!(month
+            .to_tokens_for_dialect(mssql_2019)
+            .serialize(mssql_2019)
+            .starts_with("DATEADD(MONTH"));
+    }
+
+    #[test]
+    fn test_date_add_and_diff_shapes() {
+        let add = func("DATEADD", vec![lit_str("quarter"), lit_int(2), col("d")]);
+        let cases = [
+            (Dialect::DuckDb, "\"d\" + INTERVAL '6 month'"),
+            (Dialect::TSql, "DATEADD(QUARTER, 2, [d])"),
+            (Dialect::MySql, "DATE_ADD(`d`, INTERVAL 2 QUARTER)"),
+            (Dialect::Databricks, "TIMESTAMPADD(QUARTER, 2, `d`)"),
+            (Dialect::Sqlite, "DATETIME(\"d\", '+6 months')"),
+            (Dialect::Trino, "DATE_ADD('quarter', 2, \"d\")"),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&add, dialect), expected, "{:?}", dialect);
+        }
+
+        let add = func(
+            "DATEADD",
+            vec![lit_str("day"), col("n").add(lit_int(1)), col("d")],
+        );
+        assert_eq!(
+            render(&add, Dialect::Postgres),
+            "\"d\" + (\"n\" + 1) * INTERVAL '1 day'"
+        );
+        assert_eq!(
+            render(&add, Dialect::Sqlite),
+            "DATETIME(\"d\", (\"n\" + 1) || ' days')"
+        );
+
+        let diff = func("DATEDIFF", vec![lit_str("day"), col("a"), col("b")]);
+        let cases = [
+            (Dialect::DuckDb, "DATE_DIFF('day', \"a\", \"b\")"),
+            (Dialect::Snowflake, "DATEDIFF(DAY, \"a\", \"b\")"),
+            (Dialect::MySql, "DATEDIFF(`b`, `a`)"),
+            (Dialect::BigQuery, "DATE_DIFF(`b`, `a`, DAY)"),
+            (
+                Dialect::Postgres,
+                "CAST(CAST(\"b\" AS DATE) - CAST(\"a\" AS DATE) AS BIGINT)",
+            ),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&diff, dialect), expected, "{:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_extract_and_last_day_shapes() {
+        let expr = func("EXTRACT", vec![lit_str("week"), col("d")]);
+        let cases = [
+            (Dialect::Postgres, "EXTRACT(WEEK FROM \"d\")"),
+            (Dialect::TSql, "DATEPART(ISO_WEEK, [d])"),
+            (Dialect::BigQuery, "EXTRACT(ISOWEEK FROM `d`)"),
+            (Dialect::ClickHouse, "WEEK(`d`, 3)"),
+            (Dialect::MySql, "WEEK(`d`, 3)"),
+            (Dialect::Snowflake, "EXTRACT(WEEKISO FROM \"d\")"),
+            (
+                Dialect::Sqlite,
+                "((CAST(STRFTIME('%j', DATE(\"d\", '-3 days', 'weekday 4')) AS INTEGER) - 1) / 7 + 1)",
+            ),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&expr, dialect), expected, "{:?}", dialect);
+        }
+
+        let expr = func("LAST_DAY", vec![col("d")]);
+        let cases = [
+            (Dialect::DuckDb, "LAST_DAY(\"d\")"),
+            (
+                Dialect::Postgres,
+                "CAST(DATE_TRUNC('month', \"d\") + INTERVAL '1 month' - INTERVAL '1 day' AS DATE)",
+            ),
+            (Dialect::TSql, "EOMONTH([d])"),
+            (Dialect::Trino, "LAST_DAY_OF_MONTH(\"d\")"),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(render(&expr, dialect), expected, "{:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_unrecognised_calls_are_only_renamed() {
+        // Unknown unit
+        let expr = func("DATE_TRUNC", vec![lit_str("fortnight"), col("d")]);
+        assert_eq!(render(&expr, Dialect::TSql), "DATE_TRUNC('fortnight', [d])");
+        // Non-literal format
+        let expr = func("STRFTIME", vec![col("d"), col("fmt")]);
+        assert_eq!(render(&expr, Dialect::Postgres), "TO_CHAR(\"d\", \"fmt\")");
+        // MySQL's two-argument DATEDIFF is not the canonical shape
+        let expr = func("DATEDIFF", vec![col("a"), col("b")]);
+        assert_eq!(render(&expr, Dialect::MySql), "DATEDIFF(`a`, `b`)");
+        // A bare abbreviation is a column, not a unit
+        let expr = func("DATE_TRUNC", vec![col("d"), col("ts")]);
+        assert_eq!(render(&expr, Dialect::TSql), "DATE_TRUNC([d], [ts])");
+        let expr = func("DATE_TRUNC", vec![col("month"), col("ts")]);
+        assert_eq!(
+            render(&expr, Dialect::TSql),
+            "DATEADD(MONTH, DATEDIFF(MONTH, 0, [ts]), 0)"
+        );
+    }
+
+    #[test]
+    fn test_translations_parse_in_every_dialect() {
+        for dialect in DIALECTS {
+            for unit in UNITS {
+                let exprs = [
+                    func("DATE_TRUNC", vec![lit_str(unit.name()), col("d")]),
+                    func("DATEADD", vec![lit_str(unit.name()), lit_int(-3), col("d")]),
+                    func("DATEADD", vec![lit_str(unit.name()), col("n"), col("d")]),
+                    func("DATEDIFF", vec![lit_str(unit.name()), col("a"), col("b")]),
+                    func("EXTRACT", vec![lit_str(unit.name()), col("d")]),
+                ];
+                for expr in exprs {
+                    let sql = select(expr, dialect);
+                    validate_sql(&sql, dialect).unwrap();
+                }
+            }
+            for expr in [
+                func("LAST_DAY", vec![col("d")]),
+                func("STRFTIME", vec![col("d"), lit_str("%Y-%m-%d %H:%M")]),
+                func("TO_CHAR", vec![col("d"), lit_str("YYYY-MM-DD HH24:MI")]),
+            ] {
+                let sql = select(expr, dialect);
+                validate_sql(&sql, dialect).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqlite_translations_execute() {
+        let conn = Connection::open_in_memory().unwrap();
+        let d = lit_str("2024-05-15 10:30:45"); // a Wednesday
+        let eval = |expr: Expr| -> String {
+            let sql = format!("SELECT CAST({} AS TEXT)", render(&expr, Dialect::Sqlite));
+            conn.query_row(&sql, [], |row| row.get(0)).unwrap()
+        };
+        let call = |name: &str, unit: &str, rest: Vec<Expr>| {
+            let mut args = vec![lit_str(unit)];
+            args.extend(rest);
+            eval(func(name, args))
+        };
+
+        let trunc = [
+            ("year", "2024-01-01"),
+            ("quarter", "2024-04-01"),
+            ("month", "2024-05-01"),
+            ("week", "2024-05-13"),
+            ("day", "2024-05-15"),
+            ("hour", "2024-05-15 10:00:00"),
+            ("minute", "2024-05-15 10:30:00"),
+            ("second", "2024-05-15 10:30:45"),
+        ];
+        for (unit, expected) in trunc {
+            assert_eq!(
+                call("DATE_TRUNC", unit, vec![d.clone()]),
+                expected,
+                "{}",
+                unit
+            );
+        }
+
+        assert_eq!(
+            call("DATEADD", "quarter", vec![lit_int(1), d.clone()]),
+            "2024-08-15 10:30:45"
+        );
+        assert_eq!(
+            call("DATEADD", "week", vec![lit_int(-1), d.clone()]),
+            "2024-05-08 10:30:45"
+        );
+        assert_eq!(
+            call(
+                "DATEADD",
+                "hour",
+                vec![lit_int(1).add(lit_int(1)), d.clone()]
+            ),
+            "2024-05-15 12:30:45"
+        );
+
+        let later = lit_str("2025-02-03 08:00:00");
+        let diff = [
+            ("year", "1"),
+            ("quarter", "3"),
+            ("month", "9"),
+            ("week", "38"),
+            ("day", "264"),
+            ("hour", "6334"),
+        ];
+        for (unit, expected) in diff {
+            assert_eq!(
+                call("DATEDIFF", unit, vec![d.clone(), later.clone()]),
+                expected,
+                "{}",
+                unit
+            );
+        }
+
+        assert_eq!(call("EXTRACT", "quarter", vec![d.clone()]), "2");
+        assert_eq!(call("EXTRACT", "minute", vec![d.clone()]), "30");
+        // ISO weeks: early January can belong to the previous year's last week
+        let iso_weeks = [
+            ("2024-05-15", "20"),
+            ("2021-01-01", "53"),
+            ("2021-01-04", "1"),
+            ("2024-12-30", "1"),
+            ("2023-01-01", "52"),
+        ];
+        for (day, expected) in iso_weeks {
+            assert_eq!(
+                call("EXTRACT", "week", vec![lit_str(day)]),
+                expected,
+                "{}",
+                day
+            );
+        }
+        assert_eq!(
+            eval(func("LAST_DAY", vec![lit_str("2024-02-10")])),
+            "2024-02-29"
+        );
+        assert_eq!(
+            eval(func(
+                "TO_CHAR",
+                vec![d.clone(), lit_str("DD/MM/YYYY HH24h")]
+            )),
+            "15/05/2024 10h"
+        );
+
+        let s = lit_str("crate");
+        let cases = [
+            ("LEFT", 2, "cr"),
+            ("RIGHT", 2, "te"),
+            ("RIGHT", 0, ""),
+            ("RIGHT", 9, "crate"),
+        ];
+        for (name, n, expected) in cases {
+            assert_eq!(
+                eval(func(name, vec![s.clone(), lit_int(n)])),
+                expected,
+                "{}({})",
+                name,
+                n
+            );
+        }
+    }
+}
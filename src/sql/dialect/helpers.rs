@@ -35,6 +35,12 @@ pub fn quote_string_single(s: &str) -> String {
     format!("'{}'", s.replace('\'', "''"))
 }
 
+/// Quote string with single quotes, also escaping backslashes.
+/// Used by: ClickHouse (backslash starts an escape sequence in literals)
+pub fn quote_string_backslash(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''"))
+}
+
 /// Quote string with N prefix for Unicode (T-SQL).
 /// Used by: T-SQL for non-ASCII strings
 pub fn quote_string_unicode(s: &str) -> String {
@@ -65,6 +71,28 @@ pub fn format_bool_numeric(b: bool) -> &'static str {
     }
 }
 
+// =============================================================================
+// Bind Parameters
+// =============================================================================
+
+use crate::sql::params::Param;
+
+/// `$n` placeholder, cast for non-string values.
+/// Used by: Postgres, Redshift, DuckDB
+///
+/// An uncast placeholder takes its type from context, which fails or falls back
+/// to text where there is none (`CASE WHEN ... THEN $1`). Strings stay uncast so
+/// they still coerce to date and timestamp columns the way string literals do.
+pub fn placeholder_dollar(index: usize, param: &Param, float_type: &str) -> String {
+    match param {
+        Param::String(_) => format!("${}", index),
+        Param::Int(n) if i32::try_from(*n).is_ok() => format!("${}::INTEGER", index),
+        Param::Int(_) => format!("${}::BIGINT", index),
+        Param::Float(_) => format!("${}::{}", index, float_type),
+        Param::Bool(_) => format!("${}::BOOLEAN", index),
+    }
+}
+
 // =============================================================================
 // Pagination
 // =============================================================================
@@ -94,6 +122,48 @@ pub fn emit_limit_offset_standard(limit: Option<u64>, offset: Option<u64>) -> To
     ts
 }
 
+/// Emit LIMIT ... OFFSET ..., with `LIMIT -1` when only an offset is given.
+/// Used by: SQLite (OFFSET is only valid after LIMIT)
+pub fn emit_limit_offset_sqlite(limit: Option<u64>, offset: Option<u64>) -> TokenStream {
+    match (limit, offset) {
+        (None, Some(off)) => {
+            let mut ts = TokenStream::new();
+            ts.push(Token::Limit)
+                .space()
+                .push(Token::LitInt(-1))
+                .space()
+                .push(Token::Offset)
+                .space()
+                .push(Token::LitInt(off as i64));
+            ts
+        }
+        _ => emit_limit_offset_standard(limit, offset),
+    }
+}
+
+/// Emit OFFSET ... LIMIT ... (offset first).
+/// Used by: Trino
+pub fn emit_limit_offset_trino(limit: Option<u64>, offset: Option<u64>) -> TokenStream {
+    let mut ts = TokenStream::new();
+
+    if let Some(off) = offset {
+        ts.push(Token::Offset)
+            .space()
+            .push(Token::LitInt(off as i64));
+    }
+
+    if let Some(lim) = limit {
+        if offset.is_some() {
+            ts.space();
+        }
+        ts.push(Token::Limit)
+            .space()
+            .push(Token::LitInt(lim as i64));
+    }
+
+    ts
+}
+
 /// Emit OFFSET ... ROWS FETCH NEXT ... ROWS ONLY (T-SQL style).
 /// Used by: T-SQL (SQL Server, Azure Synapse)
 /// Note: Requires ORDER BY clause in T-SQL
@@ -226,6 +296,54 @@ pub fn remap_function_databricks(name: &str) -> Option<&'static str> {
     }
 }
 
+/// Remap functions for SQLite dialect.
+/// NULL handling goes through IFNULL. `STRFTIME` takes the format first, so
+/// (value, format) functions such as `TO_CHAR` are not renamed to it; the
+/// canonical translations reorder them.
+pub fn remap_function_sqlite(name: &str) -> Option<&'static str> {
+    match name.to_uppercase().as_str() {
+        "FORMAT_TIMESTAMP" => Some("STRFTIME"),
+        "NVL" => Some("IFNULL"),
+        "ISNULL" => Some("IFNULL"),
+        "LEN" => Some("LENGTH"),
+        "CHAR_LENGTH" => Some("LENGTH"),
+        "SUBSTRING" => Some("SUBSTR"),
+        _ => None,
+    }
+}
+
+/// Remap functions for ClickHouse dialect.
+/// Targets the case-insensitive aliases, since function names are emitted upper-case.
+pub fn remap_function_clickhouse(name: &str) -> Option<&'static str> {
+    match name.to_uppercase().as_str() {
+        "STRFTIME" => Some("DATE_FORMAT"),
+        "TO_CHAR" => Some("DATE_FORMAT"),
+        "FORMAT" => Some("DATE_FORMAT"),
+        "NVL" => Some("IFNULL"),
+        "ISNULL" => Some("IFNULL"),
+        "LEN" => Some("LENGTH"),
+        "CHAR_LENGTH" => Some("LENGTH"),
+        "GETDATE" => Some("NOW"),
+        _ => None,
+    }
+}
+
+/// Remap functions for Trino dialect.
+pub fn remap_function_trino(name: &str) -> Option<&'static str> {
+    match name.to_uppercase().as_str() {
+        "STRFTIME" => Some("DATE_FORMAT"),
+        "TO_CHAR" => Some("DATE_FORMAT"),
+        "FORMAT_TIMESTAMP" => Some("DATE_FORMAT"),
+        "NVL" => Some("COALESCE"),
+        "IFNULL" => Some("COALESCE"),
+        "ISNULL" => Some("COALESCE"),
+        "LEN" => Some("LENGTH"),
+        "CHAR_LENGTH" => Some("LENGTH"),
+        "GETDATE" => Some("NOW"),
+        _ => None,
+    }
+}
+
 // =============================================================================
 // Data Type Emission
 // =============================================================================
@@ -373,6 +491,86 @@ pub fn emit_data_type_databricks(dt: &DataType) -> String {
     }
 }
 
+/// Emit data type for SQLite.
+///
+/// SQLite only has storage classes; names are chosen so each column gets the
+/// intended type affinity (INTEGER, REAL, NUMERIC, TEXT or BLOB). Dates and
+/// times are stored as ISO-8601 text, which the date functions understand.
+pub fn emit_data_type_sqlite(dt: &DataType) -> String {
+    match dt {
+        DataType::Bool
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64 => "INTEGER".into(),
+        DataType::Float32 | DataType::Float64 => "REAL".into(),
+        DataType::Decimal(_, _) => "NUMERIC".into(),
+        DataType::String | DataType::Char(_) | DataType::Varchar(_) => "TEXT".into(),
+        DataType::Date | DataType::Time | DataType::Timestamp | DataType::TimestampTz => {
+            "TEXT".into()
+        }
+        DataType::Binary => "BLOB".into(),
+        DataType::Json => "TEXT".into(),
+        DataType::Uuid => "TEXT".into(),
+    }
+}
+
+/// Emit data type for ClickHouse.
+pub fn emit_data_type_clickhouse(dt: &DataType) -> String {
+    match dt {
+        DataType::Bool => "Bool".into(),
+        DataType::Int8 => "Int8".into(),
+        DataType::Int16 => "Int16".into(),
+        DataType::Int32 => "Int32".into(),
+        DataType::Int64 => "Int64".into(),
+        DataType::Float32 => "Float32".into(),
+        DataType::Float64 => "Float64".into(),
+        DataType::Decimal(p, s) => format!("Decimal({}, {})", p, s),
+        DataType::String => "String".into(),
+        DataType::Char(_) => "String".into(), // FixedString pads with NUL bytes
+        DataType::Varchar(_) => "String".into(),
+        DataType::Date => "Date32".into(),
+        DataType::Time => "String".into(), // ClickHouse has no TIME type
+        DataType::Timestamp => "DateTime64(6)".into(),
+        DataType::TimestampTz => "DateTime64(6, 'UTC')".into(),
+        DataType::Binary => "String".into(),
+        DataType::Json => "String".into(),
+        DataType::Uuid => "UUID".into(),
+    }
+}
+
+/// Emit a ClickHouse type that accepts NULLs.
+///
+/// Plain ClickHouse types reject NULLs (inserting the type's default instead
+/// at best), so nullable columns need the `Nullable` wrapper.
+pub fn emit_nullable_type_clickhouse(dt: &DataType) -> String {
+    format!("Nullable({})", emit_data_type_clickhouse(dt))
+}
+
+/// Emit data type for Trino.
+pub fn emit_data_type_trino(dt: &DataType) -> String {
+    match dt {
+        DataType::Bool => "BOOLEAN".into(),
+        DataType::Int8 => "TINYINT".into(),
+        DataType::Int16 => "SMALLINT".into(),
+        DataType::Int32 => "INTEGER".into(),
+        DataType::Int64 => "BIGINT".into(),
+        DataType::Float32 => "REAL".into(),
+        DataType::Float64 => "DOUBLE".into(),
+        DataType::Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
+        DataType::String => "VARCHAR".into(),
+        DataType::Char(len) => format!("CHAR({})", len),
+        DataType::Varchar(len) => format!("VARCHAR({})", len),
+        DataType::Date => "DATE".into(),
+        DataType::Time => "TIME".into(),
+        DataType::Timestamp => "TIMESTAMP(6)".into(),
+        DataType::TimestampTz => "TIMESTAMP(6) WITH TIME ZONE".into(),
+        DataType::Binary => "VARBINARY".into(),
+        DataType::Json => "JSON".into(),
+        DataType::Uuid => "UUID".into(),
+    }
+}
+
 // Redshift uses emit_data_type_ansi (already exists)
 
 // =============================================================================
@@ -406,3 +604,10 @@ pub fn emit_identity_snowflake(_start: i64, _increment: i64) -> TokenStream {
     ts.push(Token::Raw("AUTOINCREMENT".into()));
     ts
 }
+
+/// Emit no identity syntax.
+/// Used by: SQLite (an `INTEGER PRIMARY KEY` column is the rowid and numbers
+/// itself), ClickHouse and Trino (no auto-increment columns)
+pub fn emit_identity_none(_start: i64, _increment: i64) -> TokenStream {
+    TokenStream::new()
+}
@@ -23,50 +23,68 @@
 //!
 //! Some SQL features require specific database versions:
 //!
-//! | Feature | PostgreSQL | SQL Server | MySQL | DuckDB | Snowflake | BigQuery |
-//! |---------|-----------|------------|-------|--------|-----------|----------|
-//! | MERGE | 15+ | 2008+ | ❌ | ❌ | ✓ | ✓ |
-//! | CTE (WITH) | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ |
-//! | Recursive CTE | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ |
-//! | Window Functions | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ |
-//! | GROUPS Frame | 11+ | ❌ | ❌ | ✓ | ✓ | ❌ |
-//! | NULLS FIRST/LAST | 8.3+ | 2022+ | ❌ | ✓ | ✓ | ✓ |
-//! | RETURNING | 8.2+ | 2005+ (OUTPUT) | ❌ | ✓ | ❌ | ❌ |
-//! | Materialized View | 9.3+ | ❌ (indexed views) | ❌ | ✓ | ✓ | ✓ |
-//! | QUALIFY | ❌ | ❌ | ❌ | ✓ | ✓ | ✓ |
-//! | DISTINCT ON | ✓ | ❌ | ❌ | ✓ | ❌ | ❌ |
-//! | FILTER Clause | 9.4+ | ❌ | ❌ | ✓ | ❌ | ❌ |
-//! | Partial Indexes | ✓ | 2008+ | ❌ | ✓ | ❌ | ❌ |
+//! | Feature | PostgreSQL | SQL Server | MySQL | DuckDB | Snowflake | BigQuery | SQLite |
+//! |---------|-----------|------------|-------|--------|-----------|----------|--------|
+//! | MERGE | 15+ | 2008+ | ❌ | ❌ | ✓ | ✓ | ❌ |
+//! | CTE (WITH) | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ | 3.8.3+ |
+//! | Recursive CTE | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ | 3.8.3+ |
+//! | Window Functions | 8.4+ | 2005+ | 8.0+ | ✓ | ✓ | ✓ | 3.25+ |
+//! | GROUPS Frame | 11+ | ❌ | ❌ | ✓ | ✓ | ❌ | 3.28+ |
+//! | NULLS FIRST/LAST | 8.3+ | ❌ | ❌ | ✓ | ✓ | ✓ | 3.30+ |
+//! | RETURNING | 8.2+ | 2005+ (OUTPUT) | ❌ | ✓ | ❌ | ❌ | 3.35+ |
+//! | Materialized View | 9.3+ | ❌ (indexed views) | ❌ | ✓ | ✓ | ✓ | ❌ |
+//! | QUALIFY | ❌ | ❌ | ❌ | ✓ | ✓ | ✓ | ❌ |
+//! | PIVOT / UNPIVOT | ❌ | 2005+ | ❌ | ✓ | ✓ | ✓ | ❌ |
+//! | DISTINCT ON | ✓ | ❌ | ❌ | ✓ | ❌ | ❌ | ❌ |
+//! | FILTER Clause | 9.4+ | ❌ | ❌ | ✓ | ❌ | ❌ | 3.30+ |
+//! | Partial Indexes | ✓ | 2008+ | ❌ | ✓ | ❌ | ❌ | ✓ |
 //!
 //! Legend: ✓ = supported, ❌ = not supported, version = minimum required
 //!
+//! `Query` emulates NULLS FIRST/LAST, QUALIFY, PIVOT and UNPIVOT where they are
+//! not supported.
+//!
 //! Check dialect feature flags (e.g., `supports_merge()`, `supports_groups_frame()`)
-//! before generating SQL that uses these features.
+//! before generating SQL that uses these features. A bare [`Dialect`] assumes the
+//! latest version; use a [`DialectTarget`] (e.g. `Dialect::Postgres.at(Version::new(14, 0))`)
+//! to gate the flags on a specific server version.
 
 mod ansi;
 mod bigquery;
+mod clickhouse;
 mod databricks;
 mod duckdb;
+pub mod functions;
 pub mod helpers;
 mod mysql;
 mod postgres;
 mod redshift;
 mod snowflake;
+mod sqlite;
+mod target;
+mod trino;
 mod tsql;
 
 // Note: Ansi is exported as a reference implementation for testing and documentation.
 // It is NOT included in the Dialect enum because real databases rarely use pure ANSI SQL.
-// Use DuckDb, Postgres, TSql, MySql, Snowflake, BigQuery, Redshift, or Databricks for actual query generation.
+// Use DuckDb, Postgres, TSql, MySql, Snowflake, BigQuery, Redshift, Databricks, Sqlite, ClickHouse,
+// or Trino for actual query generation.
 pub use ansi::Ansi;
 pub use bigquery::BigQuery;
+pub use clickhouse::ClickHouse;
 pub use databricks::Databricks;
 pub use duckdb::DuckDb;
+pub use functions::{translate_format, DateUnit, FormatStyle};
 pub use mysql::MySql;
 pub use postgres::Postgres;
 pub use redshift::Redshift;
 pub use snowflake::Snowflake;
+pub use sqlite::Sqlite;
+pub use target::{DialectTarget, Version};
+pub use trino::Trino;
 pub use tsql::TSql;
 
+use super::params::Param;
 use super::token::{Token, TokenStream};
 
 /// SQL dialect trait - defines how SQL constructs are rendered.
@@ -107,6 +125,23 @@ pub trait SqlDialect: std::fmt::Debug {
         "NULL"
     }
 
+    /// Bind-parameter placeholder for the 1-based `index`.
+    ///
+    /// - PostgreSQL/Redshift/DuckDB: `$1`, cast for non-string values
+    /// - T-SQL: `@p1`
+    /// - Snowflake: `:1`
+    /// - Others: `?` (default)
+    fn placeholder(&self, _index: usize, _param: &Param) -> String {
+        "?".into()
+    }
+
+    /// Whether placeholders carry their index, so one can be repeated.
+    ///
+    /// Positional `?` placeholders bind one value each.
+    fn numbered_placeholders(&self) -> bool {
+        false
+    }
+
     // =========================================================================
     // Pagination
     // =========================================================================
@@ -192,13 +227,24 @@ pub trait SqlDialect: std::fmt::Debug {
         true
     }
 
+    // =========================================================================
+    // Set Operations
+    // =========================================================================
+
+    /// Whether the members of UNION/INTERSECT/EXCEPT may be parenthesized.
+    ///
+    /// SQLite rejects `(SELECT ...) UNION (SELECT ...)`.
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        true
+    }
+
     // =========================================================================
     // NULLS Ordering
     // =========================================================================
 
     /// Whether this dialect supports NULLS FIRST/LAST in ORDER BY.
     ///
-    /// MySQL and older T-SQL versions don't support this.
+    /// MySQL and T-SQL don't support this; ORDER BY emulates it with a CASE sort key.
     fn supports_nulls_ordering(&self) -> bool {
         true
     }
@@ -219,14 +265,22 @@ pub trait SqlDialect: std::fmt::Debug {
     // PIVOT
     // =========================================================================
 
-    /// Whether this dialect has native PIVOT syntax.
+    /// Whether this dialect has native PIVOT and UNPIVOT syntax.
     ///
-    /// T-SQL and DuckDB have native PIVOT.
-    /// PostgreSQL/MySQL use CASE expressions.
+    /// T-SQL, DuckDB, Snowflake and Databricks have native PIVOT.
+    /// Others use conditional aggregation and UNION ALL.
     fn supports_native_pivot(&self) -> bool {
         false
     }
 
+    /// Whether `CROSS JOIN LATERAL (VALUES ...)` can reference columns of
+    /// the preceding FROM item.
+    ///
+    /// Lets UNPIVOT be emulated with a single scan instead of UNION ALL.
+    fn supports_lateral_values(&self) -> bool {
+        false
+    }
+
     // =========================================================================
     // Misc
     // =========================================================================
@@ -263,7 +317,7 @@ pub trait SqlDialect: std::fmt::Debug {
 
     /// Whether this dialect supports QUALIFY clause for window filtering.
     ///
-    /// Only DuckDB supports QUALIFY.
+    /// Elsewhere the query is wrapped in a subquery filtered with WHERE.
     fn supports_qualify(&self) -> bool {
         false
     }
@@ -287,7 +341,8 @@ pub trait SqlDialect: std::fmt::Debug {
     /// - `LENGTH` → `LEN` (T-SQL)
     ///
     /// Returns `Some(new_name)` if the function should be remapped, `None` to keep original.
-    /// The input is matched case-insensitively.
+    /// The input is matched case-insensitively. Date functions whose arguments also
+    /// differ are rewritten before renaming; see [`functions`].
     fn remap_function(&self, name: &str) -> Option<&'static str> {
         // Default: no remapping
         let _ = name;
@@ -431,6 +486,9 @@ pub enum Dialect {
     BigQuery,
     Redshift,
     Databricks,
+    Sqlite,
+    ClickHouse,
+    Trino,
 }
 
 impl Dialect {
@@ -445,6 +503,9 @@ impl Dialect {
             Dialect::BigQuery => &BigQuery,
             Dialect::Redshift => &Redshift,
             Dialect::Databricks => &Databricks,
+            Dialect::Sqlite => &Sqlite,
+            Dialect::ClickHouse => &ClickHouse,
+            Dialect::Trino => &Trino,
         }
     }
 }
@@ -467,6 +528,14 @@ impl SqlDialect for Dialect {
         self.dialect().format_bool(b)
     }
 
+    fn placeholder(&self, index: usize, param: &Param) -> String {
+        self.dialect().placeholder(index, param)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        self.dialect().numbered_placeholders()
+    }
+
     fn emit_limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> TokenStream {
         self.dialect().emit_limit_offset(limit, offset)
     }
@@ -495,6 +564,10 @@ impl SqlDialect for Dialect {
         self.dialect().supports_lateral()
     }
 
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        self.dialect().supports_parenthesized_set_operands()
+    }
+
     fn supports_nulls_ordering(&self) -> bool {
         self.dialect().supports_nulls_ordering()
     }
@@ -507,6 +580,10 @@ impl SqlDialect for Dialect {
         self.dialect().supports_native_pivot()
     }
 
+    fn supports_lateral_values(&self) -> bool {
+        self.dialect().supports_lateral_values()
+    }
+
     fn supports_returning(&self) -> bool {
         self.dialect().supports_returning()
     }
@@ -584,6 +661,27 @@ impl SqlDialect for Dialect {
     }
 }
 
+impl Dialect {
+    /// Look up a dialect by the name it displays as (e.g. `"tsql"`), ignoring case.
+    pub fn from_name(name: &str) -> Option<Dialect> {
+        [
+            Dialect::DuckDb,
+            Dialect::TSql,
+            Dialect::MySql,
+            Dialect::Postgres,
+            Dialect::Snowflake,
+            Dialect::BigQuery,
+            Dialect::Redshift,
+            Dialect::Databricks,
+            Dialect::Sqlite,
+            Dialect::ClickHouse,
+            Dialect::Trino,
+        ]
+        .into_iter()
+        .find(|d| d.name().eq_ignore_ascii_case(name))
+    }
+}
+
 impl std::fmt::Display for Dialect {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.dialect().name())
@@ -602,6 +700,13 @@ mod tests {
         assert_eq!(Dialect::MySql.to_string(), "mysql");
     }
 
+    #[test]
+    fn test_dialect_from_name() {
+        assert_eq!(Dialect::from_name("tsql"), Some(Dialect::TSql));
+        assert_eq!(Dialect::from_name("Postgres"), Some(Dialect::Postgres));
+        assert_eq!(Dialect::from_name("oracle"), None);
+    }
+
     #[test]
     fn test_quote_identifier() {
         assert_eq!(Dialect::DuckDb.quote_identifier("users"), "\"users\"");
@@ -842,4 +947,184 @@ mod tests {
         );
         assert_eq!(Dialect::Databricks.remap_function("NVL"), Some("COALESCE"));
     }
+
+    #[test]
+    fn test_sqlite_dialect() {
+        assert_eq!(Dialect::Sqlite.to_string(), "sqlite");
+        assert_eq!(Dialect::Sqlite.quote_identifier("weird\"name"), "\"weird\"\"name\"");
+        assert_eq!(Dialect::Sqlite.format_bool(true), "1");
+        assert_eq!(Dialect::Sqlite.format_date_literal("2024-01-01"), "'2024-01-01'");
+
+        assert!(!Dialect::Sqlite.supports_full_outer_join());
+        assert!(!Dialect::Sqlite.supports_parenthesized_set_operands());
+        assert!(!Dialect::Sqlite.supports_merge());
+        assert!(!Dialect::Sqlite.supports_truncate());
+        assert!(Dialect::Sqlite.supports_returning());
+
+        // Argument order differs from STRFTIME(format, value)
+        assert_eq!(Dialect::Sqlite.remap_function("TO_CHAR"), None);
+        assert_eq!(Dialect::Sqlite.remap_function("DATE_FORMAT"), None);
+        assert_eq!(Dialect::Sqlite.remap_function("FORMAT_TIMESTAMP"), Some("STRFTIME"));
+        assert_eq!(Dialect::Sqlite.remap_function("STRFTIME"), None); // native
+        assert_eq!(Dialect::Sqlite.remap_function("NVL"), Some("IFNULL"));
+    }
+
+
+    // =========================================================================
+    // ClickHouse / Trino
+    // =========================================================================
+
+    #[test]
+    fn test_clickhouse_trino_display() {
+        assert_eq!(Dialect::ClickHouse.to_string(), "clickhouse");
+        assert_eq!(Dialect::Trino.to_string(), "trino");
+    }
+
+    #[test]
+    fn test_clickhouse_trino_quoting() {
+        assert_eq!(Dialect::ClickHouse.quote_identifier("users"), "`users`");
+        assert_eq!(
+            Dialect::ClickHouse.quote_identifier("weird`name"),
+            "`weird``name`"
+        );
+        assert_eq!(Dialect::Trino.quote_identifier("users"), "\"users\"");
+        assert_eq!(
+            Dialect::Trino.quote_identifier("weird\"name"),
+            "\"weird\"\"name\""
+        );
+
+        // ClickHouse treats backslash as an escape character in literals
+        assert_eq!(Dialect::ClickHouse.quote_string(r"C:\tmp"), r"'C:\\tmp'");
+        assert_eq!(Dialect::ClickHouse.quote_string("it's"), "'it''s'");
+        assert_eq!(Dialect::Trino.quote_string(r"C:\tmp"), r"'C:\tmp'");
+    }
+
+    #[test]
+    fn test_clickhouse_trino_literals() {
+        assert_eq!(Dialect::ClickHouse.format_bool(true), "true");
+        assert_eq!(Dialect::Trino.format_bool(false), "false");
+        assert_eq!(
+            Dialect::ClickHouse.format_date_literal("2024-01-01"),
+            "toDate('2024-01-01')"
+        );
+        assert_eq!(
+            Dialect::Trino.format_date_literal("2024-01-01"),
+            "DATE '2024-01-01'"
+        );
+    }
+
+    #[test]
+    fn test_clickhouse_trino_pagination() {
+        let ch = Dialect::ClickHouse.emit_limit_offset(Some(10), Some(20));
+        assert_eq!(ch.serialize(Dialect::ClickHouse), "LIMIT 10 OFFSET 20");
+
+        // Trino puts OFFSET before LIMIT
+        let trino = Dialect::Trino.emit_limit_offset(Some(10), Some(20));
+        assert_eq!(trino.serialize(Dialect::Trino), "OFFSET 20 LIMIT 10");
+        let trino = Dialect::Trino.emit_limit_offset(Some(10), None);
+        assert_eq!(trino.serialize(Dialect::Trino), "LIMIT 10");
+    }
+
+    #[test]
+    fn test_clickhouse_trino_data_types() {
+        use crate::model::types::DataType;
+
+        assert_eq!(Dialect::ClickHouse.emit_data_type(&DataType::Int64), "Int64");
+        assert_eq!(Dialect::ClickHouse.emit_data_type(&DataType::Varchar(50)), "String");
+        assert_eq!(
+            Dialect::ClickHouse.emit_data_type(&DataType::Decimal(18, 2)),
+            "Decimal(18, 2)"
+        );
+        assert_eq!(
+            Dialect::ClickHouse.emit_data_type(&DataType::Timestamp),
+            "DateTime64(6)"
+        );
+        assert_eq!(Dialect::ClickHouse.emit_data_type(&DataType::Uuid), "UUID");
+
+        assert_eq!(Dialect::Trino.emit_data_type(&DataType::Int64), "BIGINT");
+        assert_eq!(Dialect::Trino.emit_data_type(&DataType::String), "VARCHAR");
+        assert_eq!(Dialect::Trino.emit_data_type(&DataType::Float64), "DOUBLE");
+        assert_eq!(Dialect::Trino.emit_data_type(&DataType::Binary), "VARBINARY");
+        assert_eq!(
+            Dialect::Trino.emit_data_type(&DataType::TimestampTz),
+            "TIMESTAMP(6) WITH TIME ZONE"
+        );
+
+        // Neither has auto-increment columns
+        assert!(Dialect::ClickHouse.emit_identity(1, 1).tokens().is_empty());
+        assert!(Dialect::Trino.emit_identity(1, 1).tokens().is_empty());
+    }
+
+    #[test]
+    fn test_clickhouse_trino_features() {
+        // ClickHouse features
+        assert!(Dialect::ClickHouse.supports_distinct_on());
+        assert!(Dialect::ClickHouse.supports_named_windows());
+        assert!(Dialect::ClickHouse.supports_full_outer_join());
+        assert!(!Dialect::ClickHouse.supports_merge());
+        assert!(!Dialect::ClickHouse.supports_returning());
+        assert!(!Dialect::ClickHouse.supports_lateral());
+        assert!(!Dialect::ClickHouse.supports_native_pivot());
+        assert!(!Dialect::ClickHouse.supports_qualify());
+
+        // Trino features
+        assert!(Dialect::Trino.supports_merge());
+        assert!(Dialect::Trino.supports_aggregate_filter());
+        assert!(Dialect::Trino.supports_groups_frame());
+        assert!(Dialect::Trino.supports_materialized_view());
+        assert!(!Dialect::Trino.supports_returning());
+        assert!(!Dialect::Trino.supports_distinct_on());
+        assert!(!Dialect::Trino.supports_native_pivot());
+        assert!(!Dialect::Trino.supports_qualify());
+    }
+
+    #[test]
+    fn test_clickhouse_trino_function_remapping() {
+        assert_eq!(
+            Dialect::ClickHouse.remap_function("STRFTIME"),
+            Some("DATE_FORMAT")
+        );
+        assert_eq!(Dialect::ClickHouse.remap_function("NVL"), Some("IFNULL"));
+        assert_eq!(Dialect::ClickHouse.remap_function("LEN"), Some("LENGTH"));
+        assert_eq!(Dialect::ClickHouse.remap_function("IFNULL"), None); // native
+
+        assert_eq!(Dialect::Trino.remap_function("STRFTIME"), Some("DATE_FORMAT"));
+        assert_eq!(Dialect::Trino.remap_function("TO_CHAR"), Some("DATE_FORMAT"));
+        assert_eq!(Dialect::Trino.remap_function("NVL"), Some("COALESCE"));
+        assert_eq!(Dialect::Trino.remap_function("IFNULL"), Some("COALESCE"));
+        assert_eq!(Dialect::Trino.remap_function("CUSTOM_FUNC"), None);
+    }
+
+    #[test]
+    fn test_clickhouse_trino_emitted_sql_parses() {
+        use crate::model::types::DataType;
+        use crate::sql::ddl::{ColumnDef, CreateTable};
+        use crate::sql::dml::Insert;
+        use crate::sql::expr::{col, func, lit_int, lit_str, ExprExt};
+        use crate::sql::query::{OrderByExpr, Query, TableRef};
+        use crate::sql::test_utils::validate_sql;
+
+        for dialect in [Dialect::ClickHouse, Dialect::Trino] {
+            let query = Query::new()
+                .select(vec![col("region"), func("NVL", vec![col("name"), lit_str("n/a")])])
+                .from(TableRef::new("customers").with_schema("sales"))
+                .filter(col("id").gt(lit_int(10)))
+                .order_by(vec![OrderByExpr::desc(col("region"))])
+                .limit(10)
+                .offset(5);
+            validate_sql(&query.to_sql(dialect), dialect).unwrap();
+
+            let create = CreateTable::new("customers")
+                .column(ColumnDef::new("id", DataType::Int64).not_null())
+                .column(ColumnDef::new("name", DataType::Varchar(100)))
+                .column(ColumnDef::new("amount", DataType::Decimal(18, 2)));
+            validate_sql(&create.to_sql(dialect), dialect).unwrap();
+
+            let insert = Insert::into("customers")
+                .columns(["id", "name"])
+                .values([lit_int(1), lit_str("Acme")]);
+            validate_sql(&insert.to_sql(dialect), dialect).unwrap();
+        }
+    }
+
 }
@@ -12,6 +12,7 @@
 
 use super::helpers;
 use super::SqlDialect;
+use crate::sql::params::Param;
 
 /// PostgreSQL SQL dialect.
 #[derive(Debug, Clone, Copy)]
@@ -30,6 +31,14 @@ impl SqlDialect for Postgres {
         helpers::format_bool_literal(b)
     }
 
+    fn placeholder(&self, index: usize, param: &Param) -> String {
+        helpers::placeholder_dollar(index, param, "NUMERIC")
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
     // Uses default emit_limit_offset (LIMIT ... OFFSET ...)
 
     fn supports_distinct_on(&self) -> bool {
@@ -48,6 +57,10 @@ impl SqlDialect for Postgres {
         true
     }
 
+    fn supports_lateral_values(&self) -> bool {
+        true
+    }
+
     fn remap_function(&self, name: &str) -> Option<&'static str> {
         helpers::remap_function_postgres(name)
     }
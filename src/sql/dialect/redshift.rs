@@ -10,6 +10,7 @@
 
 use super::helpers;
 use super::SqlDialect;
+use crate::sql::params::Param;
 
 /// Amazon Redshift SQL dialect.
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +29,14 @@ impl SqlDialect for Redshift {
         helpers::format_bool_literal(b)
     }
 
+    fn placeholder(&self, index: usize, param: &Param) -> String {
+        helpers::placeholder_dollar(index, param, "NUMERIC")
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
     // Redshift supports most Postgres features
     fn supports_distinct_on(&self) -> bool {
         true
@@ -51,7 +60,7 @@ impl SqlDialect for Redshift {
     }
 
     fn supports_merge(&self) -> bool {
-        false // Use DELETE + INSERT
+        false // Emulated with DELETE, UPDATE and INSERT
     }
 
     fn supports_qualify(&self) -> bool {
@@ -10,6 +10,7 @@
 
 use super::helpers;
 use super::SqlDialect;
+use crate::sql::params::Param;
 use crate::sql::token::TokenStream;
 
 /// Snowflake SQL dialect.
@@ -29,6 +30,14 @@ impl SqlDialect for Snowflake {
         helpers::format_bool_literal(b)
     }
 
+    fn placeholder(&self, index: usize, _param: &Param) -> String {
+        format!(":{}", index)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
     fn supports_native_pivot(&self) -> bool {
         true
     }
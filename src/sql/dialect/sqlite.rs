@@ -0,0 +1,370 @@
+//! SQLite dialect.
+//!
+//! SQLite differences from ANSI:
+//! - ANSI identifier quoting (`"`)
+//! - No boolean type, uses 1/0
+//! - Type affinity instead of strict types (INTEGER, REAL, NUMERIC, TEXT, BLOB)
+//! - LIMIT ... OFFSET ..., with OFFSET only valid after LIMIT
+//! - No FULL OUTER JOIN before 3.39 (emulated with LEFT JOIN + UNION ALL)
+//! - INSERT ... ON CONFLICT for upserts
+//! - STRFTIME for date formatting
+//! - No TRUNCATE (DELETE FROM instead)
+//! - Compound SELECT members cannot be parenthesized
+
+use super::helpers;
+use super::SqlDialect;
+use crate::sql::token::TokenStream;
+
+/// SQLite dialect.
+#[derive(Debug, Clone, Copy)]
+pub struct Sqlite;
+
+impl SqlDialect for Sqlite {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        helpers::quote_double(ident)
+    }
+
+    fn format_bool(&self, b: bool) -> &'static str {
+        helpers::format_bool_numeric(b)
+    }
+
+    fn emit_limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> TokenStream {
+        helpers::emit_limit_offset_sqlite(limit, offset)
+    }
+
+    fn supports_full_outer_join(&self) -> bool {
+        // FULL and RIGHT joins arrived in 3.39; being conservative here
+        false
+    }
+
+    fn supports_lateral(&self) -> bool {
+        false
+    }
+
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        false
+    }
+
+    fn format_date_literal(&self, date: &str) -> String {
+        // Dates are ISO-8601 text
+        format!("'{}'", date)
+    }
+
+    fn supports_aggregate_filter(&self) -> bool {
+        true
+    }
+
+    fn supports_groups_frame(&self) -> bool {
+        true
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        true
+    }
+
+    fn remap_function(&self, name: &str) -> Option<&'static str> {
+        helpers::remap_function_sqlite(name)
+    }
+
+    fn emit_data_type(&self, dt: &crate::model::types::DataType) -> String {
+        helpers::emit_data_type_sqlite(dt)
+    }
+
+    fn emit_identity(&self, start: i64, increment: i64) -> TokenStream {
+        helpers::emit_identity_none(start, increment)
+    }
+
+    fn supports_drop_cascade(&self) -> bool {
+        false
+    }
+
+    fn supports_include_columns(&self) -> bool {
+        false
+    }
+
+    fn supports_truncate(&self) -> bool {
+        false
+    }
+
+    fn supports_create_or_replace_view(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql::ddl::{ColumnDef, CreateTable, DataType, Truncate};
+    use crate::sql::dialect::Dialect;
+    use crate::sql::dml::{Insert, OnConflict};
+    use crate::sql::expr::{
+        col, count, func, lit_bool, lit_int, lit_null, lit_str, table_col, Expr, ExprExt,
+    };
+    use crate::sql::query::{OrderByExpr, Query, SelectExpr, TableRef};
+    use rusqlite::Connection;
+
+    const D: Dialect = Dialect::Sqlite;
+
+    fn db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        for (name, rows) in [
+            ("customers", vec![(1, "Alice"), (2, "Bob"), (3, "Cara")]),
+            ("accounts", vec![(2, "B-1"), (3, "C-1"), (4, "D-1")]),
+        ] {
+            let create = CreateTable::new(name)
+                .column(ColumnDef::new("id", DataType::Int64).primary_key())
+                .column(ColumnDef::new("name", DataType::Varchar(100)).not_null())
+                .column(ColumnDef::new("active", DataType::Bool))
+                .column(ColumnDef::new("created_at", DataType::Timestamp));
+            conn.execute(&create.to_sql(D), []).unwrap();
+
+            let mut insert = Insert::into(name).columns(["id", "name", "active", "created_at"]);
+            for (id, label) in rows {
+                insert = insert.values([
+                    lit_int(id),
+                    lit_str(label),
+                    lit_bool(id % 2 == 1),
+                    lit_str("2024-03-15 10:30:00"),
+                ]);
+            }
+            conn.execute(&insert.to_sql(D), []).unwrap();
+        }
+        conn
+    }
+
+    fn rows(conn: &Connection, sql: &str) -> Vec<(Option<i64>, Option<String>)> {
+        let mut stmt = conn.prepare(sql).unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    #[test]
+    fn test_types_and_booleans() {
+        let conn = db();
+        let sql = Query::new()
+            .select(vec![col("id"), col("name")])
+            .from(TableRef::new("customers"))
+            .filter(col("active").eq(lit_bool(true)))
+            .order_by(vec![OrderByExpr::asc(col("id"))])
+            .to_sql(D);
+        assert!(sql.contains("\"active\" = 1"), "SQL: {}", sql);
+        assert_eq!(
+            rows(&conn, &sql),
+            vec![
+                (Some(1), Some("Alice".into())),
+                (Some(3), Some("Cara".into()))
+            ]
+        );
+
+        let ddl = CreateTable::new("t")
+            .column(
+                ColumnDef::new("id", DataType::Int64)
+                    .identity()
+                    .primary_key(),
+            )
+            .column(ColumnDef::new("amount", DataType::Decimal(10, 2)))
+            .to_sql(D);
+        assert!(ddl.contains("\"id\" INTEGER PRIMARY KEY"), "SQL: {}", ddl);
+        assert!(ddl.contains("\"amount\" NUMERIC"), "SQL: {}", ddl);
+        conn.execute(&ddl, []).unwrap();
+    }
+
+    #[test]
+    fn test_offset_without_limit() {
+        let conn = db();
+        let sql = Query::new()
+            .select(vec![col("id"), col("name")])
+            .from(TableRef::new("customers"))
+            .order_by(vec![OrderByExpr::asc(col("id"))])
+            .offset(1)
+            .to_sql(D);
+        assert!(sql.ends_with("LIMIT -1 OFFSET 1"), "SQL: {}", sql);
+        assert_eq!(rows(&conn, &sql).len(), 2);
+    }
+
+    #[test]
+    fn test_full_outer_join_emulated() {
+        let conn = db();
+        let sql = Query::new()
+            .select(vec![
+                SelectExpr::new(table_col("c", "id")).with_alias("customer_id"),
+                SelectExpr::new(table_col("a", "name")).with_alias("account"),
+            ])
+            .from(TableRef::new("customers").with_alias("c"))
+            .full_join(
+                TableRef::new("accounts").with_alias("a"),
+                table_col("c", "id").eq(table_col("a", "id")),
+            )
+            .order_by(vec![OrderByExpr::asc(table_col("a", "name"))])
+            .to_sql(D);
+        assert!(!sql.contains("FULL"), "SQL: {}", sql);
+        assert!(sql.contains("NOT EXISTS"), "SQL: {}", sql);
+
+        assert_eq!(
+            rows(&conn, &sql),
+            vec![
+                (Some(1), None),
+                (Some(2), Some("B-1".into())),
+                (Some(3), Some("C-1".into())),
+                (None, Some("D-1".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chained_full_outer_joins_emulated() {
+        let conn = db();
+        conn.execute_batch(
+            "CREATE TABLE regions (id INTEGER, name TEXT);
+             INSERT INTO regions VALUES (3, 'north'), (5, 'south');",
+        )
+        .unwrap();
+
+        let sql = Query::new()
+            .select(vec![
+                SelectExpr::new(func(
+                    "COALESCE",
+                    vec![
+                        table_col("c", "id"),
+                        table_col("a", "id"),
+                        table_col("r", "id"),
+                    ],
+                ))
+                .with_alias("id"),
+                SelectExpr::new(table_col("r", "name")).with_alias("region"),
+            ])
+            .from(TableRef::new("customers").with_alias("c"))
+            .full_join(
+                TableRef::new("accounts").with_alias("a"),
+                table_col("c", "id").eq(table_col("a", "id")),
+            )
+            .full_join(
+                TableRef::new("regions").with_alias("r"),
+                table_col("a", "id").eq(table_col("r", "id")),
+            )
+            .to_sql(D);
+
+        let mut ids: Vec<_> = rows(&conn, &sql).into_iter().map(|(id, _)| id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![Some(1), Some(2), Some(3), Some(4), Some(5)]);
+    }
+
+    #[test]
+    fn test_grouped_full_outer_join_emulated() {
+        let conn = db();
+        let has_account = Expr::Case {
+            operand: None,
+            when_clauses: vec![(table_col("a", "id").is_null(), lit_str("none"))],
+            else_clause: Some(Box::new(lit_str("some"))),
+        };
+        let sql = Query::new()
+            .select(vec![
+                SelectExpr::new(count(table_col("c", "id"))).with_alias("customers"),
+                SelectExpr::new(has_account.clone()).with_alias("accounts"),
+            ])
+            .from(TableRef::new("customers").with_alias("c"))
+            .full_join(
+                TableRef::new("accounts").with_alias("a"),
+                table_col("c", "id").eq(table_col("a", "id")),
+            )
+            .group_by(vec![has_account])
+            .order_by(vec![OrderByExpr::asc(col("accounts"))])
+            .to_sql(D);
+        assert!(!sql.contains("FULL"), "SQL: {}", sql);
+        assert!(sql.contains(") AS \"full_join\""), "SQL: {}", sql);
+
+        // Each group is formed once, not once per UNION branch
+        assert_eq!(
+            rows(&conn, &sql),
+            vec![
+                (Some(1), Some("none".into())),
+                (Some(2), Some("some".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_upsert_on_conflict() {
+        let conn = db();
+        let upsert = Insert::into("customers")
+            .columns(["id", "name"])
+            .values([lit_int(2), lit_str("Bobby")])
+            .values([lit_int(9), lit_str("Zed")])
+            .on_conflict(OnConflict::do_update(
+                ["id"],
+                [("name", table_col("excluded", "name"))],
+            ));
+        conn.execute(&upsert.to_sql(D), []).unwrap();
+
+        let from_select = Insert::into("customers")
+            .columns(["id", "name"])
+            .from_select(
+                Query::new()
+                    .select(vec![col("id"), col("name")])
+                    .from(TableRef::new("accounts")),
+            )
+            .on_conflict(OnConflict::do_nothing());
+        let sql = from_select.to_sql(D);
+        assert!(sql.contains("WHERE 1"), "SQL: {}", sql);
+        conn.execute(&sql, []).unwrap();
+
+        let sql = Query::new()
+            .select(vec![col("id"), col("name")])
+            .from(TableRef::new("customers"))
+            .order_by(vec![OrderByExpr::asc(col("id"))])
+            .to_sql(D);
+        assert_eq!(
+            rows(&conn, &sql),
+            vec![
+                (Some(1), Some("Alice".into())),
+                (Some(2), Some("Bobby".into())),
+                (Some(3), Some("Cara".into())),
+                (Some(4), Some("D-1".into())),
+                (Some(9), Some("Zed".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_remapping_executes() {
+        let conn = db();
+        let sql = Query::new()
+            .select(vec![
+                func("LEN", vec![col("name")]),
+                func("NVL", vec![lit_null(), col("name")]),
+            ])
+            .from(TableRef::new("customers"))
+            .filter(col("id").eq(lit_int(1)))
+            .to_sql(D);
+        assert!(sql.contains("LENGTH(\"name\")"), "SQL: {}", sql);
+        assert!(sql.contains("IFNULL("), "SQL: {}", sql);
+        assert_eq!(rows(&conn, &sql), vec![(Some(5), Some("Alice".into()))]);
+    }
+
+    #[test]
+    fn test_set_operation_and_truncate() {
+        let conn = db();
+        let sql = Query::new()
+            .select(vec![col("id"), col("name")])
+            .from(TableRef::new("customers"))
+            .union_all(
+                Query::new()
+                    .select(vec![col("id"), col("name")])
+                    .from(TableRef::new("accounts"))
+                    .order_by(vec![OrderByExpr::desc(col("id"))])
+                    .limit(1),
+            )
+            .to_sql(D);
+        assert!(!sql.starts_with('('), "SQL: {}", sql);
+        assert_eq!(rows(&conn, &sql).len(), 4);
+
+        conn.execute(&Truncate::table("customers").to_sql(D), [])
+            .unwrap();
+        assert!(rows(&conn, "SELECT id, name FROM customers").is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+//! Versioned dialect targets.
+//!
+//! A bare [`Dialect`] answers feature questions for the newest release it knows
+//! about. A [`DialectTarget`] pins the server version, so feature flags answer for
+//! that release and emitters fall back to emulations where it comes up short:
+//!
+//! | Feature | Needs | Emulation |
+//! |---------|-------|-----------|
+//! | MERGE | PostgreSQL 15, SQL Server 2008 | DELETE, UPDATE and INSERT in a transaction |
+//! | NULLS FIRST/LAST | PostgreSQL 8.3, SQLite 3.30 | `CASE WHEN x IS NULL` sort key |
+//! | GROUPS frame | PostgreSQL 11, SQLite 3.28 | ROWS frame |
+//! | FILTER clause | PostgreSQL 9.4, SQLite 3.30 | - |
+//! | RETURNING | PostgreSQL 8.2, SQLite 3.35 | omitted |
+//! | Materialized views | PostgreSQL 9.3 | - |
+//! | DROP ... IF EXISTS | SQL Server 2016 | - |
+//!
+//! A few features only switch on when a new enough version is known, because the
+//! unversioned dialect is conservative about them: FULL OUTER JOIN on SQLite 3.39+,
+//! and the WINDOW clause and `DATETRUNC` on SQL Server 2022+.
+//!
+//! SQL Server versions are engine versions (`15` is 2019, `16` is 2022); use
+//! [`Version::sql_server`] to convert a release year.
+
+use super::{Dialect, SqlDialect};
+use crate::sql::params::Param;
+use crate::sql::token::TokenStream;
+
+/// A database server version, compared as `major.minor.patch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    /// Create a `major.minor` version.
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+
+    /// The SQL Server engine version for a release year (2019 → 15, 2022 → 16).
+    pub fn sql_server(year: u32) -> Self {
+        let major = match year {
+            ..=2008 => 10,
+            2009..=2012 => 11,
+            2013..=2014 => 12,
+            2015..=2016 => 13,
+            2017..=2018 => 14,
+            2019..=2021 => 15,
+            2022..=2024 => 16,
+            _ => 17,
+        };
+        Self::new(major, 0)
+    }
+
+    /// Parse the first version number in a server version string.
+    ///
+    /// Accepts bare versions (`8.0.34`) and banners such as
+    /// `PostgreSQL 14.9 on x86_64-pc-linux-gnu` or `v1.1.3`. Dotted numbers are
+    /// preferred, so the `2019` in `Microsoft SQL Server 2019 ... - 15.0.2000.5`
+    /// is skipped in favour of `15.0.2000`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let runs: Vec<&str> = s
+            .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .map(|run| run.trim_matches('.'))
+            .filter(|run| !run.is_empty())
+            .collect();
+        let run = runs
+            .iter()
+            .find(|run| run.contains('.'))
+            .or_else(|| runs.first())?;
+
+        let mut parts = run.split('.').map(|p| p.parse::<u32>());
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+        let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if self.patch > 0 {
+            write!(f, ".{}", self.patch)?;
+        }
+        Ok(())
+    }
+}
+
+/// A dialect plus the server version SQL is generated for.
+///
+/// Every `to_sql` path accepts `impl Into<DialectTarget>`, so a bare [`Dialect`]
+/// still works and means "latest version".
+///
+/// ```ignore
+/// let pg14 = Dialect::Postgres.at(Version::new(14, 0));
+/// assert!(!pg14.supports_merge());
+/// let sql = merge.to_sql(pg14)?; // UPDATE + INSERT in a transaction
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DialectTarget {
+    pub dialect: Dialect,
+    /// `None` means the latest version.
+    pub version: Option<Version>,
+}
+
+impl DialectTarget {
+    /// Target the latest version of `dialect`.
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            version: None,
+        }
+    }
+
+    /// Pin the server version.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Build a target from a server's reported product name and version string.
+    ///
+    /// Returns `None` for products without a dialect. An unparseable version
+    /// leaves the target at the latest version.
+    pub fn from_server(product_name: &str, product_version: &str) -> Option<Self> {
+        let dialect = Dialect::from_product_name(product_name)?;
+        let version = Version::parse(product_version).map(|v| match dialect {
+            // Year-only banners, e.g. "2019"
+            Dialect::TSql if v.major >= 2000 => Version::sql_server(v.major),
+            _ => v,
+        });
+        Some(Self { dialect, version })
+    }
+
+    /// Whether the target is at least `major.minor`. An unknown version counts as the latest.
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        self.version.is_none_or(|v| v >= Version::new(major, minor))
+    }
+
+    /// Whether the version is known and at least `major.minor`.
+    pub fn known_at_least(&self, major: u32, minor: u32) -> bool {
+        self.version
+            .is_some_and(|v| v >= Version::new(major, minor))
+    }
+}
+
+impl Dialect {
+    /// Target a specific server version of this dialect.
+    pub fn at(self, version: Version) -> DialectTarget {
+        DialectTarget::new(self).with_version(version)
+    }
+
+    /// Map a server product name (as reported by the driver) to a dialect.
+    pub fn from_product_name(name: &str) -> Option<Dialect> {
+        let name = name.to_lowercase();
+        let dialect = if name.contains("redshift") {
+            Dialect::Redshift
+        } else if name.contains("sql server") || name.contains("azure sql") {
+            Dialect::TSql
+        } else if name.contains("postgres") {
+            Dialect::Postgres
+        } else if name.contains("mysql") || name.contains("mariadb") {
+            Dialect::MySql
+        } else if name.contains("duckdb") {
+            Dialect::DuckDb
+        } else if name.contains("snowflake") {
+            Dialect::Snowflake
+        } else if name.contains("bigquery") {
+            Dialect::BigQuery
+        } else if name.contains("databricks") || name.contains("spark") {
+            Dialect::Databricks
+        } else if name.contains("sqlite") {
+            Dialect::Sqlite
+        } else if name.contains("clickhouse") {
+            Dialect::ClickHouse
+        } else if name.contains("trino") || name.contains("presto") {
+            Dialect::Trino
+        } else {
+            return None;
+        };
+        Some(dialect)
+    }
+}
+
+impl From<Dialect> for DialectTarget {
+    fn from(dialect: Dialect) -> Self {
+        Self::new(dialect)
+    }
+}
+
+impl PartialEq<Dialect> for DialectTarget {
+    fn eq(&self, other: &Dialect) -> bool {
+        self.dialect == *other
+    }
+}
+
+impl std::fmt::Display for DialectTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "{} {}", self.dialect, version),
+            None => write!(f, "{}", self.dialect),
+        }
+    }
+}
+
+// Delegate to the dialect, gating features on the target version
+impl SqlDialect for DialectTarget {
+    fn name(&self) -> &'static str {
+        self.dialect.name()
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        self.dialect.quote_identifier(ident)
+    }
+
+    fn quote_string(&self, s: &str) -> String {
+        self.dialect.quote_string(s)
+    }
+
+    fn format_bool(&self, b: bool) -> &'static str {
+        self.dialect.format_bool(b)
+    }
+
+    fn placeholder(&self, index: usize, param: &Param) -> String {
+        self.dialect.placeholder(index, param)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        self.dialect.numbered_placeholders()
+    }
+
+    fn emit_limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> TokenStream {
+        self.dialect.emit_limit_offset(limit, offset)
+    }
+
+    fn requires_order_by_for_offset(&self) -> bool {
+        self.dialect.requires_order_by_for_offset()
+    }
+
+    fn concat_operator(&self) -> &'static str {
+        self.dialect.concat_operator()
+    }
+
+    fn supports_concat_operator(&self) -> bool {
+        self.dialect.supports_concat_operator()
+    }
+
+    fn emit_recursive_keyword(&self) -> bool {
+        self.dialect.emit_recursive_keyword()
+    }
+
+    fn supports_full_outer_join(&self) -> bool {
+        match self.dialect {
+            Dialect::Sqlite => self.known_at_least(3, 39),
+            _ => self.dialect.supports_full_outer_join(),
+        }
+    }
+
+    fn supports_lateral(&self) -> bool {
+        self.dialect.supports_lateral()
+    }
+
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        self.dialect.supports_parenthesized_set_operands()
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        self.dialect.supports_nulls_ordering()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(8, 3),
+                Dialect::Sqlite => self.at_least(3, 30),
+                _ => true,
+            }
+    }
+
+    fn format_date_literal(&self, date: &str) -> String {
+        self.dialect.format_date_literal(date)
+    }
+
+    fn supports_native_pivot(&self) -> bool {
+        self.dialect.supports_native_pivot()
+    }
+
+    fn supports_lateral_values(&self) -> bool {
+        self.dialect.supports_lateral_values()
+    }
+
+    fn supports_returning(&self) -> bool {
+        self.dialect.supports_returning()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(8, 2),
+                Dialect::Sqlite => self.at_least(3, 35),
+                _ => true,
+            }
+    }
+
+    fn supports_distinct_on(&self) -> bool {
+        self.dialect.supports_distinct_on()
+    }
+
+    fn supports_aggregate_filter(&self) -> bool {
+        self.dialect.supports_aggregate_filter()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(9, 4),
+                Dialect::Sqlite => self.at_least(3, 30),
+                _ => true,
+            }
+    }
+
+    fn supports_groups_frame(&self) -> bool {
+        self.dialect.supports_groups_frame()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(11, 0),
+                Dialect::Sqlite => self.at_least(3, 28),
+                _ => true,
+            }
+    }
+
+    fn supports_qualify(&self) -> bool {
+        self.dialect.supports_qualify()
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        match self.dialect {
+            Dialect::TSql => self.known_at_least(16, 0),
+            _ => self.dialect.supports_named_windows(),
+        }
+    }
+
+    fn remap_function(&self, name: &str) -> Option<&'static str> {
+        self.dialect.remap_function(name)
+    }
+
+    fn emit_data_type(&self, dt: &crate::model::types::DataType) -> String {
+        self.dialect.emit_data_type(dt)
+    }
+
+    fn emit_identity(&self, start: i64, increment: i64) -> TokenStream {
+        self.dialect.emit_identity(start, increment)
+    }
+
+    fn supports_if_not_exists(&self) -> bool {
+        self.dialect.supports_if_not_exists()
+    }
+
+    fn supports_if_exists(&self) -> bool {
+        self.dialect.supports_if_exists()
+            && match self.dialect {
+                Dialect::TSql => self.at_least(13, 0),
+                _ => true,
+            }
+    }
+
+    fn supports_drop_cascade(&self) -> bool {
+        self.dialect.supports_drop_cascade()
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        self.dialect.supports_partial_indexes()
+    }
+
+    fn supports_include_columns(&self) -> bool {
+        self.dialect.supports_include_columns()
+    }
+
+    fn supports_merge(&self) -> bool {
+        self.dialect.supports_merge()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(15, 0),
+                Dialect::TSql => self.at_least(10, 0),
+                _ => true,
+            }
+    }
+
+    fn supports_truncate(&self) -> bool {
+        self.dialect.supports_truncate()
+    }
+
+    fn supports_truncate_cascade(&self) -> bool {
+        self.dialect.supports_truncate_cascade()
+    }
+
+    fn supports_create_or_replace_view(&self) -> bool {
+        self.dialect.supports_create_or_replace_view()
+    }
+
+    fn supports_materialized_view(&self) -> bool {
+        self.dialect.supports_materialized_view()
+            && match self.dialect {
+                Dialect::Postgres => self.at_least(9, 3),
+                _ => true,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parse() {
+        let v = |s: &str| Version::parse(s).map(|v| v.to_string());
+        assert_eq!(v("8.0.34").as_deref(), Some("8.0.34"));
+        assert_eq!(v("5.7.44-log").as_deref(), Some("5.7.44"));
+        assert_eq!(
+            v("PostgreSQL 14.9 on x86_64-pc-linux-gnu, compiled by gcc 12.2.0").as_deref(),
+            Some("14.9")
+        );
+        assert_eq!(
+            v("Microsoft SQL Server 2019 (RTM-CU22) - 15.0.4322.2 (X64)").as_deref(),
+            Some("15.0.4322")
+        );
+        assert_eq!(v("v1.1.3").as_deref(), Some("1.1.3"));
+        assert_eq!(v("16").as_deref(), Some("16.0"));
+        assert_eq!(v("unknown"), None);
+
+        assert!(Version::new(3, 39) > Version::new(3, 8));
+        assert_eq!(Version::sql_server(2019), Version::new(15, 0));
+        assert_eq!(Version::sql_server(2022), Version::new(16, 0));
+    }
+
+    #[test]
+    fn test_from_server() {
+        let target = DialectTarget::from_server("PostgreSQL", "14.9").unwrap();
+        assert_eq!(target, Dialect::Postgres.at(Version::new(14, 9)));
+
+        let target = DialectTarget::from_server("Microsoft SQL Server", "2022").unwrap();
+        assert_eq!(target, Dialect::TSql.at(Version::new(16, 0)));
+
+        let target = DialectTarget::from_server("Amazon Redshift", "").unwrap();
+        assert_eq!(target, DialectTarget::new(Dialect::Redshift));
+
+        assert!(DialectTarget::from_server("Oracle", "19c").is_none());
+    }
+
+    #[test]
+    fn test_version_gated_features() {
+        let latest = DialectTarget::from(Dialect::Postgres);
+        let pg10 = Dialect::Postgres.at(Version::new(10, 0));
+        let pg14 = Dialect::Postgres.at(Version::new(14, 0));
+        let pg15 = Dialect::Postgres.at(Version::new(15, 0));
+
+        assert!(latest.supports_merge());
+        assert!(!pg14.supports_merge());
+        assert!(pg15.supports_merge());
+        assert!(!pg10.supports_groups_frame());
+        assert!(pg14.supports_groups_frame());
+
+        // Unknown SQLite versions stay conservative about FULL JOIN
+        assert!(!DialectTarget::from(Dialect::Sqlite).supports_full_outer_join());
+        assert!(!Dialect::Sqlite
+            .at(Version::new(3, 38))
+            .supports_full_outer_join());
+        assert!(Dialect::Sqlite
+            .at(Version::new(3, 39))
+            .supports_full_outer_join());
+        assert!(!Dialect::Sqlite.at(Version::new(3, 34)).supports_returning());
+
+        let mssql_2014 = Dialect::TSql.at(Version::sql_server(2014));
+        let mssql_2022 = Dialect::TSql.at(Version::sql_server(2022));
+        assert!(!mssql_2014.supports_if_exists());
+        assert!(!mssql_2014.supports_named_windows());
+        assert!(mssql_2022.supports_named_windows());
+        assert!(!DialectTarget::from(Dialect::TSql).supports_named_windows());
+
+        // Versions never enable what the dialect lacks
+        assert!(!Dialect::MySql.at(Version::new(99, 0)).supports_merge());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            DialectTarget::from(Dialect::Postgres).to_string(),
+            "postgres"
+        );
+        assert_eq!(
+            Dialect::Postgres.at(Version::new(14, 0)).to_string(),
+            "postgres 14.0"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Trino (formerly PrestoSQL) dialect.
+//!
+//! Trino differences from ANSI:
+//! - ANSI identifier quoting (`"`)
+//! - OFFSET ... LIMIT ... for pagination (offset first)
+//! - VARCHAR/VARBINARY/DOUBLE type names
+//! - No auto-increment columns or indexes
+//! - No RETURNING clause
+//! - MERGE supported (connector permitting)
+//! - No native PIVOT (use conditional aggregation)
+
+use super::helpers;
+use super::SqlDialect;
+use crate::sql::token::TokenStream;
+
+/// Trino dialect.
+#[derive(Debug, Clone, Copy)]
+pub struct Trino;
+
+impl SqlDialect for Trino {
+    fn name(&self) -> &'static str {
+        "trino"
+    }
+
+    fn quote_identifier(&self, ident: &str) -> String {
+        helpers::quote_double(ident)
+    }
+
+    fn format_bool(&self, b: bool) -> &'static str {
+        helpers::format_bool_literal(b)
+    }
+
+    fn emit_limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> TokenStream {
+        helpers::emit_limit_offset_trino(limit, offset)
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn supports_aggregate_filter(&self) -> bool {
+        true
+    }
+
+    fn supports_groups_frame(&self) -> bool {
+        true
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        true
+    }
+
+    fn remap_function(&self, name: &str) -> Option<&'static str> {
+        helpers::remap_function_trino(name)
+    }
+
+    fn emit_data_type(&self, dt: &crate::model::types::DataType) -> String {
+        helpers::emit_data_type_trino(dt)
+    }
+
+    fn emit_identity(&self, start: i64, increment: i64) -> TokenStream {
+        helpers::emit_identity_none(start, increment)
+    }
+
+    fn supports_drop_cascade(&self) -> bool {
+        false
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        false
+    }
+
+    fn supports_include_columns(&self) -> bool {
+        false
+    }
+
+    fn supports_merge(&self) -> bool {
+        true
+    }
+
+    fn supports_materialized_view(&self) -> bool {
+        true
+    }
+}
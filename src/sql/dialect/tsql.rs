@@ -14,6 +14,7 @@
 
 use super::helpers;
 use super::SqlDialect;
+use crate::sql::params::Param;
 use crate::sql::token::TokenStream;
 
 /// T-SQL (SQL Server) dialect.
@@ -43,6 +44,14 @@ impl SqlDialect for TSql {
         helpers::format_bool_numeric(b)
     }
 
+    fn placeholder(&self, index: usize, _param: &Param) -> String {
+        format!("@p{}", index)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
     fn emit_limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> TokenStream {
         helpers::emit_limit_offset_tsql(limit, offset)
     }
@@ -66,8 +75,7 @@ impl SqlDialect for TSql {
     }
 
     fn supports_nulls_ordering(&self) -> bool {
-        // T-SQL 2022+ supports NULLS FIRST/LAST, but older versions don't
-        // Being conservative here
+        // No version of SQL Server has NULLS FIRST/LAST; ORDER BY emulates it
         false
     }
 
@@ -25,11 +25,27 @@
 //!     .filter(col("status").eq(lit_str("inactive")));
 //! ```
 
-use super::dialect::{Dialect, SqlDialect};
-use super::expr::Expr;
+use thiserror::Error;
+
+use super::dialect::{Dialect, DialectTarget, SqlDialect};
+use super::expr::{lit_bool, Expr, ExprExt};
+use super::params::ParameterizedSql;
 use super::query::Query;
 use super::token::{Token, TokenStream};
 
+/// Errors from generating DML for a target dialect.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DmlError {
+    #[error("{statement} is not supported by {dialect}")]
+    UnsupportedStatement {
+        statement: &'static str,
+        dialect: DialectTarget,
+    },
+}
+
+/// Result type for DML generation.
+pub type DmlResult<T> = Result<T, DmlError>;
+
 // ============================================================================
 // INSERT
 // ============================================================================
@@ -105,12 +121,20 @@ impl Insert {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
+    /// Convert to SQL with literal values as bind parameters.
+    pub fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        self.to_tokens(dialect).serialize_parameterized(dialect)
+    }
+
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // INSERT INTO
@@ -139,7 +163,7 @@ impl Insert {
         }
 
         // T-SQL: OUTPUT goes before VALUES
-        if !self.returning.is_empty() && matches!(dialect, Dialect::TSql) {
+        if !self.returning.is_empty() && dialect == Dialect::TSql {
             ts.space()
                 .push(Token::Output)
                 .space()
@@ -150,7 +174,17 @@ impl Insert {
 
         // VALUES or SELECT
         if let Some(ref query) = self.from_query {
-            ts.space().append(&query.to_tokens_for_dialect(dialect));
+            // SQLite would read ON CONFLICT as a join constraint without a WHERE
+            if self.on_conflict.is_some()
+                && dialect == Dialect::Sqlite
+                && query.where_clause.is_none()
+                && query.set_op.is_none()
+            {
+                let query = query.as_ref().clone().filter(lit_bool(true));
+                ts.space().append(&query.to_tokens_for_dialect(dialect));
+            } else {
+                ts.space().append(&query.to_tokens_for_dialect(dialect));
+            }
         } else if !self.values.is_empty() {
             ts.space().push(Token::Values);
             for (row_idx, row) in self.values.iter().enumerate() {
@@ -216,7 +250,7 @@ impl OnConflict {
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, _dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, _dialect: impl Into<DialectTarget>) -> TokenStream {
         let mut ts = TokenStream::new();
 
         match self {
@@ -346,12 +380,20 @@ impl Update {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
+    /// Convert to SQL with literal values as bind parameters.
+    pub fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        self.to_tokens(dialect).serialize_parameterized(dialect)
+    }
+
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // UPDATE table
@@ -380,7 +422,7 @@ impl Update {
         }
 
         // T-SQL: OUTPUT goes after SET, before FROM/WHERE
-        if !self.returning.is_empty() && matches!(dialect, Dialect::TSql) {
+        if !self.returning.is_empty() && dialect == Dialect::TSql {
             ts.space()
                 .push(Token::Output)
                 .space()
@@ -479,12 +521,20 @@ impl Delete {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens(dialect).serialize(dialect)
     }
 
+    /// Convert to SQL with literal values as bind parameters.
+    pub fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        self.to_tokens(dialect).serialize_parameterized(dialect)
+    }
+
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // DELETE FROM table
@@ -500,7 +550,7 @@ impl Delete {
         }
 
         // T-SQL: OUTPUT goes after table name, before USING/WHERE
-        if !self.returning.is_empty() && matches!(dialect, Dialect::TSql) {
+        if !self.returning.is_empty() && dialect == Dialect::TSql {
             ts.space()
                 .push(Token::Output)
                 .space()
@@ -554,12 +604,9 @@ impl Delete {
 ///
 /// # Dialect Support
 ///
-/// **Important:** MERGE is only supported by T-SQL and PostgreSQL 15+.
-/// Check `dialect.supports_merge()` before using this statement.
-///
-/// For other dialects, use:
-/// - PostgreSQL/DuckDB: `Insert::on_conflict()` for upserts
-/// - MySQL: `Insert` with `ON DUPLICATE KEY UPDATE` (not yet implemented)
+/// Targets without MERGE (`dialect.supports_merge()` is false) get the
+/// equivalent DELETE, UPDATE and INSERT statements in a transaction instead.
+/// ClickHouse has neither, so rendering for it fails.
 ///
 /// # Example
 ///
@@ -803,12 +850,46 @@ impl Merge {
     }
 
     /// Convert to SQL for the given dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
-        self.to_tokens(dialect).serialize(dialect)
+    ///
+    /// Unlike the other statements this can fail: ClickHouse has no MERGE and
+    /// no transactional UPDATE/DELETE to emulate it with.
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> DmlResult<String> {
+        let dialect = dialect.into();
+        Ok(self.to_tokens(dialect)?.serialize(dialect))
+    }
+
+    /// Convert to SQL with literal values as bind parameters, one entry per statement.
+    ///
+    /// The other statements' `to_sql_parameterized` returns a single
+    /// [`ParameterizedSql`]; a prepared statement holds one statement, so targets
+    /// without MERGE get each statement of the fallback (including the
+    /// transaction around it) separately. Run them in order on the same connection.
+    pub fn to_sql_parameterized_statements(
+        &self,
+        dialect: impl Into<DialectTarget>,
+    ) -> DmlResult<Vec<ParameterizedSql>> {
+        let dialect = dialect.into();
+        if !dialect.supports_merge() {
+            return Ok(self
+                .fallback_statements(dialect)?
+                .iter()
+                .map(|statement| statement.serialize_parameterized(dialect))
+                .collect());
+        }
+        Ok(vec![self
+            .to_tokens(dialect)?
+            .serialize_parameterized(dialect)])
     }
 
     /// Convert to token stream.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    ///
+    /// Targets without MERGE (e.g. PostgreSQL 14, MySQL) get the equivalent
+    /// DELETE, UPDATE and INSERT statements instead.
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> DmlResult<TokenStream> {
+        let dialect = dialect.into();
+        if !dialect.supports_merge() {
+            return self.to_fallback_tokens(dialect);
+        }
         let mut ts = TokenStream::new();
 
         // MERGE INTO target [AS alias]
@@ -868,17 +949,375 @@ impl Merge {
         }
 
         // T-SQL requires MERGE to end with a semicolon
-        if matches!(dialect, Dialect::TSql) {
+        if dialect == Dialect::TSql {
             ts.push(Token::Raw(";".into()));
         }
 
-        ts
+        Ok(ts)
+    }
+
+    /// Emulate MERGE with separate statements, run in this order inside a
+    /// single transaction:
+    ///
+    /// 1. `WHEN MATCHED THEN DELETE` removes matched rows.
+    /// 2. `WHEN MATCHED THEN UPDATE` updates the remaining matched rows in
+    ///    place, with `UPDATE ... FROM source` where supported and correlated
+    ///    subqueries otherwise.
+    /// 3. `WHEN NOT MATCHED THEN INSERT` adds source rows without a target match.
+    ///
+    /// As in MERGE, the first WHEN clause whose condition holds wins, and
+    /// conditions see the target as it was before the statement: deletes run
+    /// before updates can change the columns they test, and inserted rows are
+    /// never updated or deleted. When there are deletes, the rows to insert are
+    /// staged in a temporary table first, so source rows whose match was just
+    /// deleted are not inserted.
+    fn to_fallback_tokens(&self, dialect: DialectTarget) -> DmlResult<TokenStream> {
+        let mut ts = TokenStream::new();
+        for (i, statement) in self.fallback_statements(dialect)?.iter().enumerate() {
+            if i > 0 {
+                ts.push(Token::Raw(";".into())).push(Token::Newline);
+            }
+            ts.append(statement);
+        }
+        Ok(ts)
+    }
+
+    /// The statements of the MERGE fallback, in execution order.
+    fn fallback_statements(&self, dialect: DialectTarget) -> DmlResult<Vec<TokenStream>> {
+        // Without UPDATE or transactions there is nothing to emulate MERGE with
+        if dialect.dialect == Dialect::ClickHouse {
+            return Err(DmlError::UnsupportedStatement {
+                statement: "MERGE",
+                dialect,
+            });
+        }
+
+        // The target is referenced by name, so drop its alias from every expression
+        let requalify = |e: &Expr| match &self.target_alias {
+            Some(alias) => e.requalify(alias, &self.target_table),
+            None => e.clone(),
+        };
+        let on = requalify(&self.on_condition);
+
+        let target = Token::QualifiedIdent {
+            schema: self.target_schema.clone(),
+            name: self.target_table.clone(),
+        };
+        let mut source = TokenStream::new();
+        match &self.source {
+            MergeSource::Table { schema, name } => {
+                source.push(Token::QualifiedIdent {
+                    schema: schema.clone(),
+                    name: name.clone(),
+                });
+            }
+            MergeSource::Query(query) => {
+                source
+                    .lparen()
+                    .append(&query.to_tokens_for_dialect(dialect))
+                    .rparen();
+            }
+        }
+        source
+            .space()
+            .push(Token::As)
+            .space()
+            .push(Token::Ident(self.source_alias.clone()));
+
+        // (SELECT <select> FROM <from> WHERE <filter>)
+        let subquery = |select: TokenStream, from: &TokenStream, filter: &Expr| {
+            let mut ts = TokenStream::new();
+            ts.lparen()
+                .push(Token::Select)
+                .space()
+                .append(&select)
+                .space()
+                .push(Token::From)
+                .space()
+                .append(from)
+                .space()
+                .push(Token::Where)
+                .space()
+                .append(&filter.to_tokens_for_dialect(dialect))
+                .rparen();
+            ts
+        };
+        let mut one = TokenStream::new();
+        one.push(Token::Inline(Box::new(Token::LitInt(1))));
+        let mut target_from = TokenStream::new();
+        target_from.push(target.clone());
+
+        // Effective condition of each clause, given that earlier clauses take precedence
+        let effective = |matched: bool| -> Vec<(&MergeAction, Option<Expr>)> {
+            let mut earlier: Vec<Expr> = Vec::new();
+            let mut out = Vec::new();
+            for clause in self.when_clauses.iter().filter(|c| c.matched == matched) {
+                let mut cond = clause.condition.as_ref().map(|c| paren(requalify(c)));
+                for prev in &earlier {
+                    let not_prev = paren(prev.clone()).not();
+                    cond = Some(match cond {
+                        Some(c) => c.and(not_prev),
+                        None => not_prev,
+                    });
+                }
+                out.push((&clause.action, cond));
+                match &clause.condition {
+                    Some(c) => earlier.push(requalify(c)),
+                    // Later clauses are unreachable
+                    None => break,
+                }
+            }
+            out
+        };
+        let matched = effective(true);
+        let not_matched = effective(false);
+        let restrict = |on: &Expr, cond: &Option<Expr>| match cond {
+            Some(c) => paren(on.clone()).and(c.clone()),
+            None => on.clone(),
+        };
+
+        let mut statements: Vec<TokenStream> = Vec::new();
+
+        // 1. Matched deletes
+        for (action, cond) in &matched {
+            if !matches!(action, MergeAction::Delete) {
+                continue;
+            }
+            let mut ts = TokenStream::new();
+            ts.push(Token::Delete)
+                .space()
+                .push(Token::From)
+                .space()
+                .push(target.clone())
+                .space()
+                .push(Token::Where)
+                .space()
+                .push(Token::Exists)
+                .space()
+                .append(&subquery(one.clone(), &source, &restrict(&on, cond)));
+            statements.push(ts);
+        }
+        let has_deletes = !statements.is_empty();
+
+        // 2. Matched updates, in place so columns they don't assign are kept
+        let mut updates = Vec::new();
+        for (action, cond) in &matched {
+            let MergeAction::Update { assignments } = action else {
+                continue;
+            };
+            let mut ts = TokenStream::new();
+            ts.push(Token::Update)
+                .space()
+                .push(target.clone())
+                .space()
+                .push(Token::Set)
+                .space();
+            if supports_update_from(dialect) {
+                // UPDATE target SET ... FROM source WHERE on [AND cond]
+                for (i, (col, expr)) in assignments.iter().enumerate() {
+                    if i > 0 {
+                        ts.comma().space();
+                    }
+                    ts.push(Token::Ident(col.clone()))
+                        .space()
+                        .push(Token::Eq)
+                        .space()
+                        .append(&requalify(expr).to_tokens_for_dialect(dialect));
+                }
+                ts.space()
+                    .push(Token::From)
+                    .space()
+                    .append(&source)
+                    .space()
+                    .push(Token::Where)
+                    .space()
+                    .append(&restrict(&on, cond).to_tokens_for_dialect(dialect));
+            } else {
+                // Each assignment reads its value from the matching source row
+                for (i, (col, expr)) in assignments.iter().enumerate() {
+                    if i > 0 {
+                        ts.comma().space();
+                    }
+                    ts.push(Token::Ident(col.clone()))
+                        .space()
+                        .push(Token::Eq)
+                        .space()
+                        .append(&subquery(
+                            requalify(expr).to_tokens_for_dialect(dialect),
+                            &source,
+                            &on,
+                        ));
+                }
+                ts.space()
+                    .push(Token::Where)
+                    .space()
+                    .push(Token::Exists)
+                    .space()
+                    .append(&subquery(one.clone(), &source, &restrict(&on, cond)));
+            }
+            updates.push(ts);
+        }
+
+        // 3. Unmatched inserts, staged before any delete when there are deletes
+        let mut inserts = Vec::new();
+        let mut drops = Vec::new();
+        let inserted = not_matched
+            .iter()
+            .filter_map(|(action, cond)| match action {
+                MergeAction::Insert { columns, values } => Some((columns, values, cond)),
+                _ => None,
+            });
+        for (n, (columns, values, cond)) in inserted.enumerate() {
+            let mut column_list = TokenStream::new();
+            column_list.lparen();
+            for (i, col) in columns.iter().enumerate() {
+                if i > 0 {
+                    column_list.comma().space();
+                }
+                column_list.push(Token::Ident(col.clone()));
+            }
+            column_list.rparen();
+
+            // <values> FROM source WHERE NOT EXISTS (target match) [AND cond]
+            let mut rows = TokenStream::new();
+            for (i, (col, val)) in columns.iter().zip(values).enumerate() {
+                if i > 0 {
+                    rows.comma().space();
+                }
+                rows.append(&requalify(val).to_tokens_for_dialect(dialect));
+                if has_deletes {
+                    rows.space()
+                        .push(Token::As)
+                        .space()
+                        .push(Token::Ident(col.clone()));
+                }
+            }
+            let mut from = TokenStream::new();
+            from.push(Token::From)
+                .space()
+                .append(&source)
+                .space()
+                .push(Token::Where)
+                .space()
+                .push(Token::Not)
+                .space()
+                .push(Token::Exists)
+                .space()
+                .append(&subquery(one.clone(), &target_from, &on));
+            if let Some(cond) = cond {
+                from.space()
+                    .push(Token::And)
+                    .space()
+                    .append(&cond.to_tokens_for_dialect(dialect));
+            }
+
+            let mut ts = TokenStream::new();
+            ts.push(Token::Insert)
+                .space()
+                .push(Token::Into)
+                .space()
+                .push(target.clone())
+                .space()
+                .append(&column_list)
+                .space()
+                .push(Token::Select)
+                .space();
+            if !has_deletes {
+                ts.append(&rows).space().append(&from);
+                inserts.push(ts);
+                continue;
+            }
+
+            // T-SQL temporary tables are named with a leading #
+            let staged = match dialect.dialect {
+                Dialect::TSql => format!("#merge_insert_{}", n + 1),
+                _ => format!("merge_insert_{}", n + 1),
+            };
+            let mut stage = TokenStream::new();
+            if dialect == Dialect::TSql {
+                // SELECT ... INTO #staged FROM ...
+                stage
+                    .push(Token::Select)
+                    .space()
+                    .append(&rows)
+                    .space()
+                    .push(Token::Into)
+                    .space()
+                    .push(Token::Ident(staged.clone()));
+            } else {
+                stage
+                    .push(Token::Raw("CREATE TEMPORARY TABLE".into()))
+                    .space()
+                    .push(Token::Ident(staged.clone()))
+                    .space()
+                    .push(Token::As)
+                    .space()
+                    .push(Token::Select)
+                    .space()
+                    .append(&rows);
+            }
+            stage.space().append(&from);
+            statements.insert(n, stage);
+
+            for (i, col) in columns.iter().enumerate() {
+                if i > 0 {
+                    ts.comma().space();
+                }
+                ts.push(Token::Ident(col.clone()));
+            }
+            ts.space()
+                .push(Token::From)
+                .space()
+                .push(Token::Ident(staged.clone()));
+            inserts.push(ts);
+
+            let mut drop = TokenStream::new();
+            drop.push(Token::Drop)
+                .space()
+                .push(Token::Table)
+                .space()
+                .push(Token::Ident(staged));
+            drops.push(drop);
+        }
+        statements.extend(updates);
+        statements.extend(inserts);
+        statements.extend(drops);
+
+        // Several statements only apply atomically inside a transaction
+        if statements.len() > 1 {
+            let (begin, commit) = match dialect.dialect {
+                Dialect::MySql => ("START TRANSACTION", "COMMIT"),
+                Dialect::TSql => ("BEGIN TRANSACTION", "COMMIT TRANSACTION"),
+                _ => ("BEGIN", "COMMIT"),
+            };
+            let mut ts = TokenStream::new();
+            ts.push(Token::Raw(begin.into()));
+            statements.insert(0, ts);
+            let mut ts = TokenStream::new();
+            ts.push(Token::Raw(commit.into()));
+            statements.push(ts);
+        }
+
+        Ok(statements)
+    }
+}
+
+/// Whether UPDATE can join the rows to update with another table in FROM.
+fn supports_update_from(dialect: DialectTarget) -> bool {
+    match dialect.dialect {
+        Dialect::Postgres | Dialect::DuckDb => true,
+        Dialect::Sqlite => dialect.at_least(3, 33),
+        _ => false,
     }
 }
 
+fn paren(expr: Expr) -> Expr {
+    Expr::Paren(Box::new(expr))
+}
+
 impl WhenClause {
     /// Convert to token stream.
-    pub fn to_tokens(&self, _dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, _dialect: impl Into<DialectTarget>) -> TokenStream {
         let mut ts = TokenStream::new();
 
         // WHEN [NOT] MATCHED
@@ -946,7 +1385,9 @@ impl WhenClause {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sql::expr::{col, lit_int, lit_str, star, ExprExt};
+    use crate::sql::dialect::Version;
+    use crate::sql::expr::{col, lit_int, lit_str, star, table_col, ExprExt};
+    use crate::sql::params::Param;
 
     #[test]
     fn test_insert_values() {
@@ -1135,12 +1576,75 @@ mod tests {
         assert!(sql.contains("OUTPUT DELETED.*"));
     }
 
+    #[test]
+    fn test_update_parameterized() {
+        let update = Update::table("users")
+            .set("status", lit_str("active"))
+            .filter(col("id").eq(lit_int(1)));
+
+        let pg = update.to_sql_parameterized(Dialect::Postgres);
+        assert_eq!(
+            pg.sql,
+            "UPDATE \"users\" SET \"status\" = $1 WHERE \"id\" = $2::INTEGER"
+        );
+        assert_eq!(
+            pg.params,
+            vec![Param::String("active".into()), Param::Int(1)]
+        );
+
+        let tsql = update.to_sql_parameterized(Dialect::TSql);
+        assert_eq!(
+            tsql.sql,
+            "UPDATE [users] SET [status] = @p1 WHERE [id] = @p2"
+        );
+    }
+
+    #[test]
+    fn test_merge_parameterized_splits_fallback() {
+        let merge = Merge::into("target")
+            .using_table("source")
+            .source_alias("s")
+            .target_alias("t")
+            .on(table_col("t", "id").eq(table_col("s", "id")))
+            .when_matched_update(vec![("name", lit_str("updated"))])
+            .when_not_matched_insert(
+                vec!["id", "name"],
+                vec![table_col("s", "id"), lit_str("new")],
+            );
+
+        let tsql = merge
+            .to_sql_parameterized_statements(Dialect::TSql)
+            .unwrap();
+        assert_eq!(tsql.len(), 1);
+        assert!(tsql[0].sql.starts_with("MERGE INTO"));
+
+        // One prepared statement per fallback statement, each numbered from 1
+        let mysql = merge
+            .to_sql_parameterized_statements(Dialect::MySql)
+            .unwrap();
+        let sql: Vec<&str> = mysql.iter().map(|s| s.sql.as_str()).collect();
+        assert_eq!(sql.len(), 4, "{:?}", sql);
+        assert_eq!(sql[0], "START TRANSACTION");
+        assert!(sql[1].starts_with("UPDATE"), "{}", sql[1]);
+        assert!(sql[2].starts_with("INSERT"), "{}", sql[2]);
+        assert_eq!(sql[3], "COMMIT");
+        assert!(sql.iter().all(|s| !s.contains(';')));
+        assert_eq!(mysql[1].params, vec![Param::String("updated".into())]);
+        assert_eq!(mysql[2].params, vec![Param::String("new".into())]);
+
+        let pg = merge
+            .to_sql_parameterized_statements(Dialect::Postgres.at(Version::new(14, 0)))
+            .unwrap();
+        assert!(pg[2].sql.contains("$1"), "{}", pg[2].sql);
+    }
+
     // ========================================================================
     // Snapshot tests with roundtrip validation
     // ========================================================================
 
     mod snapshot_tests {
         use super::*;
+        use crate::sql::dialect::Version;
         use crate::sql::expr::{col, lit_int, lit_str, table_col, ExprExt};
         use crate::sql::query::{Query, TableRef};
         use crate::sql::test_utils::validate_sql;
@@ -1162,7 +1666,8 @@ mod tests {
                     vec!["id", "name"],
                     vec![table_col("s", "id"), table_col("s", "name")],
                 )
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
@@ -1179,7 +1684,8 @@ mod tests {
                     vec!["id", "name"],
                     vec![table_col("s", "id"), table_col("s", "name")],
                 )
-                .to_sql(Dialect::Postgres);
+                .to_sql(Dialect::Postgres)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::Postgres).unwrap();
         }
@@ -1192,7 +1698,8 @@ mod tests {
                 .target_alias("t")
                 .on(table_col("t", "id").eq(table_col("s", "id")))
                 .when_matched_delete()
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
@@ -1206,7 +1713,8 @@ mod tests {
                 .on(table_col("t", "id").eq(table_col("s", "id")))
                 .when_matched_and_delete(table_col("s", "deleted").eq(true))
                 .when_matched_update(vec![("name", table_col("s", "name"))])
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
@@ -1220,7 +1728,8 @@ mod tests {
                 .target_alias("t")
                 .on(table_col("t", "id").eq(table_col("s", "id")))
                 .when_matched_update(vec![("name", table_col("s", "name"))])
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
@@ -1242,7 +1751,8 @@ mod tests {
                     vec!["id", "name"],
                     vec![table_col("s", "id"), table_col("s", "name")],
                 )
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
@@ -1259,11 +1769,169 @@ mod tests {
                     ("price", table_col("src", "price")),
                     ("stock", table_col("src", "stock")),
                 ])
-                .to_sql(Dialect::TSql);
+                .to_sql(Dialect::TSql)
+                .unwrap();
             assert_snapshot!(sql);
             validate_sql(&sql, Dialect::TSql).unwrap();
         }
 
+        #[test]
+        fn merge_upsert_postgres_14() {
+            let sql = Merge::into("target")
+                .using_table("source")
+                .source_alias("s")
+                .target_alias("t")
+                .on(table_col("t", "id").eq(table_col("s", "id")))
+                .when_matched_update(vec![("name", table_col("s", "name"))])
+                .when_not_matched_insert(
+                    vec!["id", "name"],
+                    vec![table_col("s", "id"), table_col("s", "name")],
+                )
+                .to_sql(Dialect::Postgres.at(Version::new(14, 0)))
+                .unwrap();
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Postgres).unwrap();
+        }
+
+        #[test]
+        fn merge_update_only_mysql() {
+            let sql = Merge::into("products")
+                .using_table("product_updates")
+                .source_alias("src")
+                .target_alias("tgt")
+                .on(table_col("tgt", "sku").eq(table_col("src", "sku")))
+                .when_matched_update(vec![("price", table_col("src", "price"))])
+                .to_sql(Dialect::MySql)
+                .unwrap();
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::MySql).unwrap();
+        }
+
+        #[test]
+        fn merge_fallback_executes_sqlite() {
+            let conn = rusqlite::Connection::open_in_memory().unwrap();
+            let setup = "
+                CREATE TABLE target (id INTEGER, name TEXT);
+                CREATE TABLE source (id INTEGER, name TEXT, deleted INTEGER);
+                INSERT INTO target VALUES (1, 'one'), (2, 'two'), (3, 'three');
+                INSERT INTO source VALUES (1, 'uno', 0), (2, 'dos', 1), (4, 'cuatro', 0), (5, 'cinco', 1);
+            ";
+            let rows = |merge: Merge| {
+                conn.execute_batch(&format!(
+                    "DROP TABLE IF EXISTS target; DROP TABLE IF EXISTS source; {setup}"
+                ))
+                .unwrap();
+                conn.execute_batch(&merge.to_sql(Dialect::Sqlite).unwrap())
+                    .unwrap();
+                let mut stmt = conn
+                    .prepare("SELECT id || '=' || name FROM target ORDER BY id")
+                    .unwrap();
+                let rows = stmt.query_map([], |row| row.get::<_, String>(0)).unwrap();
+                rows.collect::<Result<Vec<_>, _>>().unwrap().join(",")
+            };
+            let merge = || {
+                Merge::into("target")
+                    .using_table("source")
+                    .source_alias("s")
+                    .target_alias("t")
+                    .on(table_col("t", "id").eq(table_col("s", "id")))
+            };
+
+            let upsert = merge()
+                .when_matched_and_delete(table_col("s", "deleted").eq(lit_int(1)))
+                .when_matched_update(vec![("name", table_col("s", "name"))])
+                .when_not_matched_insert(
+                    vec!["id", "name"],
+                    vec![table_col("s", "id"), table_col("s", "name")],
+                );
+            // 2 is deleted and not inserted again; the new 5 is inserted and not deleted
+            assert_eq!(rows(upsert), "1=uno,3=three,4=cuatro,5=cinco");
+
+            let update_only = merge().when_matched_update(vec![("name", table_col("s", "name"))]);
+            assert_eq!(rows(update_only), "1=uno,2=dos,3=three");
+
+            let insert_only = merge().when_not_matched_insert(
+                vec!["id", "name"],
+                vec![table_col("s", "id"), table_col("s", "name")],
+            );
+            assert_eq!(rows(insert_only), "1=one,2=two,3=three,4=cuatro,5=cinco");
+        }
+
+        #[test]
+        fn merge_fallback_with_delete_parses() {
+            let merge = Merge::into("target")
+                .using_table("source")
+                .source_alias("s")
+                .target_alias("t")
+                .on(table_col("t", "id").eq(table_col("s", "id")))
+                .when_matched_and_delete(table_col("s", "deleted").eq(lit_int(1)))
+                .when_matched_update(vec![("name", table_col("s", "name"))])
+                .when_not_matched_insert(
+                    vec!["id", "name"],
+                    vec![table_col("s", "id"), table_col("s", "name")],
+                );
+
+            let sql = merge.to_sql(Dialect::TSql.at(Version::new(9, 0))).unwrap();
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::TSql).unwrap();
+            for dialect in [Dialect::MySql, Dialect::Redshift, Dialect::DuckDb] {
+                let sql = merge.to_sql(dialect).unwrap();
+                assert!(sql.contains("CREATE TEMPORARY TABLE"), "{}", sql);
+                validate_sql(&sql, dialect).unwrap();
+            }
+
+            assert_eq!(
+                merge.to_sql(Dialect::ClickHouse),
+                Err(DmlError::UnsupportedStatement {
+                    statement: "MERGE",
+                    dialect: Dialect::ClickHouse.into(),
+                })
+            );
+        }
+
+        #[test]
+        fn merge_fallback_keeps_update_values_sqlite() {
+            let conn = rusqlite::Connection::open_in_memory().unwrap();
+            let rows = |dialect: DialectTarget| {
+                conn.execute_batch(
+                    "DROP TABLE IF EXISTS totals; DROP TABLE IF EXISTS payments;
+                     CREATE TABLE totals (id INTEGER, total INTEGER, note TEXT);
+                     CREATE TABLE payments (id INTEGER, amount INTEGER);
+                     INSERT INTO totals VALUES (1, 10, 'keep'), (2, 20, 'keep');
+                     INSERT INTO payments VALUES (1, 5), (3, 7), (4, 90);",
+                )
+                .unwrap();
+                let merge = Merge::into("totals")
+                    .using_table("payments")
+                    .source_alias("s")
+                    .target_alias("t")
+                    .on(table_col("t", "id").eq(table_col("s", "id")))
+                    .when_matched_update(vec![(
+                        "total",
+                        table_col("t", "total").add(table_col("s", "amount")),
+                    )])
+                    .when_not_matched_and_insert(
+                        table_col("s", "amount").lt(lit_int(50)),
+                        vec!["id", "total"],
+                        vec![table_col("s", "id"), table_col("s", "amount")],
+                    );
+                conn.execute_batch(&merge.to_sql(dialect).unwrap()).unwrap();
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT id || '=' || total || ':' || COALESCE(note, '-') \
+                         FROM totals ORDER BY id",
+                    )
+                    .unwrap();
+                let rows = stmt.query_map([], |row| row.get::<_, String>(0)).unwrap();
+                rows.collect::<Result<Vec<_>, _>>().unwrap().join(",")
+            };
+
+            // Untouched columns of updated rows survive, with UPDATE ... FROM and without
+            let expected = "1=15:keep,2=20:keep,3=7:-";
+            assert_eq!(rows(Dialect::Sqlite.into()), expected);
+            assert_eq!(rows(Dialect::Sqlite.at(Version::new(3, 30))), expected);
+        }
+
         // --------------------------------------------------------------------
         // Insert snapshot tests
         // --------------------------------------------------------------------
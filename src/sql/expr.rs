@@ -3,7 +3,7 @@
 //! This module provides a strongly-typed AST for SQL expressions
 //! with exhaustive pattern matching enforced by the compiler.
 
-use super::dialect::{Dialect, SqlDialect};
+use super::dialect::{Dialect, DialectTarget, SqlDialect};
 use super::token::{Token, TokenStream};
 
 // =============================================================================
@@ -65,6 +65,12 @@ pub enum Expr {
         negated: bool,
     },
 
+    /// EXISTS: [NOT] EXISTS (SELECT ...)
+    Exists {
+        subquery: Box<crate::query::Query>,
+        negated: bool,
+    },
+
     /// BETWEEN: expr BETWEEN low AND high
     Between {
         expr: Box<Expr>,
@@ -90,6 +96,12 @@ pub enum Expr {
     /// Parenthesized expression
     Paren(Box<Expr>),
 
+    /// Type conversion: CAST(expr AS type), with the type name chosen by the dialect
+    Cast {
+        expr: Box<Expr>,
+        data_type: crate::model::types::DataType,
+    },
+
     /// Window function expression.
     ///
     /// Example: `SUM(amount) OVER (PARTITION BY region ORDER BY date ROWS UNBOUNDED PRECEDING)`
@@ -319,6 +331,146 @@ pub enum WindowFrameBound {
     UnboundedFollowing,
 }
 
+// =============================================================================
+// Rewriting
+// =============================================================================
+
+impl Expr {
+    /// Replace the table qualifier `from` with `to` in column and star references.
+    ///
+    /// Subqueries and raw SQL are left untouched.
+    pub(crate) fn requalify(&self, from: &str, to: &str) -> Expr {
+        let qualifier = |t: &Option<String>| match t {
+            Some(t) if t == from => Some(to.to_string()),
+            other => other.clone(),
+        };
+        self.rewrite(&mut |e| match e {
+            Expr::Column { table, column } => Some(Expr::Column {
+                table: qualifier(table),
+                column: column.clone(),
+            }),
+            Expr::Star { table } => Some(Expr::Star {
+                table: qualifier(table),
+            }),
+            _ => None,
+        })
+    }
+
+    /// Rebuild this expression top-down, replacing every node for which `f`
+    /// returns a substitute. Nodes left alone are descended into.
+    ///
+    /// Subqueries and raw SQL are left untouched.
+    pub(crate) fn rewrite(&self, f: &mut dyn FnMut(&Expr) -> Option<Expr>) -> Expr {
+        if let Some(replaced) = f(self) {
+            return replaced;
+        }
+        match self {
+            Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                left: Box::new(left.rewrite(f)),
+                op: *op,
+                right: Box::new(right.rewrite(f)),
+            },
+            Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op: *op,
+                expr: Box::new(expr.rewrite(f)),
+            },
+            Expr::Function {
+                name,
+                args,
+                distinct,
+            } => Expr::Function {
+                name: name.clone(),
+                args: args.iter().map(|e| e.rewrite(f)).collect(),
+                distinct: *distinct,
+            },
+            Expr::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => Expr::Case {
+                operand: operand.as_ref().map(|e| Box::new(e.rewrite(f))),
+                when_clauses: when_clauses
+                    .iter()
+                    .map(|(w, t)| (w.rewrite(f), t.rewrite(f)))
+                    .collect(),
+                else_clause: else_clause.as_ref().map(|e| Box::new(e.rewrite(f))),
+            },
+            Expr::In {
+                expr,
+                values,
+                negated,
+            } => Expr::In {
+                expr: Box::new(expr.rewrite(f)),
+                values: values.iter().map(|e| e.rewrite(f)).collect(),
+                negated: *negated,
+            },
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => Expr::InSubquery {
+                expr: Box::new(expr.rewrite(f)),
+                subquery: subquery.clone(),
+                negated: *negated,
+            },
+            Expr::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => Expr::Between {
+                expr: Box::new(expr.rewrite(f)),
+                low: Box::new(low.rewrite(f)),
+                high: Box::new(high.rewrite(f)),
+                negated: *negated,
+            },
+            Expr::IsNull { expr, negated } => Expr::IsNull {
+                expr: Box::new(expr.rewrite(f)),
+                negated: *negated,
+            },
+            Expr::LikeEscape {
+                expr,
+                pattern,
+                escape_char,
+                negated,
+            } => Expr::LikeEscape {
+                expr: Box::new(expr.rewrite(f)),
+                pattern: Box::new(pattern.rewrite(f)),
+                escape_char: *escape_char,
+                negated: *negated,
+            },
+            Expr::Paren(expr) => Expr::Paren(Box::new(expr.rewrite(f))),
+            Expr::Cast { expr, data_type } => Expr::Cast {
+                expr: Box::new(expr.rewrite(f)),
+                data_type: data_type.clone(),
+            },
+            Expr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+                frame,
+            } => Expr::WindowFunction {
+                function: Box::new(function.rewrite(f)),
+                partition_by: partition_by.iter().map(|e| e.rewrite(f)).collect(),
+                order_by: order_by
+                    .iter()
+                    .map(|ob| WindowOrderBy {
+                        expr: ob.expr.rewrite(f),
+                        ..ob.clone()
+                    })
+                    .collect(),
+                frame: frame.clone(),
+            },
+            Expr::Column { .. }
+            | Expr::Star { .. }
+            | Expr::Literal(_)
+            | Expr::Subquery(_)
+            | Expr::Exists { .. }
+            | Expr::Raw(_) => self.clone(),
+        }
+    }
+}
+
 // =============================================================================
 // Expression to Tokens
 // =============================================================================
@@ -332,7 +484,8 @@ impl Expr {
     /// Convert this expression to a token stream for a specific dialect.
     ///
     /// This handles dialect-specific features like GROUPS frame support.
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         match self {
@@ -379,7 +532,7 @@ impl Expr {
                     UnaryOperator::Minus => Token::Minus,
                 });
                 ts.space();
-                ts.append(&expr.to_tokens());
+                ts.append(&expr.to_tokens_for_dialect(dialect));
             }
 
             Expr::Function {
@@ -387,6 +540,14 @@ impl Expr {
                 args,
                 distinct,
             } => {
+                // Date/time calls are rewritten whole when their arguments differ too
+                if let Some(translated) = (!*distinct)
+                    .then(|| super::dialect::functions::translate(dialect, name, args))
+                    .flatten()
+                {
+                    ts.append(&translated);
+                    return ts;
+                }
                 ts.push(Token::FunctionName(name.clone()));
                 ts.lparen();
                 if *distinct {
@@ -396,7 +557,7 @@ impl Expr {
                     if i > 0 {
                         ts.comma().space();
                     }
-                    ts.append(&arg.to_tokens());
+                    ts.append(&arg.to_tokens_for_dialect(dialect));
                 }
                 ts.rparen();
             }
@@ -408,24 +569,24 @@ impl Expr {
             } => {
                 ts.push(Token::Case);
                 if let Some(op) = operand {
-                    ts.space().append(&op.to_tokens());
+                    ts.space().append(&op.to_tokens_for_dialect(dialect));
                 }
                 for (when, then) in when_clauses {
                     ts.space().push(Token::When).space();
-                    ts.append(&when.to_tokens());
+                    ts.append(&when.to_tokens_for_dialect(dialect));
                     ts.space().push(Token::Then).space();
-                    ts.append(&then.to_tokens());
+                    ts.append(&then.to_tokens_for_dialect(dialect));
                 }
                 if let Some(else_expr) = else_clause {
                     ts.space().push(Token::Else).space();
-                    ts.append(&else_expr.to_tokens());
+                    ts.append(&else_expr.to_tokens_for_dialect(dialect));
                 }
                 ts.space().push(Token::End);
             }
 
             Expr::Subquery(query) => {
                 ts.lparen();
-                ts.append(&query.to_tokens());
+                ts.append(&query.to_tokens_for_dialect(dialect));
                 ts.rparen();
             }
 
@@ -439,7 +600,7 @@ impl Expr {
                 if values.is_empty() {
                     ts.push(if *negated { Token::True } else { Token::False });
                 } else {
-                    ts.append(&expr.to_tokens());
+                    ts.append(&expr.to_tokens_for_dialect(dialect));
                     if *negated {
                         ts.space().push(Token::Not);
                     }
@@ -448,7 +609,7 @@ impl Expr {
                         if i > 0 {
                             ts.comma().space();
                         }
-                        ts.append(&val.to_tokens());
+                        ts.append(&val.to_tokens_for_dialect(dialect));
                     }
                     ts.rparen();
                 }
@@ -459,12 +620,21 @@ impl Expr {
                 subquery,
                 negated,
             } => {
-                ts.append(&expr.to_tokens());
+                ts.append(&expr.to_tokens_for_dialect(dialect));
                 if *negated {
                     ts.space().push(Token::Not);
                 }
                 ts.space().push(Token::In).space().lparen();
-                ts.append(&subquery.to_tokens());
+                ts.append(&subquery.to_tokens_for_dialect(dialect));
+                ts.rparen();
+            }
+
+            Expr::Exists { subquery, negated } => {
+                if *negated {
+                    ts.push(Token::Not).space();
+                }
+                ts.push(Token::Exists).space().lparen();
+                ts.append(&subquery.to_tokens_for_dialect(dialect));
                 ts.rparen();
             }
 
@@ -474,18 +644,18 @@ impl Expr {
                 high,
                 negated,
             } => {
-                ts.append(&expr.to_tokens());
+                ts.append(&expr.to_tokens_for_dialect(dialect));
                 if *negated {
                     ts.space().push(Token::Not);
                 }
                 ts.space().push(Token::Between).space();
-                ts.append(&low.to_tokens());
+                ts.append(&low.to_tokens_for_dialect(dialect));
                 ts.space().push(Token::And).space();
-                ts.append(&high.to_tokens());
+                ts.append(&high.to_tokens_for_dialect(dialect));
             }
 
             Expr::IsNull { expr, negated } => {
-                ts.append(&expr.to_tokens());
+                ts.append(&expr.to_tokens_for_dialect(dialect));
                 ts.space();
                 ts.push(if *negated {
                     Token::IsNotNull
@@ -511,7 +681,9 @@ impl Expr {
                     .space()
                     .push(Token::Raw("ESCAPE".into()))
                     .space()
-                    .push(Token::LitString(escape_char.to_string()));
+                    .push(Token::Inline(Box::new(Token::LitString(
+                        escape_char.to_string(),
+                    ))));
             }
 
             Expr::Star { table } => {
@@ -524,7 +696,16 @@ impl Expr {
 
             Expr::Paren(inner) => {
                 ts.lparen();
-                ts.append(&inner.to_tokens());
+                ts.append(&inner.to_tokens_for_dialect(dialect));
+                ts.rparen();
+            }
+
+            Expr::Cast { expr, data_type } => {
+                ts.push(Token::FunctionName("CAST".into()));
+                ts.lparen();
+                ts.append(&expr.to_tokens_for_dialect(dialect));
+                ts.space().push(Token::As).space();
+                ts.push(Token::Raw(dialect.emit_data_type(data_type)));
                 ts.rparen();
             }
 
@@ -564,6 +745,14 @@ impl Expr {
                         if i > 0 {
                             ts.comma().space();
                         }
+                        // Emulate NULLS FIRST/LAST with a leading sort key
+                        let nulls = ob.nulls.filter(|_| !dialect.supports_nulls_ordering());
+                        if let Some(nulls) = nulls {
+                            let key = nulls_sort_key(&ob.expr, nulls == NullsOrder::First);
+                            ts.append(&key.to_tokens_for_dialect(dialect))
+                                .comma()
+                                .space();
+                        }
                         ts.append(&ob.expr.to_tokens_for_dialect(dialect));
                         if let Some(dir) = &ob.dir {
                             ts.space().push(match dir {
@@ -571,7 +760,7 @@ impl Expr {
                                 SortDir::Desc => Token::Desc,
                             });
                         }
-                        if let Some(nulls) = &ob.nulls {
+                        if let (None, Some(nulls)) = (nulls, &ob.nulls) {
                             ts.space().push(match nulls {
                                 NullsOrder::First => Token::NullsFirst,
                                 NullsOrder::Last => Token::NullsLast,
@@ -655,7 +844,7 @@ fn emit_frame_bound(ts: &mut TokenStream, bound: &WindowFrameBound) {
             ts.push(Token::Unbounded).space().push(Token::Preceding);
         }
         WindowFrameBound::Preceding(n) => {
-            ts.push(Token::LitInt(*n as i64))
+            ts.push(Token::Inline(Box::new(Token::LitInt(*n as i64))))
                 .space()
                 .push(Token::Preceding);
         }
@@ -663,7 +852,7 @@ fn emit_frame_bound(ts: &mut TokenStream, bound: &WindowFrameBound) {
             ts.push(Token::CurrentRow);
         }
         WindowFrameBound::Following(n) => {
-            ts.push(Token::LitInt(*n as i64))
+            ts.push(Token::Inline(Box::new(Token::LitInt(*n as i64))))
                 .space()
                 .push(Token::Following);
         }
@@ -806,6 +995,43 @@ pub fn coalesce(args: Vec<Expr>) -> Expr {
     }
 }
 
+/// CAST(expr AS type)
+pub fn cast(expr: Expr, data_type: crate::model::types::DataType) -> Expr {
+    Expr::Cast {
+        expr: Box::new(expr),
+        data_type,
+    }
+}
+
+/// EXISTS (subquery)
+pub fn exists(subquery: crate::query::Query) -> Expr {
+    Expr::Exists {
+        subquery: Box::new(subquery),
+        negated: false,
+    }
+}
+
+/// NOT EXISTS (subquery)
+pub fn not_exists(subquery: crate::query::Query) -> Expr {
+    Expr::Exists {
+        subquery: Box::new(subquery),
+        negated: true,
+    }
+}
+
+/// Sort key that emulates NULLS FIRST/LAST: `CASE WHEN expr IS NULL THEN 0 ELSE 1 END`.
+///
+/// Sorted ascending ahead of `expr`, it puts NULLs first (or last, with the
+/// branches swapped) regardless of the sort direction of `expr`.
+pub(crate) fn nulls_sort_key(expr: &Expr, nulls_first: bool) -> Expr {
+    let (null, not_null) = if nulls_first { (0, 1) } else { (1, 0) };
+    Expr::Case {
+        operand: None,
+        when_clauses: vec![(expr.clone().is_null(), lit_int(null))],
+        else_clause: Some(Box::new(lit_int(not_null))),
+    }
+}
+
 /// Generic function call.
 pub fn func(name: &str, args: Vec<Expr>) -> Expr {
     Expr::Function {
@@ -0,0 +1,757 @@
+//! SQL pretty-printer - configurable layout for token streams.
+//!
+//! [`TokenStream::serialize`] renders the layout the builders emit. The
+//! formatter re-lays out the same tokens, so every statement type (queries,
+//! set operations, DDL, DML) can be formatted the same way:
+//!
+//! ```ignore
+//! let options = FormatOptions::new()
+//!     .keyword_case(KeywordCase::Lower)
+//!     .indent_width(4)
+//!     .comma_style(CommaStyle::Leading)
+//!     .max_line_width(80);
+//! let sql = query.to_tokens_for_dialect(dialect).format(dialect, &options);
+//! ```
+//!
+//! Wrapping only happens at commas and at `AND`/`OR` (never the `AND` of a
+//! `BETWEEN`), starting with the outermost parenthesis level of the line.
+
+use super::dialect::DialectTarget;
+use super::token::{Token, TokenStream};
+
+/// Casing for SQL keywords. Identifiers, literals and function names are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// Placement of list separators when a list spans lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommaStyle {
+    /// `a,` / `b`
+    #[default]
+    Trailing,
+    /// `a` / `, b`
+    Leading,
+}
+
+/// Layout options for [`TokenStream::format`].
+#[derive(Debug, Clone, PartialEq)]
+#[must_use = "builders have no effect until used"]
+pub struct FormatOptions {
+    pub keyword_case: KeywordCase,
+    /// Spaces per indentation level.
+    pub indent_width: usize,
+    pub comma_style: CommaStyle,
+    /// Wrap lines longer than this many characters, where possible.
+    pub max_line_width: Option<usize>,
+    /// Indent the body of CTEs and subqueries that span multiple lines.
+    pub indent_subqueries: bool,
+    /// Render the whole statement on a single line.
+    pub compact: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            keyword_case: KeywordCase::Upper,
+            indent_width: 2,
+            comma_style: CommaStyle::Trailing,
+            max_line_width: None,
+            indent_subqueries: true,
+            compact: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Default layout: upper-case keywords, 2-space indent, trailing commas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Single-line layout.
+    pub fn compact() -> Self {
+        Self {
+            compact: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn keyword_case(mut self, case: KeywordCase) -> Self {
+        self.keyword_case = case;
+        self
+    }
+
+    pub fn indent_width(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
+    pub fn comma_style(mut self, style: CommaStyle) -> Self {
+        self.comma_style = style;
+        self
+    }
+
+    pub fn max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = Some(width);
+        self
+    }
+
+    pub fn indent_subqueries(mut self, indent: bool) -> Self {
+        self.indent_subqueries = indent;
+        self
+    }
+}
+
+impl TokenStream {
+    /// Serialize to SQL using the given layout options.
+    pub fn format(&self, dialect: impl Into<DialectTarget>, options: &FormatOptions) -> String {
+        let dialect = dialect.into();
+        let pieces = pieces(self.tokens(), dialect, options);
+        if options.compact {
+            return render_compact(&pieces);
+        }
+
+        let mut lines = Vec::new();
+        for line in split_lines(pieces, options) {
+            wrap(line, options, &mut lines);
+        }
+        if options.comma_style == CommaStyle::Leading {
+            move_commas_to_line_start(&mut lines);
+        }
+
+        lines
+            .iter()
+            .map(|line| line.render(options.indent_width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// =============================================================================
+// Pieces - rendered tokens annotated for layout
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PieceKind {
+    Text,
+    Space,
+    Newline,
+    Indent(usize),
+    Comma,
+    LParen,
+    RParen,
+    /// `AND`/`OR` joining boolean terms
+    BoolOp,
+    /// Keyword starting a clause (`FROM`, `WHERE`, `VALUES`, `WHEN`, ...)
+    Clause,
+}
+
+#[derive(Debug, Clone)]
+struct Piece {
+    text: String,
+    kind: PieceKind,
+    /// Parenthesis depth; a paren has the depth of its surroundings.
+    depth: usize,
+}
+
+fn pieces(tokens: &[Token], dialect: DialectTarget, options: &FormatOptions) -> Vec<Piece> {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut depth = 0usize;
+    // Depths with a BETWEEN still waiting for its AND
+    let mut between = Vec::new();
+    // Depths with an open CASE expression
+    let mut case = Vec::new();
+
+    for token in tokens {
+        let kind = match token {
+            Token::Space => PieceKind::Space,
+            Token::Newline => PieceKind::Newline,
+            Token::Indent(n) => PieceKind::Indent(*n),
+            Token::Comma => PieceKind::Comma,
+            Token::LParen => PieceKind::LParen,
+            Token::RParen => PieceKind::RParen,
+            Token::Between => {
+                between.push(depth);
+                PieceKind::Text
+            }
+            Token::And if between.last() == Some(&depth) => {
+                between.pop();
+                PieceKind::Text
+            }
+            Token::And | Token::Or => PieceKind::BoolOp,
+            Token::Case => {
+                case.push(depth);
+                PieceKind::Text
+            }
+            Token::End if case.last() == Some(&depth) => {
+                case.pop();
+                PieceKind::Text
+            }
+            Token::When if case.last() == Some(&depth) => PieceKind::Text,
+            Token::From
+            | Token::Where
+            | Token::GroupBy
+            | Token::Having
+            | Token::Qualify
+            | Token::OrderBy
+            | Token::Limit
+            | Token::Set
+            | Token::Values
+            | Token::Using
+            | Token::When
+            | Token::Returning
+            | Token::Output => PieceKind::Clause,
+            _ => PieceKind::Text,
+        };
+
+        if kind == PieceKind::RParen {
+            depth = depth.saturating_sub(1);
+            between.retain(|d| *d <= depth);
+            case.retain(|d| *d <= depth);
+        }
+
+        let text = match kind {
+            PieceKind::Newline | PieceKind::Indent(_) => String::new(),
+            _ if token.is_keyword() && options.keyword_case == KeywordCase::Lower => {
+                token.serialize(dialect).to_lowercase()
+            }
+            _ => token.serialize(dialect),
+        };
+        result.push(Piece { text, kind, depth });
+
+        if kind == PieceKind::LParen {
+            depth += 1;
+        }
+    }
+
+    result
+}
+
+fn render_compact(pieces: &[Piece]) -> String {
+    let mut out = String::new();
+    let mut pending_space = false;
+
+    for piece in pieces {
+        match piece.kind {
+            PieceKind::Space | PieceKind::Newline => pending_space = true,
+            PieceKind::Indent(_) => {}
+            _ => {
+                let glue = out.is_empty()
+                    || out.ends_with('(')
+                    || matches!(piece.kind, PieceKind::RParen | PieceKind::Comma);
+                if pending_space && !glue {
+                    out.push(' ');
+                }
+                pending_space = false;
+                out.push_str(&piece.text);
+            }
+        }
+    }
+
+    out
+}
+
+// =============================================================================
+// Lines
+// =============================================================================
+
+#[derive(Debug, Clone, Default)]
+struct Line {
+    /// Indentation in levels.
+    indent: usize,
+    pieces: Vec<Piece>,
+}
+
+impl Line {
+    fn new(indent: usize) -> Self {
+        Self {
+            indent,
+            pieces: Vec::new(),
+        }
+    }
+
+    fn width(&self, indent_width: usize) -> usize {
+        self.indent * indent_width
+            + self
+                .pieces
+                .iter()
+                .map(|p| p.text.chars().count())
+                .sum::<usize>()
+    }
+
+    fn trim(&mut self) {
+        while self
+            .pieces
+            .last()
+            .is_some_and(|p| p.kind == PieceKind::Space)
+        {
+            self.pieces.pop();
+        }
+        let leading = self
+            .pieces
+            .iter()
+            .take_while(|p| p.kind == PieceKind::Space)
+            .count();
+        self.pieces.drain(..leading);
+    }
+
+    fn render(&self, indent_width: usize) -> String {
+        let mut out = " ".repeat(self.indent * indent_width);
+        for piece in &self.pieces {
+            out.push_str(&piece.text);
+        }
+        out
+    }
+}
+
+/// Split at the builders' newlines, nesting lines inside multi-line parentheses.
+fn split_lines(pieces: Vec<Piece>, options: &FormatOptions) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut current = Line::new(0);
+    // For each open paren: whether it opens a block (ends its line)
+    let mut open: Vec<bool> = Vec::new();
+
+    for piece in pieces {
+        match piece.kind {
+            PieceKind::Newline => {
+                let ends_with_paren = current
+                    .pieces
+                    .iter()
+                    .rev()
+                    .find(|p| p.kind != PieceKind::Space)
+                    .is_some_and(|p| p.kind == PieceKind::LParen);
+                if ends_with_paren {
+                    if let Some(block) = open.last_mut() {
+                        *block = true;
+                    }
+                }
+                lines.push(std::mem::take(&mut current));
+                let nesting = if options.indent_subqueries {
+                    open.iter().filter(|block| **block).count()
+                } else {
+                    0
+                };
+                current = Line::new(nesting);
+            }
+            PieceKind::Indent(n) => current.indent += n,
+            PieceKind::LParen => {
+                open.push(false);
+                current.pieces.push(piece);
+            }
+            PieceKind::RParen => {
+                let block = open.pop().unwrap_or(false);
+                // A closing paren that starts a line lines up with its opener
+                if block && options.indent_subqueries && current.pieces.is_empty() {
+                    current.indent = current.indent.saturating_sub(1);
+                }
+                current.pieces.push(piece);
+            }
+            _ => current.pieces.push(piece),
+        }
+    }
+    lines.push(current);
+
+    for line in &mut lines {
+        line.trim();
+    }
+    lines
+}
+
+/// Break a line that is too long before its clauses, then at its outermost
+/// commas or boolean operators.
+fn wrap(line: Line, options: &FormatOptions, out: &mut Vec<Line>) {
+    let Some(max) = options.max_line_width else {
+        out.push(line);
+        return;
+    };
+    if line.width(options.indent_width) <= max {
+        out.push(line);
+        return;
+    }
+
+    let is_break = |p: &Piece| matches!(p.kind, PieceKind::Comma | PieceKind::BoolOp);
+    let Some(base) = line
+        .pieces
+        .iter()
+        .filter(|p| p.kind != PieceKind::Space)
+        .map(|p| p.depth)
+        .min()
+    else {
+        out.push(line);
+        return;
+    };
+
+    let clause_breaks = line
+        .pieces
+        .iter()
+        .skip(1)
+        .any(|p| p.kind == PieceKind::Clause && p.depth == base);
+    if clause_breaks {
+        let indent = line.indent;
+        let mut current = Line::new(indent);
+        for (i, piece) in line.pieces.into_iter().enumerate() {
+            if i > 0 && piece.kind == PieceKind::Clause && piece.depth == base {
+                let mut done = std::mem::replace(&mut current, Line::new(indent));
+                done.trim();
+                wrap(done, options, out);
+            }
+            current.pieces.push(piece);
+        }
+        current.trim();
+        wrap(current, options, out);
+        return;
+    }
+    // A line may start with the operator it was broken before
+    let Some(level) = line
+        .pieces
+        .iter()
+        .skip(1)
+        .filter(|p| is_break(p))
+        .map(|p| p.depth)
+        .min()
+    else {
+        out.push(line);
+        return;
+    };
+
+    // Parens (by index) at the break level's parent depth that contain breaks.
+    let mut broken_parens = vec![false; line.pieces.len()];
+    if level > base {
+        let mut stack = Vec::new();
+        for (i, piece) in line.pieces.iter().enumerate() {
+            match piece.kind {
+                PieceKind::LParen if piece.depth == level - 1 => stack.push(i),
+                PieceKind::RParen if piece.depth == level - 1 => {
+                    stack.pop();
+                }
+                _ if is_break(piece) && piece.depth == level => {
+                    if let Some(&open) = stack.last() {
+                        broken_parens[open] = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut current = Line::new(line.indent);
+    let mut next_indent = if level == base {
+        line.indent + 1
+    } else {
+        line.indent
+    };
+    let mut open_broken = Vec::new();
+
+    let flush = |current: &mut Line, indent: usize, result: &mut Vec<Line>| {
+        current.trim();
+        if !current.pieces.is_empty() {
+            result.push(std::mem::replace(current, Line::new(indent)));
+        }
+    };
+
+    for (i, piece) in line.pieces.into_iter().enumerate() {
+        match piece.kind {
+            PieceKind::LParen if level > base && piece.depth + 1 == level => {
+                open_broken.push(broken_parens[i]);
+                current.pieces.push(piece);
+                if broken_parens[i] {
+                    next_indent += 1;
+                    flush(&mut current, next_indent, &mut result);
+                }
+            }
+            PieceKind::RParen if level > base && piece.depth + 1 == level => {
+                if open_broken.pop().unwrap_or(false) {
+                    next_indent -= 1;
+                    flush(&mut current, next_indent, &mut result);
+                }
+                current.pieces.push(piece);
+            }
+            PieceKind::Comma if piece.depth == level => {
+                current.pieces.push(piece);
+                flush(&mut current, next_indent, &mut result);
+            }
+            PieceKind::BoolOp if piece.depth == level => {
+                flush(&mut current, next_indent, &mut result);
+                current.pieces.push(piece);
+            }
+            _ => current.pieces.push(piece),
+        }
+    }
+    flush(&mut current, next_indent, &mut result);
+
+    // Lines without a break point come back unchanged, so this terminates.
+    if result.len() == 1 {
+        out.extend(result);
+        return;
+    }
+    for line in result {
+        wrap(line, options, out);
+    }
+}
+
+/// Rewrite `a,` / `b` as `a` / `, b`.
+fn move_commas_to_line_start(lines: &mut [Line]) {
+    for i in 0..lines.len().saturating_sub(1) {
+        if lines[i].pieces.last().map(|p| p.kind) != Some(PieceKind::Comma) {
+            continue;
+        }
+        let comma = lines[i].pieces.pop().expect("checked above");
+        let space = Piece {
+            text: " ".into(),
+            kind: PieceKind::Space,
+            depth: comma.depth,
+        };
+        lines[i + 1].pieces.splice(0..0, [comma, space]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::ddl::{ColumnDef, CreateTable, DataType};
+    use crate::sql::dialect::Dialect;
+    use crate::sql::dml::{Insert, Merge};
+    use crate::sql::expr::{col, count_star, lit_int, lit_str, sum, table_col, Expr, ExprExt};
+    use crate::sql::query::{Cte, JoinType, OrderByExpr, Query, SelectExpr, TableRef};
+
+    fn sample_query() -> Query {
+        let recent = Query::new()
+            .select(vec![col("customer_id"), col("amount"), col("order_date")])
+            .from(TableRef::new("orders").with_schema("sales"))
+            .filter(col("order_date").gte(lit_str("2024-01-01")));
+
+        Query::new()
+            .with_cte(Cte::new("recent_orders", recent))
+            .select(vec![
+                SelectExpr::new(table_col("c", "region")).with_alias("region"),
+                SelectExpr::new(sum(table_col("o", "amount"))).with_alias("revenue"),
+                SelectExpr::new(count_star()).with_alias("order_count"),
+            ])
+            .from(TableRef::new("recent_orders").with_alias("o"))
+            .join(
+                JoinType::Inner,
+                TableRef::new("customers").with_alias("c"),
+                table_col("o", "customer_id").eq(table_col("c", "id")),
+            )
+            .filter(
+                table_col("c", "status")
+                    .eq(lit_str("active"))
+                    .and(table_col("o", "amount").between(lit_int(10), lit_int(1000)))
+                    .and(Expr::In {
+                        expr: Box::new(table_col("c", "region")),
+                        values: vec![lit_str("north"), lit_str("south"), lit_str("east")],
+                        negated: false,
+                    }),
+            )
+            .group_by(vec![table_col("c", "region")])
+            .order_by(vec![OrderByExpr::desc(col("revenue"))])
+            .limit(10)
+    }
+
+    fn format(query: &Query, dialect: Dialect, options: &FormatOptions) -> String {
+        query
+            .to_tokens_for_dialect(dialect)
+            .format(dialect, options)
+    }
+
+    #[test]
+    fn test_default_matches_serialize_without_nesting() {
+        let query = Query::new()
+            .select(vec![col("a"), col("b")])
+            .from(TableRef::new("t"));
+        let ts = query.to_tokens_for_dialect(Dialect::DuckDb);
+        assert_eq!(
+            ts.format(Dialect::DuckDb, &FormatOptions::new()),
+            ts.serialize(Dialect::DuckDb)
+        );
+    }
+
+    #[test]
+    fn test_keyword_case_lower() {
+        let sql = format(
+            &sample_query(),
+            Dialect::Postgres,
+            &FormatOptions::new().keyword_case(KeywordCase::Lower),
+        );
+        assert!(sql.contains("select"), "SQL: {}", sql);
+        assert!(sql.contains("group by"), "SQL: {}", sql);
+        // Function names and identifiers keep their case
+        assert!(sql.contains("SUM(\"o\".\"amount\")"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_compact_is_single_line() {
+        let sql = format(&sample_query(), Dialect::DuckDb, &FormatOptions::compact());
+        assert!(!sql.contains('\n'), "SQL: {}", sql);
+        assert!(
+            sql.starts_with("WITH \"recent_orders\" AS (SELECT \"customer_id\""),
+            "SQL: {}",
+            sql
+        );
+        assert!(
+            sql.contains("\"order_date\" >= '2024-01-01')"),
+            "SQL: {}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_wrap_keeps_between_and() {
+        let sql = format(
+            &sample_query(),
+            Dialect::DuckDb,
+            &FormatOptions::new().max_line_width(40),
+        );
+        assert!(sql.contains("BETWEEN 10 AND 1000"), "SQL: {}", sql);
+        assert!(
+            sql.contains("\n  AND \"c\".\"region\" IN ("),
+            "SQL: {}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_leading_commas() {
+        let sql = format(
+            &sample_query(),
+            Dialect::DuckDb,
+            &FormatOptions::new().comma_style(CommaStyle::Leading),
+        );
+        assert!(sql.contains("\n    , \"amount\""), "SQL: {}", sql);
+        assert!(!sql.contains(",\n"), "SQL: {}", sql);
+    }
+
+    mod snapshot_tests {
+        use super::*;
+        use crate::sql::test_utils::validate_sql;
+        use insta::assert_snapshot;
+
+        fn styled() -> FormatOptions {
+            FormatOptions::new()
+                .keyword_case(KeywordCase::Lower)
+                .indent_width(4)
+                .comma_style(CommaStyle::Leading)
+                .max_line_width(60)
+        }
+
+        #[test]
+        fn query_default_duckdb() {
+            let sql = format(&sample_query(), Dialect::DuckDb, &FormatOptions::new());
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::DuckDb).unwrap();
+        }
+
+        #[test]
+        fn query_styled_postgres() {
+            let sql = format(&sample_query(), Dialect::Postgres, &styled());
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Postgres).unwrap();
+        }
+
+        #[test]
+        fn query_wrapped_tsql() {
+            let sql = format(
+                &sample_query(),
+                Dialect::TSql,
+                &FormatOptions::new().max_line_width(50),
+            );
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::TSql).unwrap();
+        }
+
+        #[test]
+        fn query_compact_mysql() {
+            let sql = format(&sample_query(), Dialect::MySql, &FormatOptions::compact());
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::MySql).unwrap();
+        }
+
+        #[test]
+        fn query_flat_subqueries_snowflake() {
+            let sql = format(
+                &sample_query(),
+                Dialect::Snowflake,
+                &FormatOptions::new().indent_subqueries(false),
+            );
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Snowflake).unwrap();
+        }
+
+        #[test]
+        fn set_operation_styled_duckdb() {
+            let sql = Query::new()
+                .select(vec![col("id"), col("name")])
+                .from(TableRef::new("customers_2023"))
+                .union_all(
+                    Query::new()
+                        .select(vec![col("id"), col("name")])
+                        .from(TableRef::new("customers_2024")),
+                )
+                .to_tokens_for_dialect(Dialect::DuckDb)
+                .format(Dialect::DuckDb, &styled());
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::DuckDb).unwrap();
+        }
+
+        #[test]
+        fn create_table_wrapped_postgres() {
+            let sql = CreateTable::new("customers")
+                .schema("analytics")
+                .column(ColumnDef::new("id", DataType::Int64).primary_key())
+                .column(ColumnDef::new("name", DataType::Varchar(255)).not_null())
+                .column(ColumnDef::new("region", DataType::Varchar(50)))
+                .column(ColumnDef::new("created_at", DataType::Timestamp))
+                .to_tokens(Dialect::Postgres)
+                .format(Dialect::Postgres, &FormatOptions::new().max_line_width(60));
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::Postgres).unwrap();
+        }
+
+        #[test]
+        fn insert_styled_mysql() {
+            let sql = Insert::into("customers")
+                .columns(["id", "name", "region", "created_at"])
+                .values([
+                    lit_int(1),
+                    lit_str("Acme Corporation"),
+                    lit_str("north"),
+                    lit_str("2024-01-01"),
+                ])
+                .to_tokens(Dialect::MySql)
+                .format(Dialect::MySql, &styled());
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::MySql).unwrap();
+        }
+
+        #[test]
+        fn merge_wrapped_tsql() {
+            let sql = Merge::into("customers")
+                .using_table("staging_customers")
+                .source_alias("s")
+                .target_alias("t")
+                .on(table_col("t", "id").eq(table_col("s", "id")))
+                .when_matched_update(vec![
+                    ("name", table_col("s", "name")),
+                    ("region", table_col("s", "region")),
+                ])
+                .when_not_matched_insert(
+                    vec!["id", "name", "region"],
+                    vec![
+                        table_col("s", "id"),
+                        table_col("s", "name"),
+                        table_col("s", "region"),
+                    ],
+                )
+                .to_tokens(Dialect::TSql)
+                .unwrap()
+                .format(Dialect::TSql, &FormatOptions::new().max_line_width(60));
+            assert_snapshot!(sql);
+            validate_sql(&sql, Dialect::TSql).unwrap();
+        }
+    }
+}
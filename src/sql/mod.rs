@@ -9,11 +9,17 @@
 //! - [`dml`] - Data Manipulation Language (INSERT, UPDATE, DELETE, MERGE)
 //! - [`token`] - Token types for SQL generation
 //! - [`dialect`] - SQL dialect implementations
+//! - [`parse`] - SQL text to query builder conversion
+//! - [`format`] - Configurable pretty-printing of token streams
+//! - [`params`] - Bind-parameter extraction from generated SQL
 
 pub mod ddl;
 pub mod dialect;
 pub mod dml;
 pub mod expr;
+pub mod format;
+pub mod params;
+pub mod parse;
 pub mod query;
 pub mod token;
 
@@ -23,21 +29,30 @@ pub mod test_utils;
 // Re-export commonly used types at the sql module level
 pub use dialect::{Dialect, SqlDialect};
 pub use expr::{
-    avg, coalesce, col, count, count_distinct, count_star, func, lag_offset, lit_bool, lit_float,
-    lit_int, lit_null, lit_str, max, min, star, sum, table_col, table_star, BinaryOperator, Expr,
-    ExprExt, Literal, UnaryOperator, WindowExt, WindowFrame, WindowOrderBy,
+    avg, cast, coalesce, col, count, count_distinct, count_star, exists, func, lag_offset,
+    lit_bool, lit_float, lit_int, lit_null, lit_str, max, min, not_exists, star, sum, table_col,
+    table_star, BinaryOperator, Expr, ExprExt, Literal, UnaryOperator, WindowExt, WindowFrame,
+    WindowOrderBy,
 };
 pub use query::{
-    Cte, Join, JoinType, LimitOffset, NullsOrder, OrderByExpr, Query, SelectExpr, SortDir, TableRef,
+    Cte, Join, JoinType, LimitOffset, NullsOrder, OrderByExpr, Pivot, Query, QueryError,
+    QueryResult, SelectExpr, SortDir, TableRef, Unpivot,
 };
+pub use format::{CommaStyle, FormatOptions, KeywordCase};
+pub use params::{Param, ParameterizedSql};
+pub use parse::{parse_expr, parse_query, ParseError, ParseResult};
 pub use token::{Token, TokenStream};
 
 // Re-export DDL types
 pub use ddl::{
     AlterAction, AlterTable, ColumnConstraint, ColumnDef, CreateIndex, CreateTable, CreateView,
-    DataType, DdlStatement, DropIndex, DropTable, DropView, IndexColumn, ReferentialAction,
-    TableConstraint, Truncate,
+    DataType, Ddl, DdlError, DdlResult, DdlStatement, Distribution, DropIndex, DropTable, DropView,
+    IndexColumn, PartitionGranularity, ReferentialAction, TableConstraint, TableFormat,
+    TableOptions, Truncate,
 };
 
 // Re-export DML types
-pub use dml::{Delete, Insert, Merge, MergeAction, MergeSource, OnConflict, Update, WhenClause};
+pub use dml::{
+    Delete, DmlError, DmlResult, Insert, Merge, MergeAction, MergeSource, OnConflict, Update,
+    WhenClause,
+};
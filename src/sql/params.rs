@@ -0,0 +1,71 @@
+//! Bind-parameter extraction.
+//!
+//! `to_sql_parameterized` on queries and statements replaces literal values with
+//! dialect placeholders and returns the values separately, so the server can
+//! cache one plan per query shape:
+//!
+//! ```ignore
+//! let sql = query.to_sql_parameterized(Dialect::Postgres);
+//! // sql.sql:    SELECT "name" FROM "users" WHERE "status" = $1 LIMIT $2
+//! // sql.params: [String("active"), Int(10)]
+//! worker.execute_query(&sql.sql, Some(sql.args())).await?;
+//! ```
+//!
+//! | Dialect | Placeholder |
+//! |---------|-------------|
+//! | PostgreSQL, Redshift, DuckDB | `$1` |
+//! | T-SQL | `@p1` |
+//! | Snowflake | `:1` |
+//! | Others | `?` |
+//!
+//! Only values are bound. Identifiers, `NULL`, and literals that are part of
+//! the statement's structure (date part names, format strings, interval
+//! quantities, window frame offsets) stay inline. A value used more than once
+//! reuses its numbered placeholder, or stays inline with `?` placeholders, so
+//! grouped expressions repeated in `GROUP BY` still match.
+
+use serde::Serialize;
+
+/// A bound parameter value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Param {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl Param {
+    /// The value as JSON, for `ExecuteQueryParams.args`.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Param::Int(n) => (*n).into(),
+            Param::Float(f) => (*f).into(),
+            Param::String(s) => s.as_str().into(),
+            Param::Bool(b) => (*b).into(),
+        }
+    }
+}
+
+/// SQL with placeholders plus the values to bind, in placeholder order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterizedSql {
+    pub sql: String,
+    pub params: Vec<Param>,
+}
+
+impl ParameterizedSql {
+    /// SQL without any bound parameters.
+    pub fn unbound(sql: String) -> Self {
+        Self {
+            sql,
+            params: Vec::new(),
+        }
+    }
+
+    /// The parameter values as JSON, ready for `ExecuteQueryParams.args`.
+    pub fn args(&self) -> Vec<serde_json::Value> {
+        self.params.iter().map(Param::to_json).collect()
+    }
+}
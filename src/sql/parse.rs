@@ -0,0 +1,1135 @@
+//! SQL parser - converts SQL text into the query builder AST.
+//!
+//! Parses with the grammar of the source dialect via `sqlparser`, then maps
+//! the result onto [`Query`], [`Cte`], [`Join`], [`SetOperation`] and [`Expr`].
+//! Re-emitting the parsed query for another dialect transpiles it:
+//!
+//! ```ignore
+//! let query = Query::parse("SELECT TOP 10 [name] FROM [dbo].[users]", Dialect::TSql)?;
+//! let sql = query.to_sql(Dialect::Postgres); // ... FROM "dbo"."users" LIMIT 10
+//! ```
+//!
+//! Only constructs the builder can represent are accepted. Anything else
+//! (derived tables, `USING` joins, `DISTINCT ON`, named windows, ...) is
+//! rejected with [`ParseError::Unsupported`] instead of being dropped.
+
+use sqlparser::ast as sql;
+use sqlparser::dialect::{
+    BigQueryDialect, ClickHouseDialect, DatabricksDialect, DuckDbDialect, GenericDialect,
+    MsSqlDialect, MySqlDialect, PostgreSqlDialect, RedshiftSqlDialect, SQLiteDialect,
+    SnowflakeDialect,
+};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token as SqlToken;
+use thiserror::Error;
+
+use crate::model::types::DataType;
+
+use super::dialect::Dialect;
+use super::expr::{
+    BinaryOperator, Expr, Literal, UnaryOperator, WindowFrame, WindowFrameBound, WindowFrameKind,
+    WindowOrderBy,
+};
+use super::query::{
+    Cte, Join, JoinType, LimitOffset, OrderByExpr, Query, SelectExpr, SetOpType, SetOperation,
+    TableRef,
+};
+
+/// Error produced when SQL text cannot be converted into the builder AST.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ParseError {
+    /// The text is not valid SQL for the source dialect.
+    #[error("SQL syntax error: {0}")]
+    Syntax(String),
+
+    /// The text is valid SQL but not a single SELECT query.
+    #[error("expected a single SELECT query: {0}")]
+    NotAQuery(String),
+
+    /// The query uses a construct the builder cannot represent.
+    #[error("unsupported SQL construct: {construct}")]
+    Unsupported { construct: String },
+}
+
+/// Result type for SQL parsing.
+pub type ParseResult<T> = Result<T, ParseError>;
+
+fn unsupported<T>(construct: impl Into<String>) -> ParseResult<T> {
+    Err(ParseError::Unsupported {
+        construct: construct.into(),
+    })
+}
+
+/// Parse a single SELECT query (optionally with CTEs and set operations).
+pub fn parse_query(sql: &str, dialect: Dialect) -> ParseResult<Query> {
+    let parser_dialect = parser_dialect(dialect);
+    let statements = Parser::parse_sql(parser_dialect.as_ref(), sql)
+        .map_err(|e| ParseError::Syntax(e.to_string()))?;
+
+    match statements.as_slice() {
+        [sql::Statement::Query(query)] => convert_query(query),
+        [other] => Err(ParseError::NotAQuery(format!(
+            "found {} statement",
+            statement_kind(other)
+        ))),
+        _ => Err(ParseError::NotAQuery(format!(
+            "found {} statements",
+            statements.len()
+        ))),
+    }
+}
+
+/// Parse a standalone SQL expression.
+pub fn parse_expr(sql: &str, dialect: Dialect) -> ParseResult<Expr> {
+    let parser_dialect = parser_dialect(dialect);
+    let mut parser = Parser::new(parser_dialect.as_ref())
+        .try_with_sql(sql)
+        .map_err(|e| ParseError::Syntax(e.to_string()))?;
+    let expr = parser
+        .parse_expr()
+        .map_err(|e| ParseError::Syntax(e.to_string()))?;
+    parser
+        .expect_token(&SqlToken::EOF)
+        .map_err(|e| ParseError::Syntax(e.to_string()))?;
+    convert_expr(&expr)
+}
+
+/// The `sqlparser` grammar for a dialect.
+fn parser_dialect(dialect: Dialect) -> Box<dyn sqlparser::dialect::Dialect> {
+    match dialect {
+        Dialect::DuckDb => Box::new(DuckDbDialect {}),
+        Dialect::TSql => Box::new(MsSqlDialect {}),
+        Dialect::MySql => Box::new(MySqlDialect {}),
+        Dialect::Postgres => Box::new(PostgreSqlDialect {}),
+        Dialect::Snowflake => Box::new(SnowflakeDialect {}),
+        Dialect::BigQuery => Box::new(BigQueryDialect {}),
+        Dialect::Redshift => Box::new(RedshiftSqlDialect {}),
+        Dialect::Databricks => Box::new(DatabricksDialect {}),
+        Dialect::Sqlite => Box::new(SQLiteDialect {}),
+        Dialect::ClickHouse => Box::new(ClickHouseDialect {}),
+        Dialect::Trino => Box::new(GenericDialect {}), // sqlparser has no Trino dialect
+    }
+}
+
+fn statement_kind(statement: &sql::Statement) -> String {
+    let text = statement.to_string();
+    text.split_whitespace()
+        .next()
+        .unwrap_or("empty")
+        .to_uppercase()
+}
+
+// =============================================================================
+// Queries
+// =============================================================================
+
+fn convert_query(query: &sql::Query) -> ParseResult<Query> {
+    if !query.limit_by.is_empty() {
+        return unsupported("LIMIT BY");
+    }
+    if !query.locks.is_empty() {
+        return unsupported("locking clause");
+    }
+    if query.for_clause.is_some() {
+        return unsupported("FOR clause");
+    }
+    if query.settings.is_some() || query.format_clause.is_some() {
+        return unsupported("SETTINGS/FORMAT clause");
+    }
+
+    let mut result = convert_set_expr(&query.body)?;
+
+    if let Some(with) = &query.with {
+        let mut ctes = Vec::with_capacity(with.cte_tables.len());
+        for cte in &with.cte_tables {
+            ctes.push(convert_cte(cte, with.recursive)?);
+        }
+        // An inner WITH can only exist on a parenthesized body; keep it after ours.
+        ctes.append(&mut result.with);
+        result.with = ctes;
+    }
+
+    if let Some(order_by) = &query.order_by {
+        if order_by.interpolate.is_some() {
+            return unsupported("ORDER BY ... INTERPOLATE");
+        }
+        let exprs = order_by
+            .exprs
+            .iter()
+            .map(convert_order_by)
+            .collect::<ParseResult<Vec<_>>>()?;
+        if !exprs.is_empty() {
+            if !result.order_by.is_empty() {
+                return unsupported("nested ORDER BY");
+            }
+            result.order_by = exprs;
+        }
+    }
+
+    let mut limit = match &query.limit {
+        Some(expr) => Some(convert_count(expr, "LIMIT")?),
+        None => None,
+    };
+    if let Some(fetch) = &query.fetch {
+        if fetch.with_ties || fetch.percent {
+            return unsupported("FETCH ... PERCENT/WITH TIES");
+        }
+        if let Some(quantity) = &fetch.quantity {
+            limit = Some(convert_count(quantity, "FETCH")?);
+        }
+    }
+    // OFFSET 0 is a no-op (T-SQL emits it for every FETCH), so drop it
+    let offset = match &query.offset {
+        Some(offset) => Some(convert_count(&offset.value, "OFFSET")?).filter(|n| *n > 0),
+        None => None,
+    };
+
+    if limit.is_some() || offset.is_some() {
+        let current = result.limit_offset.take().unwrap_or_default();
+        if (limit.is_some() && current.limit.is_some())
+            || (offset.is_some() && current.offset.is_some())
+        {
+            return unsupported("nested LIMIT/OFFSET");
+        }
+        result.limit_offset = Some(LimitOffset {
+            limit: limit.or(current.limit),
+            offset: offset.or(current.offset),
+        });
+    }
+
+    Ok(result)
+}
+
+fn convert_cte(cte: &sql::Cte, recursive: bool) -> ParseResult<Cte> {
+    if cte.materialized.is_some() {
+        return unsupported("CTE [NOT] MATERIALIZED");
+    }
+    if cte.from.is_some() {
+        return unsupported("CTE FROM");
+    }
+
+    let query = convert_query(&cte.query)?;
+    let mut result = if recursive {
+        Cte::recursive(&cte.alias.name.value, query)
+    } else {
+        Cte::new(&cte.alias.name.value, query)
+    };
+
+    if !cte.alias.columns.is_empty() {
+        if cte.alias.columns.iter().any(|c| c.data_type.is_some()) {
+            return unsupported("typed CTE columns");
+        }
+        result = result.with_columns(
+            cte.alias
+                .columns
+                .iter()
+                .map(|c| c.name.value.as_str())
+                .collect(),
+        );
+    }
+
+    Ok(result)
+}
+
+fn convert_set_expr(body: &sql::SetExpr) -> ParseResult<Query> {
+    match body {
+        sql::SetExpr::Select(select) => convert_select(select),
+        sql::SetExpr::Query(query) => convert_query(query),
+        sql::SetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            let op = match op {
+                sql::SetOperator::Union => SetOpType::Union,
+                sql::SetOperator::Intersect => SetOpType::Intersect,
+                sql::SetOperator::Except => SetOpType::Except,
+            };
+            let all = match set_quantifier {
+                sql::SetQuantifier::All => true,
+                sql::SetQuantifier::Distinct | sql::SetQuantifier::None => false,
+                other => return unsupported(format!("{} {}", op_name(op), other)),
+            };
+
+            Ok(Query {
+                set_op: Some(Box::new(SetOperation {
+                    left: Box::new(convert_set_expr(left)?),
+                    op,
+                    all,
+                    right: Box::new(convert_set_expr(right)?),
+                })),
+                ..Default::default()
+            })
+        }
+        sql::SetExpr::Values(_) => unsupported("VALUES"),
+        _ => Err(ParseError::NotAQuery(format!("found {}", body))),
+    }
+}
+
+fn op_name(op: SetOpType) -> &'static str {
+    match op {
+        SetOpType::Union => "UNION",
+        SetOpType::Intersect => "INTERSECT",
+        SetOpType::Except => "EXCEPT",
+    }
+}
+
+fn convert_select(select: &sql::Select) -> ParseResult<Query> {
+    if select.into.is_some() {
+        return unsupported("SELECT INTO");
+    }
+    if !select.lateral_views.is_empty() {
+        return unsupported("LATERAL VIEW");
+    }
+    if select.prewhere.is_some() {
+        return unsupported("PREWHERE");
+    }
+    if !select.cluster_by.is_empty()
+        || !select.distribute_by.is_empty()
+        || !select.sort_by.is_empty()
+    {
+        return unsupported("CLUSTER/DISTRIBUTE/SORT BY");
+    }
+    if !select.named_window.is_empty() {
+        return unsupported("WINDOW clause");
+    }
+    if select.value_table_mode.is_some() {
+        return unsupported("SELECT AS STRUCT/VALUE");
+    }
+    if select.connect_by.is_some() {
+        return unsupported("CONNECT BY");
+    }
+
+    let mut query = Query::new();
+
+    query.distinct = match &select.distinct {
+        None => false,
+        Some(sql::Distinct::Distinct) => true,
+        Some(sql::Distinct::On(_)) => return unsupported("DISTINCT ON"),
+    };
+
+    if let Some(top) = &select.top {
+        if top.with_ties || top.percent {
+            return unsupported("TOP ... PERCENT/WITH TIES");
+        }
+        let limit = match &top.quantity {
+            Some(sql::TopQuantity::Constant(n)) => *n,
+            Some(sql::TopQuantity::Expr(expr)) => convert_count(expr, "TOP")?,
+            None => return unsupported("TOP without a quantity"),
+        };
+        query.limit_offset = Some(LimitOffset {
+            limit: Some(limit),
+            offset: None,
+        });
+    }
+
+    for item in &select.projection {
+        query.select.push(convert_select_item(item)?);
+    }
+
+    for (i, table) in select.from.iter().enumerate() {
+        let table_ref = convert_table_factor(&table.relation)?;
+        if i == 0 {
+            query.from = Some(table_ref);
+        } else {
+            // `FROM a, b` is a cross join
+            query.joins.push(Join {
+                join_type: JoinType::Cross,
+                table: table_ref,
+                on: None,
+            });
+        }
+        for join in &table.joins {
+            query.joins.push(convert_join(join)?);
+        }
+    }
+
+    query.where_clause = select.selection.as_ref().map(convert_expr).transpose()?;
+
+    query.group_by = match &select.group_by {
+        sql::GroupByExpr::Expressions(exprs, modifiers) => {
+            if !modifiers.is_empty() {
+                return unsupported("GROUP BY modifiers");
+            }
+            exprs.iter().map(convert_expr).collect::<ParseResult<_>>()?
+        }
+        sql::GroupByExpr::All(_) => return unsupported("GROUP BY ALL"),
+    };
+
+    query.having = select.having.as_ref().map(convert_expr).transpose()?;
+    query.qualify = select.qualify.as_ref().map(convert_expr).transpose()?;
+
+    Ok(query)
+}
+
+fn convert_select_item(item: &sql::SelectItem) -> ParseResult<SelectExpr> {
+    match item {
+        sql::SelectItem::UnnamedExpr(expr) => Ok(SelectExpr::new(convert_expr(expr)?)),
+        sql::SelectItem::ExprWithAlias { expr, alias } => {
+            Ok(SelectExpr::new(convert_expr(expr)?).with_alias(&alias.value))
+        }
+        sql::SelectItem::Wildcard(options) => {
+            check_wildcard_options(options)?;
+            Ok(SelectExpr::new(Expr::Star { table: None }))
+        }
+        sql::SelectItem::QualifiedWildcard(name, options) => {
+            check_wildcard_options(options)?;
+            Ok(SelectExpr::new(Expr::Star {
+                table: name.0.last().map(|i| i.value.clone()),
+            }))
+        }
+    }
+}
+
+fn check_wildcard_options(options: &sql::WildcardAdditionalOptions) -> ParseResult<()> {
+    if options.opt_ilike.is_some()
+        || options.opt_exclude.is_some()
+        || options.opt_except.is_some()
+        || options.opt_replace.is_some()
+        || options.opt_rename.is_some()
+    {
+        return unsupported("wildcard modifiers (EXCLUDE/EXCEPT/REPLACE/RENAME)");
+    }
+    Ok(())
+}
+
+fn convert_table_factor(factor: &sql::TableFactor) -> ParseResult<TableRef> {
+    match factor {
+        sql::TableFactor::Table {
+            name,
+            alias,
+            args,
+            with_hints,
+            version,
+            with_ordinality,
+            partitions,
+            json_path,
+        } => {
+            if args.is_some() {
+                return unsupported("table-valued function");
+            }
+            if !with_hints.is_empty() {
+                return unsupported("table hints");
+            }
+            if version.is_some() {
+                return unsupported("time travel");
+            }
+            if *with_ordinality || !partitions.is_empty() || json_path.is_some() {
+                return unsupported("table modifiers");
+            }
+
+            let mut table_ref = match name.0.as_slice() {
+                [table] => TableRef::new(&table.value),
+                [schema, table] => TableRef::new(&table.value).with_schema(&schema.value),
+                [] => return unsupported("empty table name"),
+                _ => return unsupported(format!("catalog-qualified table name `{}`", name)),
+            };
+            if let Some(alias) = alias {
+                if !alias.columns.is_empty() {
+                    return unsupported("table alias column list");
+                }
+                table_ref = table_ref.with_alias(&alias.name.value);
+            }
+            Ok(table_ref)
+        }
+        sql::TableFactor::Derived { .. } => unsupported("derived table in FROM"),
+        sql::TableFactor::NestedJoin { .. } => unsupported("parenthesized join"),
+        sql::TableFactor::Pivot { .. } => unsupported("PIVOT"),
+        sql::TableFactor::Unpivot { .. } => unsupported("UNPIVOT"),
+        sql::TableFactor::UNNEST { .. } => unsupported("UNNEST"),
+        other => unsupported(format!("table factor `{}`", other)),
+    }
+}
+
+fn convert_join(join: &sql::Join) -> ParseResult<Join> {
+    let table = convert_table_factor(&join.relation)?;
+
+    let (join_type, constraint) = match &join.join_operator {
+        sql::JoinOperator::Inner(c) => (JoinType::Inner, Some(c)),
+        sql::JoinOperator::LeftOuter(c) => (JoinType::Left, Some(c)),
+        sql::JoinOperator::RightOuter(c) => (JoinType::Right, Some(c)),
+        sql::JoinOperator::FullOuter(c) => (JoinType::Full, Some(c)),
+        sql::JoinOperator::CrossJoin => (JoinType::Cross, None),
+        other => return unsupported(format!("join operator {:?}", other)),
+    };
+
+    let on = match constraint {
+        None | Some(sql::JoinConstraint::None) => None,
+        Some(sql::JoinConstraint::On(expr)) => Some(convert_expr(expr)?),
+        Some(sql::JoinConstraint::Using(_)) => return unsupported("JOIN ... USING"),
+        Some(sql::JoinConstraint::Natural) => return unsupported("NATURAL JOIN"),
+    };
+
+    if on.is_none() && join_type != JoinType::Cross {
+        return unsupported("join without ON condition");
+    }
+
+    Ok(Join {
+        join_type,
+        table,
+        on,
+    })
+}
+
+fn convert_order_by(order: &sql::OrderByExpr) -> ParseResult<OrderByExpr> {
+    if order.with_fill.is_some() {
+        return unsupported("ORDER BY ... WITH FILL");
+    }
+
+    let expr = convert_expr(&order.expr)?;
+    let result = match order.asc {
+        Some(true) => OrderByExpr::asc(expr),
+        Some(false) => OrderByExpr::desc(expr),
+        None => OrderByExpr::new(expr),
+    };
+    Ok(match order.nulls_first {
+        Some(true) => result.nulls_first(),
+        Some(false) => result.nulls_last(),
+        None => result,
+    })
+}
+
+/// A non-negative integer literal in LIMIT/OFFSET/TOP/FETCH.
+fn convert_count(expr: &sql::Expr, clause: &str) -> ParseResult<u64> {
+    match expr {
+        sql::Expr::Value(sql::Value::Number(n, _)) => match n.parse::<u64>() {
+            Ok(n) => Ok(n),
+            Err(_) => unsupported(format!("{} {}", clause, n)),
+        },
+        sql::Expr::Nested(inner) => convert_count(inner, clause),
+        other => unsupported(format!("non-literal {} `{}`", clause, other)),
+    }
+}
+
+// =============================================================================
+// Expressions
+// =============================================================================
+
+fn convert_expr(expr: &sql::Expr) -> ParseResult<Expr> {
+    match expr {
+        sql::Expr::Identifier(ident) => Ok(Expr::Column {
+            table: None,
+            column: ident.value.clone(),
+        }),
+
+        sql::Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+            [column] => Ok(Expr::Column {
+                table: None,
+                column: column.value.clone(),
+            }),
+            // schema.table.column keeps the table qualifier only
+            [.., table, column] => Ok(Expr::Column {
+                table: Some(table.value.clone()),
+                column: column.value.clone(),
+            }),
+            [] => unsupported("empty identifier"),
+        },
+
+        sql::Expr::Value(value) => convert_value(value),
+
+        sql::Expr::TypedString { data_type, value } => Ok(Expr::Cast {
+            expr: Box::new(Expr::Literal(Literal::String(value.clone()))),
+            data_type: convert_data_type(data_type)?,
+        }),
+
+        sql::Expr::BinaryOp { left, op, right } => Ok(Expr::BinaryOp {
+            left: Box::new(convert_expr(left)?),
+            op: convert_binary_op(op)?,
+            right: Box::new(convert_expr(right)?),
+        }),
+
+        sql::Expr::UnaryOp { op, expr } => match op {
+            sql::UnaryOperator::Not => Ok(Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr: Box::new(convert_expr(expr)?),
+            }),
+            sql::UnaryOperator::Minus => Ok(Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: Box::new(convert_expr(expr)?),
+            }),
+            sql::UnaryOperator::Plus => convert_expr(expr),
+            other => unsupported(format!("unary operator {}", other)),
+        },
+
+        sql::Expr::Nested(inner) => Ok(Expr::Paren(Box::new(convert_expr(inner)?))),
+
+        sql::Expr::Function(func) => convert_function(func),
+
+        sql::Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => Ok(Expr::Case {
+            operand: operand
+                .as_ref()
+                .map(|o| convert_expr(o).map(Box::new))
+                .transpose()?,
+            when_clauses: conditions
+                .iter()
+                .zip(results)
+                .map(|(when, then)| Ok((convert_expr(when)?, convert_expr(then)?)))
+                .collect::<ParseResult<_>>()?,
+            else_clause: else_result
+                .as_ref()
+                .map(|e| convert_expr(e).map(Box::new))
+                .transpose()?,
+        }),
+
+        sql::Expr::Cast {
+            kind,
+            expr,
+            data_type,
+            format,
+        } => {
+            if format.is_some() {
+                return unsupported("CAST ... FORMAT");
+            }
+            match kind {
+                sql::CastKind::Cast | sql::CastKind::DoubleColon => Ok(Expr::Cast {
+                    expr: Box::new(convert_expr(expr)?),
+                    data_type: convert_data_type(data_type)?,
+                }),
+                sql::CastKind::TryCast | sql::CastKind::SafeCast => unsupported("TRY_CAST"),
+            }
+        }
+
+        sql::Expr::Subquery(query) => Ok(Expr::Subquery(Box::new(convert_query(query)?))),
+
+        sql::Expr::InList {
+            expr,
+            list,
+            negated,
+        } => Ok(Expr::In {
+            expr: Box::new(convert_expr(expr)?),
+            values: list.iter().map(convert_expr).collect::<ParseResult<_>>()?,
+            negated: *negated,
+        }),
+
+        sql::Expr::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => Ok(Expr::InSubquery {
+            expr: Box::new(convert_expr(expr)?),
+            subquery: Box::new(convert_query(subquery)?),
+            negated: *negated,
+        }),
+
+        sql::Expr::Exists { subquery, negated } => Ok(Expr::Exists {
+            subquery: Box::new(convert_query(subquery)?),
+            negated: *negated,
+        }),
+
+        sql::Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => Ok(Expr::Between {
+            expr: Box::new(convert_expr(expr)?),
+            low: Box::new(convert_expr(low)?),
+            high: Box::new(convert_expr(high)?),
+            negated: *negated,
+        }),
+
+        sql::Expr::IsNull(expr) => Ok(Expr::IsNull {
+            expr: Box::new(convert_expr(expr)?),
+            negated: false,
+        }),
+
+        sql::Expr::IsNotNull(expr) => Ok(Expr::IsNull {
+            expr: Box::new(convert_expr(expr)?),
+            negated: true,
+        }),
+
+        sql::Expr::Like {
+            negated,
+            any,
+            expr,
+            pattern,
+            escape_char,
+        } => {
+            if *any {
+                return unsupported("LIKE ANY");
+            }
+            let expr = Box::new(convert_expr(expr)?);
+            let pattern = Box::new(convert_expr(pattern)?);
+            match escape_char {
+                Some(escape) => {
+                    let mut chars = escape.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(escape_char), None) => Ok(Expr::LikeEscape {
+                            expr,
+                            pattern,
+                            escape_char,
+                            negated: *negated,
+                        }),
+                        _ => unsupported(format!("LIKE escape '{}'", escape)),
+                    }
+                }
+                None => {
+                    let like = Expr::BinaryOp {
+                        left: expr,
+                        op: BinaryOperator::Like,
+                        right: pattern,
+                    };
+                    if *negated {
+                        Ok(Expr::UnaryOp {
+                            op: UnaryOperator::Not,
+                            expr: Box::new(Expr::Paren(Box::new(like))),
+                        })
+                    } else {
+                        Ok(like)
+                    }
+                }
+            }
+        }
+
+        other => unsupported(format!("expression `{}`", other)),
+    }
+}
+
+fn convert_value(value: &sql::Value) -> ParseResult<Expr> {
+    let literal = match value {
+        sql::Value::Number(n, _) => {
+            if let Ok(i) = n.parse::<i64>() {
+                Literal::Int(i)
+            } else if let Ok(f) = n.parse::<f64>() {
+                Literal::Float(f)
+            } else {
+                return unsupported(format!("numeric literal {}", n));
+            }
+        }
+        sql::Value::SingleQuotedString(s) | sql::Value::NationalStringLiteral(s) => {
+            Literal::String(s.clone())
+        }
+        sql::Value::Boolean(b) => Literal::Bool(*b),
+        sql::Value::Null => Literal::Null,
+        other => return unsupported(format!("literal {}", other)),
+    };
+    Ok(Expr::Literal(literal))
+}
+
+fn convert_binary_op(op: &sql::BinaryOperator) -> ParseResult<BinaryOperator> {
+    Ok(match op {
+        sql::BinaryOperator::Eq => BinaryOperator::Eq,
+        sql::BinaryOperator::NotEq => BinaryOperator::Ne,
+        sql::BinaryOperator::Lt => BinaryOperator::Lt,
+        sql::BinaryOperator::Gt => BinaryOperator::Gt,
+        sql::BinaryOperator::LtEq => BinaryOperator::Lte,
+        sql::BinaryOperator::GtEq => BinaryOperator::Gte,
+        sql::BinaryOperator::And => BinaryOperator::And,
+        sql::BinaryOperator::Or => BinaryOperator::Or,
+        sql::BinaryOperator::Plus => BinaryOperator::Plus,
+        sql::BinaryOperator::Minus => BinaryOperator::Minus,
+        sql::BinaryOperator::Multiply => BinaryOperator::Mul,
+        sql::BinaryOperator::Divide => BinaryOperator::Div,
+        sql::BinaryOperator::Modulo => BinaryOperator::Mod,
+        sql::BinaryOperator::StringConcat => BinaryOperator::Concat,
+        other => return unsupported(format!("binary operator {}", other)),
+    })
+}
+
+fn convert_function(func: &sql::Function) -> ParseResult<Expr> {
+    if func.uses_odbc_syntax {
+        return unsupported("ODBC function syntax");
+    }
+    if !matches!(func.parameters, sql::FunctionArguments::None) {
+        return unsupported("parametric function");
+    }
+    if func.filter.is_some() {
+        return unsupported("aggregate FILTER");
+    }
+    if func.null_treatment.is_some() {
+        return unsupported("IGNORE/RESPECT NULLS");
+    }
+    if !func.within_group.is_empty() {
+        return unsupported("WITHIN GROUP");
+    }
+
+    let name = func
+        .name
+        .0
+        .iter()
+        .map(|i| i.value.as_str())
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let (args, distinct) = match &func.args {
+        sql::FunctionArguments::None => (vec![], false),
+        sql::FunctionArguments::Subquery(_) => return unsupported("subquery function argument"),
+        sql::FunctionArguments::List(list) => {
+            if !list.clauses.is_empty() {
+                return unsupported(format!("clauses in {}() arguments", name));
+            }
+            let distinct = matches!(
+                list.duplicate_treatment,
+                Some(sql::DuplicateTreatment::Distinct)
+            );
+            let args = list
+                .args
+                .iter()
+                .map(convert_function_arg)
+                .collect::<ParseResult<Vec<_>>>()?;
+            (args, distinct)
+        }
+    };
+
+    let function = Expr::Function {
+        name,
+        args,
+        distinct,
+    };
+
+    match &func.over {
+        None => Ok(function),
+        Some(sql::WindowType::NamedWindow(_)) => unsupported("named window"),
+        Some(sql::WindowType::WindowSpec(spec)) => {
+            if spec.window_name.is_some() {
+                return unsupported("named window");
+            }
+            Ok(Expr::WindowFunction {
+                function: Box::new(function),
+                partition_by: spec
+                    .partition_by
+                    .iter()
+                    .map(convert_expr)
+                    .collect::<ParseResult<_>>()?,
+                order_by: spec
+                    .order_by
+                    .iter()
+                    .map(convert_window_order_by)
+                    .collect::<ParseResult<_>>()?,
+                frame: spec
+                    .window_frame
+                    .as_ref()
+                    .map(convert_window_frame)
+                    .transpose()?,
+            })
+        }
+    }
+}
+
+fn convert_function_arg(arg: &sql::FunctionArg) -> ParseResult<Expr> {
+    match arg {
+        sql::FunctionArg::Unnamed(arg) => match arg {
+            sql::FunctionArgExpr::Expr(expr) => convert_expr(expr),
+            sql::FunctionArgExpr::Wildcard => Ok(Expr::Star { table: None }),
+            sql::FunctionArgExpr::QualifiedWildcard(name) => Ok(Expr::Star {
+                table: name.0.last().map(|i| i.value.clone()),
+            }),
+        },
+        _ => unsupported("named function argument"),
+    }
+}
+
+fn convert_window_order_by(order: &sql::OrderByExpr) -> ParseResult<WindowOrderBy> {
+    let expr = convert_expr(&order.expr)?;
+    let result = match order.asc {
+        Some(true) => WindowOrderBy::asc(expr),
+        Some(false) => WindowOrderBy::desc(expr),
+        None => WindowOrderBy::new(expr),
+    };
+    Ok(match order.nulls_first {
+        Some(true) => result.nulls_first(),
+        Some(false) => result.nulls_last(),
+        None => result,
+    })
+}
+
+fn convert_window_frame(frame: &sql::WindowFrame) -> ParseResult<WindowFrame> {
+    let kind = match frame.units {
+        sql::WindowFrameUnits::Rows => WindowFrameKind::Rows,
+        sql::WindowFrameUnits::Range => WindowFrameKind::Range,
+        sql::WindowFrameUnits::Groups => WindowFrameKind::Groups,
+    };
+    let start = convert_frame_bound(&frame.start_bound)?;
+    Ok(match &frame.end_bound {
+        Some(end) => WindowFrame::between(kind, start, convert_frame_bound(end)?),
+        None => WindowFrame::new(kind, start),
+    })
+}
+
+fn convert_frame_bound(bound: &sql::WindowFrameBound) -> ParseResult<WindowFrameBound> {
+    match bound {
+        sql::WindowFrameBound::CurrentRow => Ok(WindowFrameBound::CurrentRow),
+        sql::WindowFrameBound::Preceding(None) => Ok(WindowFrameBound::UnboundedPreceding),
+        sql::WindowFrameBound::Following(None) => Ok(WindowFrameBound::UnboundedFollowing),
+        sql::WindowFrameBound::Preceding(Some(n)) => {
+            Ok(WindowFrameBound::Preceding(convert_count(n, "PRECEDING")?))
+        }
+        sql::WindowFrameBound::Following(Some(n)) => {
+            Ok(WindowFrameBound::Following(convert_count(n, "FOLLOWING")?))
+        }
+    }
+}
+
+fn convert_data_type(dt: &sql::DataType) -> ParseResult<DataType> {
+    Ok(match dt {
+        sql::DataType::Boolean | sql::DataType::Bool => DataType::Bool,
+        sql::DataType::Int8(_) | sql::DataType::TinyInt(_) => DataType::Int8,
+        sql::DataType::Int16 | sql::DataType::SmallInt(_) | sql::DataType::Int2(_) => {
+            DataType::Int16
+        }
+        sql::DataType::Int32
+        | sql::DataType::Int(_)
+        | sql::DataType::Integer(_)
+        | sql::DataType::Int4(_) => DataType::Int32,
+        sql::DataType::Int64 | sql::DataType::BigInt(_) => DataType::Int64,
+        sql::DataType::Float32 | sql::DataType::Real | sql::DataType::Float4 => DataType::Float32,
+        sql::DataType::Float64
+        | sql::DataType::Double
+        | sql::DataType::DoublePrecision
+        | sql::DataType::Float8 => DataType::Float64,
+        sql::DataType::Decimal(info) | sql::DataType::Numeric(info) => match info {
+            sql::ExactNumberInfo::PrecisionAndScale(p, s) => DataType::Decimal(*p as u8, *s as u8),
+            sql::ExactNumberInfo::Precision(p) => DataType::Decimal(*p as u8, 0),
+            sql::ExactNumberInfo::None => DataType::Decimal(38, 9),
+        },
+        sql::DataType::Varchar(len) | sql::DataType::Nvarchar(len) => match len {
+            Some(sql::CharacterLength::IntegerLength { length, .. })
+                if *length <= u16::MAX as u64 =>
+            {
+                DataType::Varchar(*length as u16)
+            }
+            _ => DataType::String,
+        },
+        sql::DataType::Char(len) | sql::DataType::Character(len) => match len {
+            Some(sql::CharacterLength::IntegerLength { length, .. })
+                if *length <= u16::MAX as u64 =>
+            {
+                DataType::Char(*length as u16)
+            }
+            _ => DataType::Char(1),
+        },
+        sql::DataType::Text | sql::DataType::String(_) => DataType::String,
+        sql::DataType::Date => DataType::Date,
+        sql::DataType::Time(_, _) => DataType::Time,
+        sql::DataType::Timestamp(_, tz) => match tz {
+            sql::TimezoneInfo::WithTimeZone | sql::TimezoneInfo::Tz => DataType::TimestampTz,
+            _ => DataType::Timestamp,
+        },
+        sql::DataType::Datetime(_) => DataType::Timestamp,
+        sql::DataType::Uuid => DataType::Uuid,
+        sql::DataType::JSON | sql::DataType::JSONB => DataType::Json,
+        sql::DataType::Blob(_)
+        | sql::DataType::Binary(_)
+        | sql::DataType::Varbinary(_)
+        | sql::DataType::Bytea => DataType::Binary,
+        other => return unsupported(format!("data type {}", other)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(sql: &str, from: Dialect, to: Dialect) -> String {
+        parse_query(sql, from)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {}", sql, e))
+            .to_sql(to)
+    }
+
+    #[test]
+    fn test_parse_simple_select() {
+        let query = parse_query(
+            "SELECT o.id, SUM(o.amount) AS total FROM sales.orders AS o WHERE o.status = 'paid' GROUP BY o.id",
+            Dialect::DuckDb,
+        )
+        .unwrap();
+
+        assert_eq!(query.select.len(), 2);
+        assert_eq!(query.select[1].alias.as_deref(), Some("total"));
+        let from = query.from.as_ref().unwrap();
+        assert_eq!(from.schema.as_deref(), Some("sales"));
+        assert_eq!(from.table, "orders");
+        assert_eq!(from.alias.as_deref(), Some("o"));
+        assert!(query.where_clause.is_some());
+        assert_eq!(query.group_by.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_joins() {
+        let query = parse_query(
+            "SELECT * FROM orders o LEFT JOIN customers c ON o.customer_id = c.id CROSS JOIN dates d",
+            Dialect::Postgres,
+        )
+        .unwrap();
+
+        assert_eq!(query.joins.len(), 2);
+        assert_eq!(query.joins[0].join_type, JoinType::Left);
+        assert!(query.joins[0].on.is_some());
+        assert_eq!(query.joins[1].join_type, JoinType::Cross);
+    }
+
+    #[test]
+    fn test_parse_cte_and_union() {
+        let query = parse_query(
+            "WITH a AS (SELECT id FROM t1) SELECT id FROM a UNION ALL SELECT id FROM t2 ORDER BY id LIMIT 5",
+            Dialect::DuckDb,
+        )
+        .unwrap();
+
+        assert_eq!(query.with.len(), 1);
+        assert_eq!(query.with[0].name, "a");
+        let set_op = query.set_op.as_ref().unwrap();
+        assert_eq!(set_op.op, SetOpType::Union);
+        assert!(set_op.all);
+        assert_eq!(query.order_by.len(), 1);
+        assert_eq!(query.limit_offset.as_ref().unwrap().limit, Some(5));
+
+        let sql = query.to_sql(Dialect::DuckDb);
+        assert!(sql.starts_with("WITH \"a\" AS"), "SQL: {}", sql);
+        assert!(sql.contains("UNION ALL"), "SQL: {}", sql);
+        assert!(sql.ends_with("LIMIT 5"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_transpile_tsql_top_to_postgres() {
+        let sql = roundtrip(
+            "SELECT TOP 10 [name] FROM [dbo].[users] ORDER BY [name]",
+            Dialect::TSql,
+            Dialect::Postgres,
+        );
+        assert!(sql.contains("FROM \"dbo\".\"users\""), "SQL: {}", sql);
+        assert!(sql.ends_with("LIMIT 10"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_transpile_limit_to_tsql() {
+        let sql = roundtrip(
+            "SELECT `id` FROM `orders` ORDER BY `id` LIMIT 10 OFFSET 20",
+            Dialect::MySql,
+            Dialect::TSql,
+        );
+        assert!(sql.contains("FROM [orders]"), "SQL: {}", sql);
+        assert!(
+            sql.contains("OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"),
+            "SQL: {}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_parse_window_and_qualify() {
+        let query = parse_query(
+            "SELECT id, ROW_NUMBER() OVER (PARTITION BY region ORDER BY amount DESC) AS rn \
+             FROM orders QUALIFY rn = 1",
+            Dialect::Snowflake,
+        )
+        .unwrap();
+
+        assert!(matches!(query.select[1].expr, Expr::WindowFunction { .. }));
+        assert!(query.qualify.is_some());
+    }
+
+    #[test]
+    fn test_parse_expressions() {
+        let query = parse_query(
+            "SELECT CASE WHEN x IN (1, 2) THEN 'a' ELSE 'b' END, \
+                    CAST(y AS DECIMAL(10, 2)), \
+                    COUNT(DISTINCT z), COUNT(*) \
+             FROM t \
+             WHERE name LIKE 'a%' AND d BETWEEN 1 AND 5 AND e IS NOT NULL AND NOT (f = 1)",
+            Dialect::DuckDb,
+        )
+        .unwrap();
+
+        assert!(matches!(query.select[0].expr, Expr::Case { .. }));
+        assert!(matches!(
+            query.select[1].expr,
+            Expr::Cast {
+                data_type: DataType::Decimal(10, 2),
+                ..
+            }
+        ));
+        assert!(matches!(
+            query.select[2].expr,
+            Expr::Function { distinct: true, .. }
+        ));
+
+        let sql = query.to_sql(Dialect::DuckDb);
+        assert!(
+            sql.contains("CAST(\"y\" AS DECIMAL(10, 2))"),
+            "SQL: {}",
+            sql
+        );
+        assert!(sql.contains("COUNT(*)"), "SQL: {}", sql);
+        assert!(sql.contains("\"name\" LIKE 'a%'"), "SQL: {}", sql);
+    }
+
+    #[test]
+    fn test_reparse_generated_sql() {
+        let original = parse_query(
+            "SELECT c.region, SUM(o.amount) AS revenue FROM orders o \
+             INNER JOIN customers c ON o.customer_id = c.id \
+             GROUP BY c.region HAVING SUM(o.amount) > 100 ORDER BY revenue DESC LIMIT 3",
+            Dialect::DuckDb,
+        )
+        .unwrap();
+
+        for dialect in [
+            Dialect::DuckDb,
+            Dialect::Postgres,
+            Dialect::TSql,
+            Dialect::MySql,
+        ] {
+            let reparsed = parse_query(&original.to_sql(dialect), dialect).unwrap();
+            assert_eq!(reparsed, original, "round trip through {}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        let expr = parse_expr("a + 1 > b", Dialect::DuckDb).unwrap();
+        assert!(matches!(
+            expr,
+            Expr::BinaryOp {
+                op: BinaryOperator::Gt,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_expr("a +", Dialect::DuckDb),
+            Err(ParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_constructs() {
+        for (sql, dialect) in [
+            ("SELECT * FROM (SELECT 1) AS t", Dialect::Postgres),
+            ("SELECT * FROM a JOIN b USING (id)", Dialect::Postgres),
+            ("SELECT DISTINCT ON (a) a FROM t", Dialect::Postgres),
+            ("SELECT TRY_CAST(a AS INT) FROM t", Dialect::Snowflake),
+            ("SELECT a FROM warehouse.sales.orders", Dialect::TSql),
+        ] {
+            assert!(
+                matches!(
+                    parse_query(sql, dialect),
+                    Err(ParseError::Unsupported { .. })
+                ),
+                "{} should be unsupported",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_a_query() {
+        assert!(matches!(
+            parse_query("DELETE FROM t", Dialect::Postgres),
+            Err(ParseError::NotAQuery(_))
+        ));
+        assert!(matches!(
+            parse_query("SELECT 1; SELECT 2", Dialect::Postgres),
+            Err(ParseError::NotAQuery(_))
+        ));
+        assert!(matches!(
+            parse_query("SELEC 1", Dialect::Postgres),
+            Err(ParseError::Syntax(_))
+        ));
+    }
+}
@@ -1,7 +1,13 @@
 //! Query builder - construct SQL queries with a fluent API.
 
-use super::dialect::{Dialect, SqlDialect};
-use super::expr::{Expr, ExprExt};
+use thiserror::Error;
+
+use super::dialect::{Dialect, DialectTarget, SqlDialect};
+use super::expr::{
+    col, func, lit_int, lit_str, not_exists, nulls_sort_key, table_col, Expr, ExprExt, Literal,
+};
+use super::params::ParameterizedSql;
+use super::parse::{parse_query, ParseResult};
 use super::token::{Token, TokenStream};
 
 // =============================================================================
@@ -30,7 +36,8 @@ impl SelectExpr {
         self.to_tokens_for_dialect(Dialect::default())
     }
 
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = self.expr.to_tokens_for_dialect(dialect);
         if let Some(alias) = &self.alias {
             ts.space()
@@ -53,12 +60,19 @@ impl From<Expr> for SelectExpr {
 // =============================================================================
 
 /// A table reference with optional schema and alias.
+///
+/// A table reference can also read a table-valued function such as
+/// `UNNEST(...)` or `generate_series(...)`; see [`TableRef::function`].
 #[derive(Debug, Clone, PartialEq)]
 #[must_use = "builders have no effect until used"]
 pub struct TableRef {
     pub schema: Option<String>,
     pub table: String,
     pub alias: Option<String>,
+    /// Table-valued function read in place of a named table.
+    pub function: Option<Expr>,
+    /// Names for the function's output columns: `AS t (a, b)`.
+    pub columns: Vec<String>,
 }
 
 impl TableRef {
@@ -67,6 +81,19 @@ impl TableRef {
             schema: None,
             table: table.into(),
             alias: None,
+            function: None,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Read rows from a table-valued function call, aliased as `alias`.
+    pub fn function(call: Expr, alias: &str) -> Self {
+        Self {
+            schema: None,
+            table: alias.into(),
+            alias: Some(alias.into()),
+            function: Some(call),
+            columns: Vec::new(),
         }
     }
 
@@ -80,18 +107,41 @@ impl TableRef {
         self
     }
 
+    /// Name the output columns of a table-valued function.
+    pub fn with_columns(mut self, columns: Vec<&str>) -> Self {
+        self.columns = columns.into_iter().map(String::from).collect();
+        self
+    }
+
     pub fn to_tokens(&self) -> TokenStream {
+        self.to_tokens_for_dialect(Dialect::default())
+    }
+
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
         let mut ts = TokenStream::new();
-        ts.push(Token::QualifiedIdent {
-            schema: self.schema.clone(),
-            name: self.table.clone(),
-        });
+        match &self.function {
+            Some(call) => ts.append(&call.to_tokens_for_dialect(dialect)),
+            None => ts.push(Token::QualifiedIdent {
+                schema: self.schema.clone(),
+                name: self.table.clone(),
+            }),
+        };
         if let Some(alias) = &self.alias {
             ts.space()
                 .push(Token::As)
                 .space()
                 .push(Token::Ident(alias.clone()));
         }
+        if !self.columns.is_empty() {
+            ts.space().lparen();
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    ts.comma().space();
+                }
+                ts.push(Token::Ident(column.clone()));
+            }
+            ts.rparen();
+        }
         ts
     }
 }
@@ -123,7 +173,8 @@ impl Join {
         self.to_tokens_for_dialect(Dialect::default())
     }
 
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         match self.join_type {
@@ -135,7 +186,7 @@ impl Join {
         };
 
         ts.space().push(Token::Join).space();
-        ts.append(&self.table.to_tokens());
+        ts.append(&self.table.to_tokens_for_dialect(dialect));
 
         if let Some(on) = &self.on {
             ts.space().push(Token::On).space();
@@ -216,9 +267,21 @@ impl OrderByExpr {
 
     /// Convert to tokens for a specific dialect.
     ///
-    /// Skips NULLS FIRST/LAST for dialects that don't support it.
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
-        let mut ts = self.expr.to_tokens();
+    /// Dialects without NULLS FIRST/LAST get a leading
+    /// `CASE WHEN expr IS NULL THEN 0 ELSE 1 END` sort key instead.
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
+        let mut ts = TokenStream::new();
+
+        let nulls = self.nulls.filter(|_| !dialect.supports_nulls_ordering());
+        if let Some(nulls) = nulls {
+            let key = nulls_sort_key(&self.expr, nulls == NullsOrder::First);
+            ts.append(&key.to_tokens_for_dialect(dialect))
+                .comma()
+                .space();
+        }
+
+        ts.append(&self.expr.to_tokens_for_dialect(dialect));
 
         if let Some(dir) = &self.dir {
             ts.space().push(match dir {
@@ -227,16 +290,11 @@ impl OrderByExpr {
             });
         }
 
-        // Only emit NULLS FIRST/LAST if dialect supports it
-        if let Some(nulls) = &self.nulls {
-            if dialect.supports_nulls_ordering() {
-                ts.space().push(match nulls {
-                    NullsOrder::First => Token::NullsFirst,
-                    NullsOrder::Last => Token::NullsLast,
-                });
-            }
-            // For dialects without NULLS ordering support, we silently skip it.
-            // TODO: Consider emulating with CASE expressions for MySQL/older T-SQL
+        if let (None, Some(nulls)) = (nulls, &self.nulls) {
+            ts.space().push(match nulls {
+                NullsOrder::First => Token::NullsFirst,
+                NullsOrder::Last => Token::NullsLast,
+            });
         }
 
         ts
@@ -258,11 +316,395 @@ impl LimitOffset {
     /// Convert to token stream using dialect-specific pagination.
     ///
     /// Delegates to `SqlDialect::emit_limit_offset()` for the actual formatting.
-    pub fn to_tokens(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         dialect.emit_limit_offset(self.limit, self.offset)
     }
 }
 
+// =============================================================================
+// PIVOT / UNPIVOT
+// =============================================================================
+
+/// Alias of the FROM table's columns inside a native PIVOT or UNPIVOT.
+const PIVOT_SOURCE: &str = "pivot_source";
+
+/// Alias of the PIVOT or UNPIVOT result before it is renamed.
+const PIVOT_RESULT: &str = "pivot_result";
+
+/// A PIVOT of the FROM table: one row per distinct `group_by` combination and
+/// one column per listed value of `pivot_column`, holding the aggregate of
+/// `value_column` over the rows with that value.
+///
+/// ```ignore
+/// let query = Query::new()
+///     .select_star()
+///     .from(TableRef::new("sales"))
+///     .pivot(
+///         Pivot::new("SUM", "amount", "quarter")
+///             .group_by(vec!["region"])
+///             .value("Q1", "q1")
+///             .value("Q2", "q2"),
+///     );
+/// ```
+///
+/// Dialects with native PIVOT get `PIVOT (SUM(amount) FOR quarter IN (...))`;
+/// others get `SUM(CASE WHEN quarter = 'Q1' THEN amount END) AS q1`. Either
+/// way the result has the `group_by` columns followed by the value aliases,
+/// and takes the FROM table's name unless given an alias.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use = "builders have no effect until used"]
+pub struct Pivot {
+    /// Aggregate function name, e.g. `SUM`.
+    pub aggregate: String,
+    pub value_column: String,
+    pub pivot_column: String,
+    /// Pivot values with the output column each one becomes.
+    pub values: Vec<(Expr, String)>,
+    pub group_by: Vec<String>,
+    pub alias: Option<String>,
+}
+
+impl Pivot {
+    pub fn new(aggregate: &str, value_column: &str, pivot_column: &str) -> Self {
+        Self {
+            aggregate: aggregate.into(),
+            value_column: value_column.into(),
+            pivot_column: pivot_column.into(),
+            values: Vec::new(),
+            group_by: Vec::new(),
+            alias: None,
+        }
+    }
+
+    /// Add a pivot value and the name of the column it becomes.
+    pub fn value(mut self, value: impl Into<Expr>, alias: &str) -> Self {
+        self.values.push((value.into(), alias.into()));
+        self
+    }
+
+    /// Set the columns that identify an output row.
+    pub fn group_by(mut self, columns: Vec<&str>) -> Self {
+        self.group_by = columns.into_iter().map(String::from).collect();
+        self
+    }
+
+    pub fn with_alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    /// Convert to a derived table over `source`.
+    fn to_tokens(&self, source: &TableRef, dialect: DialectTarget) -> TokenStream {
+        let mut ts = TokenStream::new();
+        ts.lparen();
+
+        if !dialect.supports_native_pivot() {
+            let mut select: Vec<SelectExpr> = self.group_by.iter().map(|c| col(c).into()).collect();
+            for (value, alias) in &self.values {
+                let when = Expr::Case {
+                    operand: None,
+                    when_clauses: vec![(
+                        col(&self.pivot_column).eq(value.clone()),
+                        col(&self.value_column),
+                    )],
+                    else_clause: None,
+                };
+                select.push(SelectExpr::new(func(&self.aggregate, vec![when])).with_alias(alias));
+            }
+            let query = Query::new()
+                .select(select)
+                .from(source.clone())
+                .group_by(self.group_by.iter().map(|c| col(c)).collect());
+            ts.append(&query.to_tokens_for_dialect(dialect));
+            ts.rparen().space().push(Token::As).space();
+            ts.push(Token::Ident(pivot_alias(&self.alias, source)));
+            return ts;
+        }
+
+        // Native PIVOT groups by every other column, so narrow the source first
+        let columns = self
+            .group_by
+            .iter()
+            .chain([&self.pivot_column, &self.value_column]);
+        ts.push(Token::Select)
+            .space()
+            .push(Token::Star)
+            .space()
+            .push(Token::From)
+            .space();
+        emit_pivot_source(&mut ts, columns, source);
+
+        ts.space().push(Token::Pivot).space().lparen();
+        ts.push(Token::FunctionName(self.aggregate.clone()))
+            .lparen()
+            .push(Token::Ident(self.value_column.clone()))
+            .rparen()
+            .space()
+            .push(Token::For)
+            .space()
+            .push(Token::Ident(self.pivot_column.clone()))
+            .space()
+            .push(Token::In)
+            .space()
+            .lparen();
+        for (i, (value, _)) in self.values.iter().enumerate() {
+            if i > 0 {
+                ts.comma().space();
+            }
+            ts.append(&pivot_value_tokens(value, dialect));
+        }
+        ts.rparen().rparen();
+        ts.space()
+            .push(Token::As)
+            .space()
+            .push(Token::Ident(PIVOT_RESULT.into()));
+
+        // Native column names vary by dialect, so rename by position
+        ts.rparen().space().push(Token::As).space();
+        ts.push(Token::Ident(pivot_alias(&self.alias, source)));
+        ts.space().lparen();
+        let names = self
+            .group_by
+            .iter()
+            .chain(self.values.iter().map(|(_, alias)| alias));
+        for (i, name) in names.enumerate() {
+            if i > 0 {
+                ts.comma().space();
+            }
+            ts.push(Token::Ident(name.clone()));
+        }
+        ts.rparen();
+        ts
+    }
+}
+
+/// An UNPIVOT of the FROM table: each of `columns` becomes a row holding the
+/// column's name in `name_column` and its value in `value_column`, next to
+/// the `keep` columns.
+///
+/// ```ignore
+/// let query = Query::new()
+///     .select_star()
+///     .from(TableRef::new("quarterly"))
+///     .unpivot(Unpivot::new("amount", "quarter", vec!["q1", "q2"]).keep(vec!["region"]));
+/// ```
+///
+/// Dialects with native UNPIVOT get `UNPIVOT (amount FOR quarter IN (q1, q2))`.
+/// Others get `CROSS JOIN LATERAL (VALUES ...)` where the dialect allows it,
+/// and one `UNION ALL` branch per column otherwise. Rows with a NULL value are
+/// dropped, as native UNPIVOT does.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use = "builders have no effect until used"]
+pub struct Unpivot {
+    pub value_column: String,
+    pub name_column: String,
+    pub columns: Vec<String>,
+    pub keep: Vec<String>,
+    pub alias: Option<String>,
+}
+
+impl Unpivot {
+    pub fn new(value_column: &str, name_column: &str, columns: Vec<&str>) -> Self {
+        Self {
+            value_column: value_column.into(),
+            name_column: name_column.into(),
+            columns: columns.into_iter().map(String::from).collect(),
+            keep: Vec::new(),
+            alias: None,
+        }
+    }
+
+    /// Set the columns carried through unchanged.
+    pub fn keep(mut self, columns: Vec<&str>) -> Self {
+        self.keep = columns.into_iter().map(String::from).collect();
+        self
+    }
+
+    pub fn with_alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    /// Convert to a derived table over `source`.
+    fn to_tokens(&self, source: &TableRef, dialect: DialectTarget) -> TokenStream {
+        let mut ts = TokenStream::new();
+        ts.lparen();
+
+        if dialect.supports_native_pivot() {
+            let outputs = self
+                .keep
+                .iter()
+                .chain([&self.name_column, &self.value_column])
+                .map(|c| SelectExpr::new(col(c)))
+                .collect();
+            Query::new().select(outputs).emit_select(&mut ts, dialect);
+            ts.newline().push(Token::From).space();
+            emit_pivot_source(&mut ts, self.keep.iter().chain(&self.columns), source);
+            ts.space().push(Token::Unpivot).space().lparen();
+            ts.push(Token::Ident(self.value_column.clone()))
+                .space()
+                .push(Token::For)
+                .space()
+                .push(Token::Ident(self.name_column.clone()))
+                .space()
+                .push(Token::In)
+                .space()
+                .lparen();
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    ts.comma().space();
+                }
+                ts.push(Token::Ident(column.clone()));
+            }
+            ts.rparen().rparen();
+            ts.space()
+                .push(Token::As)
+                .space()
+                .push(Token::Ident(PIVOT_RESULT.into()));
+        } else if dialect.supports_lateral_values() {
+            let table = source.alias.as_deref().unwrap_or(&source.table);
+            let mut select: Vec<SelectExpr> = self
+                .keep
+                .iter()
+                .map(|c| table_col(table, c).into())
+                .collect();
+            select.push(table_col(PIVOT_RESULT, &self.name_column).into());
+            select.push(table_col(PIVOT_RESULT, &self.value_column).into());
+            let mut query = Query::new()
+                .select(select)
+                .from(source.clone())
+                .to_tokens_for_dialect(dialect);
+            query
+                .newline()
+                .push(Token::Cross)
+                .space()
+                .push(Token::Join)
+                .space()
+                .push(Token::Lateral)
+                .space()
+                .lparen()
+                .push(Token::Values)
+                .space();
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    query.comma().space();
+                }
+                query.lparen();
+                query.append(&lit_str(column).to_tokens_for_dialect(dialect));
+                query.comma().space();
+                query.append(&table_col(table, column).to_tokens_for_dialect(dialect));
+                query.rparen();
+            }
+            query
+                .rparen()
+                .space()
+                .push(Token::As)
+                .space()
+                .push(Token::Ident(PIVOT_RESULT.into()))
+                .space()
+                .lparen()
+                .push(Token::Ident(self.name_column.clone()))
+                .comma()
+                .space()
+                .push(Token::Ident(self.value_column.clone()))
+                .rparen()
+                .newline()
+                .push(Token::Where)
+                .space();
+            let present = table_col(PIVOT_RESULT, &self.value_column).is_not_null();
+            query.append(&present.to_tokens_for_dialect(dialect));
+            ts.append(&query);
+        } else {
+            let branch = |column: &String| {
+                let mut select: Vec<SelectExpr> = self.keep.iter().map(|c| col(c).into()).collect();
+                select.push(SelectExpr::new(lit_str(column)).with_alias(&self.name_column));
+                select.push(SelectExpr::new(col(column)).with_alias(&self.value_column));
+                Query::new()
+                    .select(select)
+                    .from(source.clone())
+                    .filter(col(column).is_not_null())
+            };
+            let mut branches = self.columns.iter().map(branch);
+            match (branches.next(), branches.next()) {
+                (Some(first), Some(second)) => {
+                    let union = branches.fold(first.union_all(second), |union, next| {
+                        union.chain(SetOpType::Union, true, next)
+                    });
+                    ts.append(&union.to_tokens_for_dialect(dialect));
+                }
+                (Some(only), None) => {
+                    ts.append(&only.to_tokens_for_dialect(dialect));
+                }
+                _ => {}
+            }
+        }
+
+        ts.rparen().space().push(Token::As).space();
+        ts.push(Token::Ident(pivot_alias(&self.alias, source)));
+        ts
+    }
+}
+
+/// Name of a pivoted table: its alias, else the FROM table's alias or name.
+fn pivot_alias(alias: &Option<String>, source: &TableRef) -> String {
+    alias
+        .as_ref()
+        .or(source.alias.as_ref())
+        .unwrap_or(&source.table)
+        .clone()
+}
+
+/// Emit `(SELECT <columns> FROM <source>) AS pivot_source`.
+fn emit_pivot_source<'a>(
+    ts: &mut TokenStream,
+    columns: impl Iterator<Item = &'a String>,
+    source: &TableRef,
+) {
+    ts.lparen().push(Token::Select).space();
+    for (i, column) in columns.enumerate() {
+        if i > 0 {
+            ts.comma().space();
+        }
+        ts.push(Token::Ident(column.clone()));
+    }
+    ts.space().push(Token::From).space();
+    ts.append(&source.to_tokens());
+    ts.rparen()
+        .space()
+        .push(Token::As)
+        .space()
+        .push(Token::Ident(PIVOT_SOURCE.into()));
+}
+
+/// Tokens for one value of a native PIVOT's IN list.
+///
+/// T-SQL names the pivoted columns, so literals become bracketed identifiers.
+/// Elsewhere literals stay inline: the IN list is part of the query's shape.
+fn pivot_value_tokens(value: &Expr, dialect: DialectTarget) -> TokenStream {
+    let mut ts = TokenStream::new();
+    let Expr::Literal(lit) = value else {
+        return value.to_tokens_for_dialect(dialect);
+    };
+    let token = match lit {
+        Literal::Int(n) => Token::LitInt(*n),
+        Literal::Float(f) => Token::LitFloat(*f),
+        Literal::String(s) => Token::LitString(s.clone()),
+        Literal::Bool(b) => Token::LitBool(*b),
+        Literal::Null => Token::LitNull,
+    };
+    if dialect == Dialect::TSql {
+        let name = match lit {
+            Literal::String(s) => s.clone(),
+            _ => token.serialize(dialect),
+        };
+        ts.push(Token::Ident(name));
+    } else {
+        ts.push(Token::Inline(Box::new(token)));
+    }
+    ts
+}
+
 // =============================================================================
 // Set Operations (UNION, INTERSECT, EXCEPT)
 // =============================================================================
@@ -362,7 +804,8 @@ impl SetOperation {
     }
 
     /// Convert to tokens for a specific dialect.
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
 
         // Left query (wrapped in parens for clarity)
@@ -370,9 +813,7 @@ impl SetOperation {
             // Nested set operation - emit directly
             ts.append(&self.left.to_tokens_for_dialect(dialect));
         } else {
-            ts.lparen();
-            ts.append(&self.left.to_tokens_for_dialect(dialect));
-            ts.rparen();
+            Self::emit_operand(&mut ts, &self.left, dialect);
         }
 
         // Set operation keyword
@@ -388,17 +829,51 @@ impl SetOperation {
         ts.newline();
 
         // Right query
-        ts.lparen();
-        ts.append(&self.right.to_tokens_for_dialect(dialect));
-        ts.rparen();
+        Self::emit_operand(&mut ts, &self.right, dialect);
 
         ts
     }
 
+    /// Emit one member of the set operation.
+    ///
+    /// Without parenthesized members, a member with its own WITH, ORDER BY,
+    /// LIMIT or set operation is wrapped as `SELECT * FROM (...)`.
+    fn emit_operand(ts: &mut TokenStream, query: &Query, dialect: DialectTarget) {
+        if dialect.supports_parenthesized_set_operands() {
+            ts.lparen();
+            ts.append(&query.to_tokens_for_dialect(dialect));
+            ts.rparen();
+        } else if query.with.is_empty()
+            && query.order_by.is_empty()
+            && query.limit_offset.is_none()
+            && query.set_op.is_none()
+        {
+            ts.append(&query.to_tokens_for_dialect(dialect));
+        } else {
+            ts.push(Token::Select)
+                .space()
+                .push(Token::Star)
+                .space()
+                .push(Token::From)
+                .space()
+                .lparen();
+            ts.append(&query.to_tokens_for_dialect(dialect));
+            ts.rparen();
+        }
+    }
+
     /// Generate SQL string for a specific dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens_for_dialect(dialect).serialize(dialect)
     }
+
+    /// Generate SQL with literal values as bind parameters.
+    pub fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        self.to_tokens_for_dialect(dialect)
+            .serialize_parameterized(dialect)
+    }
 }
 
 impl std::fmt::Display for SetOperation {
@@ -454,7 +929,8 @@ impl Cte {
         self.to_tokens_for_dialect(Dialect::default())
     }
 
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
         let mut ts = TokenStream::new();
         ts.push(Token::Ident(self.name.clone()));
 
@@ -486,6 +962,29 @@ impl Cte {
 // Query Builder
 // =============================================================================
 
+/// Errors from checking a query against a target dialect.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum QueryError {
+    #[error("{feature} is not supported by {dialect}")]
+    Unsupported {
+        feature: &'static str,
+        dialect: DialectTarget,
+    },
+}
+
+/// Result type for query validation.
+pub type QueryResult<T> = Result<T, QueryError>;
+
+/// Alias of the subquery that stands in for QUALIFY.
+const QUALIFY_SUBQUERY: &str = "qualified";
+
+/// Column carrying the QUALIFY condition out of that subquery.
+const QUALIFY_COLUMN: &str = "qualify_keep";
+
+/// Alias of the subquery holding emulated FULL OUTER JOIN rows that are
+/// grouped outside it.
+const FULL_JOIN_SUBQUERY: &str = "full_join";
+
 /// A SELECT query.
 #[derive(Debug, Clone, Default, PartialEq)]
 #[must_use = "Query has no effect until converted to SQL with to_sql() or to_tokens()"]
@@ -494,10 +993,18 @@ pub struct Query {
     pub select: Vec<SelectExpr>,
     pub distinct: bool,
     pub from: Option<TableRef>,
+    /// PIVOT applied to the FROM table.
+    pub pivot: Option<Pivot>,
+    /// UNPIVOT applied to the FROM table.
+    pub unpivot: Option<Unpivot>,
     pub joins: Vec<Join>,
     pub where_clause: Option<Expr>,
     pub group_by: Vec<Expr>,
     pub having: Option<Expr>,
+    /// QUALIFY condition (filter on window functions).
+    ///
+    /// Dialects without QUALIFY get a subquery filtered with WHERE.
+    pub qualify: Option<Expr>,
     pub order_by: Vec<OrderByExpr>,
     pub limit_offset: Option<LimitOffset>,
     /// Set operation (UNION, INTERSECT, EXCEPT) with another query.
@@ -510,6 +1017,40 @@ impl Query {
         Self::default()
     }
 
+    /// Parse SQL text written for `dialect` into a query.
+    ///
+    /// Fails with [`ParseError::Unsupported`](super::parse::ParseError::Unsupported)
+    /// for constructs the builder cannot represent.
+    pub fn parse(sql: &str, dialect: Dialect) -> ParseResult<Self> {
+        parse_query(sql, dialect)
+    }
+
+    /// Check that the query renders for a dialect without changing its result.
+    ///
+    /// Dialects without QUALIFY filter a subquery instead, and with `SELECT *`
+    /// that subquery's helper columns would be part of the result.
+    pub fn validate(&self, dialect: impl Into<DialectTarget>) -> QueryResult<()> {
+        let dialect = dialect.into();
+        let selects_star = self
+            .select
+            .iter()
+            .any(|s| matches!(s.expr, Expr::Star { .. }));
+        if self.qualify.is_some() && selects_star && !dialect.supports_qualify() {
+            return Err(QueryError::Unsupported {
+                feature: "QUALIFY with SELECT *",
+                dialect,
+            });
+        }
+        for cte in &self.with {
+            cte.query.validate(dialect)?;
+        }
+        if let Some(set_op) = &self.set_op {
+            set_op.left.validate(dialect)?;
+            set_op.right.validate(dialect)?;
+        }
+        Ok(())
+    }
+
     /// Add a CTE (WITH clause).
     pub fn with_cte(mut self, cte: Cte) -> Self {
         self.with.push(cte);
@@ -540,6 +1081,20 @@ impl Query {
         self
     }
 
+    /// PIVOT the FROM table (replaces any UNPIVOT).
+    pub fn pivot(mut self, pivot: Pivot) -> Self {
+        self.pivot = Some(pivot);
+        self.unpivot = None;
+        self
+    }
+
+    /// UNPIVOT the FROM table (replaces any PIVOT).
+    pub fn unpivot(mut self, unpivot: Unpivot) -> Self {
+        self.unpivot = Some(unpivot);
+        self.pivot = None;
+        self
+    }
+
     /// Add a JOIN.
     pub fn join(mut self, join_type: JoinType, table: TableRef, on: Expr) -> Self {
         self.joins.push(Join {
@@ -601,6 +1156,15 @@ impl Query {
         self
     }
 
+    /// Set the QUALIFY clause (ANDed with existing conditions).
+    pub fn qualify(mut self, condition: Expr) -> Self {
+        self.qualify = Some(match self.qualify {
+            Some(existing) => existing.and(condition),
+            None => condition,
+        });
+        self
+    }
+
     /// Set the ORDER BY clause.
     pub fn order_by(mut self, exprs: Vec<OrderByExpr>) -> Self {
         self.order_by = exprs;
@@ -669,60 +1233,77 @@ impl Query {
         SetOperation::except_all(self, other)
     }
 
+    /// Tables this query reads, including those in CTE bodies and set operation
+    /// branches. References to the query's own CTEs are excluded.
+    pub fn source_tables(&self) -> Vec<&TableRef> {
+        fn collect<'q>(query: &'q Query, ctes: &mut Vec<&'q str>, out: &mut Vec<&'q TableRef>) {
+            ctes.extend(query.with.iter().map(|cte| cte.name.as_str()));
+            for cte in &query.with {
+                collect(&cte.query, ctes, out);
+            }
+            if let Some(set_op) = &query.set_op {
+                collect(&set_op.left, ctes, out);
+                collect(&set_op.right, ctes, out);
+            }
+            for table in query.from.iter().chain(query.joins.iter().map(|j| &j.table)) {
+                if table.schema.is_none() && ctes.contains(&table.table.as_str()) {
+                    continue;
+                }
+                out.push(table);
+            }
+        }
+
+        let mut tables = Vec::new();
+        collect(self, &mut Vec::new(), &mut tables);
+        tables
+    }
+
     /// Convert to token stream (dialect-agnostic, uses DuckDB for LIMIT/OFFSET).
     pub fn to_tokens(&self) -> TokenStream {
         self.to_tokens_for_dialect(Dialect::DuckDb)
     }
 
     /// Convert to token stream for a specific dialect.
-    pub fn to_tokens_for_dialect(&self, dialect: Dialect) -> TokenStream {
+    pub fn to_tokens_for_dialect(&self, dialect: impl Into<DialectTarget>) -> TokenStream {
+        let dialect = dialect.into();
+        let mut ts = TokenStream::new();
+        self.emit_with(&mut ts, dialect);
+
         // If this query is a container for a set operation, emit that instead
         if let Some(ref set_op) = self.set_op {
-            return set_op.to_tokens_for_dialect(dialect);
+            ts.append(&set_op.to_tokens_for_dialect(dialect));
+            self.emit_order_limit(&mut ts, dialect);
+            return ts;
         }
 
-        let mut ts = TokenStream::new();
-
-        // WITH clause
-        if !self.with.is_empty() {
-            ts.push(Token::With);
-
-            // Emit RECURSIVE keyword if any CTE is recursive AND dialect supports it
-            let has_recursive = self.with.iter().any(|cte| cte.recursive);
-            if has_recursive && dialect.emit_recursive_keyword() {
-                ts.space().push(Token::Recursive);
+        if let Some(qualify) = self.qualify.as_ref() {
+            if !dialect.supports_qualify() {
+                self.emit_qualify_subquery(&mut ts, qualify, dialect);
+                return ts;
             }
-
-            ts.space();
-            for (i, cte) in self.with.iter().enumerate() {
-                if i > 0 {
-                    ts.comma().newline();
-                }
-                ts.append(&cte.to_tokens_for_dialect(dialect));
-            }
-            ts.newline();
         }
 
-        // SELECT
-        ts.push(Token::Select);
-        if self.distinct {
-            ts.space().push(Token::Distinct);
-        }
-
-        // Columns
-        for (i, select_expr) in self.select.iter().enumerate() {
-            if i == 0 {
-                ts.newline().indent(1);
-            } else {
-                ts.comma().newline().indent(1);
+        if !dialect.supports_full_outer_join() {
+            if self.groups_full_join() {
+                self.emit_grouped_full_join(&mut ts, dialect);
+                return ts;
+            }
+            if let Some(emulated) = self.emulate_full_join() {
+                ts.append(&emulated.to_tokens_for_dialect(dialect));
+                return ts;
             }
-            ts.append(&select_expr.to_tokens_for_dialect(dialect));
         }
 
+        self.emit_select(&mut ts, dialect);
+
         // FROM
         if let Some(from) = &self.from {
             ts.newline().push(Token::From).space();
-            ts.append(&from.to_tokens());
+            match (&self.pivot, &self.unpivot) {
+                (Some(pivot), _) => ts.append(&pivot.to_tokens(from, dialect)),
+                (None, Some(unpivot)) => ts.append(&unpivot.to_tokens(from, dialect)),
+                (None, None) => ts.append(&from.to_tokens_for_dialect(dialect)),
+            };
         }
 
         // JOINs
@@ -737,6 +1318,14 @@ impl Query {
             ts.append(&where_clause.to_tokens_for_dialect(dialect));
         }
 
+        self.emit_grouping(&mut ts, dialect);
+        self.emit_order_limit(&mut ts, dialect);
+
+        ts
+    }
+
+    /// Emit GROUP BY, HAVING and QUALIFY.
+    fn emit_grouping(&self, ts: &mut TokenStream, dialect: DialectTarget) {
         // GROUP BY
         if !self.group_by.is_empty() {
             ts.newline().push(Token::GroupBy).space();
@@ -754,7 +1343,377 @@ impl Query {
             ts.append(&having.to_tokens_for_dialect(dialect));
         }
 
-        // ORDER BY
+        // QUALIFY
+        if let Some(qualify) = &self.qualify {
+            ts.newline().push(Token::Qualify).space();
+            ts.append(&qualify.to_tokens_for_dialect(dialect));
+        }
+    }
+
+    /// Rewrite the last FULL OUTER JOIN as a LEFT JOIN plus the rows of the
+    /// joined table that match nothing before it:
+    ///
+    /// ```sql
+    /// SELECT ... FROM a LEFT JOIN b ON <on>
+    /// UNION ALL
+    /// SELECT ... FROM b LEFT JOIN a ON 1 = 0
+    /// WHERE NOT EXISTS (SELECT 1 FROM a WHERE <on>)
+    /// ```
+    ///
+    /// Earlier FULL OUTER JOINs are rewritten when the branches are emitted.
+    /// Returns `None` when there is nothing to rewrite, or when the query
+    /// groups, de-duplicates or pivots rows, which would differ across the
+    /// branches. The WITH clause is left for the caller to emit.
+    fn emulate_full_join(&self) -> Option<Query> {
+        let index = self
+            .joins
+            .iter()
+            .rposition(|j| j.join_type == JoinType::Full)?;
+        if self.groups_rows() || self.pivot.is_some() || self.unpivot.is_some() {
+            return None;
+        }
+        let full = &self.joins[index];
+        let on = full.on.clone()?;
+        let from = self.from.clone()?;
+
+        let mut matched = Query {
+            with: Vec::new(),
+            order_by: Vec::new(),
+            limit_offset: None,
+            ..self.clone()
+        };
+        matched.joins[index].join_type = JoinType::Left;
+
+        let preceding = Query {
+            select: vec![SelectExpr::new(lit_int(1))],
+            from: Some(from.clone()),
+            joins: self.joins[..index].to_vec(),
+            ..Default::default()
+        }
+        .filter(on);
+
+        let never = lit_int(1).eq(lit_int(0));
+        let mut unmatched = matched.clone().from(full.table.clone());
+        unmatched.joins = std::iter::once(&from)
+            .chain(self.joins[..index].iter().map(|j| &j.table))
+            .map(|table| Join {
+                join_type: JoinType::Left,
+                table: table.clone(),
+                on: Some(never.clone()),
+            })
+            .chain(self.joins[index + 1..].iter().cloned())
+            .collect();
+        unmatched = unmatched.filter(not_exists(preceding));
+
+        // ORDER BY on a compound select refers to output columns
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|o| {
+                let aliased = self
+                    .select
+                    .iter()
+                    .find(|s| s.expr == o.expr)
+                    .and_then(|s| s.alias.as_deref());
+                match aliased {
+                    Some(alias) => OrderByExpr {
+                        expr: col(alias),
+                        ..o.clone()
+                    },
+                    None => o.clone(),
+                }
+            })
+            .collect();
+
+        Some(Query {
+            order_by,
+            limit_offset: self.limit_offset.clone(),
+            set_op: Some(Box::new(SetOperation::union_all(matched, unmatched))),
+            ..Default::default()
+        })
+    }
+
+    /// Whether GROUP BY, HAVING, QUALIFY or DISTINCT work on the joined rows.
+    fn groups_rows(&self) -> bool {
+        !self.group_by.is_empty()
+            || self.having.is_some()
+            || self.qualify.is_some()
+            || self.distinct
+    }
+
+    /// Whether a FULL OUTER JOIN must be emulated beneath the grouping,
+    /// which `emit_grouped_full_join` handles.
+    fn groups_full_join(&self) -> bool {
+        self.groups_rows()
+            && self.pivot.is_none()
+            && self.unpivot.is_none()
+            && self.joins.iter().any(|j| j.join_type == JoinType::Full)
+    }
+
+    /// Emulate a FULL OUTER JOIN under grouping by building the joined rows
+    /// in a subquery and grouping outside it, so each group is formed once
+    /// rather than per UNION branch:
+    ///
+    /// ```sql
+    /// SELECT full_join_col_1 AS region, SUM(full_join_col_2)
+    /// FROM (
+    ///     SELECT c.region AS full_join_col_1, o.amount AS full_join_col_2
+    ///     FROM c FULL JOIN o ON ...    -- emulated as usual
+    /// ) AS full_join
+    /// GROUP BY full_join_col_1
+    /// ```
+    ///
+    /// Every column the grouping, ordering or select list refers to is carried
+    /// out of the subquery under a positional alias. Bare column selections
+    /// keep their names as aliases.
+    fn emit_grouped_full_join(&self, ts: &mut TokenStream, dialect: DialectTarget) {
+        let aliases: Vec<&str> = self
+            .select
+            .iter()
+            .filter_map(|s| s.alias.as_deref())
+            .collect();
+
+        // Columns of the joined tables, in order of first reference
+        let mut columns: Vec<Expr> = Vec::new();
+        let mut carry = |e: &Expr| {
+            match e {
+                // Select aliases are resolved by the outer query
+                Expr::Column {
+                    table: None,
+                    column,
+                } if aliases.contains(&column.as_str()) => {}
+                Expr::Column { .. } if !columns.contains(e) => columns.push(e.clone()),
+                _ => return None,
+            }
+            Some(e.clone())
+        };
+        let referenced = self
+            .select
+            .iter()
+            .map(|s| &s.expr)
+            .chain(&self.group_by)
+            .chain(&self.having)
+            .chain(&self.qualify)
+            .chain(self.order_by.iter().map(|o| &o.expr));
+        for expr in referenced {
+            expr.rewrite(&mut carry);
+        }
+
+        let column_alias = |i: usize| format!("full_join_col_{}", i + 1);
+        let mut rows = Query {
+            select: columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| SelectExpr::new(c.clone()).with_alias(&column_alias(i)))
+                .collect(),
+            from: self.from.clone(),
+            joins: self.joins.clone(),
+            where_clause: self.where_clause.clone(),
+            ..Default::default()
+        };
+        if rows.select.is_empty() {
+            rows.select.push(SelectExpr::new(lit_int(1)));
+        }
+
+        let outer_column = |e: &Expr| -> Expr {
+            e.rewrite(&mut |e| {
+                columns
+                    .iter()
+                    .position(|c| c == e)
+                    .map(|i| col(&column_alias(i)))
+            })
+        };
+        let outer = Query {
+            select: self
+                .select
+                .iter()
+                .map(|s| {
+                    let alias = match (&s.alias, &s.expr) {
+                        (None, Expr::Column { column, .. }) => Some(column.clone()),
+                        (alias, _) => alias.clone(),
+                    };
+                    SelectExpr {
+                        expr: outer_column(&s.expr),
+                        alias,
+                    }
+                })
+                .collect(),
+            distinct: self.distinct,
+            group_by: self.group_by.iter().map(outer_column).collect(),
+            having: self.having.as_ref().map(outer_column),
+            qualify: self.qualify.as_ref().map(outer_column),
+            order_by: self
+                .order_by
+                .iter()
+                .map(|o| OrderByExpr {
+                    expr: outer_column(&o.expr),
+                    ..o.clone()
+                })
+                .collect(),
+            limit_offset: self.limit_offset.clone(),
+            ..Default::default()
+        };
+
+        outer.emit_select(ts, dialect);
+        ts.newline().push(Token::From).space().lparen().newline();
+        ts.append(&rows.to_tokens_for_dialect(dialect));
+        ts.newline()
+            .rparen()
+            .space()
+            .push(Token::As)
+            .space()
+            .push(Token::Ident(FULL_JOIN_SUBQUERY.into()));
+        outer.emit_grouping(ts, dialect);
+        outer.emit_order_limit(ts, dialect);
+    }
+
+    /// Stand in for QUALIFY by filtering a subquery that carries the
+    /// condition as a column:
+    ///
+    /// ```sql
+    /// SELECT a, b FROM (
+    ///     SELECT a, b, CASE WHEN <qualify> THEN 1 ELSE 0 END AS qualify_keep FROM ...
+    /// ) AS qualified
+    /// WHERE qualify_keep = 1
+    /// ```
+    ///
+    /// Unnamed select items get positional aliases so the outer query can
+    /// refer to them, and are renamed back to their SQL text, the name SQLite,
+    /// MySQL and DuckDB give them. ORDER BY keys that are not selected ride
+    /// along as extra columns, which `SELECT *` would expose (see [`Query::validate`]).
+    fn emit_qualify_subquery(&self, ts: &mut TokenStream, qualify: &Expr, dialect: DialectTarget) {
+        let mut inner = Query {
+            with: Vec::new(),
+            distinct: false,
+            qualify: None,
+            order_by: Vec::new(),
+            limit_offset: None,
+            ..self.clone()
+        };
+
+        let mut outputs = Vec::new();
+        let mut names = Vec::new();
+        for (i, item) in inner.select.iter_mut().enumerate() {
+            let (output, name) = match (&item.alias, &item.expr) {
+                (Some(alias), _) => (col(alias), None),
+                (None, Expr::Column { column, .. }) => (col(column), None),
+                (None, Expr::Star { .. }) => (crate::expr::star(), None),
+                (None, expr) => {
+                    let name = expr.to_tokens_for_dialect(dialect).serialize(dialect);
+                    let alias = format!("qualify_col_{}", i + 1);
+                    item.alias = Some(alias.clone());
+                    (col(&alias), Some(name))
+                }
+            };
+            outputs.push(output);
+            names.push(name);
+        }
+
+        let order_by = self
+            .order_by
+            .iter()
+            .enumerate()
+            .map(|(i, o)| {
+                let selected = self.select.iter().position(|s| s.expr == o.expr);
+                let expr = match (selected, &o.expr) {
+                    (Some(pos), _) => outputs[pos].clone(),
+                    (None, Expr::Column { column, .. }) if outputs.contains(&col(column)) => {
+                        col(column)
+                    }
+                    (None, _) => {
+                        let alias = format!("qualify_order_{}", i + 1);
+                        inner
+                            .select
+                            .push(SelectExpr::new(o.expr.clone()).with_alias(&alias));
+                        col(&alias)
+                    }
+                };
+                OrderByExpr { expr, ..o.clone() }
+            })
+            .collect();
+
+        let keep = Expr::Case {
+            operand: None,
+            when_clauses: vec![(qualify.clone(), lit_int(1))],
+            else_clause: Some(Box::new(lit_int(0))),
+        };
+        inner
+            .select
+            .push(SelectExpr::new(keep).with_alias(QUALIFY_COLUMN));
+
+        let outer = Query {
+            select: outputs
+                .into_iter()
+                .zip(names)
+                .map(|(output, name)| match name {
+                    Some(name) => SelectExpr::new(output).with_alias(&name),
+                    None => SelectExpr::new(output),
+                })
+                .collect(),
+            distinct: self.distinct,
+            order_by,
+            limit_offset: self.limit_offset.clone(),
+            ..Default::default()
+        };
+        outer.emit_select(ts, dialect);
+        ts.newline().push(Token::From).space().lparen().newline();
+        ts.append(&inner.to_tokens_for_dialect(dialect));
+        ts.newline()
+            .rparen()
+            .space()
+            .push(Token::As)
+            .space()
+            .push(Token::Ident(QUALIFY_SUBQUERY.into()));
+        ts.newline().push(Token::Where).space();
+        ts.append(
+            &col(QUALIFY_COLUMN)
+                .eq(lit_int(1))
+                .to_tokens_for_dialect(dialect),
+        );
+        outer.emit_order_limit(ts, dialect);
+    }
+
+    /// Emit SELECT [DISTINCT] and the select list.
+    fn emit_select(&self, ts: &mut TokenStream, dialect: DialectTarget) {
+        ts.push(Token::Select);
+        if self.distinct {
+            ts.space().push(Token::Distinct);
+        }
+
+        for (i, select_expr) in self.select.iter().enumerate() {
+            if i == 0 {
+                ts.newline().indent(1);
+            } else {
+                ts.comma().newline().indent(1);
+            }
+            ts.append(&select_expr.to_tokens_for_dialect(dialect));
+        }
+    }
+
+    /// Emit the WITH clause, if any.
+    fn emit_with(&self, ts: &mut TokenStream, dialect: DialectTarget) {
+        if !self.with.is_empty() {
+            ts.push(Token::With);
+
+            // Emit RECURSIVE keyword if any CTE is recursive AND dialect supports it
+            let has_recursive = self.with.iter().any(|cte| cte.recursive);
+            if has_recursive && dialect.emit_recursive_keyword() {
+                ts.space().push(Token::Recursive);
+            }
+
+            ts.space();
+            for (i, cte) in self.with.iter().enumerate() {
+                if i > 0 {
+                    ts.comma().newline();
+                }
+                ts.append(&cte.to_tokens_for_dialect(dialect));
+            }
+            ts.newline();
+        }
+    }
+
+    /// Emit ORDER BY and LIMIT/OFFSET, if any.
+    fn emit_order_limit(&self, ts: &mut TokenStream, dialect: DialectTarget) {
         // Note: T-SQL requires ORDER BY for OFFSET FETCH syntax.
         // If ORDER BY is missing but we have LIMIT/OFFSET, emit ORDER BY (SELECT NULL).
         let needs_order_by_placeholder = dialect.requires_order_by_for_offset()
@@ -793,14 +1752,20 @@ impl Query {
             ts.newline();
             ts.append(&lo.to_tokens(dialect));
         }
-
-        ts
     }
 
     /// Generate SQL string for a specific dialect.
-    pub fn to_sql(&self, dialect: Dialect) -> String {
+    pub fn to_sql(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.to_tokens_for_dialect(dialect).serialize(dialect)
     }
+
+    /// Generate SQL with literal values as bind parameters.
+    pub fn to_sql_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        self.to_tokens_for_dialect(dialect)
+            .serialize_parameterized(dialect)
+    }
 }
 
 impl std::fmt::Display for Query {
@@ -819,7 +1784,13 @@ impl std::fmt::Display for Query {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sql::expr::{col, count_star, lit_int, sum, table_col};
+    use crate::sql::dialect::Version;
+    use crate::sql::expr::{
+        col, count_star, func, lit_bool, lit_float, lit_int, lit_str, row_number, sum, table_col,
+        WindowExt, WindowOrderBy,
+    };
+    use crate::sql::params::Param;
+    use crate::sql::test_utils::validate_sql;
 
     #[test]
     fn test_simple_select() {
@@ -905,6 +1876,59 @@ mod tests {
         assert!(sql.contains("ASC"));
     }
 
+    #[test]
+    fn test_nulls_ordering_emulated() {
+        let query = Query::new()
+            .select(vec![col("name")])
+            .from(TableRef::new("users"))
+            .order_by(vec![OrderByExpr::desc(col("age")).nulls_last()]);
+
+        let order_by = |dialect: DialectTarget| {
+            let sql = query.to_sql(dialect);
+            sql[sql.find("ORDER BY").unwrap()..].to_string()
+        };
+
+        assert_eq!(
+            order_by(Dialect::Postgres.into()),
+            "ORDER BY \"age\" DESC NULLS LAST"
+        );
+        assert_eq!(
+            order_by(Dialect::MySql.into()),
+            "ORDER BY CASE WHEN `age` IS NULL THEN 1 ELSE 0 END, `age` DESC"
+        );
+        assert_eq!(
+            order_by(Dialect::Sqlite.at(Version::new(3, 29))),
+            "ORDER BY CASE WHEN \"age\" IS NULL THEN 1 ELSE 0 END, \"age\" DESC"
+        );
+        assert_eq!(
+            order_by(Dialect::Sqlite.at(Version::new(3, 30))),
+            "ORDER BY \"age\" DESC NULLS LAST"
+        );
+    }
+
+    #[test]
+    fn test_nulls_ordering_emulation_executes() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (name TEXT, age INTEGER);
+             INSERT INTO users VALUES ('a', 30), ('b', NULL), ('c', 20);",
+        )
+        .unwrap();
+        let names = |order: OrderByExpr| {
+            let sql = Query::new()
+                .select(vec![col("name")])
+                .from(TableRef::new("users"))
+                .order_by(vec![order])
+                .to_sql(Dialect::Sqlite.at(Version::new(3, 29)));
+            let mut stmt = conn.prepare(&sql).unwrap();
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0)).unwrap();
+            rows.collect::<Result<Vec<_>, _>>().unwrap().concat()
+        };
+
+        assert_eq!(names(OrderByExpr::asc(col("age")).nulls_last()), "cab");
+        assert_eq!(names(OrderByExpr::desc(col("age")).nulls_first()), "bac");
+    }
+
     #[test]
     fn test_limit_duckdb() {
         let query = Query::new()
@@ -951,6 +1975,411 @@ mod tests {
         assert!(sql.contains("FETCH NEXT 10 ROWS ONLY"));
     }
 
+    const DIALECTS: [Dialect; 11] = [
+        Dialect::DuckDb,
+        Dialect::Postgres,
+        Dialect::TSql,
+        Dialect::MySql,
+        Dialect::Snowflake,
+        Dialect::BigQuery,
+        Dialect::Redshift,
+        Dialect::Databricks,
+        Dialect::Sqlite,
+        Dialect::ClickHouse,
+        Dialect::Trino,
+    ];
+
+    fn sqlite_rows(conn: &rusqlite::Connection, sql: &str) -> Vec<String> {
+        let mut stmt = conn.prepare(sql).unwrap();
+        let width = stmt.column_count();
+        let rows = stmt
+            .query_map([], |row| {
+                let values = (0..width)
+                    .map(|i| match row.get_ref(i)? {
+                        rusqlite::types::ValueRef::Null => Ok("NULL".to_string()),
+                        rusqlite::types::ValueRef::Integer(n) => Ok(n.to_string()),
+                        rusqlite::types::ValueRef::Text(t) => {
+                            Ok(String::from_utf8_lossy(t).into_owned())
+                        }
+                        other => Ok(format!("{:?}", other)),
+                    })
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(values.join("|"))
+            })
+            .unwrap();
+        rows.collect::<Result<Vec<_>, _>>().unwrap()
+    }
+
+    fn pivot_query() -> Query {
+        Query::new()
+            .select(vec![col("region"), col("q1"), col("q2")])
+            .from(TableRef::new("sales"))
+            .pivot(
+                Pivot::new("SUM", "amount", "quarter")
+                    .group_by(vec!["region"])
+                    .value("Q1", "q1")
+                    .value("Q2", "q2"),
+            )
+            .order_by(vec![OrderByExpr::asc(col("region"))])
+    }
+
+    #[test]
+    fn test_pivot() {
+        for dialect in DIALECTS {
+            let sql = pivot_query().to_sql(dialect);
+            if dialect.supports_native_pivot() {
+                assert!(sql.contains(" PIVOT ("), "{:?}: {}", dialect, sql);
+            } else {
+                assert!(sql.contains("SUM(CASE WHEN "), "{:?}: {}", dialect, sql);
+                assert!(sql.contains("GROUP BY"), "{:?}: {}", dialect, sql);
+            }
+            validate_sql(&sql, dialect).unwrap();
+        }
+
+        let tsql = pivot_query().to_sql(Dialect::TSql);
+        assert!(tsql.contains("FOR [quarter] IN ([Q1], [Q2])) AS [pivot_result]"));
+        assert!(tsql.contains("AS [sales] ([region], [q1], [q2])"));
+
+        let snowflake = pivot_query().to_sql_parameterized(Dialect::Snowflake);
+        assert!(snowflake.sql.contains("IN ('Q1', 'Q2')"));
+        assert!(snowflake.params.is_empty());
+
+        let postgres = pivot_query().to_sql(Dialect::Postgres);
+        assert!(
+            postgres.contains("SUM(CASE WHEN \"quarter\" = 'Q1' THEN \"amount\" END) AS \"q1\"")
+        );
+    }
+
+    #[test]
+    fn test_pivot_emulation_executes() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sales (region TEXT, quarter TEXT, amount INTEGER, note TEXT);
+             INSERT INTO sales VALUES
+                 ('east', 'Q1', 10, 'a'), ('east', 'Q1', 5, 'b'), ('east', 'Q2', 7, NULL),
+                 ('west', 'Q2', 3, NULL), ('west', 'Q3', 9, NULL);",
+        )
+        .unwrap();
+
+        let sql = pivot_query().to_sql(Dialect::Sqlite);
+        assert_eq!(sqlite_rows(&conn, &sql), vec!["east|15|7", "west|NULL|3"]);
+    }
+
+    fn unpivot_query() -> Query {
+        Query::new()
+            .select(vec![col("region"), col("quarter"), col("amount")])
+            .from(TableRef::new("quarterly").with_alias("t"))
+            .unpivot(Unpivot::new("amount", "quarter", vec!["q1", "q2"]).keep(vec!["region"]))
+            .order_by(vec![
+                OrderByExpr::asc(col("region")),
+                OrderByExpr::asc(col("quarter")),
+            ])
+    }
+
+    #[test]
+    fn test_unpivot() {
+        for dialect in DIALECTS {
+            let sql = unpivot_query().to_sql(dialect);
+            if dialect.supports_native_pivot() {
+                assert!(sql.contains(" UNPIVOT ("), "{:?}: {}", dialect, sql);
+            } else if dialect.supports_lateral_values() {
+                assert!(
+                    sql.contains("CROSS JOIN LATERAL (VALUES "),
+                    "{:?}: {}",
+                    dialect,
+                    sql
+                );
+            } else {
+                assert!(sql.contains("UNION ALL"), "{:?}: {}", dialect, sql);
+            }
+            validate_sql(&sql, dialect).unwrap();
+        }
+
+        let tsql = unpivot_query().to_sql(Dialect::TSql);
+        assert!(tsql.contains(
+            "FROM (SELECT [region], [q1], [q2] FROM [quarterly] AS [t]) AS [pivot_source] \
+             UNPIVOT ([amount] FOR [quarter] IN ([q1], [q2])) AS [pivot_result]) AS [t]"
+        ));
+
+        let postgres = unpivot_query().to_sql(Dialect::Postgres);
+        assert!(postgres.contains(
+            "CROSS JOIN LATERAL (VALUES ('q1', \"t\".\"q1\"), ('q2', \"t\".\"q2\")) \
+             AS \"pivot_result\" (\"quarter\", \"amount\")"
+        ));
+    }
+
+    #[test]
+    fn test_unpivot_emulation_executes() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE quarterly (region TEXT, q1 INTEGER, q2 INTEGER);
+             INSERT INTO quarterly VALUES ('east', 15, 7), ('west', NULL, 3);",
+        )
+        .unwrap();
+
+        let sql = unpivot_query().to_sql(Dialect::Sqlite);
+        assert_eq!(
+            sqlite_rows(&conn, &sql),
+            vec!["east|q1|15", "east|q2|7", "west|q2|3"]
+        );
+    }
+
+    fn qualify_query() -> Query {
+        let rank = row_number()
+            .over()
+            .partition_by(vec![col("region")])
+            .order_by(vec![WindowOrderBy::desc(col("amount"))])
+            .build();
+        Query::new()
+            .select(vec![
+                SelectExpr::from(col("region")),
+                SelectExpr::from(col("amount")),
+                SelectExpr::new(col("amount").mul(lit_int(2))),
+            ])
+            .from(TableRef::new("sales"))
+            .qualify(rank.eq(lit_int(1)))
+            .order_by(vec![OrderByExpr::asc(col("quarter"))])
+    }
+
+    #[test]
+    fn test_qualify() {
+        for dialect in DIALECTS {
+            let sql = qualify_query().to_sql(dialect);
+            if dialect.supports_qualify() {
+                assert!(
+                    sql.contains("\nQUALIFY ROW_NUMBER() OVER"),
+                    "{:?}: {}",
+                    dialect,
+                    sql
+                );
+            } else {
+                assert!(
+                    !sql.contains("QUALIFY ROW_NUMBER"),
+                    "{:?}: {}",
+                    dialect,
+                    sql
+                );
+                assert!(sql.contains("WHERE"), "{:?}: {}", dialect, sql);
+            }
+            validate_sql(&sql, dialect).unwrap();
+        }
+
+        let postgres = qualify_query().to_sql(Dialect::Postgres);
+        assert!(postgres.starts_with(
+            "SELECT\n  \"region\",\n  \"amount\",\n  \"qualify_col_3\" AS \"\"\"amount\"\" * 2\"\n"
+        ));
+        assert!(postgres.contains("\"quarter\" AS \"qualify_order_1\""));
+        assert!(postgres.contains(
+            "CASE WHEN ROW_NUMBER() OVER (PARTITION BY \"region\" ORDER BY \"amount\" DESC) = 1 THEN 1 ELSE 0 END AS \"qualify_keep\""
+        ));
+        assert!(postgres.ends_with(
+            ") AS \"qualified\"\nWHERE \"qualify_keep\" = 1\nORDER BY \"qualify_order_1\" ASC"
+        ));
+    }
+
+    #[test]
+    fn test_qualify_emulation_executes() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sales (region TEXT, quarter TEXT, amount INTEGER);
+             INSERT INTO sales VALUES
+                 ('east', 'Q3', 10), ('east', 'Q1', 15), ('west', 'Q2', 3), ('west', 'Q4', 2);",
+        )
+        .unwrap();
+
+        let sql = qualify_query().to_sql(Dialect::Sqlite);
+        assert_eq!(sqlite_rows(&conn, &sql), vec!["east|15|30", "west|3|6"]);
+
+        // The emulation keeps the result's column names
+        let columns = |query: Query| -> Vec<String> {
+            let stmt = conn.prepare(&query.to_sql(Dialect::Sqlite)).unwrap();
+            stmt.column_names().into_iter().map(String::from).collect()
+        };
+        let unfiltered = Query {
+            qualify: None,
+            ..qualify_query()
+        };
+        assert_eq!(columns(qualify_query()), columns(unfiltered));
+        assert_eq!(
+            columns(qualify_query()),
+            vec!["region", "amount", "\"amount\" * 2"]
+        );
+    }
+
+    #[test]
+    fn test_qualify_with_star_needs_native_qualify() {
+        let query = Query::new()
+            .select(vec![crate::expr::star()])
+            .from(TableRef::new("sales"))
+            .qualify(
+                row_number()
+                    .over()
+                    .partition_by(vec![col("region")])
+                    .build()
+                    .eq(lit_int(1)),
+            );
+        assert!(query.validate(Dialect::Snowflake).is_ok());
+        assert_eq!(
+            query.validate(Dialect::Postgres),
+            Err(QueryError::Unsupported {
+                feature: "QUALIFY with SELECT *",
+                dialect: Dialect::Postgres.into(),
+            })
+        );
+        assert!(qualify_query().validate(Dialect::Postgres).is_ok());
+    }
+
+    fn parameterized_query() -> Query {
+        Query::new()
+            .select(vec![col("name")])
+            .from(TableRef::new("users"))
+            .filter(
+                col("status")
+                    .eq(lit_str("active"))
+                    .and(col("score").gt(lit_float(0.5)))
+                    .and(col("verified").eq(lit_bool(true)))
+                    .and(col("deleted_at").is_null()),
+            )
+            .order_by(vec![OrderByExpr::asc(col("name"))])
+            .limit(10)
+    }
+
+    #[test]
+    fn test_to_sql_parameterized() {
+        let query = parameterized_query();
+        let expected_params = vec![
+            Param::String("active".into()),
+            Param::Float(0.5),
+            Param::Bool(true),
+            Param::Int(10),
+        ];
+
+        let pg = query.to_sql_parameterized(Dialect::Postgres);
+        assert!(pg.sql.contains(
+            "\"status\" = $1 AND \"score\" > $2::NUMERIC AND \"verified\" = $3::BOOLEAN AND \"deleted_at\" IS NULL"
+        ));
+        assert!(pg.sql.contains("LIMIT $4::INTEGER"));
+        assert_eq!(pg.params, expected_params);
+
+        let tsql = query.to_sql_parameterized(Dialect::TSql);
+        assert!(tsql.sql.contains("[status] = @p1 AND [score] > @p2"));
+        assert!(tsql.sql.contains("FETCH NEXT @p5 ROWS ONLY"));
+        assert_eq!(tsql.params[3], Param::Int(0));
+
+        let mysql = query.to_sql_parameterized(Dialect::MySql);
+        assert!(mysql.sql.contains("`status` = ? AND `score` > ?"));
+        assert_eq!(mysql.params, expected_params);
+
+        let snowflake = query.to_sql_parameterized(Dialect::Snowflake);
+        assert!(snowflake.sql.contains("\"status\" = :1 AND \"score\" > :2"));
+
+        assert_eq!(
+            pg.args(),
+            vec![
+                serde_json::json!("active"),
+                serde_json::json!(0.5),
+                serde_json::json!(true),
+                serde_json::json!(10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_sql_parameterized_keeps_structure_inline() {
+        let query = Query::new()
+            .select(vec![func("DATE_TRUNC", vec![lit_str("month"), col("d")])])
+            .from(TableRef::new("events"))
+            .filter(col("name").like_escape(lit_str("a!%"), '!'));
+
+        let pg = query.to_sql_parameterized(Dialect::Postgres);
+        assert!(pg.sql.contains("DATE_TRUNC('month', \"d\")"), "{}", pg.sql);
+        assert!(pg.sql.contains("LIKE $1 ESCAPE '!'"), "{}", pg.sql);
+        assert_eq!(pg.params, vec![Param::String("a!%".into())]);
+    }
+
+    #[test]
+    fn test_to_sql_parameterized_repeats_grouped_literals() {
+        use crate::sql::expr::ExprExt;
+
+        // Grouped expression repeated in SELECT and GROUP BY
+        let bucket = Expr::Case {
+            operand: None,
+            when_clauses: vec![(
+                col("region").in_list(vec![lit_str("east"), lit_str("west")]),
+                col("region"),
+            )],
+            else_clause: Some(Box::new(lit_str("Other"))),
+        };
+        let query = Query::new()
+            .select(vec![bucket.clone(), sum(col("amount"))])
+            .from(TableRef::new("sales"))
+            .group_by(vec![bucket]);
+
+        let pg = query.to_sql_parameterized(Dialect::Postgres);
+        assert_eq!(pg.sql.matches("$3").count(), 2, "{}", pg.sql);
+        assert!(!pg.sql.contains("$4"), "{}", pg.sql);
+        assert_eq!(
+            pg.params,
+            vec![
+                Param::String("east".into()),
+                Param::String("west".into()),
+                Param::String("Other".into()),
+            ]
+        );
+
+        // Positional placeholders can't repeat, so repeated values stay inline
+        let mysql = query.to_sql_parameterized(Dialect::MySql);
+        assert_eq!(mysql.sql, query.to_sql(Dialect::MySql));
+        assert!(mysql.params.is_empty());
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sales (region TEXT, amount INTEGER);
+             INSERT INTO sales VALUES ('east', 1), ('north', 2), ('south', 3), ('west', 4);",
+        )
+        .unwrap();
+        let sqlite = query.to_sql_parameterized(Dialect::Sqlite);
+        let mut rows = sqlite_rows(&conn, &sqlite.sql);
+        rows.sort();
+        assert_eq!(rows, vec!["Other|5", "east|1", "west|4"]);
+    }
+
+    #[test]
+    fn test_to_sql_parameterized_executes() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (name TEXT, status TEXT, score REAL, verified INTEGER, deleted_at TEXT);
+             INSERT INTO users VALUES
+                 ('a', 'active', 0.9, 1, NULL),
+                 ('b', 'active', 0.1, 1, NULL),
+                 ('c', 'active', 0.7, 0, NULL),
+                 ('d', 'active', 0.8, 1, NULL),
+                 ('e', 'inactive', 0.9, 1, NULL);",
+        )
+        .unwrap();
+
+        let sql = parameterized_query().to_sql_parameterized(Dialect::Sqlite);
+        let params: Vec<Box<dyn rusqlite::ToSql>> = sql
+            .params
+            .iter()
+            .map(|p| -> Box<dyn rusqlite::ToSql> {
+                match p {
+                    Param::Int(n) => Box::new(*n),
+                    Param::Float(f) => Box::new(*f),
+                    Param::String(s) => Box::new(s.clone()),
+                    Param::Bool(b) => Box::new(*b),
+                }
+            })
+            .collect();
+        let mut stmt = conn.prepare(&sql.sql).unwrap();
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                row.get::<_, String>(0)
+            })
+            .unwrap();
+        assert_eq!(rows.collect::<Result<Vec<_>, _>>().unwrap().concat(), "ad");
+    }
+
     #[test]
     fn test_distinct() {
         let query = Query::new()
@@ -984,6 +2413,19 @@ mod tests {
         assert!(sql.contains("AS"));
     }
 
+    #[test]
+    fn test_table_function() {
+        let series = func("sequence", vec![lit_int(1), lit_int(3)]);
+        let query = Query::new()
+            .select(vec![col("n")])
+            .from(TableRef::function(func("UNNEST", vec![series]), "t").with_columns(vec!["n"]));
+
+        assert_eq!(
+            query.to_sql(Dialect::Trino),
+            "SELECT\n  \"n\"\nFROM UNNEST(SEQUENCE(1, 3)) AS \"t\" (\"n\")"
+        );
+    }
+
     #[test]
     fn test_aliased_columns() {
         let query = Query::new()
@@ -4,7 +4,8 @@
 //! using sqlparser-rs for roundtrip validation.
 
 use sqlparser::dialect::{
-    DuckDbDialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SnowflakeDialect,
+    ClickHouseDialect, DuckDbDialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+    SQLiteDialect, SnowflakeDialect,
 };
 use sqlparser::parser::Parser;
 
@@ -34,6 +35,9 @@ pub fn validate_sql(sql: &str, dialect: Dialect) -> Result<(), String> {
         Dialect::BigQuery => Box::new(GenericDialect {}), // sqlparser has no BigQuery dialect
         Dialect::Redshift => Box::new(PostgreSqlDialect {}), // Redshift is Postgres-like
         Dialect::Databricks => Box::new(GenericDialect {}), // sqlparser has no Databricks dialect
+        Dialect::Sqlite => Box::new(SQLiteDialect {}),
+        Dialect::ClickHouse => Box::new(ClickHouseDialect {}),
+        Dialect::Trino => Box::new(GenericDialect {}), // sqlparser has no Trino dialect
     };
 
     Parser::parse_sql(&*parser_dialect, sql)
@@ -3,7 +3,8 @@
 //! Tokens are dialect-agnostic representations that serialize
 //! to dialect-specific strings.
 
-use super::dialect::{Dialect, SqlDialect};
+use super::dialect::{DialectTarget, SqlDialect};
+use super::params::{Param, ParameterizedSql};
 
 /// SQL Token - every possible element in a SQL statement.
 ///
@@ -27,6 +28,7 @@ pub enum Token {
     Full,
     Outer,
     Cross,
+    Lateral,
     GroupBy,
     Having,
     OrderBy,
@@ -72,6 +74,12 @@ pub enum Token {
     Preceding,
     Following,
     CurrentRow,
+    Qualify,
+
+    // === PIVOT Keywords ===
+    Pivot,
+    Unpivot,
+    For,
 
     // === DDL Keywords ===
     Create,
@@ -163,6 +171,10 @@ pub enum Token {
     LitBool(bool),
     /// NULL literal
     LitNull,
+    /// Literal that is part of the statement's structure (a date part name,
+    /// format string or frame offset) rather than a value, so it is never
+    /// replaced by a bind parameter.
+    Inline(Box<Token>),
 
     // === Function Names ===
     /// Function name - currently rendered as-is, but allows future dialect remapping
@@ -185,8 +197,46 @@ pub enum Token {
 }
 
 impl Token {
+    /// Whether this token is an SQL keyword (as opposed to punctuation,
+    /// whitespace, identifiers, literals, function names or raw SQL).
+    pub fn is_keyword(&self) -> bool {
+        !matches!(
+            self,
+            Token::Comma
+                | Token::Dot
+                | Token::Star
+                | Token::LParen
+                | Token::RParen
+                | Token::Eq
+                | Token::Ne
+                | Token::Lt
+                | Token::Gt
+                | Token::Lte
+                | Token::Gte
+                | Token::Plus
+                | Token::Minus
+                | Token::Mul
+                | Token::Div
+                | Token::Mod
+                | Token::Concat
+                | Token::Space
+                | Token::Newline
+                | Token::Indent(_)
+                | Token::Ident(_)
+                | Token::QualifiedIdent { .. }
+                | Token::LitInt(_)
+                | Token::LitFloat(_)
+                | Token::LitString(_)
+                | Token::LitBool(_)
+                | Token::Inline(_)
+                | Token::FunctionName(_)
+                | Token::Raw(_)
+        )
+    }
+
     /// Serialize this token to a string for the given dialect.
-    pub fn serialize(&self, dialect: Dialect) -> String {
+    pub fn serialize(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         match self {
             // Keywords
             Token::Select => "SELECT".into(),
@@ -204,6 +254,7 @@ impl Token {
             Token::Full => "FULL".into(),
             Token::Outer => "OUTER".into(),
             Token::Cross => "CROSS".into(),
+            Token::Lateral => "LATERAL".into(),
             Token::GroupBy => "GROUP BY".into(),
             Token::Having => "HAVING".into(),
             Token::OrderBy => "ORDER BY".into(),
@@ -249,6 +300,12 @@ impl Token {
             Token::Preceding => "PRECEDING".into(),
             Token::Following => "FOLLOWING".into(),
             Token::CurrentRow => "CURRENT ROW".into(),
+            Token::Qualify => "QUALIFY".into(),
+
+            // PIVOT keywords
+            Token::Pivot => "PIVOT".into(),
+            Token::Unpivot => "UNPIVOT".into(),
+            Token::For => "FOR".into(),
 
             // DDL keywords
             Token::Create => "CREATE".into(),
@@ -347,6 +404,7 @@ impl Token {
             Token::LitString(s) => dialect.quote_string(s),
             Token::LitBool(b) => dialect.format_bool(*b).into(),
             Token::LitNull => "NULL".into(),
+            Token::Inline(token) => token.serialize(dialect),
 
             // Function names with dialect-specific remapping
             Token::FunctionName(name) => match dialect.remap_function(name) {
@@ -390,11 +448,59 @@ impl TokenStream {
         self
     }
 
+    /// The tokens in emission order.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
     /// Serialize all tokens to a SQL string.
-    pub fn serialize(&self, dialect: Dialect) -> String {
+    pub fn serialize(&self, dialect: impl Into<DialectTarget>) -> String {
+        let dialect = dialect.into();
         self.tokens.iter().map(|t| t.serialize(dialect)).collect()
     }
 
+    /// Serialize with literal values replaced by the dialect's placeholders.
+    ///
+    /// `NULL` and [`Token::Inline`] literals stay in the SQL text. A value
+    /// used more than once (e.g. in a grouped expression repeated in
+    /// `GROUP BY`) must serialize the same way each time for the server to
+    /// match the expressions, so it reuses its placeholder where placeholders
+    /// are numbered and stays inline where they are positional.
+    pub fn serialize_parameterized(&self, dialect: impl Into<DialectTarget>) -> ParameterizedSql {
+        let dialect = dialect.into();
+        let literal = |token: &Token| match token {
+            Token::LitInt(n) => Some(Param::Int(*n)),
+            Token::LitFloat(f) => Some(Param::Float(*f)),
+            Token::LitString(s) => Some(Param::String(s.clone())),
+            Token::LitBool(b) => Some(Param::Bool(*b)),
+            _ => None,
+        };
+        let values: Vec<Param> = self.tokens.iter().filter_map(literal).collect();
+        let repeated = |param: &Param| values.iter().filter(|v| *v == param).count() > 1;
+
+        let mut sql = String::new();
+        let mut params: Vec<Param> = Vec::new();
+        for token in &self.tokens {
+            let Some(param) = literal(token) else {
+                sql.push_str(&token.serialize(dialect));
+                continue;
+            };
+            let index = match params.iter().position(|p| *p == param) {
+                Some(existing) if dialect.numbered_placeholders() => existing + 1,
+                _ if !dialect.numbered_placeholders() && repeated(&param) => {
+                    sql.push_str(&token.serialize(dialect));
+                    continue;
+                }
+                _ => {
+                    params.push(param.clone());
+                    params.len()
+                }
+            };
+            sql.push_str(&dialect.placeholder(index, &param));
+        }
+        ParameterizedSql { sql, params }
+    }
+
     // Convenience methods for common tokens
     pub fn space(&mut self) -> &mut Self {
         self.push(Token::Space)
@@ -419,6 +525,7 @@ impl TokenStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sql::dialect::Dialect;
 
     #[test]
     fn test_keyword_serialize() {
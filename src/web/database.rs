@@ -16,8 +16,18 @@ use crate::cache::{MetadataCache, SavedCredential};
 use crate::config::Settings;
 use crate::crypto;
 use crate::metadata::{
-    MetadataProvider, SchemaInfo, TableInfo, TableMetadata, WorkerMetadataProvider,
+    detect_scd_type, MetadataProvider, ModelBootstrapper, SchemaInfo,
+    TableClassification as BootstrapClassification, TableInfo, TableKind, TableMetadata,
+    WorkerMetadataProvider,
 };
+use crate::model::dimension::SCDType;
+use crate::model::emitter::{EmitConfig, LuaEmitter};
+use crate::model::types::{AggregationType, MaterializationStrategy};
+use crate::model::{
+    Cardinality, DimensionDefinition, FactDefinition, MeasureDefinition, Model, Relationship,
+    SourceEntity,
+};
+use crate::semantic::inference::{InferredRelationship, RelationshipSource};
 use crate::worker::WorkerClient;
 
 /// Supported database drivers for introspection.
@@ -1280,471 +1290,150 @@ fn classify_tables_internal(
     tables: &[TableMetadata],
     relationships: &[RelationshipSelection],
 ) -> Vec<TableClassification> {
-    // Build lookup maps
-    let table_map: std::collections::HashMap<String, &TableMetadata> = tables
-        .iter()
-        .map(|t| (t.name.to_lowercase(), t))
-        .collect();
-
-    // Build FK relationship lookup (from_table -> list of to_tables)
-    let fk_targets: std::collections::HashMap<String, Vec<String>> = relationships
-        .iter()
-        .fold(std::collections::HashMap::new(), |mut acc, rel| {
-            acc.entry(rel.from_table.to_lowercase())
-                .or_default()
-                .push(rel.to_table.to_lowercase());
-            acc
-        });
-
-    // Build reverse FK lookup (to_table -> list of from_tables that reference it)
-    let fk_sources: std::collections::HashMap<String, Vec<String>> = relationships
-        .iter()
-        .fold(std::collections::HashMap::new(), |mut acc, rel| {
-            acc.entry(rel.to_table.to_lowercase())
-                .or_default()
-                .push(rel.from_table.to_lowercase());
-            acc
-        });
-
-    // First pass: classify all tables
-    let mut classifications: Vec<TableClassification> = tables
+    let relationships: Vec<InferredRelationship> = relationships
         .iter()
-        .map(|metadata| classify_single_table(metadata, &fk_targets, &fk_sources))
+        .filter_map(|rel| selection_to_relationship(rel, tables))
         .collect();
+    let bootstrap = ModelBootstrapper::new().bootstrap(tables, &relationships);
+    let model = &bootstrap.model;
 
-    // Second pass: generate suggestions based on classification
-    // Build dimension name lookup for fact suggestions
-    let dimension_names: std::collections::HashMap<String, String> = classifications
+    bootstrap
+        .classifications
         .iter()
-        .filter(|c| c.suggested_type == SuggestedTableType::Dimension)
-        .map(|c| (c.table.to_lowercase(), c.table.clone()))
-        .collect();
-
-    for classification in &mut classifications {
-        if let Some(metadata) = table_map.get(&classification.table.to_lowercase()) {
-            match classification.suggested_type {
-                SuggestedTableType::Dimension => {
-                    classification.suggestion = Some(TableSuggestion::Dimension(
-                        generate_dimension_suggestion(metadata),
-                    ));
+        .map(|classification| {
+            let target = classification.target.as_deref();
+            let suggestion = match classification.kind {
+                TableKind::Dimension => {
+                    target
+                        .and_then(|name| model.get_dimension(name))
+                        .map(|dim| {
+                            TableSuggestion::Dimension(dimension_suggestion(dim, classification))
+                        })
                 }
-                SuggestedTableType::Fact => {
-                    classification.suggestion = Some(TableSuggestion::Fact(
-                        generate_fact_suggestion(metadata, relationships, &dimension_names),
-                    ));
-                }
-                _ => {}
+                TableKind::Fact => target.and_then(|name| model.get_fact(name)).map(|fact| {
+                    TableSuggestion::Fact(fact_suggestion(fact, classification, model))
+                }),
+                TableKind::Source | TableKind::Bridge => None,
+            };
+            TableClassification {
+                schema: classification.schema.clone(),
+                table: classification.table.clone(),
+                suggested_type: match classification.kind {
+                    TableKind::Source => SuggestedTableType::Source,
+                    TableKind::Dimension => SuggestedTableType::Dimension,
+                    TableKind::Fact => SuggestedTableType::Fact,
+                    TableKind::Bridge => SuggestedTableType::Bridge,
+                },
+                confidence: classification.confidence,
+                reasons: classification.reasons.clone(),
+                suggestion,
             }
-        }
-    }
-
-    classifications
-}
-
-/// Classify a single table based on naming patterns and structure.
-fn classify_single_table(
-    metadata: &TableMetadata,
-    fk_targets: &std::collections::HashMap<String, Vec<String>>,
-    fk_sources: &std::collections::HashMap<String, Vec<String>>,
-) -> TableClassification {
-    let name_lower = metadata.name.to_lowercase();
-    let mut reasons = Vec::new();
-    let mut suggested_type = SuggestedTableType::Unknown;
-    let mut confidence = 0.0;
-
-    // Rule 1: Naming pattern detection (highest priority)
-    if let Some((name_type, name_conf, reason)) = detect_naming_pattern(&name_lower) {
-        suggested_type = name_type;
-        confidence = name_conf;
-        reasons.push(reason);
-    }
-
-    // If not determined by naming, analyze structure
-    if suggested_type == SuggestedTableType::Unknown {
-        let (struct_type, struct_conf, struct_reasons) =
-            analyze_table_structure(metadata, fk_targets, fk_sources);
-        suggested_type = struct_type;
-        confidence = struct_conf;
-        reasons.extend(struct_reasons);
-    }
-
-    // Default to Source if still unknown
-    if suggested_type == SuggestedTableType::Unknown {
-        suggested_type = SuggestedTableType::Source;
-        confidence = 0.5;
-        reasons.push("No clear pattern detected, defaulting to source".to_string());
-    }
-
-    TableClassification {
-        schema: metadata.schema.clone(),
-        table: metadata.name.clone(),
-        suggested_type,
-        confidence,
-        reasons,
-        suggestion: None, // Filled in second pass
-    }
+        })
+        .collect()
 }
 
-/// Detect table type from naming patterns.
-fn detect_naming_pattern(name: &str) -> Option<(SuggestedTableType, f64, String)> {
-    // Dimension patterns
-    if name.starts_with("dim_") || name.starts_with("dimension_") {
-        return Some((
-            SuggestedTableType::Dimension,
-            0.95,
-            format!("Name prefix 'dim_' indicates dimension table"),
-        ));
-    }
-    if name.starts_with("d_") && name.len() > 2 {
-        return Some((
-            SuggestedTableType::Dimension,
-            0.85,
-            format!("Name prefix 'd_' suggests dimension table"),
-        ));
-    }
-
-    // Fact patterns
-    if name.starts_with("fct_") || name.starts_with("fact_") {
-        return Some((
-            SuggestedTableType::Fact,
-            0.95,
-            format!("Name prefix 'fct_' or 'fact_' indicates fact table"),
-        ));
-    }
-    if name.starts_with("f_") && name.len() > 2 {
-        return Some((
-            SuggestedTableType::Fact,
-            0.85,
-            format!("Name prefix 'f_' suggests fact table"),
-        ));
-    }
-
-    // Bridge/junction patterns
-    if name.starts_with("bridge_") || name.starts_with("xref_") || name.starts_with("link_") {
-        return Some((
-            SuggestedTableType::Bridge,
-            0.90,
-            format!("Name prefix indicates bridge/junction table"),
-        ));
-    }
-
-    // Staging/source patterns
-    if name.starts_with("stg_") || name.starts_with("staging_") || name.starts_with("raw_") {
-        return Some((
-            SuggestedTableType::Source,
-            0.85,
-            format!("Name prefix indicates staging/source table"),
-        ));
-    }
-
-    None
+/// Resolve a user-selected relationship against the fetched tables.
+fn selection_to_relationship(
+    rel: &RelationshipSelection,
+    tables: &[TableMetadata],
+) -> Option<InferredRelationship> {
+    let find = |name: &str| tables.iter().find(|t| t.name.eq_ignore_ascii_case(name));
+    let from = find(&rel.from_table)?;
+    let to = find(&rel.to_table)?;
+    Some(InferredRelationship {
+        from_schema: from.schema.clone(),
+        from_table: from.name.clone(),
+        from_column: rel.from_column.clone(),
+        to_schema: to.schema.clone(),
+        to_table: to.name.clone(),
+        to_column: rel.to_column.clone(),
+        additional_columns: Vec::new(),
+        confidence: 1.0,
+        rule: "user_selected".to_string(),
+        cardinality: Cardinality::ManyToOne,
+        signal_breakdown: None,
+        source: RelationshipSource::UserDefined,
+    })
 }
 
-/// Analyze table structure to determine type.
-fn analyze_table_structure(
-    metadata: &TableMetadata,
-    fk_targets: &std::collections::HashMap<String, Vec<String>>,
-    fk_sources: &std::collections::HashMap<String, Vec<String>>,
-) -> (SuggestedTableType, f64, Vec<String>) {
-    let name_lower = metadata.name.to_lowercase();
-    let mut reasons = Vec::new();
-
-    // Count column types
-    let pk_columns = metadata.primary_key_columns();
-    let fk_columns: Vec<&str> = metadata
-        .foreign_keys
-        .iter()
-        .flat_map(|fk| fk.columns.iter().map(|s| s.as_str()))
-        .collect();
-
-    let numeric_non_fk: Vec<&str> = metadata
-        .columns
-        .iter()
-        .filter(|c| {
-            let is_fk = fk_columns.iter().any(|fk| fk.eq_ignore_ascii_case(&c.name));
-            let is_pk = pk_columns.iter().any(|pk| pk.eq_ignore_ascii_case(&c.name));
-            !is_fk && !is_pk && is_numeric_type(&c.data_type) && !is_date_type(&c.data_type)
-        })
-        .map(|c| c.name.as_str())
-        .collect();
-
-    let date_columns: Vec<&str> = metadata
-        .columns
-        .iter()
-        .filter(|c| is_date_type(&c.data_type))
-        .map(|c| c.name.as_str())
-        .collect();
-
-    // Get FK relationships
-    let outgoing_fks = fk_targets.get(&name_lower).map(|v| v.len()).unwrap_or(0);
-    let incoming_fks = fk_sources.get(&name_lower).map(|v| v.len()).unwrap_or(0);
-
-    // Bridge table detection: exactly 2 FK columns, minimal other columns
-    if fk_columns.len() == 2 && metadata.columns.len() <= 4 {
-        reasons.push(format!(
-            "Has exactly 2 FK columns with {} total columns (bridge pattern)",
-            metadata.columns.len()
-        ));
-        return (SuggestedTableType::Bridge, 0.80, reasons);
-    }
-
-    // Fact table detection: multiple FKs + numeric columns
-    if outgoing_fks >= 2 && !numeric_non_fk.is_empty() {
-        reasons.push(format!(
-            "Has {} FK relationships and {} numeric measure columns",
-            outgoing_fks,
-            numeric_non_fk.len()
-        ));
-        if !date_columns.is_empty() {
-            reasons.push(format!("Has date columns: {:?}", date_columns));
-        }
-        return (SuggestedTableType::Fact, 0.75, reasons);
-    }
-
-    // Dimension detection: referenced by other tables, few/no outgoing FKs
-    if incoming_fks >= 1 && outgoing_fks == 0 && numeric_non_fk.is_empty() {
-        reasons.push(format!(
-            "Referenced by {} tables with no outgoing FKs (dimension pattern)",
-            incoming_fks
-        ));
-        return (SuggestedTableType::Dimension, 0.70, reasons);
-    }
-
-    // Dimension detection: has _id suffix in name and primarily descriptive columns
-    if (name_lower.ends_with("s") || name_lower.ends_with("es"))
-        && !pk_columns.is_empty()
-        && numeric_non_fk.len() <= 1
-    {
-        reasons.push("Plural name with primary key and mostly descriptive columns".to_string());
-        return (SuggestedTableType::Dimension, 0.60, reasons);
-    }
-
-    reasons.push("Structure analysis inconclusive".to_string());
-    (SuggestedTableType::Unknown, 0.0, reasons)
-}
-
-/// Generate dimension suggestion from metadata.
-fn generate_dimension_suggestion(metadata: &TableMetadata) -> DimensionSuggestion {
-    let pk_columns: Vec<String> = metadata.primary_key_columns().into_iter().map(|s| s.to_string()).collect();
-
-    // System columns to exclude
-    let system_columns: std::collections::HashSet<&str> = [
-        "created_at",
-        "updated_at",
-        "modified_at",
-        "deleted_at",
-        "created_by",
-        "updated_by",
-        "modified_by",
-        "deleted_by",
-        "row_version",
-        "etl_batch_id",
-        "dw_insert_date",
-        "dw_update_date",
-    ]
-    .into_iter()
-    .collect();
-
-    // Columns to include (exclude system columns)
-    let columns: Vec<String> = metadata
-        .columns
-        .iter()
-        .filter(|c| !system_columns.contains(c.name.to_lowercase().as_str()))
-        .map(|c| c.name.clone())
-        .collect();
-
-    // Detect SCD type from column names
-    let has_valid_from = metadata
-        .columns
-        .iter()
-        .any(|c| c.name.to_lowercase().contains("valid_from") || c.name.to_lowercase().contains("effective_from"));
-    let has_valid_to = metadata
-        .columns
-        .iter()
-        .any(|c| c.name.to_lowercase().contains("valid_to") || c.name.to_lowercase().contains("effective_to"));
-
-    let scd_type = if has_valid_from && has_valid_to { 2 } else { 1 };
-
-    // Generate target table name
-    let target_table = format!("analytics.{}", metadata.name);
-
+/// Convert a generated dimension into the suggestion shape the UI edits.
+fn dimension_suggestion(
+    dim: &DimensionDefinition,
+    classification: &BootstrapClassification,
+) -> DimensionSuggestion {
     DimensionSuggestion {
-        name: metadata.name.clone(),
-        source: metadata.name.clone(),
-        source_schema: metadata.schema.clone(),
-        target_table,
-        columns,
-        primary_key: pk_columns,
-        scd_type,
-        materialized: true,
+        name: dim.name.clone(),
+        source: classification.table.clone(),
+        source_schema: classification.schema.clone(),
+        target_table: dim.qualified_target_name(),
+        columns: dim
+            .columns
+            .iter()
+            .map(|c| c.source_column.clone())
+            .collect(),
+        primary_key: dim.primary_key.clone(),
+        scd_type: match dim.scd_type {
+            SCDType::Type0 => 0,
+            SCDType::Type1 => 1,
+            SCDType::Type2 { .. } => 2,
+            SCDType::Type3 { .. } => 3,
+            SCDType::Type6 { .. } => 6,
+        },
+        materialized: dim.materialized,
     }
 }
 
-/// Generate fact suggestion from metadata.
-fn generate_fact_suggestion(
-    metadata: &TableMetadata,
-    relationships: &[RelationshipSelection],
-    dimension_names: &std::collections::HashMap<String, String>,
+/// Convert a generated fact into the suggestion shape the UI edits.
+fn fact_suggestion(
+    fact: &FactDefinition,
+    classification: &BootstrapClassification,
+    model: &Model,
 ) -> FactSuggestion {
-    let pk_columns: Vec<String> = metadata.primary_key_columns().into_iter().map(|s| s.to_string()).collect();
-    let name_lower = metadata.name.to_lowercase();
-
-    // Get FK columns from relationships where this table is the "from" side
-    let fk_columns: Vec<String> = relationships
-        .iter()
-        .filter(|rel| rel.from_table.to_lowercase() == name_lower)
-        .map(|rel| rel.from_column.clone())
-        .collect();
-
-    // Build grain: use PK if available, otherwise use all FK columns
-    let grain: Vec<GrainColumnSuggestion> = if !pk_columns.is_empty() {
-        pk_columns
-            .iter()
-            .map(|col| GrainColumnSuggestion {
-                source_entity: metadata.name.clone(),
-                column: col.clone(),
-            })
-            .collect()
-    } else {
-        // No PK - use FK columns as grain (dimension keys define the grain)
-        fk_columns
-            .iter()
-            .map(|col| GrainColumnSuggestion {
-                source_entity: metadata.name.clone(),
-                column: col.clone(),
-            })
-            .collect()
-    };
-
-    // Get FK columns as a set for filtering measures
-    let fk_column_set: std::collections::HashSet<String> = fk_columns
-        .iter()
-        .map(|s| s.to_lowercase())
-        .collect();
-
-    // Identify measure columns (numeric, non-FK, non-PK, non-date)
-    let measures: Vec<MeasureSuggestion> = metadata
-        .columns
-        .iter()
-        .filter(|c| {
-            let col_lower = c.name.to_lowercase();
-            let is_fk = fk_column_set.contains(&col_lower);
-            let is_pk = pk_columns.iter().any(|pk| pk.eq_ignore_ascii_case(&c.name));
-            !is_fk && !is_pk && is_numeric_type(&c.data_type) && !is_date_type(&c.data_type)
-        })
-        .map(|c| {
-            let col_lower = c.name.to_lowercase();
-            let aggregation = infer_aggregation_type(&col_lower);
-            let name = generate_measure_name(&col_lower, &aggregation);
-            MeasureSuggestion {
-                name,
-                column: c.name.clone(),
-                aggregation,
-            }
+    let mut measures: Vec<MeasureSuggestion> = fact
+        .measures
+        .values()
+        .map(|m| MeasureSuggestion {
+            name: m.name.clone(),
+            column: m.source_column.clone(),
+            aggregation: m.aggregation.to_string().to_lowercase(),
         })
         .collect();
+    measures.sort_by(|a, b| a.name.cmp(&b.name));
 
-    // Build dimension references from FK relationships
-    let dimension_refs: Vec<DimensionRef> = relationships
-        .iter()
-        .filter(|rel| rel.from_table.to_lowercase() == name_lower)
+    let dimension_refs = model
+        .relationships_from(&classification.entity)
+        .into_iter()
+        .filter(|rel| fact.includes.contains_key(&rel.to_entity))
         .filter_map(|rel| {
-            let to_lower = rel.to_table.to_lowercase();
-            dimension_names.get(&to_lower).map(|dim_name| DimensionRef {
+            let dim = model
+                .dimensions
+                .values()
+                .find(|d| d.source_entity == rel.to_entity)?;
+            Some(DimensionRef {
                 column: rel.from_column.clone(),
-                dimension: dim_name.clone(),
+                dimension: dim.name.clone(),
                 dimension_key: rel.to_column.clone(),
                 include_columns: Vec::new(), // User can add columns later
             })
         })
         .collect();
 
-    // Generate target table name
-    let target_table = format!("analytics.{}", metadata.name);
-
     FactSuggestion {
-        name: metadata.name.clone(),
-        source: metadata.name.clone(),
-        source_schema: metadata.schema.clone(),
-        target_table,
-        grain,
+        name: fact.name.clone(),
+        source: classification.table.clone(),
+        source_schema: classification.schema.clone(),
+        target_table: fact.qualified_target_name(),
+        grain: fact
+            .grain
+            .iter()
+            .map(|g| GrainColumnSuggestion {
+                source_entity: g.source_entity.clone(),
+                column: g.source_column.clone(),
+            })
+            .collect(),
         measures,
         dimension_refs,
-        materialized: true,
-    }
-}
-
-/// Check if a database type is numeric.
-fn is_numeric_type(db_type: &str) -> bool {
-    let lower = db_type.to_lowercase();
-    lower.contains("int")
-        || lower.contains("decimal")
-        || lower.contains("numeric")
-        || lower.contains("float")
-        || lower.contains("double")
-        || lower.contains("real")
-        || lower == "money"
-        || lower == "smallmoney"
-}
-
-/// Check if a database type is a date/time type.
-fn is_date_type(db_type: &str) -> bool {
-    let lower = db_type.to_lowercase();
-    lower.contains("date") || lower.contains("time") || lower == "timestamp"
-}
-
-/// Infer aggregation type from column name.
-fn infer_aggregation_type(col_name: &str) -> String {
-    if col_name.ends_with("_amount")
-        || col_name.ends_with("_total")
-        || col_name.ends_with("_qty")
-        || col_name.ends_with("_quantity")
-        || col_name.ends_with("_count")
-        || col_name.ends_with("_sum")
-        || col_name.contains("revenue")
-        || col_name.contains("sales")
-        || col_name.contains("cost")
-        || col_name.contains("price")
-    {
-        return "sum".to_string();
-    }
-    if col_name.ends_with("_rate")
-        || col_name.ends_with("_pct")
-        || col_name.ends_with("_percent")
-        || col_name.ends_with("_avg")
-        || col_name.ends_with("_average")
-    {
-        return "avg".to_string();
-    }
-    // Default to sum for other numeric columns
-    "sum".to_string()
-}
-
-/// Generate a descriptive measure name.
-fn generate_measure_name(col_name: &str, aggregation: &str) -> String {
-    // If column already has a good name, use it with aggregation prefix
-    let clean_name = col_name
-        .trim_start_matches("_")
-        .trim_end_matches("_amount")
-        .trim_end_matches("_total")
-        .trim_end_matches("_qty")
-        .trim_end_matches("_sum");
-
-    match aggregation {
-        "sum" => {
-            if col_name.contains("revenue") || col_name.contains("sales") {
-                format!("total_{}", clean_name)
-            } else if col_name.contains("qty") || col_name.contains("quantity") {
-                format!("total_{}", clean_name)
-            } else {
-                format!("{}_total", clean_name)
-            }
-        }
-        "avg" => format!("avg_{}", clean_name),
-        "count" => format!("{}_count", clean_name),
-        "min" => format!("min_{}", clean_name),
-        "max" => format!("max_{}", clean_name),
-        _ => col_name.to_string(),
+        materialized: fact.materialized,
     }
 }
 
@@ -1756,397 +1445,190 @@ fn generate_lua_files(
     facts: &[FactSuggestion],
     relationships: &[RelationshipSelection],
 ) -> std::collections::HashMap<String, String> {
+    let model = suggestions_to_model(
+        all_metadata,
+        source_selections,
+        dimensions,
+        facts,
+        relationships,
+    );
+    let emitter = LuaEmitter::new(EmitConfig::minimal());
     let mut files = std::collections::HashMap::new();
 
-    // =========================================================================
-    // sources.lua - Source definitions for ALL tables (sources, dims, facts)
-    // =========================================================================
-    let mut sources_lua = String::new();
-    sources_lua.push_str("-- Auto-generated source definitions\n");
-    sources_lua.push_str("-- These define the raw tables from your database\n\n");
-
-    // Generate sources for explicitly marked source tables
-    for selection in source_selections {
-        let key = format!("{}.{}", selection.schema, selection.table).to_lowercase();
-        if let Some(metadata) = all_metadata.get(&key) {
-            sources_lua.push_str(&generate_source_block(metadata, selection.entity_name.as_deref()));
-        }
+    let mut sources: Vec<&SourceEntity> = model.sources.values().collect();
+    sources.sort_by(|a, b| a.name.cmp(&b.name));
+    if !sources.is_empty() {
+        files.insert(
+            "sources.lua".to_string(),
+            format!(
+                "-- Auto-generated source definitions\n\
+                 -- These define the raw tables from your database\n\n{}",
+                emitter.emit_sources(&sources)
+            ),
+        );
     }
 
-    // Generate sources for dimension tables
-    for dim in dimensions {
-        // Find metadata by source name
-        let metadata = all_metadata.values().find(|m| m.name.eq_ignore_ascii_case(&dim.source));
-        if let Some(metadata) = metadata {
-            sources_lua.push_str(&generate_source_block(metadata, None));
-        }
+    let mut dims: Vec<&DimensionDefinition> = model.dimensions.values().collect();
+    dims.sort_by(|a, b| a.name.cmp(&b.name));
+    if !dims.is_empty() {
+        files.insert(
+            "dimensions.lua".to_string(),
+            format!(
+                "-- Auto-generated dimension definitions\n\
+                 -- Dimensions represent descriptive attributes for analysis\n\n{}",
+                emitter.emit_dimensions(&dims)
+            ),
+        );
     }
 
-    // Generate sources for fact tables
-    for fact in facts {
-        // Find metadata by source name
-        let metadata = all_metadata.values().find(|m| m.name.eq_ignore_ascii_case(&fact.source));
-        if let Some(metadata) = metadata {
-            sources_lua.push_str(&generate_source_block(metadata, None));
-        }
+    let mut fact_defs: Vec<&FactDefinition> = model.facts.values().collect();
+    fact_defs.sort_by(|a, b| a.name.cmp(&b.name));
+    if !fact_defs.is_empty() {
+        files.insert(
+            "facts.lua".to_string(),
+            format!(
+                "-- Auto-generated fact definitions\n\
+                 -- Facts represent measurable events and transactions\n\n{}",
+                emitter.emit_facts(&fact_defs)
+            ),
+        );
     }
 
-    if !sources_lua.trim().is_empty() && sources_lua.contains("source ") {
-        files.insert("sources.lua".to_string(), sources_lua);
+    let rels: Vec<&Relationship> = model.relationships.iter().collect();
+    if !rels.is_empty() {
+        files.insert(
+            "relationships.lua".to_string(),
+            format!(
+                "-- Auto-generated relationship definitions\n\
+                 -- These define how tables are connected\n\n{}",
+                emitter.emit_relationships(&rels)
+            ),
+        );
     }
 
-    // =========================================================================
-    // dimensions.lua - Dimension definitions
-    // =========================================================================
-    if !dimensions.is_empty() {
-        let mut dims_lua = String::new();
-        dims_lua.push_str("-- Auto-generated dimension definitions\n");
-        dims_lua.push_str("-- Dimensions represent descriptive attributes for analysis\n\n");
-
-        for dim in dimensions {
-            dims_lua.push_str(&format!("dimension \"{}\" {{\n", dim.name));
-            dims_lua.push_str(&format!("  target_table = \"{}\",\n", dim.target_table));
-            dims_lua.push_str(&format!("  source = \"{}\",\n", dim.source));
-
-            // Columns
-            if !dim.columns.is_empty() {
-                let col_list: Vec<String> = dim.columns.iter().map(|s| format!("\"{}\"", s)).collect();
-                dims_lua.push_str(&format!("  columns = {{ {} }},\n", col_list.join(", ")));
-            }
-
-            // Primary key
-            if !dim.primary_key.is_empty() {
-                if dim.primary_key.len() == 1 {
-                    dims_lua.push_str(&format!("  primary_key = {{ \"{}\" }},\n", dim.primary_key[0]));
-                } else {
-                    let pk_list: Vec<String> = dim.primary_key.iter().map(|s| format!("\"{}\"", s)).collect();
-                    dims_lua.push_str(&format!("  primary_key = {{ {} }},\n", pk_list.join(", ")));
-                }
-            }
+    files
+}
 
-            // SCD type
-            dims_lua.push_str(&format!("  scd_type = SCD{},\n", dim.scd_type));
+/// Build a `Model` from the (possibly user-edited) suggestions.
+fn suggestions_to_model(
+    all_metadata: &std::collections::HashMap<String, TableMetadata>,
+    source_selections: &[TableSelection],
+    dimensions: &[DimensionSuggestion],
+    facts: &[FactSuggestion],
+    relationships: &[RelationshipSelection],
+) -> Model {
+    let mut model = Model::new();
+    let find = |schema: &str, table: &str| {
+        all_metadata.get(&format!("{}.{}", schema, table).to_lowercase())
+    };
 
-            // Materialized
-            if dim.materialized {
-                dims_lua.push_str("  materialized = true,\n");
+    // Sources for ALL tables (sources, dims, facts)
+    for selection in source_selections {
+        if let Some(metadata) = find(&selection.schema, &selection.table) {
+            let mut source = SourceEntity::from(metadata);
+            if let Some(name) = &selection.entity_name {
+                source.name = name.clone();
             }
-
-            dims_lua.push_str("}\n\n");
+            model.add_source(source);
         }
-
-        files.insert("dimensions.lua".to_string(), dims_lua);
     }
-
-    // =========================================================================
-    // facts.lua - Fact definitions
-    // =========================================================================
-    if !facts.is_empty() {
-        let mut facts_lua = String::new();
-        facts_lua.push_str("-- Auto-generated fact definitions\n");
-        facts_lua.push_str("-- Facts represent measurable events and transactions\n\n");
-
-        for fact in facts {
-            facts_lua.push_str(&format!("fact \"{}\" {{\n", fact.name));
-            facts_lua.push_str(&format!("  target_table = \"{}\",\n", fact.target_table));
-
-            // Grain
-            if !fact.grain.is_empty() {
-                let grain_list: Vec<String> = fact
-                    .grain
-                    .iter()
-                    .map(|g| format!("{}.{}", g.source_entity, g.column))
-                    .collect();
-                facts_lua.push_str(&format!("  grain = {{ {} }},\n", grain_list.join(", ")));
-            }
-
-            // Includes (dimension references)
-            if !fact.dimension_refs.is_empty() {
-                facts_lua.push_str("  include = {\n");
-                for dim_ref in &fact.dimension_refs {
-                    if dim_ref.include_columns.is_empty() {
-                        facts_lua.push_str(&format!("    {} = ALL,\n", dim_ref.dimension));
-                    } else {
-                        let cols: Vec<String> = dim_ref
-                            .include_columns
-                            .iter()
-                            .map(|s| format!("\"{}\"", s))
-                            .collect();
-                        facts_lua.push_str(&format!(
-                            "    {} = {{ {} }},\n",
-                            dim_ref.dimension,
-                            cols.join(", ")
-                        ));
-                    }
-                }
-                facts_lua.push_str("  },\n");
-            }
-
-            // Measures
-            if !fact.measures.is_empty() {
-                facts_lua.push_str("  measures = {\n");
-                for measure in &fact.measures {
-                    let agg_fn = match measure.aggregation.as_str() {
-                        "sum" => format!("sum \"{}\"", measure.column),
-                        "count" => "count()".to_string(),
-                        "count_distinct" => format!("count_distinct \"{}\"", measure.column),
-                        "avg" => format!("avg \"{}\"", measure.column),
-                        "min" => format!("min \"{}\"", measure.column),
-                        "max" => format!("max \"{}\"", measure.column),
-                        _ => format!("sum \"{}\"", measure.column),
-                    };
-                    facts_lua.push_str(&format!("    {} = {},\n", measure.name, agg_fn));
-                }
-                facts_lua.push_str("  },\n");
-            }
-
-            // Materialized
-            if fact.materialized {
-                facts_lua.push_str("  materialized = true,\n");
-            }
-
-            facts_lua.push_str("}\n\n");
+    let generated = dimensions
+        .iter()
+        .map(|d| (&d.source_schema, &d.source))
+        .chain(facts.iter().map(|f| (&f.source_schema, &f.source)));
+    for (schema, table) in generated {
+        if let Some(metadata) = find(schema, table) {
+            model.add_source(SourceEntity::from(metadata));
         }
-
-        files.insert("facts.lua".to_string(), facts_lua);
     }
 
-    // =========================================================================
-    // relationships.lua - Link statements
-    // =========================================================================
-    if !relationships.is_empty() {
-        let mut rels_lua = String::new();
-        rels_lua.push_str("-- Auto-generated relationship definitions\n");
-        rels_lua.push_str("-- These define how tables are connected\n\n");
-
-        for rel in relationships {
-            rels_lua.push_str(&format!(
-                "link({}.{}, {}.{})\n",
-                rel.from_table, rel.from_column, rel.to_table, rel.to_column
-            ));
+    for dim in dimensions {
+        let scd_type = match dim.scd_type {
+            0 => SCDType::Type0,
+            2 => detect_scd_type(&dim.columns),
+            _ => SCDType::Type1,
+        };
+        let (schema, table) = split_target(&dim.target_table);
+        let mut definition = DimensionDefinition::new(&dim.name, table, &dim.source)
+            .with_columns(dim.columns.clone())
+            .with_primary_key(dim.primary_key.clone())
+            .with_scd_type(scd_type)
+            .with_materialized(dim.materialized)
+            .with_materialization(MaterializationStrategy::Table);
+        if let Some(schema) = schema {
+            definition = definition.with_schema(schema);
         }
-
-        files.insert("relationships.lua".to_string(), rels_lua);
+        model.add_dimension(definition);
     }
 
-    files
-}
-
-/// Generate a source {} block for a table.
-fn generate_source_block(metadata: &TableMetadata, entity_name: Option<&str>) -> String {
-    let mut lua = String::new();
-    let name = entity_name.unwrap_or(&metadata.name);
-
-    lua.push_str(&format!("source \"{}\" {{\n", name));
-
-    if metadata.schema.is_empty() {
-        lua.push_str(&format!("  table = \"{}\",\n", metadata.name));
-    } else {
-        lua.push_str(&format!("  table = \"{}.{}\",\n", metadata.schema, metadata.name));
-    }
+    // Includes reference the dimension's source entity
+    let dimension_sources: std::collections::HashMap<&str, &str> = dimensions
+        .iter()
+        .map(|d| (d.name.as_str(), d.source.as_str()))
+        .collect();
 
-    let pk_columns = metadata.primary_key_columns();
-    if !pk_columns.is_empty() {
-        if pk_columns.len() == 1 {
-            lua.push_str(&format!("  primary_key = \"{}\",\n", pk_columns[0]));
-        } else {
-            let pk_list: Vec<String> = pk_columns.iter().map(|s| format!("\"{}\"", s)).collect();
-            lua.push_str(&format!("  primary_key = {{ {} }},\n", pk_list.join(", ")));
+    for fact in facts {
+        let (schema, table) = split_target(&fact.target_table);
+        let mut definition = FactDefinition::new(&fact.name, table)
+            .with_from(&fact.source)
+            .with_materialized(fact.materialized)
+            .with_materialization(MaterializationStrategy::Table);
+        if let Some(schema) = schema {
+            definition = definition.with_schema(schema);
         }
-    }
-
-    lua.push_str("  columns = {\n");
-    for col in &metadata.columns {
-        let mantis_type = db_type_to_mantis_type(&col.data_type);
-        let nullable = if col.is_nullable { ", nullable = true" } else { "" };
-        lua.push_str(&format!(
-            "    {} = {{ type = \"{}\"{} }},\n",
-            col.name, mantis_type, nullable
-        ));
-    }
-    lua.push_str("  },\n");
-    lua.push_str("}\n\n");
-
-    lua
-}
-
-/// Generate Lua code from suggestions (sources, dimensions, facts, relationships).
-/// DEPRECATED: Use generate_lua_files instead for separate file generation.
-fn generate_lua_with_suggestions(
-    source_tables: &[TableMetadata],
-    source_selections: &[TableSelection],
-    dimensions: &[DimensionSuggestion],
-    facts: &[FactSuggestion],
-    relationships: &[RelationshipSelection],
-) -> String {
-    let mut lua = String::new();
-    lua.push_str("-- Auto-generated Mantis definitions\n");
-    lua.push_str("-- Review and customize as needed\n\n");
-
-    // Generate source definitions
-    if !source_tables.is_empty() {
-        lua.push_str("-- =============================================================================\n");
-        lua.push_str("-- Sources\n");
-        lua.push_str("-- =============================================================================\n\n");
-
-        for (metadata, selection) in source_tables.iter().zip(source_selections.iter()) {
-            let entity_name = selection.entity_name.as_ref().unwrap_or(&metadata.name);
-            lua.push_str(&format!("source \"{}\" {{\n", entity_name));
-
-            if metadata.schema.is_empty() {
-                lua.push_str(&format!("  table = \"{}\",\n", metadata.name));
+        for grain in &fact.grain {
+            definition = definition.with_grain(&grain.source_entity, &grain.column);
+        }
+        for dim_ref in &fact.dimension_refs {
+            let entity = dimension_sources
+                .get(dim_ref.dimension.as_str())
+                .copied()
+                .unwrap_or(&dim_ref.dimension);
+            definition = if dim_ref.include_columns.is_empty() {
+                definition.include_all(entity)
             } else {
-                lua.push_str(&format!("  table = \"{}.{}\",\n", metadata.schema, metadata.name));
-            }
-
-            let pk_columns = metadata.primary_key_columns();
-            if !pk_columns.is_empty() {
-                if pk_columns.len() == 1 {
-                    lua.push_str(&format!("  primary_key = \"{}\",\n", pk_columns[0]));
-                } else {
-                    let pk_list: Vec<String> = pk_columns.iter().map(|s| format!("\"{}\"", s)).collect();
-                    lua.push_str(&format!("  primary_key = {{ {} }},\n", pk_list.join(", ")));
-                }
-            }
-
-            lua.push_str("  columns = {\n");
-            for col in &metadata.columns {
-                let mantis_type = db_type_to_mantis_type(&col.data_type);
-                let nullable = if col.is_nullable { ", nullable = true" } else { "" };
-                lua.push_str(&format!(
-                    "    {} = {{ type = \"{}\"{} }},\n",
-                    col.name, mantis_type, nullable
-                ));
-            }
-            lua.push_str("  },\n");
-            lua.push_str("}\n\n");
+                definition.include(entity, dim_ref.include_columns.clone())
+            };
         }
-    }
-
-    // Generate dimension definitions
-    if !dimensions.is_empty() {
-        lua.push_str("-- =============================================================================\n");
-        lua.push_str("-- Dimensions\n");
-        lua.push_str("-- =============================================================================\n\n");
-
-        for dim in dimensions {
-            lua.push_str(&format!("dimension \"{}\" {{\n", dim.name));
-            lua.push_str(&format!("  target_table = \"{}\",\n", dim.target_table));
-            lua.push_str(&format!("  source = \"{}\",\n", dim.source));
-
-            // Columns
-            if !dim.columns.is_empty() {
-                let col_list: Vec<String> = dim.columns.iter().map(|s| format!("\"{}\"", s)).collect();
-                lua.push_str(&format!("  columns = {{ {} }},\n", col_list.join(", ")));
-            }
-
-            // Primary key
-            if !dim.primary_key.is_empty() {
-                if dim.primary_key.len() == 1 {
-                    lua.push_str(&format!("  primary_key = {{ \"{}\" }},\n", dim.primary_key[0]));
-                } else {
-                    let pk_list: Vec<String> = dim.primary_key.iter().map(|s| format!("\"{}\"", s)).collect();
-                    lua.push_str(&format!("  primary_key = {{ {} }},\n", pk_list.join(", ")));
-                }
-            }
-
-            // SCD type
-            lua.push_str(&format!("  scd_type = SCD{},\n", dim.scd_type));
-
-            // Materialized
-            if dim.materialized {
-                lua.push_str("  materialized = true,\n");
-            }
-
-            lua.push_str("}\n\n");
+        for measure in &fact.measures {
+            let aggregation = match measure.aggregation.as_str() {
+                "count" => AggregationType::Count,
+                "count_distinct" => AggregationType::CountDistinct,
+                "avg" => AggregationType::Avg,
+                "min" => AggregationType::Min,
+                "max" => AggregationType::Max,
+                _ => AggregationType::Sum,
+            };
+            let column = if aggregation == AggregationType::Count {
+                "*"
+            } else {
+                &measure.column
+            };
+            definition =
+                definition.with_measure(MeasureDefinition::new(&measure.name, aggregation, column));
         }
+        model.add_fact(definition);
     }
 
-    // Generate fact definitions
-    if !facts.is_empty() {
-        lua.push_str("-- =============================================================================\n");
-        lua.push_str("-- Facts\n");
-        lua.push_str("-- =============================================================================\n\n");
-
-        for fact in facts {
-            lua.push_str(&format!("fact \"{}\" {{\n", fact.name));
-            lua.push_str(&format!("  target_table = \"{}\",\n", fact.target_table));
-
-            // Grain
-            if !fact.grain.is_empty() {
-                let grain_list: Vec<String> = fact
-                    .grain
-                    .iter()
-                    .map(|g| format!("{}.{}", g.source_entity, g.column))
-                    .collect();
-                lua.push_str(&format!("  grain = {{ {} }},\n", grain_list.join(", ")));
-            }
-
-            // Includes (dimension references)
-            if !fact.dimension_refs.is_empty() {
-                lua.push_str("  include = {\n");
-                for dim_ref in &fact.dimension_refs {
-                    if dim_ref.include_columns.is_empty() {
-                        lua.push_str(&format!("    {} = ALL,\n", dim_ref.dimension));
-                    } else {
-                        let cols: Vec<String> = dim_ref
-                            .include_columns
-                            .iter()
-                            .map(|s| format!("\"{}\"", s))
-                            .collect();
-                        lua.push_str(&format!(
-                            "    {} = {{ {} }},\n",
-                            dim_ref.dimension,
-                            cols.join(", ")
-                        ));
-                    }
-                }
-                lua.push_str("  },\n");
-            }
-
-            // Measures
-            if !fact.measures.is_empty() {
-                lua.push_str("  measures = {\n");
-                for measure in &fact.measures {
-                    let agg_fn = match measure.aggregation.as_str() {
-                        "sum" => format!("sum \"{}\"", measure.column),
-                        "count" => "count()".to_string(),
-                        "count_distinct" => format!("count_distinct \"{}\"", measure.column),
-                        "avg" => format!("avg \"{}\"", measure.column),
-                        "min" => format!("min \"{}\"", measure.column),
-                        "max" => format!("max \"{}\"", measure.column),
-                        _ => format!("sum \"{}\"", measure.column),
-                    };
-                    lua.push_str(&format!("    {} = {},\n", measure.name, agg_fn));
-                }
-                lua.push_str("  },\n");
-            }
-
-            // Materialized
-            if fact.materialized {
-                lua.push_str("  materialized = true,\n");
-            }
-
-            lua.push_str("}\n\n");
-        }
+    for rel in relationships {
+        model.add_relationship(Relationship::new(
+            &rel.from_table,
+            &rel.to_table,
+            &rel.from_column,
+            &rel.to_column,
+            Cardinality::ManyToOne,
+        ));
     }
 
-    // Generate link statements for relationships
-    if !relationships.is_empty() {
-        lua.push_str("-- =============================================================================\n");
-        lua.push_str("-- Relationships\n");
-        lua.push_str("-- =============================================================================\n\n");
+    model
+}
 
-        for rel in relationships {
-            lua.push_str(&format!(
-                "link({}.{}, {}.{})\n",
-                rel.from_table, rel.from_column, rel.to_table, rel.to_column
-            ));
-        }
-        lua.push('\n');
+/// Split `schema.table` into its parts.
+fn split_target(target: &str) -> (Option<&str>, &str) {
+    match target.rsplit_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, target),
     }
-
-    lua
 }
 
 /// Map database type string to Mantis type.
@@ -2541,6 +2023,93 @@ mod tests {
         assert_eq!(extract_type_params("decimal()"), None);
     }
 
+    #[test]
+    fn test_suggestions_generate_loadable_lua() {
+        use crate::metadata::{ColumnInfo, PrimaryKeyInfo, TableType};
+
+        let column = |name: &str, data_type: &str| ColumnInfo {
+            name: name.to_string(),
+            position: 0,
+            data_type: data_type.to_string(),
+            is_nullable: false,
+            max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            default_value: None,
+            is_identity: false,
+            is_computed: false,
+        };
+        let table = |name: &str, columns: Vec<ColumnInfo>, pk: &str| TableMetadata {
+            schema: "main".to_string(),
+            name: name.to_string(),
+            table_type: TableType::Table,
+            columns,
+            primary_key: Some(PrimaryKeyInfo {
+                name: format!("pk_{}", name),
+                columns: vec![pk.to_string()],
+            }),
+            foreign_keys: vec![],
+            unique_constraints: vec![],
+        };
+        let tables = vec![
+            table(
+                "customers",
+                vec![column("customer_id", "int"), column("name", "varchar")],
+                "customer_id",
+            ),
+            table(
+                "fct_orders",
+                vec![
+                    column("order_id", "int"),
+                    column("customer_id", "int"),
+                    column("sales_amount", "decimal"),
+                ],
+                "order_id",
+            ),
+        ];
+        let relationships = vec![RelationshipSelection {
+            from_table: "fct_orders".to_string(),
+            from_column: "customer_id".to_string(),
+            to_table: "customers".to_string(),
+            to_column: "customer_id".to_string(),
+        }];
+
+        let classifications = classify_tables_internal(&tables, &relationships);
+        let mut dimensions = Vec::new();
+        let mut facts = Vec::new();
+        for classification in classifications {
+            match classification.suggestion {
+                Some(TableSuggestion::Dimension(dim)) => dimensions.push(dim),
+                Some(TableSuggestion::Fact(fact)) => facts.push(fact),
+                None => {}
+            }
+        }
+        assert_eq!(dimensions[0].name, "dim_customers");
+        assert_eq!(facts[0].name, "fact_orders");
+        assert_eq!(facts[0].dimension_refs[0].dimension, "dim_customers");
+
+        let metadata = tables
+            .into_iter()
+            .map(|t| (format!("{}.{}", t.schema, t.name), t))
+            .collect();
+        let files = generate_lua_files(&metadata, &[], &dimensions, &facts, &relationships);
+        let lua: String = [
+            "sources.lua",
+            "relationships.lua",
+            "dimensions.lua",
+            "facts.lua",
+        ]
+        .iter()
+        .map(|name| files[*name].as_str())
+        .collect();
+
+        let model = crate::model::loader::load_model_from_str(&lua, "generated.lua").unwrap();
+        let fact = model.get_fact("fact_orders").unwrap();
+        assert!(fact.includes.contains_key("customers"));
+        assert_eq!(fact.measures["total_sales"].source_column, "sales_amount");
+        assert!(model.get_dimension("dim_customers").is_some());
+    }
+
     #[test]
     fn test_supported_drivers() {
         assert!(SUPPORTED_DRIVERS.contains(&"mssql"));